@@ -1,5 +1,8 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+use deno_typescript::SnapshotBuilderExt;
 use std::env;
+use std::fs;
+use std::path::Path;
 use std::path::PathBuf;
 
 fn main() {
@@ -14,15 +17,49 @@ fn main() {
   let c = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap());
   let o = PathBuf::from(env::var_os("OUT_DIR").unwrap());
 
+  // Once a rerun-if-changed is printed, cargo only reruns this script when
+  // the listed files change, so every source of the snapshots is listed.
+  rerun_if_changed(&c.join("js"));
+
   let root_names = vec![c.join("js/main.ts")];
   let bundle = o.join("CLI_SNAPSHOT.js");
   let state = deno_typescript::compile_bundle(&bundle, root_names).unwrap();
   assert!(bundle.exists());
-  deno_typescript::mksnapshot_bundle(&bundle, state).unwrap();
+  let mut builder = deno_typescript::snapshot_builder()
+    .unwrap()
+    .add_bundle(&bundle, state)
+    .unwrap();
+  // Scripts listed like PATH, which run after the runtime is set up, are
+  // baked into the snapshot too.
+  println!("cargo:rerun-if-env-changed=DENO_SNAPSHOT_SCRIPTS");
+  if let Some(paths) = env::var_os("DENO_SNAPSHOT_SCRIPTS") {
+    for path in env::split_paths(&paths) {
+      println!("cargo:rerun-if-changed={}", path.display());
+      let source = fs::read_to_string(&path).unwrap();
+      builder = builder
+        .add_script(&path.to_string_lossy(), &source)
+        .unwrap();
+    }
+  }
+  let size = builder.write(&bundle.with_extension("bin")).unwrap();
+  println!("snapshot bytes {}", size);
 
   let root_names = vec![c.join("js/compiler.ts")];
   let bundle = o.join("COMPILER_SNAPSHOT.js");
   let state = deno_typescript::compile_bundle(&bundle, root_names).unwrap();
   assert!(bundle.exists());
-  deno_typescript::mksnapshot_bundle_ts(&bundle, state).unwrap();
+  let size = deno_typescript::mksnapshot_bundle_ts(&bundle, state).unwrap();
+  println!("snapshot bytes {}", size);
+}
+
+/// Prints rerun-if-changed for every file under `dir`.
+fn rerun_if_changed(dir: &Path) {
+  for entry in fs::read_dir(dir).unwrap() {
+    let path = entry.unwrap().path();
+    if path.is_dir() {
+      rerun_if_changed(&path);
+    } else {
+      println!("cargo:rerun-if-changed={}", path.display());
+    }
+  }
 }
@@ -5,10 +5,12 @@ use futures::Future;
 mod js;
 mod json;
 mod ts;
+mod wasm;
 
 pub use js::JsCompiler;
 pub use json::JsonCompiler;
 pub use ts::TsCompiler;
+pub use wasm::WasmCompiler;
 
 #[derive(Debug, Clone)]
 pub struct CompiledModule {
@@ -1,6 +1,7 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
 use crate::compilers::CompiledModule;
 use crate::compilers::CompiledModuleFuture;
+use crate::deno_error::print_err_and_exit;
 use crate::diagnostics::Diagnostic;
 use crate::disk_cache::DiskCache;
 use crate::file_fetcher::SourceFile;
@@ -278,8 +279,7 @@ impl TsCompiler {
         .then(move |result| {
           if let Err(err) = result {
             // TODO(ry) Need to forward the error instead of exiting.
-            eprintln!("{}", err.to_string());
-            std::process::exit(1);
+            print_err_and_exit(err);
           }
           debug!("Sent message to worker");
           let stream_future =
@@ -386,8 +386,7 @@ impl TsCompiler {
         .then(move |result| {
           if let Err(err) = result {
             // TODO(ry) Need to forward the error instead of exiting.
-            eprintln!("{}", err.to_string());
-            std::process::exit(1);
+            print_err_and_exit(err);
           }
           debug!("Sent message to worker");
           let stream_future =
@@ -0,0 +1,62 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+use crate::compilers::CompiledModule;
+use crate::compilers::CompiledModuleFuture;
+use crate::file_fetcher::SourceFile;
+use crate::state::ThreadSafeState;
+
+/// Turns a WebAssembly module into a JavaScript module which instantiates it.
+/// When it's the main module, the program is run as a WASI command with the
+/// imports of `Deno.Wasi` and access to the current directory, and Deno exits
+/// with its exit code. Otherwise the exports of the instance are the default
+/// export of the module.
+pub struct WasmCompiler {}
+
+impl WasmCompiler {
+  pub fn compile_async(
+    self: &Self,
+    state: ThreadSafeState,
+    source_file: &SourceFile,
+  ) -> Box<CompiledModuleFuture> {
+    let is_main = state
+      .main_module()
+      .map_or(false, |main| *main.as_url() == source_file.url);
+    let module = CompiledModule {
+      code: wrap_wasm(&source_file.source_code, is_main),
+      name: source_file.url.to_string(),
+    };
+
+    Box::new(futures::future::ok(module))
+  }
+}
+
+fn wrap_wasm(source_code: &[u8], is_main: bool) -> String {
+  let bytes = format!(
+    r#"const bytes = Uint8Array.from(atob("{}"), c => c.charCodeAt(0));
+const module = new WebAssembly.Module(bytes);
+"#,
+    base64::encode(source_code)
+  );
+  if is_main {
+    bytes
+      + r#"const wasi = new Deno.Wasi({ args: Deno.args, preopens: { ".": "." } });
+const instance = new WebAssembly.Instance(module, wasi.imports);
+Deno.exit(wasi.start(instance));
+"#
+  } else {
+    bytes
+      + r#"const instance = new WebAssembly.Instance(module);
+export default instance.exports;
+"#
+  }
+}
+
+#[test]
+fn test_wrap_wasm() {
+  let code = wrap_wasm(b"\0asm\x01\0\0\0", true);
+  assert!(code.contains(r#"atob("AGFzbQEAAAA=")"#));
+  assert!(code.contains("Deno.exit(wasi.start(instance));"));
+
+  let code = wrap_wasm(b"\0asm\x01\0\0\0", false);
+  assert!(!code.contains("Deno.Wasi"));
+  assert!(code.contains("export default instance.exports;"));
+}
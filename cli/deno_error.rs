@@ -8,6 +8,7 @@ use deno::ErrBox;
 use deno::ModuleResolutionError;
 use http::uri;
 use hyper;
+use notify;
 use reqwest;
 use rustyline::error::ReadlineError;
 use std;
@@ -25,6 +26,7 @@ pub struct DenoError {
 
 pub fn print_err_and_exit(err: ErrBox) {
   eprintln!("{}", err.to_string());
+  crate::resources::cleanup();
   std::process::exit(1);
 }
 
@@ -63,6 +65,11 @@ pub fn bad_resource() -> ErrBox {
   StaticError(ErrorKind::BadResource, "bad resource id").into()
 }
 
+/// The error of an op that was still pending on a resource when it was closed.
+pub fn resource_closed() -> ErrBox {
+  StaticError(ErrorKind::Interrupted, "resource closed").into()
+}
+
 pub fn permission_denied() -> ErrBox {
   StaticError(ErrorKind::PermissionDenied, "permission denied").into()
 }
@@ -247,6 +254,18 @@ impl GetErrorKind for ReadlineError {
   }
 }
 
+impl GetErrorKind for notify::Error {
+  fn kind(&self) -> ErrorKind {
+    use notify::Error::*;
+    match self {
+      Io(err) => GetErrorKind::kind(err),
+      PathNotFound => ErrorKind::NotFound,
+      WatchNotFound => ErrorKind::NotFound,
+      Generic(_) => ErrorKind::Other,
+    }
+  }
+}
+
 impl GetErrorKind for serde_json::error::Error {
   fn kind(&self) -> ErrorKind {
     use serde_json::error::*;
@@ -307,6 +326,7 @@ impl GetErrorKind for dyn AnyError {
       .or_else(|| self.downcast_ref::<url::ParseError>().map(Get::kind))
       .or_else(|| self.downcast_ref::<VarError>().map(Get::kind))
       .or_else(|| self.downcast_ref::<ReadlineError>().map(Get::kind))
+      .or_else(|| self.downcast_ref::<notify::Error>().map(Get::kind))
       .or_else(|| {
         self
           .downcast_ref::<serde_json::error::Error>()
@@ -476,6 +496,13 @@ mod tests {
     assert_eq!(err.to_string(), "bad resource id");
   }
 
+  #[test]
+  fn test_resource_closed() {
+    let err = resource_closed();
+    assert_eq!(err.kind(), ErrorKind::Interrupted);
+    assert_eq!(err.to_string(), "resource closed");
+  }
+
   #[test]
   fn test_permission_denied() {
     let err = permission_denied();
@@ -0,0 +1,183 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+//! Loads dotenv files, which set environment variables with lines like
+//! `KEY=value`. Blank lines and lines starting with `#` are skipped, and a
+//! line may start with `export`. Values can be:
+//!
+//! - unquoted, up to a ` #` comment, with surrounding whitespace trimmed,
+//! - in single quotes, taken literally,
+//! - in double quotes, with `\n`, `\r`, `\t`, `\"` and `\\` escapes, and
+//!   possibly spanning several lines.
+use crate::deno_error::{DenoError, ErrorKind};
+use deno::ErrBox;
+use std::env;
+use std::fs;
+use std::io;
+
+/// Sets the variables of the dotenv file at `path` that are not set yet.
+pub fn load(path: &str) -> Result<(), ErrBox> {
+  let source = fs::read_to_string(path).map_err(|e| {
+    io::Error::new(e.kind(), format!("Cannot read env file {}: {}", path, e))
+  })?;
+  let vars = parse(&source).map_err(|e| {
+    DenoError::new(ErrorKind::InvalidInput, format!("{}:{}", path, e))
+  })?;
+  for (key, value) in vars {
+    if env::var_os(&key).is_none() {
+      env::set_var(key, value);
+    }
+  }
+  Ok(())
+}
+
+/// Parses a dotenv file. Errors are prefixed with the line they occur on.
+pub fn parse(source: &str) -> Result<Vec<(String, String)>, String> {
+  let mut vars = Vec::new();
+  let mut lines = source.lines().enumerate();
+  while let Some((i, line)) = lines.next() {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+      continue;
+    }
+    let line = strip_export(line);
+    let eq = match line.find('=') {
+      Some(eq) => eq,
+      None => return Err(format!("{}: expected KEY=value", i + 1)),
+    };
+    let key = line[..eq].trim();
+    if !is_valid_key(key) {
+      return Err(format!("{}: invalid variable name {:?}", i + 1, key));
+    }
+    let rest = line[eq + 1..].trim_start();
+
+    let value = if rest.starts_with('\'') {
+      match rest[1..].find('\'') {
+        Some(end) => rest[1..=end].to_string(),
+        None => return Err(format!("{}: unterminated quote", i + 1)),
+      }
+    } else if rest.starts_with('"') {
+      let mut quoted = rest[1..].to_string();
+      loop {
+        if let Some(value) = unescape_double_quoted(&quoted) {
+          break value;
+        }
+        match lines.next() {
+          Some((_, next)) => {
+            quoted.push('\n');
+            quoted.push_str(next);
+          }
+          None => return Err(format!("{}: unterminated quote", i + 1)),
+        }
+      }
+    } else {
+      strip_comment(rest).trim_end().to_string()
+    };
+    vars.push((key.to_string(), value));
+  }
+  Ok(vars)
+}
+
+fn strip_export(line: &str) -> &str {
+  let rest = line.trim_start_matches("export");
+  if rest.len() < line.len() && rest.starts_with(char::is_whitespace) {
+    rest.trim_start()
+  } else {
+    line
+  }
+}
+
+fn is_valid_key(key: &str) -> bool {
+  let mut chars = key.chars();
+  match chars.next() {
+    Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+    _ => return false,
+  }
+  chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+/// Returns the value up to the closing quote of `s`, or None if there is no
+/// closing quote yet.
+fn unescape_double_quoted(s: &str) -> Option<String> {
+  let mut value = String::new();
+  let mut chars = s.chars();
+  while let Some(c) = chars.next() {
+    match c {
+      '"' => return Some(value),
+      '\\' => match chars.next() {
+        Some('n') => value.push('\n'),
+        Some('r') => value.push('\r'),
+        Some('t') => value.push('\t'),
+        Some(c @ '"') | Some(c @ '\\') => value.push(c),
+        Some(c) => {
+          value.push('\\');
+          value.push(c);
+        }
+        None => return None,
+      },
+      c => value.push(c),
+    }
+  }
+  None
+}
+
+fn strip_comment(value: &str) -> &str {
+  let mut prev_is_space = true;
+  for (i, c) in value.char_indices() {
+    if c == '#' && prev_is_space {
+      return &value[..i];
+    }
+    prev_is_space = c.is_whitespace();
+  }
+  value
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn pairs(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+    vars
+      .iter()
+      .map(|(k, v)| (k.to_string(), v.to_string()))
+      .collect()
+  }
+
+  #[test]
+  fn test_parse() {
+    let source = r#"
+# comment
+BASIC=basic
+export EXPORTED=exported
+SPACED = spaced value  # comment
+HASH=a#b
+EMPTY=
+SINGLE='single # "quoted" \n'
+DOUBLE="double\tquoted \"x\"" # comment
+MULTI="line 1
+line 2"
+"#;
+    assert_eq!(
+      parse(source).unwrap(),
+      pairs(&[
+        ("BASIC", "basic"),
+        ("EXPORTED", "exported"),
+        ("SPACED", "spaced value"),
+        ("HASH", "a#b"),
+        ("EMPTY", ""),
+        ("SINGLE", "single # \"quoted\" \\n"),
+        ("DOUBLE", "double\tquoted \"x\""),
+        ("MULTI", "line 1\nline 2"),
+      ])
+    );
+  }
+
+  #[test]
+  fn test_parse_errors() {
+    assert_eq!(parse("A=1\nNOPE\n").unwrap_err(), "2: expected KEY=value");
+    assert_eq!(
+      parse("1A=1").unwrap_err(),
+      "1: invalid variable name \"1A\""
+    );
+    assert_eq!(parse("A=\"open\n").unwrap_err(), "1: unterminated quote");
+    assert_eq!(parse("A='open").unwrap_err(), "1: unterminated quote");
+  }
+}
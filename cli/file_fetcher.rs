@@ -73,6 +73,7 @@ pub struct SourceFileFetcher {
   cache_blacklist: Vec<String>,
   use_disk_cache: bool,
   no_remote_fetch: bool,
+  unsafely_ignore_certificate_errors: Option<Vec<String>>,
 }
 
 impl SourceFileFetcher {
@@ -82,6 +83,7 @@ impl SourceFileFetcher {
     use_disk_cache: bool,
     cache_blacklist: Vec<String>,
     no_remote_fetch: bool,
+    unsafely_ignore_certificate_errors: Option<Vec<String>>,
   ) -> std::io::Result<Self> {
     let file_fetcher = Self {
       deps_cache,
@@ -90,6 +92,7 @@ impl SourceFileFetcher {
       cache_blacklist,
       use_disk_cache,
       no_remote_fetch,
+      unsafely_ignore_certificate_errors,
     };
 
     Ok(file_fetcher)
@@ -347,8 +350,14 @@ impl SourceFileFetcher {
     let dir = self.clone();
     let module_url = module_url.clone();
 
+    let accept_invalid_certs = http_util::ignores_certificate_errors(
+      &self.unsafely_ignore_certificate_errors,
+      module_url.host_str().unwrap_or(""),
+    );
+
     // Single pass fetch, either yields code or yields redirect.
-    let f = http_util::fetch_string_once(&module_url).and_then(move |r| {
+    let fetch = http_util::fetch_string_once(&module_url, accept_invalid_certs);
+    let f = fetch.and_then(move |r| {
       match r {
         FetchOnceResult::Redirect(new_module_url) => {
           // If redirects, update module_name and filename for next looped call.
@@ -491,6 +500,7 @@ fn map_file_extension(path: &Path) -> msg::MediaType {
       Some("jsx") => msg::MediaType::JSX,
       Some("mjs") => msg::MediaType::JavaScript,
       Some("json") => msg::MediaType::Json,
+      Some("wasm") => msg::MediaType::Wasm,
       _ => msg::MediaType::Unknown,
     },
   }
@@ -521,6 +531,7 @@ fn map_content_type(path: &Path, content_type: Option<&str>) -> msg::MediaType {
           map_js_like_extension(path, msg::MediaType::JavaScript)
         }
         "application/json" | "text/json" => msg::MediaType::Json,
+        "application/wasm" => msg::MediaType::Wasm,
         "text/plain" => map_file_extension(path),
         _ => {
           debug!("unknown content type: {}", content_type);
@@ -663,6 +674,7 @@ mod tests {
       true,
       vec![],
       false,
+      None,
     )
     .expect("setup fail")
   }
@@ -1503,6 +1515,10 @@ mod tests {
       map_file_extension(Path::new("foo/bar.json")),
       msg::MediaType::Json
     );
+    assert_eq!(
+      map_file_extension(Path::new("foo/bar.wasm")),
+      msg::MediaType::Wasm
+    );
     assert_eq!(
       map_file_extension(Path::new("foo/bar.txt")),
       msg::MediaType::Unknown
@@ -1597,6 +1613,10 @@ mod tests {
       map_content_type(Path::new("foo/bar"), Some("application/json")),
       msg::MediaType::Json
     );
+    assert_eq!(
+      map_content_type(Path::new("foo/bar"), Some("application/wasm")),
+      msg::MediaType::Wasm
+    );
     assert_eq!(
       map_content_type(Path::new("foo/bar"), Some("text/json")),
       msg::MediaType::Json
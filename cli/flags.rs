@@ -1,5 +1,6 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
 use crate::fs as deno_fs;
+use crate::permissions::split_host_port;
 use clap::App;
 use clap::AppSettings;
 use clap::Arg;
@@ -9,6 +10,7 @@ use clap::SubCommand;
 use deno::ModuleSpecifier;
 use log::Level;
 use std;
+use std::net::SocketAddr;
 use std::str;
 use std::str::FromStr;
 use url::Url;
@@ -28,6 +30,27 @@ const TEST_RUNNER_URL: &str = std_url!("testing/runner.ts");
 /// Used for `deno xeval...` subcommand
 const XEVAL_URL: &str = std_url!("xeval/mod.ts");
 
+/// Where `--inspect` listens by default, like Node
+const DEFAULT_INSPECT_ADDR: &str = "127.0.0.1:9229";
+
+/// What to do when a promise is rejected without a handler, as set by
+/// `--unhandled-rejections`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UnhandledRejections {
+  /// Print the error and exit with a non-zero code.
+  Strict,
+  /// Print the error and keep running.
+  Warn,
+  /// Ignore the rejection.
+  None,
+}
+
+impl Default for UnhandledRejections {
+  fn default() -> Self {
+    UnhandledRejections::Strict
+  }
+}
+
 // Creates vector of strings, Vec<String>
 macro_rules! svec {
     ($($x:expr),*) => (vec![$($x.to_string()),*]);
@@ -44,6 +67,9 @@ pub struct DenoFlags {
   /// When the `--importmap` flag is used to pass the name, this will be set
   /// the path passed on the command line, otherwise `None`.
   pub import_map_path: Option<String>,
+  /// When the `--env` flag is used, this will be set to the path of the
+  /// dotenv file to load, `.env` unless another one was passed.
+  pub env_file: Option<String>,
   pub allow_read: bool,
   pub read_whitelist: Vec<String>,
   pub cache_blacklist: Vec<String>,
@@ -52,11 +78,29 @@ pub struct DenoFlags {
   pub allow_net: bool,
   pub net_whitelist: Vec<String>,
   pub allow_env: bool,
+  pub env_whitelist: Vec<String>,
   pub allow_run: bool,
+  pub run_whitelist: Vec<String>,
   pub allow_hrtime: bool,
+  pub allow_plugin: bool,
+  pub deny_read: bool,
+  pub read_blacklist: Vec<String>,
+  pub deny_write: bool,
+  pub write_blacklist: Vec<String>,
+  pub deny_net: bool,
+  pub net_blacklist: Vec<String>,
+  pub deny_env: bool,
   pub no_prompts: bool,
+  pub log_permissions: Option<String>,
+  pub trace_ops: Option<String>,
+  pub inspect: Option<SocketAddr>,
+  pub inspect_brk: bool,
+  pub unhandled_rejections: UnhandledRejections,
+  pub permissions_file: Option<String>,
+  pub unsafely_ignore_certificate_errors: Option<Vec<String>>,
   pub no_fetch: bool,
   pub seed: Option<u64>,
+  pub unsafely_seed_crypto: bool,
   pub v8_flags: Option<Vec<String>>,
   // Use tokio::runtime::current_thread
   pub current_thread: bool,
@@ -100,11 +144,19 @@ fn add_run_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
     .arg(
       Arg::with_name("allow-env")
         .long("allow-env")
+        .min_values(0)
+        .takes_value(true)
+        .use_delimiter(true)
+        .require_equals(true)
         .help("Allow environment access"),
     )
     .arg(
       Arg::with_name("allow-run")
         .long("allow-run")
+        .min_values(0)
+        .takes_value(true)
+        .use_delimiter(true)
+        .require_equals(true)
         .help("Allow running subprocesses"),
     )
     .arg(
@@ -112,17 +164,170 @@ fn add_run_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
         .long("allow-hrtime")
         .help("Allow high resolution time measurement"),
     )
+    .arg(
+      Arg::with_name("allow-plugin")
+        .long("allow-plugin")
+        .help("Allow loading plugins"),
+    )
+    .arg(
+      Arg::with_name("deny-read")
+        .long("deny-read")
+        .min_values(0)
+        .takes_value(true)
+        .use_delimiter(true)
+        .require_equals(true)
+        .help("Deny file system read access, overriding --allow-read"),
+    )
+    .arg(
+      Arg::with_name("deny-write")
+        .long("deny-write")
+        .min_values(0)
+        .takes_value(true)
+        .use_delimiter(true)
+        .require_equals(true)
+        .help("Deny file system write access, overriding --allow-write"),
+    )
+    .arg(
+      Arg::with_name("deny-net")
+        .long("deny-net")
+        .min_values(0)
+        .takes_value(true)
+        .use_delimiter(true)
+        .require_equals(true)
+        .help("Deny network access, overriding --allow-net"),
+    )
+    .arg(
+      Arg::with_name("deny-env")
+        .long("deny-env")
+        .help("Deny environment access, overriding --allow-env"),
+    )
     .arg(
       Arg::with_name("allow-all")
         .short("A")
         .long("allow-all")
         .help("Allow all permissions"),
     )
+    .arg(
+      Arg::with_name("env")
+        .long("env")
+        .min_values(0)
+        .takes_value(true)
+        .require_equals(true)
+        .value_name("FILE")
+        .help("Load environment variables from a dotenv file")
+        .long_help(
+          "Load environment variables from a dotenv file, .env by default.
+Variables that are already set are left as they are. Requires --allow-env,
+which reading them requires too.",
+        ),
+    )
     .arg(
       Arg::with_name("no-prompt")
         .long("no-prompt")
         .help("Do not use prompts"),
     )
+    .arg(
+      Arg::with_name("permissions")
+        .long("permissions")
+        .takes_value(true)
+        .require_equals(true)
+        .value_name("FILE")
+        .help("Grant the permissions of a JSON manifest")
+        .long_help(
+          "Grant the permissions of a JSON manifest, in addition to the
+--allow-* flags. For example:
+{\"read\": [\"./data\"], \"net\": [\"deno.land\"], \"env\": true, \"run\": [\"git\"]}",
+        ),
+    )
+    .arg(
+      Arg::with_name("log-permissions")
+        .long("log-permissions")
+        .min_values(0)
+        .takes_value(true)
+        .require_equals(true)
+        .value_name("FILE")
+        .help("Log every permission check")
+        .long_help(
+          "Log every read, write, net and run permission check as a line of
+JSON, to FILE or to stderr by default. Lines look like
+{\"origin\":\"file:///main.ts\",\"permission\":\"read\",\"target\":\"/etc/hosts\",\"granted\":true}",
+        ),
+    )
+    .arg(
+      Arg::with_name("trace-ops")
+        .long("trace-ops")
+        .takes_value(true)
+        .require_equals(true)
+        .value_name("FILE")
+        .help("Record every op to a trace file")
+        .long_help(
+          "Record when every op starts and ends, and how many bytes it sends
+and receives, to FILE as it runs. FILE is in the Chrome trace event format,
+which chrome://tracing can open.",
+        ),
+    )
+    .arg(
+      Arg::with_name("inspect")
+        .long("inspect")
+        .min_values(0)
+        .takes_value(true)
+        .require_equals(true)
+        .value_name("HOST:PORT")
+        .validator(validate_inspect_addr)
+        .conflicts_with_all(&["inspect-brk", "current-thread"])
+        .help("Activate the inspector on HOST:PORT")
+        .long_help(
+          "Activate the inspector on HOST:PORT, 127.0.0.1:9229 by default,
+to debug the program with the Chrome DevTools (chrome://inspect).",
+        ),
+    )
+    .arg(
+      Arg::with_name("inspect-brk")
+        .long("inspect-brk")
+        .min_values(0)
+        .takes_value(true)
+        .require_equals(true)
+        .value_name("HOST:PORT")
+        .validator(validate_inspect_addr)
+        .conflicts_with("current-thread")
+        .help("Activate the inspector and break at the start of the program")
+        .long_help(
+          "Activate the inspector on HOST:PORT, 127.0.0.1:9229 by default,
+then wait for a debugger to connect and break at the start of the main
+module.",
+        ),
+    )
+    .arg(
+      Arg::with_name("unhandled-rejections")
+        .long("unhandled-rejections")
+        .takes_value(true)
+        .require_equals(true)
+        .value_name("MODE")
+        .possible_values(&["strict", "warn", "none"])
+        .help("What to do with unhandled promise rejections")
+        .long_help(
+          "What to do when a promise is rejected without a handler. \"strict\",
+the default, prints the error and exits with a non-zero code. \"warn\"
+prints the error and keeps running. \"none\" ignores the rejection.",
+        ),
+    )
+    .arg(
+      Arg::with_name("unsafely-ignore-certificate-errors")
+        .long("unsafely-ignore-certificate-errors")
+        .min_values(0)
+        .takes_value(true)
+        .use_delimiter(true)
+        .require_equals(true)
+        .value_name("HOSTNAMES")
+        .help("DANGER: Disable TLS certificate validation")
+        .long_help(
+          "DANGER: Disable TLS certificate validation for the listed
+hostnames, or for all hostnames when none are given. This applies to
+module downloads, fetch() and Deno.dialTLS(), and leaves connections open
+to interception. Use it only to reach hosts with self-signed certificates
+during development.",
+        ),
+    )
     .arg(
       Arg::with_name("no-fetch")
         .long("no-fetch")
@@ -130,6 +335,13 @@ fn add_run_args<'a, 'b>(app: App<'a, 'b>) -> App<'a, 'b> {
     )
 }
 
+fn validate_inspect_addr(val: String) -> Result<(), String> {
+  match val.parse::<SocketAddr>() {
+    Ok(_) => Ok(()),
+    Err(_) => Err(format!("{} is not a valid HOST:PORT address", val)),
+  }
+}
+
 pub fn create_cli_app<'a, 'b>() -> App<'a, 'b> {
   add_run_args(App::new("deno"))
     .bin_name("deno")
@@ -228,6 +440,17 @@ Examples: https://github.com/WICG/import-maps#the-import-map",
           }
         })
         .global(true),
+    ).arg(
+      Arg::with_name("unsafely-seed-crypto")
+        .long("unsafely-seed-crypto")
+        .requires("seed")
+        .help("DANGER: Seed crypto.getRandomValues() too")
+        .long_help(
+          "DANGER: Seed crypto.getRandomValues() with --seed too, so its
+values are reproducible. They're predictable as well, so only use this to
+reproduce test runs, never where the values need to be secure.",
+        )
+        .global(true),
     ).arg(
       Arg::with_name("v8-options")
         .long("v8-options")
@@ -505,7 +728,7 @@ Example:
 /// Convert paths supplied into full path.
 /// If a path is invalid, we print out a warning
 /// and ignore this path in the output.
-fn resolve_paths(paths: Vec<String>) -> Vec<String> {
+pub fn resolve_paths(paths: Vec<String>) -> Vec<String> {
   let mut out: Vec<String> = vec![];
   for pathstr in paths.iter() {
     let result = deno_fs::resolve_from_cwd(pathstr);
@@ -523,6 +746,16 @@ fn resolve_paths(paths: Vec<String>) -> Vec<String> {
   out
 }
 
+/// Resolves the program paths in the run whitelist like `resolve_paths`.
+/// Bare program names are kept as they are, to be looked up in PATH.
+pub fn resolve_programs(programs: Vec<String>) -> Vec<String> {
+  let (paths, mut out): (Vec<String>, Vec<String>) = programs
+    .into_iter()
+    .partition(|program| program.contains(std::path::is_separator));
+  out.extend(resolve_paths(paths));
+  out
+}
+
 pub fn resolve_urls(urls: Vec<String>) -> Vec<String> {
   let mut out: Vec<String> = vec![];
   for urlstr in urls.iter() {
@@ -543,32 +776,18 @@ pub fn resolve_urls(urls: Vec<String>) -> Vec<String> {
 /// This function expands "bare port" paths (eg. ":8080")
 /// into full paths with hosts. It expands to such paths
 /// into 3 paths with following hosts: `0.0.0.0:port`, `127.0.0.1:port` and `localhost:port`.
-fn resolve_hosts(paths: Vec<String>) -> Vec<String> {
+/// IPv6 hosts are written as `[::1]:port`, see `split_host_port`.
+pub fn resolve_hosts(paths: Vec<String>) -> Vec<String> {
   let mut out: Vec<String> = vec![];
   for host_and_port in paths.iter() {
-    let parts = host_and_port.split(':').collect::<Vec<&str>>();
-
-    match parts.len() {
-      // host only
-      1 => {
-        out.push(host_and_port.to_owned());
-      }
-      // host and port (NOTE: host might be empty string)
-      2 => {
-        let host = parts[0];
-        let port = parts[1];
-
-        if !host.is_empty() {
-          out.push(host_and_port.to_owned());
-          continue;
-        }
-
-        // we got bare port, let's add default hosts
+    match split_host_port(host_and_port) {
+      // we got bare port, let's add default hosts
+      ("", Some(port)) => {
         for host in ["0.0.0.0", "127.0.0.1", "localhost"].iter() {
           out.push(format!("{}:{}", host, port));
         }
       }
-      _ => panic!("Bad host:port pair: {}", host_and_port),
+      _ => out.push(host_and_port.to_owned()),
     }
   }
 
@@ -639,6 +858,9 @@ pub fn parse_flags(
       }
     }
   }
+  if matches.is_present("unsafely-seed-crypto") {
+    flags.unsafely_seed_crypto = true;
+  }
 
   flags = parse_run_args(flags, matches);
   // flags specific to "run" subcommand
@@ -690,14 +912,32 @@ fn parse_run_args(mut flags: DenoFlags, matches: &ArgMatches) -> DenoFlags {
     }
   }
   if matches.is_present("allow-env") {
-    flags.allow_env = true;
+    if matches.value_of("allow-env").is_some() {
+      let env_wl = matches.values_of("allow-env").unwrap();
+      flags.env_whitelist =
+        env_wl.map(std::string::ToString::to_string).collect();
+      debug!("env whitelist: {:#?}", &flags.env_whitelist);
+    } else {
+      flags.allow_env = true;
+    }
   }
   if matches.is_present("allow-run") {
-    flags.allow_run = true;
+    if matches.value_of("allow-run").is_some() {
+      let run_wl = matches.values_of("allow-run").unwrap();
+      let raw_run_whitelist =
+        run_wl.map(std::string::ToString::to_string).collect();
+      flags.run_whitelist = resolve_programs(raw_run_whitelist);
+      debug!("run whitelist: {:#?}", &flags.run_whitelist);
+    } else {
+      flags.allow_run = true;
+    }
   }
   if matches.is_present("allow-hrtime") {
     flags.allow_hrtime = true;
   }
+  if matches.is_present("allow-plugin") {
+    flags.allow_plugin = true;
+  }
   if matches.is_present("allow-all") {
     flags.allow_read = true;
     flags.allow_env = true;
@@ -706,14 +946,86 @@ fn parse_run_args(mut flags: DenoFlags, matches: &ArgMatches) -> DenoFlags {
     flags.allow_read = true;
     flags.allow_write = true;
     flags.allow_hrtime = true;
+    flags.allow_plugin = true;
+  }
+  if matches.is_present("deny-read") {
+    if matches.value_of("deny-read").is_some() {
+      let read_bl = matches.values_of("deny-read").unwrap();
+      let raw_read_blacklist =
+        read_bl.map(std::string::ToString::to_string).collect();
+      flags.read_blacklist = resolve_paths(raw_read_blacklist);
+      debug!("read blacklist: {:#?}", &flags.read_blacklist);
+    } else {
+      flags.deny_read = true;
+    }
+  }
+  if matches.is_present("deny-write") {
+    if matches.value_of("deny-write").is_some() {
+      let write_bl = matches.values_of("deny-write").unwrap();
+      let raw_write_blacklist =
+        write_bl.map(std::string::ToString::to_string).collect();
+      flags.write_blacklist = resolve_paths(raw_write_blacklist);
+      debug!("write blacklist: {:#?}", &flags.write_blacklist);
+    } else {
+      flags.deny_write = true;
+    }
+  }
+  if matches.is_present("deny-net") {
+    if matches.value_of("deny-net").is_some() {
+      let net_bl = matches.values_of("deny-net").unwrap();
+      let raw_net_blacklist =
+        net_bl.map(std::string::ToString::to_string).collect();
+      flags.net_blacklist = resolve_hosts(raw_net_blacklist);
+      debug!("net blacklist: {:#?}", &flags.net_blacklist);
+    } else {
+      flags.deny_net = true;
+    }
+  }
+  if matches.is_present("deny-env") {
+    flags.deny_env = true;
   }
   if matches.is_present("no-prompt") {
     flags.no_prompts = true;
   }
+  flags.permissions_file =
+    matches.value_of("permissions").map(ToOwned::to_owned);
+  if matches.is_present("log-permissions") {
+    let log_file = matches.value_of("log-permissions").unwrap_or("-");
+    flags.log_permissions = Some(log_file.to_owned());
+  }
+  if let Some(trace_file) = matches.value_of("trace-ops") {
+    flags.trace_ops = Some(trace_file.to_owned());
+  }
+  for name in &["inspect", "inspect-brk"] {
+    if matches.is_present(name) {
+      let addr = matches.value_of(name).unwrap_or(DEFAULT_INSPECT_ADDR);
+      flags.inspect = Some(addr.parse().unwrap());
+      flags.inspect_brk = *name == "inspect-brk";
+    }
+  }
+  if let Some(mode) = matches.value_of("unhandled-rejections") {
+    flags.unhandled_rejections = match mode {
+      "strict" => UnhandledRejections::Strict,
+      "warn" => UnhandledRejections::Warn,
+      "none" => UnhandledRejections::None,
+      _ => unreachable!(),
+    };
+  }
+  if matches.is_present("unsafely-ignore-certificate-errors") {
+    let hosts = match matches.values_of("unsafely-ignore-certificate-errors") {
+      Some(hosts) => hosts.map(std::string::ToString::to_string).collect(),
+      None => vec![],
+    };
+    flags.unsafely_ignore_certificate_errors = Some(hosts);
+  }
   if matches.is_present("no-fetch") {
     flags.no_fetch = true;
   }
   flags.import_map_path = matches.value_of("importmap").map(ToOwned::to_owned);
+  if matches.is_present("env") {
+    let env_file = matches.value_of("env").unwrap_or(".env");
+    flags.env_file = Some(env_file.to_owned());
+  }
 
   flags
 }
@@ -850,6 +1162,7 @@ pub fn flags_from_vec(
       flags.allow_read = true;
       flags.allow_write = true;
       flags.allow_hrtime = true;
+      flags.allow_plugin = true;
       let code: &str = eval_match.value_of("code").unwrap();
       argv.extend(vec![code.to_string()]);
       DenoSubcommand::Eval
@@ -981,6 +1294,7 @@ pub fn flags_from_vec(
       flags.allow_read = true;
       flags.allow_write = true;
       flags.allow_hrtime = true;
+      flags.allow_plugin = true;
       argv.push(XEVAL_URL.to_string());
 
       if xeval_match.is_present("delim") {
@@ -1024,6 +1338,7 @@ pub fn flags_from_vec(
       flags.allow_read = true;
       flags.allow_write = true;
       flags.allow_hrtime = true;
+      flags.allow_plugin = true;
       DenoSubcommand::Repl
     }
   };
@@ -1183,6 +1498,7 @@ mod tests {
         allow_read: true,
         allow_write: true,
         allow_hrtime: true,
+        allow_plugin: true,
         ..DenoFlags::default()
       }
     );
@@ -1329,6 +1645,7 @@ mod tests {
         allow_read: true,
         allow_write: true,
         allow_hrtime: true,
+        allow_plugin: true,
         ..DenoFlags::default()
       }
     );
@@ -1348,6 +1665,7 @@ mod tests {
         allow_read: true,
         allow_write: true,
         allow_hrtime: true,
+        allow_plugin: true,
         ..DenoFlags::default()
       }
     );
@@ -1375,6 +1693,7 @@ mod tests {
         allow_read: true,
         allow_write: true,
         allow_hrtime: true,
+        allow_plugin: true,
         ..DenoFlags::default()
       }
     );
@@ -1898,4 +2217,322 @@ mod tests {
     assert_eq!(subcommand, DenoSubcommand::Run);
     assert_eq!(argv, svec!["deno", "script.ts"])
   }
+
+  #[test]
+  fn test_flags_from_vec_38() {
+    let (flags, subcommand, argv) =
+      flags_from_vec(svec!["deno", "run", "--env", "script.ts"]);
+    assert_eq!(
+      flags,
+      DenoFlags {
+        env_file: Some(".env".to_owned()),
+        ..DenoFlags::default()
+      }
+    );
+    assert_eq!(subcommand, DenoSubcommand::Run);
+    assert_eq!(argv, svec!["deno", "script.ts"]);
+
+    let (flags, subcommand, argv) =
+      flags_from_vec(svec!["deno", "--env=prod.env", "script.ts"]);
+    assert_eq!(
+      flags,
+      DenoFlags {
+        env_file: Some("prod.env".to_owned()),
+        ..DenoFlags::default()
+      }
+    );
+    assert_eq!(subcommand, DenoSubcommand::Run);
+    assert_eq!(argv, svec!["deno", "script.ts"])
+  }
+
+  #[test]
+  fn test_flags_from_vec_39() {
+    let (flags, subcommand, argv) = flags_from_vec(svec![
+      "deno",
+      "--allow-net=::1,[::1]:8000,deno.land:443",
+      "script.ts"
+    ]);
+    assert_eq!(
+      flags,
+      DenoFlags {
+        net_whitelist: svec!["::1", "[::1]:8000", "deno.land:443"],
+        ..DenoFlags::default()
+      }
+    );
+    assert_eq!(subcommand, DenoSubcommand::Run);
+    assert_eq!(argv, svec!["deno", "script.ts"])
+  }
+
+  #[test]
+  fn test_flags_from_vec_40() {
+    let (flags, subcommand, argv) = flags_from_vec(svec![
+      "deno",
+      "--allow-run=git,/usr/local/bin/tool",
+      "script.ts"
+    ]);
+    assert_eq!(
+      flags,
+      DenoFlags {
+        run_whitelist: svec!["git", "/usr/local/bin/tool"],
+        ..DenoFlags::default()
+      }
+    );
+    assert_eq!(subcommand, DenoSubcommand::Run);
+    assert_eq!(argv, svec!["deno", "script.ts"]);
+
+    let (flags, _, _) =
+      flags_from_vec(svec!["deno", "--allow-run", "script.ts"]);
+    assert_eq!(
+      flags,
+      DenoFlags {
+        allow_run: true,
+        ..DenoFlags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn test_flags_from_vec_41() {
+    use tempfile::TempDir;
+    let temp_dir = TempDir::new().expect("tempdir fail");
+    let (_, temp_dir_path) =
+      deno_fs::resolve_from_cwd(temp_dir.path().to_str().unwrap()).unwrap();
+
+    let (flags, subcommand, argv) = flags_from_vec(svec![
+      "deno",
+      "-A",
+      "--deny-net=169.254.169.254",
+      format!("--deny-read={}", &temp_dir_path),
+      "--deny-write",
+      "--deny-env",
+      "script.ts"
+    ]);
+    assert_eq!(
+      flags,
+      DenoFlags {
+        allow_read: true,
+        allow_write: true,
+        allow_net: true,
+        allow_env: true,
+        allow_run: true,
+        allow_hrtime: true,
+        allow_plugin: true,
+        net_blacklist: svec!["169.254.169.254"],
+        read_blacklist: svec![&temp_dir_path],
+        deny_write: true,
+        deny_env: true,
+        ..DenoFlags::default()
+      }
+    );
+    assert_eq!(subcommand, DenoSubcommand::Run);
+    assert_eq!(argv, svec!["deno", "script.ts"]);
+  }
+
+  #[test]
+  fn test_flags_from_vec_42() {
+    let (flags, subcommand, argv) =
+      flags_from_vec(svec!["deno", "run", "--log-permissions", "script.ts"]);
+    assert_eq!(
+      flags,
+      DenoFlags {
+        log_permissions: Some("-".to_owned()),
+        ..DenoFlags::default()
+      }
+    );
+    assert_eq!(subcommand, DenoSubcommand::Run);
+    assert_eq!(argv, svec!["deno", "script.ts"]);
+
+    let (flags, _, _) =
+      flags_from_vec(svec!["deno", "--log-permissions=audit.log", "script.ts"]);
+    assert_eq!(
+      flags,
+      DenoFlags {
+        log_permissions: Some("audit.log".to_owned()),
+        ..DenoFlags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn test_flags_from_vec_43() {
+    let (flags, subcommand, argv) = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--permissions=deno-permissions.json",
+      "script.ts"
+    ]);
+    assert_eq!(
+      flags,
+      DenoFlags {
+        permissions_file: Some("deno-permissions.json".to_owned()),
+        ..DenoFlags::default()
+      }
+    );
+    assert_eq!(subcommand, DenoSubcommand::Run);
+    assert_eq!(argv, svec!["deno", "script.ts"]);
+  }
+
+  #[test]
+  fn test_flags_from_vec_44() {
+    let (flags, subcommand, argv) = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--unsafely-ignore-certificate-errors",
+      "script.ts"
+    ]);
+    assert_eq!(
+      flags,
+      DenoFlags {
+        unsafely_ignore_certificate_errors: Some(vec![]),
+        ..DenoFlags::default()
+      }
+    );
+    assert_eq!(subcommand, DenoSubcommand::Run);
+    assert_eq!(argv, svec!["deno", "script.ts"]);
+
+    let (flags, _, _) = flags_from_vec(svec![
+      "deno",
+      "--unsafely-ignore-certificate-errors=localhost,127.0.0.1",
+      "script.ts"
+    ]);
+    assert_eq!(
+      flags,
+      DenoFlags {
+        unsafely_ignore_certificate_errors: Some(svec![
+          "localhost",
+          "127.0.0.1"
+        ]),
+        ..DenoFlags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn test_flags_from_vec_45() {
+    let (flags, subcommand, argv) = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--allow-env=HOME,PATH",
+      "script.ts"
+    ]);
+    assert_eq!(
+      flags,
+      DenoFlags {
+        env_whitelist: svec!["HOME", "PATH"],
+        ..DenoFlags::default()
+      }
+    );
+    assert_eq!(subcommand, DenoSubcommand::Run);
+    assert_eq!(argv, svec!["deno", "script.ts"]);
+  }
+
+  #[test]
+  fn test_flags_from_vec_46() {
+    let (flags, subcommand, argv) = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--trace-ops=trace.json",
+      "script.ts"
+    ]);
+    assert_eq!(
+      flags,
+      DenoFlags {
+        trace_ops: Some("trace.json".to_owned()),
+        ..DenoFlags::default()
+      }
+    );
+    assert_eq!(subcommand, DenoSubcommand::Run);
+    assert_eq!(argv, svec!["deno", "script.ts"]);
+  }
+
+  #[test]
+  fn test_flags_from_vec_47() {
+    let (flags, subcommand, argv) =
+      flags_from_vec(svec!["deno", "run", "--inspect", "script.ts"]);
+    assert_eq!(
+      flags,
+      DenoFlags {
+        inspect: Some("127.0.0.1:9229".parse().unwrap()),
+        ..DenoFlags::default()
+      }
+    );
+    assert_eq!(subcommand, DenoSubcommand::Run);
+    assert_eq!(argv, svec!["deno", "script.ts"]);
+  }
+
+  #[test]
+  fn test_flags_from_vec_48() {
+    let (flags, subcommand, argv) = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--inspect-brk=0.0.0.0:9230",
+      "script.ts"
+    ]);
+    assert_eq!(
+      flags,
+      DenoFlags {
+        inspect: Some("0.0.0.0:9230".parse().unwrap()),
+        inspect_brk: true,
+        ..DenoFlags::default()
+      }
+    );
+    assert_eq!(subcommand, DenoSubcommand::Run);
+    assert_eq!(argv, svec!["deno", "script.ts"]);
+  }
+
+  #[test]
+  fn test_flags_from_vec_49() {
+    let (flags, subcommand, argv) = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--unhandled-rejections=warn",
+      "script.ts"
+    ]);
+    assert_eq!(
+      flags,
+      DenoFlags {
+        unhandled_rejections: UnhandledRejections::Warn,
+        ..DenoFlags::default()
+      }
+    );
+    assert_eq!(subcommand, DenoSubcommand::Run);
+    assert_eq!(argv, svec!["deno", "script.ts"]);
+  }
+
+  #[test]
+  fn test_flags_from_vec_50() {
+    let (flags, subcommand, argv) =
+      flags_from_vec(svec!["deno", "run", "--allow-plugin", "script.ts"]);
+    assert_eq!(
+      flags,
+      DenoFlags {
+        allow_plugin: true,
+        ..DenoFlags::default()
+      }
+    );
+    assert_eq!(subcommand, DenoSubcommand::Run);
+    assert_eq!(argv, svec!["deno", "script.ts"]);
+  }
+
+  #[test]
+  fn test_flags_from_vec_51() {
+    let (flags, subcommand, argv) = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--seed=250",
+      "--unsafely-seed-crypto",
+      "script.ts"
+    ]);
+    assert_eq!(
+      flags,
+      DenoFlags {
+        seed: Some(250 as u64),
+        unsafely_seed_crypto: true,
+        v8_flags: Some(svec!["deno", "--random-seed=250"]),
+        ..DenoFlags::default()
+      }
+    );
+    assert_eq!(subcommand, DenoSubcommand::Run);
+    assert_eq!(argv, svec!["deno", "script.ts"]);
+  }
 }
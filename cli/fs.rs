@@ -1,6 +1,6 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
 use std;
-use std::fs::{create_dir, DirBuilder, File, OpenOptions};
+use std::fs::{DirBuilder, File, OpenOptions};
 use std::io::ErrorKind;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -15,6 +15,8 @@ use nix::unistd::{chown as unix_chown, Gid, Uid};
 #[cfg(any(unix))]
 use std::os::unix::fs::DirBuilderExt;
 #[cfg(any(unix))]
+use std::os::unix::fs::OpenOptionsExt;
+#[cfg(any(unix))]
 use std::os::unix::fs::PermissionsExt;
 
 pub fn write_file<T: AsRef<[u8]>>(
@@ -59,10 +61,13 @@ fn set_permissions(_file: &mut File, _perm: u32) -> std::io::Result<()> {
   Ok(())
 }
 
-pub fn make_temp_dir(
+/// Atomically creates a uniquely named directory (mode 0o700) or file (mode
+/// 0o600) inside `dir`, or the system temp dir if `dir` is `None`.
+pub fn make_temp(
   dir: Option<&Path>,
   prefix: Option<&str>,
   suffix: Option<&str>,
+  is_dir: bool,
 ) -> std::io::Result<PathBuf> {
   let prefix_ = prefix.unwrap_or("");
   let suffix_ = suffix.unwrap_or("");
@@ -75,8 +80,17 @@ pub fn make_temp_dir(
   loop {
     let unique = rng.gen::<u32>();
     buf.set_file_name(format!("{}{:08x}{}", prefix_, unique, suffix_));
-    // TODO: on posix, set mode flags to 0o700.
-    let r = create_dir(buf.as_path());
+    let r = if is_dir {
+      let mut builder = DirBuilder::new();
+      set_dir_permission(&mut builder, 0o700);
+      builder.create(buf.as_path())
+    } else {
+      let mut open_options = OpenOptions::new();
+      open_options.write(true).create_new(true);
+      #[cfg(unix)]
+      open_options.mode(0o600);
+      open_options.open(buf.as_path()).map(|_| ())
+    };
     match r {
       Err(ref e) if e.kind() == ErrorKind::AlreadyExists => continue,
       Ok(_) => return Ok(buf),
@@ -85,6 +99,230 @@ pub fn make_temp_dir(
   }
 }
 
+/// Creates a temporary file in `dir` that has no name, so it disappears
+/// without a trace when it's closed or the process dies. On Linux this uses
+/// O_TMPFILE. Elsewhere, and on file systems that don't support it, a named
+/// file is created and immediately unlinked, or on Windows opened with
+/// FILE_FLAG_DELETE_ON_CLOSE.
+#[cfg(unix)]
+pub fn make_anonymous_temp(dir: &Path) -> std::io::Result<File> {
+  #[cfg(target_os = "linux")]
+  {
+    let r = OpenOptions::new()
+      .read(true)
+      .write(true)
+      .mode(0o600)
+      .custom_flags(libc::O_TMPFILE)
+      .open(dir);
+    match r {
+      Ok(file) => return Ok(file),
+      Err(ref e)
+        if e.raw_os_error() == Some(libc::EOPNOTSUPP)
+          || e.raw_os_error() == Some(libc::EISDIR) => {}
+      Err(e) => return Err(e),
+    }
+  }
+  let path = make_temp(Some(dir), Some(".tmp"), None, false)?;
+  let file = OpenOptions::new().read(true).write(true).open(&path)?;
+  std::fs::remove_file(&path)?;
+  Ok(file)
+}
+
+#[cfg(windows)]
+pub fn make_anonymous_temp(dir: &Path) -> std::io::Result<File> {
+  use std::os::windows::fs::OpenOptionsExt;
+  use winapi::um::winbase::FILE_FLAG_DELETE_ON_CLOSE;
+  use winapi::um::winnt::{DELETE, GENERIC_READ, GENERIC_WRITE};
+  use winapi::um::winnt::{FILE_SHARE_DELETE, FILE_SHARE_READ};
+
+  let path = make_temp(Some(dir), Some(".tmp"), None, false)?;
+  let r = OpenOptions::new()
+    .access_mode(GENERIC_READ | GENERIC_WRITE | DELETE)
+    .share_mode(FILE_SHARE_READ | FILE_SHARE_DELETE)
+    .custom_flags(FILE_FLAG_DELETE_ON_CLOSE)
+    .open(&path);
+  if r.is_err() {
+    let _ = std::fs::remove_file(&path);
+  }
+  r
+}
+
+/// Gives a file created by `make_anonymous_temp` the name `path`, atomically
+/// replacing anything already there. On Linux an O_TMPFILE file is linked
+/// into place. Otherwise, or when it's on another file system, the contents
+/// are copied into a new file next to `path` which is then renamed over it.
+pub fn link_anonymous_temp(file: &File, path: &Path) -> std::io::Result<()> {
+  let dir = match path.parent() {
+    Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+    _ => PathBuf::from("."),
+  };
+  let tmp = match link_fd(file, &dir)? {
+    Some(tmp) => tmp,
+    None => copy_to_temp(file, &dir)?,
+  };
+  std::fs::rename(&tmp, path).map_err(|e| {
+    let _ = std::fs::remove_file(&tmp);
+    e
+  })
+}
+
+/// Links an O_TMPFILE file to a fresh name in `dir`. Returns `None` if the
+/// file can't be linked because it wasn't created with O_TMPFILE.
+#[cfg(target_os = "linux")]
+fn link_fd(file: &File, dir: &Path) -> std::io::Result<Option<PathBuf>> {
+  use std::ffi::CString;
+  use std::os::unix::ffi::OsStrExt;
+  use std::os::unix::io::AsRawFd;
+
+  let fd_path = CString::new(format!("/proc/self/fd/{}", file.as_raw_fd()))?;
+  let mut rng = rand::thread_rng();
+  loop {
+    let tmp = dir.join(format!(".tmp{:08x}", rng.gen::<u32>()));
+    let c_tmp = CString::new(tmp.as_os_str().as_bytes())?;
+    let r = unsafe {
+      libc::linkat(
+        libc::AT_FDCWD,
+        fd_path.as_ptr(),
+        libc::AT_FDCWD,
+        c_tmp.as_ptr(),
+        libc::AT_SYMLINK_FOLLOW,
+      )
+    };
+    if r == 0 {
+      return Ok(Some(tmp));
+    }
+    let e = std::io::Error::last_os_error();
+    match e.raw_os_error() {
+      Some(libc::EEXIST) => continue,
+      // An unlinked regular file can't be given a name again, and no file
+      // can be linked across file systems.
+      Some(libc::ENOENT) | Some(libc::EXDEV) => return Ok(None),
+      _ => return Err(e),
+    }
+  }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn link_fd(_file: &File, _dir: &Path) -> std::io::Result<Option<PathBuf>> {
+  Ok(None)
+}
+
+fn copy_to_temp(file: &File, dir: &Path) -> std::io::Result<PathBuf> {
+  use std::io::{Seek, SeekFrom};
+
+  // `read_at` moves the cursor on Windows. It's put back afterwards, as the
+  // file stays open for the script to use.
+  let mut cursor = file;
+  let pos = cursor.seek(SeekFrom::Current(0))?;
+  let tmp = make_temp(Some(dir), Some(".tmp"), None, false)?;
+  let r = (|| {
+    let mut out = OpenOptions::new().write(true).open(&tmp)?;
+    let mut buf = vec![0; 64 * 1024];
+    let mut offset = 0;
+    loop {
+      let n = read_at(file, &mut buf, offset)?;
+      if n == 0 {
+        break;
+      }
+      out.write_all(&buf[..n])?;
+      offset += n as u64;
+    }
+    out.sync_all()
+  })();
+  let restored = cursor.seek(SeekFrom::Start(pos));
+  let r = r.and(restored.map(|_| ()));
+  match r {
+    Ok(()) => Ok(tmp),
+    Err(e) => {
+      let _ = std::fs::remove_file(&tmp);
+      Err(e)
+    }
+  }
+}
+
+/// Creates a FIFO at `path` with permissions `mode`, minus the umask, and
+/// opens it. Opening blocks until the other end is opened too, unless both
+/// `read` and `write` are set. If opening fails, the FIFO is removed again.
+#[cfg(unix)]
+pub fn create_pipe(
+  path: &Path,
+  mode: u32,
+  read: bool,
+  write: bool,
+) -> std::io::Result<File> {
+  use std::ffi::CString;
+  use std::os::unix::ffi::OsStrExt;
+
+  let c_path = CString::new(path.as_os_str().as_bytes())?;
+  if unsafe { libc::mkfifo(c_path.as_ptr(), mode as libc::mode_t) } != 0 {
+    return Err(std::io::Error::last_os_error());
+  }
+  OpenOptions::new()
+    .read(read)
+    .write(write)
+    .open(path)
+    .map_err(|e| {
+      let _ = std::fs::remove_file(path);
+      e
+    })
+}
+
+/// Creates the named pipe `path`, which must look like `\\.\pipe\name`, and
+/// waits for a client to connect to it. The pipe goes away once the
+/// returned handle is closed. `mode` is ignored.
+#[cfg(windows)]
+pub fn create_pipe(
+  path: &Path,
+  _mode: u32,
+  read: bool,
+  write: bool,
+) -> std::io::Result<File> {
+  use std::os::windows::ffi::OsStrExt;
+  use std::os::windows::io::FromRawHandle;
+  use winapi::shared::winerror::ERROR_PIPE_CONNECTED;
+  use winapi::um::handleapi::INVALID_HANDLE_VALUE;
+  use winapi::um::namedpipeapi::ConnectNamedPipe;
+  use winapi::um::winbase::{CreateNamedPipeW, FILE_FLAG_FIRST_PIPE_INSTANCE};
+  use winapi::um::winbase::{PIPE_ACCESS_DUPLEX, PIPE_ACCESS_INBOUND};
+  use winapi::um::winbase::{PIPE_ACCESS_OUTBOUND, PIPE_READMODE_BYTE};
+  use winapi::um::winbase::{PIPE_TYPE_BYTE, PIPE_WAIT};
+
+  let access = match (read, write) {
+    (true, true) => PIPE_ACCESS_DUPLEX,
+    (false, true) => PIPE_ACCESS_OUTBOUND,
+    _ => PIPE_ACCESS_INBOUND,
+  };
+  let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+  let handle = unsafe {
+    CreateNamedPipeW(
+      wide.as_ptr(),
+      access | FILE_FLAG_FIRST_PIPE_INSTANCE,
+      PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT,
+      1,
+      64 * 1024,
+      64 * 1024,
+      0,
+      std::ptr::null_mut(),
+    )
+  };
+  if handle == INVALID_HANDLE_VALUE {
+    return Err(std::io::Error::last_os_error());
+  }
+  // Owning the handle right away makes sure it's closed on error.
+  let file = unsafe { File::from_raw_handle(handle as _) };
+  if unsafe { ConnectNamedPipe(handle, std::ptr::null_mut()) } == 0 {
+    let e = std::io::Error::last_os_error();
+    // The client connected between creation and ConnectNamedPipe.
+    if e.raw_os_error() != Some(ERROR_PIPE_CONNECTED as i32) {
+      return Err(e);
+    }
+  }
+  Ok(file)
+}
+
+/// Creates `path`, and with `recursive` all of its missing parents as well, in
+/// one go. `perm` is applied to every directory that gets created, not only
+/// the last one. An existing directory is only an error if not `recursive`.
 pub fn mkdir(path: &Path, perm: u32, recursive: bool) -> std::io::Result<()> {
   debug!("mkdir -p {}", path.display());
   let mut builder = DirBuilder::new();
@@ -115,20 +353,760 @@ pub fn normalize_path(path: &Path) -> String {
 }
 
 #[cfg(unix)]
-pub fn chown(path: &str, uid: u32, gid: u32) -> Result<(), ErrBox> {
+pub fn chown(path: &Path, uid: u32, gid: u32) -> Result<(), ErrBox> {
   let nix_uid = Uid::from_raw(uid);
   let nix_gid = Gid::from_raw(gid);
   unix_chown(path, Option::Some(nix_uid), Option::Some(nix_gid))
     .map_err(ErrBox::from)
 }
 
+/// Like `chown`, but changes the owner of a symlink itself rather than the
+/// file it points to.
+#[cfg(unix)]
+pub fn lchown(path: &Path, uid: u32, gid: u32) -> Result<(), ErrBox> {
+  use std::ffi::CString;
+  use std::os::unix::ffi::OsStrExt;
+
+  let c_path =
+    CString::new(path.as_os_str().as_bytes()).map_err(std::io::Error::from)?;
+  if unsafe { libc::lchown(c_path.as_ptr(), uid, gid) } == 0 {
+    Ok(())
+  } else {
+    Err(std::io::Error::last_os_error().into())
+  }
+}
+
+// Windows has no notion of numeric uids and gids, ownership is expressed
+// through security descriptors instead.
+#[cfg(not(unix))]
+pub fn chown(_path: &Path, _uid: u32, _gid: u32) -> Result<(), ErrBox> {
+  Err(crate::deno_error::op_not_implemented())
+}
+
+#[cfg(not(unix))]
+pub fn lchown(_path: &Path, _uid: u32, _gid: u32) -> Result<(), ErrBox> {
+  Err(crate::deno_error::op_not_implemented())
+}
+
+/// Calls `f` with `path` and, if it's a directory, everything below it,
+/// parents before children. Symlinks found below `path` are passed to `f`
+/// themselves, or with `follow_symlinks` resolved and descended into. Link
+/// cycles are reported as errors.
+pub fn walk_tree<F>(
+  path: &Path,
+  follow_symlinks: bool,
+  mut f: F,
+) -> Result<(), ErrBox>
+where
+  F: FnMut(&Path, bool) -> Result<(), ErrBox>,
+{
+  for entry in walkdir::WalkDir::new(path).follow_links(follow_symlinks) {
+    let entry = entry.map_err(std::io::Error::from)?;
+    let is_symlink = entry.depth() > 0 && entry.path_is_symlink();
+    f(entry.path(), is_symlink && !follow_symlinks)?;
+  }
+  Ok(())
+}
+
+/// Changes the owner of an already open file.
+#[cfg(unix)]
+pub fn fchown(file: &File, uid: u32, gid: u32) -> Result<(), ErrBox> {
+  use std::os::unix::io::AsRawFd;
+  if unsafe { libc::fchown(file.as_raw_fd(), uid, gid) } == 0 {
+    Ok(())
+  } else {
+    Err(std::io::Error::last_os_error().into())
+  }
+}
+
+#[cfg(not(unix))]
+pub fn fchown(_file: &File, _uid: u32, _gid: u32) -> Result<(), ErrBox> {
+  Err(crate::deno_error::op_not_implemented())
+}
+
+/// Returns an error unless `file` was opened for writing. Ops that change
+/// metadata through a file descriptor use this in place of a path based write
+/// permission check, since Unix would otherwise let them modify a file that
+/// was only opened for reading.
+#[cfg(unix)]
+pub fn ensure_opened_for_write(file: &File) -> std::io::Result<()> {
+  use std::os::unix::io::AsRawFd;
+  let flags = unsafe { libc::fcntl(file.as_raw_fd(), libc::F_GETFL) };
+  if flags == -1 {
+    return Err(std::io::Error::last_os_error());
+  }
+  if flags & libc::O_ACCMODE == libc::O_RDONLY {
+    return Err(std::io::Error::new(
+      ErrorKind::PermissionDenied,
+      "file was not opened for writing",
+    ));
+  }
+  Ok(())
+}
+
+// On Windows the access rights of the handle are enforced by the system
+// itself, a read-only handle can't be used to change attributes.
+#[cfg(not(unix))]
+pub fn ensure_opened_for_write(_file: &File) -> std::io::Result<()> {
+  Ok(())
+}
+
+/// Space on the file system containing a path, in bytes. `available` is what
+/// an unprivileged user may use, which is less than `free` when the file
+/// system reserves blocks for root.
+pub struct FsUsage {
+  pub total: u64,
+  pub free: u64,
+  pub available: u64,
+}
+
+#[cfg(unix)]
+pub fn statfs(path: &Path) -> std::io::Result<FsUsage> {
+  use std::ffi::CString;
+  use std::os::unix::ffi::OsStrExt;
+
+  let c_path = CString::new(path.as_os_str().as_bytes())?;
+  let mut buf: libc::statvfs = unsafe { std::mem::zeroed() };
+  if unsafe { libc::statvfs(c_path.as_ptr(), &mut buf) } != 0 {
+    return Err(std::io::Error::last_os_error());
+  }
+  let frsize = buf.f_frsize as u64;
+  Ok(FsUsage {
+    total: buf.f_blocks as u64 * frsize,
+    free: buf.f_bfree as u64 * frsize,
+    available: buf.f_bavail as u64 * frsize,
+  })
+}
+
+#[cfg(windows)]
+pub fn statfs(path: &Path) -> std::io::Result<FsUsage> {
+  use std::os::windows::ffi::OsStrExt;
+  use winapi::um::fileapi::GetDiskFreeSpaceExW;
+  use winapi::um::winnt::ULARGE_INTEGER;
+
+  // GetDiskFreeSpaceExW only accepts directories.
+  let dir = if std::fs::metadata(path)?.is_dir() {
+    path
+  } else {
+    path.parent().unwrap_or(path)
+  };
+  let wide: Vec<u16> = dir.as_os_str().encode_wide().chain(Some(0)).collect();
+  let mut available: ULARGE_INTEGER = unsafe { std::mem::zeroed() };
+  let mut total: ULARGE_INTEGER = unsafe { std::mem::zeroed() };
+  let mut free: ULARGE_INTEGER = unsafe { std::mem::zeroed() };
+  let r = unsafe {
+    GetDiskFreeSpaceExW(wide.as_ptr(), &mut available, &mut total, &mut free)
+  };
+  if r == 0 {
+    return Err(std::io::Error::last_os_error());
+  }
+  unsafe {
+    Ok(FsUsage {
+      total: *total.QuadPart(),
+      free: *free.QuadPart(),
+      available: *available.QuadPart(),
+    })
+  }
+}
+
+/// Reads from `file` at `offset` without using or moving its cursor, so one
+/// file can be read from several places at once.
+#[cfg(unix)]
+pub fn read_at(
+  file: &File,
+  buf: &mut [u8],
+  offset: u64,
+) -> std::io::Result<usize> {
+  use std::os::unix::fs::FileExt;
+  file.read_at(buf, offset)
+}
+
+/// Windows has no positional read that leaves the cursor alone on a
+/// synchronous handle, so there it ends up after the bytes read.
+#[cfg(windows)]
+pub fn read_at(
+  file: &File,
+  buf: &mut [u8],
+  offset: u64,
+) -> std::io::Result<usize> {
+  use std::os::windows::fs::FileExt;
+  file.seek_read(buf, offset)
+}
+
+/// Writes to `file` at `offset` without using or moving its cursor. Like
+/// pwrite(2), this ignores the offset and appends on Linux when the file was
+/// opened in append mode.
+#[cfg(unix)]
+pub fn write_at(
+  file: &File,
+  buf: &[u8],
+  offset: u64,
+) -> std::io::Result<usize> {
+  use std::os::unix::fs::FileExt;
+  file.write_at(buf, offset)
+}
+
+/// Windows has no positional write that leaves the cursor alone on a
+/// synchronous handle, so there it ends up after the bytes written.
+#[cfg(windows)]
+pub fn write_at(
+  file: &File,
+  buf: &[u8],
+  offset: u64,
+) -> std::io::Result<usize> {
+  use std::os::windows::fs::FileExt;
+  file.seek_write(buf, offset)
+}
+
+/// The parts of a file's status that `std::fs::Metadata` doesn't expose on
+/// every platform. Fields without an equivalent on the current platform are
+/// `None`.
+#[derive(Default)]
+pub struct StatExt {
+  pub dev: Option<u64>,
+  pub ino: Option<u64>,
+  pub nlink: Option<u64>,
+  pub uid: Option<u32>,
+  pub gid: Option<u32>,
+  pub rdev: Option<u64>,
+  pub blksize: Option<u64>,
+  pub blocks: Option<u64>,
+}
+
+#[cfg(unix)]
+pub fn stat_ext(metadata: &std::fs::Metadata) -> StatExt {
+  use std::os::unix::fs::MetadataExt;
+
+  StatExt {
+    dev: Some(metadata.dev()),
+    ino: Some(metadata.ino()),
+    nlink: Some(metadata.nlink()),
+    uid: Some(metadata.uid()),
+    gid: Some(metadata.gid()),
+    rdev: Some(metadata.rdev()),
+    blksize: Some(metadata.blksize()),
+    blocks: Some(metadata.blocks()),
+  }
+}
+
+/// Windows only reports the volume and file id through an open handle, so
+/// they're filled in by `fstat_ext` and `path_stat_ext` instead.
+#[cfg(not(unix))]
+pub fn stat_ext(_metadata: &std::fs::Metadata) -> StatExt {
+  StatExt::default()
+}
+
+#[cfg(not(windows))]
+pub fn fstat_ext(
+  _file: &File,
+  metadata: &std::fs::Metadata,
+) -> std::io::Result<StatExt> {
+  Ok(stat_ext(metadata))
+}
+
+#[cfg(windows)]
+pub fn fstat_ext(
+  file: &File,
+  _metadata: &std::fs::Metadata,
+) -> std::io::Result<StatExt> {
+  use std::os::windows::io::AsRawHandle;
+  use winapi::um::fileapi::GetFileInformationByHandle;
+  use winapi::um::fileapi::BY_HANDLE_FILE_INFORMATION;
+
+  let mut info: BY_HANDLE_FILE_INFORMATION = unsafe { std::mem::zeroed() };
+  let r =
+    unsafe { GetFileInformationByHandle(file.as_raw_handle() as _, &mut info) };
+  if r == 0 {
+    return Err(std::io::Error::last_os_error());
+  }
+  Ok(StatExt {
+    dev: Some(u64::from(info.dwVolumeSerialNumber)),
+    ino: Some(
+      u64::from(info.nFileIndexHigh) << 32 | u64::from(info.nFileIndexLow),
+    ),
+    nlink: Some(u64::from(info.nNumberOfLinks)),
+    ..Default::default()
+  })
+}
+
+#[cfg(not(windows))]
+pub fn path_stat_ext(
+  _path: &Path,
+  metadata: &std::fs::Metadata,
+  _follow_symlinks: bool,
+) -> StatExt {
+  stat_ext(metadata)
+}
+
+/// Opens `path` without any access rights just to query it. Files that can't
+/// be opened at all, like the page file, get no extended status rather than
+/// an error, since their metadata could be read.
+#[cfg(windows)]
+pub fn path_stat_ext(
+  path: &Path,
+  metadata: &std::fs::Metadata,
+  follow_symlinks: bool,
+) -> StatExt {
+  use std::os::windows::fs::OpenOptionsExt;
+  use winapi::um::winbase::FILE_FLAG_BACKUP_SEMANTICS;
+  use winapi::um::winbase::FILE_FLAG_OPEN_REPARSE_POINT;
+
+  // Directories can only be opened with FILE_FLAG_BACKUP_SEMANTICS.
+  let mut flags = FILE_FLAG_BACKUP_SEMANTICS;
+  if !follow_symlinks {
+    flags |= FILE_FLAG_OPEN_REPARSE_POINT;
+  }
+  OpenOptions::new()
+    .access_mode(0)
+    .custom_flags(flags)
+    .open(path)
+    .and_then(|file| fstat_ext(&file, metadata))
+    .unwrap_or_default()
+}
+
+/// Sets the process umask to `mask` and returns the previous value. With no
+/// `mask` the umask is only read. Linux reports it in /proc/self/status.
+/// Elsewhere, and on kernels older than 4.7, POSIX has no way to query it
+/// without changing it, so it's briefly set to 0 and restored. Files created
+/// by other threads in that window get permissions as if there was no umask.
+#[cfg(unix)]
+pub fn umask(mask: Option<u32>) -> Result<u32, ErrBox> {
+  use libc::mode_t;
+
+  let previous = match mask {
+    Some(mask) => unsafe { libc::umask(mask as mode_t) },
+    None => {
+      #[cfg(target_os = "linux")]
+      {
+        if let Some(mask) = read_proc_umask() {
+          return Ok(mask);
+        }
+      }
+      unsafe {
+        let previous = libc::umask(0);
+        libc::umask(previous);
+        previous
+      }
+    }
+  };
+  Ok(previous as u32)
+}
+
+#[cfg(target_os = "linux")]
+fn read_proc_umask() -> Option<u32> {
+  let status = std::fs::read_to_string("/proc/self/status").ok()?;
+  let line = status.lines().find(|line| line.starts_with("Umask:"))?;
+  u32::from_str_radix(line["Umask:".len()..].trim(), 8).ok()
+}
+
 #[cfg(not(unix))]
-pub fn chown(_path: &str, _uid: u32, _gid: u32) -> Result<(), ErrBox> {
-  // Noop
-  // TODO: implement chown for Windows
+pub fn umask(_mask: Option<u32>) -> Result<u32, ErrBox> {
   Err(crate::deno_error::op_not_implemented())
 }
 
+/// Removes `path`. Directories are only removed when empty, unless
+/// `recursive` is set, in which case the whole tree below is removed first.
+/// Symlinks are never followed: the link itself is removed, not its target.
+pub fn remove(path: &Path, recursive: bool) -> std::io::Result<()> {
+  let metadata = std::fs::symlink_metadata(path)?;
+  if !metadata.is_dir() {
+    return remove_non_dir(path, &metadata);
+  }
+  if !recursive {
+    return std::fs::remove_dir(path);
+  }
+
+  // Walk the tree iteratively so that deeply nested directories can't
+  // overflow the stack. Each directory is pushed twice: once to be expanded
+  // and once more, beneath its children, to be removed after they're gone.
+  let mut stack = vec![(path.to_path_buf(), false)];
+  while let Some((dir, expanded)) = stack.pop() {
+    if expanded {
+      std::fs::remove_dir(&dir)?;
+      continue;
+    }
+    stack.push((dir.clone(), true));
+    for entry in std::fs::read_dir(&dir)? {
+      let entry = entry?;
+      let entry_path = entry.path();
+      let metadata = std::fs::symlink_metadata(&entry_path)?;
+      if metadata.is_dir() {
+        stack.push((entry_path, false));
+      } else {
+        remove_non_dir(&entry_path, &metadata)?;
+      }
+    }
+  }
+  Ok(())
+}
+
+#[cfg(unix)]
+fn remove_non_dir(
+  path: &Path,
+  _metadata: &std::fs::Metadata,
+) -> std::io::Result<()> {
+  std::fs::remove_file(path)
+}
+
+#[cfg(windows)]
+fn remove_non_dir(
+  path: &Path,
+  metadata: &std::fs::Metadata,
+) -> std::io::Result<()> {
+  use std::os::windows::fs::MetadataExt;
+  use winapi::um::winnt::{FILE_ATTRIBUTE_DIRECTORY, FILE_ATTRIBUTE_READONLY};
+
+  let attributes = metadata.file_attributes();
+  // Symlinks and junctions that point at directories are directories
+  // themselves as far as Windows is concerned, and need RemoveDirectory.
+  if attributes & FILE_ATTRIBUTE_DIRECTORY != 0 {
+    return std::fs::remove_dir(path);
+  }
+  // DeleteFile refuses to remove read-only files.
+  if attributes & FILE_ATTRIBUTE_READONLY != 0 {
+    let mut permissions = metadata.permissions();
+    permissions.set_readonly(false);
+    std::fs::set_permissions(path, permissions)?;
+  }
+  std::fs::remove_file(path)
+}
+
+/// Copies the contents and permission bits of `from` to `to`, overwriting
+/// `to` if it exists. Where the file system supports it the data is cloned
+/// (reflinked) instead of copied, and otherwise the copy is done in the kernel
+/// without bouncing through user space.
+#[cfg(target_os = "linux")]
+pub fn copy_file(from: &Path, to: &Path) -> std::io::Result<u64> {
+  use std::os::unix::io::AsRawFd;
+
+  // From linux/fs.h: _IOW(0x94, 9, int)
+  const FICLONE: libc::c_ulong = 0x4004_9409;
+
+  let mut src = File::open(from)?;
+  let metadata = src.metadata()?;
+  let len = metadata.len();
+  let mut dst = OpenOptions::new()
+    .write(true)
+    .create(true)
+    .truncate(true)
+    .open(to)?;
+
+  let src_fd = src.as_raw_fd();
+  let dst_fd = dst.as_raw_fd();
+
+  if unsafe { libc::ioctl(dst_fd, FICLONE as _, src_fd) } != 0 {
+    let mut copied = 0u64;
+    while copied < len {
+      let n = unsafe {
+        libc::syscall(
+          libc::SYS_copy_file_range,
+          src_fd,
+          std::ptr::null_mut::<libc::loff_t>(),
+          dst_fd,
+          std::ptr::null_mut::<libc::loff_t>(),
+          (len - copied) as usize,
+          0,
+        )
+      };
+      if n < 0 {
+        let err = std::io::Error::last_os_error();
+        match err.raw_os_error() {
+          // Old kernel, cross-device copy or a file system that doesn't
+          // support it. Nothing has been written yet, so fall back to a
+          // plain read/write loop.
+          Some(libc::ENOSYS) | Some(libc::EXDEV) | Some(libc::EINVAL)
+          | Some(libc::EPERM)
+            if copied == 0 =>
+          {
+            std::io::copy(&mut src, &mut dst)?;
+            break;
+          }
+          _ => return Err(err),
+        }
+      } else if n == 0 {
+        break;
+      }
+      copied += n as u64;
+    }
+  }
+
+  dst.set_permissions(metadata.permissions())?;
+  Ok(len)
+}
+
+#[cfg(target_os = "macos")]
+pub fn copy_file(from: &Path, to: &Path) -> std::io::Result<u64> {
+  use std::ffi::CString;
+  use std::os::unix::ffi::OsStrExt;
+
+  extern "C" {
+    // From <sys/clonefile.h>, available since macOS 10.12.
+    fn clonefile(
+      src: *const libc::c_char,
+      dst: *const libc::c_char,
+      flags: u32,
+    ) -> libc::c_int;
+  }
+
+  // clonefile(2) refuses to overwrite, and keeps permissions by itself. When
+  // it can't be used std::fs::copy goes through fcopyfile(3) which does the
+  // same for regular copies.
+  if !to.exists() {
+    let src = CString::new(from.as_os_str().as_bytes())?;
+    let dst = CString::new(to.as_os_str().as_bytes())?;
+    if unsafe { clonefile(src.as_ptr(), dst.as_ptr(), 0) } == 0 {
+      return Ok(std::fs::metadata(to)?.len());
+    }
+  }
+  std::fs::copy(from, to)
+}
+
+/// On Windows std::fs::copy is implemented on top of CopyFileExW, which
+/// already clones blocks on ReFS and preserves file attributes.
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn copy_file(from: &Path, to: &Path) -> std::io::Result<u64> {
+  std::fs::copy(from, to)
+}
+
+/// Canonicalizes `path`, resolving every symlink along the way. Unlike
+/// `std::fs::canonicalize` this doesn't return verbatim (`\\?\`) paths on
+/// Windows, so the result can be compared against user supplied paths.
+pub fn realpath(path: &Path) -> std::io::Result<PathBuf> {
+  let canonical = std::fs::canonicalize(path)?;
+  #[cfg(windows)]
+  let canonical = strip_verbatim(canonical);
+  Ok(canonical)
+}
+
+/// Turns `\\?\UNC\server\share` into `\\server\share` and `\\?\C:\` into `C:\`.
+/// It works on the wide string, so paths which aren't valid Unicode survive.
+#[cfg(windows)]
+fn strip_verbatim(path: PathBuf) -> PathBuf {
+  use std::ffi::OsString;
+  use std::os::windows::ffi::{OsStrExt, OsStringExt};
+
+  let wide: Vec<u16> = path.as_os_str().encode_wide().collect();
+  let unc: Vec<u16> = r"\\?\UNC\".encode_utf16().collect();
+  let verbatim: Vec<u16> = r"\\?\".encode_utf16().collect();
+  if wide.starts_with(&unc) {
+    let mut stripped: Vec<u16> = r"\\".encode_utf16().collect();
+    stripped.extend_from_slice(&wide[unc.len()..]);
+    PathBuf::from(OsString::from_wide(&stripped))
+  } else if wide.starts_with(&verbatim) {
+    PathBuf::from(OsString::from_wide(&wide[verbatim.len()..]))
+  } else {
+    path
+  }
+}
+
+/// Windows needs to know up front whether a symlink points at a file or a
+/// directory. If `link_type` isn't given it is inferred from `target`, which
+/// therefore has to exist.
+#[cfg(windows)]
+pub fn symlink_windows(
+  target: &Path,
+  link: &Path,
+  link_type: Option<&String>,
+) -> Result<(), ErrBox> {
+  use crate::deno_error::{DenoError, ErrorKind as DenoErrorKind};
+  use std::os::windows::fs::{symlink_dir, symlink_file};
+
+  // See https://docs.microsoft.com/en-us/windows/win32/debug/system-error-codes--1300-1699-
+  const ERROR_PRIVILEGE_NOT_HELD: i32 = 1314;
+
+  let is_dir = match link_type.map(String::as_str) {
+    Some("dir") => true,
+    Some("file") => false,
+    Some(t) => {
+      return Err(
+        DenoError::new(
+          DenoErrorKind::InvalidInput,
+          format!("Invalid symlink type: {}", t),
+        )
+        .into(),
+      )
+    }
+    None => std::fs::metadata(target)?.is_dir(),
+  };
+
+  let result = if is_dir {
+    symlink_dir(target, link)
+  } else {
+    symlink_file(target, link)
+  };
+
+  match result {
+    Err(ref e) if e.raw_os_error() == Some(ERROR_PRIVILEGE_NOT_HELD) => Err(
+      DenoError::new(
+        DenoErrorKind::PermissionDenied,
+        "Creating symlinks on Windows requires Developer Mode or the \
+         SeCreateSymbolicLinkPrivilege privilege"
+          .to_string(),
+      )
+      .into(),
+    ),
+    r => r.map_err(ErrBox::from),
+  }
+}
+
+/// Sets the access and modification times, in seconds since the Unix epoch, of
+/// an already open file.
+#[cfg(unix)]
+pub fn futime(file: &File, atime: u64, mtime: u64) -> std::io::Result<()> {
+  use std::os::unix::io::AsRawFd;
+  let times = [
+    libc::timeval {
+      tv_sec: atime as libc::time_t,
+      tv_usec: 0,
+    },
+    libc::timeval {
+      tv_sec: mtime as libc::time_t,
+      tv_usec: 0,
+    },
+  ];
+  let r = unsafe { libc::futimes(file.as_raw_fd(), times.as_ptr()) };
+  if r == 0 {
+    Ok(())
+  } else {
+    Err(std::io::Error::last_os_error())
+  }
+}
+
+#[cfg(windows)]
+pub fn futime(file: &File, atime: u64, mtime: u64) -> std::io::Result<()> {
+  use std::os::windows::io::AsRawHandle;
+  use winapi::shared::minwindef::FILETIME;
+  use winapi::um::fileapi::SetFileTime;
+
+  fn to_filetime(secs: u64) -> FILETIME {
+    // FILETIME counts 100ns intervals since 1601-01-01.
+    let intervals = secs * 10_000_000 + 116_444_736_000_000_000;
+    FILETIME {
+      dwLowDateTime: intervals as u32,
+      dwHighDateTime: (intervals >> 32) as u32,
+    }
+  }
+
+  let atime = to_filetime(atime);
+  let mtime = to_filetime(mtime);
+  let r = unsafe {
+    SetFileTime(file.as_raw_handle() as _, std::ptr::null(), &atime, &mtime)
+  };
+  if r != 0 {
+    Ok(())
+  } else {
+    Err(std::io::Error::last_os_error())
+  }
+}
+
+/// Places an advisory lock on the whole of `file`. Unless `nonblocking` is
+/// set this waits for conflicting locks to be released. Returns whether the
+/// lock was acquired, which is always the case for blocking calls.
+#[cfg(unix)]
+pub fn flock(
+  file: &File,
+  exclusive: bool,
+  nonblocking: bool,
+) -> std::io::Result<bool> {
+  use std::os::unix::io::AsRawFd;
+  let mut operation = if exclusive {
+    libc::LOCK_EX
+  } else {
+    libc::LOCK_SH
+  };
+  if nonblocking {
+    operation |= libc::LOCK_NB;
+  }
+  if unsafe { libc::flock(file.as_raw_fd(), operation) } == 0 {
+    return Ok(true);
+  }
+  let err = std::io::Error::last_os_error();
+  if nonblocking && err.kind() == ErrorKind::WouldBlock {
+    Ok(false)
+  } else {
+    Err(err)
+  }
+}
+
+#[cfg(unix)]
+pub fn funlock(file: &File) -> std::io::Result<()> {
+  use std::os::unix::io::AsRawFd;
+  if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_UN) } == 0 {
+    Ok(())
+  } else {
+    Err(std::io::Error::last_os_error())
+  }
+}
+
+#[cfg(windows)]
+pub fn flock(
+  file: &File,
+  exclusive: bool,
+  nonblocking: bool,
+) -> std::io::Result<bool> {
+  use std::os::windows::io::AsRawHandle;
+  use winapi::shared::minwindef::DWORD;
+  use winapi::shared::winerror::ERROR_LOCK_VIOLATION;
+  use winapi::um::fileapi::LockFileEx;
+  use winapi::um::minwinbase::{
+    LOCKFILE_EXCLUSIVE_LOCK, LOCKFILE_FAIL_IMMEDIATELY, OVERLAPPED,
+  };
+
+  let mut flags = 0;
+  if exclusive {
+    flags |= LOCKFILE_EXCLUSIVE_LOCK;
+  }
+  if nonblocking {
+    flags |= LOCKFILE_FAIL_IMMEDIATELY;
+  }
+  // Lock the largest possible range, which amounts to the whole file no
+  // matter how much it grows.
+  let r = unsafe {
+    let mut overlapped: OVERLAPPED = std::mem::zeroed();
+    LockFileEx(
+      file.as_raw_handle() as _,
+      flags,
+      0,
+      DWORD::max_value(),
+      DWORD::max_value(),
+      &mut overlapped,
+    )
+  };
+  if r != 0 {
+    return Ok(true);
+  }
+  let err = std::io::Error::last_os_error();
+  if nonblocking && err.raw_os_error() == Some(ERROR_LOCK_VIOLATION as i32) {
+    Ok(false)
+  } else {
+    Err(err)
+  }
+}
+
+#[cfg(windows)]
+pub fn funlock(file: &File) -> std::io::Result<()> {
+  use std::os::windows::io::AsRawHandle;
+  use winapi::shared::minwindef::DWORD;
+  use winapi::um::fileapi::UnlockFileEx;
+  use winapi::um::minwinbase::OVERLAPPED;
+
+  let r = unsafe {
+    let mut overlapped: OVERLAPPED = std::mem::zeroed();
+    UnlockFileEx(
+      file.as_raw_handle() as _,
+      0,
+      DWORD::max_value(),
+      DWORD::max_value(),
+      &mut overlapped,
+    )
+  };
+  if r != 0 {
+    Ok(())
+  } else {
+    Err(std::io::Error::last_os_error())
+  }
+}
+
 pub fn resolve_from_cwd(path: &str) -> Result<(PathBuf, String), ErrBox> {
   let candidate_path = Path::new(path);
 
@@ -189,4 +1167,39 @@ mod tests {
     let expected = Path::new("/a");
     assert_eq!(resolve_from_cwd("/a").unwrap().0, expected);
   }
+
+  #[cfg(windows)]
+  #[test]
+  fn strip_verbatim_paths() {
+    use std::ffi::OsString;
+    use std::os::windows::ffi::OsStringExt;
+
+    assert_eq!(
+      strip_verbatim(PathBuf::from(r"\\?\C:\a")),
+      PathBuf::from(r"C:\a")
+    );
+    assert_eq!(
+      strip_verbatim(PathBuf::from(r"\\?\UNC\server\share")),
+      PathBuf::from(r"\\server\share")
+    );
+    // An unpaired surrogate, which isn't valid Unicode.
+    let mut wide: Vec<u16> = r"\\?\C:\".encode_utf16().collect();
+    wide.push(0xD800);
+    let mut expected: Vec<u16> = r"C:\".encode_utf16().collect();
+    expected.push(0xD800);
+    assert_eq!(
+      strip_verbatim(PathBuf::from(OsString::from_wide(&wide))),
+      PathBuf::from(OsString::from_wide(&expected))
+    );
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn create_pipe_removed_on_open_error() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("fifo");
+    // Opening with neither read nor write access fails.
+    assert!(create_pipe(&path, 0o600, false, false).is_err());
+    assert!(std::fs::symlink_metadata(&path).is_err());
+  }
 }
@@ -14,8 +14,9 @@ use reqwest::RedirectPolicy;
 use url::Url;
 
 /// Create new instance of async reqwest::Client. This client supports
-/// proxies and doesn't follow redirects.
-pub fn get_client() -> Client {
+/// proxies and doesn't follow redirects. With `accept_invalid_certs`, it
+/// doesn't verify TLS certificates either.
+pub fn get_client(accept_invalid_certs: bool) -> Client {
   let mut headers = HeaderMap::new();
   headers.insert(
     USER_AGENT,
@@ -25,10 +26,24 @@ pub fn get_client() -> Client {
     .redirect(RedirectPolicy::none())
     .default_headers(headers)
     .use_sys_proxy()
+    .danger_accept_invalid_certs(accept_invalid_certs)
     .build()
     .unwrap()
 }
 
+/// Returns whether TLS certificate errors are ignored for `host`, given the
+/// hosts passed to --unsafely-ignore-certificate-errors. An empty list
+/// stands for all hosts.
+pub fn ignores_certificate_errors(
+  hosts: &Option<Vec<String>>,
+  host: &str,
+) -> bool {
+  match hosts {
+    Some(hosts) => hosts.is_empty() || hosts.iter().any(|h| h == host),
+    None => false,
+  }
+}
+
 /// Construct the next uri based on base uri and location header fragment
 /// See <https://tools.ietf.org/html/rfc3986#section-4.2>
 fn resolve_url_from_location(base_url: &Url, location: &str) -> Url {
@@ -70,11 +85,12 @@ pub enum FetchOnceResult {
 /// yields Redirect(url).
 pub fn fetch_string_once(
   url: &Url,
+  accept_invalid_certs: bool,
 ) -> impl Future<Item = FetchOnceResult, Error = ErrBox> {
   type FetchAttempt = (Option<String>, Option<String>, Option<FetchOnceResult>);
 
   let url = url.clone();
-  let client = get_client();
+  let client = get_client(accept_invalid_certs);
 
   client
     .get(url.clone())
@@ -145,7 +161,7 @@ mod tests {
     // Relies on external http server. See tools/http_server.py
     let url = Url::parse("http://127.0.0.1:4545/package.json").unwrap();
 
-    let fut = fetch_string_once(&url).then(|result| match result {
+    let fut = fetch_string_once(&url, false).then(|result| match result {
       Ok(FetchOnceResult::Code(code, maybe_content_type)) => {
         assert!(!code.is_empty());
         assert_eq!(maybe_content_type, Some("application/json".to_string()));
@@ -165,7 +181,7 @@ mod tests {
     let url = Url::parse("http://127.0.0.1:4546/package.json").unwrap();
     // Dns resolver substitutes `127.0.0.1` with `localhost`
     let target_url = Url::parse("http://localhost:4545/package.json").unwrap();
-    let fut = fetch_string_once(&url).then(move |result| match result {
+    let fut = fetch_string_once(&url, false).then(move |result| match result {
       Ok(FetchOnceResult::Redirect(url)) => {
         assert_eq!(url, target_url);
         Ok(())
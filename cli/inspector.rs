@@ -0,0 +1,386 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+//! The server of `--inspect`, through which the Chrome DevTools debug the main
+//! isolate. Like Node, it lists the debugging target as JSON over HTTP, and
+//! speaks the DevTools protocol over a WebSocket.
+use crate::version;
+use deno::ErrBox;
+use deno::InspectorHandle;
+use futures::future::{loop_fn, Either, Loop, Shared};
+use futures::stream;
+use futures::sync::mpsc;
+use futures::sync::oneshot;
+use futures::Future;
+use futures::Stream;
+use hyper::header::{
+  HeaderValue, CONNECTION, CONTENT_TYPE, HOST, SEC_WEBSOCKET_ACCEPT,
+  SEC_WEBSOCKET_KEY, UPGRADE,
+};
+use hyper::service::service_fn_ok;
+use hyper::upgrade::Upgraded;
+use hyper::{Body, Request, Response, Server, StatusCode};
+use serde_json::Value;
+use std::io;
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::thread;
+use tokio_io::io::{read_exact, write_all};
+use tokio_io::AsyncRead;
+
+const OPCODE_CONTINUATION: u8 = 0x0;
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xa;
+
+// The frontend's messages are small, unlike some of the inspector's.
+const MAX_FRAME_LEN: u64 = 16 * 1024 * 1024;
+
+#[derive(Clone)]
+struct Target {
+  id: String,
+  addr: SocketAddr,
+  url: String,
+  handle: InspectorHandle,
+  shutdown: Shared<oneshot::Receiver<()>>,
+}
+
+/// Serves the inspector until it is dropped, which also closes the sessions
+/// of the frontends.
+pub struct InspectorServer {
+  _shutdown: oneshot::Sender<()>,
+}
+
+impl Target {
+  fn websocket_url(&self) -> String {
+    format!("ws://{}/{}", self.addr, self.id)
+  }
+
+  fn json(&self) -> Value {
+    json!({
+      "description": "deno",
+      "devtoolsFrontendUrl": format!(
+        "chrome-devtools://devtools/bundled/js_app.html?experiments=true&v8only=true&ws={}/{}",
+        self.addr,
+        self.id
+      ),
+      "faviconUrl": "https://deno.land/favicon.ico",
+      "id": self.id,
+      "title": format!("deno[{}]", std::process::id()),
+      // The DevTools list Node's targets, which are V8 only too.
+      "type": "node",
+      "url": self.url,
+      "webSocketDebuggerUrl": self.websocket_url(),
+    })
+  }
+}
+
+/// Starts serving the inspector of the main isolate, which runs the module
+/// `url`, on `addr`.
+pub fn start(
+  addr: SocketAddr,
+  handle: InspectorHandle,
+  url: &str,
+) -> Result<InspectorServer, ErrBox> {
+  let (shutdown_tx, shutdown_rx) = oneshot::channel();
+  let target = Target {
+    id: new_id(),
+    addr,
+    url: url.to_string(),
+    handle,
+    shutdown: shutdown_rx.shared(),
+  };
+  let builder = Server::try_bind(&addr)?;
+  eprintln!("Debugger listening on {}", target.websocket_url());
+  eprintln!("Visit chrome://inspect to connect to the debugger.");
+
+  // Dropping the sender resolves the receiver too, with an error.
+  let shutdown = target.shutdown.clone().then(|_| Ok::<(), ()>(()));
+  let server = builder
+    .serve(move || {
+      let target = target.clone();
+      service_fn_ok(move |req| respond(&target, req))
+    })
+    .with_graceful_shutdown(shutdown)
+    .map_err(|err| eprintln!("Inspector server error: {}", err));
+  // The server, and the sessions it spawns, run on a thread of their own. The
+  // thread of the isolate blocks while JavaScript is paused, which may be the
+  // only thread of the runtime, e.g. given --current-thread.
+  thread::Builder::new()
+    .name("inspector".to_string())
+    .spawn(move || tokio::runtime::current_thread::run(server))?;
+  Ok(InspectorServer {
+    _shutdown: shutdown_tx,
+  })
+}
+
+/// A random UUID, which the DevTools use to tell targets apart.
+fn new_id() -> String {
+  let mut bytes: [u8; 16] = rand::random();
+  bytes[6] = (bytes[6] & 0x0f) | 0x40;
+  bytes[8] = (bytes[8] & 0x3f) | 0x80;
+  let hex: Vec<String> = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+  format!(
+    "{}-{}-{}-{}-{}",
+    hex[0..4].concat(),
+    hex[4..6].concat(),
+    hex[6..8].concat(),
+    hex[8..10].concat(),
+    hex[10..16].concat()
+  )
+}
+
+fn respond(target: &Target, req: Request<Body>) -> Response<Body> {
+  let host = req.headers().get(HOST).and_then(|host| host.to_str().ok());
+  if !host.map_or(false, is_allowed_host) {
+    return Response::builder()
+      .status(StatusCode::FORBIDDEN)
+      .body(Body::empty())
+      .unwrap();
+  }
+  let path = req.uri().path().to_string();
+  match path.as_str() {
+    "/json" | "/json/list" => json_response(json!([target.json()])),
+    "/json/version" => json_response(json!({
+      "Browser": format!("Deno/{}", version::DENO),
+      "Protocol-Version": "1.3",
+      "V8-Version": version::v8(),
+    })),
+    _ if path[1..] == target.id => upgrade(target, req),
+    _ => Response::builder()
+      .status(StatusCode::NOT_FOUND)
+      .body(Body::empty())
+      .unwrap(),
+  }
+}
+
+/// Whether the Host header names localhost or an IP address. Other names could
+/// be rebound by a web page to this server, letting it find and take over the
+/// debugging target.
+fn is_allowed_host(host: &str) -> bool {
+  if host.starts_with('[') {
+    return match host.find(']') {
+      Some(end) => host[1..end].parse::<Ipv6Addr>().is_ok(),
+      None => false,
+    };
+  }
+  let hostname = match host.rfind(':') {
+    Some(colon) => &host[..colon],
+    None => host,
+  };
+  hostname.eq_ignore_ascii_case("localhost")
+    || hostname.parse::<IpAddr>().is_ok()
+}
+
+fn json_response(value: Value) -> Response<Body> {
+  Response::builder()
+    .header(CONTENT_TYPE, "application/json; charset=UTF-8")
+    .body(Body::from(value.to_string()))
+    .unwrap()
+}
+
+/// Accepts the WebSocket handshake, then starts a session over the
+/// connection.
+fn upgrade(target: &Target, req: Request<Body>) -> Response<Body> {
+  let accept = match req.headers().get(SEC_WEBSOCKET_KEY) {
+    Some(key) => accept_key(key.as_bytes()),
+    None => {
+      return Response::builder()
+        .status(StatusCode::BAD_REQUEST)
+        .body(Body::empty())
+        .unwrap()
+    }
+  };
+  let handle = target.handle.clone();
+  let shutdown = target.shutdown.clone().then(|_| Ok(()));
+  let session = req
+    .into_body()
+    .on_upgrade()
+    .map_err(|err| eprintln!("Inspector connection error: {}", err))
+    .and_then(move |upgraded| serve_session(upgraded, &handle))
+    .select(shutdown)
+    .then(|_| Ok(()));
+  tokio::spawn(session);
+
+  Response::builder()
+    .status(StatusCode::SWITCHING_PROTOCOLS)
+    .header(UPGRADE, HeaderValue::from_static("websocket"))
+    .header(CONNECTION, HeaderValue::from_static("Upgrade"))
+    .header(SEC_WEBSOCKET_ACCEPT, accept)
+    .body(Body::empty())
+    .unwrap()
+}
+
+/// The Sec-WebSocket-Accept header which answers a Sec-WebSocket-Key one.
+fn accept_key(key: &[u8]) -> String {
+  const GUID: &[u8] = b"258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+  let mut ctx =
+    ring::digest::Context::new(&ring::digest::SHA1_FOR_LEGACY_USE_ONLY);
+  ctx.update(key);
+  ctx.update(GUID);
+  base64::encode(ctx.finish().as_ref())
+}
+
+/// Relays the messages between the frontend and the inspector until either
+/// of them closes the session.
+fn serve_session(
+  upgraded: Upgraded,
+  handle: &InspectorHandle,
+) -> impl Future<Item = (), Error = ()> {
+  let (sender, outgoing) = handle.connect();
+  let (reader, writer) = upgraded.split();
+  // Pongs and closing frames are written along with the inspector's messages.
+  let (control_tx, control_rx) = mpsc::unbounded::<Vec<u8>>();
+
+  let read = loop_fn(
+    (reader, sender, Vec::new()),
+    move |(reader, sender, mut message)| {
+      let control_tx = control_tx.clone();
+      read_frame(reader).map(move |(reader, frame)| {
+        match frame.opcode {
+          OPCODE_TEXT | OPCODE_CONTINUATION => {
+            message.extend_from_slice(&frame.payload);
+            if frame.fin {
+              sender.send(String::from_utf8_lossy(&message).into_owned());
+              message.clear();
+            }
+          }
+          OPCODE_PING => {
+            let pong = encode_frame(OPCODE_PONG, &frame.payload);
+            let _ = control_tx.unbounded_send(pong);
+          }
+          OPCODE_CLOSE => {
+            let _ = control_tx.unbounded_send(encode_frame(OPCODE_CLOSE, &[]));
+            // Dropping the sender ends the session.
+            return Loop::Break(());
+          }
+          _ => {}
+        }
+        Loop::Continue((reader, sender, message))
+      })
+    },
+  );
+
+  // Once the inspector ends the session, e.g. for another frontend, this
+  // closes the connection.
+  let close = stream::once(Ok(encode_frame(OPCODE_CLOSE, &[])));
+  let write = outgoing
+    .map(|message| encode_frame(OPCODE_TEXT, message.as_bytes()))
+    .chain(close)
+    .select(control_rx)
+    .fold(writer, |writer, frame| {
+      write_all(writer, frame)
+        .map(|(writer, _)| writer)
+        .map_err(|_| ())
+    });
+
+  read.map_err(|_| ()).join(write).map(|_| ())
+}
+
+struct Frame {
+  fin: bool,
+  opcode: u8,
+  payload: Vec<u8>,
+}
+
+/// Reads a frame of the frontend, which are all masked.
+fn read_frame<R: AsyncRead>(
+  reader: R,
+) -> impl Future<Item = (R, Frame), Error = io::Error> {
+  read_exact(reader, [0u8; 2]).and_then(|(reader, head)| {
+    let fin = head[0] & 0x80 != 0;
+    let opcode = head[0] & 0x0f;
+    let masked = head[1] & 0x80 != 0;
+    let ext_len = match head[1] & 0x7f {
+      126 => 2,
+      127 => 8,
+      _ => 0,
+    };
+    read_exact(reader, vec![0u8; ext_len + 4]).and_then(move |(reader, ext)| {
+      let len = match ext_len {
+        0 => u64::from(head[1] & 0x7f),
+        _ => ext[..ext_len]
+          .iter()
+          .fold(0, |len, b| (len << 8) | u64::from(*b)),
+      };
+      if !masked || len > MAX_FRAME_LEN {
+        let err =
+          io::Error::new(io::ErrorKind::InvalidData, "Invalid WebSocket frame");
+        return Either::A(futures::future::err(err));
+      }
+      let mut mask = [0u8; 4];
+      mask.copy_from_slice(&ext[ext_len..]);
+      let read_payload = read_exact(reader, vec![0u8; len as usize]).map(
+        move |(reader, mut payload)| {
+          for (i, b) in payload.iter_mut().enumerate() {
+            *b ^= mask[i % 4];
+          }
+          let frame = Frame {
+            fin,
+            opcode,
+            payload,
+          };
+          (reader, frame)
+        },
+      );
+      Either::B(read_payload)
+    })
+  })
+}
+
+/// Encodes a frame of the server, which are never masked.
+fn encode_frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+  let len = payload.len();
+  let mut frame = vec![0x80 | opcode];
+  if len < 126 {
+    frame.push(len as u8);
+  } else if len <= 0xffff {
+    frame.push(126);
+    frame.extend_from_slice(&(len as u16).to_be_bytes());
+  } else {
+    frame.push(127);
+    frame.extend_from_slice(&(len as u64).to_be_bytes());
+  }
+  frame.extend_from_slice(payload);
+  frame
+}
+
+#[test]
+fn test_accept_key() {
+  // From RFC 6455.
+  assert_eq!(
+    accept_key(b"dGhlIHNhbXBsZSBub25jZQ=="),
+    "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+  );
+}
+
+#[test]
+fn test_encode_frame() {
+  assert_eq!(encode_frame(OPCODE_TEXT, b"hi"), vec![0x81, 2, b'h', b'i']);
+  let frame = encode_frame(OPCODE_TEXT, &[0; 300]);
+  assert_eq!(&frame[..4], &[0x81, 126, 1, 44]);
+  assert_eq!(frame.len(), 304);
+}
+
+#[test]
+fn test_is_allowed_host() {
+  assert!(is_allowed_host("localhost:9229"));
+  assert!(is_allowed_host("LOCALHOST"));
+  assert!(is_allowed_host("127.0.0.1:9229"));
+  assert!(is_allowed_host("[::1]:9229"));
+  assert!(is_allowed_host("[::1]"));
+  assert!(!is_allowed_host("example.com:9229"));
+  assert!(!is_allowed_host("localhost.example.com"));
+  assert!(!is_allowed_host("[::1"));
+  assert!(!is_allowed_host("127.0.0.1.example.com:9229"));
+}
+
+#[test]
+fn test_read_frame() {
+  let mask = [1u8, 2, 3, 4];
+  let mut data = vec![0x81, 0x80 | 2];
+  data.extend_from_slice(&mask);
+  data.extend_from_slice(&[b'h' ^ 1, b'i' ^ 2]);
+  let (_, frame) = read_frame(io::Cursor::new(data)).wait().unwrap();
+  assert!(frame.fin);
+  assert_eq!(frame.opcode, OPCODE_TEXT);
+  assert_eq!(frame.payload, b"hi");
+}
@@ -0,0 +1,94 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import {
+  sendSync,
+  sendAsync,
+  unwrapResponse,
+  JsonResponse
+} from "./dispatch_json.ts";
+import * as dispatch from "./dispatch.ts";
+import { FileInfo, FileInfoImpl } from "./file_info.ts";
+import { StatResponse } from "./stat.ts";
+
+/** A file system query made by `batch`, named after the function which makes
+ * it on its own, e.g. `{ op: "stat", path: "hello.txt" }`.
+ */
+export interface BatchCall {
+  op: "stat" | "lstat" | "readDir" | "readlink" | "realpath";
+  path: string;
+}
+
+/** The outcome of a `BatchCall`, which failed if `error` is set. */
+export interface BatchResult {
+  value?: FileInfo | FileInfo[] | string;
+  error?: Error;
+}
+
+interface BatchedOp {
+  name: string;
+  args: object;
+}
+
+function batchedOp(call: BatchCall): BatchedOp {
+  const path = call.path;
+  switch (call.op) {
+    case "stat":
+      return { name: "stat", args: { filename: path, lstat: false } };
+    case "lstat":
+      return { name: "stat", args: { filename: path, lstat: true } };
+    case "readDir":
+      return { name: "read_dir", args: { path } };
+    case "readlink":
+      return { name: "read_link", args: { name: path } };
+    case "realpath":
+      return { name: "realpath", args: { path } };
+    default:
+      throw new TypeError("Unknown batch op");
+  }
+}
+
+function batchResult(call: BatchCall, res: JsonResponse): BatchResult {
+  let ok;
+  try {
+    ok = unwrapResponse(res);
+  } catch (error) {
+    return { error };
+  }
+  switch (call.op) {
+    case "stat":
+    case "lstat":
+      return { value: new FileInfoImpl(ok as StatResponse) };
+    case "readDir":
+      return {
+        value: (ok.entries as StatResponse[]).map(
+          (statRes): FileInfo => new FileInfoImpl(statRes)
+        )
+      };
+    default:
+      return { value: ok as string };
+  }
+}
+
+/** Makes several file system queries with a single op, which saves crossing
+ * over to the privileged side once per query when there are many of them.
+ * The results are in the order of `calls`, and one failing doesn't fail the
+ * others.
+ *
+ *       const [info, target] = await Deno.batch([
+ *         { op: "stat", path: "hello.txt" },
+ *         { op: "readlink", path: "link" }
+ *       ]);
+ */
+export async function batch(calls: BatchCall[]): Promise<BatchResult[]> {
+  const res = (await sendAsync(dispatch.OP_BATCH, {
+    ops: calls.map(batchedOp)
+  })) as JsonResponse[];
+  return res.map((r, i): BatchResult => batchResult(calls[i], r));
+}
+
+/** Synchronous version of `batch`. */
+export function batchSync(calls: BatchCall[]): BatchResult[] {
+  const res = sendSync(dispatch.OP_BATCH, {
+    ops: calls.map(batchedOp)
+  }) as JsonResponse[];
+  return res.map((r, i): BatchResult => batchResult(calls[i], r));
+}
@@ -0,0 +1,38 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { testPerm, assert, assertEquals } from "./test_util.ts";
+
+testPerm({ read: true }, async function batchStat(): Promise<void> {
+  const results = await Deno.batch([
+    { op: "stat", path: "package.json" },
+    { op: "stat", path: "does_not_exist" },
+    { op: "readDir", path: "tests" },
+    { op: "realpath", path: "." }
+  ]);
+  assertEquals(results.length, 4);
+
+  const info = results[0].value as Deno.FileInfo;
+  assert(info.isFile());
+  assertEquals(results[1].value, undefined);
+  assertEquals(
+    (results[1].error as Deno.DenoError<Deno.ErrorKind.NotFound>).kind,
+    Deno.ErrorKind.NotFound
+  );
+  const entries = results[2].value as Deno.FileInfo[];
+  assert(entries.some((e): boolean => e.name === "hello.txt"));
+  assertEquals(results[3].value, Deno.cwd());
+});
+
+testPerm({ read: true }, function batchSyncStat(): void {
+  const [res] = Deno.batchSync([{ op: "lstat", path: "package.json" }]);
+  assertEquals(res.error, undefined);
+  assert((res.value as Deno.FileInfo).isFile());
+  assertEquals(Deno.batchSync([]), []);
+});
+
+testPerm({ read: false }, async function batchPerm(): Promise<void> {
+  const [res] = await Deno.batch([{ op: "stat", path: "package.json" }]);
+  assertEquals(
+    (res.error as Deno.DenoError<Deno.ErrorKind.PermissionDenied>).kind,
+    Deno.ErrorKind.PermissionDenied
+  );
+});
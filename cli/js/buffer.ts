@@ -8,6 +8,10 @@ import { Reader, Writer, EOF, SyncReader, SyncWriter } from "./io.ts";
 import { assert } from "./util.ts";
 import { TextDecoder } from "./text_encoding.ts";
 import { DenoError, ErrorKind } from "./errors.ts";
+import { sendSync, sendAsync } from "./dispatch_json.ts";
+import * as dispatch from "./dispatch.ts";
+import { File } from "./files.ts";
+import { ConnImpl } from "./net.ts";
 
 // MIN_READ is the minimum ArrayBuffer size passed to a read call by
 // buffer.ReadFrom. As long as the Buffer has at least MIN_READ bytes beyond
@@ -15,6 +19,9 @@ import { DenoError, ErrorKind } from "./errors.ts";
 // underlying buffer.
 const MIN_READ = 512;
 const MAX_SIZE = 2 ** 32 - 2;
+// Size of the first buffer handed to the read_all op. It is doubled every
+// time the op fills it.
+const READ_ALL_INITIAL_SIZE = 64 * 1024;
 
 // `off` is the offset into `dst` where it will at which to begin writing values
 // from `src`.
@@ -259,34 +266,135 @@ export class Buffer implements Reader, SyncReader, Writer, SyncWriter {
   }
 }
 
-/** Read `r` until EOF and return the content as `Uint8Array`.
+export interface ReadAllOptions {
+  /** Largest number of bytes to read. Reading more than this throws
+   * `ErrorKind.TooLarge`. Defaults to no limit other than the largest
+   * possible buffer.
+   */
+  maxSize?: number;
+}
+
+// Returns the resource behind `r` if it is one of the runtime's own readers
+// or writers, which lets the whole transfer happen in a single op.
+function resourceOf(
+  r: Reader | SyncReader | Writer | SyncWriter
+): number | null {
+  return r instanceof File || r instanceof ConnImpl ? r.rid : null;
+}
+
+function tooLarge(maxSize: number): never {
+  throw new DenoError(
+    ErrorKind.TooLarge,
+    `readAll: more than ${maxSize} bytes available`
+  );
+}
+
+// Grows `buf` for the next read_all op, keeping one byte past `maxSize` so
+// that an oversized stream is noticed.
+function growForReadAll(buf: Uint8Array, maxSize: number): Uint8Array {
+  const size = Math.min(buf.byteLength * 2, maxSize + 1);
+  const grown = new Uint8Array(size);
+  grown.set(buf);
+  return grown;
+}
+
+async function readAllResource(
+  rid: number,
+  maxSize: number
+): Promise<Uint8Array> {
+  let buf = new Uint8Array(Math.min(READ_ALL_INITIAL_SIZE, maxSize + 1));
+  let n = 0;
+  do {
+    if (n > 0) {
+      buf = growForReadAll(buf, maxSize);
+    }
+    n += await sendAsync(dispatch.OP_READ_ALL, { rid }, buf.subarray(n));
+    if (n > maxSize) {
+      tooLarge(maxSize);
+    }
+  } while (n === buf.byteLength);
+  return buf.subarray(0, n);
+}
+
+function readAllResourceSync(rid: number, maxSize: number): Uint8Array {
+  let buf = new Uint8Array(Math.min(READ_ALL_INITIAL_SIZE, maxSize + 1));
+  let n = 0;
+  do {
+    if (n > 0) {
+      buf = growForReadAll(buf, maxSize);
+    }
+    n += sendSync(dispatch.OP_READ_ALL, { rid }, buf.subarray(n));
+    if (n > maxSize) {
+      tooLarge(maxSize);
+    }
+  } while (n === buf.byteLength);
+  return buf.subarray(0, n);
+}
+
+/** Read `r` until EOF and return the content as `Uint8Array`. Files and
+ * connections are read in as few ops as possible.
  */
-export async function readAll(r: Reader): Promise<Uint8Array> {
+export async function readAll(
+  r: Reader,
+  options: ReadAllOptions = {}
+): Promise<Uint8Array> {
+  const { maxSize = MAX_SIZE } = options;
+  const rid = resourceOf(r);
+  if (rid !== null) {
+    return readAllResource(rid, maxSize);
+  }
   const buf = new Buffer();
   await buf.readFrom(r);
+  if (buf.length > maxSize) {
+    tooLarge(maxSize);
+  }
   return buf.bytes();
 }
 
 /** Read synchronously `r` until EOF and return the content as `Uint8Array`.
+ * Files and connections are read in as few ops as possible.
  */
-export function readAllSync(r: SyncReader): Uint8Array {
+export function readAllSync(
+  r: SyncReader,
+  options: ReadAllOptions = {}
+): Uint8Array {
+  const { maxSize = MAX_SIZE } = options;
+  const rid = resourceOf(r);
+  if (rid !== null) {
+    return readAllResourceSync(rid, maxSize);
+  }
   const buf = new Buffer();
   buf.readFromSync(r);
+  if (buf.length > maxSize) {
+    tooLarge(maxSize);
+  }
   return buf.bytes();
 }
 
-/** Write all the content of `arr` to `w`.
+/** Write all the content of `arr` to `w`. Files and connections are written
+ * with a single op.
  */
 export async function writeAll(w: Writer, arr: Uint8Array): Promise<void> {
+  const rid = resourceOf(w);
+  if (rid !== null) {
+    await sendAsync(dispatch.OP_WRITE_ALL, { rid }, arr);
+    return;
+  }
   let nwritten = 0;
   while (nwritten < arr.length) {
     nwritten += await w.write(arr.subarray(nwritten));
   }
 }
 
-/** Write synchronously all the content of `arr` to `w`.
+/** Write synchronously all the content of `arr` to `w`. Files and connections
+ * are written with a single op.
  */
 export function writeAllSync(w: SyncWriter, arr: Uint8Array): void {
+  const rid = resourceOf(w);
+  if (rid !== null) {
+    sendSync(dispatch.OP_WRITE_ALL, { rid }, arr);
+    return;
+  }
   let nwritten = 0;
   while (nwritten < arr.length) {
     nwritten += w.writeSync(arr.subarray(nwritten));
@@ -3,7 +3,7 @@
 // This code has been ported almost directly from Go's src/bytes/buffer_test.go
 // Copyright 2009 The Go Authors. All rights reserved. BSD license.
 // https://github.com/golang/go/blob/master/LICENSE
-import { assertEquals, test } from "./test_util.ts";
+import { assert, assertEquals, test, testPerm } from "./test_util.ts";
 
 const { Buffer, readAll, readAllSync, writeAll, writeAllSync } = Deno;
 type Buffer = Deno.Buffer;
@@ -275,3 +275,87 @@ test(function testWriteAllSync(): void {
     assertEquals(testBytes[i], actualBytes[i]);
   }
 });
+
+test(async function testReadAllMaxSize(): Promise<void> {
+  init();
+  const reader = new Buffer(testBytes.buffer as ArrayBuffer);
+  let err;
+  try {
+    await readAll(reader, { maxSize: N - 1 });
+  } catch (e) {
+    err = e;
+  }
+  assert(!!err);
+  assertEquals(err.kind, Deno.ErrorKind.TooLarge);
+});
+
+// Larger than the first buffer handed to the read_all op, so that it has to
+// grow at least once.
+function bigBytes(): Uint8Array {
+  const bytes = new Uint8Array(200 * 1024);
+  for (let i = 0; i < bytes.length; i++) {
+    bytes[i] = i % 251;
+  }
+  return bytes;
+}
+
+testPerm({ read: true, write: true }, async function testReadAllFile(): Promise<
+  void
+> {
+  const data = bigBytes();
+  const filename = Deno.makeTempDirSync() + "/test.bin";
+  Deno.writeFileSync(filename, data);
+  const file = await Deno.open(filename);
+  const actual = await readAll(file);
+  file.close();
+  assertEquals(actual, data);
+});
+
+testPerm({ read: true, write: true }, function testReadAllSyncFile(): void {
+  const data = bigBytes();
+  const filename = Deno.makeTempDirSync() + "/test.bin";
+  Deno.writeFileSync(filename, data);
+  const file = Deno.openSync(filename);
+  const actual = readAllSync(file);
+  file.close();
+  assertEquals(actual, data);
+});
+
+testPerm(
+  { read: true, write: true },
+  function testReadAllSyncFileMaxSize(): void {
+    const filename = Deno.makeTempDirSync() + "/test.bin";
+    Deno.writeFileSync(filename, bigBytes());
+    const file = Deno.openSync(filename);
+    let err;
+    try {
+      readAllSync(file, { maxSize: 100 * 1024 });
+    } catch (e) {
+      err = e;
+    }
+    file.close();
+    assert(!!err);
+    assertEquals(err.kind, Deno.ErrorKind.TooLarge);
+  }
+);
+
+testPerm(
+  { read: true, write: true },
+  async function testWriteAllFile(): Promise<void> {
+    const data = bigBytes();
+    const filename = Deno.makeTempDirSync() + "/test.bin";
+    const file = await Deno.open(filename, "w");
+    await writeAll(file, data);
+    file.close();
+    assertEquals(Deno.readFileSync(filename), data);
+  }
+);
+
+testPerm({ read: true, write: true }, function testWriteAllSyncFile(): void {
+  const data = bigBytes();
+  const filename = Deno.makeTempDirSync() + "/test.bin";
+  const file = Deno.openSync(filename, "w");
+  writeAllSync(file, data);
+  file.close();
+  assertEquals(Deno.readFileSync(filename), data);
+});
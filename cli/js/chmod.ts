@@ -2,19 +2,69 @@
 import { sendSync, sendAsync } from "./dispatch_json.ts";
 import * as dispatch from "./dispatch.ts";
 
+export interface ChmodOptions {
+  /** Also change everything below `path` when it's a directory. The tree is
+   * walked on the privileged side. Defaults to `false`.
+   */
+  recursive?: boolean;
+  /** With `recursive`, resolve symlinks found below `path`, changing their
+   * targets and descending into linked directories. Otherwise they're left
+   * alone. Defaults to `false`.
+   */
+  followSymlinks?: boolean;
+}
+
+function chmodArgs(path: string, mode: number, options: ChmodOptions): object {
+  const { recursive = false, followSymlinks = false } = options;
+  return { path, mode, recursive, followSymlinks };
+}
+
 /** Changes the permission of a specific file/directory of specified path
- * synchronously.
+ * synchronously. On Windows only the owner write bit is used, to set or
+ * clear the read-only attribute.
  *
  *       Deno.chmodSync("/path/to/file", 0o666);
+ *       Deno.chmodSync("/path/to/dir", 0o755, { recursive: true });
  */
-export function chmodSync(path: string, mode: number): void {
-  sendSync(dispatch.OP_CHMOD, { path, mode });
+export function chmodSync(
+  path: string,
+  mode: number,
+  options: ChmodOptions = {}
+): void {
+  sendSync(dispatch.OP_CHMOD, chmodArgs(path, mode, options));
 }
 
 /** Changes the permission of a specific file/directory of specified path.
+ * On Windows only the owner write bit is used, to set or clear the read-only
+ * attribute.
  *
  *       await Deno.chmod("/path/to/file", 0o666);
+ *       await Deno.chmod("/path/to/dir", 0o755, { recursive: true });
+ */
+export async function chmod(
+  path: string,
+  mode: number,
+  options: ChmodOptions = {}
+): Promise<void> {
+  await sendAsync(dispatch.OP_CHMOD, chmodArgs(path, mode, options));
+}
+
+/** Synchronously changes the permission of the file referenced by `rid`,
+ * which must have been opened for writing.
+ *
+ *       const file = Deno.openSync("/path/to/file", "r+");
+ *       Deno.fchmodSync(file.rid, 0o600);
+ */
+export function fchmodSync(rid: number, mode: number): void {
+  sendSync(dispatch.OP_FCHMOD, { rid, mode });
+}
+
+/** Changes the permission of the file referenced by `rid`, which must have
+ * been opened for writing.
+ *
+ *       const file = await Deno.open("/path/to/file", "r+");
+ *       await Deno.fchmod(file.rid, 0o600);
  */
-export async function chmod(path: string, mode: number): Promise<void> {
-  await sendAsync(dispatch.OP_CHMOD, { path, mode });
+export async function fchmod(rid: number, mode: number): Promise<void> {
+  await sendAsync(dispatch.OP_FCHMOD, { rid, mode });
 }
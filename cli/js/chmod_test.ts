@@ -1,5 +1,5 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
-import { testPerm, assertEquals } from "./test_util.ts";
+import { testPerm, assert, assertEquals } from "./test_util.ts";
 
 const isNotWindows = Deno.build.os !== "win";
 
@@ -140,3 +140,109 @@ testPerm({ write: false }, async function chmodPerm(): Promise<void> {
   assertEquals(err.kind, Deno.ErrorKind.PermissionDenied);
   assertEquals(err.name, "PermissionDenied");
 });
+
+testPerm({ read: true, write: true }, function fchmodSyncSuccess(): void {
+  const filename = Deno.makeTempDirSync() + "/test.txt";
+  const file = Deno.openSync(filename, "w");
+
+  // On windows no effect, but should not crash
+  Deno.fchmodSync(file.rid, 0o600);
+  file.close();
+
+  if (isNotWindows) {
+    assertEquals(Deno.statSync(filename).mode & 0o777, 0o600);
+  }
+});
+
+testPerm({ read: true, write: true }, async function fchmodSuccess(): Promise<
+  void
+> {
+  const filename = Deno.makeTempDirSync() + "/test.txt";
+  const file = await Deno.open(filename, "w");
+
+  await Deno.fchmod(file.rid, 0o640);
+  file.close();
+
+  if (isNotWindows) {
+    assertEquals(Deno.statSync(filename).mode & 0o777, 0o640);
+  }
+});
+
+if (isNotWindows) {
+  testPerm({ read: true, write: true }, function fchmodSyncReadOnly(): void {
+    const filename = Deno.makeTempDirSync() + "/test.txt";
+    Deno.writeFileSync(filename, new Uint8Array([1]), { perm: 0o644 });
+    const file = Deno.openSync(filename, "r");
+
+    let caughtError = false;
+    try {
+      Deno.fchmodSync(file.rid, 0o777);
+    } catch (e) {
+      caughtError = true;
+      assertEquals(e.kind, Deno.ErrorKind.PermissionDenied);
+    }
+    assert(caughtError);
+    assertEquals(Deno.statSync(filename).mode & 0o777, 0o644);
+    file.close();
+  });
+
+  testPerm(
+    { read: true, write: true },
+    function chmodSyncRecursive(): void {
+      const dir = Deno.makeTempDirSync();
+      Deno.mkdirSync(dir + "/a/b", true);
+      Deno.writeFileSync(dir + "/a/b/c.txt", new Uint8Array([1]));
+      const outside = Deno.makeTempDirSync() + "/outside.txt";
+      Deno.writeFileSync(outside, new Uint8Array([1]), { perm: 0o600 });
+      Deno.symlinkSync(outside, dir + "/a/link");
+
+      Deno.chmodSync(dir, 0o700, { recursive: true });
+      for (const path of [dir, dir + "/a", dir + "/a/b", dir + "/a/b/c.txt"]) {
+        assertEquals(Deno.statSync(path).mode! & 0o777, 0o700);
+      }
+      // Symlinks aren't followed by default.
+      assertEquals(Deno.statSync(outside).mode! & 0o777, 0o600);
+
+      Deno.chmodSync(dir, 0o750, { recursive: true, followSymlinks: true });
+      assertEquals(Deno.statSync(outside).mode! & 0o777, 0o750);
+    }
+  );
+
+  testPerm(
+    { read: true, write: true },
+    async function chmodRecursive(): Promise<void> {
+      const dir = Deno.makeTempDirSync();
+      Deno.mkdirSync(dir + "/a");
+      Deno.writeFileSync(dir + "/a/b.txt", new Uint8Array([1]));
+
+      await Deno.chmod(dir, 0o711, { recursive: true });
+      assertEquals(Deno.statSync(dir + "/a").mode! & 0o777, 0o711);
+      assertEquals(Deno.statSync(dir + "/a/b.txt").mode! & 0o777, 0o711);
+    }
+  );
+}
+
+testPerm({ read: true, write: true }, function chmodSyncReadOnly(): void {
+  const filename = Deno.makeTempDirSync() + "/test.txt";
+  Deno.writeFileSync(filename, new Uint8Array([1]));
+
+  // Works the same on Windows, where it maps to the read-only attribute.
+  Deno.chmodSync(filename, 0o444);
+  const info = Deno.statSync(filename);
+  assertEquals(info.mode! & 0o222, 0);
+  // Root can write to anything on Unix.
+  if (info.uid !== 0) {
+    let caughtError = false;
+    try {
+      Deno.openSync(filename, "w");
+    } catch (e) {
+      caughtError = true;
+      assertEquals(e.kind, Deno.ErrorKind.PermissionDenied);
+    }
+    assert(caughtError);
+  }
+
+  Deno.chmodSync(filename, 0o644);
+  assert((Deno.statSync(filename).mode! & 0o200) !== 0);
+  Deno.openSync(filename, "w").close();
+});
@@ -2,26 +2,122 @@
 import { sendSync, sendAsync } from "./dispatch_json.ts";
 import * as dispatch from "./dispatch.ts";
 
+export interface ChownOptions {
+  /** Also change everything below `path` when it's a directory. The tree is
+   * walked on the privileged side. Defaults to `false`.
+   */
+  recursive?: boolean;
+  /** With `recursive`, resolve symlinks found below `path`, changing their
+   * targets and descending into linked directories. Otherwise the owner of
+   * the links themselves is changed. Defaults to `false`.
+   */
+  followSymlinks?: boolean;
+}
+
+function chownArgs(
+  path: string,
+  uid: number,
+  gid: number,
+  options: ChownOptions
+): object {
+  const { recursive = false, followSymlinks = false } = options;
+  return { path, uid, gid, recursive, followSymlinks };
+}
+
 /**
- * Change owner of a regular file or directory synchronously. Unix only at the moment.
+ * Change owner of a regular file or directory synchronously. Unix only at the
+ * moment, throws `OpNotAvailable` on Windows.
  * @param path path to the file
  * @param uid user id of the new owner
  * @param gid group id of the new owner
+ * @param options see `ChownOptions`
  */
-export function chownSync(path: string, uid: number, gid: number): void {
-  sendSync(dispatch.OP_CHOWN, { path, uid, gid });
+export function chownSync(
+  path: string,
+  uid: number,
+  gid: number,
+  options: ChownOptions = {}
+): void {
+  sendSync(dispatch.OP_CHOWN, chownArgs(path, uid, gid, options));
 }
 
 /**
- * Change owner of a regular file or directory asynchronously. Unix only at the moment.
+ * Change owner of a regular file or directory asynchronously. Unix only at the
+ * moment, throws `OpNotAvailable` on Windows.
  * @param path path to the file
  * @param uid user id of the new owner
  * @param gid group id of the new owner
+ * @param options see `ChownOptions`
  */
 export async function chown(
   path: string,
   uid: number,
+  gid: number,
+  options: ChownOptions = {}
+): Promise<void> {
+  await sendAsync(dispatch.OP_CHOWN, chownArgs(path, uid, gid, options));
+}
+
+/**
+ * Change owner of a file synchronously without following symlinks: if `path`
+ * is a symlink, the owner of the link itself is changed. Unix only at the
+ * moment, throws `OpNotAvailable` on Windows.
+ * @param path path to the file
+ * @param uid user id of the new owner
+ * @param gid group id of the new owner
+ * @param options see `ChownOptions`
+ */
+export function lchownSync(
+  path: string,
+  uid: number,
+  gid: number,
+  options: ChownOptions = {}
+): void {
+  sendSync(dispatch.OP_LCHOWN, chownArgs(path, uid, gid, options));
+}
+
+/**
+ * Change owner of a file asynchronously without following symlinks: if `path`
+ * is a symlink, the owner of the link itself is changed. Unix only at the
+ * moment, throws `OpNotAvailable` on Windows.
+ * @param path path to the file
+ * @param uid user id of the new owner
+ * @param gid group id of the new owner
+ * @param options see `ChownOptions`
+ */
+export async function lchown(
+  path: string,
+  uid: number,
+  gid: number,
+  options: ChownOptions = {}
+): Promise<void> {
+  await sendAsync(dispatch.OP_LCHOWN, chownArgs(path, uid, gid, options));
+}
+
+/**
+ * Change owner of the file referenced by `rid` synchronously. The file must
+ * have been opened for writing. Unix only at the moment, throws
+ * `OpNotAvailable` on Windows.
+ * @param rid resource id of the open file
+ * @param uid user id of the new owner
+ * @param gid group id of the new owner
+ */
+export function fchownSync(rid: number, uid: number, gid: number): void {
+  sendSync(dispatch.OP_FCHOWN, { rid, uid, gid });
+}
+
+/**
+ * Change owner of the file referenced by `rid` asynchronously. The file must
+ * have been opened for writing. Unix only at the moment, throws
+ * `OpNotAvailable` on Windows.
+ * @param rid resource id of the open file
+ * @param uid user id of the new owner
+ * @param gid group id of the new owner
+ */
+export async function fchown(
+  rid: number,
+  uid: number,
   gid: number
 ): Promise<void> {
-  await sendAsync(dispatch.OP_CHOWN, { path, uid, gid });
+  await sendAsync(dispatch.OP_FCHOWN, { rid, uid, gid });
 }
@@ -1,5 +1,5 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
-import { testPerm, assertEquals } from "./test_util.ts";
+import { testPerm, assert, assertEquals } from "./test_util.ts";
 
 // chown on Windows is noop for now, so ignore its testing on Windows
 if (Deno.build.os !== "win") {
@@ -142,4 +142,109 @@ if (Deno.build.os !== "win") {
 
     Deno.removeSync(dirPath, { recursive: true });
   });
+
+  testPerm(
+    { run: true, write: true },
+    async function lchownSyncSymlinkSucceed(): Promise<void> {
+      const { uid, gid } = await getUidAndGid();
+
+      const dirPath = Deno.makeTempDirSync();
+      const filePath = dirPath + "/chown_test_file.txt";
+      const linkPath = dirPath + "/chown_test_link";
+      Deno.writeFileSync(filePath, new TextEncoder().encode("Hello"));
+      Deno.symlinkSync(filePath, linkPath);
+
+      // Same as chownSyncSucceed, a noop change of owner succeeds for the
+      // unprivileged test user.
+      Deno.lchownSync(linkPath, uid, gid);
+
+      Deno.removeSync(dirPath, { recursive: true });
+    }
+  );
+
+  testPerm({ write: true }, async function lchownDanglingSymlink(): Promise<
+    void
+  > {
+    const dirPath = await Deno.makeTempDir();
+    const linkPath = dirPath + "/chown_test_link";
+    Deno.symlinkSync(dirPath + "/does_not_exist", linkPath);
+
+    try {
+      // Unlike chown, lchown doesn't care whether the target exists. It can
+      // only fail with PermissionDenied because we aren't root.
+      await Deno.lchown(linkPath, 0, 0);
+    } catch (e) {
+      assertEquals(e.kind, Deno.ErrorKind.PermissionDenied);
+    }
+    await Deno.remove(dirPath, { recursive: true });
+  });
+
+  testPerm({}, function lchownSyncNoWritePermission(): void {
+    try {
+      Deno.lchownSync("chown_test_link", 1000, 1000);
+    } catch (e) {
+      assertEquals(e.kind, Deno.ErrorKind.PermissionDenied);
+      assertEquals(e.name, "PermissionDenied");
+    }
+  });
+
+  testPerm(
+    { run: true, read: true, write: true },
+    async function fchownSucceed(): Promise<void> {
+      const { uid, gid } = await getUidAndGid();
+      const filePath = Deno.makeTempDirSync() + "/chown_test_file.txt";
+      const file = await Deno.open(filePath, "w");
+
+      // Noop change of owner, which the test user is always allowed to do.
+      Deno.fchownSync(file.rid, uid, gid);
+      await Deno.fchown(file.rid, uid, gid);
+
+      file.close();
+    }
+  );
+
+  testPerm({ read: true, write: true }, function fchownSyncReadOnly(): void {
+    const filePath = Deno.makeTempDirSync() + "/chown_test_file.txt";
+    Deno.writeFileSync(filePath, new Uint8Array([1]));
+    const file = Deno.openSync(filePath, "r");
+
+    try {
+      Deno.fchownSync(file.rid, 0, 0);
+    } catch (e) {
+      assertEquals(e.kind, Deno.ErrorKind.PermissionDenied);
+    }
+    file.close();
+  });
+
+  testPerm(
+    { read: true, write: true },
+    function chownSyncRecursive(): void {
+      const dir = Deno.makeTempDirSync();
+      Deno.mkdirSync(dir + "/a");
+      Deno.writeFileSync(dir + "/a/b.txt", new Uint8Array([1]));
+      Deno.symlinkSync(dir + "/a/b.txt", dir + "/a/link");
+      const { uid, gid } = Deno.statSync(dir);
+
+      // Noop change of owner, which the test user is always allowed to do.
+      Deno.chownSync(dir, uid!, gid!, { recursive: true });
+      Deno.lchownSync(dir, uid!, gid!, {
+        recursive: true,
+        followSymlinks: true
+      });
+      assertEquals(Deno.lstatSync(dir + "/a/link").uid, uid);
+    }
+  );
+
+  testPerm({ write: true }, async function chownRecursiveNotFound(): Promise<
+    void
+  > {
+    let caughtError = false;
+    try {
+      await Deno.chown("not_a_dir", 0, 0, { recursive: true });
+    } catch (e) {
+      caughtError = true;
+      assertEquals(e.kind, Deno.ErrorKind.NotFound);
+    }
+    assert(caughtError);
+  });
 }
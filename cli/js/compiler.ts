@@ -28,7 +28,8 @@ enum MediaType {
   TypeScript = 2,
   TSX = 3,
   Json = 4,
-  Unknown = 5
+  Wasm = 5,
+  Unknown = 6
 }
 
 // Startup boilerplate. This is necessary because the compiler has its own
@@ -339,6 +340,7 @@ function getExtension(fileName: string, mediaType: MediaType): ts.Extension {
       return ts.Extension.Tsx;
     case MediaType.Json:
       return ts.Extension.Json;
+    case MediaType.Wasm:
     case MediaType.Unknown:
     default:
       throw TypeError("Cannot resolve extension.");
@@ -85,6 +85,36 @@ testPerm({ read: true, write: true }, function copyFileSyncOverwrite(): void {
   assertSameContent(fromFilename, toFilename);
 });
 
+testPerm({ read: true, write: true }, function copyFileSyncLarge(): void {
+  const tempDir = Deno.makeTempDirSync();
+  const fromFilename = tempDir + "/from.bin";
+  const toFilename = tempDir + "/to.bin";
+  // Big enough to need more than one copy_file_range call on most kernels.
+  const data = new Uint8Array(4 * 1024 * 1024 + 7);
+  for (let i = 0; i < data.length; i++) {
+    data[i] = i % 251;
+  }
+  Deno.writeFileSync(fromFilename, data);
+  Deno.copyFileSync(fromFilename, toFilename);
+  assertSameContent(fromFilename, toFilename);
+});
+
+testPerm(
+  { read: true, write: true },
+  function copyFileSyncPreservesMode(): void {
+    if (Deno.build.os === "win") {
+      return;
+    }
+    const tempDir = Deno.makeTempDirSync();
+    const fromFilename = tempDir + "/from.txt";
+    const toFilename = tempDir + "/to.txt";
+    writeFileString(fromFilename, "Hello world!");
+    Deno.chmodSync(fromFilename, 0o741);
+    Deno.copyFileSync(fromFilename, toFilename);
+    assertEquals(Deno.statSync(toFilename).mode! & 0o777, 0o741);
+  }
+);
+
 testPerm({ read: true, write: true }, async function copyFileSuccess(): Promise<
   void
 > {
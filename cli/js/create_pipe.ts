@@ -0,0 +1,56 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { sendSync, sendAsync } from "./dispatch_json.ts";
+import * as dispatch from "./dispatch.ts";
+import { File } from "./files.ts";
+
+export interface CreatePipeOptions {
+  /** Permissions of the FIFO, before the umask is applied. Ignored on
+   * Windows. Defaults to `0o666`.
+   */
+  mode?: number;
+  /** Open the pipe for reading. Defaults to `true`. */
+  read?: boolean;
+  /** Open the pipe for writing. Defaults to `false`. */
+  write?: boolean;
+}
+
+/** Synchronously creates a named pipe and opens it. Blocks until the other
+ * end is opened, see `createPipe`.
+ *
+ *       const fifo = Deno.createPipeSync("/tmp/events", { mode: 0o600 });
+ */
+export function createPipeSync(
+  path: string,
+  options: CreatePipeOptions = {}
+): File {
+  const { mode = 0o666, read = true, write = false } = options;
+  return new File(
+    sendSync(dispatch.OP_CREATE_PIPE, { path, mode, read, write })
+  );
+}
+
+/** Creates a named pipe and opens it. Requires `allow-write`, and
+ * `allow-read` unless only opened for writing.
+ *
+ * On Unix this creates a FIFO at `path` which stays around until removed.
+ * Opening it waits for another process to open the other end, unless it's
+ * opened for both reading and writing.
+ *
+ * On Windows `path` must look like `\\.\pipe\name`. The pipe only lives
+ * as long as the returned file is open, and this waits for a client to
+ * connect to it.
+ *
+ *       const fifo = await Deno.createPipe("/tmp/events");
+ *       for await (const chunk of Deno.toAsyncIterator(fifo)) {
+ *         console.log(chunk);
+ *       }
+ */
+export async function createPipe(
+  path: string,
+  options: CreatePipeOptions = {}
+): Promise<File> {
+  const { mode = 0o666, read = true, write = false } = options;
+  return new File(
+    await sendAsync(dispatch.OP_CREATE_PIPE, { path, mode, read, write })
+  );
+}
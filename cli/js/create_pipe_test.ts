@@ -0,0 +1,74 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { testPerm, assert, assertEquals } from "./test_util.ts";
+
+const isNotWindows = Deno.build.os !== "win";
+
+if (isNotWindows) {
+  testPerm(
+    { read: true, write: true },
+    function createPipeSyncReadWrite(): void {
+      const path = Deno.makeTempDirSync() + "/fifo";
+      // Opening both ends at once doesn't wait for anybody else.
+      const fifo = Deno.createPipeSync(path, { read: true, write: true });
+      assertEquals(fifo.writeSync(new Uint8Array([1, 2, 3])), 3);
+      const buf = new Uint8Array(3);
+      assertEquals(fifo.readSync(buf), 3);
+      assertEquals(buf, new Uint8Array([1, 2, 3]));
+      fifo.close();
+
+      const info = Deno.lstatSync(path);
+      assert(!info.isFile());
+      assert(!info.isDirectory());
+    }
+  );
+
+  testPerm(
+    { read: true, write: true },
+    async function createPipeReader(): Promise<void> {
+      const path = Deno.makeTempDirSync() + "/fifo";
+      const readerPromise = Deno.createPipe(path, { mode: 0o600 });
+      // Wait for the FIFO to show up, then connect to it like another program
+      // would.
+      let exists = false;
+      while (!exists) {
+        try {
+          Deno.lstatSync(path);
+          exists = true;
+        } catch (e) {
+          await new Promise((resolve): number => setTimeout(resolve, 10));
+        }
+      }
+      const writer = await Deno.open(path, "w");
+      const reader = await readerPromise;
+      assertEquals(Deno.lstatSync(path).mode! & 0o777, 0o600 & ~Deno.umask());
+
+      await Deno.writeAll(writer, new TextEncoder().encode("hello"));
+      writer.close();
+      const data = await Deno.readAll(reader);
+      assertEquals(new TextDecoder().decode(data), "hello");
+      reader.close();
+    }
+  );
+
+  testPerm({ read: true, write: true }, function createPipeSyncExists(): void {
+    let caughtError = false;
+    try {
+      Deno.createPipeSync("package.json", { read: true, write: true });
+    } catch (e) {
+      caughtError = true;
+      assertEquals(e.kind, Deno.ErrorKind.AlreadyExists);
+    }
+    assert(caughtError);
+  });
+}
+
+testPerm({ read: true, write: false }, function createPipeSyncPerm(): void {
+  let caughtError = false;
+  try {
+    Deno.createPipeSync("fifo", { read: true, write: true });
+  } catch (e) {
+    caughtError = true;
+    assertEquals(e.kind, Deno.ErrorKind.PermissionDenied);
+  }
+  assert(caughtError);
+});
@@ -0,0 +1,42 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { sendSync, sendAsync, asyncMsgFromRust } from "./dispatch_json.ts";
+import { setAsyncHandler } from "./dispatch.ts";
+import { core } from "./core.ts";
+import { ErrorKind, DenoError } from "./errors.ts";
+
+// Fetched again when an op is missing, as plugins may have registered ops
+// since.
+let opIds: { [name: string]: number } = {};
+
+function opId(name: string): number {
+  if (!(name in opIds)) {
+    opIds = core.ops();
+  }
+  const id = opIds[name];
+  if (id === undefined) {
+    throw new DenoError(ErrorKind.NotFound, `Unknown op: ${name}`);
+  }
+  return id;
+}
+
+/** Calls the JSON op `name`, which a program embedding Deno registered with
+ * `deno_cli::ops::register_custom_ops()`, and returns its result.
+ *
+ *       const result = Deno.opSync("my_op", { answer: 42 });
+ */
+// eslint-disable-next-line @typescript-eslint/no-explicit-any
+export function opSync(name: string, args: object = {}): any {
+  return sendSync(opId(name), args);
+}
+
+/** Calls the JSON op `name` like `opSync`, except that the op may run
+ * asynchronously.
+ *
+ *       const result = await Deno.opAsync("my_op", { answer: 42 });
+ */
+// eslint-disable-next-line @typescript-eslint/no-explicit-any
+export async function opAsync(name: string, args: object = {}): Promise<any> {
+  const id = opId(name);
+  setAsyncHandler(id, (msg: Uint8Array): void => asyncMsgFromRust(id, msg));
+  return sendAsync(id, args);
+}
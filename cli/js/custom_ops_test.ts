@@ -0,0 +1,24 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { test, assert, assertEquals } from "./test_util.ts";
+
+test(function opSyncUnknown(): void {
+  let err;
+  try {
+    Deno.opSync("not_an_op");
+  } catch (e) {
+    err = e;
+  }
+  assert(err instanceof Deno.DenoError);
+  assertEquals(err.kind, Deno.ErrorKind.NotFound);
+});
+
+test(async function opAsyncUnknown(): Promise<void> {
+  let err;
+  try {
+    await Deno.opAsync("not_an_op");
+  } catch (e) {
+    err = e;
+  }
+  assert(err instanceof Deno.DenoError);
+  assertEquals(err.kind, Deno.ErrorKind.NotFound);
+});
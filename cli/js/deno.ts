@@ -1,7 +1,27 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
 
 // Public deno module.
-export { env, exit, isTTY, execPath, homeDir, hostname } from "./os.ts";
+export {
+  env,
+  exit,
+  isTTY,
+  execPath,
+  homeDir,
+  hostname,
+  ppid,
+  uid,
+  gid,
+  euid,
+  egid,
+  osInfo,
+  OSInfo,
+  loadavg,
+  memInfo,
+  MemInfo,
+  networkInterfaces,
+  NetworkInterface,
+  NetworkInterfaceAddress
+} from "./os.ts";
 export { chdir, cwd } from "./dir.ts";
 export {
   File,
@@ -12,12 +32,23 @@ export {
   stderr,
   read,
   readSync,
+  readPooled,
+  releaseReadBuffer,
   write,
   writeSync,
+  writev,
+  waitReadable,
+  waitWritable,
+  readAt,
+  readAtSync,
+  writeAt,
+  writeAtSync,
   seek,
   seekSync,
   close,
-  OpenMode
+  dup,
+  OpenMode,
+  OpenOptions
 } from "./files.ts";
 export {
   EOF,
@@ -43,24 +74,96 @@ export {
   readAll,
   readAllSync,
   writeAll,
-  writeAllSync
+  writeAllSync,
+  ReadAllOptions
 } from "./buffer.ts";
-export { mkdirSync, mkdir } from "./mkdir.ts";
+export { mkdirSync, mkdir, MkdirOption } from "./mkdir.ts";
+export {
+  createPipeSync,
+  createPipe,
+  CreatePipeOptions
+} from "./create_pipe.ts";
 export {
   makeTempDirSync,
   makeTempDir,
-  MakeTempDirOptions
+  makeTempFileSync,
+  makeTempFile,
+  MakeTempOptions,
+  MakeTempDirOptions,
+  MakeTempFileOptions,
+  linkTempFileSync,
+  linkTempFile
 } from "./make_temp_dir.ts";
-export { chmodSync, chmod } from "./chmod.ts";
-export { chownSync, chown } from "./chown.ts";
-export { utimeSync, utime } from "./utime.ts";
+export {
+  chmodSync,
+  chmod,
+  fchmodSync,
+  fchmod,
+  ChmodOptions
+} from "./chmod.ts";
+export {
+  chownSync,
+  chown,
+  lchownSync,
+  lchown,
+  fchownSync,
+  fchown,
+  ChownOptions
+} from "./chown.ts";
+export { umask } from "./umask.ts";
+export { utimeSync, utime, futimeSync, futime } from "./utime.ts";
+export {
+  flockSync,
+  flock,
+  tryFlockSync,
+  funlockSync,
+  funlock
+} from "./flock.ts";
+export { mmap, MappedFile } from "./mmap.ts";
+export { transfer } from "./transfer.ts";
+export { tee } from "./tee.ts";
+export {
+  isatty,
+  consoleSize,
+  setRaw,
+  SetRawOptions,
+  watchConsoleSize,
+  ConsoleSize
+} from "./tty.ts";
 export { removeSync, remove, RemoveOption } from "./remove.ts";
 export { renameSync, rename } from "./rename.ts";
-export { readFileSync, readFile } from "./read_file.ts";
+export {
+  readFileSync,
+  readFile,
+  readFileChunksSync,
+  readFileChunks,
+  ReadFileChunksOptions
+} from "./read_file.ts";
 export { readDirSync, readDir } from "./read_dir.ts";
+export { globSync, glob, GlobOptions } from "./glob.ts";
 export { copyFileSync, copyFile } from "./copy_file.ts";
 export { readlinkSync, readlink } from "./read_link.ts";
-export { statSync, lstatSync, stat, lstat } from "./stat.ts";
+export { BatchCall, BatchResult, batch, batchSync } from "./batch.ts";
+export { realpathSync, realpath } from "./realpath.ts";
+export {
+  statSync,
+  lstatSync,
+  stat,
+  lstat,
+  fstatSync,
+  fstat
+} from "./stat.ts";
+export { statfsSync, statfs, FsUsage } from "./statfs.ts";
+export {
+  getXattrSync,
+  getXattr,
+  setXattrSync,
+  setXattr,
+  listXattrSync,
+  listXattr,
+  removeXattrSync,
+  removeXattr
+} from "./xattr.ts";
 export { linkSync, link } from "./link.ts";
 export { symlinkSync, symlink } from "./symlink.ts";
 export { writeFileSync, writeFile, WriteFileOptions } from "./write_file.ts";
@@ -68,24 +171,57 @@ export { applySourceMap } from "./error_stack.ts";
 export { ErrorKind, DenoError } from "./errors.ts";
 export {
   permissions,
+  permissionDescriptors,
+  requestPermission,
   revokePermission,
   Permission,
+  PermissionDescriptor,
   Permissions
 } from "./permissions.ts";
-export { truncateSync, truncate } from "./truncate.ts";
+export {
+  truncateSync,
+  truncate,
+  ftruncateSync,
+  ftruncate
+} from "./truncate.ts";
+export { fsyncSync, fsync, fdatasyncSync, fdatasync } from "./fsync.ts";
+export { watchFs, FsEvent, FsEventKind, WatchFsOptions } from "./fs_events.ts";
 export { FileInfo } from "./file_info.ts";
 export { connect, dial, listen, Listener, Conn } from "./net.ts";
 export { dialTLS, listenTLS } from "./tls.ts";
-export { metrics, Metrics } from "./metrics.ts";
+export {
+  metrics,
+  Metrics,
+  OpMetrics,
+  heapStats,
+  HeapStats,
+  writeHeapSnapshot,
+  startCpuProfile,
+  stopCpuProfile,
+  CpuProfile,
+  CpuProfileNode
+} from "./metrics.ts";
 export { resources } from "./resources.ts";
+export { opSync, opAsync } from "./custom_ops.ts";
+export { openPlugin, Plugin, PluginOp } from "./plugins.ts";
+export { Wasi, WasiOptions } from "./wasi.ts";
 export {
+  exec,
+  ExecOptions,
+  getPriority,
   kill,
   run,
+  RunLimits,
   RunOptions,
+  setIds,
+  setPriority,
   Process,
+  ProcessOutput,
   ProcessStatus,
+  Pty,
   Signal
 } from "./process.ts";
+export { signal } from "./signals.ts";
 export { inspect, customInspect } from "./console.ts";
 export { build, OperatingSystem, Arch } from "./build.ts";
 export { version } from "./version.ts";
@@ -4,14 +4,31 @@ import * as json from "./dispatch_json.ts";
 
 // These consts are shared with Rust. Update with care.
 export let OP_READ: number;
+export let OP_READ_POOLED: number;
+export let OP_READ_BUFFER: number;
 export let OP_WRITE: number;
+export let OP_TRANSFER: number;
+export let OP_READ_ALL: number;
+export let OP_WRITE_ALL: number;
+export let OP_WRITEV: number;
+export let OP_WAIT_READY: number;
+export let OP_TEE: number;
 export let OP_EXIT: number;
 export let OP_IS_TTY: number;
 export let OP_ENV: number;
 export let OP_EXEC_PATH: number;
 export let OP_UTIME: number;
+export let OP_FUTIME: number;
+export let OP_FLOCK: number;
+export let OP_MMAP: number;
+export let OP_MMAP_BUFFER: number;
+export let OP_FUNLOCK: number;
+export let OP_FSYNC: number;
+export let OP_READ_AT: number;
+export let OP_WRITE_AT: number;
 export let OP_SET_ENV: number;
 export let OP_GET_ENV: number;
+export let OP_DELETE_ENV: number;
 export let OP_HOME_DIR: number;
 export let OP_START: number;
 export let OP_APPLY_SOURCE_MAP: number;
@@ -20,6 +37,8 @@ export let OP_CACHE: number;
 export let OP_FETCH_SOURCE_FILES: number;
 export let OP_OPEN: number;
 export let OP_CLOSE: number;
+export let OP_DUP: number;
+export let OP_READ_FILE_STREAM: number;
 export let OP_SEEK: number;
 export let OP_FETCH: number;
 export let OP_METRICS: number;
@@ -29,14 +48,17 @@ export let OP_ACCEPT: number;
 export let OP_ACCEPT_TLS: number;
 export let OP_DIAL: number;
 export let OP_SHUTDOWN: number;
+export let OP_DRAIN_LISTENER: number;
 export let OP_LISTEN: number;
 export let OP_LISTEN_TLS: number;
 export let OP_RESOURCES: number;
 export let OP_GET_RANDOM_VALUES: number;
-export let OP_GLOBAL_TIMER_STOP: number;
-export let OP_GLOBAL_TIMER: number;
+export let OP_TIMER: number;
 export let OP_NOW: number;
+export let OP_RING: number;
 export let OP_PERMISSIONS: number;
+export let OP_PERMISSION_DESCRIPTORS: number;
+export let OP_REQUEST_PERMISSION: number;
 export let OP_REVOKE_PERMISSION: number;
 export let OP_CREATE_WORKER: number;
 export let OP_HOST_GET_WORKER_CLOSED: number;
@@ -45,26 +67,78 @@ export let OP_HOST_GET_MESSAGE: number;
 export let OP_WORKER_POST_MESSAGE: number;
 export let OP_WORKER_GET_MESSAGE: number;
 export let OP_RUN: number;
+export let OP_EXEC: number;
+export let OP_SET_IDS: number;
+export let OP_GET_PRIORITY: number;
+export let OP_SET_PRIORITY: number;
 export let OP_RUN_STATUS: number;
+export let OP_RUN_OUTPUT: number;
 export let OP_KILL: number;
+export let OP_KILL_GROUP: number;
+export let OP_PTY_RESIZE: number;
 export let OP_CHDIR: number;
 export let OP_MKDIR: number;
+export let OP_CREATE_PIPE: number;
 export let OP_CHMOD: number;
+export let OP_FCHMOD: number;
 export let OP_CHOWN: number;
+export let OP_LCHOWN: number;
+export let OP_FCHOWN: number;
+export let OP_UMASK: number;
 export let OP_REMOVE: number;
 export let OP_COPY_FILE: number;
 export let OP_STAT: number;
+export let OP_BATCH: number;
+export let OP_FSTAT: number;
+export let OP_STATFS: number;
+export let OP_GET_XATTR: number;
+export let OP_SET_XATTR: number;
+export let OP_LIST_XATTR: number;
+export let OP_REMOVE_XATTR: number;
 export let OP_READ_DIR: number;
+export let OP_GLOB_OPEN: number;
+export let OP_GLOB_NEXT: number;
 export let OP_RENAME: number;
 export let OP_LINK: number;
 export let OP_SYMLINK: number;
 export let OP_READ_LINK: number;
+export let OP_REALPATH: number;
 export let OP_TRUNCATE: number;
+export let OP_FTRUNCATE: number;
 export let OP_MAKE_TEMP_DIR: number;
+export let OP_MAKE_TEMP_FILE: number;
+export let OP_LINK_TEMP_FILE: number;
 export let OP_CWD: number;
 export let OP_FETCH_ASSET: number;
 export let OP_DIAL_TLS: number;
 export let OP_HOSTNAME: number;
+export let OP_PROCESS_IDS: number;
+export let OP_OS_INFO: number;
+export let OP_LOADAVG: number;
+export let OP_MEM_INFO: number;
+export let OP_NETWORK_INTERFACES: number;
+export let OP_FS_EVENTS_OPEN: number;
+export let OP_FS_EVENTS_POLL: number;
+export let OP_ISATTY: number;
+export let OP_CONSOLE_SIZE: number;
+export let OP_SET_RAW: number;
+export let OP_CONSOLE_RESIZE_OPEN: number;
+export let OP_CONSOLE_RESIZE_POLL: number;
+export let OP_SIGNAL_BIND: number;
+export let OP_SIGNAL_POLL: number;
+export let OP_OPEN_PLUGIN: number;
+
+// Ops with a format of their own, like the timer op and the ops of plugins,
+// handle their async messages themselves. So do the ops of embedders, which
+// Deno.opAsync() registers as JSON ops.
+const asyncHandlers = new Map<number, (msg: Uint8Array) => void>();
+
+export function setAsyncHandler(
+  opId: number,
+  handler: (msg: Uint8Array) => void
+): void {
+  asyncHandlers.set(opId, handler);
+}
 
 export function asyncMsgFromRust(opId: number, ui8: Uint8Array): void {
   switch (opId) {
@@ -77,37 +151,74 @@ export function asyncMsgFromRust(opId: number, ui8: Uint8Array): void {
     case OP_ENV:
     case OP_EXEC_PATH:
     case OP_UTIME:
+    case OP_FUTIME:
+    case OP_FLOCK:
+    case OP_FUNLOCK:
+    case OP_FSYNC:
+    case OP_READ_AT:
+    case OP_WRITE_AT:
     case OP_OPEN:
     case OP_SEEK:
+    case OP_READ_FILE_STREAM:
+    case OP_TRANSFER:
+    case OP_READ_POOLED:
+    case OP_READ_ALL:
+    case OP_WRITE_ALL:
+    case OP_WRITEV:
+    case OP_WAIT_READY:
     case OP_FETCH:
     case OP_REPL_START:
     case OP_REPL_READLINE:
     case OP_ACCEPT:
     case OP_ACCEPT_TLS:
     case OP_DIAL:
-    case OP_GLOBAL_TIMER:
     case OP_HOST_GET_WORKER_CLOSED:
     case OP_HOST_GET_MESSAGE:
     case OP_WORKER_GET_MESSAGE:
     case OP_RUN_STATUS:
+    case OP_RUN_OUTPUT:
     case OP_MKDIR:
+    case OP_CREATE_PIPE:
     case OP_CHMOD:
+    case OP_FCHMOD:
     case OP_CHOWN:
+    case OP_LCHOWN:
+    case OP_FCHOWN:
     case OP_REMOVE:
     case OP_COPY_FILE:
     case OP_STAT:
+    case OP_BATCH:
+    case OP_FSTAT:
+    case OP_STATFS:
+    case OP_GET_XATTR:
+    case OP_SET_XATTR:
+    case OP_LIST_XATTR:
+    case OP_REMOVE_XATTR:
     case OP_READ_DIR:
+    case OP_GLOB_NEXT:
     case OP_RENAME:
     case OP_LINK:
     case OP_SYMLINK:
     case OP_READ_LINK:
+    case OP_REALPATH:
     case OP_TRUNCATE:
+    case OP_FTRUNCATE:
     case OP_MAKE_TEMP_DIR:
+    case OP_MAKE_TEMP_FILE:
+    case OP_LINK_TEMP_FILE:
     case OP_DIAL_TLS:
     case OP_FETCH_SOURCE_FILES:
+    case OP_FS_EVENTS_POLL:
+    case OP_CONSOLE_RESIZE_POLL:
+    case OP_SIGNAL_POLL:
       json.asyncMsgFromRust(opId, ui8);
       break;
-    default:
-      throw Error("bad async opId");
+    default: {
+      const asyncHandler = asyncHandlers.get(opId);
+      if (!asyncHandler) {
+        throw Error("bad async opId");
+      }
+      asyncHandler(ui8);
+    }
   }
 }
@@ -12,7 +12,7 @@ interface JsonError {
   message: string;
 }
 
-interface JsonResponse {
+export interface JsonResponse {
   ok?: Ok;
   err?: JsonError;
   promiseId?: number; // Only present in async messages.
@@ -35,7 +35,7 @@ function encode(args: object): Uint8Array {
   return new TextEncoder().encode(s);
 }
 
-function unwrapResponse(res: JsonResponse): Ok {
+export function unwrapResponse(res: JsonResponse): Ok {
   if (res.err != null) {
     throw new DenoError(res.err!.kind, res.err!.message);
   }
@@ -23,9 +23,32 @@ export interface FileInfo {
    */
   created: number | null;
   /** The underlying raw st_mode bits that contain the standard Unix permissions
-   * for this file/directory. TODO Match behavior with Go on windows for mode.
+   * for this file/directory. Windows only has a read-only attribute, so there
+   * this is `0o444` for read-only files and `0o666` otherwise, plus `0o111`
+   * for directories.
    */
   mode: number | null;
+  /** ID of the device containing the file. This is the volume serial number
+   * on Windows. It's a decimal string, as it may not fit in a number.
+   */
+  dev: string | null;
+  /** Inode number of the file, or its file index on Windows, as a decimal
+   * string. Together with `dev` it identifies a file, across renames, for as
+   * long as it exists.
+   */
+  ino: string | null;
+  /** Number of hard links pointing to the file. */
+  nlink: number | null;
+  /** User ID of the owner of the file. Unix only. */
+  uid: number | null;
+  /** Group ID of the owner of the file. Unix only. */
+  gid: number | null;
+  /** Device ID, for device files, as a decimal string. Unix only. */
+  rdev: string | null;
+  /** Preferred block size for I/O on the file. Unix only. */
+  blksize: number | null;
+  /** Number of 512 byte blocks allocated to the file. Unix only. */
+  blocks: number | null;
 
   /** The file or directory name. */
   name: string | null;
@@ -56,14 +79,20 @@ export class FileInfoImpl implements FileInfo {
   created: number | null;
   mode: number | null;
   name: string | null;
+  dev: string | null;
+  ino: string | null;
+  nlink: number | null;
+  uid: number | null;
+  gid: number | null;
+  rdev: string | null;
+  blksize: number | null;
+  blocks: number | null;
 
   /* @internal */
   constructor(private _res: StatResponse) {
     const modified = this._res.modified;
     const accessed = this._res.accessed;
     const created = this._res.created;
-    const hasMode = this._res.hasMode;
-    const mode = this._res.mode; // negative for invalid mode (Windows)
     const name = this._res.name;
 
     this._isFile = this._res.isFile;
@@ -72,9 +101,16 @@ export class FileInfoImpl implements FileInfo {
     this.modified = modified ? modified : null;
     this.accessed = accessed ? accessed : null;
     this.created = created ? created : null;
-    // null on Windows
-    this.mode = hasMode ? mode : null;
+    this.mode = this._res.mode;
     this.name = name ? name : null;
+    this.dev = this._res.dev;
+    this.ino = this._res.ino;
+    this.nlink = this._res.nlink;
+    this.uid = this._res.uid;
+    this.gid = this._res.gid;
+    this.rdev = this._res.rdev;
+    this.blksize = this._res.blksize;
+    this.blocks = this._res.blocks;
   }
 
   isFile(): boolean {
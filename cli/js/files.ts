@@ -11,19 +11,35 @@ import {
   SyncSeeker
 } from "./io.ts";
 import { sendAsyncMinimal, sendSyncMinimal } from "./dispatch_minimal.ts";
+import { core } from "./core.ts";
 import * as dispatch from "./dispatch.ts";
 import {
   sendSync as sendSyncJson,
   sendAsync as sendAsyncJson
 } from "./dispatch_json.ts";
+import { sendRing, ringResult, RING_SEEK } from "./ring.ts";
+
+function openArgs(
+  filename: string,
+  modeOrOptions: OpenMode | OpenOptions
+): object {
+  return typeof modeOrOptions === "string"
+    ? { filename, mode: modeOrOptions }
+    : { filename, options: modeOrOptions };
+}
 
 /** Open a file and return an instance of the `File` object
  *  synchronously.
  *
  *       const file = Deno.openSync("/foo/bar.txt");
+ *       const opts = { write: true, createNew: true };
+ *       const lock = Deno.openSync("/foo/.lock", opts);
  */
-export function openSync(filename: string, mode: OpenMode = "r"): File {
-  const rid = sendSyncJson(dispatch.OP_OPEN, { filename, mode });
+export function openSync(
+  filename: string,
+  modeOrOptions: OpenMode | OpenOptions = "r"
+): File {
+  const rid = sendSyncJson(dispatch.OP_OPEN, openArgs(filename, modeOrOptions));
   return new File(rid);
 }
 
@@ -31,13 +47,17 @@ export function openSync(filename: string, mode: OpenMode = "r"): File {
  *
  *       (async () => {
  *         const file = await Deno.open("/foo/bar.txt");
+ *         const log = await Deno.open("/foo/log.txt", { append: true });
  *       })();
  */
 export async function open(
   filename: string,
-  mode: OpenMode = "r"
+  modeOrOptions: OpenMode | OpenOptions = "r"
 ): Promise<File> {
-  const rid = await sendAsyncJson(dispatch.OP_OPEN, { filename, mode });
+  const rid = await sendAsyncJson(
+    dispatch.OP_OPEN,
+    openArgs(filename, modeOrOptions)
+  );
   return new File(rid);
 }
 
@@ -84,6 +104,38 @@ export async function read(rid: number, p: Uint8Array): Promise<number | EOF> {
   }
 }
 
+const control = new Uint32Array(1);
+const controlBytes = new Uint8Array(control.buffer);
+
+/** Read from a file ID like `read`, except into a buffer which Deno takes from
+ * a pool of them, instead of one allocated by the caller.
+ *
+ * Resolves with a view of the bytes read, or `EOF`. Pass it to
+ * `Deno.releaseReadBuffer()` once done with it, so that the buffer goes back
+ * to the pool right away instead of once it is garbage collected.
+ *
+ *       const buf = await Deno.readPooled(conn.rid);
+ *       if (buf !== Deno.EOF) {
+ *         await Deno.writeAll(other, buf);
+ *         Deno.releaseReadBuffer(buf);
+ *       }
+ */
+export async function readPooled(rid: number): Promise<Uint8Array | EOF> {
+  const bufferRid = await sendAsyncJson(dispatch.OP_READ_POOLED, { rid });
+  if (bufferRid === null) {
+    return EOF;
+  }
+  control[0] = bufferRid;
+  return core.dispatch(dispatch.OP_READ_BUFFER, controlBytes)!;
+}
+
+/** Puts a buffer returned by `Deno.readPooled()` back into the pool. It, and
+ * every other view of it, become empty.
+ */
+export function releaseReadBuffer(buf: Uint8Array): void {
+  core.release(buf);
+}
+
 /** Write synchronously to the file ID the contents of the array buffer.
  *
  * Resolves with the number of bytes written.
@@ -123,35 +175,208 @@ export async function write(rid: number, p: Uint8Array): Promise<number> {
   }
 }
 
+/** Write the contents of all the `bufs` to the file ID, in order, as if they
+ * were concatenated. A TCP connection is usually written with a single
+ * syscall on Linux.
+ *
+ * Ops take a single buffer, so this only avoids copying `bufs` when they are
+ * all views of the same `ArrayBuffer`. Views of separate buffers are copied
+ * into a new one, the same as concatenating them would.
+ *
+ * Resolves with the number of bytes written, which is their total length.
+ *
+ *       const buf = new Uint8Array(4096);
+ *       // Fill in the header and the body, then write both without copying.
+ *       const header = buf.subarray(0, headerLength);
+ *       const body = buf.subarray(1024, 1024 + bodyLength);
+ *       await Deno.writev(conn.rid, [header, body]);
+ */
+export async function writev(
+  rid: number,
+  bufs: Uint8Array[]
+): Promise<number> {
+  // Empty buffers can't be passed to ops.
+  bufs = bufs.filter((buf): boolean => buf.byteLength > 0);
+  if (bufs.length === 0) {
+    return 0;
+  }
+  // An op takes a single buffer, so the buffers are passed as ranges of one.
+  const buffer = bufs[0].buffer;
+  let whole: Uint8Array;
+  let ranges: Array<[number, number]>;
+  if (bufs.every((buf): boolean => buf.buffer === buffer)) {
+    whole = new Uint8Array(buffer);
+    ranges = bufs.map((buf): [number, number] => [
+      buf.byteOffset,
+      buf.byteLength
+    ]);
+  } else {
+    let length = 0;
+    for (const buf of bufs) {
+      length += buf.byteLength;
+    }
+    whole = new Uint8Array(length);
+    ranges = [];
+    let offset = 0;
+    for (const buf of bufs) {
+      whole.set(buf, offset);
+      ranges.push([offset, buf.byteLength]);
+      offset += buf.byteLength;
+    }
+  }
+  return await sendAsyncJson(dispatch.OP_WRITEV, { rid, ranges }, whole);
+}
+
+/** Resolves once the TCP connection `rid` can be read from without waiting,
+ * without reading anything. It stays readable until a read finds out
+ * otherwise, which allows buffering reads in userland.
+ *
+ *       await Deno.waitReadable(conn.rid);
+ */
+export async function waitReadable(rid: number): Promise<void> {
+  await sendAsyncJson(dispatch.OP_WAIT_READY, { rid, write: false });
+}
+
+/** Resolves once the TCP connection `rid` can be written to without waiting,
+ * without writing anything.
+ *
+ *       await Deno.waitWritable(conn.rid);
+ */
+export async function waitWritable(rid: number): Promise<void> {
+  await sendAsyncJson(dispatch.OP_WAIT_READY, { rid, write: true });
+}
+
+/** Read synchronously from a file ID into an array buffer, starting at byte
+ * `offset` of the file. Unlike `readSync` this doesn't use the file's cursor,
+ * so several readers can share one file ID. On Windows the cursor is moved
+ * to the end of the bytes read as a side effect.
+ *
+ * Return `number | EOF` for the operation.
+ *
+ *       const file = Deno.openSync("/foo/bar.db");
+ *       const page = new Uint8Array(4096);
+ *       const nread = Deno.readAtSync(file.rid, page, 3 * 4096);
+ */
+export function readAtSync(
+  rid: number,
+  p: Uint8Array,
+  offset: number
+): number | EOF {
+  const nread = sendSyncJson(dispatch.OP_READ_AT, { rid, offset }, p);
+  return nread === 0 ? EOF : nread;
+}
+
+/** Read from a file ID into an array buffer, starting at byte `offset` of the
+ * file. Unlike `read` this doesn't use the file's cursor, so several readers
+ * can share one file ID. On Windows the cursor is moved to the end of the
+ * bytes read as a side effect.
+ *
+ * Resolves with the `number | EOF` for the operation.
+ *
+ *       const file = await Deno.open("/foo/bar.db");
+ *       const page = new Uint8Array(4096);
+ *       const nread = await Deno.readAt(file.rid, page, 3 * 4096);
+ */
+export async function readAt(
+  rid: number,
+  p: Uint8Array,
+  offset: number
+): Promise<number | EOF> {
+  const args = { rid, offset };
+  const nread = await sendAsyncJson(dispatch.OP_READ_AT, args, p);
+  return nread === 0 ? EOF : nread;
+}
+
+/** Write synchronously to the file ID the contents of the array buffer,
+ * starting at byte `offset` of the file. Unlike `writeSync` this doesn't use
+ * the file's cursor, so several writers can share one file ID. On Windows the
+ * cursor is moved to the end of the bytes written as a side effect.
+ *
+ * Returns the number of bytes written.
+ *
+ *       const file = Deno.openSync("/foo/bar.db", "r+");
+ *       Deno.writeAtSync(file.rid, page, 3 * 4096);
+ */
+export function writeAtSync(
+  rid: number,
+  p: Uint8Array,
+  offset: number
+): number {
+  return sendSyncJson(dispatch.OP_WRITE_AT, { rid, offset }, p);
+}
+
+/** Write to the file ID the contents of the array buffer, starting at byte
+ * `offset` of the file. Unlike `write` this doesn't use the file's cursor, so
+ * several writers can share one file ID. On Windows the cursor is moved to
+ * the end of the bytes written as a side effect.
+ *
+ * Resolves with the number of bytes written.
+ *
+ *       const file = await Deno.open("/foo/bar.db", "r+");
+ *       await Deno.writeAt(file.rid, page, 3 * 4096);
+ */
+export async function writeAt(
+  rid: number,
+  p: Uint8Array,
+  offset: number
+): Promise<number> {
+  return await sendAsyncJson(dispatch.OP_WRITE_AT, { rid, offset }, p);
+}
+
 /** Seek a file ID synchronously to the given offset under mode given by `whence`.
+ *
+ * Returns the new position of the cursor, in bytes from the start of the file.
  *
  *       const file = Deno.openSync("/foo/bar.txt");
- *       Deno.seekSync(file.rid, 0, 0);
+ *       const pos = Deno.seekSync(file.rid, 0, Deno.SeekMode.SEEK_END);
  */
-export function seekSync(rid: number, offset: number, whence: SeekMode): void {
-  sendSyncJson(dispatch.OP_SEEK, { rid, offset, whence });
+export function seekSync(
+  rid: number,
+  offset: number,
+  whence: SeekMode
+): number {
+  if (sendRing(RING_SEEK, rid, offset, whence)) {
+    return ringResult(0);
+  }
+  return sendSyncJson(dispatch.OP_SEEK, { rid, offset, whence });
 }
 
 /** Seek a file ID to the given offset under mode given by `whence`.
+ *
+ * Resolves with the new position of the cursor, in bytes from the start of the
+ * file.
  *
  *      (async () => {
  *        const file = await Deno.open("/foo/bar.txt");
- *        await Deno.seek(file.rid, 0, 0);
+ *        const pos = await Deno.seek(file.rid, 0, Deno.SeekMode.SEEK_END);
  *      })();
  */
 export async function seek(
   rid: number,
   offset: number,
   whence: SeekMode
-): Promise<void> {
-  await sendAsyncJson(dispatch.OP_SEEK, { rid, offset, whence });
+): Promise<number> {
+  return await sendAsyncJson(dispatch.OP_SEEK, { rid, offset, whence });
 }
 
-/** Close the file ID. */
+/** Close the file ID. Operations still pending on it, like a read or an
+ * accept, fail with an `Interrupted` error. */
 export function close(rid: number): void {
   sendSyncJson(dispatch.OP_CLOSE, { rid });
 }
 
+/** Returns a new resource ID for the same file or connection as `rid`. The
+ * two can be closed independently of each other, but share the underlying
+ * handle, including its cursor. Only files, stdio and TCP connections can be
+ * duplicated.
+ *
+ *       const copy = Deno.dup(conn.rid);
+ *       conn.close(); // `copy` is still open.
+ */
+export function dup(rid: number): number {
+  return sendSyncJson(dispatch.OP_DUP, { rid });
+}
+
 /** The Deno abstraction for reading and writing files. */
 export class File
   implements
@@ -180,11 +405,27 @@ export class File
     return readSync(this.rid, p);
   }
 
-  seek(offset: number, whence: SeekMode): Promise<void> {
+  readAt(p: Uint8Array, offset: number): Promise<number | EOF> {
+    return readAt(this.rid, p, offset);
+  }
+
+  readAtSync(p: Uint8Array, offset: number): number | EOF {
+    return readAtSync(this.rid, p, offset);
+  }
+
+  writeAt(p: Uint8Array, offset: number): Promise<number> {
+    return writeAt(this.rid, p, offset);
+  }
+
+  writeAtSync(p: Uint8Array, offset: number): number {
+    return writeAtSync(this.rid, p, offset);
+  }
+
+  seek(offset: number, whence: SeekMode): Promise<number> {
     return seek(this.rid, offset, whence);
   }
 
-  seekSync(offset: number, whence: SeekMode): void {
+  seekSync(offset: number, whence: SeekMode): number {
     return seekSync(this.rid, offset, whence);
   }
 
@@ -226,6 +467,28 @@ export type OpenMode =
   /** Read-write. Behaves like `x` and allows to read from file. */
   | "x+";
 
+/** Options for opening a file, mapped directly onto Rust's
+ * `std::fs::OpenOptions`. At least one of `read`, `write` or `append` must be
+ * set; `create`, `createNew` and `truncate` additionally require `write` or
+ * `append`.
+ */
+export interface OpenOptions {
+  /** Open for reading. */
+  read?: boolean;
+  /** Open for writing. */
+  write?: boolean;
+  /** Open for writing, with every write going to the end of the file. */
+  append?: boolean;
+  /** Truncate the file to zero length if it already exists. */
+  truncate?: boolean;
+  /** Create the file if it doesn't exist yet. */
+  create?: boolean;
+  /** Create a new file, failing if it already exists (`O_EXCL`). Implies
+   * `create` and ignores it.
+   */
+  createNew?: boolean;
+}
+
 /** A factory function for creating instances of `File` associated with the
  * supplied file name.
  * @internal
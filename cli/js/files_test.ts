@@ -172,6 +172,82 @@ testPerm({ read: true, write: true }, async function createFile(): Promise<
   await Deno.remove(tempDir, { recursive: true });
 });
 
+testPerm({ read: true, write: true }, function openOptionsCreateNew(): void {
+  const tempDir = Deno.makeTempDirSync();
+  const filename = tempDir + "/lock";
+  const options = { write: true, createNew: true };
+  const f = Deno.openSync(filename, options);
+  f.close();
+  // A second exclusive create must fail, that's what makes it a lock.
+  let err;
+  try {
+    Deno.openSync(filename, options);
+  } catch (e) {
+    err = e;
+  }
+  assert(!!err);
+  assertEquals(err.kind, Deno.ErrorKind.AlreadyExists);
+  Deno.removeSync(tempDir, { recursive: true });
+});
+
+testPerm(
+  { read: true, write: true },
+  async function openOptionsAppend(): Promise<void> {
+    const tempDir = await Deno.makeTempDir();
+    const filename = tempDir + "/log.txt";
+    const enc = new TextEncoder();
+    await Deno.writeFile(filename, enc.encode("Hello"));
+    const f = await Deno.open(filename, { append: true });
+    await f.write(enc.encode(" world"));
+    f.close();
+    const text = new TextDecoder().decode(await Deno.readFile(filename));
+    assertEquals(text, "Hello world");
+    await Deno.remove(tempDir, { recursive: true });
+  }
+);
+
+testPerm({ read: true, write: true }, function openOptionsInvalid(): void {
+  const invalid: Deno.OpenOptions[] = [
+    {},
+    { read: true, truncate: true },
+    { read: true, create: true },
+    { append: true, truncate: true }
+  ];
+  for (const options of invalid) {
+    let err;
+    try {
+      Deno.openSync("tests/hello.txt", options);
+    } catch (e) {
+      err = e;
+    }
+    assert(!!err);
+    assertEquals(err.kind, Deno.ErrorKind.InvalidInput);
+  }
+});
+
+testPerm({ read: true }, function openOptionsWritePerm(): void {
+  let err;
+  try {
+    Deno.openSync("tests/hello.txt", { read: true, append: true });
+  } catch (e) {
+    err = e;
+  }
+  assert(!!err);
+  assertEquals(err.kind, Deno.ErrorKind.PermissionDenied);
+});
+
+testPerm({ read: true }, function openModeInvalid(): void {
+  let err;
+  try {
+    // eslint-disable-next-line @typescript-eslint/no-explicit-any
+    Deno.openSync("tests/hello.txt", "z" as any);
+  } catch (e) {
+    err = e;
+  }
+  assert(!!err);
+  assertEquals(err.kind, Deno.ErrorKind.InvalidInput);
+});
+
 testPerm({ read: true, write: true }, async function openModeWrite(): Promise<
   void
 > {
@@ -308,6 +384,26 @@ testPerm({ read: true }, function seekSyncEnd(): void {
   assertEquals(decoded, "world!");
 });
 
+testPerm({ read: true }, async function seekReturnsPosition(): Promise<void> {
+  const filename = "tests/hello.txt";
+  const file = await Deno.open(filename);
+  assertEquals(await file.seek(6, Deno.SeekMode.SEEK_START), 6);
+  assertEquals(await file.seek(2, Deno.SeekMode.SEEK_CURRENT), 8);
+  const len = Deno.statSync(filename).len;
+  assertEquals(await file.seek(0, Deno.SeekMode.SEEK_END), len);
+  file.close();
+});
+
+testPerm({ read: true }, function seekSyncReturnsPosition(): void {
+  const filename = "tests/hello.txt";
+  const file = Deno.openSync(filename);
+  assertEquals(file.seekSync(6, Deno.SeekMode.SEEK_START), 6);
+  assertEquals(file.seekSync(-2, Deno.SeekMode.SEEK_CURRENT), 4);
+  const len = Deno.statSync(filename).len;
+  assertEquals(file.seekSync(0, Deno.SeekMode.SEEK_END), len);
+  file.close();
+});
+
 testPerm({ read: true }, async function seekMode(): Promise<void> {
   const filename = "tests/hello.txt";
   const file = await Deno.open(filename);
@@ -327,3 +423,152 @@ testPerm({ read: true }, async function seekMode(): Promise<void> {
   await file.read(buf); // "H"
   assertEquals(new TextDecoder().decode(buf), "H");
 });
+
+testPerm({ read: true }, function seekSyncMode(): void {
+  const file = Deno.openSync("tests/hello.txt");
+  let err;
+  try {
+    file.seekSync(1, -1);
+  } catch (e) {
+    err = e;
+  }
+  assert(!!err);
+  assertEquals(err.kind, Deno.ErrorKind.InvalidSeekMode);
+  // Many more seeks than the records of the ring they go through.
+  for (let i = 0; i < 40; i++) {
+    assertEquals(file.seekSync(i % 5, Deno.SeekMode.SEEK_START), i % 5);
+  }
+  file.close();
+});
+
+testPerm({ read: true, write: true }, function readAtSyncWriteAtSync(): void {
+  const filename = Deno.makeTempDirSync() + "/test.bin";
+  const file = Deno.openSync(filename, "w+");
+  assertEquals(file.writeAtSync(new Uint8Array([4, 5, 6]), 3), 3);
+  assertEquals(Deno.writeAtSync(file.rid, new Uint8Array([1, 2, 3]), 0), 3);
+
+  const buf = new Uint8Array(4);
+  assertEquals(Deno.readAtSync(file.rid, buf, 2), 4);
+  assertEquals(buf, new Uint8Array([3, 4, 5, 6]));
+  assertEquals(file.readAtSync(buf, 6), Deno.EOF);
+  file.close();
+
+  assertEquals(Deno.readFileSync(filename), new Uint8Array([1, 2, 3, 4, 5, 6]));
+});
+
+testPerm(
+  { read: true, write: true },
+  async function readAtConcurrent(): Promise<void> {
+    const filename = Deno.makeTempDirSync() + "/test.bin";
+    const data = new Uint8Array(256).map((_, i): number => i);
+    Deno.writeFileSync(filename, data);
+
+    const file = await Deno.open(filename);
+    const chunks = [0, 64, 128, 192].map(
+      async (offset): Promise<Uint8Array> => {
+        const buf = new Uint8Array(64);
+        assertEquals(await file.readAt(buf, offset), 64);
+        return buf;
+      }
+    );
+    const joined = new Uint8Array(256);
+    (await Promise.all(chunks)).forEach((chunk, i): void => {
+      joined.set(chunk, i * 64);
+    });
+    assertEquals(joined, data);
+
+    // The cursor wasn't used.
+    if (Deno.build.os !== "win") {
+      const buf = new Uint8Array(1);
+      assertEquals(await file.read(buf), 1);
+      assertEquals(buf[0], 0);
+    }
+    file.close();
+  }
+);
+
+testPerm({ read: true, write: true }, async function writevFile(): Promise<
+  void
+> {
+  const filename = Deno.makeTempDirSync() + "/test.bin";
+  const file = await Deno.open(filename, "w");
+  const bufs = [
+    new Uint8Array([1, 2]),
+    new Uint8Array(0),
+    new Uint8Array([3]),
+    new Uint8Array([4, 5, 6])
+  ];
+  assertEquals(await Deno.writev(file.rid, bufs), 6);
+  assertEquals(await Deno.writev(file.rid, []), 0);
+  // Views of the same buffer, out of order.
+  const shared = new Uint8Array([9, 7, 8]);
+  const views = [shared.subarray(1), shared.subarray(0, 1)];
+  assertEquals(await Deno.writev(file.rid, views), 3);
+  file.close();
+
+  assertEquals(
+    Deno.readFileSync(filename),
+    new Uint8Array([1, 2, 3, 4, 5, 6, 7, 8, 9])
+  );
+});
+
+testPerm({ read: true }, function writeAtSyncReadOnly(): void {
+  const file = Deno.openSync("package.json");
+  let caughtError = false;
+  try {
+    Deno.writeAtSync(file.rid, new Uint8Array([1]), 0);
+  } catch (e) {
+    caughtError = true;
+  }
+  assert(caughtError);
+  file.close();
+});
+
+testPerm({ read: true }, async function dupOutlivesOriginal(): Promise<void> {
+  const file = await Deno.open("tests/hello.txt");
+  const rid = Deno.dup(file.rid);
+  assert(rid !== file.rid);
+  file.close();
+  assert(!(file.rid in Deno.resources()));
+  assertEquals(Deno.resources()[rid], "fsFile");
+
+  const buf = new Uint8Array(5);
+  const nread = await Deno.read(rid, buf);
+  assertEquals(nread, 5);
+  Deno.close(rid);
+});
+
+testPerm({ read: true }, function dupSharesCursor(): void {
+  const file = Deno.openSync("tests/hello.txt");
+  const rid = Deno.dup(file.rid);
+  file.seekSync(6, Deno.SeekMode.SEEK_START);
+  const buf = new Uint8Array(5);
+  Deno.readSync(rid, buf);
+  assertEquals(new TextDecoder().decode(buf), "world");
+  Deno.close(rid);
+  file.close();
+});
+
+test(function dupBadResource(): void {
+  let err;
+  try {
+    Deno.dup(9999);
+  } catch (e) {
+    err = e;
+  }
+  assert(!!err);
+  assertEquals(err.kind, Deno.ErrorKind.BadResource);
+});
+
+testPerm({ read: true }, async function readPooledFile(): Promise<void> {
+  const file = await Deno.open("tests/hello.txt");
+  const buf = await Deno.readPooled(file.rid);
+  assert(buf !== Deno.EOF);
+  assertEquals(buf, Deno.readFileSync("tests/hello.txt"));
+  Deno.releaseReadBuffer(buf);
+  assertEquals(buf.byteLength, 0);
+
+  assertEquals(await Deno.readPooled(file.rid), Deno.EOF);
+  assert(!Object.values(Deno.resources()).includes("readBuffer"));
+  file.close();
+});
@@ -0,0 +1,57 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { sendSync, sendAsync } from "./dispatch_json.ts";
+import * as dispatch from "./dispatch.ts";
+
+/** Synchronously places an advisory lock on the whole file referenced by
+ * `rid`, waiting until any conflicting lock is released. Many shared locks
+ * can be held at once, an exclusive lock excludes all others. Uses `flock(2)`
+ * on Unix and `LockFileEx` on Windows.
+ *
+ *       const file = Deno.openSync("deno.lock", "w");
+ *       Deno.flockSync(file.rid, true);
+ */
+export function flockSync(rid: number, exclusive = false): void {
+  sendSync(dispatch.OP_FLOCK, { rid, exclusive, nonblocking: false });
+}
+
+/** Places an advisory lock on the whole file referenced by `rid`, resolving
+ * once any conflicting lock is released. Many shared locks can be held at
+ * once, an exclusive lock excludes all others. Uses `flock(2)` on Unix and
+ * `LockFileEx` on Windows.
+ *
+ *       const file = await Deno.open("deno.lock", "w");
+ *       await Deno.flock(file.rid, true);
+ */
+export async function flock(rid: number, exclusive = false): Promise<void> {
+  await sendAsync(dispatch.OP_FLOCK, { rid, exclusive, nonblocking: false });
+}
+
+/** Attempts to place an advisory lock on the whole file referenced by `rid`
+ * without waiting. Returns `false` if a conflicting lock is held.
+ *
+ *       const file = Deno.openSync("deno.lock", "w");
+ *       if (!Deno.tryFlockSync(file.rid, true)) {
+ *         throw new Error("Another instance is already running");
+ *       }
+ */
+export function tryFlockSync(rid: number, exclusive = false): boolean {
+  return sendSync(dispatch.OP_FLOCK, { rid, exclusive, nonblocking: true });
+}
+
+/** Synchronously releases the advisory lock held on the file referenced by
+ * `rid`. Closing the file releases the lock as well.
+ *
+ *       Deno.funlockSync(file.rid);
+ */
+export function funlockSync(rid: number): void {
+  sendSync(dispatch.OP_FUNLOCK, { rid });
+}
+
+/** Releases the advisory lock held on the file referenced by `rid`. Closing
+ * the file releases the lock as well.
+ *
+ *       await Deno.funlock(file.rid);
+ */
+export async function funlock(rid: number): Promise<void> {
+  await sendAsync(dispatch.OP_FUNLOCK, { rid });
+}
@@ -0,0 +1,72 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { testPerm, assert, assertEquals } from "./test_util.ts";
+
+testPerm({ read: true, write: true }, function flockSyncExclusive(): void {
+  const filename = Deno.makeTempDirSync() + "/test.lock";
+  const a = Deno.openSync(filename, "w");
+  const b = Deno.openSync(filename, "r");
+
+  Deno.flockSync(a.rid, true);
+  assert(!Deno.tryFlockSync(b.rid, false));
+  assert(!Deno.tryFlockSync(b.rid, true));
+
+  Deno.funlockSync(a.rid);
+  assert(Deno.tryFlockSync(b.rid, true));
+  assert(!Deno.tryFlockSync(a.rid, false));
+
+  a.close();
+  b.close();
+});
+
+testPerm({ read: true, write: true }, function flockSyncShared(): void {
+  const filename = Deno.makeTempDirSync() + "/test.lock";
+  const a = Deno.openSync(filename, "w");
+  const b = Deno.openSync(filename, "r");
+
+  Deno.flockSync(a.rid, false);
+  assert(Deno.tryFlockSync(b.rid, false));
+
+  Deno.funlockSync(a.rid);
+  Deno.funlockSync(b.rid);
+  a.close();
+  b.close();
+});
+
+testPerm(
+  { read: true, write: true },
+  async function flockWaitsForUnlock(): Promise<void> {
+    const filename = Deno.makeTempDirSync() + "/test.lock";
+    const a = await Deno.open(filename, "w");
+    const b = await Deno.open(filename, "r");
+
+    await Deno.flock(a.rid, true);
+    let acquired = false;
+    const pending = Deno.flock(b.rid, true).then((): void => {
+      acquired = true;
+    });
+    assert(!acquired);
+
+    await Deno.funlock(a.rid);
+    await pending;
+    assert(acquired);
+
+    await Deno.funlock(b.rid);
+    a.close();
+    b.close();
+  }
+);
+
+testPerm({ read: true, write: true }, function flockSyncClosedRid(): void {
+  const filename = Deno.makeTempDirSync() + "/test.lock";
+  const file = Deno.openSync(filename, "w");
+  file.close();
+
+  let err;
+  try {
+    Deno.flockSync(file.rid);
+  } catch (e) {
+    err = e;
+  }
+  assert(!!err);
+  assertEquals(err.kind, Deno.ErrorKind.BadResource);
+});
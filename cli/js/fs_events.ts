@@ -0,0 +1,74 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { sendSync, sendAsync } from "./dispatch_json.ts";
+import * as dispatch from "./dispatch.ts";
+import { close } from "./files.ts";
+
+export type FsEventKind = "create" | "modify" | "remove" | "rename";
+
+export interface FsEvent {
+  kind: FsEventKind;
+  paths: string[];
+}
+
+export interface WatchFsOptions {
+  /** Watch directories recursively. Defaults to `true`. */
+  recursive?: boolean;
+  /** Only report these kinds of events. Filtering is done on the privileged
+   * side, so events of other kinds never reach JS. Defaults to all kinds.
+   */
+  kinds?: FsEventKind[];
+  /** Collect events for this many milliseconds after the first one and send
+   * them together, dropping duplicates. This bounds how often JS is woken up
+   * during bursts of changes, like an editor saving many files at once.
+   * Defaults to no debouncing.
+   */
+  debounce?: number;
+  /** Drop events for paths matching any of these globs, relative to the
+   * watched path, like `"**\/node_modules/**"` or `".git/**"`. Matching
+   * happens on the privileged side.
+   */
+  ignore?: string[];
+}
+
+class FsEvents implements AsyncIterableIterator<FsEvent> {
+  readonly rid: number;
+
+  constructor(paths: string[], options: WatchFsOptions) {
+    const { recursive = true, kinds, debounce, ignore } = options;
+    this.rid = sendSync(dispatch.OP_FS_EVENTS_OPEN, {
+      paths,
+      recursive,
+      kinds,
+      debounce,
+      ignore
+    });
+  }
+
+  async next(): Promise<IteratorResult<FsEvent>> {
+    return await sendAsync(dispatch.OP_FS_EVENTS_POLL, { rid: this.rid });
+  }
+
+  async return(value?: FsEvent): Promise<IteratorResult<FsEvent>> {
+    close(this.rid);
+    return { value, done: true };
+  }
+
+  [Symbol.asyncIterator](): AsyncIterableIterator<FsEvent> {
+    return this;
+  }
+}
+
+/** Watch for file system events against one or more `paths`, which can be
+ * files or directories. Requires `allow-read`.
+ *
+ *       const iter = Deno.watchFs("/", { kinds: ["create", "remove"] });
+ *       for await (const event of iter) {
+ *         console.log(">>>> event", event);
+ *       }
+ */
+export function watchFs(
+  paths: string | string[],
+  options: WatchFsOptions = {}
+): AsyncIterableIterator<FsEvent> {
+  return new FsEvents(Array.isArray(paths) ? paths : [paths], options);
+}
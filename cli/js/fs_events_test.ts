@@ -0,0 +1,122 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { testPerm, assert, assertEquals } from "./test_util.ts";
+
+testPerm({ read: false }, function watchFsPermissions(): void {
+  let thrown = false;
+  try {
+    Deno.watchFs(".");
+  } catch (err) {
+    assertEquals(err.kind, Deno.ErrorKind.PermissionDenied);
+    thrown = true;
+  }
+  assert(thrown);
+});
+
+testPerm({ read: true }, function watchFsInvalidPath(): void {
+  let thrown = false;
+  try {
+    Deno.watchFs("non-existant.file");
+  } catch (err) {
+    assertEquals(err.kind, Deno.ErrorKind.NotFound);
+    thrown = true;
+  }
+  assert(thrown);
+});
+
+testPerm({ read: true }, function watchFsInvalidKind(): void {
+  let thrown = false;
+  try {
+    // eslint-disable-next-line @typescript-eslint/no-explicit-any
+    Deno.watchFs(".", { kinds: ["bogus" as any] });
+  } catch (err) {
+    assertEquals(err.kind, Deno.ErrorKind.InvalidInput);
+    thrown = true;
+  }
+  assert(thrown);
+});
+
+async function getTwoEvents(
+  iter: AsyncIterableIterator<Deno.FsEvent>
+): Promise<Deno.FsEvent[]> {
+  const events = [];
+  for await (const event of iter) {
+    events.push(event);
+    if (events.length >= 2) break;
+  }
+  return events;
+}
+
+testPerm(
+  { read: true, write: true },
+  async function watchFsRecursive(): Promise<void> {
+    const testDir = Deno.makeTempDirSync();
+    const iter = Deno.watchFs(testDir, { kinds: ["create"] });
+
+    // Asynchronously capture two fs events.
+    const eventsPromise = getTwoEvents(iter);
+
+    // Make some random file system activity.
+    Deno.mkdirSync(testDir + "/nested");
+    const data = new Uint8Array([0, 1, 2]);
+    Deno.writeFileSync(testDir + "/nested/file1.txt", data);
+    Deno.writeFileSync(testDir + "/file2.txt", data);
+
+    // We should have gotten two fs events, and they should all be creates
+    // because every other kind was filtered out.
+    const events = await eventsPromise;
+    assert(events.length >= 2);
+    for (const event of events) {
+      assertEquals(event.kind, "create");
+    }
+    assert(events.some(e => e.paths[0].includes("file1.txt")));
+  }
+);
+
+testPerm({ read: true }, function watchFsInvalidIgnore(): void {
+  let thrown = false;
+  try {
+    Deno.watchFs(".", { ignore: ["[unclosed"] });
+  } catch (err) {
+    assertEquals(err.kind, Deno.ErrorKind.InvalidInput);
+    thrown = true;
+  }
+  assert(thrown);
+});
+
+testPerm({ read: true, write: true }, async function watchFsIgnore(): Promise<
+  void
+> {
+  const testDir = Deno.makeTempDirSync();
+  const iter = Deno.watchFs(testDir, {
+    kinds: ["create"],
+    ignore: ["*.tmp"]
+  });
+  const eventsPromise = getTwoEvents(iter);
+
+  const data = new Uint8Array([0, 1, 2]);
+  Deno.writeFileSync(testDir + "/file1.tmp", data);
+  Deno.writeFileSync(testDir + "/file2.txt", data);
+  Deno.writeFileSync(testDir + "/file3.txt", data);
+
+  const events = await eventsPromise;
+  for (const event of events) {
+    assert(!event.paths[0].endsWith(".tmp"));
+  }
+});
+
+testPerm({ read: true, write: true }, async function watchFsDebounce(): Promise<
+  void
+> {
+  const testDir = Deno.makeTempDirSync();
+  const iter = Deno.watchFs(testDir, { kinds: ["create"], debounce: 50 });
+  const eventsPromise = getTwoEvents(iter);
+
+  const data = new Uint8Array([0, 1, 2]);
+  Deno.writeFileSync(testDir + "/file1.txt", data);
+  Deno.writeFileSync(testDir + "/file2.txt", data);
+
+  // Both creates are delivered, once each.
+  const events = await eventsPromise;
+  assert(events.some(e => e.paths[0].includes("file1.txt")));
+  assert(events.some(e => e.paths[0].includes("file2.txt")));
+});
@@ -0,0 +1,45 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { sendSync, sendAsync } from "./dispatch_json.ts";
+import * as dispatch from "./dispatch.ts";
+
+/** Synchronously flushes the data and metadata of the file `rid` to stable
+ * storage, like fsync(2).
+ *
+ *       Deno.writeAllSync(file, entry);
+ *       Deno.fsyncSync(file.rid);
+ */
+export function fsyncSync(rid: number): void {
+  sendSync(dispatch.OP_FSYNC, { rid, dataOnly: false });
+}
+
+/** Flushes the data and metadata of the file `rid` to stable storage, like
+ * fsync(2). Once this resolves, what was written survives a crash or power
+ * loss.
+ *
+ *       await Deno.writeAll(file, entry);
+ *       await Deno.fsync(file.rid);
+ */
+export async function fsync(rid: number): Promise<void> {
+  await sendAsync(dispatch.OP_FSYNC, { rid, dataOnly: false });
+}
+
+/** Synchronously flushes the data of the file `rid` to stable storage, like
+ * fdatasync(2).
+ *
+ *       Deno.writeAllSync(file, entry);
+ *       Deno.fdatasyncSync(file.rid);
+ */
+export function fdatasyncSync(rid: number): void {
+  sendSync(dispatch.OP_FSYNC, { rid, dataOnly: true });
+}
+
+/** Flushes the data of the file `rid` to stable storage, like fdatasync(2).
+ * Metadata such as the modification time is only flushed when it's needed to
+ * read the data back, which saves a disk write compared to `fsync`.
+ *
+ *       await Deno.writeAll(file, entry);
+ *       await Deno.fdatasync(file.rid);
+ */
+export async function fdatasync(rid: number): Promise<void> {
+  await sendAsync(dispatch.OP_FSYNC, { rid, dataOnly: true });
+}
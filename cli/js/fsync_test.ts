@@ -0,0 +1,37 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { testPerm, assert, assertEquals } from "./test_util.ts";
+
+testPerm({ read: true, write: true }, function fsyncSyncSuccess(): void {
+  const filename = Deno.makeTempDirSync() + "/test.txt";
+  const file = Deno.openSync(filename, "w+");
+  const data = new Uint8Array(1024).fill(7);
+  Deno.writeAllSync(file, data);
+  Deno.fsyncSync(file.rid);
+  Deno.fdatasyncSync(file.rid);
+  assertEquals(Deno.statSync(filename).len, data.length);
+  file.close();
+});
+
+testPerm({ read: true, write: true }, async function fsyncSuccess(): Promise<
+  void
+> {
+  const filename = Deno.makeTempDirSync() + "/test.txt";
+  const file = await Deno.open(filename, "w+");
+  const data = new Uint8Array(1024).fill(7);
+  await Deno.writeAll(file, data);
+  await Deno.fsync(file.rid);
+  await Deno.fdatasync(file.rid);
+  assertEquals((await Deno.stat(filename)).len, data.length);
+  file.close();
+});
+
+testPerm({ read: true }, function fsyncSyncBadResource(): void {
+  let caughtError = false;
+  try {
+    Deno.fsyncSync(9999);
+  } catch (e) {
+    caughtError = true;
+    assertEquals(e.kind, Deno.ErrorKind.BadResource);
+  }
+  assert(caughtError);
+});
@@ -0,0 +1,65 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { sendSync, sendAsync } from "./dispatch_json.ts";
+import * as dispatch from "./dispatch.ts";
+import { close } from "./files.ts";
+
+export interface GlobOptions {
+  /** How many paths are sent over from the privileged side at a time.
+   * Defaults to `256`.
+   */
+  batchSize?: number;
+}
+
+interface GlobBatch {
+  paths: string[];
+  done: boolean;
+}
+
+/** Synchronously expands the glob `pattern` against the file system and
+ * returns the matched paths, sorted per directory. Supports `*`, `?`, `[...]`,
+ * `{a,b}` and `**`, which matches any number of directories. Requires
+ * `allow-read` for the directory named by the literal part of the pattern.
+ *
+ *       const sources = Deno.globSync("src/**\/*.{ts,tsx}");
+ */
+export function globSync(pattern: string, options: GlobOptions = {}): string[] {
+  const { batchSize = 256 } = options;
+  const rid = sendSync(dispatch.OP_GLOB_OPEN, { pattern });
+  const paths: string[] = [];
+  try {
+    let batch: GlobBatch;
+    do {
+      batch = sendSync(dispatch.OP_GLOB_NEXT, { rid, batchSize });
+      paths.push(...batch.paths);
+    } while (!batch.done);
+  } finally {
+    close(rid);
+  }
+  return paths;
+}
+
+/** Expands the glob `pattern` against the file system, yielding matched paths
+ * as the walk progresses. Supports `*`, `?`, `[...]`, `{a,b}` and `**`, which
+ * matches any number of directories. Requires `allow-read` for the directory
+ * named by the literal part of the pattern.
+ *
+ *       for await (const path of Deno.glob("src/**\/*.{ts,tsx}")) {
+ *         console.log(path);
+ *       }
+ */
+export async function* glob(
+  pattern: string,
+  options: GlobOptions = {}
+): AsyncIterableIterator<string> {
+  const { batchSize = 256 } = options;
+  const rid = sendSync(dispatch.OP_GLOB_OPEN, { pattern });
+  try {
+    let batch: GlobBatch;
+    do {
+      batch = await sendAsync(dispatch.OP_GLOB_NEXT, { rid, batchSize });
+      yield* batch.paths;
+    } while (!batch.done);
+  } finally {
+    close(rid);
+  }
+}
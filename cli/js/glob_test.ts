@@ -0,0 +1,104 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { testPerm, assert, assertEquals } from "./test_util.ts";
+
+function makeTree(): string {
+  const root = Deno.makeTempDirSync();
+  const data = new Uint8Array();
+  Deno.mkdirSync(root + "/a/b", true);
+  Deno.writeFileSync(root + "/x.ts", data);
+  Deno.writeFileSync(root + "/y.js", data);
+  Deno.writeFileSync(root + "/z.md", data);
+  Deno.writeFileSync(root + "/a/b/deep.ts", data);
+  return root;
+}
+
+testPerm({ read: true, write: true }, function globSyncStar(): void {
+  const root = makeTree();
+  assertEquals(Deno.globSync(root + "/*.ts"), [root + "/x.ts"]);
+  assertEquals(Deno.globSync(root + "/*.{ts,js}"), [
+    root + "/x.ts",
+    root + "/y.js"
+  ]);
+});
+
+testPerm({ read: true, write: true }, function globSyncGlobstar(): void {
+  const root = makeTree();
+  assertEquals(Deno.globSync(root + "/**/*.ts"), [
+    root + "/a/b/deep.ts",
+    root + "/x.ts"
+  ]);
+  assertEquals(Deno.globSync(root + "/*/*/*.ts"), [root + "/a/b/deep.ts"]);
+});
+
+testPerm({ read: true, write: true }, function globSyncLiteral(): void {
+  const root = makeTree();
+  assertEquals(Deno.globSync(root + "/z.md"), [root + "/z.md"]);
+  assertEquals(Deno.globSync(root + "/missing.md"), []);
+});
+
+testPerm({ read: true, write: true }, function globSyncUnreadableRoot(): void {
+  if (Deno.build.os === "win") {
+    return;
+  }
+  const root = makeTree();
+  Deno.chmodSync(root + "/a", 0);
+  let readDirError;
+  try {
+    Deno.readDirSync(root + "/a");
+  } catch (e) {
+    readDirError = e;
+  }
+  let globError;
+  try {
+    Deno.globSync(root + "/a/*");
+  } catch (e) {
+    globError = e;
+  }
+  Deno.chmodSync(root + "/a", 0o755);
+  // The glob fails like listing the directory does, unless the mode doesn't
+  // stop us, e.g. as root.
+  if (readDirError) {
+    assert(!!globError);
+    assertEquals(globError.kind, Deno.ErrorKind.PermissionDenied);
+  }
+});
+
+testPerm({ read: true, write: true }, async function globBatches(): Promise<
+  void
+> {
+  const root = makeTree();
+  const paths = [];
+  for await (const path of Deno.glob(root + "/**", { batchSize: 1 })) {
+    paths.push(path);
+  }
+  assertEquals(paths, [
+    root + "/a",
+    root + "/a/b",
+    root + "/a/b/deep.ts",
+    root + "/x.ts",
+    root + "/y.js",
+    root + "/z.md"
+  ]);
+});
+
+testPerm({ read: true }, function globSyncInvalidPattern(): void {
+  let caughtError = false;
+  try {
+    Deno.globSync("tests/[");
+  } catch (e) {
+    caughtError = true;
+    assertEquals(e.kind, Deno.ErrorKind.InvalidInput);
+  }
+  assert(caughtError);
+});
+
+testPerm({ read: false }, function globSyncPerm(): void {
+  let caughtError = false;
+  try {
+    Deno.globSync("tests/*.txt");
+  } catch (e) {
+    caughtError = true;
+    assertEquals(e.kind, Deno.ErrorKind.PermissionDenied);
+  }
+  assert(caughtError);
+});
@@ -32,6 +32,7 @@ import * as request from "./request.ts";
 // These imports are not exposed and therefore are fine to just import the
 // symbols required.
 import { core } from "./core.ts";
+import { setUnloaded } from "./os.ts";
 
 // During the build process, augmentations to the variable `window` in this
 // file are tracked and created as part of default library that is built into
@@ -172,6 +173,13 @@ window.addEventListener(
     }
   }
 );
+// Lets Deno.exit() know not to unload again, e.g. when called by a handler.
+window.addEventListener(
+  "unload",
+  (): void => {
+    setUnloaded();
+  }
+);
 // Registers the handler for window.onunload function.
 window.addEventListener(
   "unload",
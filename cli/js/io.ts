@@ -80,11 +80,11 @@ export interface Seeker {
    * any positive offset is legal, but the behavior of subsequent I/O operations
    * on the underlying object is implementation-dependent.
    */
-  seek(offset: number, whence: SeekMode): Promise<void>;
+  seek(offset: number, whence: SeekMode): Promise<number>;
 }
 
 export interface SyncSeeker {
-  seekSync(offset: number, whence: SeekMode): void;
+  seekSync(offset: number, whence: SeekMode): number;
 }
 
 // https://golang.org/pkg/io/#ReadCloser
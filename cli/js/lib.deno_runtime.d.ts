@@ -28,16 +28,118 @@ declare namespace Deno {
    *       console.log(Deno.hostname());
    */
   export function hostname(): string;
-  /** Exit the Deno process with optional exit code. */
+  /** Returns the pid of the parent process. */
+  export function ppid(): number;
+  /** Returns the real user id of the process, or `null` on Windows. */
+  export function uid(): number | null;
+  /** Returns the real group id of the process, or `null` on Windows. */
+  export function gid(): number | null;
+  /** Returns the effective user id of the process, which its permissions are
+   * checked against, or `null` on Windows.
+   *
+   *       if (Deno.euid() === 0) {
+   *         throw new Error("refusing to run as root");
+   *       }
+   */
+  export function euid(): number | null;
+  /** Returns the effective group id of the process, or `null` on Windows. */
+  export function egid(): number | null;
+  /** Information about the operating system, as returned by `Deno.osInfo()`. */
+  export interface OSInfo {
+    /** Same as `Deno.build.os`. */
+    os: OperatingSystem;
+    /** Same as `Deno.build.arch`. */
+    arch: Arch;
+    /** Name of the OS or distribution, e.g. `"Ubuntu"`, `"macOS"` or
+     * `"Windows"`. */
+    name: string;
+    /** Version of the OS or distribution, e.g. `"18.04"` or `"10.14.6"`. Empty
+     * if it can't be determined. */
+    version: string;
+    /** Name of the kernel, e.g. `"Linux"`, `"Darwin"` or `"Windows_NT"`. */
+    kernelName: string;
+    /** Release of the kernel, e.g. `"5.0.0-29-generic"`. */
+    kernelRelease: string;
+    /** Build details of the kernel. Empty on Windows. */
+    kernelVersion: string;
+    /** Hardware architecture as reported by the OS, e.g. `"x86_64"`. */
+    machine: string;
+  }
+  /** Get structured information about the operating system and kernel.
+   * Requires the `--allow-env` flag.
+   *
+   *       const { name, version, kernelRelease } = Deno.osInfo();
+   */
+  export function osInfo(): OSInfo;
+  /** Returns the 1, 5 and 15 minute load averages. Always `[0, 0, 0]` on
+   * Windows, which has no such concept.
+   * Requires the `--allow-env` flag.
+   *
+   *       const [oneMinute] = Deno.loadavg();
+   */
+  export function loadavg(): number[];
+  /** Memory figures returned by `Deno.memInfo()`, all in bytes. */
+  export interface MemInfo {
+    /** Total physical memory. */
+    total: number;
+    /** Memory that is not used at all. */
+    free: number;
+    /** Memory that can be handed to new allocations without swapping,
+     * including reclaimable caches. */
+    available: number;
+    swapTotal: number;
+    swapFree: number;
+    /** Resident set size of the current process. */
+    rss: number;
+  }
+  /** Get system memory usage and the resident set size of this process.
+   * Requires the `--allow-env` flag.
+   *
+   *       const { available, total } = Deno.memInfo();
+   */
+  export function memInfo(): MemInfo;
+  /** An address assigned to a network interface. */
+  export interface NetworkInterfaceAddress {
+    family: "IPv4" | "IPv6";
+    address: string;
+    /** `null` if the OS doesn't report a netmask for the address. */
+    netmask: string | null;
+  }
+  /** A network interface, as returned by `Deno.networkInterfaces()`. */
+  export interface NetworkInterface {
+    name: string;
+    /** Hardware address, e.g. `"00:1a:2b:3c:4d:5e"`. `null` for interfaces
+     * that don't have one, like loopback. */
+    mac: string | null;
+    up: boolean;
+    loopback: boolean;
+    addresses: NetworkInterfaceAddress[];
+  }
+  /** Lists the network interfaces of the machine with their addresses.
+   * Requires the `--allow-env` flag.
+   *
+   *       for (const iface of Deno.networkInterfaces()) {
+   *         if (iface.up && !iface.loopback) {
+   *           console.log(iface.name, iface.addresses);
+   *         }
+   *       }
+   */
+  export function networkInterfaces(): NetworkInterface[];
+  /** Exit the Deno process with optional exit code. The window `unload` event
+   * is dispatched first, unless exiting from one of its handlers, and output
+   * still on its way to stdout and stderr is written.
+   */
   export function exit(code?: number): never;
   /** Returns a snapshot of the environment variables at invocation. Mutating a
    * property in the object will set that variable in the environment for
-   * the process. The environment object will only accept `string`s
-   * as values.
+   * the process, and deleting one will remove the variable. The environment
+   * object will only accept `string`s as values. Each call takes a fresh
+   * snapshot, reflecting changes made since.
    *
    *       const myEnv = Deno.env();
    *       console.log(myEnv.SHELL);
    *       myEnv.TEST_VAR = "HELLO";
+   *       delete myEnv.OTHER_VAR;
    *       const newEnv = Deno.env();
    *       console.log(myEnv.TEST_VAR == newEnv.TEST_VAR);
    */
@@ -141,10 +243,10 @@ declare namespace Deno {
      * any positive offset is legal, but the behavior of subsequent I/O operations
      * on the underlying object is implementation-dependent.
      */
-    seek(offset: number, whence: SeekMode): Promise<void>;
+    seek(offset: number, whence: SeekMode): Promise<number>;
   }
   export interface SyncSeeker {
-    seekSync(offset: number, whence: SeekMode): void;
+    seekSync(offset: number, whence: SeekMode): number;
   }
   export interface ReadCloser extends Reader, Closer {}
   export interface WriteCloser extends Writer, Closer {}
@@ -174,15 +276,24 @@ declare namespace Deno {
    *  synchronously.
    *
    *       const file = Deno.openSync("/foo/bar.txt");
+   *       const opts = { write: true, createNew: true };
+   *       const lock = Deno.openSync("/foo/.lock", opts);
    */
-  export function openSync(filename: string, mode?: OpenMode): File;
+  export function openSync(
+    filename: string,
+    modeOrOptions?: OpenMode | OpenOptions
+  ): File;
   /** Open a file and return an instance of the `File` object.
    *
    *       (async () => {
    *         const file = await Deno.open("/foo/bar.txt");
+   *         const log = await Deno.open("/foo/log.txt", { append: true });
    *       })();
    */
-  export function open(filename: string, mode?: OpenMode): Promise<File>;
+  export function open(
+    filename: string,
+    modeOrOptions?: OpenMode | OpenOptions
+  ): Promise<File>;
   /** Read synchronously from a file ID into an array buffer.
    *
    * Return `number | EOF` for the operation.
@@ -206,6 +317,24 @@ declare namespace Deno {
    *       })();
    */
   export function read(rid: number, p: Uint8Array): Promise<number | EOF>;
+  /** Read from a file ID like `read`, except into a buffer which Deno takes
+   * from a pool of them, instead of one allocated by the caller.
+   *
+   * Resolves with a view of the bytes read, or `EOF`. Pass it to
+   * `Deno.releaseReadBuffer()` once done with it, so that the buffer goes back
+   * to the pool right away instead of once it is garbage collected.
+   *
+   *       const buf = await Deno.readPooled(conn.rid);
+   *       if (buf !== Deno.EOF) {
+   *         await Deno.writeAll(other, buf);
+   *         Deno.releaseReadBuffer(buf);
+   *       }
+   */
+  export function readPooled(rid: number): Promise<Uint8Array | EOF>;
+  /** Puts a buffer returned by `Deno.readPooled()` back into the pool. It, and
+   * every other view of it, become empty.
+   */
+  export function releaseReadBuffer(buf: Uint8Array): void;
   /** Write synchronously to the file ID the contents of the array buffer.
    *
    * Resolves with the number of bytes written.
@@ -229,26 +358,137 @@ declare namespace Deno {
    *
    */
   export function write(rid: number, p: Uint8Array): Promise<number>;
+  /** Write the contents of all the `bufs` to the file ID, in order, as if they
+   * were concatenated. A TCP connection is usually written with a single
+   * syscall on Linux.
+   *
+   * Ops take a single buffer, so this only avoids copying `bufs` when they are
+   * all views of the same `ArrayBuffer`. Views of separate buffers are copied
+   * into a new one, the same as concatenating them would.
+   *
+   * Resolves with the number of bytes written, which is their total length.
+   *
+   *       const buf = new Uint8Array(4096);
+   *       // Fill in the header and the body, then write both without copying.
+   *       const header = buf.subarray(0, headerLength);
+   *       const body = buf.subarray(1024, 1024 + bodyLength);
+   *       await Deno.writev(conn.rid, [header, body]);
+   */
+  export function writev(rid: number, bufs: Uint8Array[]): Promise<number>;
+  /** Resolves once the TCP connection `rid` can be read from without waiting,
+   * without reading anything. It stays readable until a read finds out
+   * otherwise, which allows buffering reads in userland.
+   *
+   *       await Deno.waitReadable(conn.rid);
+   */
+  export function waitReadable(rid: number): Promise<void>;
+  /** Resolves once the TCP connection `rid` can be written to without waiting,
+   * without writing anything.
+   *
+   *       await Deno.waitWritable(conn.rid);
+   */
+  export function waitWritable(rid: number): Promise<void>;
+  /** Read synchronously from a file ID into an array buffer, starting at byte
+   * `offset` of the file. Unlike `readSync` this doesn't use the file's cursor,
+   * so several readers can share one file ID. On Windows the cursor is moved
+   * to the end of the bytes read as a side effect.
+   *
+   * Return `number | EOF` for the operation.
+   *
+   *       const file = Deno.openSync("/foo/bar.db");
+   *       const page = new Uint8Array(4096);
+   *       const nread = Deno.readAtSync(file.rid, page, 3 * 4096);
+   */
+  export function readAtSync(
+    rid: number,
+    p: Uint8Array,
+    offset: number
+  ): number | EOF;
+  /** Read from a file ID into an array buffer, starting at byte `offset` of the
+   * file. Unlike `read` this doesn't use the file's cursor, so several readers
+   * can share one file ID. On Windows the cursor is moved to the end of the
+   * bytes read as a side effect.
+   *
+   * Resolves with the `number | EOF` for the operation.
+   *
+   *       const file = await Deno.open("/foo/bar.db");
+   *       const page = new Uint8Array(4096);
+   *       const nread = await Deno.readAt(file.rid, page, 3 * 4096);
+   */
+  export function readAt(
+    rid: number,
+    p: Uint8Array,
+    offset: number
+  ): Promise<number | EOF>;
+  /** Write synchronously to the file ID the contents of the array buffer,
+   * starting at byte `offset` of the file. Unlike `writeSync` this doesn't use
+   * the file's cursor, so several writers can share one file ID. On Windows the
+   * cursor is moved to the end of the bytes written as a side effect.
+   *
+   * Returns the number of bytes written.
+   *
+   *       const file = Deno.openSync("/foo/bar.db", "r+");
+   *       Deno.writeAtSync(file.rid, page, 3 * 4096);
+   */
+  export function writeAtSync(
+    rid: number,
+    p: Uint8Array,
+    offset: number
+  ): number;
+  /** Write to the file ID the contents of the array buffer, starting at byte
+   * `offset` of the file. Unlike `write` this doesn't use the file's cursor, so
+   * several writers can share one file ID. On Windows the cursor is moved to
+   * the end of the bytes written as a side effect.
+   *
+   * Resolves with the number of bytes written.
+   *
+   *       const file = await Deno.open("/foo/bar.db", "r+");
+   *       await Deno.writeAt(file.rid, page, 3 * 4096);
+   */
+  export function writeAt(
+    rid: number,
+    p: Uint8Array,
+    offset: number
+  ): Promise<number>;
   /** Seek a file ID synchronously to the given offset under mode given by `whence`.
+   *
+   * Returns the new position of the cursor, in bytes from the start of the file.
    *
    *       const file = Deno.openSync("/foo/bar.txt");
-   *       Deno.seekSync(file.rid, 0, 0);
+   *       const pos = Deno.seekSync(file.rid, 0, Deno.SeekMode.SEEK_END);
    */
-  export function seekSync(rid: number, offset: number, whence: SeekMode): void;
+  export function seekSync(
+    rid: number,
+    offset: number,
+    whence: SeekMode
+  ): number;
   /** Seek a file ID to the given offset under mode given by `whence`.
+   *
+   * Resolves with the new position of the cursor, in bytes from the start of the
+   * file.
    *
    *      (async () => {
    *        const file = await Deno.open("/foo/bar.txt");
-   *        await Deno.seek(file.rid, 0, 0);
+   *        const pos = await Deno.seek(file.rid, 0, Deno.SeekMode.SEEK_END);
    *      })();
    */
   export function seek(
     rid: number,
     offset: number,
     whence: SeekMode
-  ): Promise<void>;
-  /** Close the file ID. */
+  ): Promise<number>;
+  /** Close the file ID. Operations still pending on it, like a read or an
+   * accept, fail with an `Interrupted` error. */
   export function close(rid: number): void;
+  /** Returns a new resource ID for the same file or connection as `rid`. The
+   * two can be closed independently of each other, but share the underlying
+   * handle, including its cursor. Only files, stdio and TCP connections can be
+   * duplicated.
+   *
+   *       const copy = Deno.dup(conn.rid);
+   *       conn.close(); // `copy` is still open.
+   */
+  export function dup(rid: number): number;
   /** The Deno abstraction for reading and writing files. */
   export class File
     implements
@@ -265,8 +505,12 @@ declare namespace Deno {
     writeSync(p: Uint8Array): number;
     read(p: Uint8Array): Promise<number | EOF>;
     readSync(p: Uint8Array): number | EOF;
-    seek(offset: number, whence: SeekMode): Promise<void>;
-    seekSync(offset: number, whence: SeekMode): void;
+    readAt(p: Uint8Array, offset: number): Promise<number | EOF>;
+    readAtSync(p: Uint8Array, offset: number): number | EOF;
+    writeAt(p: Uint8Array, offset: number): Promise<number>;
+    writeAtSync(p: Uint8Array, offset: number): number;
+    seek(offset: number, whence: SeekMode): Promise<number>;
+    seekSync(offset: number, whence: SeekMode): number;
     close(): void;
   }
   /** An instance of `File` for stdin. */
@@ -300,6 +544,28 @@ declare namespace Deno {
     /** Read-write. Behaves like `x` and allows to read from file. */
     | "x+";
 
+  /** Options for opening a file, mapped directly onto Rust's
+   * `std::fs::OpenOptions`. At least one of `read`, `write` or `append` must be
+   * set; `create`, `createNew` and `truncate` additionally require `write` or
+   * `append`.
+   */
+  export interface OpenOptions {
+    /** Open for reading. */
+    read?: boolean;
+    /** Open for writing. */
+    write?: boolean;
+    /** Open for writing, with every write going to the end of the file. */
+    append?: boolean;
+    /** Truncate the file to zero length if it already exists. */
+    truncate?: boolean;
+    /** Create the file if it doesn't exist yet. */
+    create?: boolean;
+    /** Create a new file, failing if it already exists (`O_EXCL`). Implies
+     * `create` and ignores it.
+     */
+    createNew?: boolean;
+  }
+
   // @url js/buffer.d.ts
 
   /** A Buffer is a variable-sized buffer of bytes with read() and write()
@@ -379,30 +645,59 @@ declare namespace Deno {
      */
     readFromSync(r: SyncReader): number;
   }
-  /** Read `r` until EOF and return the content as `Uint8Array`.
+  export interface ReadAllOptions {
+    /** Largest number of bytes to read. Reading more than this throws
+     * `ErrorKind.TooLarge`. Defaults to no limit other than the largest
+     * possible buffer.
+     */
+    maxSize?: number;
+  }
+  /** Read `r` until EOF and return the content as `Uint8Array`. Files and
+   * connections are read in as few ops as possible.
    */
-  export function readAll(r: Reader): Promise<Uint8Array>;
+  export function readAll(
+    r: Reader,
+    options?: ReadAllOptions
+  ): Promise<Uint8Array>;
   /** Read synchronously `r` until EOF and return the content as `Uint8Array`.
+   * Files and connections are read in as few ops as possible.
    */
-  export function readAllSync(r: SyncReader): Uint8Array;
-  /** Write all the content of `arr` to `w`.
+  export function readAllSync(
+    r: SyncReader,
+    options?: ReadAllOptions
+  ): Uint8Array;
+  /** Write all the content of `arr` to `w`. Files and connections are written
+   * with a single op.
    */
   export function writeAll(w: Writer, arr: Uint8Array): Promise<void>;
-  /** Write synchronously all the content of `arr` to `w`.
+  /** Write synchronously all the content of `arr` to `w`. Files and connections
+   * are written with a single op.
    */
   export function writeAllSync(w: SyncWriter, arr: Uint8Array): void;
 
   // @url js/mkdir.d.ts
 
+  export interface MkdirOption {
+    /** Create missing parent directories as well (also known as "mkdir -p"),
+     * and don't fail if `path` already is a directory. Defaults to `false`.
+     */
+    recursive?: boolean;
+    /** Permission bits (before umask) for every created directory on UNIX,
+     * ignored on Windows. Defaults to `0o777`.
+     */
+    mode?: number;
+  }
   /** Creates a new directory with the specified path synchronously.
    * If `recursive` is set to true, nested directories will be created (also known
    * as "mkdir -p").
-   * `mode` sets permission bits (before umask) on UNIX and does nothing on
-   * Windows.
+   * `mode` sets permission bits (before umask) of every created directory on
+   * UNIX and does nothing on Windows.
    *
    *       Deno.mkdirSync("new_dir");
    *       Deno.mkdirSync("nested/directories", true);
+   *       Deno.mkdirSync("private/dirs", { recursive: true, mode: 0o700 });
    */
+  export function mkdirSync(path: string, options?: MkdirOption): void;
   export function mkdirSync(
     path: string,
     recursive?: boolean,
@@ -411,74 +706,425 @@ declare namespace Deno {
   /** Creates a new directory with the specified path.
    * If `recursive` is set to true, nested directories will be created (also known
    * as "mkdir -p").
-   * `mode` sets permission bits (before umask) on UNIX and does nothing on
-   * Windows.
+   * `mode` sets permission bits (before umask) of every created directory on
+   * UNIX and does nothing on Windows.
    *
    *       await Deno.mkdir("new_dir");
    *       await Deno.mkdir("nested/directories", true);
+   *       await Deno.mkdir("private/dirs", { recursive: true, mode: 0o700 });
    */
+  export function mkdir(path: string, options?: MkdirOption): Promise<void>;
   export function mkdir(
     path: string,
     recursive?: boolean,
     mode?: number
   ): Promise<void>;
 
+  // @url js/create_pipe.d.ts
+
+  export interface CreatePipeOptions {
+    /** Permissions of the FIFO, before the umask is applied. Ignored on
+     * Windows. Defaults to `0o666`.
+     */
+    mode?: number;
+    /** Open the pipe for reading. Defaults to `true`. */
+    read?: boolean;
+    /** Open the pipe for writing. Defaults to `false`. */
+    write?: boolean;
+  }
+  /** Synchronously creates a named pipe and opens it. Blocks until the other
+   * end is opened, see `createPipe`.
+   *
+   *       const fifo = Deno.createPipeSync("/tmp/events", { mode: 0o600 });
+   */
+  export function createPipeSync(
+    path: string,
+    options?: CreatePipeOptions
+  ): File;
+  /** Creates a named pipe and opens it. Requires `allow-write`, and
+   * `allow-read` unless only opened for writing.
+   *
+   * On Unix this creates a FIFO at `path` which stays around until removed.
+   * Opening it waits for another process to open the other end, unless it's
+   * opened for both reading and writing.
+   *
+   * On Windows `path` must look like `\\.\pipe\name`. The pipe only lives
+   * as long as the returned file is open, and this waits for a client to
+   * connect to it.
+   *
+   *       const fifo = await Deno.createPipe("/tmp/events");
+   *       for await (const chunk of Deno.toAsyncIterator(fifo)) {
+   *         console.log(chunk);
+   *       }
+   */
+  export function createPipe(
+    path: string,
+    options?: CreatePipeOptions
+  ): Promise<File>;
+
   // @url js/make_temp_dir.d.ts
 
-  export interface MakeTempDirOptions {
+  export interface MakeTempOptions {
     dir?: string;
     prefix?: string;
     suffix?: string;
   }
+  export type MakeTempDirOptions = MakeTempOptions;
+  export interface MakeTempFileOptions extends MakeTempOptions {
+    /** Create a file without a name, which is returned opened for reading and
+     * writing instead of a path. It disappears once closed, even if the
+     * process crashes, unless it's given a name with `linkTempFile`. `prefix`
+     * and `suffix` are ignored.
+     */
+    anonymous?: boolean;
+  }
   /** makeTempDirSync is the synchronous version of `makeTempDir`.
    *
    *       const tempDirName0 = Deno.makeTempDirSync();
    *       const tempDirName1 = Deno.makeTempDirSync({ prefix: 'my_temp' });
    */
-  export function makeTempDirSync(options?: MakeTempDirOptions): string;
+  export function makeTempDirSync(options?: MakeTempOptions): string;
   /** makeTempDir creates a new temporary directory in the directory `dir`, its
    * name beginning with `prefix` and ending with `suffix`.
    * It returns the full path to the newly created directory.
    * If `dir` is unspecified, tempDir uses the default directory for temporary
    * files. Multiple programs calling tempDir simultaneously will not choose the
-   * same directory. It is the caller's responsibility to remove the directory
+   * same directory. On UNIX the directory is only accessible by its owner
+   * (mode 0o700). It is the caller's responsibility to remove the directory
    * when no longer needed.
    *
    *       const tempDirName0 = await Deno.makeTempDir();
    *       const tempDirName1 = await Deno.makeTempDir({ prefix: 'my_temp' });
    */
-  export function makeTempDir(options?: MakeTempDirOptions): Promise<string>;
+  export function makeTempDir(options?: MakeTempOptions): Promise<string>;
+  /** makeTempFileSync is the synchronous version of `makeTempFile`.
+   *
+   *       const tempFileName0 = Deno.makeTempFileSync();
+   *       const tempFileName1 = Deno.makeTempFileSync({ prefix: 'my_temp' });
+   */
+  export function makeTempFileSync(
+    options: MakeTempFileOptions & { anonymous: true }
+  ): File;
+  export function makeTempFileSync(options?: MakeTempFileOptions): string;
+  /** makeTempFile creates a new, empty temporary file in the directory `dir`,
+   * its name beginning with `prefix` and ending with `suffix`.
+   * It returns the full path to the newly created file.
+   * If `dir` is unspecified, tempFile uses the default directory for temporary
+   * files. The file is created exclusively, so multiple programs calling
+   * tempFile simultaneously will not choose the same file. On UNIX the file is
+   * only accessible by its owner (mode 0o600). It is the caller's
+   * responsibility to remove the file when no longer needed.
+   *
+   * With `anonymous` the file has no name and is returned opened instead.
+   * This uses O_TMPFILE on Linux and FILE_FLAG_DELETE_ON_CLOSE on Windows.
+   *
+   *       const tempFileName0 = await Deno.makeTempFile();
+   *       const tempFileName1 = await Deno.makeTempFile({ prefix: 'my_temp' });
+   *       const tempFile = await Deno.makeTempFile({ anonymous: true });
+   */
+  export function makeTempFile(
+    options: MakeTempFileOptions & { anonymous: true }
+  ): Promise<File>;
+  export function makeTempFile(options?: MakeTempFileOptions): Promise<string>;
+  /** linkTempFileSync is the synchronous version of `linkTempFile`.
+   *
+   *       const file = Deno.makeTempFileSync({ anonymous: true, dir: "." });
+   *       Deno.writeAllSync(file, data);
+   *       Deno.linkTempFileSync(file.rid, "data.json");
+   */
+  export function linkTempFileSync(rid: number, newname: string): void;
+  /** linkTempFile gives a file created by `makeTempFile` with `anonymous` the
+   * name `newname`, atomically replacing any file that's already there.
+   * Readers see either the old file or the complete new one, which makes this
+   * suitable for crash-safe writes. Requires `allow-write`.
+   *
+   * On Linux the file is linked into place when it was created with
+   * O_TMPFILE on the same file system as `newname`. Otherwise its contents
+   * are copied, which is slower. Any other kind of rid is rejected.
+   *
+   *       const file = await Deno.makeTempFile({ anonymous: true, dir: "." });
+   *       await Deno.writeAll(file, data);
+   *       await Deno.linkTempFile(file.rid, "data.json");
+   */
+  export function linkTempFile(rid: number, newname: string): Promise<void>;
 
   // @url js/chmod.d.ts
 
+  export interface ChmodOptions {
+    /** Also change everything below `path` when it's a directory. The tree is
+     * walked on the privileged side. Defaults to `false`.
+     */
+    recursive?: boolean;
+    /** With `recursive`, resolve symlinks found below `path`, changing their
+     * targets and descending into linked directories. Otherwise they're left
+     * alone. Defaults to `false`.
+     */
+    followSymlinks?: boolean;
+  }
   /** Changes the permission of a specific file/directory of specified path
-   * synchronously.
+   * synchronously. On Windows only the owner write bit is used, to set or
+   * clear the read-only attribute.
    *
    *       Deno.chmodSync("/path/to/file", 0o666);
+   *       Deno.chmodSync("/path/to/dir", 0o755, { recursive: true });
    */
-  export function chmodSync(path: string, mode: number): void;
+  export function chmodSync(
+    path: string,
+    mode: number,
+    options?: ChmodOptions
+  ): void;
   /** Changes the permission of a specific file/directory of specified path.
+   * On Windows only the owner write bit is used, to set or clear the read-only
+   * attribute.
    *
    *       await Deno.chmod("/path/to/file", 0o666);
+   *       await Deno.chmod("/path/to/dir", 0o755, { recursive: true });
    */
-  export function chmod(path: string, mode: number): Promise<void>;
+  export function chmod(
+    path: string,
+    mode: number,
+    options?: ChmodOptions
+  ): Promise<void>;
+  /** Synchronously changes the permission of the file referenced by `rid`,
+   * which must have been opened for writing.
+   *
+   *       const file = Deno.openSync("/path/to/file", "r+");
+   *       Deno.fchmodSync(file.rid, 0o600);
+   */
+  export function fchmodSync(rid: number, mode: number): void;
+  /** Changes the permission of the file referenced by `rid`, which must have
+   * been opened for writing.
+   *
+   *       const file = await Deno.open("/path/to/file", "r+");
+   *       await Deno.fchmod(file.rid, 0o600);
+   */
+  export function fchmod(rid: number, mode: number): Promise<void>;
+
+  // @url js/umask.d.ts
+
+  /** Sets the process umask to `mask` and returns the previous value. When
+   * `mask` is omitted the current umask is returned unchanged. The umask is
+   * process wide, so it also affects files created by other workers. Unix only
+   * at the moment, throws `OpNotAvailable` on Windows.
+   *
+   * Other than on Linux, reading the umask sets it to 0 for a moment, so files
+   * created by other workers at the same time ignore it.
+   *
+   *       const previous = Deno.umask(0o002);
+   *       Deno.openSync("shared.txt", "w"); // created as rw-rw-r--
+   *       Deno.umask(previous);
+   */
+  export function umask(mask?: number): number;
 
   // @url js/chown.d.ts
 
+  export interface ChownOptions {
+    /** Also change everything below `path` when it's a directory. The tree is
+     * walked on the privileged side. Defaults to `false`.
+     */
+    recursive?: boolean;
+    /** With `recursive`, resolve symlinks found below `path`, changing their
+     * targets and descending into linked directories. Otherwise the owner of
+     * the links themselves is changed. Defaults to `false`.
+     */
+    followSymlinks?: boolean;
+  }
   /**
-   * Change owner of a regular file or directory synchronously. Unix only at the moment.
+   * Change owner of a regular file or directory synchronously. Unix only at the
+   * moment, throws `OpNotAvailable` on Windows.
    * @param path path to the file
    * @param uid user id of the new owner
    * @param gid group id of the new owner
+   * @param options see `ChownOptions`
    */
-  export function chownSync(path: string, uid: number, gid: number): void;
+  export function chownSync(
+    path: string,
+    uid: number,
+    gid: number,
+    options?: ChownOptions
+  ): void;
   /**
-   * Change owner of a regular file or directory asynchronously. Unix only at the moment.
+   * Change owner of a regular file or directory asynchronously. Unix only at the
+   * moment, throws `OpNotAvailable` on Windows.
    * @param path path to the file
    * @param uid user id of the new owner
    * @param gid group id of the new owner
+   * @param options see `ChownOptions`
    */
-  export function chown(path: string, uid: number, gid: number): Promise<void>;
+  export function chown(
+    path: string,
+    uid: number,
+    gid: number,
+    options?: ChownOptions
+  ): Promise<void>;
+  /**
+   * Change owner of a file synchronously without following symlinks: if `path`
+   * is a symlink, the owner of the link itself is changed. Unix only at the
+   * moment, throws `OpNotAvailable` on Windows.
+   * @param path path to the file
+   * @param uid user id of the new owner
+   * @param gid group id of the new owner
+   * @param options see `ChownOptions`
+   */
+  export function lchownSync(
+    path: string,
+    uid: number,
+    gid: number,
+    options?: ChownOptions
+  ): void;
+  /**
+   * Change owner of a file asynchronously without following symlinks: if
+   * `path` is a symlink, the owner of the link itself is changed. Unix only at
+   * the moment, throws `OpNotAvailable` on Windows.
+   * @param path path to the file
+   * @param uid user id of the new owner
+   * @param gid group id of the new owner
+   * @param options see `ChownOptions`
+   */
+  export function lchown(
+    path: string,
+    uid: number,
+    gid: number,
+    options?: ChownOptions
+  ): Promise<void>;
+  /**
+   * Change owner of the file referenced by `rid` synchronously. The file must
+   * have been opened for writing. Unix only at the moment, throws
+   * `OpNotAvailable` on Windows.
+   * @param rid resource id of the open file
+   * @param uid user id of the new owner
+   * @param gid group id of the new owner
+   */
+  export function fchownSync(rid: number, uid: number, gid: number): void;
+  /**
+   * Change owner of the file referenced by `rid` asynchronously. The file must
+   * have been opened for writing. Unix only at the moment, throws
+   * `OpNotAvailable` on Windows.
+   * @param rid resource id of the open file
+   * @param uid user id of the new owner
+   * @param gid group id of the new owner
+   */
+  export function fchown(rid: number, uid: number, gid: number): Promise<void>;
+
+  // @url js/transfer.d.ts
+
+  /** Copies `count` bytes, or everything up to EOF if `count` is omitted, from
+   * the resource `src` to the resource `dst` without passing them through JS.
+   * Resolves to the number of bytes copied.
+   *
+   * On Linux files are sent to TCP connections with `sendfile(2)` and TCP
+   * connections are piped into each other with `splice(2)`, so the data never
+   * leaves the kernel.
+   *
+   *       const file = await Deno.open("index.html");
+   *       await Deno.transfer(file.rid, conn.rid);
+   */
+  export function transfer(
+    src: number,
+    dst: number,
+    count?: number
+  ): Promise<number>;
+
+  // @url js/tee.d.ts
+
+  /** Splits the readable resource `rid` into two resources which both read the
+   * same bytes from it. `rid` itself is consumed and closed once both new
+   * resources are. Up to 64 KiB are buffered for the slower of the two, after
+   * which reading the faster one waits for it to catch up. Closing one of them
+   * lets the other read on alone.
+   *
+   *       const [toHash, toSave] = Deno.tee(conn.rid);
+   *       await Promise.all([
+   *         hash(new Deno.File(toHash)),
+   *         Deno.transfer(toSave, file.rid)
+   *       ]);
+   */
+  export function tee(rid: number): [number, number];
+
+  // @url js/tty.d.ts
+
+  export interface ConsoleSize {
+    columns: number;
+    rows: number;
+  }
+  /** Check if the resource `rid` is a terminal. Resources that can't be one,
+   * like sockets, are never reported as such.
+   *
+   *       if (Deno.isatty(Deno.stdout.rid)) {
+   *         console.log("\x1b[1mbold\x1b[0m");
+   *       }
+   */
+  export function isatty(rid: number): boolean;
+  /** Returns the size of the terminal behind `rid`. Throws if `rid` is not a
+   * terminal.
+   *
+   *       const { columns } = Deno.consoleSize(Deno.stdout.rid);
+   */
+  export function consoleSize(rid: number): ConsoleSize;
+  export interface SetRawOptions {
+    /** Use cbreak mode rather than raw mode: input is still passed on a key
+     * at a time and not echoed, but Ctrl-C and the like still raise signals.
+     * Defaults to `false`.
+     */
+    cbreak?: boolean;
+  }
+  /** Switches the terminal behind stdin into raw mode, where every key press
+   * is passed on as it happens, without echo or line editing, or back to the
+   * mode it was in before. The original mode is also restored when the process
+   * exits. Only `Deno.stdin.rid` is supported.
+   *
+   *       Deno.setRaw(Deno.stdin.rid, true);
+   *       const key = new Uint8Array(8);
+   *       await Deno.stdin.read(key);
+   *       Deno.setRaw(Deno.stdin.rid, false);
+   */
+  export function setRaw(
+    rid: number,
+    mode: boolean,
+    options?: SetRawOptions
+  ): void;
+  /** Yields the new size of the terminal behind `rid` every time it is
+   * resized. Throws if `rid` is not a terminal.
+   *
+   *       for await (const { columns } of Deno.watchConsoleSize(rid)) {
+   *         progressBar.setWidth(columns);
+   *       }
+   */
+  export function watchConsoleSize(
+    rid: number
+  ): AsyncIterableIterator<ConsoleSize>;
+
+  // @url js/mmap.d.ts
+
+  /** A file mapped into memory. `bytes` accesses the mapping directly, without
+   * copying it or going through the file system. Writes to `bytes` are private
+   * to the mapping and never reach the file.
+   *
+   * If someone truncates the file while it's mapped, accessing `bytes` past the
+   * new end of the file raises SIGBUS, which kills the process. Only map files
+   * that don't shrink.
+   */
+  export class MappedFile implements Closer {
+    readonly bytes: Uint8Array;
+    constructor(bytes: Uint8Array);
+    /** The size of the mapping, or 0 once it's unmapped. */
+    readonly byteLength: number;
+    /** Unmaps the file right away, instead of once `bytes` is garbage
+     * collected. `bytes` and every other view of the mapping become empty.
+     */
+    unmap(): void;
+    close(): void;
+  }
+  /** Maps the file referenced by `rid` into memory. The mapping remains valid
+   * after the file is closed, until it's unmapped.
+   *
+   *       const file = Deno.openSync("data.bin");
+   *       const mapped = Deno.mmap(file.rid);
+   *       file.close();
+   *       const header = mapped.bytes.subarray(0, 16);
+   *       mapped.unmap();
+   */
+  export function mmap(rid: number): MappedFile;
 
   // @url js/utime.d.ts
 
@@ -504,6 +1150,72 @@ declare namespace Deno {
     atime: number | Date,
     mtime: number | Date
   ): Promise<void>;
+  /** Synchronously changes the access and modification times of the already
+   * open file `rid`. Given times are either in seconds (Unix epoch time) or as
+   * `Date` objects.
+   *
+   *       const file = Deno.openSync("myfile.txt", "r+");
+   *       Deno.futimeSync(file.rid, 1556495550, new Date());
+   */
+  export function futimeSync(
+    rid: number,
+    atime: number | Date,
+    mtime: number | Date
+  ): void;
+  /** Changes the access and modification times of the already open file
+   * `rid`. Given times are either in seconds (Unix epoch time) or as `Date`
+   * objects.
+   *
+   *       const file = await Deno.open("myfile.txt", "r+");
+   *       await Deno.futime(file.rid, 1556495550, new Date());
+   */
+  export function futime(
+    rid: number,
+    atime: number | Date,
+    mtime: number | Date
+  ): Promise<void>;
+
+  // @url js/flock.d.ts
+
+  /** Synchronously places an advisory lock on the whole file referenced by
+   * `rid`, waiting until any conflicting lock is released. Many shared locks
+   * can be held at once, an exclusive lock excludes all others. Uses `flock(2)`
+   * on Unix and `LockFileEx` on Windows.
+   *
+   *       const file = Deno.openSync("deno.lock", "w");
+   *       Deno.flockSync(file.rid, true);
+   */
+  export function flockSync(rid: number, exclusive?: boolean): void;
+  /** Places an advisory lock on the whole file referenced by `rid`, resolving
+   * once any conflicting lock is released. Many shared locks can be held at
+   * once, an exclusive lock excludes all others. Uses `flock(2)` on Unix and
+   * `LockFileEx` on Windows.
+   *
+   *       const file = await Deno.open("deno.lock", "w");
+   *       await Deno.flock(file.rid, true);
+   */
+  export function flock(rid: number, exclusive?: boolean): Promise<void>;
+  /** Attempts to place an advisory lock on the whole file referenced by `rid`
+   * without waiting. Returns `false` if a conflicting lock is held.
+   *
+   *       const file = Deno.openSync("deno.lock", "w");
+   *       if (!Deno.tryFlockSync(file.rid, true)) {
+   *         throw new Error("Another instance is already running");
+   *       }
+   */
+  export function tryFlockSync(rid: number, exclusive?: boolean): boolean;
+  /** Synchronously releases the advisory lock held on the file referenced by
+   * `rid`. Closing the file releases the lock as well.
+   *
+   *       Deno.funlockSync(file.rid);
+   */
+  export function funlockSync(rid: number): void;
+  /** Releases the advisory lock held on the file referenced by `rid`. Closing
+   * the file releases the lock as well.
+   *
+   *       await Deno.funlock(file.rid);
+   */
+  export function funlock(rid: number): Promise<void>;
 
   // @url js/remove.d.ts
 
@@ -561,6 +1273,39 @@ declare namespace Deno {
    *       console.log(decoder.decode(data));
    */
   export function readFile(filename: string): Promise<Uint8Array>;
+  export interface ReadFileChunksOptions {
+    /** Position in the file to start reading at. Defaults to `0`. */
+    offset?: number;
+    /** Number of bytes to read. Defaults to everything up to the end of the
+     * file.
+     */
+    length?: number;
+    /** Maximum size of each chunk. Defaults to 64 KiB. */
+    chunkSize?: number;
+  }
+  /** Read the contents of a file synchronously, one chunk at a time, so that
+   * the whole file never has to be held in memory.
+   *
+   *       for (const chunk of Deno.readFileChunksSync("big.bin")) {
+   *         hash.update(chunk);
+   *       }
+   */
+  export function readFileChunksSync(
+    filename: string,
+    options?: ReadFileChunksOptions
+  ): IterableIterator<Uint8Array>;
+  /** Read the contents of a file one chunk at a time, so that the whole file
+   * never has to be held in memory. Use `offset` and `length` to read only a
+   * part of the file.
+   *
+   *       for await (const chunk of Deno.readFileChunks("big.bin")) {
+   *         hash.update(chunk);
+   *       }
+   */
+  export function readFileChunks(
+    filename: string,
+    options?: ReadFileChunksOptions
+  ): AsyncIterableIterator<Uint8Array>;
 
   // @url js/file_info.d.ts
 
@@ -586,9 +1331,32 @@ declare namespace Deno {
      */
     created: number | null;
     /** The underlying raw st_mode bits that contain the standard Unix permissions
-     * for this file/directory. TODO Match behavior with Go on windows for mode.
+     * for this file/directory. Windows only has a read-only attribute, so there
+     * this is `0o444` for read-only files and `0o666` otherwise, plus `0o111`
+     * for directories.
      */
     mode: number | null;
+    /** ID of the device containing the file. This is the volume serial number
+     * on Windows. It's a decimal string, as it may not fit in a number.
+     */
+    dev: string | null;
+    /** Inode number of the file, or its file index on Windows, as a decimal
+     * string. Together with `dev` it identifies a file, across renames, for as
+     * long as it exists.
+     */
+    ino: string | null;
+    /** Number of hard links pointing to the file. */
+    nlink: number | null;
+    /** User ID of the owner of the file. Unix only. */
+    uid: number | null;
+    /** Group ID of the owner of the file. Unix only. */
+    gid: number | null;
+    /** Device ID, for device files, as a decimal string. Unix only. */
+    rdev: string | null;
+    /** Preferred block size for I/O on the file. Unix only. */
+    blksize: number | null;
+    /** Number of 512 byte blocks allocated to the file. Unix only. */
+    blocks: number | null;
     /** The file or directory name. */
     name: string | null;
     /** Returns whether this is info for a regular file. This result is mutually
@@ -619,6 +1387,37 @@ declare namespace Deno {
    */
   export function readDir(path: string): Promise<FileInfo[]>;
 
+  // @url js/glob.d.ts
+
+  export interface GlobOptions {
+    /** How many paths are sent over from the privileged side at a time.
+     * Defaults to `256`.
+     */
+    batchSize?: number;
+  }
+  /** Synchronously expands the glob `pattern` against the file system and
+   * returns the matched paths, sorted per directory. Supports `*`, `?`,
+   * `[...]`, `{a,b}` and `**`, which matches any number of directories.
+   * Requires `allow-read` for the directory named by the literal part of the
+   * pattern.
+   *
+   *       const sources = Deno.globSync("src/**\/*.{ts,tsx}");
+   */
+  export function globSync(pattern: string, options?: GlobOptions): string[];
+  /** Expands the glob `pattern` against the file system, yielding matched
+   * paths as the walk progresses. Supports `*`, `?`, `[...]`, `{a,b}` and
+   * `**`, which matches any number of directories. Requires `allow-read` for
+   * the directory named by the literal part of the pattern.
+   *
+   *       for await (const path of Deno.glob("src/**\/*.{ts,tsx}")) {
+   *         console.log(path);
+   *       }
+   */
+  export function glob(
+    pattern: string,
+    options?: GlobOptions
+  ): AsyncIterableIterator<string>;
+
   // @url js/copy_file.d.ts
 
   /** Copies the contents of a file to another by name synchronously.
@@ -656,6 +1455,50 @@ declare namespace Deno {
    */
   export function readlink(name: string): Promise<string>;
 
+  // @url js/realpath.d.ts
+
+  /** Returns absolute normalized path with symbolic links resolved
+   * synchronously. Requires read permission for both `path` and the resolved
+   * location.
+   *
+   *       const realPath = Deno.realpathSync("./some/path");
+   */
+  export function realpathSync(path: string): string;
+  /** Returns absolute normalized path with symbolic links resolved. Requires
+   * read permission for both `path` and the resolved location.
+   *
+   *       const realPath = await Deno.realpath("./some/path");
+   */
+  export function realpath(path: string): Promise<string>;
+
+  // @url js/batch.d.ts
+
+  /** A file system query made by `batch`, named after the function which
+   * makes it on its own, e.g. `{ op: "stat", path: "hello.txt" }`.
+   */
+  export interface BatchCall {
+    op: "stat" | "lstat" | "readDir" | "readlink" | "realpath";
+    path: string;
+  }
+  /** The outcome of a `BatchCall`, which failed if `error` is set. */
+  export interface BatchResult {
+    value?: FileInfo | FileInfo[] | string;
+    error?: Error;
+  }
+  /** Makes several file system queries with a single op, which saves crossing
+   * over to the privileged side once per query when there are many of them.
+   * The results are in the order of `calls`, and one failing doesn't fail the
+   * others.
+   *
+   *       const [info, target] = await Deno.batch([
+   *         { op: "stat", path: "hello.txt" },
+   *         { op: "readlink", path: "link" }
+   *       ]);
+   */
+  export function batch(calls: BatchCall[]): Promise<BatchResult[]>;
+  /** Synchronous version of `batch`. */
+  export function batchSync(calls: BatchCall[]): BatchResult[];
+
   // @url js/stat.d.ts
 
   interface StatResponse {
@@ -666,8 +1509,15 @@ declare namespace Deno {
     accessed: number;
     created: number;
     mode: number;
-    hasMode: boolean;
     name: string | null;
+    dev: string | null;
+    ino: string | null;
+    nlink: number | null;
+    uid: number | null;
+    gid: number | null;
+    rdev: string | null;
+    blksize: number | null;
+    blocks: number | null;
   }
   /** Queries the file system for information on the path provided. If the given
    * path is a symlink information about the symlink will be returned.
@@ -698,6 +1548,134 @@ declare namespace Deno {
    *       assert(fileInfo.isFile());
    */
   export function statSync(filename: string): FileInfo;
+  /** Queries the file system for information on the file referenced by `rid`.
+   * Unlike `stat` this describes exactly the file that was opened, even if the
+   * path has since been replaced.
+   *
+   *       const file = await Deno.open("hello.txt");
+   *       const fileInfo = await Deno.fstat(file.rid);
+   *       assert(fileInfo.isFile());
+   */
+  export function fstat(rid: number): Promise<FileInfo>;
+  /** Queries the file system for information on the file referenced by `rid`
+   * synchronously. Unlike `statSync` this describes exactly the file that was
+   * opened, even if the path has since been replaced.
+   *
+   *       const file = Deno.openSync("hello.txt");
+   *       const fileInfo = Deno.fstatSync(file.rid);
+   *       assert(fileInfo.isFile());
+   */
+  export function fstatSync(rid: number): FileInfo;
+
+  // @url js/statfs.d.ts
+
+  /** Space on a file system, in bytes. `available` is the space usable by the
+   * current user, which can be less than `free` when some of it is reserved.
+   */
+  export interface FsUsage {
+    total: number;
+    free: number;
+    available: number;
+  }
+  /** Synchronously returns the total, free and available space of the file
+   * system containing `path`. Requires `allow-read`.
+   *
+   *       const { available } = Deno.statfsSync("/tmp");
+   */
+  export function statfsSync(path: string): FsUsage;
+  /** Returns the total, free and available space of the file system containing
+   * `path`. Requires `allow-read`.
+   *
+   *       const { available } = await Deno.statfs("/tmp");
+   */
+  export function statfs(path: string): Promise<FsUsage>;
+
+  // @url js/xattr.d.ts
+
+  /** Synchronously returns the value of the extended attribute `name` of a file,
+   * or `null` if it isn't set. The file is given either as a path, which
+   * requires `allow-read`, or as the rid of an open file. Linux and macOS only,
+   * throws `OpNotAvailable` elsewhere.
+   *
+   *       const value = Deno.getXattrSync("file.zip", "com.apple.quarantine");
+   */
+  export function getXattrSync(
+    pathOrRid: string | number,
+    name: string
+  ): Uint8Array | null;
+  /** Returns the value of the extended attribute `name` of a file, or `null` if
+   * it isn't set. The file is given either as a path, which requires
+   * `allow-read`, or as the rid of an open file. Linux and macOS only, throws
+   * `OpNotAvailable` elsewhere.
+   *
+   *       const value = await Deno.getXattr("file.zip", "com.apple.quarantine");
+   */
+  export function getXattr(
+    pathOrRid: string | number,
+    name: string
+  ): Promise<Uint8Array | null>;
+  /** Synchronously sets the extended attribute `name` of a file to `value`. The
+   * file is given either as a path, which requires `allow-write`, or as the rid
+   * of an open file. Linux and macOS only, throws `OpNotAvailable` elsewhere.
+   *
+   *       Deno.setXattrSync("file.txt", "user.checksum", checksum);
+   */
+  export function setXattrSync(
+    pathOrRid: string | number,
+    name: string,
+    value: Uint8Array
+  ): void;
+  /** Sets the extended attribute `name` of a file to `value`. The file is given
+   * either as a path, which requires `allow-write`, or as the rid of an open
+   * file. Linux and macOS only, throws `OpNotAvailable` elsewhere.
+   *
+   *       await Deno.setXattr("file.txt", "user.checksum", checksum);
+   */
+  export function setXattr(
+    pathOrRid: string | number,
+    name: string,
+    value: Uint8Array
+  ): Promise<void>;
+  /** Synchronously returns the names of all extended attributes of a file. The
+   * file is given either as a path, which requires `allow-read`, or as the rid
+   * of an open file. Linux and macOS only, throws `OpNotAvailable` elsewhere.
+   *
+   *       for (const name of Deno.listXattrSync("file.txt")) {
+   *         console.log(name);
+   *       }
+   */
+  export function listXattrSync(pathOrRid: string | number): string[];
+  /** Returns the names of all extended attributes of a file. The file is given
+   * either as a path, which requires `allow-read`, or as the rid of an open
+   * file. Linux and macOS only, throws `OpNotAvailable` elsewhere.
+   *
+   *       for (const name of await Deno.listXattr("file.txt")) {
+   *         console.log(name);
+   *       }
+   */
+  export function listXattr(
+    pathOrRid: string | number
+  ): Promise<string[]>;
+  /** Synchronously removes the extended attribute `name` of a file. The file is
+   * given either as a path, which requires `allow-write`, or as the rid of an
+   * open file. Linux and macOS only, throws `OpNotAvailable` elsewhere.
+   *
+   *       Deno.removeXattrSync("file.zip", "com.apple.quarantine");
+   */
+  export function removeXattrSync(
+    pathOrRid: string | number,
+    name: string
+  ): void;
+  /** Removes the extended attribute `name` of a file. The file is given either
+   * as a path, which requires `allow-write`, or as the rid of an open file.
+   * Linux and macOS only, throws `OpNotAvailable` elsewhere.
+   *
+   *       await Deno.removeXattr("file.zip", "com.apple.quarantine");
+   */
+  export function removeXattr(
+    pathOrRid: string | number,
+    name: string
+  ): Promise<void>;
 
   // @url js/link.d.ts
 
@@ -715,26 +1693,28 @@ declare namespace Deno {
   // @url js/symlink.d.ts
 
   /** Synchronously creates `newname` as a symbolic link to `oldname`. The type
-   * argument can be set to `dir` or `file` and is only available on Windows
-   * (ignored on other platforms).
+   * argument can be set to `dir` or `file` and is only used on Windows (ignored
+   * on other platforms). When it is omitted on Windows, the type is inferred
+   * from `oldname`, which must then exist.
    *
    *       Deno.symlinkSync("old/name", "new/name");
    */
   export function symlinkSync(
     oldname: string,
     newname: string,
-    type?: string
+    type?: "dir" | "file"
   ): void;
   /** Creates `newname` as a symbolic link to `oldname`. The type argument can be
-   * set to `dir` or `file` and is only available on Windows (ignored on other
-   * platforms).
+   * set to `dir` or `file` and is only used on Windows (ignored on other
+   * platforms). When it is omitted on Windows, the type is inferred from
+   * `oldname`, which must then exist.
    *
    *       await Deno.symlink("old/name", "new/name");
    */
   export function symlink(
     oldname: string,
     newname: string,
-    type?: string
+    type?: "dir" | "file"
   ): Promise<void>;
 
   // @url js/write_file.d.ts
@@ -892,6 +1872,7 @@ declare namespace Deno {
     env: boolean;
     run: boolean;
     hrtime: boolean;
+    plugin: boolean;
   }
   export type Permission = keyof Permissions;
   /** Inspect granted permissions for the current program.
@@ -902,6 +1883,34 @@ declare namespace Deno {
    *       }
    */
   export function permissions(): Permissions;
+  /** The state of a permission, or of a permission for a single target. */
+  export interface PermissionDescriptor {
+    permission: Permission;
+    /** The path, host, variable or program the state is for, if not for the
+     * whole permission. */
+    target?: string;
+    state: "granted" | "prompt" | "denied";
+  }
+  /** List the effective permissions of the current program: one descriptor
+   * for each permission, followed by one for each target it is granted or
+   * denied for on its own, like the paths of `--allow-read=/data`.
+   *
+   *       for (const { permission, target, state } of
+   *         Deno.permissionDescriptors()) {
+   *         console.log(permission, target || "*", state);
+   *       }
+   */
+  export function permissionDescriptors(): PermissionDescriptor[];
+  /** Ask the user for a permission at the point it is needed, instead of
+   * requiring it upfront. Returns whether it is granted, which it stays until
+   * revoked. Nobody is asked if it was already granted or denied for good, or
+   * if there is no terminal to ask on.
+   *
+   *       if (Deno.requestPermission("write")) {
+   *         await Deno.writeFile("report.txt", report);
+   *       }
+   */
+  export function requestPermission(permission: Permission): boolean;
   /** Revoke a permission. When the permission was already revoked nothing changes
    *
    *       if (Deno.permissions().read) {
@@ -927,6 +1936,92 @@ declare namespace Deno {
    *       await Deno.truncate("hello.txt", 10);
    */
   export function truncate(name: string, len?: number): Promise<void>;
+  /** Truncates or extends the already open file `rid` synchronously, updating
+   * the size of this file to become size. The file must be open for writing.
+   *
+   *       const file = Deno.openSync("hello.txt", "r+");
+   *       Deno.ftruncateSync(file.rid, 10);
+   */
+  export function ftruncateSync(rid: number, len?: number): void;
+  /** Truncates or extends the already open file `rid`, updating the size of
+   * this file to become size. The file must be open for writing.
+   *
+   *       const file = await Deno.open("hello.txt", "r+");
+   *       await Deno.ftruncate(file.rid, 10);
+   */
+  export function ftruncate(rid: number, len?: number): Promise<void>;
+
+  // @url js/fsync.d.ts
+
+  /** Synchronously flushes the data and metadata of the file `rid` to stable
+   * storage, like fsync(2).
+   *
+   *       Deno.writeAllSync(file, entry);
+   *       Deno.fsyncSync(file.rid);
+   */
+  export function fsyncSync(rid: number): void;
+  /** Flushes the data and metadata of the file `rid` to stable storage, like
+   * fsync(2). Once this resolves, what was written survives a crash or power
+   * loss.
+   *
+   *       await Deno.writeAll(file, entry);
+   *       await Deno.fsync(file.rid);
+   */
+  export function fsync(rid: number): Promise<void>;
+  /** Synchronously flushes the data of the file `rid` to stable storage, like
+   * fdatasync(2).
+   *
+   *       Deno.writeAllSync(file, entry);
+   *       Deno.fdatasyncSync(file.rid);
+   */
+  export function fdatasyncSync(rid: number): void;
+  /** Flushes the data of the file `rid` to stable storage, like fdatasync(2).
+   * Metadata such as the modification time is only flushed when it's needed to
+   * read the data back, which saves a disk write compared to `fsync`.
+   *
+   *       await Deno.writeAll(file, entry);
+   *       await Deno.fdatasync(file.rid);
+   */
+  export function fdatasync(rid: number): Promise<void>;
+
+  // @url js/fs_events.d.ts
+
+  export type FsEventKind = "create" | "modify" | "remove" | "rename";
+  export interface FsEvent {
+    kind: FsEventKind;
+    paths: string[];
+  }
+  export interface WatchFsOptions {
+    /** Watch directories recursively. Defaults to `true`. */
+    recursive?: boolean;
+    /** Only report these kinds of events. Filtering is done on the privileged
+     * side, so events of other kinds never reach JS. Defaults to all kinds.
+     */
+    kinds?: FsEventKind[];
+    /** Collect events for this many milliseconds after the first one and send
+     * them together, dropping duplicates. This bounds how often JS is woken up
+     * during bursts of changes, like an editor saving many files at once.
+     * Defaults to no debouncing.
+     */
+    debounce?: number;
+    /** Drop events for paths matching any of these globs, relative to the
+     * watched path, like `"**\/node_modules/**"` or `".git/**"`. Matching
+     * happens on the privileged side.
+     */
+    ignore?: string[];
+  }
+  /** Watch for file system events against one or more `paths`, which can be
+   * files or directories. Requires `allow-read`.
+   *
+   *       const iter = Deno.watchFs("/", { kinds: ["create", "remove"] });
+   *       for await (const event of iter) {
+   *         console.log(">>>> event", event);
+   *       }
+   */
+  export function watchFs(
+    paths: string | string[],
+    options?: WatchFsOptions
+  ): AsyncIterableIterator<FsEvent>;
 
   // @url js/net.d.ts
 
@@ -944,6 +2039,11 @@ declare namespace Deno {
      * with errors.
      */
     close(): void;
+    /** Closes the listener like `close()`, except that a pending accept
+     * promise first gets a connection that is already waiting to be accepted,
+     * if there is one. TLS listeners are closed right away.
+     */
+    drain(): void;
     /** Return the address of the `Listener`. */
     addr(): Addr;
     [Symbol.asyncIterator](): AsyncIterator<Conn>;
@@ -1050,12 +2150,25 @@ declare namespace Deno {
   export function dialTLS(options: DialTLSOptions): Promise<Conn>;
 
   // @url js/metrics.d.ts
+  export interface OpMetrics {
+    opsDispatched: number;
+    opsCompleted: number;
+    bytesSentControl: number;
+    bytesSentData: number;
+    bytesReceived: number;
+    /** Milliseconds from dispatch to completion, summed over the completed
+     * ops. Only precise to 2ms without `--allow-hrtime`. */
+    latency: number;
+  }
+
   export interface Metrics {
     opsDispatched: number;
     opsCompleted: number;
     bytesSentControl: number;
     bytesSentData: number;
     bytesReceived: number;
+    /** The metrics of each op dispatched so far, by op name. */
+    ops: { [name: string]: OpMetrics };
   }
   /** Receive metrics from the privileged side of Deno.
    *
@@ -1069,18 +2182,201 @@ declare namespace Deno {
    *      │  bytesSentData   │   0    │
    *      │  bytesReceived   │  856   │
    *      └──────────────────┴────────┘
+   *
+   * The same counts are kept for every op, along with the time they took:
+   *
+   *      > console.table(Deno.metrics().ops)
    */
   export function metrics(): Metrics;
 
+  interface HeapStats {
+    totalHeapSize: number;
+    totalHeapSizeExecutable: number;
+    totalPhysicalSize: number;
+    totalAvailableSize: number;
+    usedHeapSize: number;
+    heapSizeLimit: number;
+    mallocedMemory: number;
+    peakMallocedMemory: number;
+    /** Memory held outside of the heap by objects in it, such as the backing
+     * stores of array buffers. */
+    externalMemory: number;
+    numberOfNativeContexts: number;
+    numberOfDetachedContexts: number;
+  }
+  /** Returns the statistics of the V8 heap of this isolate, in bytes. Unlike
+   * `Deno.metrics()`, it dispatches no op.
+   *
+   *       const { usedHeapSize, heapSizeLimit } = Deno.heapStats();
+   */
+  export function heapStats(): HeapStats;
+
+  /** Takes a snapshot of the V8 heap and writes it to `path`, which the memory
+   * panel of the Chrome DevTools can load. Requires `allow-write`.
+   *
+   *       Deno.writeHeapSnapshot("app.heapsnapshot");
+   */
+  export function writeHeapSnapshot(path: string): void;
+
+  interface CpuProfileNode {
+    id: number;
+    callFrame: {
+      functionName: string;
+      scriptId: string;
+      url: string;
+      /** Zero based. */
+      lineNumber: number;
+      /** Zero based. */
+      columnNumber: number;
+    };
+    /** The number of samples in which this node was on top of the stack. */
+    hitCount: number;
+    /** The ids of the nodes called from this one. */
+    children: number[];
+  }
+
+  /** A CPU profile in the .cpuprofile format. */
+  interface CpuProfile {
+    /** The nodes of the call tree, the first one being its root. */
+    nodes: CpuProfileNode[];
+    /** In microseconds. */
+    startTime: number;
+    /** In microseconds. */
+    endTime: number;
+    /** The id of the node on top of the stack at each sample. */
+    samples: number[];
+    /** The microseconds between each sample and the previous one, or the start
+     * of the profile for the first one. */
+    timeDeltas: number[];
+  }
+
+  /** Starts sampling this isolate with the V8 CPU profiler, until
+   * `Deno.stopCpuProfile()` is called. Throws if it's already started.
+   */
+  export function startCpuProfile(): void;
+
+  /** Stops the CPU profiler and returns the profile it recorded. Written to a
+   * file with the `.cpuprofile` extension, it can be loaded in the performance
+   * panel of the Chrome DevTools.
+   *
+   *       Deno.startCpuProfile();
+   *       await handleRequests();
+   *       const profile = Deno.stopCpuProfile();
+   *       const data = new TextEncoder().encode(JSON.stringify(profile));
+   *       Deno.writeFileSync("app.cpuprofile", data);
+   */
+  export function stopCpuProfile(): CpuProfile;
+
   // @url js/resources.d.ts
 
   interface ResourceMap {
     [rid: number]: string;
   }
+  interface ResourceInfo {
+    /** The string representation of the resource, as in `ResourceMap`. */
+    type: string;
+    /** When the resource was opened, in milliseconds since the epoch. */
+    created: number;
+    /** The op which opened the resource, e.g. "accept". Null for stdio. */
+    op: string | null;
+    bytesRead: number;
+    bytesWritten: number;
+  }
+  interface VerboseResourceMap {
+    [rid: number]: ResourceInfo;
+  }
+  interface ResourcesOptions {
+    verbose?: boolean;
+  }
   /** Returns a map of open _file like_ resource ids along with their string
-   * representation.
+   * representation. With `verbose`, each resource is described by when and by
+   * which op it was opened and how many bytes were read from and written to
+   * it.
+   *
+   *       const info = Deno.resources({ verbose: true });
    */
   export function resources(): ResourceMap;
+  export function resources(options: { verbose: true }): VerboseResourceMap;
+  export function resources(
+    options?: ResourcesOptions
+  ): ResourceMap | VerboseResourceMap;
+
+  // @url js/custom_ops.d.ts
+
+  /** Calls the JSON op `name`, which a program embedding Deno registered with
+   * `deno_cli::ops::register_custom_ops()`, and returns its result.
+   *
+   *       const result = Deno.opSync("my_op", { answer: 42 });
+   */
+  export function opSync(name: string, args?: object): any;
+  /** Calls the JSON op `name` like `opSync`, except that the op may run
+   * asynchronously.
+   *
+   *       const result = await Deno.opAsync("my_op", { answer: 42 });
+   */
+  export function opAsync(name: string, args?: object): Promise<any>;
+
+  // @url js/plugins.d.ts
+
+  export interface PluginOp {
+    /** Calls the op with `control` and an optional zero copy buffer. Returns
+     * the response of a sync op, or null for an async one, whose response is
+     * given to the handler set with `setAsyncHandler()`. */
+    dispatch(
+      control: Uint8Array,
+      zeroCopy?: ArrayBufferView | null
+    ): Uint8Array | null;
+    /** Sets the function called with each response of the op when async. */
+    setAsyncHandler(handler: (msg: Uint8Array) => void): void;
+  }
+  export interface Plugin {
+    /** The ops the plugin registered, by name. */
+    ops: {
+      [name: string]: PluginOp;
+    };
+  }
+  /** Loads the native plugin `filename`, a dynamic library exporting the init
+   * function of `deno::init_fn!`, and returns the ops it registered. Requires
+   * the `allow-plugin` permission.
+   *
+   *       const plugin = Deno.openPlugin("./libhello.so");
+   *       const response = plugin.ops.hello.dispatch(new Uint8Array([42]));
+   */
+  export function openPlugin(filename: string): Plugin;
+
+  // @url js/wasi.d.ts
+
+  export interface WasiOptions {
+    /** The arguments of the program, including its name. */
+    args?: string[];
+    /** The environment of the program. Defaults to the one of Deno, which
+     * requires the `allow-env` permission once the program reads it. */
+    env?: { [key: string]: string };
+    /** Directories the program may open files in, as a map from the path the
+     * program sees to the path on the host. */
+    preopens?: { [path: string]: string };
+  }
+  /** Runs WebAssembly programs built for WASI, the WebAssembly System
+   * Interface. Its `wasi_snapshot_preview1` functions are implemented with
+   * the APIs of Deno, so the program needs the same permissions as a script
+   * reading files, the environment, and so on would.
+   *
+   *       const wasi = new Deno.Wasi({ args: Deno.args });
+   *       const bytes = Deno.readFileSync("program.wasm");
+   *       const module = new WebAssembly.Module(bytes);
+   *       const instance = new WebAssembly.Instance(module, wasi.imports);
+   *       Deno.exit(wasi.start(instance));
+   */
+  export class Wasi {
+    constructor(options?: WasiOptions);
+    /** The imports to instantiate the program with. */
+    readonly imports: {
+      wasi_snapshot_preview1: { [name: string]: (...args: any[]) => number };
+    };
+    /** Runs the `_start` function of a program instantiated with `imports`,
+     * and returns its exit code. */
+    start(instance: WebAssembly.Instance): number;
+  }
 
   // @url js/process.d.ts
 
@@ -1096,6 +2392,19 @@ declare namespace Deno {
    * stream to /dev/null.
    */
   type ProcessStdio = "inherit" | "piped" | "null";
+  /** Resource limits for a subprocess. Each of them is applied as both the soft
+   * and the hard limit, so the subprocess can't raise it again.
+   */
+  export interface RunLimits {
+    /** CPU time in seconds, after which the subprocess is killed. */
+    cpu?: number;
+    /** Size of the virtual memory of the subprocess in bytes, beyond which its
+     * allocations fail.
+     */
+    addressSpace?: number;
+    /** Number of files the subprocess can have open at once. */
+    openFiles?: number;
+  }
   export interface RunOptions {
     args: string[];
     cwd?: string;
@@ -1105,20 +2414,92 @@ declare namespace Deno {
     stdout?: ProcessStdio | number;
     stderr?: ProcessStdio | number;
     stdin?: ProcessStdio | number;
+    /** Start the subprocess in a session (Unix) or process group and without a
+     * console (Windows) of its own, so that it keeps running when the terminal
+     * goes away or Ctrl+C is pressed. Closing the process leaves it running
+     * instead of killing it. Defaults to `false`.
+     */
+    detached?: boolean;
+    /** Start the subprocess as the leader of a new process group, so that it
+     * and everything it spawns can be signaled at once with
+     * `Process.killGroup()`. Implied by `detached`. Defaults to `false`.
+     */
+    processGroup?: boolean;
+    /** Resource limits to apply to the subprocess before it starts. Not
+     * supported on Windows.
+     */
+    limits?: RunLimits;
+    /** Run the subprocess as this user. Requires the privileges to switch to
+     * it, e.g. being root. Not supported on Windows.
+     */
+    uid?: number;
+    /** Run the subprocess as this group. Not supported on Windows. */
+    gid?: number;
+    /** Kill the subprocess, and on Unix the process group it leads, if it is
+     * still running after this many milliseconds. Its status then has
+     * `timedOut` set. The timer stops once the status has been awaited or the
+     * process is closed.
+     */
+    timeout?: number;
+    /** Attach the subprocess to a new pseudo-terminal of the given size, or of
+     * 80 columns by 24 rows if `true`, instead of `stdin`, `stdout` and
+     * `stderr`. The subprocess then behaves as it would in a terminal, e.g.
+     * colors its output, and is driven through `Process.pty`. Not supported
+     * on Windows.
+     */
+    pty?: boolean | ConsoleSize;
   }
-  /** Send a signal to process under given PID. Unix only at this moment.
-   * If pid is negative, the signal will be sent to the process group identified
-   * by -pid.
+  /** Send a signal, given by number or by name such as `"SIGTERM"`, to process
+   * under given PID. If pid is negative, the signal will be sent to the process
+   * group identified by -pid. Signals this platform lacks throw `InvalidInput`.
+   *
+   * Windows has no signals: `SIGKILL` and `SIGTERM` terminate the process,
+   * while `SIGINT` and `SIGBREAK` send Ctrl+Break to a process started with
+   * `processGroup`.
+   * Requires the `--allow-run` flag.
+   */
+  export function kill(pid: number, signo: number | string): void;
+  /** Returns the scheduling priority of the process under given PID, or of
+   * the current process if `pid` is `0`, as a nice value from `-20` (highest)
+   * to `19` (lowest). On Windows, priority classes are mapped to nice values.
    * Requires the `--allow-run` flag.
    */
-  export function kill(pid: number, signo: number): void;
+  export function getPriority(pid?: number): number;
+  /** Sets the scheduling priority of the process under given PID, or of the
+   * current process if `pid` is `0`, as a nice value from `-20` (highest) to
+   * `19` (lowest). Raising the priority usually needs elevated privileges. On
+   * Windows, the nice value is mapped to the nearest priority class.
+   * Requires the `--allow-run` flag.
+   *
+   *       const indexer = Deno.run({ args: ["./indexer"] });
+   *       Deno.setPriority(indexer.pid, 19);
+   */
+  export function setPriority(pid: number, priority: number): void;
+  /** The master side of the pseudo-terminal of a subprocess started with
+   * `RunOptions.pty`. Reading it gives what the subprocess prints, writing to
+   * it types into the subprocess.
+   */
+  export class Pty extends File {
+    /** Change the size of the terminal, sending the subprocess `SIGWINCH`. */
+    resize(columns: number, rows: number): void;
+  }
   export class Process {
     readonly rid: number;
     readonly pid: number;
     readonly stdin?: WriteCloser;
     readonly stdout?: ReadCloser;
     readonly stderr?: ReadCloser;
+    readonly pty?: Pty;
     status(): Promise<ProcessStatus>;
+    /** Wait for the process to exit while reading its piped stdout and stderr
+     * to the end, all at once so that it can't get stuck writing to a full
+     * pipe. At most `maxBuffer` bytes of each are kept, and `truncated` is set
+     * if there were more. This closes stdout and stderr.
+     *
+     *       const p = Deno.run({ args, stdout: "piped", stderr: "piped" });
+     *       const { status, stdout, stderr } = await p.combinedOutput();
+     */
+    combinedOutput(maxBuffer?: number): Promise<ProcessOutput>;
     /** Buffer the stdout and return it as Uint8Array after EOF.
      * You must set stdout to "piped" when creating the process.
      * This calls close() on stdout after its done.
@@ -1130,12 +2511,30 @@ declare namespace Deno {
      */
     stderrOutput(): Promise<Uint8Array>;
     close(): void;
-    kill(signo: number): void;
+    kill(signo: number | string): void;
+    /** Send a signal to the process group the subprocess leads, reaching e.g.
+     * every command of a shell pipeline. The subprocess must have been started
+     * with `processGroup` or `detached`. On Windows it can only be sent
+     * `SIGINT` or `SIGBREAK`, which both become Ctrl+Break.
+     */
+    killGroup(signo: number | string): void;
+  }
+  export interface ProcessOutput {
+    status: ProcessStatus;
+    /** Empty unless stdout was piped. */
+    stdout: Uint8Array;
+    /** Empty unless stderr was piped. */
+    stderr: Uint8Array;
+    /** Set if stdout or stderr was longer than the buffer allowed for it. */
+    truncated: boolean;
   }
   export interface ProcessStatus {
     success: boolean;
     code?: number;
     signal?: number;
+    /** Set if the subprocess was killed for running past `RunOptions.timeout`.
+     */
+    timedOut?: boolean;
   }
   /**
    * Spawns new subprocess.
@@ -1148,9 +2547,56 @@ declare namespace Deno {
    *
    * By default subprocess inherits stdio of parent process. To change that
    * `opt.stdout`, `opt.stderr` and `opt.stdin` can be specified independently -
-   * they can be set to either `ProcessStdio` or `rid` of open file.
+   * they can be set to either `ProcessStdio` or the `rid` of an open file, of
+   * `Deno.stdout` or `Deno.stderr`, of a TCP connection (Unix only) or of
+   * another subprocess's piped stdio. The latter two are handed over to the
+   * subprocess and closed, e.g. to build a pipeline that never copies through
+   * JS:
+   *
+   *       const ls = Deno.run({ args: ["ls"], stdout: "piped" });
+   *       const wc = Deno.run({ args: ["wc", "-l"], stdin: ls.stdout!.rid });
+   *
+   * A subprocess started with `opt.detached` outlives its `Process` being
+   * closed, e.g. to launch a daemon:
+   *
+   *       const p = Deno.run({ args: ["./server"], detached: true });
+   *       p.close();
    */
   export function run(opt: RunOptions): Process;
+  export interface ExecOptions {
+    cwd?: string;
+    env?: { [key: string]: string };
+    /** Start the subprocess with an empty environment instead of a copy of
+     * ours, so that it only sees `env` and the variables named in `inheritEnv`.
+     * Defaults to `false`.
+     */
+    clearEnv?: boolean;
+    /** Names of the variables of our environment to pass on to the subprocess.
+     * Implies `clearEnv`. Variables we don't have are skipped.
+     */
+    inheritEnv?: string[];
+  }
+  /** Replaces the current process with the program `args[0]`, which is looked
+   * up in `PATH`, called with the remaining `args`. It keeps the pid and stdio
+   * of the current process, and `opt.env` is added to its environment. Never
+   * returns unless the program can't be started. Requires `allow-run`.
+   *
+   * Windows can't replace a process, so there the program is run as a
+   * subprocess instead and the current process exits with its exit code once
+   * it is done.
+   *
+   *       Deno.exec(["node", "server.js"], { env: { PORT: "8080" } });
+   */
+  export function exec(args: string[], opt?: ExecOptions): never;
+  /** Switches the current process to another user and/or group, e.g. to give
+   * up root once a server has bound a privileged port. The group is switched
+   * first. Switching as root also drops root's supplementary groups. Requires
+   * `allow-run`. Not supported on Windows.
+   *
+   *       const listener = Deno.listen({ port: 80 });
+   *       Deno.setIds({ uid: 1000, gid: 1000 });
+   */
+  export function setIds(ids: { uid?: number; gid?: number }): void;
   enum LinuxSignal {
     SIGHUP = 1,
     SIGINT = 2,
@@ -1222,6 +2668,25 @@ declare namespace Deno {
   export const Signal: typeof MacOSSignal | typeof LinuxSignal;
   export {};
 
+  // @url js/signals.d.ts
+
+  /** Yields every time the process receives the signal given by number or by
+   * name such as `"SIGTERM"`, which then no longer has its default effect.
+   * That comes back once the iteration ends and nothing else listens to the
+   * signal.
+   *
+   * On Windows, Ctrl+C and Ctrl+Break in the console are received as
+   * `SIGINT` and `SIGBREAK`, the only signals that can be listened to there.
+   * Signals that can't be caught, like `SIGKILL`, and `SIGCHLD` and `SIGPIPE`,
+   * which Deno handles itself, throw `InvalidInput`.
+   *
+   *       for await (const _ of Deno.signal("SIGINT")) {
+   *         await server.close();
+   *         Deno.exit();
+   *       }
+   */
+  export function signal(signo: number | string): AsyncIterableIterator<void>;
+
   // @url js/console.d.ts
 
   type ConsoleOptions = Partial<{
@@ -2311,10 +3776,10 @@ declare namespace io {
      * any positive offset is legal, but the behavior of subsequent I/O operations
      * on the underlying object is implementation-dependent.
      */
-    seek(offset: number, whence: SeekMode): Promise<void>;
+    seek(offset: number, whence: SeekMode): Promise<number>;
   }
   export interface SyncSeeker {
-    seekSync(offset: number, whence: SeekMode): void;
+    seekSync(offset: number, whence: SeekMode): number;
   }
   export interface ReadCloser extends Reader, Closer {}
   export interface WriteCloser extends Writer, Closer {}
@@ -2614,9 +4079,12 @@ declare namespace workers {
   /** Extended Deno Worker initialization options.
    * `noDenoNamespace` hides global `window.Deno` namespace for
    * spawned worker and nested workers spawned by it (default: false).
+   * `permissions` lists the permissions the worker keeps, as far as the
+   * spawning worker has them; the others are denied (default: all).
    */
   export interface DenoWorkerOptions extends WorkerOptions {
     noDenoNamespace?: boolean;
+    permissions?: Deno.Permission[];
   }
   export class WorkerImpl implements Worker {
     private readonly rid;
@@ -1,19 +1,31 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
 import { sendSync, sendAsync } from "./dispatch_json.ts";
 import * as dispatch from "./dispatch.ts";
+import { File } from "./files.ts";
 
-export interface MakeTempDirOptions {
+export interface MakeTempOptions {
   dir?: string;
   prefix?: string;
   suffix?: string;
 }
 
+export type MakeTempDirOptions = MakeTempOptions;
+
+export interface MakeTempFileOptions extends MakeTempOptions {
+  /** Create a file without a name, which is returned opened for reading and
+   * writing instead of a path. It disappears once closed, even if the process
+   * crashes, unless it's given a name with `linkTempFile`. `prefix` and
+   * `suffix` are ignored.
+   */
+  anonymous?: boolean;
+}
+
 /** makeTempDirSync is the synchronous version of `makeTempDir`.
  *
  *       const tempDirName0 = Deno.makeTempDirSync();
  *       const tempDirName1 = Deno.makeTempDirSync({ prefix: 'my_temp' });
  */
-export function makeTempDirSync(options: MakeTempDirOptions = {}): string {
+export function makeTempDirSync(options: MakeTempOptions = {}): string {
   return sendSync(dispatch.OP_MAKE_TEMP_DIR, options);
 }
 
@@ -22,14 +34,88 @@ export function makeTempDirSync(options: MakeTempDirOptions = {}): string {
  * It returns the full path to the newly created directory.
  * If `dir` is unspecified, tempDir uses the default directory for temporary
  * files. Multiple programs calling tempDir simultaneously will not choose the
- * same directory. It is the caller's responsibility to remove the directory
+ * same directory. On UNIX the directory is only accessible by its owner
+ * (mode 0o700). It is the caller's responsibility to remove the directory
  * when no longer needed.
  *
  *       const tempDirName0 = await Deno.makeTempDir();
  *       const tempDirName1 = await Deno.makeTempDir({ prefix: 'my_temp' });
  */
 export async function makeTempDir(
-  options: MakeTempDirOptions = {}
+  options: MakeTempOptions = {}
 ): Promise<string> {
   return await sendAsync(dispatch.OP_MAKE_TEMP_DIR, options);
 }
+
+/** makeTempFileSync is the synchronous version of `makeTempFile`.
+ *
+ *       const tempFileName0 = Deno.makeTempFileSync();
+ *       const tempFileName1 = Deno.makeTempFileSync({ prefix: 'my_temp' });
+ */
+export function makeTempFileSync(
+  options: MakeTempFileOptions & { anonymous: true }
+): File;
+export function makeTempFileSync(options?: MakeTempFileOptions): string;
+export function makeTempFileSync(
+  options: MakeTempFileOptions = {}
+): string | File {
+  const res = sendSync(dispatch.OP_MAKE_TEMP_FILE, options);
+  return options.anonymous ? new File(res) : res;
+}
+
+/** makeTempFile creates a new, empty temporary file in the directory `dir`,
+ * its name beginning with `prefix` and ending with `suffix`.
+ * It returns the full path to the newly created file.
+ * If `dir` is unspecified, tempFile uses the default directory for temporary
+ * files. The file is created exclusively, so multiple programs calling
+ * tempFile simultaneously will not choose the same file. On UNIX the file is
+ * only accessible by its owner (mode 0o600). It is the caller's responsibility
+ * to remove the file when no longer needed.
+ *
+ * With `anonymous` the file has no name and is returned opened instead. This
+ * uses O_TMPFILE on Linux and FILE_FLAG_DELETE_ON_CLOSE on Windows.
+ *
+ *       const tempFileName0 = await Deno.makeTempFile();
+ *       const tempFileName1 = await Deno.makeTempFile({ prefix: 'my_temp' });
+ *       const tempFile = await Deno.makeTempFile({ anonymous: true });
+ */
+export function makeTempFile(
+  options: MakeTempFileOptions & { anonymous: true }
+): Promise<File>;
+export function makeTempFile(options?: MakeTempFileOptions): Promise<string>;
+export async function makeTempFile(
+  options: MakeTempFileOptions = {}
+): Promise<string | File> {
+  const res = await sendAsync(dispatch.OP_MAKE_TEMP_FILE, options);
+  return options.anonymous ? new File(res) : res;
+}
+
+/** linkTempFileSync is the synchronous version of `linkTempFile`.
+ *
+ *       const file = Deno.makeTempFileSync({ anonymous: true, dir: "." });
+ *       Deno.writeAllSync(file, data);
+ *       Deno.linkTempFileSync(file.rid, "data.json");
+ */
+export function linkTempFileSync(rid: number, newname: string): void {
+  sendSync(dispatch.OP_LINK_TEMP_FILE, { rid, newname });
+}
+
+/** linkTempFile gives a file created by `makeTempFile` with `anonymous` the
+ * name `newname`, atomically replacing any file that's already there. Readers
+ * see either the old file or the complete new one, which makes this suitable
+ * for crash-safe writes. Requires `allow-write`.
+ *
+ * On Linux the file is linked into place when it was created with O_TMPFILE
+ * on the same file system as `newname`. Otherwise its contents are copied,
+ * which is slower. Any other kind of rid is rejected.
+ *
+ *       const file = await Deno.makeTempFile({ anonymous: true, dir: "." });
+ *       await Deno.writeAll(file, data);
+ *       await Deno.linkTempFile(file.rid, "data.json");
+ */
+export async function linkTempFile(
+  rid: number,
+  newname: string
+): Promise<void> {
+  await sendAsync(dispatch.OP_LINK_TEMP_FILE, { rid, newname });
+}
@@ -64,3 +64,136 @@ testPerm({ write: true }, async function makeTempDirSuccess(): Promise<void> {
   assertEquals(err.kind, Deno.ErrorKind.NotFound);
   assertEquals(err.name, "NotFound");
 });
+
+testPerm({ write: true, read: true }, function makeTempDirSyncMode(): void {
+  const path = Deno.makeTempDirSync();
+  const info = Deno.statSync(path);
+  if (Deno.build.os !== "win") {
+    assertEquals(info.mode! & 0o777, 0o700);
+  }
+});
+
+testPerm({ write: true, read: true }, function makeTempFileSyncSuccess(): void {
+  const file1 = Deno.makeTempFileSync({ prefix: "hello", suffix: "world" });
+  const file2 = Deno.makeTempFileSync({ prefix: "hello", suffix: "world" });
+  // Check that both files are different.
+  assert(file1 !== file2);
+  for (const file of [file1, file2]) {
+    // Check that the prefix and suffix are applied.
+    const lastPart = file.replace(/^.*[\\\/]/, "");
+    assert(lastPart.startsWith("hello"));
+    assert(lastPart.endsWith("world"));
+    const info = Deno.statSync(file);
+    assert(info.isFile());
+    assertEquals(info.len, 0);
+    if (Deno.build.os !== "win") {
+      assertEquals(info.mode! & 0o777, 0o600);
+    }
+  }
+  // Check that the `dir` option works.
+  const dir = Deno.makeTempDirSync();
+  const file3 = Deno.makeTempFileSync({ dir });
+  assert(file3.startsWith(dir));
+  assert(/^[\\\/]/.test(file3.slice(dir.length)));
+  // Check that creating a temp file inside a nonexisting directory fails.
+  let err;
+  try {
+    Deno.makeTempFileSync({ dir: "/baddir" });
+  } catch (err_) {
+    err = err_;
+  }
+  assertEquals(err.kind, Deno.ErrorKind.NotFound);
+  assertEquals(err.name, "NotFound");
+});
+
+test(function makeTempFileSyncPerm(): void {
+  // makeTempFileSync should require write permissions.
+  let err;
+  try {
+    Deno.makeTempFileSync({ dir: "/baddir" });
+  } catch (err_) {
+    err = err_;
+  }
+  assertEquals(err.kind, Deno.ErrorKind.PermissionDenied);
+  assertEquals(err.name, "PermissionDenied");
+});
+
+testPerm({ write: true }, async function makeTempFileSuccess(): Promise<void> {
+  const file1 = await Deno.makeTempFile({ prefix: "hello", suffix: "world" });
+  const file2 = await Deno.makeTempFile({ prefix: "hello", suffix: "world" });
+  // Check that both files are different.
+  assert(file1 !== file2);
+  // Check that creating a temp file inside a nonexisting directory fails.
+  let err;
+  try {
+    await Deno.makeTempFile({ dir: "/baddir" });
+  } catch (err_) {
+    err = err_;
+  }
+  assertEquals(err.kind, Deno.ErrorKind.NotFound);
+  assertEquals(err.name, "NotFound");
+});
+
+testPerm(
+  { write: true, read: true },
+  function makeTempFileSyncAnonymous(): void {
+    const dir = Deno.makeTempDirSync();
+    const file = Deno.makeTempFileSync({ anonymous: true, dir });
+    const data = new TextEncoder().encode("hello");
+    Deno.writeAllSync(file, data);
+    // The file has no name until it's linked. On Windows it has a hidden one
+    // until it's closed.
+    if (Deno.build.os !== "win") {
+      assertEquals(Deno.readDirSync(dir).length, 0);
+    }
+
+    Deno.writeFileSync(dir + "/data.txt", new TextEncoder().encode("old"));
+    Deno.linkTempFileSync(file.rid, dir + "/data.txt");
+    file.close();
+    assertEquals(Deno.readFileSync(dir + "/data.txt"), data);
+    assertEquals(Deno.readDirSync(dir).length, 1);
+  }
+);
+
+testPerm(
+  { write: true, read: true },
+  async function makeTempFileAnonymous(): Promise<void> {
+    const dir = await Deno.makeTempDir();
+    const file = await Deno.makeTempFile({ anonymous: true, dir });
+    await Deno.writeAll(file, new Uint8Array([1, 2, 3]));
+    file.close();
+    // Closing an unlinked file leaves nothing behind.
+    assertEquals((await Deno.readDir(dir)).length, 0);
+  }
+);
+
+testPerm(
+  { write: true, read: true },
+  function linkTempFileSyncNotAnonymous(): void {
+    const dir = Deno.makeTempDirSync();
+    Deno.writeFileSync(dir + "/a.txt", new TextEncoder().encode("hello"));
+    const file = Deno.openSync(dir + "/a.txt", "r");
+
+    let caughtError = false;
+    try {
+      Deno.linkTempFileSync(file.rid, dir + "/b.txt");
+    } catch (e) {
+      caughtError = true;
+      assertEquals(e.kind, Deno.ErrorKind.BadResource);
+    }
+    file.close();
+    assert(caughtError);
+    assertEquals(Deno.readDirSync(dir).length, 1);
+  }
+);
+
+testPerm({ write: false }, function makeTempFileSyncAnonymousPerm(): void {
+  let caughtError = false;
+  try {
+    Deno.makeTempFileSync({ anonymous: true });
+  } catch (e) {
+    caughtError = true;
+    assertEquals(e.kind, Deno.ErrorKind.PermissionDenied);
+  }
+  assert(caughtError);
+});
@@ -1,6 +1,20 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
 import * as dispatch from "./dispatch.ts";
 import { sendSync } from "./dispatch_json.ts";
+import { core } from "./core.ts";
+import { openSync } from "./files.ts";
+import { writeAllSync } from "./buffer.ts";
+
+export interface OpMetrics {
+  opsDispatched: number;
+  opsCompleted: number;
+  bytesSentControl: number;
+  bytesSentData: number;
+  bytesReceived: number;
+  /** Milliseconds from dispatch to completion, summed over the completed
+   * ops. Only precise to 2ms without `--allow-hrtime`. */
+  latency: number;
+}
 
 export interface Metrics {
   opsDispatched: number;
@@ -8,6 +22,8 @@ export interface Metrics {
   bytesSentControl: number;
   bytesSentData: number;
   bytesReceived: number;
+  /** The metrics of each op dispatched so far, by op name. */
+  ops: { [name: string]: OpMetrics };
 }
 
 /** Receive metrics from the privileged side of Deno.
@@ -22,7 +38,106 @@ export interface Metrics {
  *      │  bytesSentData   │   0    │
  *      │  bytesReceived   │  856   │
  *      └──────────────────┴────────┘
+ *
+ * The same counts are kept for every op, along with the time they took:
+ *
+ *      > console.table(Deno.metrics().ops)
  */
 export function metrics(): Metrics {
   return sendSync(dispatch.OP_METRICS);
 }
+
+export interface HeapStats {
+  totalHeapSize: number;
+  totalHeapSizeExecutable: number;
+  totalPhysicalSize: number;
+  totalAvailableSize: number;
+  usedHeapSize: number;
+  heapSizeLimit: number;
+  mallocedMemory: number;
+  peakMallocedMemory: number;
+  /** Memory held outside of the heap by objects in it, such as the backing
+   * stores of array buffers. */
+  externalMemory: number;
+  numberOfNativeContexts: number;
+  numberOfDetachedContexts: number;
+}
+
+/** Returns the statistics of the V8 heap of this isolate, in bytes. Unlike
+ * `Deno.metrics()`, it dispatches no op.
+ *
+ *       const { usedHeapSize, heapSizeLimit } = Deno.heapStats();
+ */
+export function heapStats(): HeapStats {
+  return core.heapStats();
+}
+
+/** Takes a snapshot of the V8 heap and writes it to `path`, which the memory
+ * panel of the Chrome DevTools can load. Requires `allow-write`.
+ *
+ *       Deno.writeHeapSnapshot("app.heapsnapshot");
+ */
+export function writeHeapSnapshot(path: string): void {
+  // Opening the file checks the permission before the snapshot is taken.
+  const file = openSync(path, "w");
+  try {
+    core.heapSnapshot((chunk: Uint8Array): void => {
+      writeAllSync(file, chunk);
+    });
+  } finally {
+    file.close();
+  }
+}
+
+export interface CpuProfileNode {
+  id: number;
+  callFrame: {
+    functionName: string;
+    scriptId: string;
+    url: string;
+    /** Zero based. */
+    lineNumber: number;
+    /** Zero based. */
+    columnNumber: number;
+  };
+  /** The number of samples in which this node was on top of the stack. */
+  hitCount: number;
+  /** The ids of the nodes called from this one. */
+  children: number[];
+}
+
+/** A CPU profile in the .cpuprofile format. */
+export interface CpuProfile {
+  /** The nodes of the call tree, the first one being its root. */
+  nodes: CpuProfileNode[];
+  /** In microseconds. */
+  startTime: number;
+  /** In microseconds. */
+  endTime: number;
+  /** The id of the node on top of the stack at each sample. */
+  samples: number[];
+  /** The microseconds between each sample and the previous one, or the start
+   * of the profile for the first one. */
+  timeDeltas: number[];
+}
+
+/** Starts sampling this isolate with the V8 CPU profiler, until
+ * `Deno.stopCpuProfile()` is called. Throws if it's already started.
+ */
+export function startCpuProfile(): void {
+  core.startCpuProfile();
+}
+
+/** Stops the CPU profiler and returns the profile it recorded. Written to a
+ * file with the `.cpuprofile` extension, it can be loaded in the performance
+ * panel of the Chrome DevTools.
+ *
+ *       Deno.startCpuProfile();
+ *       await handleRequests();
+ *       const profile = Deno.stopCpuProfile();
+ *       const data = new TextEncoder().encode(JSON.stringify(profile));
+ *       Deno.writeFileSync("app.cpuprofile", data);
+ */
+export function stopCpuProfile(): CpuProfile {
+  return core.stopCpuProfile();
+}
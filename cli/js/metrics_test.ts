@@ -1,5 +1,5 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
-import { test, testPerm, assert } from "./test_util.ts";
+import { test, testPerm, assert, assertEquals } from "./test_util.ts";
 
 test(async function metrics(): Promise<void> {
   const m1 = Deno.metrics();
@@ -22,6 +22,32 @@ test(async function metrics(): Promise<void> {
   assert(m2.bytesReceived > m1.bytesReceived);
 });
 
+test(async function metricsPerOp(): Promise<void> {
+  const dataMsg = new Uint8Array([41, 42, 43]);
+  await Deno.stdout.write(dataMsg);
+
+  const m1 = Deno.metrics();
+  const write1 = m1.ops["write"];
+  assert(write1.opsDispatched > 0);
+  assert(write1.opsCompleted > 0);
+  assert(write1.bytesSentData >= dataMsg.byteLength);
+  assert(write1.latency >= 0);
+
+  await Deno.stdout.write(dataMsg);
+
+  const write2 = Deno.metrics().ops["write"];
+  assertEquals(write2.opsDispatched, write1.opsDispatched + 1);
+  assertEquals(write2.opsCompleted, write1.opsCompleted + 1);
+  assertEquals(write2.bytesSentData, write1.bytesSentData + dataMsg.byteLength);
+  assert(write2.latency >= write1.latency);
+
+  let dispatched = 0;
+  for (const name in m1.ops) {
+    dispatched += m1.ops[name].opsDispatched;
+  }
+  assertEquals(dispatched, m1.opsDispatched);
+});
+
 testPerm({ write: true }, function metricsUpdatedIfNoResponseSync(): void {
   const filename = Deno.makeTempDirSync() + "/test.txt";
 
@@ -44,3 +70,65 @@ testPerm(
     assert(metrics.opsDispatched === metrics.opsCompleted);
   }
 );
+
+test(function heapStats(): void {
+  const stats = Deno.heapStats();
+  assert(stats.usedHeapSize > 0);
+  assert(stats.totalHeapSize >= stats.usedHeapSize);
+  assert(stats.heapSizeLimit > stats.totalHeapSize);
+  assert(stats.numberOfNativeContexts >= 1);
+
+  const before = Deno.heapStats().externalMemory;
+  const buf = new Uint8Array(1 << 20);
+  assert(Deno.heapStats().externalMemory >= before + buf.byteLength);
+});
+
+testPerm({ read: true, write: true }, function writeHeapSnapshot(): void {
+  const filename = Deno.makeTempDirSync() + "/test.heapsnapshot";
+  Deno.writeHeapSnapshot(filename);
+  const json = new TextDecoder().decode(Deno.readFileSync(filename));
+  const snapshot = JSON.parse(json);
+  assert(snapshot.snapshot.node_count > 0);
+  assert(snapshot.strings.length > 0);
+});
+
+testPerm({ write: false }, function writeHeapSnapshotPerm(): void {
+  let caughtError = false;
+  try {
+    Deno.writeHeapSnapshot("test.heapsnapshot");
+  } catch (e) {
+    caughtError = true;
+    assertEquals(e.kind, Deno.ErrorKind.PermissionDenied);
+    assertEquals(e.name, "PermissionDenied");
+  }
+  assert(caughtError);
+});
+
+test(function cpuProfile(): void {
+  Deno.startCpuProfile();
+  let x = 0;
+  for (let i = 0; i < 1e6; i++) {
+    x += Math.sqrt(i);
+  }
+  assert(x > 0);
+  const profile = Deno.stopCpuProfile();
+  assertEquals(profile.nodes[0].callFrame.functionName, "(root)");
+  assertEquals(profile.samples.length, profile.timeDeltas.length);
+  assert(profile.endTime >= profile.startTime);
+  const ids = new Set(profile.nodes.map((node): number => node.id));
+  for (const node of profile.nodes) {
+    for (const child of node.children) {
+      assert(ids.has(child));
+    }
+  }
+});
+
+test(function cpuProfileNotStarted(): void {
+  let caughtError = false;
+  try {
+    Deno.stopCpuProfile();
+  } catch (e) {
+    caughtError = true;
+  }
+  assert(caughtError);
+});
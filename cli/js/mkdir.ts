@@ -2,32 +2,77 @@
 import { sendSync, sendAsync } from "./dispatch_json.ts";
 import * as dispatch from "./dispatch.ts";
 
+export interface MkdirOption {
+  /** Create missing parent directories as well (also known as "mkdir -p"),
+   * and don't fail if `path` already is a directory. Defaults to `false`.
+   */
+  recursive?: boolean;
+  /** Permission bits (before umask) for every created directory on UNIX,
+   * ignored on Windows. Defaults to `0o777`.
+   */
+  mode?: number;
+}
+
+function mkdirArgs(
+  path: string,
+  optionsOrRecursive?: MkdirOption | boolean,
+  mode?: number
+): object {
+  if (typeof optionsOrRecursive === "object") {
+    const { recursive = false, mode = 0o777 } = optionsOrRecursive;
+    return { path, recursive, mode };
+  }
+  return {
+    path,
+    recursive: !!optionsOrRecursive,
+    mode: mode === undefined ? 0o777 : mode
+  };
+}
+
 /** Creates a new directory with the specified path synchronously.
  * If `recursive` is set to true, nested directories will be created (also known
  * as "mkdir -p").
- * `mode` sets permission bits (before umask) on UNIX and does nothing on
- * Windows.
+ * `mode` sets permission bits (before umask) of every created directory on
+ * UNIX and does nothing on Windows.
  *
  *       Deno.mkdirSync("new_dir");
  *       Deno.mkdirSync("nested/directories", true);
+ *       Deno.mkdirSync("private/dirs", { recursive: true, mode: 0o700 });
  */
-export function mkdirSync(path: string, recursive = false, mode = 0o777): void {
-  sendSync(dispatch.OP_MKDIR, { path, recursive, mode });
+export function mkdirSync(path: string, options?: MkdirOption): void;
+export function mkdirSync(
+  path: string,
+  recursive?: boolean,
+  mode?: number
+): void;
+export function mkdirSync(
+  path: string,
+  optionsOrRecursive?: MkdirOption | boolean,
+  mode?: number
+): void {
+  sendSync(dispatch.OP_MKDIR, mkdirArgs(path, optionsOrRecursive, mode));
 }
 
 /** Creates a new directory with the specified path.
  * If `recursive` is set to true, nested directories will be created (also known
  * as "mkdir -p").
- * `mode` sets permission bits (before umask) on UNIX and does nothing on
- * Windows.
+ * `mode` sets permission bits (before umask) of every created directory on
+ * UNIX and does nothing on Windows.
  *
  *       await Deno.mkdir("new_dir");
  *       await Deno.mkdir("nested/directories", true);
+ *       await Deno.mkdir("private/dirs", { recursive: true, mode: 0o700 });
  */
+export function mkdir(path: string, options?: MkdirOption): Promise<void>;
+export function mkdir(
+  path: string,
+  recursive?: boolean,
+  mode?: number
+): Promise<void>;
 export async function mkdir(
   path: string,
-  recursive = false,
-  mode = 0o777
+  optionsOrRecursive?: MkdirOption | boolean,
+  mode?: number
 ): Promise<void> {
-  await sendAsync(dispatch.OP_MKDIR, { path, recursive, mode });
+  await sendAsync(dispatch.OP_MKDIR, mkdirArgs(path, optionsOrRecursive, mode));
 }
@@ -64,3 +64,52 @@ testPerm({ read: true, write: true }, async function mkdirRecursive(): Promise<
   const pathInfo = Deno.statSync(path);
   assert(pathInfo.isDirectory());
 });
+
+testPerm({ read: true, write: true }, function mkdirSyncRecursiveMode(): void {
+  const tempDir = Deno.makeTempDirSync();
+  const path = tempDir + "/nested/directory";
+  Deno.mkdirSync(path, { recursive: true, mode: 0o700 });
+  assert(Deno.statSync(path).isDirectory());
+  // The mode applies to intermediate directories too.
+  for (const p of [path, tempDir + "/nested"]) {
+    const pathInfo = Deno.statSync(p);
+    if (pathInfo.mode !== null) {
+      // Skip windows
+      assertEquals(pathInfo.mode & 0o777, 0o700);
+    }
+  }
+});
+
+testPerm({ read: true, write: true }, async function mkdirOptions(): Promise<
+  void
+> {
+  const path = Deno.makeTempDirSync() + "/nested/directory";
+  await Deno.mkdir(path, { recursive: true });
+  assert(Deno.statSync(path).isDirectory());
+});
+
+testPerm(
+  { read: true, write: true },
+  function mkdirSyncRecursiveIfExists(): void {
+    const path = Deno.makeTempDirSync() + "/nested/directory";
+    Deno.mkdirSync(path, { recursive: true });
+    // Unlike the non-recursive variant, an existing directory is fine.
+    Deno.mkdirSync(path, { recursive: true });
+    assert(Deno.statSync(path).isDirectory());
+  }
+);
+
+testPerm(
+  { read: true, write: true },
+  function mkdirSyncRecursiveOverFile(): void {
+    const path = Deno.makeTempDirSync() + "/file";
+    Deno.writeFileSync(path, new Uint8Array());
+    let err;
+    try {
+      Deno.mkdirSync(path, { recursive: true });
+    } catch (e) {
+      err = e;
+    }
+    assert(!!err);
+  }
+);
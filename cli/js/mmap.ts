@@ -0,0 +1,61 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { core } from "./core.ts";
+import { sendSync } from "./dispatch_json.ts";
+import * as dispatch from "./dispatch.ts";
+import { DenoError, ErrorKind } from "./errors.ts";
+import { Closer } from "./io.ts";
+
+/** A file mapped into memory. `bytes` accesses the mapping directly, without
+ * copying it or going through the file system. Writes to `bytes` are private
+ * to the mapping and never reach the file.
+ *
+ * If someone truncates the file while it's mapped, accessing `bytes` past the
+ * new end of the file raises SIGBUS, which kills the process. Only map files
+ * that don't shrink.
+ */
+export class MappedFile implements Closer {
+  constructor(readonly bytes: Uint8Array) {}
+
+  /** The size of the mapping, or 0 once it's unmapped. */
+  get byteLength(): number {
+    return this.bytes.byteLength;
+  }
+
+  /** Unmaps the file right away, instead of once `bytes` is garbage
+   * collected. `bytes` and every other view of the mapping become empty.
+   */
+  unmap(): void {
+    if (this.bytes.byteLength === 0) {
+      throw new DenoError(
+        ErrorKind.BadResource,
+        "Mapping is already unmapped"
+      );
+    }
+    core.release(this.bytes);
+  }
+
+  close(): void {
+    this.unmap();
+  }
+}
+
+const control = new Uint32Array(1);
+const controlBytes = new Uint8Array(control.buffer);
+
+/** Maps the file referenced by `rid` into memory. The mapping remains valid
+ * after the file is closed, until it's unmapped.
+ *
+ *       const file = Deno.openSync("data.bin");
+ *       const mapped = Deno.mmap(file.rid);
+ *       file.close();
+ *       const header = mapped.bytes.subarray(0, 16);
+ *       mapped.unmap();
+ */
+export function mmap(rid: number): MappedFile {
+  control[0] = sendSync(dispatch.OP_MMAP, { rid });
+  const bytes = core.dispatch(dispatch.OP_MMAP_BUFFER, controlBytes);
+  if (!bytes) {
+    throw new DenoError(ErrorKind.BadResource, "bad resource id");
+  }
+  return new MappedFile(bytes);
+}
@@ -0,0 +1,81 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { testPerm, assert, assertEquals } from "./test_util.ts";
+
+testPerm({ read: true }, function mmapRead(): void {
+  const expected = Deno.readFileSync("tests/hello.txt");
+  const file = Deno.openSync("tests/hello.txt");
+  const mapped = Deno.mmap(file.rid);
+  // The mapping outlives the file.
+  file.close();
+
+  assertEquals(mapped.byteLength, expected.byteLength);
+  assertEquals(mapped.bytes, expected);
+  assertEquals(mapped.bytes.subarray(1, 4), expected.subarray(1, 4));
+  // The mapping isn't a resource of its own.
+  assert(!Object.values(Deno.resources()).includes("mmap"));
+
+  mapped.unmap();
+});
+
+testPerm({ read: true, write: true }, function mmapWrite(): void {
+  const filename = Deno.makeTempDirSync() + "/test.bin";
+  Deno.writeFileSync(filename, new Uint8Array([1, 2, 3, 4]));
+  const file = Deno.openSync(filename);
+  const mapped = Deno.mmap(file.rid);
+  file.close();
+
+  mapped.bytes.set([9, 9], 2);
+  assertEquals(mapped.bytes, new Uint8Array([1, 2, 9, 9]));
+  // Writes never reach the file.
+  assertEquals(Deno.readFileSync(filename), new Uint8Array([1, 2, 3, 4]));
+
+  mapped.unmap();
+});
+
+testPerm({ read: true }, function mmapUnmap(): void {
+  const file = Deno.openSync("tests/hello.txt");
+  const mapped = Deno.mmap(file.rid);
+  file.close();
+  const view = mapped.bytes.subarray(1);
+  mapped.unmap();
+
+  assertEquals(mapped.byteLength, 0);
+  assertEquals(mapped.bytes.byteLength, 0);
+  assertEquals(view.byteLength, 0);
+
+  let caughtError = false;
+  try {
+    mapped.unmap();
+  } catch (e) {
+    caughtError = true;
+    assertEquals(e.kind, Deno.ErrorKind.BadResource);
+  }
+  assert(caughtError);
+});
+
+testPerm({ read: true }, function mmapBadResource(): void {
+  let caughtError = false;
+  try {
+    Deno.mmap(12345);
+  } catch (e) {
+    caughtError = true;
+    assertEquals(e.kind, Deno.ErrorKind.BadResource);
+  }
+  assert(caughtError);
+});
+
+testPerm({ read: true, write: true }, function mmapEmptyFile(): void {
+  const filename = Deno.makeTempDirSync() + "/empty.bin";
+  Deno.writeFileSync(filename, new Uint8Array());
+  const file = Deno.openSync(filename);
+
+  let caughtError = false;
+  try {
+    Deno.mmap(file.rid);
+  } catch (e) {
+    caughtError = true;
+    assertEquals(e.kind, Deno.ErrorKind.InvalidInput);
+  }
+  assert(caughtError);
+  file.close();
+});
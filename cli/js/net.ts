@@ -26,6 +26,12 @@ export interface Listener extends AsyncIterator<Conn> {
    */
   close(): void;
 
+  /** Closes the listener like `close()`, except that a pending accept promise
+   * first gets a connection that is already waiting to be accepted, if there
+   * is one. TLS listeners are closed right away.
+   */
+  drain(): void;
+
   /** Return the address of the `Listener`. */
   addr(): Addr;
 
@@ -94,6 +100,10 @@ export class ListenerImpl implements Listener {
     close(this.rid);
   }
 
+  drain(): void {
+    sendSync(dispatch.OP_DRAIN_LISTENER, { rid: this.rid });
+  }
+
   addr(): Addr {
     return {
       transport: this.transport,
@@ -22,18 +22,19 @@ testPerm({ net: true }, async function netCloseWhileAccept(): Promise<void> {
     err = e;
   }
   assert(!!err);
-  assertEquals(err.kind, Deno.ErrorKind.Other);
-  assertEquals(err.message, "Listener has been closed");
+  assertEquals(err.kind, Deno.ErrorKind.Interrupted);
+  assertEquals(err.message, "resource closed");
 });
 
 testPerm({ net: true }, async function netConcurrentAccept(): Promise<void> {
   const listener = Deno.listen({ port: 4502 });
   let acceptErrCount = 0;
   const checkErr = (e): void => {
-    assertEquals(e.kind, Deno.ErrorKind.Other);
-    if (e.message === "Listener has been closed") {
+    if (e.message === "resource closed") {
+      assertEquals(e.kind, Deno.ErrorKind.Interrupted);
       assertEquals(acceptErrCount, 1);
     } else if (e.message === "Another accept task is ongoing") {
+      assertEquals(e.kind, Deno.ErrorKind.Other);
       acceptErrCount++;
     } else {
       throw new Error("Unexpected error message");
@@ -47,6 +48,117 @@ testPerm({ net: true }, async function netConcurrentAccept(): Promise<void> {
   assertEquals(acceptErrCount, 1);
 });
 
+testPerm({ net: true }, async function netWritev(): Promise<void> {
+  const listener = Deno.listen({ port: 4500 });
+  const p = listener.accept();
+  const conn = await Deno.dial({ hostname: "127.0.0.1", port: 4500 });
+  const accepted = await p;
+  const header = new TextEncoder().encode("header\n");
+  const body = new Uint8Array(1 << 20).fill(42);
+  const written = Deno.writev(accepted.rid, [header, body]).then(
+    (n): number => {
+      accepted.close();
+      return n;
+    }
+  );
+  const received = await Deno.readAll(conn);
+  assertEquals(await written, header.length + body.length);
+  assertEquals(received.length, header.length + body.length);
+  assertEquals(received.subarray(0, header.length), header);
+  assert(received.subarray(header.length).every((b): boolean => b === 42));
+  conn.close();
+  listener.close();
+});
+
+testPerm({ net: true }, async function netWaitReady(): Promise<void> {
+  const listener = Deno.listen({ port: 4500 });
+  const p = listener.accept();
+  const conn = await Deno.dial({ hostname: "127.0.0.1", port: 4500 });
+  const accepted = await p;
+  await Deno.waitWritable(conn.rid);
+
+  let readable = false;
+  const ready = Deno.waitReadable(accepted.rid).then((): void => {
+    readable = true;
+  });
+  await Deno.waitWritable(conn.rid);
+  assert(!readable);
+  await conn.write(new Uint8Array([1, 2, 3]));
+  await ready;
+  // Still readable, since nothing has been read.
+  await Deno.waitReadable(accepted.rid);
+  const buf = new Uint8Array(3);
+  assertEquals(await accepted.read(buf), 3);
+
+  let err;
+  try {
+    await Deno.waitReadable(listener.rid);
+  } catch (e) {
+    err = e;
+  }
+  assertEquals(err.kind, Deno.ErrorKind.BadResource);
+
+  accepted.close();
+  conn.close();
+  listener.close();
+});
+
+testPerm({ net: true }, async function netDrainWhileAccept(): Promise<void> {
+  const listener = Deno.listen({ port: 4500 });
+  const p = listener.accept();
+  const conn = await Deno.dial({ hostname: "127.0.0.1", port: 4500 });
+  listener.drain();
+  const accepted = await p;
+  await accepted.write(new Uint8Array([1]));
+  const buf = new Uint8Array(1);
+  assertEquals(await conn.read(buf), 1);
+  accepted.close();
+  conn.close();
+
+  let err;
+  try {
+    await listener.accept();
+  } catch (e) {
+    err = e;
+  }
+  assertEquals(err.kind, Deno.ErrorKind.BadResource);
+});
+
+testPerm({ net: true }, async function netDrainIdle(): Promise<void> {
+  const listener = Deno.listen({ port: 4500 });
+  listener.drain();
+  // With no accept pending, the listener is closed right away.
+  Deno.listen({ port: 4500 }).close();
+});
+
+testPerm({ net: true }, async function netCloseWhileRead(): Promise<void> {
+  const listener = Deno.listen({ port: 4500 });
+  const accepted = listener.accept();
+  const conn = await Deno.dial({ hostname: "127.0.0.1", port: 4500 });
+  const p = conn.read(new Uint8Array(1024));
+  conn.close();
+  let err;
+  try {
+    await p;
+  } catch (e) {
+    err = e;
+  }
+  assert(!!err);
+  assertEquals(err.kind, Deno.ErrorKind.Interrupted);
+  assertEquals(err.message, "resource closed");
+
+  // A closed resource is no longer there for new ops.
+  err = undefined;
+  try {
+    await conn.read(new Uint8Array(1024));
+  } catch (e) {
+    err = e;
+  }
+  assertEquals(err.kind, Deno.ErrorKind.BadResource);
+  (await accepted).close();
+  listener.close();
+});
+
 testPerm({ net: true }, async function netDialListen(): Promise<void> {
   const listener = Deno.listen({ port: 4500 });
   listener.accept().then(
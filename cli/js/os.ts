@@ -27,8 +27,161 @@ export function hostname(): string {
   return sendSync(dispatch.OP_HOSTNAME);
 }
 
-/** Exit the Deno process with optional exit code. */
+interface ProcessIds {
+  ppid: number;
+  uid: number | null;
+  gid: number | null;
+  euid: number | null;
+  egid: number | null;
+}
+
+function processIds(): ProcessIds {
+  return sendSync(dispatch.OP_PROCESS_IDS);
+}
+
+/** Returns the pid of the parent process. */
+export function ppid(): number {
+  return processIds().ppid;
+}
+
+/** Returns the real user id of the process, or `null` on Windows. */
+export function uid(): number | null {
+  return processIds().uid;
+}
+
+/** Returns the real group id of the process, or `null` on Windows. */
+export function gid(): number | null {
+  return processIds().gid;
+}
+
+/** Returns the effective user id of the process, which its permissions are
+ * checked against, or `null` on Windows.
+ *
+ *       if (Deno.euid() === 0) {
+ *         throw new Error("refusing to run as root");
+ *       }
+ */
+export function euid(): number | null {
+  return processIds().euid;
+}
+
+/** Returns the effective group id of the process, or `null` on Windows. */
+export function egid(): number | null {
+  return processIds().egid;
+}
+
+/** Information about the operating system, as returned by `Deno.osInfo()`. */
+export interface OSInfo {
+  /** Same as `Deno.build.os`. */
+  os: OperatingSystem;
+  /** Same as `Deno.build.arch`. */
+  arch: Arch;
+  /** Name of the OS or distribution, e.g. `"Ubuntu"`, `"macOS"` or
+   * `"Windows"`. */
+  name: string;
+  /** Version of the OS or distribution, e.g. `"18.04"` or `"10.14.6"`. Empty
+   * if it can't be determined. */
+  version: string;
+  /** Name of the kernel, e.g. `"Linux"`, `"Darwin"` or `"Windows_NT"`. */
+  kernelName: string;
+  /** Release of the kernel, e.g. `"5.0.0-29-generic"`. */
+  kernelRelease: string;
+  /** Build details of the kernel. Empty on Windows. */
+  kernelVersion: string;
+  /** Hardware architecture as reported by the OS, e.g. `"x86_64"`. */
+  machine: string;
+}
+
+/** Get structured information about the operating system and kernel.
+ * Requires the `--allow-env` flag.
+ *
+ *       const { name, version, kernelRelease } = Deno.osInfo();
+ */
+export function osInfo(): OSInfo {
+  return sendSync(dispatch.OP_OS_INFO);
+}
+
+/** Returns the 1, 5 and 15 minute load averages. Always `[0, 0, 0]` on
+ * Windows, which has no such concept.
+ * Requires the `--allow-env` flag.
+ *
+ *       const [oneMinute] = Deno.loadavg();
+ */
+export function loadavg(): number[] {
+  return sendSync(dispatch.OP_LOADAVG);
+}
+
+/** Memory figures returned by `Deno.memInfo()`, all in bytes. */
+export interface MemInfo {
+  /** Total physical memory. */
+  total: number;
+  /** Memory that is not used at all. */
+  free: number;
+  /** Memory that can be handed to new allocations without swapping,
+   * including reclaimable caches. */
+  available: number;
+  swapTotal: number;
+  swapFree: number;
+  /** Resident set size of the current process. */
+  rss: number;
+}
+
+/** Get system memory usage and the resident set size of this process.
+ * Requires the `--allow-env` flag.
+ *
+ *       const { available, total } = Deno.memInfo();
+ */
+export function memInfo(): MemInfo {
+  return sendSync(dispatch.OP_MEM_INFO);
+}
+
+/** An address assigned to a network interface. */
+export interface NetworkInterfaceAddress {
+  family: "IPv4" | "IPv6";
+  address: string;
+  /** `null` if the OS doesn't report a netmask for the address. */
+  netmask: string | null;
+}
+
+/** A network interface, as returned by `Deno.networkInterfaces()`. */
+export interface NetworkInterface {
+  name: string;
+  /** Hardware address, e.g. `"00:1a:2b:3c:4d:5e"`. `null` for interfaces
+   * that don't have one, like loopback. */
+  mac: string | null;
+  up: boolean;
+  loopback: boolean;
+  addresses: NetworkInterfaceAddress[];
+}
+
+/** Lists the network interfaces of the machine with their addresses.
+ * Requires the `--allow-env` flag.
+ *
+ *       for (const iface of Deno.networkInterfaces()) {
+ *         if (iface.up && !iface.loopback) {
+ *           console.log(iface.name, iface.addresses);
+ *         }
+ *       }
+ */
+export function networkInterfaces(): NetworkInterface[] {
+  return sendSync(dispatch.OP_NETWORK_INTERFACES);
+}
+
+let unloaded = false;
+
+/** Called when the window `unload` event is dispatched. */
+export function setUnloaded(): void {
+  unloaded = true;
+}
+
+/** Exit the Deno process with optional exit code. The window `unload` event
+ * is dispatched first, unless exiting from one of its handlers, and output
+ * still on its way to stdout and stderr is written.
+ */
 export function exit(code = 0): never {
+  if (!unloaded) {
+    window.dispatchEvent(new window.Event("unload"));
+  }
   sendSync(dispatch.OP_EXIT, { code });
   return util.unreachable();
 }
@@ -41,15 +194,21 @@ function getEnv(key: string): string | undefined {
   return sendSync(dispatch.OP_GET_ENV, { key })[0];
 }
 
+function deleteEnv(key: string): void {
+  sendSync(dispatch.OP_DELETE_ENV, { key });
+}
+
 /** Returns a snapshot of the environment variables at invocation. Mutating a
  * property in the object will set that variable in the environment for
- * the process. The environment object will only accept `string`s
- * as values.
+ * the process, and deleting one will remove the variable. The environment
+ * object will only accept `string`s as values. Each call takes a fresh
+ * snapshot, reflecting changes made since.
  *
  *       console.log(Deno.env("SHELL"));
  *       const myEnv = Deno.env();
  *       console.log(myEnv.SHELL);
  *       myEnv.TEST_VAR = "HELLO";
+ *       delete myEnv.OTHER_VAR;
  *       const newEnv = Deno.env();
  *       console.log(myEnv.TEST_VAR == newEnv.TEST_VAR);
  */
@@ -66,6 +225,10 @@ export function env(
     set(obj, prop: string, value: string): boolean {
       setEnv(prop, value);
       return Reflect.set(obj, prop, value);
+    },
+    deleteProperty(obj, prop: string): boolean {
+      deleteEnv(prop);
+      return Reflect.deleteProperty(obj, prop);
     }
   });
 }
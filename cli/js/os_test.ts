@@ -17,6 +17,28 @@ testPerm({ env: true }, function envSuccess(): void {
   assertEquals(Deno.env("test_var"), env.test_var);
 });
 
+testPerm({ env: true }, function envDelete(): void {
+  const env = Deno.env();
+  env.test_delete_var = "Hello World";
+  assertEquals(Deno.env("test_delete_var"), "Hello World");
+  delete env.test_delete_var;
+  assertEquals(Deno.env("test_delete_var"), undefined);
+  assert(!("test_delete_var" in Deno.env()));
+});
+
+testPerm({ env: true }, function envInvalidKey(): void {
+  const env = Deno.env();
+  let err;
+  try {
+    env["a=b"] = "value";
+  } catch (e) {
+    err = e;
+  }
+  assert(!!err);
+  assertEquals(err.kind, Deno.ErrorKind.InvalidInput);
+  assertEquals(Deno.env("a"), undefined);
+});
+
 testPerm({ env: true }, function envNotFound(): void {
   const r = Deno.env("env_var_does_not_exist!");
   assertEquals(r, undefined);
@@ -152,6 +174,19 @@ testPerm({ env: true }, function hostnameDir(): void {
   assertNotEquals(Deno.hostname(), "");
 });
 
+test(function processIds(): void {
+  assert(Deno.ppid() > 0);
+  assert(Deno.ppid() !== Deno.pid);
+  const ids = [Deno.uid(), Deno.gid(), Deno.euid(), Deno.egid()];
+  for (const id of ids) {
+    if (Deno.build.os === "win") {
+      assertEquals(id, null);
+    } else {
+      assertEquals(typeof id, "number");
+    }
+  }
+});
+
 testPerm({ env: false }, function hostnamePerm(): void {
   let caughtError = false;
   try {
@@ -163,3 +198,98 @@ testPerm({ env: false }, function hostnamePerm(): void {
   }
   assert(caughtError);
 });
+
+testPerm({ env: true }, function osInfo(): void {
+  const info = Deno.osInfo();
+  assertEquals(info.os, Deno.build.os);
+  assertEquals(info.arch, Deno.build.arch);
+  assertNotEquals(info.name, "");
+  assertNotEquals(info.kernelName, "");
+  assertNotEquals(info.kernelRelease, "");
+  assertNotEquals(info.machine, "");
+});
+
+testPerm({ env: false }, function osInfoPerm(): void {
+  let caughtError = false;
+  try {
+    Deno.osInfo();
+  } catch (err) {
+    caughtError = true;
+    assertEquals(err.kind, Deno.ErrorKind.PermissionDenied);
+    assertEquals(err.name, "PermissionDenied");
+  }
+  assert(caughtError);
+});
+
+testPerm({ env: true }, function loadavg(): void {
+  const load = Deno.loadavg();
+  assertEquals(load.length, 3);
+  for (const n of load) {
+    assert(n >= 0);
+  }
+});
+
+testPerm({ env: false }, function loadavgPerm(): void {
+  let caughtError = false;
+  try {
+    Deno.loadavg();
+  } catch (err) {
+    caughtError = true;
+    assertEquals(err.kind, Deno.ErrorKind.PermissionDenied);
+    assertEquals(err.name, "PermissionDenied");
+  }
+  assert(caughtError);
+});
+
+testPerm({ env: true }, function memInfo(): void {
+  const mem = Deno.memInfo();
+  assert(mem.total > 0);
+  assert(mem.free <= mem.total);
+  assert(mem.available <= mem.total);
+  assert(mem.swapFree <= mem.swapTotal);
+  assert(mem.rss > 0);
+  assert(mem.rss <= mem.total);
+});
+
+testPerm({ env: false }, function memInfoPerm(): void {
+  let caughtError = false;
+  try {
+    Deno.memInfo();
+  } catch (err) {
+    caughtError = true;
+    assertEquals(err.kind, Deno.ErrorKind.PermissionDenied);
+    assertEquals(err.name, "PermissionDenied");
+  }
+  assert(caughtError);
+});
+
+testPerm({ env: true }, function networkInterfaces(): void {
+  const interfaces = Deno.networkInterfaces();
+  assert(interfaces.length > 0);
+  const loopback = interfaces.find((i): boolean => i.loopback);
+  assert(loopback !== undefined);
+  assert(
+    loopback!.addresses.some(
+      (a): boolean => a.address === "127.0.0.1" || a.address === "::1"
+    )
+  );
+  for (const iface of interfaces) {
+    assertNotEquals(iface.name, "");
+    for (const { family, address } of iface.addresses) {
+      assert(family === "IPv4" || family === "IPv6");
+      assertEquals(address.includes(":"), family === "IPv6");
+    }
+  }
+});
+
+testPerm({ env: false }, function networkInterfacesPerm(): void {
+  let caughtError = false;
+  try {
+    Deno.networkInterfaces();
+  } catch (err) {
+    caughtError = true;
+    assertEquals(err.kind, Deno.ErrorKind.PermissionDenied);
+    assertEquals(err.name, "PermissionDenied");
+  }
+  assert(caughtError);
+});
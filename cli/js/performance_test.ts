@@ -8,3 +8,14 @@ testPerm({ hrtime: false }, function now(): void {
     assert(end - start >= 10);
   }, 10);
 });
+
+testPerm({ hrtime: false }, function nowCoarse(): void {
+  const start = performance.now();
+  let last = start;
+  while (last - start < 10) {
+    const t = performance.now();
+    assert(t >= last);
+    assert(t % 2 === 0);
+    last = t;
+  }
+});
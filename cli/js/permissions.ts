@@ -10,6 +10,7 @@ export interface Permissions {
   env: boolean;
   run: boolean;
   hrtime: boolean;
+  plugin: boolean;
   // NOTE: Keep in sync with src/permissions.rs
 }
 
@@ -26,6 +27,41 @@ export function permissions(): Permissions {
   return sendSync(dispatch.OP_PERMISSIONS) as Permissions;
 }
 
+/** The state of a permission, or of a permission for a single target. */
+export interface PermissionDescriptor {
+  permission: Permission;
+  /** The path, host, variable or program the state is for, if not for the
+   * whole permission. */
+  target?: string;
+  state: "granted" | "prompt" | "denied";
+}
+
+/** List the effective permissions of the current program: one descriptor for
+ * each permission, followed by one for each target it is granted or denied
+ * for on its own, like the paths of `--allow-read=/data`.
+ *
+ *       for (const { permission, target, state } of
+ *         Deno.permissionDescriptors()) {
+ *         console.log(permission, target || "*", state);
+ *       }
+ */
+export function permissionDescriptors(): PermissionDescriptor[] {
+  return sendSync(dispatch.OP_PERMISSION_DESCRIPTORS);
+}
+
+/** Ask the user for a permission at the point it is needed, instead of
+ * requiring it upfront. Returns whether it is granted, which it stays until
+ * revoked. Nobody is asked if it was already granted or denied for good, or
+ * if there is no terminal to ask on.
+ *
+ *       if (Deno.requestPermission("write")) {
+ *         await Deno.writeFile("report.txt", report);
+ *       }
+ */
+export function requestPermission(permission: Permission): boolean {
+  return sendSync(dispatch.OP_REQUEST_PERMISSION, { permission });
+}
+
 /** Revoke a permission. When the permission was already revoked nothing changes
  *
  *       if (Deno.permissions().read) {
@@ -1,5 +1,5 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
-import { testPerm, assert, assertEquals } from "./test_util.ts";
+import { test, testPerm, assert, assertEquals } from "./test_util.ts";
 
 const knownPermissions: Deno.Permission[] = [
   "run",
@@ -7,7 +7,8 @@ const knownPermissions: Deno.Permission[] = [
   "write",
   "net",
   "env",
-  "hrtime"
+  "hrtime",
+  "plugin"
 ];
 
 for (const grant of knownPermissions) {
@@ -26,3 +27,34 @@ for (const grant of knownPermissions) {
     }
   });
 }
+
+testPerm({ read: true, net: true }, function permissionDescriptors(): void {
+  const states = Deno.permissionDescriptors().map(
+    ({ permission, state }): string => `${permission}:${state}`
+  );
+  assertEquals(states, [
+    "read:granted",
+    "write:prompt",
+    "net:granted",
+    "env:prompt",
+    "run:prompt",
+    "hrtime:prompt",
+    "plugin:prompt"
+  ]);
+
+  Deno.revokePermission("net");
+  const net = Deno.permissionDescriptors().find(
+    ({ permission }): boolean => permission === "net"
+  );
+  assertEquals(net, { permission: "net", state: "prompt" });
+});
+
+testPerm({ read: true }, function requestPermissionGranted(): void {
+  assertEquals(Deno.requestPermission("read"), true);
+});
+
+test(function requestPermissionNoPrompt(): void {
+  // The tests run with --no-prompt.
+  assertEquals(Deno.requestPermission("write"), false);
+  assertEquals(Deno.permissions().write, false);
+});
@@ -0,0 +1,62 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { sendSync } from "./dispatch_json.ts";
+import * as dispatch from "./dispatch.ts";
+import { core } from "./core.ts";
+
+export interface PluginOp {
+  /** Calls the op with `control` and an optional zero copy buffer. Returns
+   * the response of a sync op, or null for an async one, whose response is
+   * given to the handler set with `setAsyncHandler()`. */
+  dispatch(
+    control: Uint8Array,
+    zeroCopy?: ArrayBufferView | null
+  ): Uint8Array | null;
+  /** Sets the function called with each response of the op when async. */
+  setAsyncHandler(handler: (msg: Uint8Array) => void): void;
+}
+
+class PluginOpImpl implements PluginOp {
+  constructor(private readonly opId: number) {}
+
+  dispatch(
+    control: Uint8Array,
+    zeroCopy?: ArrayBufferView | null
+  ): Uint8Array | null {
+    return core.dispatch(this.opId, control, zeroCopy);
+  }
+
+  setAsyncHandler(handler: (msg: Uint8Array) => void): void {
+    dispatch.setAsyncHandler(this.opId, handler);
+  }
+}
+
+export interface Plugin {
+  /** The ops the plugin registered, by name. */
+  ops: {
+    [name: string]: PluginOp;
+  };
+}
+
+interface OpenPluginResponse {
+  ops: {
+    [name: string]: number;
+  };
+}
+
+/** Loads the native plugin `filename`, a dynamic library exporting the init
+ * function of `deno::init_fn!`, and returns the ops it registered. Requires
+ * the `allow-plugin` permission.
+ *
+ *       const plugin = Deno.openPlugin("./libhello.so");
+ *       const response = plugin.ops.hello.dispatch(new Uint8Array([42]));
+ */
+export function openPlugin(filename: string): Plugin {
+  const res: OpenPluginResponse = sendSync(dispatch.OP_OPEN_PLUGIN, {
+    filename
+  });
+  const ops: { [name: string]: PluginOp } = {};
+  for (const [name, opId] of Object.entries(res.ops)) {
+    ops[name] = new PluginOpImpl(opId);
+  }
+  return { ops };
+}
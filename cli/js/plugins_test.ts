@@ -0,0 +1,25 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { testPerm, assert, assertEquals } from "./test_util.ts";
+
+testPerm({ plugin: false }, function openPluginPerm(): void {
+  let err;
+  try {
+    Deno.openPlugin("libnot_a_plugin.so");
+  } catch (e) {
+    err = e;
+  }
+  assert(err instanceof Deno.DenoError);
+  assertEquals(err.kind, Deno.ErrorKind.PermissionDenied);
+  assertEquals(err.name, "PermissionDenied");
+});
+
+testPerm({ read: true, plugin: true }, function openPluginNotFound(): void {
+  let err;
+  try {
+    Deno.openPlugin("libnot_a_plugin.so");
+  } catch (e) {
+    err = e;
+  }
+  assert(err instanceof Deno.DenoError);
+  assertEquals(err.kind, Deno.ErrorKind.Other);
+});
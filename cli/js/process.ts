@@ -6,6 +6,7 @@ import { ReadCloser, WriteCloser } from "./io.ts";
 import { readAll } from "./buffer.ts";
 import { assert, unreachable } from "./util.ts";
 import { build } from "./build.ts";
+import { ConsoleSize } from "./tty.ts";
 
 /** How to handle subprocess stdio.
  *
@@ -20,52 +21,157 @@ import { build } from "./build.ts";
  */
 export type ProcessStdio = "inherit" | "piped" | "null";
 
+/** Resource limits for a subprocess. Each of them is applied as both the soft
+ * and the hard limit, so the subprocess can't raise it again.
+ */
+export interface RunLimits {
+  /** CPU time in seconds, after which the subprocess is killed. */
+  cpu?: number;
+  /** Size of the virtual memory of the subprocess in bytes, beyond which its
+   * allocations fail.
+   */
+  addressSpace?: number;
+  /** Number of files the subprocess can have open at once. */
+  openFiles?: number;
+}
+
 // TODO Maybe extend VSCode's 'CommandOptions'?
 // See https://code.visualstudio.com/docs/editor/tasks-appendix#_schema-for-tasksjson
 export interface RunOptions {
   args: string[];
   cwd?: string;
   env?: { [key: string]: string };
+  /** Start the subprocess with an empty environment instead of a copy of
+   * ours, so that it only sees `env` and the variables named in `inheritEnv`.
+   * Defaults to `false`.
+   */
+  clearEnv?: boolean;
+  /** Names of the variables of our environment to pass on to the subprocess.
+   * Implies `clearEnv`. Variables we don't have are skipped.
+   */
+  inheritEnv?: string[];
   stdout?: ProcessStdio | number;
   stderr?: ProcessStdio | number;
   stdin?: ProcessStdio | number;
+  /** Start the subprocess in a session (Unix) or process group and without a
+   * console (Windows) of its own, so that it keeps running when the terminal
+   * goes away or Ctrl+C is pressed. Closing the process leaves it running
+   * instead of killing it. Defaults to `false`.
+   */
+  detached?: boolean;
+  /** Start the subprocess as the leader of a new process group, so that it
+   * and everything it spawns can be signaled at once with
+   * `Process.killGroup()`. Implied by `detached`. Defaults to `false`.
+   */
+  processGroup?: boolean;
+  /** Resource limits to apply to the subprocess before it starts. Not
+   * supported on Windows.
+   */
+  limits?: RunLimits;
+  /** Run the subprocess as this user. Requires the privileges to switch to
+   * it, e.g. being root. Not supported on Windows.
+   */
+  uid?: number;
+  /** Run the subprocess as this group. Not supported on Windows. */
+  gid?: number;
+  /** Kill the subprocess, and on Unix the process group it leads, if it is
+   * still running after this many milliseconds. Its status then has
+   * `timedOut` set. The timer stops once the status has been awaited or the
+   * process is closed.
+   */
+  timeout?: number;
+  /** Attach the subprocess to a new pseudo-terminal of the given size, or of
+   * 80 columns by 24 rows if `true`, instead of `stdin`, `stdout` and
+   * `stderr`. The subprocess then behaves as it would in a terminal, e.g.
+   * colors its output, and is driven through `Process.pty`. Not supported on
+   * Windows.
+   */
+  pty?: boolean | ConsoleSize;
 }
 
 interface RunStatusResponse {
   gotSignal: boolean;
   exitCode: number;
   exitSignal: number;
+  timedOut: boolean;
 }
 
 async function runStatus(rid: number): Promise<ProcessStatus> {
   const res = (await sendAsync(dispatch.OP_RUN_STATUS, {
     rid
   })) as RunStatusResponse;
+  return toProcessStatus(res);
+}
 
+function toProcessStatus(res: RunStatusResponse): ProcessStatus {
+  let status: ProcessStatus;
   if (res.gotSignal) {
     const signal = res.exitSignal;
-    return { signal, success: false };
+    status = { signal, success: false };
   } else {
     const code = res.exitCode;
-    return { code, success: code === 0 };
+    status = { code, success: code === 0 };
+  }
+  if (res.timedOut) {
+    status.success = false;
+    status.timedOut = true;
   }
+  return status;
 }
 
-/** Send a signal to process under given PID. Unix only at this moment.
- * If pid is negative, the signal will be sent to the process group identified
- * by -pid.
+/** Send a signal, given by number or by name such as `"SIGTERM"`, to process
+ * under given PID. If pid is negative, the signal will be sent to the process
+ * group identified by -pid. Signals this platform lacks throw `InvalidInput`.
+ *
+ * Windows has no signals: `SIGKILL` and `SIGTERM` terminate the process, while
+ * `SIGINT` and `SIGBREAK` send Ctrl+Break to a process started with
+ * `processGroup`.
  * Requires the `--allow-run` flag.
  */
-export function kill(pid: number, signo: number): void {
+export function kill(pid: number, signo: number | string): void {
   sendSync(dispatch.OP_KILL, { pid, signo });
 }
 
+/** Returns the scheduling priority of the process under given PID, or of
+ * the current process if `pid` is `0`, as a nice value from `-20` (highest)
+ * to `19` (lowest). On Windows, priority classes are mapped to nice values.
+ * Requires the `--allow-run` flag.
+ */
+export function getPriority(pid = 0): number {
+  return sendSync(dispatch.OP_GET_PRIORITY, { pid });
+}
+
+/** Sets the scheduling priority of the process under given PID, or of the
+ * current process if `pid` is `0`, as a nice value from `-20` (highest) to
+ * `19` (lowest). Raising the priority usually needs elevated privileges. On
+ * Windows, the nice value is mapped to the nearest priority class.
+ * Requires the `--allow-run` flag.
+ *
+ *       const indexer = Deno.run({ args: ["./indexer"] });
+ *       Deno.setPriority(indexer.pid, 19);
+ */
+export function setPriority(pid: number, priority: number): void {
+  sendSync(dispatch.OP_SET_PRIORITY, { pid, priority });
+}
+
+/** The master side of the pseudo-terminal of a subprocess started with
+ * `RunOptions.pty`. Reading it gives what the subprocess prints, writing to it
+ * types into the subprocess.
+ */
+export class Pty extends File {
+  /** Change the size of the terminal, sending the subprocess `SIGWINCH`. */
+  resize(columns: number, rows: number): void {
+    sendSync(dispatch.OP_PTY_RESIZE, { rid: this.rid, columns, rows });
+  }
+}
+
 export class Process {
   readonly rid: number;
   readonly pid: number;
   readonly stdin?: WriteCloser;
   readonly stdout?: ReadCloser;
   readonly stderr?: ReadCloser;
+  readonly pty?: Pty;
 
   // @internal
   constructor(res: RunResponse) {
@@ -83,12 +189,39 @@ export class Process {
     if (res.stderrRid && res.stderrRid > 0) {
       this.stderr = new File(res.stderrRid);
     }
+
+    if (res.ptyRid) {
+      this.pty = new Pty(res.ptyRid);
+    }
   }
 
   async status(): Promise<ProcessStatus> {
     return await runStatus(this.rid);
   }
 
+  /** Wait for the process to exit while reading its piped stdout and stderr
+   * to the end, all at once so that it can't get stuck writing to a full
+   * pipe. At most `maxBuffer` bytes of each are kept, and `truncated` is set
+   * if there were more. This closes stdout and stderr.
+   *
+   *       const p = Deno.run({ args, stdout: "piped", stderr: "piped" });
+   *       const { status, stdout, stderr } = await p.combinedOutput();
+   */
+  async combinedOutput(maxBuffer = 1024 * 1024): Promise<ProcessOutput> {
+    const res = (await sendAsync(dispatch.OP_RUN_OUTPUT, {
+      rid: this.rid,
+      stdoutRid: this.stdout && (this.stdout as File).rid,
+      stderrRid: this.stderr && (this.stderr as File).rid,
+      maxBuffer
+    })) as RunOutputResponse;
+    return {
+      status: toProcessStatus(res.status),
+      stdout: new Uint8Array(res.stdout),
+      stderr: new Uint8Array(res.stderr),
+      truncated: res.truncated
+    };
+  }
+
   /** Buffer the stdout and return it as Uint8Array after EOF.
    * You must set stdout to "piped" when creating the process.
    * This calls close() on stdout after its done.
@@ -123,15 +256,44 @@ export class Process {
     close(this.rid);
   }
 
-  kill(signo: number): void {
+  kill(signo: number | string): void {
     kill(this.pid, signo);
   }
+
+  /** Send a signal to the process group the subprocess leads, reaching e.g.
+   * every command of a shell pipeline. The subprocess must have been started
+   * with `processGroup` or `detached`. On Windows it can only be sent `SIGINT`
+   * or `SIGBREAK`, which both become Ctrl+Break.
+   */
+  killGroup(signo: number | string): void {
+    sendSync(dispatch.OP_KILL_GROUP, { rid: this.rid, signo });
+  }
 }
 
 export interface ProcessStatus {
   success: boolean;
   code?: number;
   signal?: number; // TODO: Make this a string, e.g. 'SIGTERM'.
+  /** Set if the subprocess was killed for running past `RunOptions.timeout`.
+   */
+  timedOut?: boolean;
+}
+
+export interface ProcessOutput {
+  status: ProcessStatus;
+  /** Empty unless stdout was piped. */
+  stdout: Uint8Array;
+  /** Empty unless stderr was piped. */
+  stderr: Uint8Array;
+  /** Set if stdout or stderr was longer than the buffer allowed for it. */
+  truncated: boolean;
+}
+
+interface RunOutputResponse {
+  status: RunStatusResponse;
+  stdout: number[];
+  stderr: number[];
+  truncated: boolean;
 }
 
 // TODO: this method is only used to validate proper option, probably can be renamed
@@ -156,6 +318,7 @@ interface RunResponse {
   stdinRid: number | null;
   stdoutRid: number | null;
   stderrRid: number | null;
+  ptyRid: number | null;
 }
 /**
  * Spawns new subprocess.
@@ -168,7 +331,19 @@ interface RunResponse {
  *
  * By default subprocess inherits stdio of parent process. To change that
  * `opt.stdout`, `opt.stderr` and `opt.stdin` can be specified independently -
- * they can be set to either `ProcessStdio` or `rid` of open file.
+ * they can be set to either `ProcessStdio` or the `rid` of an open file, of
+ * `Deno.stdout` or `Deno.stderr`, of a TCP connection (Unix only) or of another
+ * subprocess's piped stdio. The latter two are handed over to the subprocess
+ * and closed, e.g. to build a pipeline that never copies through JS:
+ *
+ *       const ls = Deno.run({ args: ["ls"], stdout: "piped" });
+ *       const wc = Deno.run({ args: ["wc", "-l"], stdin: ls.stdout!.rid });
+ *
+ * A subprocess started with `opt.detached` outlives its `Process` being
+ * closed, e.g. to launch a daemon:
+ *
+ *       const p = Deno.run({ args: ["./server"], detached: true });
+ *       p.close();
  */
 export function run(opt: RunOptions): Process {
   assert(opt.args.length > 0);
@@ -212,18 +387,65 @@ export function run(opt: RunOptions): Process {
     args: opt.args.map(String),
     cwd: opt.cwd,
     env,
+    clearEnv: opt.clearEnv || false,
+    inheritEnv: opt.inheritEnv,
     stdin,
     stdout,
     stderr,
     stdinRid,
     stdoutRid,
-    stderrRid
+    stderrRid,
+    detached: opt.detached || false,
+    processGroup: opt.processGroup || false,
+    limits: opt.limits,
+    uid: opt.uid,
+    gid: opt.gid,
+    timeout: opt.timeout,
+    pty: opt.pty === true ? { columns: 80, rows: 24 } : opt.pty || undefined
   };
 
   const res = sendSync(dispatch.OP_RUN, req) as RunResponse;
   return new Process(res);
 }
 
+export interface ExecOptions {
+  cwd?: string;
+  env?: { [key: string]: string };
+}
+
+/** Replaces the current process with the program `args[0]`, which is looked
+ * up in `PATH`, called with the remaining `args`. It keeps the pid and stdio
+ * of the current process, and `opt.env` is added to its environment. Never
+ * returns unless the program can't be started. Requires `allow-run`.
+ *
+ * Windows can't replace a process, so there the program is run as a
+ * subprocess instead and the current process exits with its exit code once
+ * it is done.
+ *
+ *       Deno.exec(["node", "server.js"], { env: { PORT: "8080" } });
+ */
+export function exec(args: string[], opt: ExecOptions = {}): never {
+  assert(args.length > 0);
+  let env: Array<[string, string]> = [];
+  if (opt.env) {
+    env = Array.from(Object.entries(opt.env));
+  }
+  sendSync(dispatch.OP_EXEC, { args: args.map(String), cwd: opt.cwd, env });
+  return unreachable();
+}
+
+/** Switches the current process to another user and/or group, e.g. to give
+ * up root once a server has bound a privileged port. The group is switched
+ * first. Switching as root also drops root's supplementary groups. Requires
+ * `allow-run`. Not supported on Windows.
+ *
+ *       const listener = Deno.listen({ port: 80 });
+ *       Deno.setIds({ uid: 1000, gid: 1000 });
+ */
+export function setIds(ids: { uid?: number; gid?: number }): void {
+  sendSync(dispatch.OP_SET_IDS, ids);
+}
+
 // From `kill -l`
 enum LinuxSignal {
   SIGHUP = 1,
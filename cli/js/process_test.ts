@@ -82,6 +82,30 @@ testPerm({ run: true }, function runNotFound(): void {
   assertEquals(error.kind, ErrorKind.NotFound);
 });
 
+test(function execPermissions(): void {
+  let caughtError = false;
+  try {
+    Deno.exec(["python", "-c", "print('hello world')"]);
+  } catch (e) {
+    caughtError = true;
+    assertEquals(e.kind, Deno.ErrorKind.PermissionDenied);
+    assertEquals(e.name, "PermissionDenied");
+  }
+  assert(caughtError);
+});
+
+testPerm({ run: true }, function execNotFound(): void {
+  let error;
+  try {
+    Deno.exec(["this file hopefully doesn't exist"]);
+  } catch (e) {
+    error = e;
+  }
+  assert(error !== undefined);
+  assert(error instanceof DenoError);
+  assertEquals(error.kind, ErrorKind.NotFound);
+});
+
 testPerm(
   { write: true, run: true },
   async function runWithCwdIsAsync(): Promise<void> {
@@ -146,6 +170,170 @@ testPerm({ run: true }, async function runStdinPiped(): Promise<void> {
   p.close();
 });
 
+testPerm({ run: true }, async function runLimits(): Promise<void> {
+  const args = [
+    "python",
+    "-c",
+    "import resource; print(resource.getrlimit(resource.RLIMIT_NOFILE))"
+  ];
+  if (Deno.build.os === "win") {
+    let err;
+    try {
+      run({ args, limits: { openFiles: 64 } });
+    } catch (e) {
+      err = e;
+    }
+    assert(!!err);
+    assertEquals(err.kind, Deno.ErrorKind.OpNotAvailable);
+    return;
+  }
+  const p = run({ args, stdout: "piped", limits: { openFiles: 64 } });
+  const output = await p.output();
+  assertEquals(new TextDecoder().decode(output), "(64, 64)\n");
+  p.close();
+});
+
+testPerm({ run: true }, async function runUidGid(): Promise<void> {
+  const args = ["python", "-c", "import os; print(os.getuid(), os.getgid())"];
+  if (Deno.build.os === "win") {
+    let err;
+    try {
+      run({ args, uid: 0 });
+    } catch (e) {
+      err = e;
+    }
+    assert(!!err);
+    assertEquals(err.kind, Deno.ErrorKind.OpNotAvailable);
+    return;
+  }
+  const decoder = new TextDecoder();
+  const p1 = run({ args, stdout: "piped" });
+  const ids = decoder.decode(await p1.output());
+  p1.close();
+  const [uid, gid] = ids.trim().split(" ").map(Number);
+  // Switching to the ids we already have is always allowed.
+  const p2 = run({ args, stdout: "piped", uid, gid });
+  assertEquals(decoder.decode(await p2.output()), ids);
+  p2.close();
+});
+
+test(function setIdsPermissions(): void {
+  let caughtError = false;
+  try {
+    Deno.setIds({ uid: 0 });
+  } catch (e) {
+    caughtError = true;
+    assertEquals(e.kind, Deno.ErrorKind.PermissionDenied);
+    assertEquals(e.name, "PermissionDenied");
+  }
+  assert(caughtError);
+});
+
+test(function getPriorityPermissions(): void {
+  let caughtError = false;
+  try {
+    Deno.getPriority();
+  } catch (e) {
+    caughtError = true;
+    assertEquals(e.kind, Deno.ErrorKind.PermissionDenied);
+    assertEquals(e.name, "PermissionDenied");
+  }
+  assert(caughtError);
+});
+
+testPerm({ run: true }, async function setPriorityChild(): Promise<void> {
+  const p = run({ args: ["python", "-c", "import time; time.sleep(5)"] });
+  try {
+    // Lowering the priority never needs privileges.
+    Deno.setPriority(p.pid, 19);
+    assertEquals(Deno.getPriority(p.pid), 19);
+  } finally {
+    p.kill(Deno.Signal.SIGKILL);
+    await p.status();
+    p.close();
+  }
+});
+
+testPerm({ run: true }, function setPriorityInvalid(): void {
+  let err;
+  try {
+    Deno.setPriority(0, 20);
+  } catch (e) {
+    err = e;
+  }
+  assert(!!err);
+  assertEquals(err.kind, Deno.ErrorKind.InvalidInput);
+});
+
+testPerm(
+  { run: true },
+  async function runQuietChildKeepsLoopResponsive(): Promise<void> {
+    const p = run({
+      args: ["python", "-c", "import time; time.sleep(1); print('done')"],
+      stdout: "piped"
+    });
+    let ticks = 0;
+    const timer = setInterval((): void => {
+      ticks++;
+    }, 10);
+    const output = await p.output();
+    clearInterval(timer);
+    p.close();
+    assertEquals(new TextDecoder().decode(output).trim(), "done");
+    // Timers kept firing while the read was pending.
+    assert(ticks > 10);
+  }
+);
+
+testPerm({ run: true }, async function runTimeout(): Promise<void> {
+  const p = run({
+    args: ["python", "-c", "import time; time.sleep(10)"],
+    timeout: 100
+  });
+  const status = await p.status();
+  assertEquals(status.success, false);
+  assertEquals(status.timedOut, true);
+  p.close();
+});
+
+testPerm({ run: true }, async function runTimeoutNotReached(): Promise<void> {
+  const p = run({ args: ["python", "-c", "pass"], timeout: 10000 });
+  const status = await p.status();
+  assertEquals(status.success, true);
+  assertEquals(status.timedOut, undefined);
+  p.close();
+});
+
+testPerm({ run: true }, async function runPty(): Promise<void> {
+  const args = [
+    "python",
+    "-c",
+    "import os, sys; print(os.isatty(1), os.get_terminal_size(1))"
+  ];
+  if (Deno.build.os === "win") {
+    let err;
+    try {
+      run({ args, pty: true });
+    } catch (e) {
+      err = e;
+    }
+    assert(!!err);
+    assertEquals(err.kind, Deno.ErrorKind.OpNotAvailable);
+    return;
+  }
+  const p = run({ args, pty: { columns: 100, rows: 30 } });
+  assert(!p.stdin);
+  assert(!p.stdout);
+  assert(!p.stderr);
+  p.pty.resize(120, 40);
+  const output = new TextDecoder().decode(await Deno.readAll(p.pty));
+  assertStrContains(output, "True os.terminal_size(columns=120, lines=40)");
+  const status = await p.status();
+  assertEquals(status.success, true);
+  p.pty.close();
+  p.close();
+});
+
 testPerm({ run: true }, async function runStdoutPiped(): Promise<void> {
   const p = run({
     args: ["python", "-c", "import sys; sys.stdout.write('hello')"],
@@ -273,6 +461,44 @@ testPerm(
   }
 );
 
+testPerm({ run: true }, async function runCombinedOutput(): Promise<void> {
+  // Both outputs are bigger than a pipe buffer, so that the child would get
+  // stuck on one of them if they weren't read at once.
+  const p = run({
+    args: [
+      "python",
+      "-c",
+      "import sys; sys.stdout.write('o' * 200000); sys.stderr.write('e' * 300000); sys.exit(3)"
+    ],
+    stdout: "piped",
+    stderr: "piped"
+  });
+  const output = await p.combinedOutput();
+  assertEquals(output.status.success, false);
+  assertEquals(output.status.code, 3);
+  assertEquals(output.stdout.length, 200000);
+  assertEquals(output.stderr.length, 300000);
+  assertEquals(output.stdout[0], "o".charCodeAt(0));
+  assertEquals(output.stderr[0], "e".charCodeAt(0));
+  assertEquals(output.truncated, false);
+  p.close();
+});
+
+testPerm({ run: true }, async function runCombinedOutputTruncated(): Promise<
+  void
+> {
+  const p = run({
+    args: ["python", "-c", "import sys; sys.stdout.write('hello world')"],
+    stdout: "piped"
+  });
+  const output = await p.combinedOutput(5);
+  assertEquals(output.status.success, true);
+  assertEquals(new TextDecoder().decode(output.stdout), "hello");
+  assertEquals(output.stderr.length, 0);
+  assertEquals(output.truncated, true);
+  p.close();
+});
+
 testPerm({ run: true }, async function runEnv(): Promise<void> {
   const p = run({
     args: [
@@ -292,6 +518,24 @@ testPerm({ run: true }, async function runEnv(): Promise<void> {
   p.close();
 });
 
+testPerm({ run: true }, async function runClearEnv(): Promise<void> {
+  const p = run({
+    args: [
+      "python",
+      "-c",
+      "import os, sys; sys.stdout.write(repr([os.environ.get(k) for k in ['FOO', 'USER', 'HOME']]))"
+    ],
+    env: { FOO: "0123" },
+    // Python needs SYSTEMROOT to start on Windows.
+    inheritEnv: ["PATH", "SYSTEMROOT"],
+    stdout: "piped"
+  });
+  const output = await p.output();
+  const s = new TextDecoder().decode(output);
+  assertEquals(s, "['0123', None, None]");
+  p.close();
+});
+
 testPerm({ run: true }, async function runClose(): Promise<void> {
   const p = run({
     args: [
@@ -311,6 +555,34 @@ testPerm({ run: true }, async function runClose(): Promise<void> {
   assertEquals(r, Deno.EOF);
 });
 
+testPerm({ run: true }, async function runStdinFromChildStdout(): Promise<
+  void
+> {
+  const producer = run({
+    args: ["python", "-c", "import sys; sys.stdout.write('hello')"],
+    stdout: "piped"
+  });
+  const stdoutRid = producer.stdout!.rid;
+  const consumer = run({
+    args: [
+      "python",
+      "-c",
+      "import sys; sys.stdout.write(sys.stdin.read().upper())"
+    ],
+    stdin: stdoutRid,
+    stdout: "piped"
+  });
+  // The pipe was handed over to the consumer.
+  assert(!(stdoutRid in Deno.resources()));
+
+  const output = await consumer.output();
+  assertEquals(new TextDecoder().decode(output), "HELLO");
+  assertEquals((await producer.status()).success, true);
+  assertEquals((await consumer.status()).success, true);
+  producer.close();
+  consumer.close();
+});
+
 test(function signalNumbers(): void {
   if (Deno.build.os === "mac") {
     assertEquals(Deno.Signal.SIGSTOP, 17);
@@ -319,6 +591,18 @@ test(function signalNumbers(): void {
   }
 });
 
+testPerm({ run: true }, function killInvalidSignalName(): void {
+  let err;
+  try {
+    kill(Deno.pid, "SIGNOTASIGNAL");
+  } catch (e) {
+    err = e;
+  }
+
+  assert(!!err);
+  assertEquals(err.kind, Deno.ErrorKind.InvalidInput);
+});
+
 // Ignore signal tests on windows for now...
 if (Deno.build.os !== "win") {
   test(function killPermissions(): void {
@@ -374,4 +658,65 @@ if (Deno.build.os !== "win") {
 
     p.close();
   });
+
+  testPerm({ run: true }, async function runDetachedOwnSession(): Promise<
+    void
+  > {
+    const p = run({
+      args: [
+        "python",
+        "-c",
+        "import os, sys; sys.stdout.write(str(os.getsid(0) == os.getpid()))"
+      ],
+      stdout: "piped",
+      detached: true
+    });
+    const output = await p.output();
+    assertEquals(new TextDecoder().decode(output), "True");
+    const status = await p.status();
+    assertEquals(status.success, true);
+    p.close();
+  });
+
+  testPerm({ run: true }, async function killGroupPipeline(): Promise<void> {
+    // The shell and the sleep it spawns share the new process group.
+    const p = run({
+      args: ["sh", "-c", "sleep 10000 | sleep 10000"],
+      processGroup: true
+    });
+    p.killGroup(Deno.Signal.SIGKILL);
+    const status = await p.status();
+    assertEquals(status.success, false);
+    assertEquals(status.signal, Deno.Signal.SIGKILL);
+    p.close();
+  });
+
+  testPerm({ run: true }, function killGroupWithoutGroup(): void {
+    const p = run({
+      args: ["python", "-c", "from time import sleep; sleep(10000)"]
+    });
+
+    let err;
+    try {
+      p.killGroup(Deno.Signal.SIGKILL);
+    } catch (e) {
+      err = e;
+    }
+
+    assert(!!err);
+    assertEquals(err.kind, Deno.ErrorKind.InvalidInput);
+    p.close();
+  });
+
+  testPerm({ run: true }, async function killSignalName(): Promise<void> {
+    const p = run({
+      args: ["python", "-c", "from time import sleep; sleep(10000)"]
+    });
+
+    p.kill("SIGKILL");
+    const status = await p.status();
+    assertEquals(status.success, false);
+    assertEquals(status.signal, Deno.Signal.SIGKILL);
+    p.close();
+  });
 }
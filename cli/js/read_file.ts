@@ -1,6 +1,11 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
-import { open, openSync } from "./files.ts";
+import { sendSync, sendAsync } from "./dispatch_json.ts";
+import * as dispatch from "./dispatch.ts";
+import { close, open, openSync, read, readSync } from "./files.ts";
 import { readAll, readAllSync } from "./buffer.ts";
+import { EOF } from "./io.ts";
+
+const DEFAULT_CHUNK_SIZE = 64 * 1024;
 
 /** Read the entire contents of a file synchronously.
  *
@@ -27,3 +32,77 @@ export async function readFile(filename: string): Promise<Uint8Array> {
   file.close();
   return contents;
 }
+
+export interface ReadFileChunksOptions {
+  /** Position in the file to start reading at. Defaults to `0`. */
+  offset?: number;
+  /** Number of bytes to read. Defaults to everything up to the end of the
+   * file.
+   */
+  length?: number;
+  /** Maximum size of each chunk. Defaults to 64 KiB. */
+  chunkSize?: number;
+}
+
+/** Read the contents of a file synchronously, one chunk at a time, so that
+ * the whole file never has to be held in memory.
+ *
+ *       for (const chunk of Deno.readFileChunksSync("big.bin")) {
+ *         hash.update(chunk);
+ *       }
+ */
+export function* readFileChunksSync(
+  filename: string,
+  options: ReadFileChunksOptions = {}
+): IterableIterator<Uint8Array> {
+  const { offset = 0, length, chunkSize = DEFAULT_CHUNK_SIZE } = options;
+  const rid = sendSync(dispatch.OP_READ_FILE_STREAM, {
+    filename,
+    offset,
+    length
+  });
+  try {
+    while (true) {
+      const p = new Uint8Array(chunkSize);
+      const nread = readSync(rid, p);
+      if (nread === EOF) {
+        break;
+      }
+      yield p.subarray(0, nread);
+    }
+  } finally {
+    close(rid);
+  }
+}
+
+/** Read the contents of a file one chunk at a time, so that the whole file
+ * never has to be held in memory. Use `offset` and `length` to read only a
+ * part of the file.
+ *
+ *       for await (const chunk of Deno.readFileChunks("big.bin")) {
+ *         hash.update(chunk);
+ *       }
+ */
+export async function* readFileChunks(
+  filename: string,
+  options: ReadFileChunksOptions = {}
+): AsyncIterableIterator<Uint8Array> {
+  const { offset = 0, length, chunkSize = DEFAULT_CHUNK_SIZE } = options;
+  const rid = await sendAsync(dispatch.OP_READ_FILE_STREAM, {
+    filename,
+    offset,
+    length
+  });
+  try {
+    while (true) {
+      const p = new Uint8Array(chunkSize);
+      const nread = await read(rid, p);
+      if (nread === EOF) {
+        break;
+      }
+      yield p.subarray(0, nread);
+    }
+  } finally {
+    close(rid);
+  }
+}
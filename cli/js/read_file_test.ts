@@ -55,3 +55,74 @@ testPerm({ read: false }, async function readFilePerm(): Promise<void> {
   }
   assert(caughtError);
 });
+
+function concat(chunks: Uint8Array[]): Uint8Array {
+  const len = chunks.reduce((n, c): number => n + c.byteLength, 0);
+  const result = new Uint8Array(len);
+  let offset = 0;
+  for (const chunk of chunks) {
+    result.set(chunk, offset);
+    offset += chunk.byteLength;
+  }
+  return result;
+}
+
+testPerm({ read: true }, function readFileChunksSyncSuccess(): void {
+  const data = Deno.readFileSync("package.json");
+  const chunks = [];
+  for (const chunk of Deno.readFileChunksSync("package.json", {
+    chunkSize: 100
+  })) {
+    assert(chunk.byteLength <= 100);
+    chunks.push(chunk);
+  }
+  assertEquals(chunks.length, Math.ceil(data.byteLength / 100));
+  assertEquals(concat(chunks), data);
+});
+
+testPerm({ read: true }, async function readFileChunksRange(): Promise<void> {
+  const data = Deno.readFileSync("package.json");
+  const chunks = [];
+  for await (const chunk of Deno.readFileChunks("package.json", {
+    offset: 10,
+    length: 25,
+    chunkSize: 10
+  })) {
+    chunks.push(chunk);
+  }
+  assertEquals(chunks.map((c): number => c.byteLength), [10, 10, 5]);
+  assertEquals(concat(chunks), data.subarray(10, 35));
+});
+
+testPerm({ read: true }, async function readFileChunksPastEnd(): Promise<
+  void
+> {
+  const data = Deno.readFileSync("package.json");
+  const chunks = [];
+  for await (const chunk of Deno.readFileChunks("package.json", {
+    offset: data.byteLength - 4,
+    length: 100
+  })) {
+    chunks.push(chunk);
+  }
+  assertEquals(concat(chunks), data.subarray(data.byteLength - 4));
+});
+
+testPerm({ read: false }, async function readFileChunksPerm(): Promise<void> {
+  let caughtError = false;
+  try {
+    await Deno.readFileChunks("package.json").next();
+  } catch (e) {
+    caughtError = true;
+    assertEquals(e.kind, Deno.ErrorKind.PermissionDenied);
+  }
+  assert(caughtError);
+});
+
+testPerm({ read: true }, function readFileChunksSyncReleasesRid(): void {
+  const before = Object.keys(Deno.resources()).length;
+  for (const _ of Deno.readFileChunksSync("package.json", { chunkSize: 1 })) {
+    break;
+  }
+  assertEquals(Object.keys(Deno.resources()).length, before);
+});
@@ -333,3 +333,52 @@ testPerm({ write: false }, async function removeAllPerm(): Promise<void> {
   assertEquals(err.kind, Deno.ErrorKind.PermissionDenied);
   assertEquals(err.name, "PermissionDenied");
 });
+
+testPerm(
+  { read: true, write: true },
+  function removeSyncRecursiveDoesNotFollowSymlinks(): void {
+    if (Deno.build.os === "win") {
+      return;
+    }
+    const testDir = Deno.makeTempDirSync();
+    const outside = Deno.makeTempDirSync();
+    Deno.writeFileSync(outside + "/keep.txt", new TextEncoder().encode("hi"));
+    Deno.mkdirSync(testDir + "/a/b", true);
+    Deno.writeFileSync(testDir + "/a/b/file.txt", new Uint8Array([1]));
+    Deno.symlinkSync(outside, testDir + "/a/link");
+    Deno.removeSync(testDir, { recursive: true });
+    let err;
+    try {
+      Deno.statSync(testDir);
+    } catch (e) {
+      err = e;
+    }
+    assertEquals(err.kind, Deno.ErrorKind.NotFound);
+    // The symlink target and its contents must be left alone.
+    assert(Deno.statSync(outside + "/keep.txt").isFile());
+  }
+);
+
+testPerm(
+  { read: true, write: true },
+  async function removeSymlinkToDir(): Promise<void> {
+    if (Deno.build.os === "win") {
+      return;
+    }
+    const testDir = Deno.makeTempDirSync();
+    const target = testDir + "/target";
+    const link = testDir + "/link";
+    Deno.mkdirSync(target);
+    Deno.symlinkSync(target, link);
+    // Removing the link without `recursive` removes the link, not the target.
+    await Deno.remove(link);
+    assert(Deno.statSync(target).isDirectory());
+    let err;
+    try {
+      Deno.lstatSync(link);
+    } catch (e) {
+      err = e;
+    }
+    assertEquals(err.kind, Deno.ErrorKind.NotFound);
+  }
+);
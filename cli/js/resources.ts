@@ -6,14 +6,45 @@ export interface ResourceMap {
   [rid: number]: string;
 }
 
+export interface ResourceInfo {
+  /** The string representation of the resource, as in `ResourceMap`. */
+  type: string;
+  /** When the resource was opened, in milliseconds since the epoch. */
+  created: number;
+  /** The op which opened the resource, e.g. "accept". Null for stdio. */
+  op: string | null;
+  bytesRead: number;
+  bytesWritten: number;
+}
+
+export interface VerboseResourceMap {
+  [rid: number]: ResourceInfo;
+}
+
+export interface ResourcesOptions {
+  verbose?: boolean;
+}
+
 /** Returns a map of open _file like_ resource ids along with their string
- * representation.
+ * representation. With `verbose`, each resource is described by when and by
+ * which op it was opened and how many bytes were read from and written to it.
+ *
+ *       const info = Deno.resources({ verbose: true });
  */
-export function resources(): ResourceMap {
-  const res = sendSync(dispatch.OP_RESOURCES) as Array<[number, string]>;
-  const resources: ResourceMap = {};
+export function resources(): ResourceMap;
+export function resources(options: { verbose: true }): VerboseResourceMap;
+export function resources(
+  options?: ResourcesOptions
+): ResourceMap | VerboseResourceMap;
+export function resources(
+  options: ResourcesOptions = {}
+): ResourceMap | VerboseResourceMap {
+  const res = sendSync(dispatch.OP_RESOURCES, {
+    verbose: !!options.verbose
+  }) as Array<[number, string | ResourceInfo]>;
+  const resources: { [rid: number]: string | ResourceInfo } = {};
   for (const resourceTuple of res) {
     resources[resourceTuple[0]] = resourceTuple[1];
   }
-  return resources;
+  return resources as ResourceMap | VerboseResourceMap;
 }
@@ -1,5 +1,5 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
-import { test, testPerm, assertEquals } from "./test_util.ts";
+import { test, testPerm, assert, assertEquals } from "./test_util.ts";
 
 test(function resourcesStdio(): void {
   const res = Deno.resources();
@@ -46,3 +46,35 @@ testPerm({ read: true }, async function resourcesFile(): Promise<void> {
   );
   assertEquals(resourcesAfter[newRid], "fsFile");
 });
+
+testPerm({ net: true }, async function resourcesVerbose(): Promise<void> {
+  const start = Date.now();
+  const listener = Deno.listen({ port: 4501 });
+  const dialerConn = await Deno.dial({ port: 4501 });
+  const listenerConn = await listener.accept();
+  await dialerConn.write(new Uint8Array([1, 2, 3]));
+  await listenerConn.read(new Uint8Array(8));
+
+  const res = Deno.resources({ verbose: true });
+  assertEquals(res[0].type, "stdin");
+  assertEquals(res[0].op, null);
+
+  const listenerInfo = res[listener.rid];
+  assertEquals(listenerInfo.type, "tcpListener");
+  assertEquals(listenerInfo.op, "listen");
+  assert(listenerInfo.created >= start - 1);
+
+  const dialerInfo = res[dialerConn.rid];
+  assertEquals(dialerInfo.op, "dial");
+  assertEquals(dialerInfo.bytesWritten, 3);
+  assertEquals(dialerInfo.bytesRead, 0);
+
+  const listenerConnInfo = res[listenerConn.rid];
+  assertEquals(listenerConnInfo.type, "tcpStream");
+  assertEquals(listenerConnInfo.op, "accept");
+  assertEquals(listenerConnInfo.bytesRead, 3);
+
+  listenerConn.close();
+  dialerConn.close();
+  listener.close();
+});
@@ -0,0 +1,46 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import * as dispatch from "./dispatch.ts";
+import { core } from "./core.ts";
+
+// These consts are shared with Rust. Update with care.
+const HEADER_LEN = 2;
+const RECORD_LEN = 4;
+const CAPACITY = 16;
+const RING_OK = 0;
+export const RING_NOW = 1;
+export const RING_SEEK = 2;
+
+// The float64s `[head, tail]` followed by `CAPACITY` records of
+// `[op, x, y, z]`. It's shared with Rust by the first dispatch, rather than
+// when this module is evaluated, so that it isn't part of the snapshot.
+let ring: Float64Array | null = null;
+let record = 0;
+const empty = new Uint8Array(0);
+
+/** Queues the small sync op `op` in the ring and has Rust service it, which
+ * neither encodes its arguments and results nor allocates buffers for them.
+ * Returns whether it succeeded, and `ringResult()` then gives its results. If
+ * it failed, callers make the op again without the ring, which throws the
+ * error.
+ */
+export function sendRing(op: number, x = 0, y = 0, z = 0): boolean {
+  let zeroCopy: Float64Array | null = null;
+  if (ring === null) {
+    ring = new Float64Array(HEADER_LEN + RECORD_LEN * CAPACITY);
+    zeroCopy = ring;
+  }
+  const tail = ring[1];
+  record = HEADER_LEN + tail * RECORD_LEN;
+  ring[record] = op;
+  ring[record + 1] = x;
+  ring[record + 2] = y;
+  ring[record + 3] = z;
+  ring[1] = (tail + 1) % CAPACITY;
+  core.dispatch(dispatch.OP_RING, empty, zeroCopy);
+  return ring[record] === RING_OK;
+}
+
+/** The `i`th result of the op `sendRing()` made last. */
+export function ringResult(i: number): number {
+  return ring![record + 1 + i];
+}
@@ -0,0 +1,43 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { sendSync, sendAsync } from "./dispatch_json.ts";
+import * as dispatch from "./dispatch.ts";
+import { close } from "./files.ts";
+
+class SignalEvents implements AsyncIterableIterator<void> {
+  readonly rid: number;
+
+  constructor(signo: number | string) {
+    this.rid = sendSync(dispatch.OP_SIGNAL_BIND, { signo });
+  }
+
+  async next(): Promise<IteratorResult<void>> {
+    return await sendAsync(dispatch.OP_SIGNAL_POLL, { rid: this.rid });
+  }
+
+  async return(): Promise<IteratorResult<void>> {
+    close(this.rid);
+    return { value: undefined, done: true };
+  }
+
+  [Symbol.asyncIterator](): AsyncIterableIterator<void> {
+    return this;
+  }
+}
+
+/** Yields every time the process receives the signal given by number or by
+ * name such as `"SIGTERM"`, which then no longer has its default effect. That
+ * comes back once the iteration ends and nothing else listens to the signal.
+ *
+ * On Windows, Ctrl+C and Ctrl+Break in the console are received as `SIGINT`
+ * and `SIGBREAK`, the only signals that can be listened to there. Signals
+ * that can't be caught, like `SIGKILL`, and `SIGCHLD` and `SIGPIPE`, which
+ * Deno handles itself, throw `InvalidInput`.
+ *
+ *       for await (const _ of Deno.signal("SIGINT")) {
+ *         await server.close();
+ *         Deno.exit();
+ *       }
+ */
+export function signal(signo: number | string): AsyncIterableIterator<void> {
+  return new SignalEvents(signo);
+}
@@ -0,0 +1,50 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { test, testPerm, assert, assertEquals } from "./test_util.ts";
+
+testPerm({ run: true }, async function signalDelivered(): Promise<void> {
+  if (Deno.build.os === "win") {
+    // Windows only has Ctrl+C and Ctrl+Break, which would reach every
+    // process on the console.
+    const events = Deno.signal("SIGINT");
+    await events.return!();
+    return;
+  }
+  const events = Deno.signal("SIGUSR1");
+  Deno.kill(Deno.pid, "SIGUSR1");
+  const result = await events.next();
+  assertEquals(result.done, false);
+  await events.return!();
+});
+
+test(async function signalClosed(): Promise<void> {
+  const events = Deno.signal("SIGINT");
+  const pending = events.next();
+  await events.return!();
+  assertEquals((await pending).done, true);
+});
+
+test(function signalUncatchable(): void {
+  let err;
+  try {
+    Deno.signal("SIGKILL");
+  } catch (e) {
+    err = e;
+  }
+  assert(!!err);
+  assertEquals(err.kind, Deno.ErrorKind.InvalidInput);
+});
+
+if (Deno.build.os !== "win") {
+  test(function signalHandledByDeno(): void {
+    for (const name of ["SIGCHLD", "SIGPIPE"]) {
+      let err;
+      try {
+        Deno.signal(name);
+      } catch (e) {
+        err = e;
+      }
+      assert(!!err);
+      assertEquals(err.kind, Deno.ErrorKind.InvalidInput);
+    }
+  });
+}
@@ -11,8 +11,16 @@ export interface StatResponse {
   accessed: number;
   created: number;
   mode: number;
-  hasMode: boolean; // false on windows
   name: string | null;
+  // null where the platform has no equivalent
+  dev: string | null;
+  ino: string | null;
+  nlink: number | null;
+  uid: number | null;
+  gid: number | null;
+  rdev: string | null;
+  blksize: number | null;
+  blocks: number | null;
 }
 
 /** Queries the file system for information on the path provided. If the given
@@ -71,3 +79,29 @@ export function statSync(filename: string): FileInfo {
   }) as StatResponse;
   return new FileInfoImpl(res);
 }
+
+/** Queries the file system for information on the file referenced by `rid`.
+ * Unlike `stat` this describes exactly the file that was opened, even if the
+ * path has since been replaced.
+ *
+ *       const file = await Deno.open("hello.txt");
+ *       const fileInfo = await Deno.fstat(file.rid);
+ *       assert(fileInfo.isFile());
+ */
+export async function fstat(rid: number): Promise<FileInfo> {
+  const res = (await sendAsync(dispatch.OP_FSTAT, { rid })) as StatResponse;
+  return new FileInfoImpl(res);
+}
+
+/** Queries the file system for information on the file referenced by `rid`
+ * synchronously. Unlike `statSync` this describes exactly the file that was
+ * opened, even if the path has since been replaced.
+ *
+ *       const file = Deno.openSync("hello.txt");
+ *       const fileInfo = Deno.fstatSync(file.rid);
+ *       assert(fileInfo.isFile());
+ */
+export function fstatSync(rid: number): FileInfo {
+  const res = sendSync(dispatch.OP_FSTAT, { rid }) as StatResponse;
+  return new FileInfoImpl(res);
+}
@@ -170,3 +170,66 @@ testPerm({ read: true }, async function lstatNotFound(): Promise<void> {
   assert(caughtError);
   assertEquals(badInfo, undefined);
 });
+
+testPerm({ read: true }, async function fstatSuccess(): Promise<void> {
+  const file = await Deno.open("package.json");
+  const fileInfo = await Deno.fstat(file.rid);
+  assert(fileInfo.isFile());
+  assert(!fileInfo.isSymlink());
+  assertEquals(fileInfo.len, Deno.statSync("package.json").len);
+  file.close();
+});
+
+testPerm({ read: true }, function fstatSyncSuccess(): void {
+  const file = Deno.openSync("tests/hello.txt");
+  const fileInfo = Deno.fstatSync(file.rid);
+  assert(fileInfo.isFile());
+  assertEquals(fileInfo.len, Deno.statSync("tests/hello.txt").len);
+  file.close();
+});
+
+testPerm({ read: true }, function fstatSyncBadResource(): void {
+  let caughtError = false;
+  try {
+    Deno.fstatSync(9999);
+  } catch (err) {
+    caughtError = true;
+    assertEquals(err.kind, Deno.ErrorKind.BadResource);
+  }
+  assert(caughtError);
+});
+
+testPerm({ read: true, write: true }, function statSyncFileIdentity(): void {
+  const dir = Deno.makeTempDirSync();
+  const a = dir + "/a.txt";
+  const b = dir + "/b.txt";
+  Deno.writeFileSync(a, new Uint8Array([1, 2, 3]));
+  const before = Deno.statSync(a);
+  assertEquals(typeof before.dev, "string");
+  assertEquals(typeof before.ino, "string");
+  assertEquals(before.nlink, 1);
+
+  // A file keeps its identity when it's renamed.
+  Deno.renameSync(a, b);
+  const after = Deno.statSync(b);
+  assertEquals(after.dev, before.dev);
+  assertEquals(after.ino, before.ino);
+  assertEquals(Deno.lstatSync(b).ino, before.ino);
+
+  const file = Deno.openSync(b);
+  assertEquals(Deno.fstatSync(file.rid).ino, before.ino);
+  file.close();
+});
+
+testPerm({ read: true }, function statSyncUnixFields(): void {
+  const info = Deno.statSync("package.json");
+  if (Deno.build.os === "win") {
+    assertEquals(info.uid, null);
+    assertEquals(info.blocks, null);
+  } else {
+    assert(info.uid !== null);
+    assert(info.gid !== null);
+    assert(info.blksize! > 0);
+    assert(info.blocks! > 0);
+  }
+});
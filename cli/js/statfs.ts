@@ -0,0 +1,30 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { sendSync, sendAsync } from "./dispatch_json.ts";
+import * as dispatch from "./dispatch.ts";
+
+/** Space on a file system, in bytes. `available` is the space usable by the
+ * current user, which can be less than `free` when some of it is reserved.
+ */
+export interface FsUsage {
+  total: number;
+  free: number;
+  available: number;
+}
+
+/** Synchronously returns the total, free and available space of the file
+ * system containing `path`. Requires `allow-read`.
+ *
+ *       const { available } = Deno.statfsSync("/tmp");
+ */
+export function statfsSync(path: string): FsUsage {
+  return sendSync(dispatch.OP_STATFS, { path });
+}
+
+/** Returns the total, free and available space of the file system containing
+ * `path`. Requires `allow-read`.
+ *
+ *       const { available } = await Deno.statfs("/tmp");
+ */
+export async function statfs(path: string): Promise<FsUsage> {
+  return await sendAsync(dispatch.OP_STATFS, { path });
+}
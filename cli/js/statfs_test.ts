@@ -0,0 +1,38 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { testPerm, assert, assertEquals } from "./test_util.ts";
+
+testPerm({ read: true }, function statfsSyncSuccess(): void {
+  const usage = Deno.statfsSync(".");
+  assert(usage.total > 0);
+  assert(usage.free <= usage.total);
+  assert(usage.available <= usage.free);
+});
+
+testPerm({ read: true }, async function statfsSuccess(): Promise<void> {
+  const usage = await Deno.statfs("tests/hello.txt");
+  assert(usage.total > 0);
+  assert(usage.available <= usage.total);
+});
+
+testPerm({ read: false }, function statfsSyncPerm(): void {
+  let caughtError = false;
+  try {
+    Deno.statfsSync(".");
+  } catch (e) {
+    caughtError = true;
+    assertEquals(e.kind, Deno.ErrorKind.PermissionDenied);
+    assertEquals(e.name, "PermissionDenied");
+  }
+  assert(caughtError);
+});
+
+testPerm({ read: true }, async function statfsNotFound(): Promise<void> {
+  let caughtError = false;
+  try {
+    await Deno.statfs("bad_file_name");
+  } catch (e) {
+    caughtError = true;
+    assertEquals(e.kind, Deno.ErrorKind.NotFound);
+  }
+  assert(caughtError);
+});
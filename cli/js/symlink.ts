@@ -1,39 +1,33 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
 import { sendSync, sendAsync } from "./dispatch_json.ts";
 import * as dispatch from "./dispatch.ts";
-import * as util from "./util.ts";
-import { build } from "./build.ts";
 
 /** Synchronously creates `newname` as a symbolic link to `oldname`. The type
- * argument can be set to `dir` or `file` and is only available on Windows
- * (ignored on other platforms).
+ * argument can be set to `dir` or `file` and is only used on Windows (ignored
+ * on other platforms). When it is omitted on Windows, the type is inferred
+ * from `oldname`, which must then exist.
  *
  *       Deno.symlinkSync("old/name", "new/name");
  */
 export function symlinkSync(
   oldname: string,
   newname: string,
-  type?: string
+  type?: "dir" | "file"
 ): void {
-  if (build.os === "win" && type) {
-    return util.notImplemented();
-  }
-  sendSync(dispatch.OP_SYMLINK, { oldname, newname });
+  sendSync(dispatch.OP_SYMLINK, { oldname, newname, type });
 }
 
 /** Creates `newname` as a symbolic link to `oldname`. The type argument can be
- * set to `dir` or `file` and is only available on Windows (ignored on other
- * platforms).
+ * set to `dir` or `file` and is only used on Windows (ignored on other
+ * platforms). When it is omitted on Windows, the type is inferred from
+ * `oldname`, which must then exist.
  *
  *       await Deno.symlink("old/name", "new/name");
  */
 export async function symlink(
   oldname: string,
   newname: string,
-  type?: string
+  type?: "dir" | "file"
 ): Promise<void> {
-  if (build.os === "win" && type) {
-    return util.notImplemented();
-  }
-  await sendAsync(dispatch.OP_SYMLINK, { oldname, newname });
+  await sendAsync(dispatch.OP_SYMLINK, { oldname, newname, type });
 }
@@ -1,28 +1,46 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
 import { test, testPerm, assert, assertEquals } from "./test_util.ts";
 
+// Creating symlinks on Windows requires Developer Mode or an elevated process.
+// When neither is available the op must fail with a clear PermissionDenied
+// error instead of a generic one.
+function assertMissingWindowsPrivilege(
+  err: Deno.DenoError<Deno.ErrorKind>
+): void {
+  assertEquals(Deno.build.os, "win");
+  assertEquals(err.kind, Deno.ErrorKind.PermissionDenied);
+}
+
 testPerm({ read: true, write: true }, function symlinkSyncSuccess(): void {
   const testDir = Deno.makeTempDirSync();
   const oldname = testDir + "/oldname";
   const newname = testDir + "/newname";
   Deno.mkdirSync(oldname);
-  let errOnWindows;
-  // Just for now, until we implement symlink for Windows.
   try {
     Deno.symlinkSync(oldname, newname);
   } catch (e) {
-    errOnWindows = e;
+    assertMissingWindowsPrivilege(e);
+    return;
   }
-  if (errOnWindows) {
-    assertEquals(Deno.build.os, "win");
-    assertEquals(errOnWindows.kind, Deno.ErrorKind.Other);
-    assertEquals(errOnWindows.message, "Not implemented");
-  } else {
-    const newNameInfoLStat = Deno.lstatSync(newname);
-    const newNameInfoStat = Deno.statSync(newname);
-    assert(newNameInfoLStat.isSymlink());
-    assert(newNameInfoStat.isDirectory());
+  const newNameInfoLStat = Deno.lstatSync(newname);
+  const newNameInfoStat = Deno.statSync(newname);
+  assert(newNameInfoLStat.isSymlink());
+  assert(newNameInfoStat.isDirectory());
+});
+
+testPerm({ read: true, write: true }, function symlinkSyncFile(): void {
+  const testDir = Deno.makeTempDirSync();
+  const oldname = testDir + "/oldname.txt";
+  const newname = testDir + "/newname.txt";
+  Deno.writeFileSync(oldname, new TextEncoder().encode("hello"));
+  try {
+    Deno.symlinkSync(oldname, newname);
+  } catch (e) {
+    assertMissingWindowsPrivilege(e);
+    return;
   }
+  assert(Deno.lstatSync(newname).isSymlink());
+  assert(Deno.statSync(newname).isFile());
 });
 
 test(function symlinkSyncPerm(): void {
@@ -36,23 +54,23 @@ test(function symlinkSyncPerm(): void {
   assertEquals(err.name, "PermissionDenied");
 });
 
-// Just for now, until we implement symlink for Windows.
-// Symlink with type should succeed on other platforms with type ignored
-testPerm({ write: true }, function symlinkSyncNotImplemented(): void {
-  const testDir = Deno.makeTempDirSync();
-  const oldname = testDir + "/oldname";
-  const newname = testDir + "/newname";
-  let err;
-  try {
-    Deno.symlinkSync(oldname, newname, "dir");
-  } catch (e) {
-    err = e;
-  }
-  if (err) {
-    assertEquals(Deno.build.os, "win");
-    assertEquals(err.message, "Not implemented");
+// The type argument is what lets a dangling link be created on Windows. On
+// other platforms it is ignored.
+testPerm(
+  { read: true, write: true },
+  function symlinkSyncDanglingWithType(): void {
+    const testDir = Deno.makeTempDirSync();
+    const oldname = testDir + "/oldname";
+    const newname = testDir + "/newname";
+    try {
+      Deno.symlinkSync(oldname, newname, "dir");
+    } catch (e) {
+      assertMissingWindowsPrivilege(e);
+      return;
+    }
+    assert(Deno.lstatSync(newname).isSymlink());
   }
-});
+);
 
 testPerm({ read: true, write: true }, async function symlinkSuccess(): Promise<
   void
@@ -61,20 +79,14 @@ testPerm({ read: true, write: true }, async function symlinkSuccess(): Promise<
   const oldname = testDir + "/oldname";
   const newname = testDir + "/newname";
   Deno.mkdirSync(oldname);
-  let errOnWindows;
-  // Just for now, until we implement symlink for Windows.
   try {
     await Deno.symlink(oldname, newname);
   } catch (e) {
-    errOnWindows = e;
-  }
-  if (errOnWindows) {
-    assertEquals(errOnWindows.kind, Deno.ErrorKind.Other);
-    assertEquals(errOnWindows.message, "Not implemented");
-  } else {
-    const newNameInfoLStat = Deno.lstatSync(newname);
-    const newNameInfoStat = Deno.statSync(newname);
-    assert(newNameInfoLStat.isSymlink());
-    assert(newNameInfoStat.isDirectory());
+    assertMissingWindowsPrivilege(e);
+    return;
   }
+  const newNameInfoLStat = Deno.lstatSync(newname);
+  const newNameInfoStat = Deno.statSync(newname);
+  assert(newNameInfoLStat.isSymlink());
+  assert(newNameInfoStat.isDirectory());
 });
@@ -0,0 +1,19 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { sendSync } from "./dispatch_json.ts";
+import * as dispatch from "./dispatch.ts";
+
+/** Splits the readable resource `rid` into two resources which both read the
+ * same bytes from it. `rid` itself is consumed and closed once both new
+ * resources are. Up to 64 KiB are buffered for the slower of the two, after
+ * which reading the faster one waits for it to catch up. Closing one of them
+ * lets the other read on alone.
+ *
+ *       const [toHash, toSave] = Deno.tee(conn.rid);
+ *       await Promise.all([
+ *         hash(new Deno.File(toHash)),
+ *         Deno.transfer(toSave, file.rid)
+ *       ]);
+ */
+export function tee(rid: number): [number, number] {
+  return sendSync(dispatch.OP_TEE, { rid });
+}
@@ -0,0 +1,60 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { test, testPerm, assert, assertEquals } from "./test_util.ts";
+
+// Larger than what is buffered for the slower branch, so that the faster one
+// has to wait for it at least once.
+function writeBigFile(): string {
+  const data = new Uint8Array(200 * 1024);
+  for (let i = 0; i < data.length; i++) {
+    data[i] = i % 251;
+  }
+  const filename = Deno.makeTempDirSync() + "/test.bin";
+  Deno.writeFileSync(filename, data);
+  return filename;
+}
+
+testPerm({ read: true, write: true }, async function teeBothBranches(): Promise<
+  void
+> {
+  const filename = writeBigFile();
+  const file = await Deno.open(filename);
+  const [a, b] = Deno.tee(file.rid);
+  assert(!(file.rid in Deno.resources()));
+  assertEquals(Deno.resources()[a], "tee");
+  assertEquals(Deno.resources()[b], "tee");
+
+  const fileA = new Deno.File(a);
+  const fileB = new Deno.File(b);
+  const [dataA, dataB] = await Promise.all([
+    Deno.readAll(fileA),
+    Deno.readAll(fileB)
+  ]);
+  fileA.close();
+  fileB.close();
+  const expected = Deno.readFileSync(filename);
+  assertEquals(dataA, expected);
+  assertEquals(dataB, expected);
+});
+
+testPerm({ read: true, write: true }, function teeOneBranchClosed(): void {
+  const filename = writeBigFile();
+  const file = Deno.openSync(filename);
+  const [a, b] = Deno.tee(file.rid);
+  Deno.close(b);
+
+  const fileA = new Deno.File(a);
+  const data = Deno.readAllSync(fileA);
+  fileA.close();
+  assertEquals(data, Deno.readFileSync(filename));
+});
+
+test(function teeBadResource(): void {
+  let err;
+  try {
+    Deno.tee(9999);
+  } catch (e) {
+    err = e;
+  }
+  assert(!!err);
+  assertEquals(err.kind, Deno.ErrorKind.BadResource);
+});
@@ -27,6 +27,7 @@ interface TestPermissions {
   env?: boolean;
   run?: boolean;
   hrtime?: boolean;
+  plugin?: boolean;
 }
 
 const processPerms = Deno.permissions();
@@ -53,7 +54,8 @@ function permToString(perms: Deno.Permissions): string {
   const e = perms.env ? 1 : 0;
   const u = perms.run ? 1 : 0;
   const h = perms.hrtime ? 1 : 0;
-  return `permR${r}W${w}N${n}E${e}U${u}H${h}`;
+  const p = perms.plugin ? 1 : 0;
+  return `permR${r}W${w}N${n}E${e}U${u}H${h}P${p}`;
 }
 
 function registerPermCombination(perms: Deno.Permissions): void {
@@ -70,7 +72,8 @@ function normalizeTestPermissions(perms: TestPermissions): Deno.Permissions {
     net: !!perms.net,
     run: !!perms.run,
     env: !!perms.env,
-    hrtime: !!perms.hrtime
+    hrtime: !!perms.hrtime,
+    plugin: !!perms.plugin
   };
 }
 
@@ -97,7 +100,8 @@ export function test(fn: testing.TestFunction): void {
       net: false,
       env: false,
       run: false,
-      hrtime: false
+      hrtime: false,
+      plugin: false
     },
     fn
   );
@@ -153,7 +157,8 @@ test(function permissionsMatches(): void {
         net: false,
         env: false,
         run: false,
-        hrtime: false
+        hrtime: false,
+        plugin: false
       },
       normalizeTestPermissions({ read: true })
     )
@@ -167,7 +172,8 @@ test(function permissionsMatches(): void {
         net: false,
         env: false,
         run: false,
-        hrtime: false
+        hrtime: false,
+        plugin: false
       },
       normalizeTestPermissions({})
     )
@@ -181,7 +187,8 @@ test(function permissionsMatches(): void {
         net: true,
         env: true,
         run: true,
-        hrtime: true
+        hrtime: true,
+        plugin: true
       },
       normalizeTestPermissions({ read: true })
     ),
@@ -196,7 +203,8 @@ test(function permissionsMatches(): void {
         net: true,
         env: false,
         run: false,
-        hrtime: false
+        hrtime: false,
+        plugin: false
       },
       normalizeTestPermissions({ read: true })
     ),
@@ -211,7 +219,8 @@ test(function permissionsMatches(): void {
         net: true,
         env: true,
         run: true,
-        hrtime: true
+        hrtime: true,
+        plugin: true
       },
       {
         read: true,
@@ -219,7 +228,8 @@ test(function permissionsMatches(): void {
         net: true,
         env: true,
         run: true,
-        hrtime: true
+        hrtime: true,
+        plugin: true
       }
     )
   );
@@ -1,8 +1,7 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
-import { assert } from "./util.ts";
 import { window } from "./window.ts";
 import * as dispatch from "./dispatch.ts";
-import { sendSync, sendAsync } from "./dispatch_json.ts";
+import { core } from "./core.ts";
 
 const { console } = window;
 
@@ -10,110 +9,57 @@ interface Timer {
   id: number;
   callback: () => void;
   delay: number;
-  due: number;
   repeat: boolean;
   scheduled: boolean;
 }
 
-// We'll subtract EPOCH every time we retrieve the time with Date.now(). This
-// ensures that absolute time values stay below UINT32_MAX - 2, which is the
-// maximum object key that EcmaScript considers "numerical". After running for
-// about a month, this is no longer true, and Deno explodes.
-// TODO(piscisaureus): fix that ^.
-const EPOCH = Date.now();
-const APOCALYPSE = 2 ** 32 - 2;
-
 // Timeout values > TIMEOUT_MAX are set to 1.
 const TIMEOUT_MAX = 2 ** 31 - 1;
 
-let globalTimeoutDue: number | null = null;
+// These consts are shared with cli/ops/timers.rs. Update with care.
+const TIMER_START = 0;
+const TIMER_CLEAR = 1;
+const TIMER_POLL = 2;
 
 let nextTimerId = 1;
 const idMap = new Map<number, Timer>();
-const dueMap: { [due: number]: Timer[] } = Object.create(null);
-
-function getTime(): number {
-  // TODO: use a monotonic clock.
-  const now = Date.now() - EPOCH;
-  assert(now >= 0 && now < APOCALYPSE);
-  return now;
-}
 
-function clearGlobalTimeout(): void {
-  globalTimeoutDue = null;
-  sendSync(dispatch.OP_GLOBAL_TIMER_STOP);
-}
+// The number of timers that Rust has yet to fire, and whether the poll for
+// them is pending.
+let scheduledTimers = 0;
+let polling = false;
 
-let pendingEvents = 0;
+const control = new Int32Array(4);
+const controlBytes = new Uint8Array(control.buffer);
 
-async function setGlobalTimeout(due: number, now: number): Promise<void> {
-  // Since JS and Rust don't use the same clock, pass the time to rust as a
-  // relative time value. On the Rust side we'll turn that into an absolute
-  // value again.
-  const timeout = due - now;
-  assert(timeout >= 0);
-  // Send message to the backend.
-  globalTimeoutDue = due;
-  pendingEvents++;
-  await sendAsync(dispatch.OP_GLOBAL_TIMER, { timeout });
-  pendingEvents--;
-  // eslint-disable-next-line @typescript-eslint/no-use-before-define
-  fireTimers();
-}
-
-function setOrClearGlobalTimeout(due: number | null, now: number): void {
-  if (due == null) {
-    clearGlobalTimeout();
-  } else {
-    setGlobalTimeout(due, now);
-  }
+function sendTimerOp(
+  command: number,
+  id = 0,
+  delay = 0,
+  repeat = false
+): Uint8Array | null {
+  control[0] = command;
+  control[1] = id;
+  control[2] = delay;
+  control[3] = repeat ? 1 : 0;
+  return core.dispatch(dispatch.OP_TIMER, controlBytes);
 }
 
-function schedule(timer: Timer, now: number): void {
-  assert(!timer.scheduled);
-  assert(now <= timer.due);
-  // Find or create the list of timers that will fire at point-in-time `due`.
-  let list = dueMap[timer.due];
-  if (list === undefined) {
-    list = dueMap[timer.due] = [];
-  }
-  // Append the newly scheduled timer to the list and mark it as scheduled.
-  list.push(timer);
+function schedule(timer: Timer): void {
+  sendTimerOp(TIMER_START, timer.id, timer.delay, timer.repeat);
   timer.scheduled = true;
-  // If the new timer is scheduled to fire before any timer that existed before,
-  // update the global timeout to reflect this.
-  if (globalTimeoutDue === null || globalTimeoutDue > timer.due) {
-    setOrClearGlobalTimeout(timer.due, now);
-  }
+  scheduledTimers++;
+  // eslint-disable-next-line @typescript-eslint/no-use-before-define
+  poll();
 }
 
 function unschedule(timer: Timer): void {
   if (!timer.scheduled) {
     return;
   }
-  // Find the list of timers that will fire at point-in-time `due`.
-  const list = dueMap[timer.due];
-  if (list.length === 1) {
-    // Time timer is the only one in the list. Remove the entire list.
-    assert(list[0] === timer);
-    delete dueMap[timer.due];
-    // If the unscheduled timer was 'next up', find when the next timer that
-    // still exists is due, and update the global alarm accordingly.
-    if (timer.due === globalTimeoutDue) {
-      let nextTimerDue: number | null = null;
-      for (const key in dueMap) {
-        nextTimerDue = Number(key);
-        break;
-      }
-      setOrClearGlobalTimeout(nextTimerDue, getTime());
-    }
-  } else {
-    // Multiple timers that are due at the same point in time.
-    // Remove this timer from the list.
-    const index = list.indexOf(timer);
-    assert(index > -1);
-    list.splice(index, 1);
-  }
+  sendTimerOp(TIMER_CLEAR, timer.id);
+  timer.scheduled = false;
+  scheduledTimers--;
 }
 
 function fire(timer: Timer): void {
@@ -122,16 +68,9 @@ function fire(timer: Timer): void {
   if (!idMap.has(timer.id)) {
     return;
   }
-  // Reschedule the timer if it is a repeating one, otherwise drop it.
+  // Interval timers stay scheduled in Rust, one-shot ones are dropped.
   if (!timer.repeat) {
-    // One-shot timer: remove the timer from this id-to-timer map.
     idMap.delete(timer.id);
-  } else {
-    // Interval timer: compute when timer was supposed to fire next.
-    // However make sure to never schedule the next interval in the past.
-    const now = getTime();
-    timer.due = Math.max(now, timer.due + timer.delay);
-    schedule(timer, now);
   }
   // Call the user callback. Intermediate assignment is to avoid leaking `this`
   // to it, while also keeping the stack trace neat when it shows up in there.
@@ -139,41 +78,40 @@ function fire(timer: Timer): void {
   callback();
 }
 
-function fireTimers(): void {
-  const now = getTime();
-  // Bail out if we're not expecting the global timer to fire.
-  if (globalTimeoutDue === null || pendingEvents > 0) {
-    return;
-  }
-  // After firing the timers that are due now, this will hold the due time of
-  // the first timer that hasn't fired yet.
-  let nextTimerDue: number | null = null;
-  // Walk over the keys of the 'due' map. Since dueMap is actually a regular
-  // object and its keys are numerical and smaller than UINT32_MAX - 2,
-  // keys are iterated in ascending order.
-  for (const key in dueMap) {
-    // Convert the object key (a string) to a number.
-    const due = Number(key);
-    // Break out of the loop if the next timer isn't due to fire yet.
-    if (Number(due) > now) {
-      nextTimerDue = due;
-      break;
+function fireTimers(ui8: Uint8Array): void {
+  polling = false;
+  // The ids of the timers that fired, as int32s.
+  const view = new DataView(ui8.buffer, ui8.byteOffset, ui8.byteLength);
+  for (let i = 0; i < ui8.byteLength; i += 4) {
+    const timer = idMap.get(view.getInt32(i, true));
+    if (timer === undefined) {
+      continue;
     }
-    // Get the list of timers that have this due time, then drop it.
-    const list = dueMap[key];
-    delete dueMap[key];
-    // Fire all the timers in the list.
-    for (const timer of list) {
-      // With the list dropped, the timer is no longer scheduled.
+    if (!timer.repeat) {
+      // With the timer fired, Rust forgot about it.
       timer.scheduled = false;
-      // Place the callback on the microtask queue.
-      Promise.resolve(timer).then(fire);
+      scheduledTimers--;
     }
+    // Place the callback on the microtask queue.
+    Promise.resolve(timer).then(fire);
   }
+  // eslint-disable-next-line @typescript-eslint/no-use-before-define
+  poll();
+}
 
-  // Update the global alarm to go off when the first-up timer that hasn't fired
-  // yet is due.
-  setOrClearGlobalTimeout(nextTimerDue, now);
+/** Waits for the next timers to fire, unless it's waiting already or there
+ * are no timers. */
+function poll(): void {
+  if (polling || scheduledTimers === 0) {
+    return;
+  }
+  polling = true;
+  dispatch.setAsyncHandler(dispatch.OP_TIMER, fireTimers);
+  const buf = sendTimerOp(TIMER_POLL);
+  if (buf) {
+    // Timers were due already.
+    fireTimers(buf);
+  }
 }
 
 export type Args = unknown[];
@@ -201,7 +139,6 @@ function setTimer(
   // In the browser, the delay value must be coercible to an integer between 0
   // and INT32_MAX. Any other value will cause the timer to fire immediately.
   // We emulate this behavior.
-  const now = getTime();
   if (delay > TIMEOUT_MAX) {
     console.warn(
       `${delay} does not fit into` +
@@ -218,14 +155,13 @@ function setTimer(
     callback,
     args,
     delay,
-    due: now + delay,
     repeat,
     scheduled: false
   };
   // Register the timer's existence in the id-to-timer map.
   idMap.set(timer.id, timer);
-  // Schedule the timer in the due table.
-  schedule(timer, now);
+  // Schedule the timer in Rust.
+  schedule(timer);
   return timer.id;
 }
 
@@ -301,3 +301,20 @@ test(async function timerMaxCpuBug(): Promise<void> {
   console.log("opsDispatched", opsDispatched, "opsDispatched_", opsDispatched_);
   assert(opsDispatched_ - opsDispatched < 10);
 });
+
+test(async function manyIntervals(): Promise<void> {
+  // Thousands of intervals share a single pending op, which resolves with all
+  // those due at once.
+  const counts = new Array(2000).fill(0);
+  const ids = counts.map((_, i): number =>
+    setInterval((): void => {
+      counts[i]++;
+    }, 10)
+  );
+  const { opsDispatched } = Deno.metrics();
+  await waitForMs(55);
+  const opsDispatched_ = Deno.metrics().opsDispatched;
+  ids.forEach((id): void => clearInterval(id));
+  assert(counts.every((count): boolean => count >= 2));
+  assert(opsDispatched_ - opsDispatched < 100);
+});
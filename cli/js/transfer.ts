@@ -0,0 +1,22 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { sendAsync } from "./dispatch_json.ts";
+import * as dispatch from "./dispatch.ts";
+
+/** Copies `count` bytes, or everything up to EOF if `count` is omitted, from
+ * the resource `src` to the resource `dst` without passing them through JS.
+ * Resolves to the number of bytes copied.
+ *
+ * On Linux files are sent to TCP connections with `sendfile(2)` and TCP
+ * connections are piped into each other with `splice(2)`, so the data never
+ * leaves the kernel.
+ *
+ *       const file = await Deno.open("index.html");
+ *       await Deno.transfer(file.rid, conn.rid);
+ */
+export async function transfer(
+  src: number,
+  dst: number,
+  count?: number
+): Promise<number> {
+  return await sendAsync(dispatch.OP_TRANSFER, { src, dst, count });
+}
@@ -0,0 +1,100 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { testPerm, assert, assertEquals } from "./test_util.ts";
+
+testPerm(
+  { read: true, write: true },
+  async function transferFileToFile(): Promise<void> {
+    const data = Deno.readFileSync("tests/hello.txt");
+    const dst = Deno.makeTempDirSync() + "/hello.txt";
+    const srcFile = await Deno.open("tests/hello.txt");
+    const dstFile = await Deno.open(dst, "w");
+
+    const copied = await Deno.transfer(srcFile.rid, dstFile.rid);
+    assertEquals(copied, data.byteLength);
+    srcFile.close();
+    dstFile.close();
+    assertEquals(Deno.readFileSync(dst), data);
+  }
+);
+
+testPerm({ read: true, write: true }, async function transferCount(): Promise<
+  void
+> {
+  const data = Deno.readFileSync("tests/hello.txt");
+  const dst = Deno.makeTempDirSync() + "/hello.txt";
+  const srcFile = await Deno.open("tests/hello.txt");
+  const dstFile = await Deno.open(dst, "w");
+
+  assertEquals(await Deno.transfer(srcFile.rid, dstFile.rid, 3), 3);
+  // The source offset advanced past the copied bytes.
+  assertEquals(await Deno.transfer(srcFile.rid, dstFile.rid, 2), 2);
+  srcFile.close();
+  dstFile.close();
+  assertEquals(Deno.readFileSync(dst), data.subarray(0, 5));
+});
+
+testPerm(
+  { read: true, net: true },
+  async function transferFileToConn(): Promise<void> {
+    const data = Deno.readFileSync("tests/hello.txt");
+    const listener = Deno.listen({ port: 4600 });
+    listener.accept().then(
+      async (conn): Promise<void> => {
+        const file = await Deno.open("tests/hello.txt");
+        assertEquals(await Deno.transfer(file.rid, conn.rid), data.byteLength);
+        file.close();
+        conn.close();
+      }
+    );
+    const conn = await Deno.dial({ hostname: "127.0.0.1", port: 4600 });
+    assertEquals(await Deno.readAll(conn), data);
+    conn.close();
+    listener.close();
+  }
+);
+
+testPerm({ net: true }, async function transferConnToConn(): Promise<void> {
+  const data = new Uint8Array(256 * 1024);
+  for (let i = 0; i < data.byteLength; i++) {
+    data[i] = i % 251;
+  }
+
+  // A forwards everything it receives from B back to B.
+  const listener = Deno.listen({ port: 4601 });
+  listener.accept().then(
+    async (conn): Promise<void> => {
+      const upstream = await Deno.dial({ hostname: "127.0.0.1", port: 4602 });
+      await Deno.transfer(conn.rid, upstream.rid, data.byteLength);
+      conn.close();
+      upstream.close();
+    }
+  );
+  const upstreamListener = Deno.listen({ port: 4602 });
+  const received = upstreamListener.accept().then(
+    async (conn): Promise<Uint8Array> => {
+      const bytes = await Deno.readAll(conn);
+      conn.close();
+      return bytes;
+    }
+  );
+
+  const conn = await Deno.dial({ hostname: "127.0.0.1", port: 4601 });
+  await Deno.writeAll(conn, data);
+  assertEquals(await received, data);
+  conn.close();
+  listener.close();
+  upstreamListener.close();
+});
+
+testPerm({ read: true }, async function transferBadResource(): Promise<void> {
+  const file = await Deno.open("tests/hello.txt");
+  let err;
+  try {
+    await Deno.transfer(file.rid, 9999);
+  } catch (e) {
+    err = e;
+  }
+  file.close();
+  assert(!!err);
+  assertEquals(err.kind, Deno.ErrorKind.BadResource);
+});
@@ -32,3 +32,23 @@ export function truncateSync(name: string, len?: number): void {
 export async function truncate(name: string, len?: number): Promise<void> {
   await sendAsync(dispatch.OP_TRUNCATE, { name, len: coerceLen(len) });
 }
+
+/** Truncates or extends the already open file `rid` synchronously, updating
+ * the size of this file to become size. The file must be open for writing.
+ *
+ *       const file = Deno.openSync("hello.txt", "r+");
+ *       Deno.ftruncateSync(file.rid, 10);
+ */
+export function ftruncateSync(rid: number, len?: number): void {
+  sendSync(dispatch.OP_FTRUNCATE, { rid, len: coerceLen(len) });
+}
+
+/** Truncates or extends the already open file `rid`, updating the size of
+ * this file to become size. The file must be open for writing.
+ *
+ *       const file = await Deno.open("hello.txt", "r+");
+ *       await Deno.ftruncate(file.rid, 10);
+ */
+export async function ftruncate(rid: number, len?: number): Promise<void> {
+  await sendAsync(dispatch.OP_FTRUNCATE, { rid, len: coerceLen(len) });
+}
@@ -51,6 +51,42 @@ testPerm({ read: true, write: true }, async function truncateSuccess(): Promise<
   await Deno.remove(filename);
 });
 
+testPerm({ read: true, write: true }, function ftruncateSyncSuccess(): void {
+  const filename = Deno.makeTempDirSync() + "/test_ftruncateSync.txt";
+  Deno.writeFileSync(filename, new TextEncoder().encode("Hello"));
+  const file = Deno.openSync(filename, "r+");
+  Deno.ftruncateSync(file.rid, 20);
+  assertEquals(readDataSync(filename).length, 20);
+  Deno.ftruncateSync(file.rid, 2);
+  assertEquals(readDataSync(filename), "He");
+  file.close();
+  Deno.removeSync(filename);
+});
+
+testPerm({ read: true, write: true }, async function ftruncateSuccess(): Promise<
+  void
+> {
+  const filename = Deno.makeTempDirSync() + "/test_ftruncate.txt";
+  await Deno.writeFile(filename, new TextEncoder().encode("Hello"));
+  const file = await Deno.open(filename, "r+");
+  await Deno.ftruncate(file.rid, 20);
+  assertEquals((await readData(filename)).length, 20);
+  await Deno.ftruncate(file.rid);
+  assertEquals((await readData(filename)).length, 0);
+  file.close();
+  await Deno.remove(filename);
+});
+
+testPerm({ read: true }, function ftruncateSyncBadResource(): void {
+  let err;
+  try {
+    Deno.ftruncateSync(123456, 0);
+  } catch (e) {
+    err = e;
+  }
+  assertEquals(err.kind, Deno.ErrorKind.BadResource);
+});
+
 testPerm({ write: false }, function truncateSyncPerm(): void {
   let err;
   try {
@@ -0,0 +1,90 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { sendSync, sendAsync } from "./dispatch_json.ts";
+import * as dispatch from "./dispatch.ts";
+import { close } from "./files.ts";
+
+export interface ConsoleSize {
+  columns: number;
+  rows: number;
+}
+
+/** Check if the resource `rid` is a terminal. Resources that can't be one,
+ * like sockets, are never reported as such.
+ *
+ *       if (Deno.isatty(Deno.stdout.rid)) {
+ *         console.log("\x1b[1mbold\x1b[0m");
+ *       }
+ */
+export function isatty(rid: number): boolean {
+  return sendSync(dispatch.OP_ISATTY, { rid });
+}
+
+/** Returns the size of the terminal behind `rid`. Throws if `rid` is not a
+ * terminal.
+ *
+ *       const { columns } = Deno.consoleSize(Deno.stdout.rid);
+ */
+export function consoleSize(rid: number): ConsoleSize {
+  return sendSync(dispatch.OP_CONSOLE_SIZE, { rid });
+}
+
+export interface SetRawOptions {
+  /** Use cbreak mode rather than raw mode: input is still passed on a key
+   * at a time and not echoed, but Ctrl-C and the like still raise signals.
+   * Defaults to `false`.
+   */
+  cbreak?: boolean;
+}
+
+/** Switches the terminal behind stdin into raw mode, where every key press
+ * is passed on as it happens, without echo or line editing, or back to the
+ * mode it was in before. The original mode is also restored when the process
+ * exits. Only `Deno.stdin.rid` is supported.
+ *
+ *       Deno.setRaw(Deno.stdin.rid, true);
+ *       const key = new Uint8Array(8);
+ *       await Deno.stdin.read(key);
+ *       Deno.setRaw(Deno.stdin.rid, false);
+ */
+export function setRaw(
+  rid: number,
+  mode: boolean,
+  options: SetRawOptions = {}
+): void {
+  const { cbreak = false } = options;
+  sendSync(dispatch.OP_SET_RAW, { rid, mode, cbreak });
+}
+
+class ConsoleResizeEvents implements AsyncIterableIterator<ConsoleSize> {
+  readonly rid: number;
+
+  constructor(rid: number) {
+    this.rid = sendSync(dispatch.OP_CONSOLE_RESIZE_OPEN, { rid });
+  }
+
+  async next(): Promise<IteratorResult<ConsoleSize>> {
+    return await sendAsync(dispatch.OP_CONSOLE_RESIZE_POLL, { rid: this.rid });
+  }
+
+  async return(value?: ConsoleSize): Promise<IteratorResult<ConsoleSize>> {
+    close(this.rid);
+    return { value, done: true };
+  }
+
+  [Symbol.asyncIterator](): AsyncIterableIterator<ConsoleSize> {
+    return this;
+  }
+}
+
+/** Yields the new size of the terminal behind `rid` every time it is
+ * resized. Throws if `rid` is not a terminal.
+ *
+ *       for await (const { columns } of Deno.watchConsoleSize(rid)) {
+ *         progressBar.setWidth(columns);
+ *       }
+ */
+export function watchConsoleSize(
+  rid: number
+): AsyncIterableIterator<ConsoleSize> {
+  return new ConsoleResizeEvents(rid);
+}
@@ -0,0 +1,87 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { test, testPerm, assert, assertEquals } from "./test_util.ts";
+
+test(function isattyStdio(): void {
+  const isTTY = Deno.isTTY();
+  assertEquals(Deno.isatty(Deno.stdin.rid), isTTY.stdin);
+  assertEquals(Deno.isatty(Deno.stdout.rid), isTTY.stdout);
+  assertEquals(Deno.isatty(Deno.stderr.rid), isTTY.stderr);
+});
+
+testPerm({ read: true }, function isattyFile(): void {
+  const file = Deno.openSync("tests/hello.txt");
+  assert(!Deno.isatty(file.rid));
+  file.close();
+});
+
+test(function isattyBadResource(): void {
+  let err;
+  try {
+    Deno.isatty(1e6);
+  } catch (e) {
+    err = e;
+  }
+  assert(!!err);
+  assertEquals(err.kind, Deno.ErrorKind.BadResource);
+});
+
+test(function consoleSizeStdout(): void {
+  if (!Deno.isatty(Deno.stdout.rid)) {
+    return;
+  }
+  const { columns, rows } = Deno.consoleSize(Deno.stdout.rid);
+  assert(columns > 0);
+  assert(rows > 0);
+});
+
+testPerm({ read: true }, function consoleSizeNotTty(): void {
+  const file = Deno.openSync("tests/hello.txt");
+  let err;
+  try {
+    Deno.consoleSize(file.rid);
+  } catch (e) {
+    err = e;
+  }
+  file.close();
+  assert(!!err);
+});
+
+testPerm({ read: true }, function watchConsoleSizeNotTty(): void {
+  const file = Deno.openSync("tests/hello.txt");
+  let err;
+  try {
+    Deno.watchConsoleSize(file.rid);
+  } catch (e) {
+    err = e;
+  }
+  file.close();
+  assert(!!err);
+});
+
+test(function setRawNotTty(): void {
+  if (Deno.isatty(Deno.stdin.rid)) {
+    return;
+  }
+  let err;
+  try {
+    Deno.setRaw(Deno.stdin.rid, true);
+  } catch (e) {
+    err = e;
+  }
+  assert(!!err);
+  // Leaving raw mode when not in it is a no-op.
+  Deno.setRaw(Deno.stdin.rid, false);
+});
+
+testPerm({ read: true }, function setRawNotStdin(): void {
+  const file = Deno.openSync("tests/hello.txt");
+  let err;
+  try {
+    Deno.setRaw(file.rid, true);
+  } catch (e) {
+    err = e;
+  }
+  file.close();
+  assert(!!err);
+  assertEquals(err.kind, Deno.ErrorKind.InvalidInput);
+});
@@ -0,0 +1,19 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { sendSync } from "./dispatch_json.ts";
+import * as dispatch from "./dispatch.ts";
+
+/** Sets the process umask to `mask` and returns the previous value. When
+ * `mask` is omitted the current umask is returned unchanged. The umask is
+ * process wide, so it also affects files created by other workers. Unix only
+ * at the moment, throws `OpNotAvailable` on Windows.
+ *
+ * Other than on Linux, reading the umask sets it to 0 for a moment, so files
+ * created by other workers at the same time ignore it.
+ *
+ *       const previous = Deno.umask(0o002);
+ *       Deno.openSync("shared.txt", "w"); // created as rw-rw-r--
+ *       Deno.umask(previous);
+ */
+export function umask(mask?: number): number {
+  return sendSync(dispatch.OP_UMASK, { mask });
+}
@@ -0,0 +1,28 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { test, testPerm, assertEquals } from "./test_util.ts";
+
+const isNotWindows = Deno.build.os !== "win";
+
+if (isNotWindows) {
+  test(function umaskSuccess(): void {
+    const prevMask = Deno.umask(0o020);
+    const newMask = Deno.umask(prevMask);
+    const finalMask = Deno.umask();
+    assertEquals(newMask, 0o020);
+    assertEquals(finalMask, prevMask);
+  });
+
+  testPerm(
+    { read: true, write: true },
+    function umaskAppliesToNewFiles(): void {
+      const prevMask = Deno.umask(0o002);
+      try {
+        const filename = Deno.makeTempDirSync() + "/test.txt";
+        Deno.openSync(filename, "w").close();
+        assertEquals(Deno.statSync(filename).mode! & 0o777, 0o664);
+      } finally {
+        Deno.umask(prevMask);
+      }
+    }
+  );
+}
@@ -3,6 +3,7 @@
 // This test is executed as part of tools/test.py
 // But it can also be run manually: ./target/debug/deno cli/js/unit_tests.ts
 
+import "./batch_test.ts";
 import "./blob_test.ts";
 import "./body_test.ts";
 import "./buffer_test.ts";
@@ -11,7 +12,9 @@ import "./chmod_test.ts";
 import "./chown_test.ts";
 import "./console_test.ts";
 import "./copy_file_test.ts";
+import "./create_pipe_test.ts";
 import "./custom_event_test.ts";
+import "./custom_ops_test.ts";
 import "./dir_test.ts";
 import "./dispatch_json_test.ts";
 import "./error_stack_test.ts";
@@ -21,7 +24,11 @@ import "./fetch_test.ts";
 import "./file_test.ts";
 import "./files_test.ts";
 import "./form_data_test.ts";
+import "./flock_test.ts";
+import "./fs_events_test.ts";
+import "./fsync_test.ts";
 import "./get_random_values_test.ts";
+import "./glob_test.ts";
 import "./globals_test.ts";
 import "./headers_test.ts";
 import "./link_test.ts";
@@ -30,27 +37,38 @@ import "./make_temp_dir_test.ts";
 import "./metrics_test.ts";
 import "./mixins/dom_iterable_test.ts";
 import "./mkdir_test.ts";
+import "./mmap_test.ts";
 import "./net_test.ts";
 import "./os_test.ts";
 import "./process_test.ts";
 import "./read_dir_test.ts";
 import "./read_file_test.ts";
 import "./read_link_test.ts";
+import "./realpath_test.ts";
 import "./rename_test.ts";
 import "./request_test.ts";
 import "./resources_test.ts";
+import "./signals_test.ts";
 import "./stat_test.ts";
+import "./statfs_test.ts";
 import "./symlink_test.ts";
+import "./tee_test.ts";
 import "./text_encoding_test.ts";
 import "./timers_test.ts";
 import "./tls_test.ts";
+import "./transfer_test.ts";
 import "./truncate_test.ts";
+import "./tty_test.ts";
+import "./umask_test.ts";
 import "./url_test.ts";
 import "./url_search_params_test.ts";
 import "./utime_test.ts";
+import "./wasi_test.ts";
 import "./write_file_test.ts";
+import "./xattr_test.ts";
 import "./performance_test.ts";
 import "./permissions_test.ts";
+import "./plugins_test.ts";
 import "./version_test.ts";
 
 import "../../website/app_test.ts";
@@ -1,6 +1,6 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
 import { sendSync, sendAsync } from "./dispatch_json.ts";
-import { OP_UTIME } from "./dispatch.ts";
+import { OP_UTIME, OP_FUTIME } from "./dispatch.ts";
 
 function toSecondsFromEpoch(v: number | Date): number {
   return v instanceof Date ? v.valueOf() / 1000 : v;
@@ -43,3 +43,40 @@ export async function utime(
     mtime: toSecondsFromEpoch(mtime)
   });
 }
+
+/** Synchronously changes the access and modification times of the already
+ * open file `rid`. Given times are either in seconds (Unix epoch time) or as
+ * `Date` objects.
+ *
+ *       const file = Deno.openSync("myfile.txt", "r+");
+ *       Deno.futimeSync(file.rid, 1556495550, new Date());
+ */
+export function futimeSync(
+  rid: number,
+  atime: number | Date,
+  mtime: number | Date
+): void {
+  sendSync(OP_FUTIME, {
+    rid,
+    atime: toSecondsFromEpoch(atime),
+    mtime: toSecondsFromEpoch(mtime)
+  });
+}
+
+/** Changes the access and modification times of the already open file `rid`.
+ * Given times are either in seconds (Unix epoch time) or as `Date` objects.
+ *
+ *       const file = await Deno.open("myfile.txt", "r+");
+ *       await Deno.futime(file.rid, 1556495550, new Date());
+ */
+export async function futime(
+  rid: number,
+  atime: number | Date,
+  mtime: number | Date
+): Promise<void> {
+  await sendAsync(OP_FUTIME, {
+    rid,
+    atime: toSecondsFromEpoch(atime),
+    mtime: toSecondsFromEpoch(mtime)
+  });
+}
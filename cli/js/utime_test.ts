@@ -179,3 +179,64 @@ testPerm({ read: true, write: false }, async function utimeSyncPerm(): Promise<
   }
   assert(caughtError);
 });
+
+testPerm({ read: true, write: true }, function futimeSyncSuccess(): void {
+  const testDir = Deno.makeTempDirSync();
+  const filename = testDir + "/file.txt";
+  Deno.writeFileSync(filename, new TextEncoder().encode("hello"));
+  const file = Deno.openSync(filename, "r+");
+
+  const atime = 1000;
+  const mtime = 50000;
+  Deno.futimeSync(file.rid, atime, mtime);
+  file.close();
+
+  const fileInfo = Deno.statSync(filename);
+  assertFuzzyTimestampEquals(fileInfo.accessed, atime);
+  assertFuzzyTimestampEquals(fileInfo.modified, mtime);
+});
+
+testPerm({ read: true, write: true }, async function futimeSuccess(): Promise<
+  void
+> {
+  const testDir = Deno.makeTempDirSync();
+  const filename = testDir + "/file.txt";
+  Deno.writeFileSync(filename, new TextEncoder().encode("hello"));
+  const file = await Deno.open(filename, "r+");
+
+  const atime = 1000;
+  const mtime = new Date(50000 * 1000);
+  await Deno.futime(file.rid, atime, mtime);
+  file.close();
+
+  const fileInfo = Deno.statSync(filename);
+  assertFuzzyTimestampEquals(fileInfo.accessed, atime);
+  assertFuzzyTimestampEquals(fileInfo.modified, 50000);
+});
+
+testPerm({ read: true, write: true }, function futimeSyncReadOnly(): void {
+  const filename = Deno.makeTempDirSync() + "/file.txt";
+  Deno.writeFileSync(filename, new TextEncoder().encode("hello"));
+  const file = Deno.openSync(filename, "r");
+
+  let caughtError = false;
+  try {
+    Deno.futimeSync(file.rid, 1000, 50000);
+  } catch (e) {
+    caughtError = true;
+    assertEquals(e.kind, Deno.ErrorKind.PermissionDenied);
+  }
+  assert(caughtError);
+  file.close();
+});
+
+testPerm({ write: true }, function futimeSyncBadResource(): void {
+  let caughtError = false;
+  try {
+    Deno.futimeSync(123456, 1000, 1000);
+  } catch (e) {
+    caughtError = true;
+    assertEquals(e.kind, Deno.ErrorKind.BadResource);
+  }
+  assert(caughtError);
+});
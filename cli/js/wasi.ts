@@ -0,0 +1,781 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { close, openSync, readSync, seekSync, writeSync } from "./files.ts";
+import { EOF, SeekMode } from "./io.ts";
+import { DenoError, ErrorKind } from "./errors.ts";
+import { env } from "./os.ts";
+import { fstatSync, lstatSync, statSync } from "./stat.ts";
+import { FileInfo } from "./file_info.ts";
+import { fsyncSync, fdatasyncSync } from "./fsync.ts";
+import { mkdirSync } from "./mkdir.ts";
+import { removeSync } from "./remove.ts";
+import { renameSync } from "./rename.ts";
+import { getRandomValues } from "./get_random_values.ts";
+import * as dispatch from "./dispatch.ts";
+import { sendSync } from "./dispatch_json.ts";
+
+// The errno values of wasi_snapshot_preview1.
+const ESUCCESS = 0;
+const EACCES = 2;
+const EBADF = 8;
+const EEXIST = 20;
+const EINVAL = 28;
+const EIO = 29;
+const EISDIR = 31;
+const ENOENT = 44;
+const ENOSYS = 52;
+const ENOTDIR = 54;
+const ENOTEMPTY = 55;
+const ENOTCAPABLE = 76;
+
+const CLOCK_REALTIME = 0;
+const CLOCK_MONOTONIC = 1;
+const CLOCK_PROCESS_CPUTIME_ID = 2;
+const CLOCK_THREAD_CPUTIME_ID = 3;
+
+const FILETYPE_UNKNOWN = 0;
+const FILETYPE_CHARACTER_DEVICE = 2;
+const FILETYPE_DIRECTORY = 3;
+const FILETYPE_REGULAR_FILE = 4;
+const FILETYPE_SYMBOLIC_LINK = 7;
+
+const FDFLAGS_APPEND = 0x0001;
+
+const LOOKUPFLAGS_SYMLINK_FOLLOW = 0x0001;
+
+const OFLAGS_CREAT = 0x0001;
+const OFLAGS_DIRECTORY = 0x0002;
+const OFLAGS_EXCL = 0x0004;
+const OFLAGS_TRUNC = 0x0008;
+
+const RIGHTS_FD_READ = BigInt(0x0000000000000002);
+const RIGHTS_FD_WRITE = BigInt(0x0000000000000040);
+const RIGHTS_ALL = BigInt("0xffffffffffffffff");
+
+const PREOPENTYPE_DIR = 0;
+
+const WHENCE_SET = 0;
+const WHENCE_CUR = 1;
+const WHENCE_END = 2;
+
+const encoder = new TextEncoder();
+const decoder = new TextDecoder();
+
+/** Thrown by `proc_exit` to unwind the program. */
+class ExitStatus {
+  constructor(readonly code: number) {}
+}
+
+interface FileDescriptor {
+  /** The resource of an open file or stdio. Directories don't have one. */
+  rid?: number;
+  /** The path on the host of a file or directory. */
+  path?: string;
+  /** The path the program sees a preopened directory as. */
+  preopen?: string;
+  /** The type of the file, as a filetype of WASI. */
+  type: number;
+}
+
+export interface WasiOptions {
+  /** The arguments of the program, including its name. */
+  args?: string[];
+  /** The environment of the program. Defaults to the one of Deno, which
+   * requires the `allow-env` permission once the program reads it. */
+  env?: { [key: string]: string };
+  /** Directories the program may open files in, as a map from the path the
+   * program sees to the path on the host. */
+  preopens?: { [path: string]: string };
+}
+
+function errno(e: unknown): number {
+  if (e instanceof ExitStatus) {
+    throw e;
+  }
+  if (!(e instanceof DenoError)) {
+    return EIO;
+  }
+  switch (e.kind) {
+    case ErrorKind.NotFound:
+      return ENOENT;
+    case ErrorKind.PermissionDenied:
+      return EACCES;
+    case ErrorKind.AlreadyExists:
+      return EEXIST;
+    case ErrorKind.BadResource:
+      return EBADF;
+    case ErrorKind.InvalidInput:
+    case ErrorKind.InvalidSeekMode:
+      return EINVAL;
+    default:
+      return EIO;
+  }
+}
+
+function fileType(info: FileInfo): number {
+  if (info.isFile()) {
+    return FILETYPE_REGULAR_FILE;
+  } else if (info.isDirectory()) {
+    return FILETYPE_DIRECTORY;
+  } else if (info.isSymlink()) {
+    return FILETYPE_SYMBOLIC_LINK;
+  }
+  return FILETYPE_UNKNOWN;
+}
+
+/** Joins `path` to `dir`, unless it's absolute or goes above `dir`. */
+function resolvePath(dir: string, path: string): string | null {
+  if (path.startsWith("/")) {
+    return null;
+  }
+  const parts: string[] = [];
+  for (const part of path.split("/")) {
+    if (part === "..") {
+      if (parts.length === 0) {
+        return null;
+      }
+      parts.pop();
+    } else if (part !== "" && part !== ".") {
+      parts.push(part);
+    }
+  }
+  return parts.length === 0 ? dir : `${dir}/${parts.join("/")}`;
+}
+
+/** Runs WebAssembly programs built for WASI, the WebAssembly System
+ * Interface. Its `wasi_snapshot_preview1` functions are implemented with
+ * the APIs of Deno, so the program needs the same permissions as a script
+ * reading files, the environment, and so on would.
+ *
+ *       const wasi = new Deno.Wasi({ args: Deno.args });
+ *       const bytes = Deno.readFileSync("program.wasm");
+ *       const module = new WebAssembly.Module(bytes);
+ *       const instance = new WebAssembly.Instance(module, wasi.imports);
+ *       Deno.exit(wasi.start(instance));
+ */
+export class Wasi {
+  /** The imports to instantiate the program with. */
+  readonly imports: {
+    // eslint-disable-next-line @typescript-eslint/no-explicit-any
+    wasi_snapshot_preview1: { [name: string]: (...args: any[]) => number };
+  };
+
+  private memory?: WebAssembly.Memory;
+  private readonly args: string[];
+  private readonly env?: { [key: string]: string };
+  private readonly fds: Array<FileDescriptor | undefined> = [
+    { rid: 0, type: FILETYPE_CHARACTER_DEVICE },
+    { rid: 1, type: FILETYPE_CHARACTER_DEVICE },
+    { rid: 2, type: FILETYPE_CHARACTER_DEVICE }
+  ];
+
+  constructor(options: WasiOptions = {}) {
+    this.args = options.args || [];
+    this.env = options.env;
+    const preopens = options.preopens || {};
+    for (const [preopen, path] of Object.entries(preopens)) {
+      this.fds.push({ path, preopen, type: FILETYPE_DIRECTORY });
+    }
+
+    // eslint-disable-next-line @typescript-eslint/no-explicit-any
+    const functions: { [name: string]: (...args: any[]) => number } = {
+      args_get: this.argsGet,
+      args_sizes_get: this.argsSizesGet,
+      environ_get: this.environGet,
+      environ_sizes_get: this.environSizesGet,
+      clock_res_get: this.clockResGet,
+      clock_time_get: this.clockTimeGet,
+      fd_close: this.fdClose,
+      fd_datasync: this.fdDatasync,
+      fd_fdstat_get: this.fdFdstatGet,
+      fd_filestat_get: this.fdFilestatGet,
+      fd_prestat_get: this.fdPrestatGet,
+      fd_prestat_dir_name: this.fdPrestatDirName,
+      fd_read: this.fdRead,
+      fd_seek: this.fdSeek,
+      fd_sync: this.fdSync,
+      fd_tell: this.fdTell,
+      fd_write: this.fdWrite,
+      path_create_directory: this.pathCreateDirectory,
+      path_filestat_get: this.pathFilestatGet,
+      path_open: this.pathOpen,
+      path_remove_directory: this.pathRemoveDirectory,
+      path_rename: this.pathRename,
+      path_unlink_file: this.pathUnlinkFile,
+      proc_exit: this.procExit,
+      random_get: this.randomGet,
+      sched_yield: (): number => ESUCCESS
+    };
+    const unsupported = [
+      "fd_advise",
+      "fd_allocate",
+      "fd_fdstat_set_flags",
+      "fd_fdstat_set_rights",
+      "fd_filestat_set_size",
+      "fd_filestat_set_times",
+      "fd_pread",
+      "fd_pwrite",
+      "fd_readdir",
+      "fd_renumber",
+      "path_filestat_set_times",
+      "path_link",
+      "path_readlink",
+      "path_symlink",
+      "poll_oneoff",
+      "proc_raise",
+      "sock_recv",
+      "sock_send",
+      "sock_shutdown"
+    ];
+
+    this.imports = { wasi_snapshot_preview1: {} };
+    for (const [name, fn] of Object.entries(functions)) {
+      this.imports.wasi_snapshot_preview1[name] = fn.bind(this);
+    }
+    for (const name of unsupported) {
+      this.imports.wasi_snapshot_preview1[name] = (): number => ENOSYS;
+    }
+  }
+
+  /** Runs the `_start` function of a program instantiated with `imports`,
+   * and returns its exit code. */
+  start(instance: WebAssembly.Instance): number {
+    const exports = instance.exports as {
+      memory: WebAssembly.Memory;
+      _start: () => void;
+    };
+    this.memory = exports.memory;
+    try {
+      exports._start();
+    } catch (e) {
+      if (e instanceof ExitStatus) {
+        return e.code;
+      }
+      throw e;
+    }
+    return 0;
+  }
+
+  private view(): DataView {
+    return new DataView(this.memory!.buffer);
+  }
+
+  private bytes(ptr: number, len: number): Uint8Array {
+    return new Uint8Array(this.memory!.buffer, ptr, len);
+  }
+
+  private string(ptr: number, len: number): string {
+    return decoder.decode(this.bytes(ptr, len));
+  }
+
+  private fd(fd: number): FileDescriptor | undefined {
+    return this.fds[fd];
+  }
+
+  /** Resolves `path` against the directory `fd`. */
+  private path(fd: number, ptr: number, len: number): string | number {
+    const dir = this.fd(fd);
+    if (!dir) {
+      return EBADF;
+    }
+    if (dir.path === undefined) {
+      return ENOTDIR;
+    }
+    const path = resolvePath(dir.path, this.string(ptr, len));
+    return path === null ? ENOTCAPABLE : path;
+  }
+
+  /** Writes `strings` as NUL-terminated strings, and pointers to them. */
+  private writeStrings(strings: string[], ptrs: number, buf: number): void {
+    const view = this.view();
+    for (const s of strings) {
+      view.setUint32(ptrs, buf, true);
+      ptrs += 4;
+      const bytes = encoder.encode(`${s}\0`);
+      this.bytes(buf, bytes.length).set(bytes);
+      buf += bytes.length;
+    }
+  }
+
+  private writeSizes(
+    strings: string[],
+    countPtr: number,
+    sizePtr: number
+  ): void {
+    const view = this.view();
+    view.setUint32(countPtr, strings.length, true);
+    const size = strings.reduce(
+      (size, s): number => size + encoder.encode(s).length + 1,
+      0
+    );
+    view.setUint32(sizePtr, size, true);
+  }
+
+  private environ(): string[] {
+    const vars = this.env || env();
+    return Object.entries(vars).map(([k, v]): string => `${k}=${v}`);
+  }
+
+  private argsGet(argvPtr: number, argvBufPtr: number): number {
+    this.writeStrings(this.args, argvPtr, argvBufPtr);
+    return ESUCCESS;
+  }
+
+  private argsSizesGet(argcPtr: number, argvBufSizePtr: number): number {
+    this.writeSizes(this.args, argcPtr, argvBufSizePtr);
+    return ESUCCESS;
+  }
+
+  private environGet(environPtr: number, environBufPtr: number): number {
+    try {
+      this.writeStrings(this.environ(), environPtr, environBufPtr);
+      return ESUCCESS;
+    } catch (e) {
+      return errno(e);
+    }
+  }
+
+  private environSizesGet(countPtr: number, bufSizePtr: number): number {
+    try {
+      this.writeSizes(this.environ(), countPtr, bufSizePtr);
+      return ESUCCESS;
+    } catch (e) {
+      return errno(e);
+    }
+  }
+
+  private clockResGet(id: number, resolutionPtr: number): number {
+    switch (id) {
+      case CLOCK_REALTIME:
+        this.view().setBigUint64(resolutionPtr, BigInt(1e6), true);
+        return ESUCCESS;
+      case CLOCK_MONOTONIC:
+      case CLOCK_PROCESS_CPUTIME_ID:
+      case CLOCK_THREAD_CPUTIME_ID:
+        this.view().setBigUint64(resolutionPtr, BigInt(1e3), true);
+        return ESUCCESS;
+      default:
+        return EINVAL;
+    }
+  }
+
+  private clockTimeGet(
+    id: number,
+    _precision: bigint,
+    timePtr: number
+  ): number {
+    let time: bigint;
+    switch (id) {
+      case CLOCK_REALTIME:
+        time = BigInt(Date.now()) * BigInt(1e6);
+        break;
+      case CLOCK_MONOTONIC:
+      case CLOCK_PROCESS_CPUTIME_ID:
+      case CLOCK_THREAD_CPUTIME_ID: {
+        // Like performance.now(), only precise to 2ms without --allow-hrtime.
+        const now = sendSync(dispatch.OP_NOW);
+        time = BigInt(now.seconds) * BigInt(1e9) + BigInt(now.subsecNanos);
+        break;
+      }
+      default:
+        return EINVAL;
+    }
+    this.view().setBigUint64(timePtr, time, true);
+    return ESUCCESS;
+  }
+
+  private fdClose(fd: number): number {
+    const entry = this.fd(fd);
+    if (!entry) {
+      return EBADF;
+    }
+    try {
+      if (entry.rid !== undefined) {
+        close(entry.rid);
+      }
+      this.fds[fd] = undefined;
+      return ESUCCESS;
+    } catch (e) {
+      return errno(e);
+    }
+  }
+
+  private fdDatasync(fd: number): number {
+    const entry = this.fd(fd);
+    if (!entry || entry.rid === undefined) {
+      return EBADF;
+    }
+    try {
+      fdatasyncSync(entry.rid);
+      return ESUCCESS;
+    } catch (e) {
+      return errno(e);
+    }
+  }
+
+  private fdSync(fd: number): number {
+    const entry = this.fd(fd);
+    if (!entry || entry.rid === undefined) {
+      return EBADF;
+    }
+    try {
+      fsyncSync(entry.rid);
+      return ESUCCESS;
+    } catch (e) {
+      return errno(e);
+    }
+  }
+
+  private fdFdstatGet(fd: number, statPtr: number): number {
+    const entry = this.fd(fd);
+    if (!entry) {
+      return EBADF;
+    }
+    // Deno checks access when an op runs, so every right is granted here.
+    const view = this.view();
+    view.setUint8(statPtr, entry.type);
+    view.setUint16(statPtr + 2, 0, true);
+    view.setBigUint64(statPtr + 8, RIGHTS_ALL, true);
+    view.setBigUint64(statPtr + 16, RIGHTS_ALL, true);
+    return ESUCCESS;
+  }
+
+  private writeFilestat(info: FileInfo, ptr: number): void {
+    const view = this.view();
+    const ns = (ms: number | null): bigint => BigInt(ms || 0) * BigInt(1e6);
+    view.setBigUint64(ptr, BigInt(info.dev || 0), true);
+    view.setBigUint64(ptr + 8, BigInt(info.ino || 0), true);
+    view.setUint8(ptr + 16, fileType(info));
+    view.setBigUint64(ptr + 24, BigInt(info.nlink || 0), true);
+    view.setBigUint64(ptr + 32, BigInt(info.len), true);
+    view.setBigUint64(ptr + 40, ns(info.accessed), true);
+    view.setBigUint64(ptr + 48, ns(info.modified), true);
+    view.setBigUint64(ptr + 56, ns(info.created), true);
+  }
+
+  private fdFilestatGet(fd: number, statPtr: number): number {
+    const entry = this.fd(fd);
+    if (!entry) {
+      return EBADF;
+    }
+    try {
+      const info =
+        entry.rid !== undefined
+          ? fstatSync(entry.rid)
+          : statSync(entry.path!);
+      this.writeFilestat(info, statPtr);
+      return ESUCCESS;
+    } catch (e) {
+      return errno(e);
+    }
+  }
+
+  private fdPrestatGet(fd: number, prestatPtr: number): number {
+    const entry = this.fd(fd);
+    if (!entry || entry.preopen === undefined) {
+      return EBADF;
+    }
+    const view = this.view();
+    view.setUint8(prestatPtr, PREOPENTYPE_DIR);
+    view.setUint32(prestatPtr + 4, encoder.encode(entry.preopen).length, true);
+    return ESUCCESS;
+  }
+
+  private fdPrestatDirName(fd: number, pathPtr: number, len: number): number {
+    const entry = this.fd(fd);
+    if (!entry || entry.preopen === undefined) {
+      return EBADF;
+    }
+    const preopen = encoder.encode(entry.preopen);
+    this.bytes(pathPtr, len).set(preopen.subarray(0, len));
+    return ESUCCESS;
+  }
+
+  /** Calls `f` with each buffer of an iovec array, and writes the total number
+   * of bytes it returned. */
+  private iovecs(
+    iovsPtr: number,
+    iovsLen: number,
+    nPtr: number,
+    f: (buf: Uint8Array) => number | EOF
+  ): void {
+    const view = this.view();
+    let total = 0;
+    for (let i = 0; i < iovsLen; i++) {
+      const ptr = view.getUint32(iovsPtr + i * 8, true);
+      const len = view.getUint32(iovsPtr + i * 8 + 4, true);
+      const n = f(this.bytes(ptr, len));
+      if (n === EOF) {
+        break;
+      }
+      total += n;
+      if (n < len) {
+        break;
+      }
+    }
+    view.setUint32(nPtr, total, true);
+  }
+
+  private fdRead(
+    fd: number,
+    iovsPtr: number,
+    iovsLen: number,
+    nreadPtr: number
+  ): number {
+    const entry = this.fd(fd);
+    if (!entry) {
+      return EBADF;
+    }
+    if (entry.rid === undefined) {
+      return EISDIR;
+    }
+    const rid = entry.rid;
+    try {
+      this.iovecs(iovsPtr, iovsLen, nreadPtr, (buf): number | EOF =>
+        readSync(rid, buf)
+      );
+      return ESUCCESS;
+    } catch (e) {
+      return errno(e);
+    }
+  }
+
+  private fdWrite(
+    fd: number,
+    iovsPtr: number,
+    iovsLen: number,
+    nwrittenPtr: number
+  ): number {
+    const entry = this.fd(fd);
+    if (!entry) {
+      return EBADF;
+    }
+    if (entry.rid === undefined) {
+      return EISDIR;
+    }
+    const rid = entry.rid;
+    try {
+      this.iovecs(iovsPtr, iovsLen, nwrittenPtr, (buf): number =>
+        writeSync(rid, buf)
+      );
+      return ESUCCESS;
+    } catch (e) {
+      return errno(e);
+    }
+  }
+
+  private fdSeek(
+    fd: number,
+    offset: bigint,
+    whence: number,
+    newOffsetPtr: number
+  ): number {
+    const entry = this.fd(fd);
+    if (!entry || entry.rid === undefined) {
+      return EBADF;
+    }
+    let mode: SeekMode;
+    switch (whence) {
+      case WHENCE_SET:
+        mode = SeekMode.SEEK_START;
+        break;
+      case WHENCE_CUR:
+        mode = SeekMode.SEEK_CURRENT;
+        break;
+      case WHENCE_END:
+        mode = SeekMode.SEEK_END;
+        break;
+      default:
+        return EINVAL;
+    }
+    try {
+      const newOffset = seekSync(entry.rid, Number(offset), mode);
+      this.view().setBigUint64(newOffsetPtr, BigInt(newOffset), true);
+      return ESUCCESS;
+    } catch (e) {
+      return errno(e);
+    }
+  }
+
+  private fdTell(fd: number, offsetPtr: number): number {
+    const entry = this.fd(fd);
+    if (!entry || entry.rid === undefined) {
+      return EBADF;
+    }
+    try {
+      const offset = seekSync(entry.rid, 0, SeekMode.SEEK_CURRENT);
+      this.view().setBigUint64(offsetPtr, BigInt(offset), true);
+      return ESUCCESS;
+    } catch (e) {
+      return errno(e);
+    }
+  }
+
+  private pathCreateDirectory(
+    fd: number,
+    pathPtr: number,
+    len: number
+  ): number {
+    const path = this.path(fd, pathPtr, len);
+    if (typeof path === "number") {
+      return path;
+    }
+    try {
+      mkdirSync(path);
+      return ESUCCESS;
+    } catch (e) {
+      return errno(e);
+    }
+  }
+
+  private pathFilestatGet(
+    fd: number,
+    flags: number,
+    pathPtr: number,
+    len: number,
+    statPtr: number
+  ): number {
+    const path = this.path(fd, pathPtr, len);
+    if (typeof path === "number") {
+      return path;
+    }
+    try {
+      const info =
+        flags & LOOKUPFLAGS_SYMLINK_FOLLOW ? statSync(path) : lstatSync(path);
+      this.writeFilestat(info, statPtr);
+      return ESUCCESS;
+    } catch (e) {
+      return errno(e);
+    }
+  }
+
+  private pathOpen(
+    fd: number,
+    _dirflags: number,
+    pathPtr: number,
+    len: number,
+    oflags: number,
+    rightsBase: bigint,
+    _rightsInheriting: bigint,
+    fdflags: number,
+    fdPtr: number
+  ): number {
+    const path = this.path(fd, pathPtr, len);
+    if (typeof path === "number") {
+      return path;
+    }
+    try {
+      let info: FileInfo | null = null;
+      try {
+        info = statSync(path);
+      } catch (e) {
+        const notFound =
+          e instanceof DenoError && e.kind === ErrorKind.NotFound;
+        if (!notFound || !(oflags & OFLAGS_CREAT)) {
+          throw e;
+        }
+      }
+      let entry: FileDescriptor;
+      if (info && info.isDirectory()) {
+        entry = { path, type: FILETYPE_DIRECTORY };
+      } else if (oflags & OFLAGS_DIRECTORY) {
+        return ENOTDIR;
+      } else {
+        const append = (fdflags & FDFLAGS_APPEND) !== 0;
+        const write =
+          (rightsBase & RIGHTS_FD_WRITE) !== BigInt(0) ||
+          (oflags & (OFLAGS_CREAT | OFLAGS_TRUNC)) !== 0;
+        const read = (rightsBase & RIGHTS_FD_READ) !== BigInt(0) || !write;
+        const file = openSync(path, {
+          read,
+          write: write && !append,
+          append,
+          truncate: (oflags & OFLAGS_TRUNC) !== 0,
+          create: (oflags & OFLAGS_CREAT) !== 0,
+          createNew: (oflags & OFLAGS_EXCL) !== 0
+        });
+        const type = info ? fileType(info) : FILETYPE_REGULAR_FILE;
+        entry = { rid: file.rid, path, type };
+      }
+      this.fds.push(entry);
+      this.view().setUint32(fdPtr, this.fds.length - 1, true);
+      return ESUCCESS;
+    } catch (e) {
+      return errno(e);
+    }
+  }
+
+  private pathRemoveDirectory(
+    fd: number,
+    pathPtr: number,
+    len: number
+  ): number {
+    const path = this.path(fd, pathPtr, len);
+    if (typeof path === "number") {
+      return path;
+    }
+    try {
+      if (!lstatSync(path).isDirectory()) {
+        return ENOTDIR;
+      }
+      removeSync(path);
+      return ESUCCESS;
+    } catch (e) {
+      // Removing a directory which isn't empty fails with an OS error.
+      return e instanceof DenoError && e.kind === ErrorKind.Other
+        ? ENOTEMPTY
+        : errno(e);
+    }
+  }
+
+  private pathRename(
+    fd: number,
+    oldPathPtr: number,
+    oldLen: number,
+    newFd: number,
+    newPathPtr: number,
+    newLen: number
+  ): number {
+    const oldPath = this.path(fd, oldPathPtr, oldLen);
+    if (typeof oldPath === "number") {
+      return oldPath;
+    }
+    const newPath = this.path(newFd, newPathPtr, newLen);
+    if (typeof newPath === "number") {
+      return newPath;
+    }
+    try {
+      renameSync(oldPath, newPath);
+      return ESUCCESS;
+    } catch (e) {
+      return errno(e);
+    }
+  }
+
+  private pathUnlinkFile(fd: number, pathPtr: number, len: number): number {
+    const path = this.path(fd, pathPtr, len);
+    if (typeof path === "number") {
+      return path;
+    }
+    try {
+      if (lstatSync(path).isDirectory()) {
+        return EISDIR;
+      }
+      removeSync(path);
+      return ESUCCESS;
+    } catch (e) {
+      return errno(e);
+    }
+  }
+
+  private procExit(code: number): never {
+    throw new ExitStatus(code);
+  }
+
+  private randomGet(bufPtr: number, len: number): number {
+    // getRandomValues() fills at most 65536 bytes at a time.
+    for (let i = 0; i < len; i += 65536) {
+      getRandomValues(this.bytes(bufPtr + i, Math.min(len - i, 65536)));
+    }
+    return ESUCCESS;
+  }
+}
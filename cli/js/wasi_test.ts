@@ -0,0 +1,12 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { test, assert, assertEquals } from "./test_util.ts";
+
+test(function wasiImports(): void {
+  const wasi = new Deno.Wasi();
+  const imports = wasi.imports.wasi_snapshot_preview1;
+  for (const name of ["fd_write", "path_open", "clock_time_get"]) {
+    assert(typeof imports[name] === "function");
+  }
+  // Functions which aren't supported fail with ENOSYS.
+  assertEquals(imports.sock_recv(), 52);
+});
@@ -7,6 +7,7 @@ import { TextDecoder, TextEncoder } from "./text_encoding.ts";
 import { window } from "./window.ts";
 import { blobURLMap } from "./url.ts";
 import { blobBytesWeakMap } from "./blob.ts";
+import { Permission } from "./permissions.ts";
 
 const encoder = new TextEncoder();
 const decoder = new TextDecoder();
@@ -25,13 +26,15 @@ function createWorker(
   specifier: string,
   includeDenoNamespace: boolean,
   hasSourceCode: boolean,
-  sourceCode: Uint8Array
+  sourceCode: Uint8Array,
+  permissions?: Permission[]
 ): number {
   return sendSync(dispatch.OP_CREATE_WORKER, {
     specifier,
     includeDenoNamespace,
     hasSourceCode,
-    sourceCode: new TextDecoder().decode(sourceCode)
+    sourceCode: new TextDecoder().decode(sourceCode),
+    permissions
   });
 }
 
@@ -118,9 +121,12 @@ export interface WorkerOptions {}
 /** Extended Deno Worker initialization options.
  * `noDenoNamespace` hides global `window.Deno` namespace for
  * spawned worker and nested workers spawned by it (default: false).
+ * `permissions` lists the permissions the worker keeps, as far as the
+ * spawning worker has them; the others are denied (default: all).
  */
 export interface DenoWorkerOptions extends WorkerOptions {
   noDenoNamespace?: boolean;
+  permissions?: Permission[];
 }
 
 export class WorkerImpl implements Worker {
@@ -157,7 +163,8 @@ export class WorkerImpl implements Worker {
       specifier,
       includeDenoNamespace,
       hasSourceCode,
-      sourceCode
+      sourceCode,
+      options && options.permissions
     );
     this.run();
     this.isClosedPromise = hostGetWorkerClosed(this.rid);
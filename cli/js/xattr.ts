@@ -0,0 +1,127 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { sendSync, sendAsync } from "./dispatch_json.ts";
+import * as dispatch from "./dispatch.ts";
+
+// Extended attributes are read from a path, or from an open file given by its
+// rid.
+function target(pathOrRid: string | number): object {
+  return typeof pathOrRid === "number"
+    ? { rid: pathOrRid }
+    : { path: pathOrRid };
+}
+
+function toBytes(value: number[] | null): Uint8Array | null {
+  return value === null ? null : new Uint8Array(value);
+}
+
+/** Synchronously returns the value of the extended attribute `name` of a file,
+ * or `null` if it isn't set. The file is given either as a path, which
+ * requires `allow-read`, or as the rid of an open file. Linux and macOS only,
+ * throws `OpNotAvailable` elsewhere.
+ *
+ *       const value = Deno.getXattrSync("file.zip", "com.apple.quarantine");
+ */
+export function getXattrSync(
+  pathOrRid: string | number,
+  name: string
+): Uint8Array | null {
+  return toBytes(
+    sendSync(dispatch.OP_GET_XATTR, { ...target(pathOrRid), name })
+  );
+}
+
+/** Returns the value of the extended attribute `name` of a file, or `null` if
+ * it isn't set. The file is given either as a path, which requires
+ * `allow-read`, or as the rid of an open file. Linux and macOS only, throws
+ * `OpNotAvailable` elsewhere.
+ *
+ *       const value = await Deno.getXattr("file.zip", "com.apple.quarantine");
+ */
+export async function getXattr(
+  pathOrRid: string | number,
+  name: string
+): Promise<Uint8Array | null> {
+  return toBytes(
+    await sendAsync(dispatch.OP_GET_XATTR, { ...target(pathOrRid), name })
+  );
+}
+
+/** Synchronously sets the extended attribute `name` of a file to `value`. The
+ * file is given either as a path, which requires `allow-write`, or as the rid
+ * of an open file. Linux and macOS only, throws `OpNotAvailable` elsewhere.
+ *
+ *       Deno.setXattrSync("file.txt", "user.checksum", checksum);
+ */
+export function setXattrSync(
+  pathOrRid: string | number,
+  name: string,
+  value: Uint8Array
+): void {
+  sendSync(dispatch.OP_SET_XATTR, { ...target(pathOrRid), name }, value);
+}
+
+/** Sets the extended attribute `name` of a file to `value`. The file is given
+ * either as a path, which requires `allow-write`, or as the rid of an open
+ * file. Linux and macOS only, throws `OpNotAvailable` elsewhere.
+ *
+ *       await Deno.setXattr("file.txt", "user.checksum", checksum);
+ */
+export async function setXattr(
+  pathOrRid: string | number,
+  name: string,
+  value: Uint8Array
+): Promise<void> {
+  await sendAsync(dispatch.OP_SET_XATTR, { ...target(pathOrRid), name }, value);
+}
+
+/** Synchronously returns the names of all extended attributes of a file. The
+ * file is given either as a path, which requires `allow-read`, or as the rid
+ * of an open file. Linux and macOS only, throws `OpNotAvailable` elsewhere.
+ *
+ *       for (const name of Deno.listXattrSync("file.txt")) {
+ *         console.log(name);
+ *       }
+ */
+export function listXattrSync(pathOrRid: string | number): string[] {
+  return sendSync(dispatch.OP_LIST_XATTR, target(pathOrRid));
+}
+
+/** Returns the names of all extended attributes of a file. The file is given
+ * either as a path, which requires `allow-read`, or as the rid of an open
+ * file. Linux and macOS only, throws `OpNotAvailable` elsewhere.
+ *
+ *       for (const name of await Deno.listXattr("file.txt")) {
+ *         console.log(name);
+ *       }
+ */
+export async function listXattr(
+  pathOrRid: string | number
+): Promise<string[]> {
+  return await sendAsync(dispatch.OP_LIST_XATTR, target(pathOrRid));
+}
+
+/** Synchronously removes the extended attribute `name` of a file. The file is
+ * given either as a path, which requires `allow-write`, or as the rid of an
+ * open file. Linux and macOS only, throws `OpNotAvailable` elsewhere.
+ *
+ *       Deno.removeXattrSync("file.zip", "com.apple.quarantine");
+ */
+export function removeXattrSync(
+  pathOrRid: string | number,
+  name: string
+): void {
+  sendSync(dispatch.OP_REMOVE_XATTR, { ...target(pathOrRid), name });
+}
+
+/** Removes the extended attribute `name` of a file. The file is given either
+ * as a path, which requires `allow-write`, or as the rid of an open file.
+ * Linux and macOS only, throws `OpNotAvailable` elsewhere.
+ *
+ *       await Deno.removeXattr("file.zip", "com.apple.quarantine");
+ */
+export async function removeXattr(
+  pathOrRid: string | number,
+  name: string
+): Promise<void> {
+  await sendAsync(dispatch.OP_REMOVE_XATTR, { ...target(pathOrRid), name });
+}
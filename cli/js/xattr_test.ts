@@ -0,0 +1,115 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+import { testPerm, assert, assertEquals } from "./test_util.ts";
+
+const isSupported = Deno.build.os === "linux" || Deno.build.os === "mac";
+const name = Deno.build.os === "linux" ? "user.deno.test" : "deno.test";
+
+// Some file systems, like tmpfs on older kernels, don't support user
+// attributes at all.
+function trySetXattr(path: string, value: Uint8Array): boolean {
+  try {
+    Deno.setXattrSync(path, name, value);
+    return true;
+  } catch (e) {
+    if (e.message.includes("not supported")) {
+      return false;
+    }
+    throw e;
+  }
+}
+
+if (isSupported) {
+  testPerm({ read: true, write: true }, function xattrSyncPath(): void {
+    const path = Deno.makeTempDirSync() + "/test.txt";
+    Deno.writeFileSync(path, new Uint8Array());
+    const value = new TextEncoder().encode("hello");
+    if (!trySetXattr(path, value)) {
+      return;
+    }
+
+    assertEquals(Deno.getXattrSync(path, name), value);
+    assert(Deno.listXattrSync(path).includes(name));
+
+    Deno.removeXattrSync(path, name);
+    assertEquals(Deno.getXattrSync(path, name), null);
+    assert(!Deno.listXattrSync(path).includes(name));
+  });
+
+  testPerm({ read: true, write: true }, async function xattrRid(): Promise<
+    void
+  > {
+    const path = Deno.makeTempDirSync() + "/test.txt";
+    Deno.writeFileSync(path, new Uint8Array());
+    if (!trySetXattr(path, new Uint8Array([0]))) {
+      return;
+    }
+
+    const file = await Deno.open(path, "r+");
+    const value = new Uint8Array([1, 2, 3]);
+    await Deno.setXattr(file.rid, name, value);
+    assertEquals(await Deno.getXattr(file.rid, name), value);
+    assert((await Deno.listXattr(file.rid)).includes(name));
+    await Deno.removeXattr(file.rid, name);
+    assertEquals(await Deno.getXattr(path, name), null);
+    file.close();
+  });
+
+  testPerm({ read: true, write: true }, function xattrSyncRidReadOnly(): void {
+    const path = Deno.makeTempDirSync() + "/test.txt";
+    Deno.writeFileSync(path, new Uint8Array());
+    if (!trySetXattr(path, new Uint8Array([0]))) {
+      return;
+    }
+
+    const file = Deno.openSync(path, "r");
+    assertEquals(Deno.getXattrSync(file.rid, name), new Uint8Array([0]));
+    for (const f of [
+      (): void => Deno.setXattrSync(file.rid, name, new Uint8Array([1])),
+      (): void => Deno.removeXattrSync(file.rid, name)
+    ]) {
+      let caughtError = false;
+      try {
+        f();
+      } catch (e) {
+        caughtError = true;
+        assertEquals(e.kind, Deno.ErrorKind.PermissionDenied);
+      }
+      assert(caughtError);
+    }
+    assertEquals(Deno.getXattrSync(path, name), new Uint8Array([0]));
+    file.close();
+  });
+
+  testPerm({ read: true }, function xattrSyncWritePerm(): void {
+    let caughtError = false;
+    try {
+      Deno.setXattrSync("package.json", name, new Uint8Array([1]));
+    } catch (e) {
+      caughtError = true;
+      assertEquals(e.kind, Deno.ErrorKind.PermissionDenied);
+    }
+    assert(caughtError);
+  });
+} else {
+  testPerm({ read: true }, function xattrSyncUnsupported(): void {
+    let caughtError = false;
+    try {
+      Deno.listXattrSync("package.json");
+    } catch (e) {
+      caughtError = true;
+      assertEquals(e.kind, Deno.ErrorKind.OpNotAvailable);
+    }
+    assert(caughtError);
+  });
+}
+
+testPerm({ read: false }, function xattrSyncReadPerm(): void {
+  let caughtError = false;
+  try {
+    Deno.getXattrSync("package.json", name);
+  } catch (e) {
+    caughtError = true;
+    assertEquals(e.kind, Deno.ErrorKind.PermissionDenied);
+  }
+  assert(caughtError);
+});
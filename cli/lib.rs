@@ -23,19 +23,25 @@ pub mod deno_dir;
 pub mod deno_error;
 pub mod diagnostics;
 mod disk_cache;
+mod dotenv;
 mod file_fetcher;
 pub mod flags;
 pub mod fmt_errors;
 mod fs;
-mod global_timer;
 mod http_body;
 mod http_util;
 mod import_map;
+mod inspector;
 mod js;
 pub mod msg;
+mod op_trace;
 pub mod ops;
 pub mod permissions;
+mod permissions_manifest;
 mod progress;
+#[cfg(unix)]
+mod pty;
+mod read_pool;
 mod repl;
 pub mod resolve_addr;
 pub mod resources;
@@ -44,15 +50,27 @@ mod signal;
 pub mod source_maps;
 mod startup_data;
 pub mod state;
+mod stdio;
 pub mod test_util;
+mod timers;
+mod tokio_copy;
 mod tokio_read;
 mod tokio_util;
 mod tokio_write;
 pub mod version;
 pub mod worker;
 
+// For the build scripts of embedders, which bake their own JavaScript into
+// the startup snapshot.
+pub use deno::SnapshotBuilder;
+pub use deno_typescript::compile_bundle;
+pub use deno_typescript::snapshot_builder;
+pub use deno_typescript::SnapshotBuilderExt;
+
 use crate::deno_error::js_check;
 use crate::deno_error::print_err_and_exit;
+use crate::deno_error::DenoError;
+use crate::deno_error::ErrorKind;
 use crate::progress::Progress;
 use crate::state::ThreadSafeState;
 use crate::worker::Worker;
@@ -175,6 +193,7 @@ pub fn print_file_info(
         .map_err(|e| {
           debug!("compiler error exiting!");
           eprintln!("\n{}", e.to_string());
+          resources::cleanup();
           std::process::exit(1);
         })
         .and_then(move |compiled| {
@@ -349,6 +368,8 @@ fn run_repl(flags: DenoFlags, argv: Vec<String>) {
 
 fn run_script(flags: DenoFlags, argv: Vec<String>) {
   let use_current_thread = flags.current_thread;
+  let inspect = flags.inspect;
+  let inspect_brk = flags.inspect_brk;
   let (mut worker, state) = create_worker_and_state(flags, argv);
 
   let main_module = state.main_module().unwrap();
@@ -358,6 +379,18 @@ fn run_script(flags: DenoFlags, argv: Vec<String>) {
     js_check(worker.execute("denoMain()"));
     debug!("main_module {}", main_module);
 
+    let mut inspector_server = None;
+    if let Some(addr) = inspect {
+      let handle = worker.inspector_handle();
+      match inspector::start(addr, handle, main_module.as_str()) {
+        Ok(server) => inspector_server = Some(server),
+        Err(err) => print_err_and_exit(err),
+      }
+      if inspect_brk {
+        worker.wait_for_debugger();
+      }
+    }
+
     let mut worker_ = worker.clone();
 
     worker
@@ -369,6 +402,9 @@ fn run_script(flags: DenoFlags, argv: Vec<String>) {
           js_check(
             worker_.execute("window.dispatchEvent(new Event('unload'))"),
           );
+          resources::cleanup();
+          // Otherwise the server would keep the runtime from finishing.
+          drop(inspector_server);
           Ok(())
         })
       })
@@ -394,8 +430,20 @@ pub fn main() {
 
   log::set_logger(&LOGGER).unwrap();
   let args: Vec<String> = env::args().collect();
-  let (flags, subcommand, argv) = flags::flags_from_vec(args);
-
+  let (mut flags, subcommand, argv) = flags::flags_from_vec(args);
+
+  // WASI programs pass 64 bit integers to the functions of Deno.Wasi. Scripts
+  // which run them with Deno.Wasi pass the flag with --v8-flags.
+  let runs_wasm = subcommand == DenoSubcommand::Run
+    && argv
+      .get(1)
+      .map_or(false, |script| script.ends_with(".wasm"));
+  if runs_wasm {
+    v8_set_flags(vec![
+      "deno".to_string(),
+      "--experimental-wasm-bigint".to_string(),
+    ]);
+  }
   if let Some(ref v8_flags) = flags.v8_flags {
     v8_set_flags(v8_flags.clone());
   }
@@ -406,6 +454,59 @@ pub fn main() {
   };
   log::set_max_level(log_level.to_level_filter());
 
+  if let Some(manifest) = flags.permissions_file.clone() {
+    if let Err(err) = permissions_manifest::load(&manifest, &mut flags) {
+      print_err_and_exit(err);
+    }
+  }
+
+  // Setting variables is as much a use of the environment as reading them,
+  // so it takes the same permission, which the manifest may have granted.
+  if let Some(ref env_file) = flags.env_file {
+    if !flags.allow_env {
+      print_err_and_exit(
+        DenoError::new(
+          ErrorKind::PermissionDenied,
+          "--env requires --allow-env".to_string(),
+        )
+        .into(),
+      );
+    }
+    if let Err(err) = dotenv::load(env_file) {
+      print_err_and_exit(err);
+    }
+  }
+
+  if flags.unsafely_seed_crypto {
+    eprintln!(
+      "{}",
+      colors::yellow(
+        "DANGER: crypto.getRandomValues() is seeded and predictable"
+          .to_string()
+      )
+    );
+  }
+
+  if let Some(ref hosts) = flags.unsafely_ignore_certificate_errors {
+    let domains = if hosts.is_empty() {
+      "all hostnames".to_string()
+    } else {
+      hosts.join(", ")
+    };
+    eprintln!(
+      "{}",
+      colors::yellow(format!(
+        "DANGER: TLS certificate validation is disabled for: {}",
+        domains
+      ))
+    );
+  }
+
+  if let Some(ref path) = flags.trace_ops {
+    op_trace::start(path);
+    resources::on_cleanup(op_trace::finish);
+  }
+
   match subcommand {
     DenoSubcommand::Bundle => bundle_command(flags, argv),
     DenoSubcommand::Completions => {}
@@ -417,4 +518,5 @@ pub fn main() {
     DenoSubcommand::Types => types_command(),
     DenoSubcommand::Version => version_command(),
   }
+  resources::cleanup();
 }
@@ -0,0 +1,204 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+//! Records every op for `--trace-ops`, as events of the Chrome trace event
+//! format which chrome://tracing can open.
+use serde_json::json;
+use serde_json::Value;
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Events are written out as they are recorded, in the JSON array form of the
+/// format, which can be opened even if the closing bracket is missing, e.g.
+/// after a crash.
+struct Trace<W: Write> {
+  path: String,
+  start: Instant,
+  out: W,
+  empty: bool,
+}
+
+lazy_static! {
+  static ref TRACE: Mutex<Option<Trace<BufWriter<File>>>> = Mutex::new(None);
+  static ref ENABLED: AtomicBool = AtomicBool::new(false);
+  static ref NEXT_ASYNC_ID: AtomicUsize = AtomicUsize::new(0);
+}
+
+impl<W: Write> Trace<W> {
+  /// Starts a trace written to `out`. `path` names it in error messages.
+  fn new(path: &str, mut out: W) -> io::Result<Self> {
+    out.write_all(b"[\n")?;
+    Ok(Self {
+      path: path.to_string(),
+      start: Instant::now(),
+      out,
+      empty: true,
+    })
+  }
+
+  fn micros(&self, at: Instant) -> u64 {
+    let d = at.duration_since(self.start);
+    d.as_secs() * 1_000_000 + u64::from(d.subsec_micros())
+  }
+
+  fn record(
+    &mut self,
+    name: &str,
+    tid: u32,
+    dispatched_at: Instant,
+    is_async: bool,
+    bytes_sent: usize,
+    bytes_received: usize,
+  ) {
+    let now = Instant::now();
+    let pid = std::process::id();
+    let ts = self.micros(dispatched_at);
+    let end = self.micros(now);
+    if is_async {
+      let id = NEXT_ASYNC_ID.fetch_add(1, Ordering::SeqCst);
+      self.write_event(json!({
+        "name": name,
+        "cat": "op",
+        "ph": "b",
+        "id": id,
+        "ts": ts,
+        "pid": pid,
+        "tid": tid,
+        "args": { "bytesSent": bytes_sent },
+      }));
+      self.write_event(json!({
+        "name": name,
+        "cat": "op",
+        "ph": "e",
+        "id": id,
+        "ts": end,
+        "pid": pid,
+        "tid": tid,
+        "args": { "bytesReceived": bytes_received },
+      }));
+    } else {
+      self.write_event(json!({
+        "name": name,
+        "cat": "op",
+        "ph": "X",
+        "ts": ts,
+        "dur": end - ts,
+        "pid": pid,
+        "tid": tid,
+        "args": { "bytesSent": bytes_sent, "bytesReceived": bytes_received },
+      }));
+    }
+  }
+
+  fn write_event(&mut self, event: Value) {
+    let separator: &[u8] = if self.empty { b"" } else { b",\n" };
+    self.empty = false;
+    let r = self
+      .out
+      .write_all(separator)
+      .and_then(|_| self.out.write_all(event.to_string().as_bytes()));
+    if let Err(err) = r {
+      eprintln!("Failed to write the op trace to {}: {}", self.path, err);
+    }
+  }
+
+  /// Closes the array and flushes the trace.
+  fn finish(mut self) -> W {
+    let r = self.out.write_all(b"\n]\n").and_then(|_| self.out.flush());
+    if let Err(err) = r {
+      eprintln!("Failed to write the op trace to {}: {}", self.path, err);
+    }
+    self.out
+  }
+}
+
+/// Starts recording ops to `path`, which `finish` completes. What was
+/// recorded is also flushed when SIGINT ends the process.
+pub fn start(path: &str) {
+  let trace =
+    File::create(path).and_then(|file| Trace::new(path, BufWriter::new(file)));
+  match trace {
+    Ok(trace) => *TRACE.lock().unwrap() = Some(trace),
+    Err(err) => {
+      eprintln!("Failed to create the op trace {}: {}", path, err);
+      return;
+    }
+  }
+  ENABLED.store(true, Ordering::SeqCst);
+
+  if let Ok(signo) = crate::signal::signal_from_name("SIGINT") {
+    // Only flushes: the signal still ends the process, unless the program
+    // listens to it too.
+    let _ = crate::signal::listen(
+      signo,
+      Box::new(|| {
+        flush();
+        false
+      }),
+    );
+  }
+}
+
+pub fn is_enabled() -> bool {
+  ENABLED.load(Ordering::SeqCst)
+}
+
+/// Records an op of the worker `tid` which was dispatched at `dispatched_at`
+/// and has just completed. Sync ops are a single complete event, async ones
+/// a pair of events, since they overlap with each other.
+pub fn record(
+  name: &str,
+  tid: u32,
+  dispatched_at: Instant,
+  is_async: bool,
+  bytes_sent: usize,
+  bytes_received: usize,
+) {
+  if let Some(trace) = TRACE.lock().unwrap().as_mut() {
+    trace.record(
+      name,
+      tid,
+      dispatched_at,
+      is_async,
+      bytes_sent,
+      bytes_received,
+    );
+  }
+}
+
+/// Writes out what is buffered of the trace, if ops are being recorded.
+fn flush() {
+  if let Some(trace) = TRACE.lock().unwrap().as_mut() {
+    let _ = trace.out.flush();
+  }
+}
+
+/// Completes the trace file and stops recording. Does nothing unless `start`
+/// was called.
+pub fn finish() {
+  ENABLED.store(false, Ordering::SeqCst);
+  if let Some(trace) = TRACE.lock().unwrap().take() {
+    trace.finish();
+  }
+}
+
+#[test]
+fn test_record() {
+  let mut trace = Trace::new("test", Vec::new()).unwrap();
+  trace.record("read", 3, Instant::now(), false, 12, 12);
+  trace.record("accept", 3, Instant::now(), true, 20, 40);
+  let out = trace.finish();
+
+  let json: Value = serde_json::from_slice(&out).unwrap();
+  let events = json.as_array().unwrap();
+  assert_eq!(events.len(), 3);
+  assert_eq!(events[0]["name"], "read");
+  assert_eq!(events[0]["ph"], "X");
+  assert_eq!(events[0]["args"]["bytesSent"], 12);
+  assert_eq!(events[1]["ph"], "b");
+  assert_eq!(events[2]["ph"], "e");
+  assert_eq!(events[1]["id"], events[2]["id"]);
+  assert_eq!(events[2]["args"]["bytesReceived"], 40);
+}
@@ -0,0 +1,98 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+use super::dispatch_json::{json_err, AsyncJsonOp, Deserialize, JsonOp, Value};
+use super::fs;
+use crate::deno_error::DenoError;
+use crate::deno_error::ErrorKind;
+use crate::ops::json_op;
+use crate::state::ThreadSafeState;
+use deno::*;
+use futures::Future;
+
+pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
+  s.register_op(i, "batch", json_op(s.stateful_op(op_batch)));
+}
+
+type Dispatcher =
+  fn(&ThreadSafeState, Value, Option<PinnedBuf>) -> Result<JsonOp, ErrBox>;
+
+/// The ops which can be run as part of a batch. They are file system queries
+/// which take no zero-copy buffer.
+fn lookup_op(name: &str) -> Option<Dispatcher> {
+  let d: Dispatcher = match name {
+    "stat" => fs::op_stat,
+    "read_dir" => fs::op_read_dir,
+    "read_link" => fs::op_read_link,
+    "realpath" => fs::op_realpath,
+    _ => return None,
+  };
+  Some(d)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchArgs {
+  promise_id: Option<u64>,
+  ops: Vec<BatchedOp>,
+}
+
+#[derive(Deserialize)]
+struct BatchedOp {
+  name: String,
+  args: Value,
+}
+
+/// Runs several independent ops with a single dispatch. Each of them is
+/// sync or async like the batch, and its result is laid out like the
+/// response to a single op, so that one failing doesn't fail the others.
+fn op_batch(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: BatchArgs = serde_json::from_value(args)?;
+  debug!("batch ops={}", args.ops.len());
+  let promise_id = args.promise_id;
+
+  let results: Vec<AsyncJsonOp> = args
+    .ops
+    .into_iter()
+    .map(|op| {
+      let result = match lookup_op(&op.name) {
+        Some(d) => {
+          let mut op_args = op.args;
+          if let Value::Object(ref mut m) = op_args {
+            m.insert("promiseId".to_string(), json!(promise_id));
+          }
+          d(state, op_args, None)
+        }
+        None => Err(
+          DenoError::new(
+            ErrorKind::InvalidInput,
+            format!("{} can't be batched", op.name),
+          )
+          .into(),
+        ),
+      };
+      let fut: AsyncJsonOp = match result {
+        Ok(JsonOp::Sync(v)) => Box::new(futures::future::ok(v)),
+        Ok(JsonOp::Async(fut)) => fut,
+        Err(err) => Box::new(futures::future::err(err)),
+      };
+      let fut = fut.then(|result| -> Result<Value, ErrBox> {
+        Ok(match result {
+          Ok(v) => json!({ "ok": v }),
+          Err(err) => json!({ "err": json_err(err) }),
+        })
+      });
+      Box::new(fut) as AsyncJsonOp
+    })
+    .collect();
+
+  let op = futures::future::join_all(results).map(|results| json!(results));
+  if promise_id.is_none() {
+    let value = op.wait()?;
+    Ok(JsonOp::Sync(value))
+  } else {
+    Ok(JsonOp::Async(Box::new(op)))
+  }
+}
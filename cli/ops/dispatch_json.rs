@@ -14,7 +14,7 @@ pub enum JsonOp {
   Async(AsyncJsonOp),
 }
 
-fn json_err(err: ErrBox) -> Value {
+pub fn json_err(err: ErrBox) -> Value {
   use crate::deno_error::GetErrorKind;
   json!({
     "message": err.to_string(),
@@ -1,6 +1,6 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
 use super::dispatch_json::{Deserialize, JsonOp, Value};
-use crate::http_util::get_client;
+use crate::http_util::{get_client, ignores_certificate_errors};
 use crate::ops::json_op;
 use crate::resources;
 use crate::state::ThreadSafeState;
@@ -14,7 +14,7 @@ use std;
 use std::convert::From;
 
 pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
-  i.register_op("fetch", s.core_op(json_op(s.stateful_op(op_fetch))));
+  s.register_op(i, "fetch", json_op(s.stateful_op(op_fetch)));
 }
 
 #[derive(Deserialize)]
@@ -32,8 +32,6 @@ pub fn op_fetch(
   let args: FetchArgs = serde_json::from_value(args)?;
   let url = args.url;
 
-  let client = get_client();
-
   let method = match args.method {
     Some(method_str) => Method::from_bytes(method_str.as_bytes())?,
     None => Method::GET,
@@ -42,6 +40,11 @@ pub fn op_fetch(
   let url_ = url::Url::parse(&url).map_err(ErrBox::from)?;
   state.check_net_url(&url_)?;
 
+  let client = get_client(ignores_certificate_errors(
+    &state.flags.unsafely_ignore_certificate_errors,
+    url_.host_str().unwrap_or(""),
+  ));
+
   let mut request = client.request(method, url_);
 
   if let Some(buf) = data {
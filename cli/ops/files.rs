@@ -1,5 +1,8 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
-use super::dispatch_json::{Deserialize, JsonOp, Value};
+use super::dispatch_json::{blocking_json, Deserialize, JsonOp, Value};
+use crate::deno_error;
+use crate::deno_error::DenoError;
+use crate::deno_error::ErrorKind;
 use crate::fs as deno_fs;
 use crate::ops::json_op;
 use crate::resources;
@@ -8,12 +11,26 @@ use deno::*;
 use futures::Future;
 use std;
 use std::convert::From;
+use std::io::{Seek, SeekFrom};
 use tokio;
 
 pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
-  i.register_op("open", s.core_op(json_op(s.stateful_op(op_open))));
-  i.register_op("close", s.core_op(json_op(s.stateful_op(op_close))));
-  i.register_op("seek", s.core_op(json_op(s.stateful_op(op_seek))));
+  s.register_op(i, "open", json_op(s.stateful_op(op_open)));
+  s.register_op(i, "close", json_op(s.stateful_op(op_close)));
+  s.register_op(i, "dup", json_op(s.stateful_op(op_dup)));
+  s.register_op(
+    i,
+    "read_file_stream",
+    json_op(s.stateful_op(op_read_file_stream)),
+  );
+  s.register_op(i, "seek", json_op(s.stateful_op(op_seek)));
+  s.register_op(i, "ftruncate", json_op(s.stateful_op(op_ftruncate)));
+  s.register_op(i, "futime", json_op(s.stateful_op(op_futime)));
+  s.register_op(i, "flock", json_op(s.stateful_op(op_flock)));
+  s.register_op(i, "funlock", json_op(s.stateful_op(op_funlock)));
+  s.register_op(i, "fsync", json_op(s.stateful_op(op_fsync)));
+  s.register_op(i, "read_at", json_op(s.stateful_op(op_read_at)));
+  s.register_op(i, "write_at", json_op(s.stateful_op(op_write_at)));
 }
 
 #[derive(Deserialize)]
@@ -21,7 +38,98 @@ pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
 struct OpenArgs {
   promise_id: Option<u64>,
   filename: String,
-  mode: String,
+  mode: Option<String>,
+  options: Option<OpenOptions>,
+}
+
+/// Mirrors `std::fs::OpenOptions`. Either given directly from JS, or derived
+/// from one of the fopen(3) style mode strings.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct OpenOptions {
+  read: bool,
+  write: bool,
+  append: bool,
+  truncate: bool,
+  create: bool,
+  create_new: bool,
+}
+
+fn invalid_open_options(msg: &str) -> ErrBox {
+  DenoError::new(ErrorKind::InvalidInput, msg.to_string()).into()
+}
+
+impl OpenOptions {
+  fn from_mode(mode: &str) -> Result<Self, ErrBox> {
+    let mut o = OpenOptions::default();
+    match mode {
+      "r" => {
+        o.read = true;
+      }
+      "r+" => {
+        o.read = true;
+        o.write = true;
+      }
+      "w" => {
+        o.create = true;
+        o.write = true;
+        o.truncate = true;
+      }
+      "w+" => {
+        o.read = true;
+        o.create = true;
+        o.write = true;
+        o.truncate = true;
+      }
+      "a" => {
+        o.create = true;
+        o.append = true;
+      }
+      "a+" => {
+        o.read = true;
+        o.create = true;
+        o.append = true;
+      }
+      "x" => {
+        o.create_new = true;
+        o.write = true;
+      }
+      "x+" => {
+        o.create_new = true;
+        o.read = true;
+        o.write = true;
+      }
+      _ => {
+        return Err(invalid_open_options(&format!(
+          "Unknown file open mode: {}",
+          mode
+        )))
+      }
+    }
+    Ok(o)
+  }
+
+  /// Rejects the combinations `std::fs::OpenOptions` would refuse, but with an
+  /// error message that names the offending options.
+  fn validate(&self) -> Result<(), ErrBox> {
+    let writable = self.write || self.append;
+    if !self.read && !writable {
+      return Err(invalid_open_options(
+        "One of 'read', 'write' or 'append' must be set",
+      ));
+    }
+    if !writable && (self.truncate || self.create || self.create_new) {
+      return Err(invalid_open_options(
+        "'create', 'createNew' and 'truncate' require 'write' or 'append'",
+      ));
+    }
+    if self.truncate && self.append {
+      return Err(invalid_open_options(
+        "'truncate' and 'append' can't be used together",
+      ));
+    }
+    Ok(())
+  }
 }
 
 fn op_open(
@@ -31,57 +139,30 @@ fn op_open(
 ) -> Result<JsonOp, ErrBox> {
   let args: OpenArgs = serde_json::from_value(args)?;
   let (filename, filename_) = deno_fs::resolve_from_cwd(&args.filename)?;
-  let mode = args.mode.as_ref();
 
-  let mut open_options = tokio::fs::OpenOptions::new();
+  let options = match (args.options, args.mode) {
+    (Some(options), _) => options,
+    (None, Some(mode)) => OpenOptions::from_mode(&mode)?,
+    (None, None) => OpenOptions::from_mode("r")?,
+  };
+  options.validate()?;
 
-  match mode {
-    "r" => {
-      open_options.read(true);
-    }
-    "r+" => {
-      open_options.read(true).write(true);
-    }
-    "w" => {
-      open_options.create(true).write(true).truncate(true);
-    }
-    "w+" => {
-      open_options
-        .read(true)
-        .create(true)
-        .write(true)
-        .truncate(true);
-    }
-    "a" => {
-      open_options.create(true).append(true);
-    }
-    "a+" => {
-      open_options.read(true).create(true).append(true);
-    }
-    "x" => {
-      open_options.create_new(true).write(true);
-    }
-    "x+" => {
-      open_options.create_new(true).read(true).write(true);
-    }
-    &_ => {
-      panic!("Unknown file open mode.");
-    }
+  if options.read {
+    state.check_read(&filename_)?;
   }
-
-  match mode {
-    "r" => {
-      state.check_read(&filename_)?;
-    }
-    "w" | "a" | "x" => {
-      state.check_write(&filename_)?;
-    }
-    &_ => {
-      state.check_read(&filename_)?;
-      state.check_write(&filename_)?;
-    }
+  if options.write || options.append {
+    state.check_write(&filename_)?;
   }
 
+  let mut open_options = tokio::fs::OpenOptions::new();
+  open_options
+    .read(options.read)
+    .write(options.write)
+    .append(options.append)
+    .truncate(options.truncate)
+    .create(options.create)
+    .create_new(options.create_new);
+
   let is_sync = args.promise_id.is_none();
   let op = open_options.open(filename).map_err(ErrBox::from).and_then(
     move |fs_file| {
@@ -98,6 +179,39 @@ fn op_open(
   }
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReadFileStreamArgs {
+  promise_id: Option<u64>,
+  filename: String,
+  offset: u64,
+  length: Option<u64>,
+}
+
+/// Opens `filename` for reading `length` bytes, or everything up to EOF,
+/// starting at `offset`. The contents are then pulled with the regular read
+/// op, one caller supplied buffer at a time.
+fn op_read_file_stream(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: ReadFileStreamArgs = serde_json::from_value(args)?;
+  let (filename, filename_) = deno_fs::resolve_from_cwd(&args.filename)?;
+
+  state.check_read(&filename_)?;
+
+  let is_sync = args.promise_id.is_none();
+  blocking_json(is_sync, move || {
+    debug!("op_read_file_stream {} {}", filename_, args.offset);
+    let mut file = std::fs::File::open(&filename)?;
+    file.seek(SeekFrom::Start(args.offset))?;
+    let resource =
+      resources::add_file_stream(tokio::fs::File::from_std(file), args.length);
+    Ok(json!(resource.rid))
+  })
+}
+
 #[derive(Deserialize)]
 struct CloseArgs {
   rid: i32,
@@ -115,12 +229,28 @@ fn op_close(
   Ok(JsonOp::Sync(json!({})))
 }
 
+#[derive(Deserialize)]
+struct DupArgs {
+  rid: i32,
+}
+
+fn op_dup(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: DupArgs = serde_json::from_value(args)?;
+
+  let resource = resources::dup(args.rid as u32)?;
+  Ok(JsonOp::Sync(json!(resource.rid)))
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct SeekArgs {
   promise_id: Option<u64>,
   rid: i32,
-  offset: i32,
+  offset: i64,
   whence: i32,
 }
 
@@ -133,7 +263,7 @@ fn op_seek(
 
   let resource = resources::lookup(args.rid as u32)?;
   let op = resources::seek(resource, args.offset, args.whence as u32)
-    .and_then(move |_| futures::future::ok(json!({})));
+    .and_then(move |pos| futures::future::ok(json!(pos)));
   if args.promise_id.is_none() {
     let buf = op.wait()?;
     Ok(JsonOp::Sync(buf))
@@ -141,3 +271,194 @@ fn op_seek(
     Ok(JsonOp::Async(Box::new(op)))
   }
 }
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FtruncateArgs {
+  promise_id: Option<u64>,
+  rid: i32,
+  len: u64,
+}
+
+fn op_ftruncate(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: FtruncateArgs = serde_json::from_value(args)?;
+  let rid = args.rid as u32;
+  let len = args.len;
+
+  let file = resources::get_file(rid)?;
+
+  let is_sync = args.promise_id.is_none();
+  blocking_json(is_sync, move || {
+    debug!("op_ftruncate {} {}", rid, len);
+    file.set_len(len)?;
+    Ok(json!({}))
+  })
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FutimeArgs {
+  promise_id: Option<u64>,
+  rid: i32,
+  atime: u64,
+  mtime: u64,
+}
+
+fn op_futime(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: FutimeArgs = serde_json::from_value(args)?;
+  let rid = args.rid as u32;
+
+  let file = resources::get_file(rid)?;
+
+  let is_sync = args.promise_id.is_none();
+  blocking_json(is_sync, move || {
+    debug!("op_futime {} {} {}", rid, args.atime, args.mtime);
+    deno_fs::ensure_opened_for_write(&file)?;
+    deno_fs::futime(&file, args.atime, args.mtime)?;
+    Ok(json!({}))
+  })
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FlockArgs {
+  promise_id: Option<u64>,
+  rid: i32,
+  exclusive: bool,
+  nonblocking: bool,
+}
+
+fn op_flock(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: FlockArgs = serde_json::from_value(args)?;
+  let rid = args.rid as u32;
+
+  let file = resources::get_file(rid)?;
+
+  let is_sync = args.promise_id.is_none();
+  blocking_json(is_sync, move || {
+    debug!("op_flock {} exclusive={}", rid, args.exclusive);
+    let acquired = deno_fs::flock(&file, args.exclusive, args.nonblocking)?;
+    Ok(json!(acquired))
+  })
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FunlockArgs {
+  promise_id: Option<u64>,
+  rid: i32,
+}
+
+fn op_funlock(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: FunlockArgs = serde_json::from_value(args)?;
+  let rid = args.rid as u32;
+
+  let file = resources::get_file(rid)?;
+
+  let is_sync = args.promise_id.is_none();
+  blocking_json(is_sync, move || {
+    debug!("op_funlock {}", rid);
+    deno_fs::funlock(&file)?;
+    Ok(json!({}))
+  })
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FsyncArgs {
+  promise_id: Option<u64>,
+  rid: i32,
+  data_only: bool,
+}
+
+fn op_fsync(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: FsyncArgs = serde_json::from_value(args)?;
+  let rid = args.rid as u32;
+
+  let file = resources::get_file(rid)?;
+
+  let is_sync = args.promise_id.is_none();
+  blocking_json(is_sync, move || {
+    debug!("op_fsync {} data_only={}", rid, args.data_only);
+    if args.data_only {
+      file.sync_data()?;
+    } else {
+      file.sync_all()?;
+    }
+    Ok(json!({}))
+  })
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PositionalArgs {
+  promise_id: Option<u64>,
+  rid: i32,
+  offset: u64,
+}
+
+fn op_read_at(
+  _state: &ThreadSafeState,
+  args: Value,
+  zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: PositionalArgs = serde_json::from_value(args)?;
+  let rid = args.rid as u32;
+  let mut buf = match zero_copy {
+    Some(buf) => buf,
+    None => return Err(deno_error::no_buffer_specified()),
+  };
+
+  // Read permission was already checked when the file was opened.
+  let file = resources::get_file(rid)?;
+
+  let is_sync = args.promise_id.is_none();
+  blocking_json(is_sync, move || {
+    debug!("op_read_at {} {}", rid, args.offset);
+    let nread = deno_fs::read_at(&file, &mut buf, args.offset)?;
+    Ok(json!(nread))
+  })
+}
+
+fn op_write_at(
+  _state: &ThreadSafeState,
+  args: Value,
+  zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: PositionalArgs = serde_json::from_value(args)?;
+  let rid = args.rid as u32;
+  let buf = match zero_copy {
+    Some(buf) => buf,
+    None => return Err(deno_error::no_buffer_specified()),
+  };
+
+  // Write permission was already checked when the file was opened.
+  let file = resources::get_file(rid)?;
+
+  let is_sync = args.promise_id.is_none();
+  blocking_json(is_sync, move || {
+    debug!("op_write_at {} {}", rid, args.offset);
+    let nwritten = deno_fs::write_at(&file, &buf, args.offset)?;
+    Ok(json!(nwritten))
+  })
+}
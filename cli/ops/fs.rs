@@ -5,37 +5,52 @@ use crate::deno_error::DenoError;
 use crate::deno_error::ErrorKind;
 use crate::fs as deno_fs;
 use crate::ops::json_op;
+use crate::resources;
 use crate::state::ThreadSafeState;
 use deno::*;
-use remove_dir_all::remove_dir_all;
 use std::convert::From;
 use std::fs;
-use std::path::PathBuf;
+use std::path::Path;
 use std::time::UNIX_EPOCH;
 
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 
 pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
-  i.register_op("chdir", s.core_op(json_op(s.stateful_op(op_chdir))));
-  i.register_op("mkdir", s.core_op(json_op(s.stateful_op(op_mkdir))));
-  i.register_op("chmod", s.core_op(json_op(s.stateful_op(op_chmod))));
-  i.register_op("chown", s.core_op(json_op(s.stateful_op(op_chown))));
-  i.register_op("remove", s.core_op(json_op(s.stateful_op(op_remove))));
-  i.register_op("copy_file", s.core_op(json_op(s.stateful_op(op_copy_file))));
-  i.register_op("stat", s.core_op(json_op(s.stateful_op(op_stat))));
-  i.register_op("read_dir", s.core_op(json_op(s.stateful_op(op_read_dir))));
-  i.register_op("rename", s.core_op(json_op(s.stateful_op(op_rename))));
-  i.register_op("link", s.core_op(json_op(s.stateful_op(op_link))));
-  i.register_op("symlink", s.core_op(json_op(s.stateful_op(op_symlink))));
-  i.register_op("read_link", s.core_op(json_op(s.stateful_op(op_read_link))));
-  i.register_op("truncate", s.core_op(json_op(s.stateful_op(op_truncate))));
-  i.register_op(
-    "make_temp_dir",
-    s.core_op(json_op(s.stateful_op(op_make_temp_dir))),
+  s.register_op(i, "chdir", json_op(s.stateful_op(op_chdir)));
+  s.register_op(i, "mkdir", json_op(s.stateful_op(op_mkdir)));
+  s.register_op(i, "create_pipe", json_op(s.stateful_op(op_create_pipe)));
+  s.register_op(i, "chmod", json_op(s.stateful_op(op_chmod)));
+  s.register_op(i, "fchmod", json_op(s.stateful_op(op_fchmod)));
+  s.register_op(i, "chown", json_op(s.stateful_op(op_chown)));
+  s.register_op(i, "lchown", json_op(s.stateful_op(op_lchown)));
+  s.register_op(i, "fchown", json_op(s.stateful_op(op_fchown)));
+  s.register_op(i, "remove", json_op(s.stateful_op(op_remove)));
+  s.register_op(i, "copy_file", json_op(s.stateful_op(op_copy_file)));
+  s.register_op(i, "stat", json_op(s.stateful_op(op_stat)));
+  s.register_op(i, "fstat", json_op(s.stateful_op(op_fstat)));
+  s.register_op(i, "statfs", json_op(s.stateful_op(op_statfs)));
+  s.register_op(i, "read_dir", json_op(s.stateful_op(op_read_dir)));
+  s.register_op(i, "rename", json_op(s.stateful_op(op_rename)));
+  s.register_op(i, "link", json_op(s.stateful_op(op_link)));
+  s.register_op(i, "symlink", json_op(s.stateful_op(op_symlink)));
+  s.register_op(i, "read_link", json_op(s.stateful_op(op_read_link)));
+  s.register_op(i, "realpath", json_op(s.stateful_op(op_realpath)));
+  s.register_op(i, "truncate", json_op(s.stateful_op(op_truncate)));
+  s.register_op(i, "make_temp_dir", json_op(s.stateful_op(op_make_temp_dir)));
+  s.register_op(
+    i,
+    "make_temp_file",
+    json_op(s.stateful_op(op_make_temp_file)),
   );
-  i.register_op("cwd", s.core_op(json_op(s.stateful_op(op_cwd))));
-  i.register_op("utime", s.core_op(json_op(s.stateful_op(op_utime))));
+  s.register_op(
+    i,
+    "link_temp_file",
+    json_op(s.stateful_op(op_link_temp_file)),
+  );
+  s.register_op(i, "cwd", json_op(s.stateful_op(op_cwd)));
+  s.register_op(i, "utime", json_op(s.stateful_op(op_utime)));
+  s.register_op(i, "umask", json_op(s.stateful_op(op_umask)));
 }
 
 #[derive(Deserialize)]
@@ -80,12 +95,46 @@ fn op_mkdir(
   })
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreatePipeArgs {
+  promise_id: Option<u64>,
+  path: String,
+  mode: u32,
+  read: bool,
+  write: bool,
+}
+
+fn op_create_pipe(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: CreatePipeArgs = serde_json::from_value(args)?;
+  let (path, path_) = deno_fs::resolve_from_cwd(args.path.as_ref())?;
+
+  state.check_write(&path_)?;
+  if args.read {
+    state.check_read(&path_)?;
+  }
+
+  let is_sync = args.promise_id.is_none();
+  blocking_json(is_sync, move || {
+    debug!("op_create_pipe {}", path_);
+    let file = deno_fs::create_pipe(&path, args.mode, args.read, args.write)?;
+    let resource = resources::add_fs_file(tokio::fs::File::from_std(file));
+    Ok(json!(resource.rid))
+  })
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct ChmodArgs {
   promise_id: Option<u64>,
   path: String,
   mode: u32,
+  recursive: bool,
+  follow_symlinks: bool,
 }
 
 fn op_chmod(
@@ -98,17 +147,80 @@ fn op_chmod(
 
   state.check_write(&path_)?;
 
+  let state = state.clone();
   let is_sync = args.promise_id.is_none();
   blocking_json(is_sync, move || {
-    debug!("op_chmod {}", &path_);
-    // Still check file/dir exists on windows
-    let _metadata = fs::metadata(&path)?;
-    #[cfg(any(unix))]
-    {
-      let mut permissions = _metadata.permissions();
-      permissions.set_mode(args.mode);
-      fs::set_permissions(&path, permissions)?;
+    debug!("op_chmod {} recursive={}", &path_, args.recursive);
+    if !args.recursive {
+      chmod_one(&path, args.mode)?;
+      return Ok(json!({}));
     }
+    // The mode of a symlink itself can't be changed, and changing that of
+    // its target could reach outside of `path`.
+    let root = if args.follow_symlinks {
+      Some(fs::canonicalize(&path)?)
+    } else {
+      None
+    };
+    deno_fs::walk_tree(&path, args.follow_symlinks, |p, is_link| {
+      if let Some(ref root) = root {
+        check_write_resolved(&state, root, p)?;
+      }
+      if !is_link {
+        chmod_one(p, args.mode)?;
+      }
+      Ok(())
+    })?;
+    Ok(json!({}))
+  })
+}
+
+/// Symlinks followed while walking the tree `root`, which is resolved, may
+/// lead outside of it. Write permission was only checked for `root`, so it's
+/// checked for every path outside of it too.
+fn check_write_resolved(
+  state: &ThreadSafeState,
+  root: &Path,
+  path: &Path,
+) -> Result<(), ErrBox> {
+  let resolved = fs::canonicalize(path)?;
+  if resolved.starts_with(root) {
+    return Ok(());
+  }
+  state.check_write(&resolved.to_string_lossy())
+}
+
+fn chmod_one(path: &Path, mode: u32) -> std::io::Result<()> {
+  let mut permissions = fs::metadata(path)?.permissions();
+  set_mode(&mut permissions, mode);
+  fs::set_permissions(path, permissions)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FchmodArgs {
+  promise_id: Option<u64>,
+  rid: i32,
+  mode: u32,
+}
+
+fn op_fchmod(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: FchmodArgs = serde_json::from_value(args)?;
+  let rid = args.rid as u32;
+
+  let file = resources::get_file(rid)?;
+
+  let is_sync = args.promise_id.is_none();
+  blocking_json(is_sync, move || {
+    debug!("op_fchmod {} {:o}", rid, args.mode);
+    deno_fs::ensure_opened_for_write(&file)?;
+    let mut permissions = file.metadata()?.permissions();
+    set_mode(&mut permissions, args.mode);
+    file.set_permissions(permissions)?;
     Ok(json!({}))
   })
 }
@@ -120,24 +232,97 @@ struct ChownArgs {
   path: String,
   uid: u32,
   gid: u32,
+  recursive: bool,
+  follow_symlinks: bool,
 }
 
-fn op_chown(
+fn chown(
   state: &ThreadSafeState,
   args: Value,
-  _zero_copy: Option<PinnedBuf>,
+  follow_symlinks: bool,
 ) -> Result<JsonOp, ErrBox> {
   let args: ChownArgs = serde_json::from_value(args)?;
+  let (path, path_) = deno_fs::resolve_from_cwd(args.path.as_ref())?;
 
-  state.check_write(&args.path)?;
+  state.check_write(&path_)?;
 
+  let state = state.clone();
   let is_sync = args.promise_id.is_none();
   blocking_json(is_sync, move || {
-    debug!("op_chown {}", &args.path);
-    match deno_fs::chown(args.path.as_ref(), args.uid, args.gid) {
-      Ok(_) => Ok(json!({})),
-      Err(e) => Err(e),
+    debug!("op_chown {} follow_symlinks={}", path_, follow_symlinks);
+    if follow_symlinks {
+      deno_fs::chown(&path, args.uid, args.gid)?;
+    } else {
+      deno_fs::lchown(&path, args.uid, args.gid)?;
+    }
+    if !args.recursive {
+      return Ok(json!({}));
     }
+    // `path` itself was handled above. Below it, symlinks that aren't
+    // followed get their own owner changed.
+    let root = if args.follow_symlinks {
+      Some(fs::canonicalize(&path)?)
+    } else {
+      None
+    };
+    deno_fs::walk_tree(&path, args.follow_symlinks, |p, is_link| {
+      if p == path.as_path() {
+        return Ok(());
+      }
+      if let Some(ref root) = root {
+        check_write_resolved(&state, root, p)?;
+      }
+      if is_link {
+        deno_fs::lchown(p, args.uid, args.gid)
+      } else {
+        deno_fs::chown(p, args.uid, args.gid)
+      }
+    })?;
+    Ok(json!({}))
+  })
+}
+
+fn op_chown(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  chown(state, args, true)
+}
+
+fn op_lchown(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  chown(state, args, false)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FchownArgs {
+  promise_id: Option<u64>,
+  rid: i32,
+  uid: u32,
+  gid: u32,
+}
+
+fn op_fchown(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: FchownArgs = serde_json::from_value(args)?;
+  let rid = args.rid as u32;
+
+  let file = resources::get_file(rid)?;
+
+  let is_sync = args.promise_id.is_none();
+  blocking_json(is_sync, move || {
+    debug!("op_fchown {}", rid);
+    deno_fs::ensure_opened_for_write(&file)?;
+    deno_fs::fchown(&file, args.uid, args.gid)?;
+    Ok(json!({}))
   })
 }
 
@@ -162,15 +347,8 @@ fn op_remove(
 
   let is_sync = args.promise_id.is_none();
   blocking_json(is_sync, move || {
-    debug!("op_remove {}", path.display());
-    let metadata = fs::metadata(&path)?;
-    if metadata.is_file() {
-      fs::remove_file(&path)?;
-    } else if recursive {
-      remove_dir_all(&path)?;
-    } else {
-      fs::remove_dir(&path)?;
-    }
+    debug!("op_remove {} recursive={}", path.display(), recursive);
+    deno_fs::remove(&path, recursive)?;
     Ok(json!({}))
   })
 }
@@ -209,7 +387,7 @@ fn op_copy_file(
       );
     }
 
-    fs::copy(&from, &to)?;
+    deno_fs::copy_file(&from, &to)?;
     Ok(json!({}))
   })
 }
@@ -225,13 +403,36 @@ macro_rules! to_seconds {
 }
 
 #[cfg(any(unix))]
-fn get_mode(perm: &fs::Permissions) -> u32 {
-  perm.mode()
+fn get_mode(metadata: &fs::Metadata) -> u32 {
+  metadata.permissions().mode()
 }
 
+/// Windows only knows whether a file is read-only. Like Go, report that as
+/// either 0o444 or 0o666, plus 0o111 for directories.
 #[cfg(not(any(unix)))]
-fn get_mode(_perm: &fs::Permissions) -> u32 {
-  0
+fn get_mode(metadata: &fs::Metadata) -> u32 {
+  let mode = if metadata.permissions().readonly() {
+    0o444
+  } else {
+    0o666
+  };
+  if metadata.is_dir() {
+    mode | 0o111
+  } else {
+    mode
+  }
+}
+
+#[cfg(any(unix))]
+fn set_mode(permissions: &mut fs::Permissions, mode: u32) {
+  permissions.set_mode(mode);
+}
+
+/// Windows only knows whether a file is read-only, which is the case when
+/// `mode` lacks the owner write bit. The other bits are ignored.
+#[cfg(not(any(unix)))]
+fn set_mode(permissions: &mut fs::Permissions, mode: u32) {
+  permissions.set_readonly(mode & 0o200 == 0);
 }
 
 #[derive(Deserialize)]
@@ -242,7 +443,7 @@ struct StatArgs {
   lstat: bool,
 }
 
-fn op_stat(
+pub fn op_stat(
   state: &ThreadSafeState,
   args: Value,
   _zero_copy: Option<PinnedBuf>,
@@ -263,16 +464,86 @@ fn op_stat(
     } else {
       fs::metadata(&filename)?
     };
+    let ext = deno_fs::path_stat_ext(&filename, &metadata, !lstat);
+    Ok(stat_json(&metadata, &ext))
+  })
+}
 
+fn stat_json(metadata: &fs::Metadata, ext: &deno_fs::StatExt) -> Value {
+  // Device IDs and inode numbers use all 64 bits, more than a JS number holds
+  // exactly, so they're sent as decimal strings.
+  let id = |v: Option<u64>| v.map(|v| v.to_string());
+  json!({
+    "isFile": metadata.is_file(),
+    "isSymlink": metadata.file_type().is_symlink(),
+    "len": metadata.len(),
+    "modified":to_seconds!(metadata.modified()),
+    "accessed":to_seconds!(metadata.accessed()),
+    "created":to_seconds!(metadata.created()),
+    "mode": get_mode(metadata),
+    "dev": id(ext.dev),
+    "ino": id(ext.ino),
+    "nlink": ext.nlink,
+    "uid": ext.uid,
+    "gid": ext.gid,
+    "rdev": id(ext.rdev),
+    "blksize": ext.blksize,
+    "blocks": ext.blocks,
+  })
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FstatArgs {
+  promise_id: Option<u64>,
+  rid: i32,
+}
+
+fn op_fstat(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: FstatArgs = serde_json::from_value(args)?;
+  let rid = args.rid as u32;
+
+  // Read permission was already checked when the file was opened.
+  let file = resources::get_file(rid)?;
+
+  let is_sync = args.promise_id.is_none();
+  blocking_json(is_sync, move || {
+    debug!("op_fstat {}", rid);
+    let metadata = file.metadata()?;
+    let ext = deno_fs::fstat_ext(&file, &metadata)?;
+    Ok(stat_json(&metadata, &ext))
+  })
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StatfsArgs {
+  promise_id: Option<u64>,
+  path: String,
+}
+
+fn op_statfs(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: StatfsArgs = serde_json::from_value(args)?;
+  let (path, path_) = deno_fs::resolve_from_cwd(args.path.as_ref())?;
+
+  state.check_read(&path_)?;
+
+  let is_sync = args.promise_id.is_none();
+  blocking_json(is_sync, move || {
+    debug!("op_statfs {}", path.display());
+    let usage = deno_fs::statfs(&path)?;
     Ok(json!({
-      "isFile": metadata.is_file(),
-      "isSymlink": metadata.file_type().is_symlink(),
-      "len": metadata.len(),
-      "modified":to_seconds!(metadata.modified()),
-      "accessed":to_seconds!(metadata.accessed()),
-      "created":to_seconds!(metadata.created()),
-      "mode": get_mode(&metadata.permissions()),
-      "hasMode": cfg!(target_family = "unix"), // false on windows,
+      "total": usage.total,
+      "free": usage.free,
+      "available": usage.available,
     }))
   })
 }
@@ -284,7 +555,7 @@ struct ReadDirArgs {
   path: String,
 }
 
-fn op_read_dir(
+pub fn op_read_dir(
   state: &ThreadSafeState,
   args: Value,
   _zero_copy: Option<PinnedBuf>,
@@ -302,19 +573,11 @@ fn op_read_dir(
       .map(|entry| {
         let entry = entry.unwrap();
         let metadata = entry.metadata().unwrap();
-        let file_type = metadata.file_type();
-
-        json!({
-          "isFile": file_type.is_file(),
-          "isSymlink": file_type.is_symlink(),
-          "len": metadata.len(),
-          "modified": to_seconds!(metadata.modified()),
-          "accessed": to_seconds!(metadata.accessed()),
-          "created": to_seconds!(metadata.created()),
-          "mode": get_mode(&metadata.permissions()),
-          "name": entry.file_name().to_str().unwrap(),
-          "hasMode": cfg!(target_family = "unix"), // false on windows,
-        })
+        // Opening every entry just for its file id would make listing large
+        // directories slow on Windows, so only the cheap fields are filled.
+        let mut value = stat_json(&metadata, &deno_fs::stat_ext(&metadata));
+        value["name"] = json!(entry.file_name().to_str().unwrap());
+        value
       })
       .collect();
 
@@ -387,6 +650,9 @@ struct SymlinkArgs {
   promise_id: Option<u64>,
   oldname: String,
   newname: String,
+  #[allow(dead_code)]
+  #[serde(rename = "type")]
+  link_type: Option<String>,
 }
 
 fn op_symlink(
@@ -400,17 +666,14 @@ fn op_symlink(
   let (newname, newname_) = deno_fs::resolve_from_cwd(args.newname.as_ref())?;
 
   state.check_write(&newname_)?;
-  // TODO Use type for Windows.
-  if cfg!(windows) {
-    return Err(
-      DenoError::new(ErrorKind::Other, "Not implemented".to_string()).into(),
-    );
-  }
+
   let is_sync = args.promise_id.is_none();
   blocking_json(is_sync, move || {
     debug!("op_symlink {} {}", oldname.display(), newname.display());
-    #[cfg(any(unix))]
+    #[cfg(unix)]
     std::os::unix::fs::symlink(&oldname, &newname)?;
+    #[cfg(windows)]
+    deno_fs::symlink_windows(&oldname, &newname, args.link_type.as_ref())?;
     Ok(json!({}))
   })
 }
@@ -422,7 +685,7 @@ struct ReadLinkArgs {
   name: String,
 }
 
-fn op_read_link(
+pub fn op_read_link(
   state: &ThreadSafeState,
   args: Value,
   _zero_copy: Option<PinnedBuf>,
@@ -443,6 +706,41 @@ fn op_read_link(
   })
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RealpathArgs {
+  promise_id: Option<u64>,
+  path: String,
+}
+
+pub fn op_realpath(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: RealpathArgs = serde_json::from_value(args)?;
+  let (path, path_) = deno_fs::resolve_from_cwd(args.path.as_ref())?;
+
+  state.check_read(&path_)?;
+
+  let state = state.clone();
+  let is_sync = args.promise_id.is_none();
+  blocking_json(is_sync, move || {
+    debug!("op_realpath {}", path.display());
+    let realpath = deno_fs::realpath(&path)?;
+    let realpath_str = realpath.to_str().ok_or_else(|| {
+      DenoError::new(
+        ErrorKind::InvalidData,
+        format!("Path is not valid UTF-8: {}", realpath.display()),
+      )
+    })?;
+    // The resolved target may lie outside of what the caller is allowed to
+    // read, in which case even revealing its location is denied.
+    state.check_read(realpath_str)?;
+    Ok(json!(realpath_str))
+  })
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct TruncateArgs {
@@ -474,37 +772,56 @@ fn op_truncate(
 
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct MakeTempDirArgs {
+struct MakeTempArgs {
   promise_id: Option<u64>,
   dir: Option<String>,
   prefix: Option<String>,
   suffix: Option<String>,
+  anonymous: Option<bool>,
 }
 
-fn op_make_temp_dir(
+fn make_temp(
   state: &ThreadSafeState,
   args: Value,
-  _zero_copy: Option<PinnedBuf>,
+  is_dir: bool,
 ) -> Result<JsonOp, ErrBox> {
-  let args: MakeTempDirArgs = serde_json::from_value(args)?;
+  let args: MakeTempArgs = serde_json::from_value(args)?;
+
+  let dir = match args.dir {
+    Some(dir) => deno_fs::resolve_from_cwd(&dir)?.0,
+    None => std::env::temp_dir(),
+  };
+  let dir_str = dir.to_str().ok_or_else(|| {
+    DenoError::new(
+      ErrorKind::InvalidInput,
+      format!("Path is not valid UTF-8: {}", dir.display()),
+    )
+  })?;
+  state.check_write(dir_str)?;
 
-  // FIXME
-  state.check_write("make_temp")?;
+  let is_sync = args.promise_id.is_none();
+  if !is_dir && args.anonymous == Some(true) {
+    return blocking_json(is_sync, move || {
+      debug!("op_make_temp_file anonymous in {}", dir.display());
+      let file = deno_fs::make_anonymous_temp(&dir)?;
+      let resource = resources::add_temp_file(tokio::fs::File::from_std(file));
+      Ok(json!(resource.rid))
+    });
+  }
 
-  let dir = args.dir.map(PathBuf::from);
   let prefix = args.prefix.map(String::from);
   let suffix = args.suffix.map(String::from);
 
-  let is_sync = args.promise_id.is_none();
   blocking_json(is_sync, move || {
     // TODO(piscisaureus): use byte vector for paths, not a string.
     // See https://github.com/denoland/deno/issues/627.
     // We can't assume that paths are always valid utf8 strings.
-    let path = deno_fs::make_temp_dir(
+    let path = deno_fs::make_temp(
+      Some(&dir),
       // Converting Option<String> to Option<&str>
-      dir.as_ref().map(|x| &**x),
       prefix.as_ref().map(|x| &**x),
       suffix.as_ref().map(|x| &**x),
+      is_dir,
     )?;
     let path_str = path.to_str().unwrap();
 
@@ -512,6 +829,50 @@ fn op_make_temp_dir(
   })
 }
 
+fn op_make_temp_dir(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  make_temp(state, args, true)
+}
+
+fn op_make_temp_file(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  make_temp(state, args, false)
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LinkTempFileArgs {
+  promise_id: Option<u64>,
+  rid: i32,
+  newname: String,
+}
+
+fn op_link_temp_file(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: LinkTempFileArgs = serde_json::from_value(args)?;
+  let rid = args.rid as u32;
+  let (newname, newname_) = deno_fs::resolve_from_cwd(&args.newname)?;
+
+  state.check_write(&newname_)?;
+  let file = resources::get_temp_file(rid)?;
+
+  let is_sync = args.promise_id.is_none();
+  blocking_json(is_sync, move || {
+    debug!("op_link_temp_file {} {}", rid, newname.display());
+    deno_fs::link_anonymous_temp(&file, &newname)?;
+    Ok(json!({}))
+  })
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct Utime {
@@ -527,11 +888,14 @@ fn op_utime(
   _zero_copy: Option<PinnedBuf>,
 ) -> Result<JsonOp, ErrBox> {
   let args: Utime = serde_json::from_value(args)?;
-  state.check_write(&args.filename)?;
+  let (filename, filename_) = deno_fs::resolve_from_cwd(&args.filename)?;
+
+  state.check_write(&filename_)?;
+
   let is_sync = args.promise_id.is_none();
   blocking_json(is_sync, move || {
-    debug!("op_utimes {} {} {}", args.filename, args.atime, args.mtime);
-    utime::set_file_times(args.filename, args.atime, args.mtime)?;
+    debug!("op_utime {} {} {}", filename_, args.atime, args.mtime);
+    utime::set_file_times(filename, args.atime, args.mtime)?;
     Ok(json!({}))
   })
 }
@@ -545,3 +909,19 @@ fn op_cwd(
   let path_str = path.into_os_string().into_string().unwrap();
   Ok(JsonOp::Sync(json!(path_str)))
 }
+
+#[derive(Deserialize)]
+struct UmaskArgs {
+  mask: Option<u32>,
+}
+
+fn op_umask(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: UmaskArgs = serde_json::from_value(args)?;
+  debug!("op_umask {:?}", args.mask);
+  let previous = deno_fs::umask(args.mask)?;
+  Ok(JsonOp::Sync(json!(previous)))
+}
@@ -0,0 +1,296 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+use super::dispatch_json::{Deserialize, JsonOp, Value};
+use crate::deno_error::bad_resource;
+use crate::deno_error::DenoError;
+use crate::deno_error::ErrorKind;
+use crate::fs as deno_fs;
+use crate::ops::json_op;
+use crate::resources;
+use crate::state::ThreadSafeState;
+use deno::*;
+use futures::sync::mpsc;
+use futures::Future;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use notify::op::Op;
+use notify::RawEvent;
+use notify::RecommendedWatcher;
+use notify::RecursiveMode;
+use notify::Watcher;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::RecvTimeoutError;
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
+  s.register_op(
+    i,
+    "fs_events_open",
+    json_op(s.stateful_op(op_fs_events_open)),
+  );
+  s.register_op(
+    i,
+    "fs_events_poll",
+    json_op(s.stateful_op(op_fs_events_poll)),
+  );
+}
+
+/// Kinds of file system events that can be requested from `fs_events_open`.
+/// The raw `notify` op bits are collapsed into these before anything is sent
+/// to JS.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FsEventKind {
+  Create,
+  Modify,
+  Remove,
+  Rename,
+}
+
+impl FsEventKind {
+  fn from_str(s: &str) -> Result<Self, ErrBox> {
+    match s {
+      "create" => Ok(FsEventKind::Create),
+      "modify" => Ok(FsEventKind::Modify),
+      "remove" => Ok(FsEventKind::Remove),
+      "rename" => Ok(FsEventKind::Rename),
+      _ => Err(
+        DenoError::new(
+          ErrorKind::InvalidInput,
+          format!("Invalid fs event kind: {}", s),
+        )
+        .into(),
+      ),
+    }
+  }
+
+  fn as_str(self) -> &'static str {
+    match self {
+      FsEventKind::Create => "create",
+      FsEventKind::Modify => "modify",
+      FsEventKind::Remove => "remove",
+      FsEventKind::Rename => "rename",
+    }
+  }
+
+  fn matches(self, op: Op) -> bool {
+    match self {
+      FsEventKind::Create => op.contains(Op::CREATE),
+      FsEventKind::Modify => {
+        op.intersects(Op::WRITE | Op::CHMOD | Op::CLOSE_WRITE)
+      }
+      FsEventKind::Remove => op.contains(Op::REMOVE),
+      FsEventKind::Rename => op.contains(Op::RENAME),
+    }
+  }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct FsEvent {
+  pub kind: FsEventKind,
+  pub path: PathBuf,
+}
+
+impl FsEvent {
+  fn to_json(&self) -> Value {
+    json!({
+      "kind": self.kind.as_str(),
+      "paths": [self.path.to_string_lossy()],
+    })
+  }
+}
+
+/// The watcher is kept alive for as long as the resource is in the table.
+/// Dropping it closes the raw `notify` channel, which in turn ends the
+/// forwarding thread and the event stream seen by JS.
+pub struct FsEventsWatcher {
+  _watcher: RecommendedWatcher,
+  pub receiver: mpsc::UnboundedReceiver<FsEvent>,
+}
+
+/// Decides which events are dropped before they reach JS.
+struct EventFilter {
+  kinds: Vec<FsEventKind>,
+  /// Watched paths, which ignore patterns are relative to.
+  roots: Vec<PathBuf>,
+  ignore: GlobSet,
+}
+
+impl EventFilter {
+  fn is_ignored(&self, path: &Path) -> bool {
+    if self.ignore.is_empty() {
+      return false;
+    }
+    self.roots.iter().any(|root| match path.strip_prefix(root) {
+      Ok(relative) => self.ignore.is_match(relative),
+      Err(_) => false,
+    })
+  }
+}
+
+/// Translates a raw `notify` event into zero or more `FsEvent`s, dropping
+/// everything that isn't in `kinds` or matches an ignore pattern.
+fn filter_raw_event(raw: RawEvent, filter: &EventFilter) -> Vec<FsEvent> {
+  let (path, op) = match (raw.path, raw.op) {
+    (Some(path), Ok(op)) => (path, op),
+    _ => return vec![],
+  };
+  if filter.is_ignored(&path) {
+    return vec![];
+  }
+  filter
+    .kinds
+    .iter()
+    .filter(|kind| kind.matches(op))
+    .map(|kind| FsEvent {
+      kind: *kind,
+      path: path.clone(),
+    })
+    .collect()
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FsEventsOpenArgs {
+  paths: Vec<String>,
+  recursive: bool,
+  kinds: Option<Vec<String>>,
+  debounce: Option<u64>,
+  ignore: Option<Vec<String>>,
+}
+
+fn op_fs_events_open(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: FsEventsOpenArgs = serde_json::from_value(args)?;
+
+  let kinds = match args.kinds {
+    Some(kinds) => kinds
+      .iter()
+      .map(|k| FsEventKind::from_str(k))
+      .collect::<Result<Vec<_>, _>>()?,
+    None => vec![
+      FsEventKind::Create,
+      FsEventKind::Modify,
+      FsEventKind::Remove,
+      FsEventKind::Rename,
+    ],
+  };
+
+  let mut ignore = GlobSetBuilder::new();
+  for pattern in args.ignore.unwrap_or_default() {
+    let glob = Glob::new(&pattern)
+      .map_err(|e| DenoError::new(ErrorKind::InvalidInput, e.to_string()))?;
+    ignore.add(glob);
+  }
+  let ignore = ignore
+    .build()
+    .map_err(|e| DenoError::new(ErrorKind::InvalidInput, e.to_string()))?;
+  let debounce = args.debounce.map(Duration::from_millis);
+
+  let recursive_mode = if args.recursive {
+    RecursiveMode::Recursive
+  } else {
+    RecursiveMode::NonRecursive
+  };
+
+  let (raw_sender, raw_receiver) = std::sync::mpsc::channel::<RawEvent>();
+  let (sender, receiver) = mpsc::unbounded::<FsEvent>();
+  let mut watcher: RecommendedWatcher = notify::raw_watcher(raw_sender)?;
+
+  let mut roots = vec![];
+  for path in &args.paths {
+    let (path, path_) = deno_fs::resolve_from_cwd(path)?;
+    state.check_read(&path_)?;
+    debug!("op_fs_events_open {} recursive={}", path_, args.recursive);
+    watcher.watch(&path, recursive_mode)?;
+    // Events are reported with canonical paths on some platforms.
+    roots.push(path.canonicalize().unwrap_or_else(|_| path.clone()));
+    roots.push(path);
+  }
+  let filter = EventFilter {
+    kinds,
+    roots,
+    ignore,
+  };
+
+  thread::spawn(move || {
+    // Filtering happens here so that uninteresting events never reach the
+    // resource table, let alone JS.
+    while let Ok(raw) = raw_receiver.recv() {
+      let mut events = filter_raw_event(raw, &filter);
+      let mut disconnected = false;
+      // Everything that happens within `debounce` of the first event is sent
+      // together, without duplicates. The window isn't extended by later
+      // events, so a steady stream of them can't hold events back forever.
+      if let Some(debounce) = debounce {
+        let deadline = Instant::now() + debounce;
+        loop {
+          let now = Instant::now();
+          if now >= deadline {
+            break;
+          }
+          match raw_receiver.recv_timeout(deadline - now) {
+            Ok(raw) => {
+              for event in filter_raw_event(raw, &filter) {
+                if !events.contains(&event) {
+                  events.push(event);
+                }
+              }
+            }
+            Err(RecvTimeoutError::Timeout) => break,
+            Err(RecvTimeoutError::Disconnected) => {
+              disconnected = true;
+              break;
+            }
+          }
+        }
+      }
+      for event in events {
+        if sender.unbounded_send(event).is_err() {
+          return;
+        }
+      }
+      if disconnected {
+        return;
+      }
+    }
+  });
+
+  let resource = resources::add_fs_events_watcher(FsEventsWatcher {
+    _watcher: watcher,
+    receiver,
+  });
+  Ok(JsonOp::Sync(json!(resource.rid)))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FsEventsPollArgs {
+  rid: i32,
+}
+
+fn op_fs_events_poll(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: FsEventsPollArgs = serde_json::from_value(args)?;
+  let rid = args.rid as u32;
+
+  if resources::get_type(rid) != Some(String::from("fsEvents")) {
+    return Err(bad_resource());
+  }
+
+  let op = resources::cancel_on_close(rid, resources::poll_fs_event(rid));
+  let op = op.then(move |result| {
+    let value = match result {
+      Ok(Some(event)) => json!({ "value": event.to_json(), "done": false }),
+      // The watcher was closed while we were waiting.
+      Ok(None) | Err(_) => json!({ "done": true }),
+    };
+    futures::future::ok(value)
+  });
+
+  Ok(JsonOp::Async(Box::new(op)))
+}
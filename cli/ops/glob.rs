@@ -0,0 +1,179 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+use super::dispatch_json::{blocking_json, Deserialize, JsonOp, Value};
+use crate::deno_error::DenoError;
+use crate::deno_error::ErrorKind;
+use crate::fs as deno_fs;
+use crate::ops::json_op;
+use crate::resources;
+use crate::state::ThreadSafeState;
+use deno::*;
+use globset::GlobBuilder;
+use globset::GlobMatcher;
+use std::io;
+use std::path::Path;
+use walkdir::WalkDir;
+
+pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
+  s.register_op(i, "glob_open", json_op(s.stateful_op(op_glob_open)));
+  s.register_op(i, "glob_next", json_op(s.stateful_op(op_glob_next)));
+}
+
+/// Walks the directory named by the literal prefix of a pattern and matches
+/// everything below it against the rest of the pattern. The walk is only
+/// pruned by depth: without `**` it stops at the number of components of the
+/// rest of the pattern, while `**` walks the whole tree below the prefix.
+pub struct GlobWalker {
+  /// Literal prefix of the pattern, as written. Matches are reported relative
+  /// to it so that they look like the pattern.
+  prefix: String,
+  matcher: GlobMatcher,
+  iter: walkdir::IntoIter,
+}
+
+impl GlobWalker {
+  /// Returns up to `max` matched paths. Fewer than `max` paths are only
+  /// returned once the walk is complete.
+  pub fn next_batch(&mut self, max: usize) -> Result<Vec<String>, ErrBox> {
+    let mut paths = Vec::new();
+    while paths.len() < max {
+      let entry = match self.iter.next() {
+        Some(Ok(entry)) => entry,
+        // A root which doesn't exist matches nothing, but other errors of it,
+        // like not being allowed to list it, fail the glob.
+        Some(Err(err)) if err.depth() == 0 => {
+          let err = io::Error::from(err);
+          if err.kind() == io::ErrorKind::NotFound {
+            continue;
+          }
+          return Err(err.into());
+        }
+        // Unreadable directories below the root are skipped, like a shell
+        // does.
+        Some(Err(_)) => continue,
+        None => break,
+      };
+      // Only the root itself is at depth 0, for patterns without any glob.
+      if entry.depth() == 0 {
+        paths.push(self.prefix.clone());
+        continue;
+      }
+      let relative = to_slash(entry.path(), entry.depth());
+      if self.matcher.is_match(&relative) {
+        paths.push(join(&self.prefix, &relative));
+      }
+    }
+    Ok(paths)
+  }
+}
+
+fn is_glob(component: &str) -> bool {
+  component.contains(|c| match c {
+    '*' | '?' | '[' | '{' => true,
+    _ => false,
+  })
+}
+
+/// Splits a pattern into the longest leading run of literal components and
+/// the glob that follows it.
+fn split_pattern(pattern: &str) -> (String, String) {
+  let components: Vec<&str> = pattern.split('/').collect();
+  let n = components
+    .iter()
+    .position(|c| is_glob(c))
+    .unwrap_or_else(|| components.len());
+  let prefix = match components[..n].join("/") {
+    ref p if p.is_empty() && n > 0 => "/".to_string(),
+    p => p,
+  };
+  (prefix, components[n..].join("/"))
+}
+
+/// Joins the last `depth` components of `path` with forward slashes, which is
+/// what the glob is matched against on every platform.
+fn to_slash(path: &Path, depth: usize) -> String {
+  let components: Vec<_> = path
+    .components()
+    .map(|c| c.as_os_str().to_string_lossy().into_owned())
+    .collect();
+  components[components.len() - depth..].join("/")
+}
+
+fn join(prefix: &str, relative: &str) -> String {
+  if prefix.is_empty() {
+    relative.to_string()
+  } else if prefix.ends_with('/') {
+    format!("{}{}", prefix, relative)
+  } else {
+    format!("{}/{}", prefix, relative)
+  }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GlobOpenArgs {
+  pattern: String,
+}
+
+fn op_glob_open(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: GlobOpenArgs = serde_json::from_value(args)?;
+  let (prefix, rest) = split_pattern(&args.pattern);
+
+  let (root, root_) =
+    deno_fs::resolve_from_cwd(if prefix.is_empty() { "." } else { &prefix })?;
+  state.check_read(&root_)?;
+
+  let matcher = GlobBuilder::new(&rest)
+    .literal_separator(true)
+    .build()
+    .map_err(|e| DenoError::new(ErrorKind::InvalidInput, e.to_string()))?
+    .compile_matcher();
+
+  debug!("op_glob_open {} in {}", rest, root_);
+  // A pattern without "**" can't match anything deeper than its own number
+  // of components.
+  let mut walker =
+    WalkDir::new(root).sort_by(|a, b| a.file_name().cmp(b.file_name()));
+  if rest.is_empty() {
+    walker = walker.max_depth(0);
+  } else if rest.contains("**") {
+    walker = walker.min_depth(1);
+  } else {
+    walker = walker.min_depth(1).max_depth(rest.split('/').count());
+  }
+
+  let resource = resources::add_glob_walker(GlobWalker {
+    prefix,
+    matcher,
+    iter: walker.into_iter(),
+  });
+  Ok(JsonOp::Sync(json!(resource.rid)))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GlobNextArgs {
+  promise_id: Option<u64>,
+  rid: i32,
+  batch_size: usize,
+}
+
+fn op_glob_next(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: GlobNextArgs = serde_json::from_value(args)?;
+  let walker = resources::get_glob_walker(args.rid as u32)?;
+  let batch_size = std::cmp::max(args.batch_size, 1);
+
+  let is_sync = args.promise_id.is_none();
+  blocking_json(is_sync, move || {
+    let paths = walker.lock().unwrap().next_batch(batch_size)?;
+    let done = paths.len() < batch_size;
+    Ok(json!({ "paths": paths, "done": done }))
+  })
+}
@@ -1,16 +1,32 @@
+use super::dispatch_json::{Deserialize, JsonOp, Value};
 use super::dispatch_minimal::MinimalOp;
 use crate::deno_error;
+use crate::deno_error::{DenoError, ErrorKind};
+use crate::ops::json_op;
 use crate::ops::minimal_op;
+use crate::read_pool::PooledBuf;
 use crate::resources;
 use crate::state::ThreadSafeState;
+use crate::tokio_copy;
 use crate::tokio_read;
 use crate::tokio_write;
 use deno::*;
 use futures::Future;
+use std::convert::TryInto;
 
 pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
-  i.register_op("read", s.core_op(minimal_op(op_read)));
-  i.register_op("write", s.core_op(minimal_op(op_write)));
+  s.register_op(i, "read", minimal_op(op_read));
+  s.register_op(i, "read_pooled", json_op(s.stateful_op(op_read_pooled)));
+  s.register_op(i, "read_buffer", |control, _zero_copy| {
+    op_read_buffer(control)
+  });
+  s.register_op(i, "write", minimal_op(op_write));
+  s.register_op(i, "transfer", json_op(s.stateful_op(op_transfer)));
+  s.register_op(i, "read_all", json_op(s.stateful_op(op_read_all)));
+  s.register_op(i, "write_all", json_op(s.stateful_op(op_write_all)));
+  s.register_op(i, "writev", json_op(s.stateful_op(op_writev)));
+  s.register_op(i, "tee", json_op(s.stateful_op(op_tee)));
+  s.register_op(i, "wait_ready", json_op(s.stateful_op(op_wait_ready)));
 }
 
 pub fn op_read(rid: i32, zero_copy: Option<PinnedBuf>) -> Box<MinimalOp> {
@@ -24,11 +40,56 @@ pub fn op_read(rid: i32, zero_copy: Option<PinnedBuf>) -> Box<MinimalOp> {
 
   match resources::lookup(rid as u32) {
     Err(e) => Box::new(futures::future::err(e)),
-    Ok(resource) => Box::new(
+    Ok(resource) => Box::new(resources::cancel_on_close(
+      rid as u32,
       tokio_read::read(resource, zero_copy)
         .map_err(ErrBox::from)
         .and_then(move |(_resource, _buf, nread)| Ok(nread as i32)),
-    ),
+    )),
+  }
+}
+
+#[derive(Deserialize)]
+struct ReadPooledArgs {
+  rid: i32,
+}
+
+/// Reads into a buffer of the pool, then adds the buffer as a resource, which
+/// `read_buffer` hands to JS. Resolves with its rid, or null at EOF.
+fn op_read_pooled(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: ReadPooledArgs = serde_json::from_value(args)?;
+  debug!("read_pooled rid={}", args.rid);
+
+  let resource = resources::lookup(args.rid as u32)?;
+  let op = tokio_read::read(resource, PooledBuf::take()).and_then(
+    |(_resource, mut buf, nread)| {
+      if nread == 0 {
+        return futures::future::ok(json!(null));
+      }
+      buf.set_len(nread);
+      futures::future::ok(json!(resources::add_read_buffer(buf).rid))
+    },
+  );
+  let op = resources::cancel_on_close(args.rid as u32, op);
+  Ok(JsonOp::Async(Box::new(op)))
+}
+
+/// Takes the buffer added by `read_pooled` out of the table and returns it as
+/// an external buffer, without copying it. The control buffer holds the rid
+/// as a little endian u32. A bad rid returns an empty response, which a read
+/// buffer never is.
+fn op_read_buffer(control: &[u8]) -> CoreOp {
+  let rid = match control.try_into() {
+    Ok(bytes) => u32::from_le_bytes(bytes),
+    Err(_) => panic!("bad read_buffer op control buffer"),
+  };
+  match resources::take_read_buffer(rid) {
+    Ok(buf) => Op::External(ExternalBuf::new(buf)),
+    Err(_) => Op::Sync(Vec::new().into_boxed_slice()),
   }
 }
 
@@ -43,10 +104,201 @@ pub fn op_write(rid: i32, zero_copy: Option<PinnedBuf>) -> Box<MinimalOp> {
 
   match resources::lookup(rid as u32) {
     Err(e) => Box::new(futures::future::err(e)),
-    Ok(resource) => Box::new(
+    Ok(resource) => Box::new(resources::cancel_on_close(
+      rid as u32,
       tokio_write::write(resource, zero_copy)
         .map_err(ErrBox::from)
         .and_then(move |(_resource, _buf, nwritten)| Ok(nwritten as i32)),
-    ),
+    )),
+  }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransferArgs {
+  src: i32,
+  dst: i32,
+  count: Option<u64>,
+}
+
+fn op_transfer(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: TransferArgs = serde_json::from_value(args)?;
+  debug!(
+    "transfer src={} dst={} count={:?}",
+    args.src, args.dst, args.count
+  );
+
+  let src = resources::lookup(args.src as u32)?;
+  let dst = resources::lookup(args.dst as u32)?;
+  let op = tokio_copy::copy(src, dst, args.count)?
+    .and_then(|copied| futures::future::ok(json!(copied)));
+  let op = resources::cancel_on_close(args.src as u32, op);
+  let op = resources::cancel_on_close(args.dst as u32, op);
+  Ok(JsonOp::Async(Box::new(op)))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReadAllArgs {
+  promise_id: Option<u64>,
+  rid: i32,
+}
+
+/// Reads into the zero-copy buffer until it is full or the resource reaches
+/// EOF, so that slurping a stream takes one op per buffer instead of one per
+/// chunk the resource happens to return.
+fn op_read_all(
+  _state: &ThreadSafeState,
+  args: Value,
+  zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: ReadAllArgs = serde_json::from_value(args)?;
+  debug!("read_all rid={}", args.rid);
+  let zero_copy = match zero_copy {
+    None => return Err(deno_error::no_buffer_specified()),
+    Some(buf) => buf,
+  };
+
+  let resource = resources::lookup(args.rid as u32)?;
+  let op = tokio_read::read_all(resource, zero_copy)
+    .and_then(|(_resource, _buf, nread)| futures::future::ok(json!(nread)));
+  let op = resources::cancel_on_close(args.rid as u32, op);
+  if args.promise_id.is_none() {
+    let buf = op.wait()?;
+    Ok(JsonOp::Sync(buf))
+  } else {
+    Ok(JsonOp::Async(Box::new(op)))
+  }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WriteAllArgs {
+  promise_id: Option<u64>,
+  rid: i32,
+}
+
+/// Writes the whole zero-copy buffer to the resource, looping over short
+/// writes on this side instead of in JS.
+fn op_write_all(
+  _state: &ThreadSafeState,
+  args: Value,
+  zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: WriteAllArgs = serde_json::from_value(args)?;
+  debug!("write_all rid={}", args.rid);
+  let zero_copy = match zero_copy {
+    None => return Err(deno_error::no_buffer_specified()),
+    Some(buf) => buf,
+  };
+
+  let resource = resources::lookup(args.rid as u32)?;
+  let op = tokio_write::write_all(resource, zero_copy).and_then(
+    |(_resource, _buf, nwritten)| futures::future::ok(json!(nwritten)),
+  );
+  let op = resources::cancel_on_close(args.rid as u32, op);
+  if args.promise_id.is_none() {
+    let buf = op.wait()?;
+    Ok(JsonOp::Sync(buf))
+  } else {
+    Ok(JsonOp::Async(Box::new(op)))
   }
 }
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WritevArgs {
+  promise_id: Option<u64>,
+  rid: i32,
+  /// The offset and length of each buffer in the zero-copy buffer.
+  ranges: Vec<(usize, usize)>,
+}
+
+/// Writes several buffers to the resource as a whole, without concatenating
+/// them, which is a single writev(2) for a TCP stream on Linux unless the
+/// write comes up short. An op only takes one zero-copy buffer, so they are
+/// passed as ranges of it.
+fn op_writev(
+  _state: &ThreadSafeState,
+  args: Value,
+  zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: WritevArgs = serde_json::from_value(args)?;
+  debug!("writev rid={} ranges={:?}", args.rid, args.ranges);
+  let zero_copy = match zero_copy {
+    None => return Err(deno_error::no_buffer_specified()),
+    Some(buf) => buf,
+  };
+
+  let mut ranges = Vec::with_capacity(args.ranges.len());
+  for (start, len) in args.ranges {
+    match start.checked_add(len) {
+      Some(end) if end <= zero_copy.len() => ranges.push(start..end),
+      _ => {
+        return Err(
+          DenoError::new(ErrorKind::InvalidInput, "range out of bounds".into())
+            .into(),
+        )
+      }
+    }
+  }
+
+  let rid = args.rid as u32;
+  let resource = resources::lookup(rid)?;
+  let op = tokio_write::write_vectored_all(resource, zero_copy, ranges)
+    .and_then(|(_resource, _buf, nwritten)| {
+      futures::future::ok(json!(nwritten))
+    });
+  let op = resources::cancel_on_close(rid, op);
+  if args.promise_id.is_none() {
+    let buf = op.wait()?;
+    Ok(JsonOp::Sync(buf))
+  } else {
+    Ok(JsonOp::Async(Box::new(op)))
+  }
+}
+
+#[derive(Deserialize)]
+struct TeeArgs {
+  rid: i32,
+}
+
+fn op_tee(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: TeeArgs = serde_json::from_value(args)?;
+  debug!("tee rid={}", args.rid);
+
+  let (a, b) = resources::add_tee(args.rid as u32)?;
+  Ok(JsonOp::Sync(json!([a.rid, b.rid])))
+}
+
+#[derive(Deserialize)]
+struct WaitReadyArgs {
+  rid: i32,
+  write: bool,
+}
+
+/// Resolves once the resource can be read from, or written to, without
+/// transferring anything.
+fn op_wait_ready(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: WaitReadyArgs = serde_json::from_value(args)?;
+  debug!("wait_ready rid={} write={}", args.rid, args.write);
+
+  let resource = resources::lookup(args.rid as u32)?;
+  let write = args.write;
+  let op = futures::future::poll_fn(move || resource.poll_ready(write))
+    .and_then(|()| futures::future::ok(json!({})));
+  let op = resources::cancel_on_close(args.rid as u32, op);
+  Ok(JsonOp::Async(Box::new(op)))
+}
@@ -0,0 +1,71 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+use super::dispatch_json::{Deserialize, JsonOp, Value};
+use crate::deno_error::DenoError;
+use crate::deno_error::ErrorKind;
+use crate::ops::json_op;
+use crate::resources;
+use crate::state::ThreadSafeState;
+use deno::*;
+use memmap::MmapOptions;
+use std::convert::TryInto;
+
+pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
+  s.register_op(i, "mmap", json_op(s.stateful_op(op_mmap)));
+  s.register_op(i, "mmap_buffer", |control, _zero_copy| {
+    op_mmap_buffer(control)
+  });
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MmapArgs {
+  rid: i32,
+}
+
+/// Maps a file and adds the mapping as a resource, which `mmap_buffer` then
+/// hands to JS. The mapping is private and writable, so writes from JS never
+/// fault and never reach the file.
+fn op_mmap(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: MmapArgs = serde_json::from_value(args)?;
+  let rid = args.rid as u32;
+
+  // Read permission was already checked when the file was opened.
+  let file = resources::get_file(rid)?;
+  if file.metadata()?.len() == 0 {
+    return Err(
+      DenoError::new(
+        ErrorKind::InvalidInput,
+        "Cannot map an empty file".to_string(),
+      )
+      .into(),
+    );
+  }
+
+  debug!("op_mmap {}", rid);
+  // The mapping stays valid when the file is closed. But if the file is
+  // truncated by someone else in the meantime, accessing the pages past its
+  // new end raises SIGBUS, which kills the process.
+  let mapping = unsafe { MmapOptions::new().map_copy(&file)? };
+  let resource = resources::add_mmap(mapping);
+  Ok(JsonOp::Sync(json!(resource.rid)))
+}
+
+/// Takes the mapping added by `mmap` out of the table and returns it as an
+/// external buffer, without copying it. The control buffer holds the rid as a
+/// little endian u32. A bad rid returns an empty response, which a mapping
+/// never is. The file is unmapped once JS releases or garbage collects the
+/// buffer.
+fn op_mmap_buffer(control: &[u8]) -> CoreOp {
+  let rid = match control.try_into() {
+    Ok(bytes) => u32::from_le_bytes(bytes),
+    Err(_) => panic!("bad mmap_buffer op control buffer"),
+  };
+  match resources::take_mmap(rid) {
+    Ok(mapping) => Op::External(ExternalBuf::new(mapping)),
+    Err(_) => Op::Sync(Vec::new().into_boxed_slice()),
+  }
+}
@@ -1,24 +1,61 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+use crate::state::ThreadSafeState;
+use deno::Isolate;
+use std::sync::Mutex;
+
 mod dispatch_json;
 mod dispatch_minimal;
 
 pub use dispatch_json::json_op;
+pub use dispatch_json::AsyncJsonOp;
 pub use dispatch_json::JsonOp;
 pub use dispatch_minimal::minimal_op;
 
+pub mod batch;
 pub mod compiler;
 pub mod errors;
 pub mod fetch;
 pub mod files;
 pub mod fs;
+pub mod fs_events;
+pub mod glob;
 pub mod io;
+pub mod mmap;
 pub mod net;
 pub mod os;
 pub mod permissions;
+pub mod plugins;
 pub mod process;
 pub mod random;
 pub mod repl;
 pub mod resources;
+pub mod ring;
+pub mod signal;
 pub mod timers;
 pub mod tls;
+pub mod tty;
 pub mod workers;
+pub mod xattr;
+
+/// Registers ops on the isolate of a worker, like the `init` function of each
+/// module above.
+pub type CustomOpsInit = fn(&mut Isolate, &ThreadSafeState);
+
+lazy_static! {
+  static ref CUSTOM_OPS: Mutex<Vec<CustomOpsInit>> = Mutex::new(Vec::new());
+}
+
+/// Lets crates which embed Deno add ops of their own, without patching this
+/// module. Every worker created afterwards gets them along with the built-in
+/// ones, so call it before `deno_cli::main()`. `init` registers them as the
+/// built-in ops are, e.g. with `json_op` and `ThreadSafeState::stateful_op`,
+/// and JavaScript calls them with `Deno.opSync()` and `Deno.opAsync()`.
+pub fn register_custom_ops(init: CustomOpsInit) {
+  CUSTOM_OPS.lock().unwrap().push(init);
+}
+
+pub fn init_custom(i: &mut Isolate, s: &ThreadSafeState) {
+  for init in CUSTOM_OPS.lock().unwrap().iter() {
+    init(i, s);
+  }
+}
@@ -1,6 +1,7 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
 use super::dispatch_json::{Deserialize, JsonOp, Value};
 use crate::ops::json_op;
+use crate::permissions::join_host_port;
 use crate::resolve_addr::resolve_addr;
 use crate::resources;
 use crate::resources::Resource;
@@ -16,10 +17,15 @@ use tokio::net::TcpListener;
 use tokio::net::TcpStream;
 
 pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
-  i.register_op("accept", s.core_op(json_op(s.stateful_op(op_accept))));
-  i.register_op("dial", s.core_op(json_op(s.stateful_op(op_dial))));
-  i.register_op("shutdown", s.core_op(json_op(s.stateful_op(op_shutdown))));
-  i.register_op("listen", s.core_op(json_op(s.stateful_op(op_listen))));
+  s.register_op(i, "accept", json_op(s.stateful_op(op_accept)));
+  s.register_op(i, "dial", json_op(s.stateful_op(op_dial)));
+  s.register_op(i, "shutdown", json_op(s.stateful_op(op_shutdown)));
+  s.register_op(
+    i,
+    "drain_listener",
+    json_op(s.stateful_op(op_drain_listener)),
+  );
+  s.register_op(i, "listen", json_op(s.stateful_op(op_listen)));
 }
 
 #[derive(Deserialize)]
@@ -51,6 +57,7 @@ fn op_accept(
         "remoteAddr": remote_addr.to_string(),
       }))
     });
+  let op = resources::cancel_on_close(server_rid, op);
 
   Ok(JsonOp::Async(Box::new(op)))
 }
@@ -70,9 +77,9 @@ fn op_dial(
   let args: DialArgs = serde_json::from_value(args)?;
   assert_eq!(args.transport, "tcp"); // TODO Support others.
 
-  // TODO(ry) Using format! is suboptimal here. Better would be if
+  // TODO(ry) Joining the address is suboptimal here. Better would be if
   // state.check_net and resolve_addr() took hostname and port directly.
-  let address = format!("{}:{}", args.hostname, args.port);
+  let address = join_host_port(&args.hostname, Some(&args.port.to_string()));
 
   state.check_net(&address)?;
 
@@ -126,6 +133,23 @@ fn op_shutdown(
   Ok(JsonOp::Sync(json!({})))
 }
 
+#[derive(Deserialize)]
+struct DrainListenerArgs {
+  rid: i32,
+}
+
+fn op_drain_listener(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: DrainListenerArgs = serde_json::from_value(args)?;
+
+  let resource = resources::lookup(args.rid as u32)?;
+  resource.drain()?;
+  Ok(JsonOp::Sync(json!({})))
+}
+
 #[derive(Deserialize)]
 struct ListenArgs {
   transport: String,
@@ -141,9 +165,9 @@ fn op_listen(
   let args: ListenArgs = serde_json::from_value(args)?;
   assert_eq!(args.transport, "tcp");
 
-  // TODO(ry) Using format! is suboptimal here. Better would be if
+  // TODO(ry) Joining the address is suboptimal here. Better would be if
   // state.check_net and resolve_addr() took hostname and port directly.
-  let address = format!("{}:{}", args.hostname, args.port);
+  let address = join_host_port(&args.hostname, Some(&args.port.to_string()));
 
   state.check_net(&address)?;
 
@@ -1,8 +1,11 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
 use super::dispatch_json::{Deserialize, JsonOp, Value};
 use crate::colors;
+use crate::deno_error::DenoError;
+use crate::deno_error::ErrorKind;
 use crate::fs as deno_fs;
 use crate::ops::json_op;
+use crate::resources;
 use crate::state::ThreadSafeState;
 use crate::version;
 use atty;
@@ -10,7 +13,7 @@ use deno::*;
 use log;
 use std::collections::HashMap;
 use std::env;
-use sys_info;
+use std::net::IpAddr;
 use url::Url;
 
 /// BUILD_OS and BUILD_ARCH match the values in Deno.build. See js/build.ts.
@@ -24,15 +27,25 @@ static BUILD_OS: &str = "win";
 static BUILD_ARCH: &str = "x64";
 
 pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
-  i.register_op("exit", s.core_op(json_op(s.stateful_op(op_exit))));
-  i.register_op("is_tty", s.core_op(json_op(s.stateful_op(op_is_tty))));
-  i.register_op("env", s.core_op(json_op(s.stateful_op(op_env))));
-  i.register_op("exec_path", s.core_op(json_op(s.stateful_op(op_exec_path))));
-  i.register_op("set_env", s.core_op(json_op(s.stateful_op(op_set_env))));
-  i.register_op("get_env", s.core_op(json_op(s.stateful_op(op_get_env))));
-  i.register_op("home_dir", s.core_op(json_op(s.stateful_op(op_home_dir))));
-  i.register_op("hostname", s.core_op(json_op(s.stateful_op(op_hostname))));
-  i.register_op("start", s.core_op(json_op(s.stateful_op(op_start))));
+  s.register_op(i, "exit", json_op(s.stateful_op(op_exit)));
+  s.register_op(i, "is_tty", json_op(s.stateful_op(op_is_tty)));
+  s.register_op(i, "env", json_op(s.stateful_op(op_env)));
+  s.register_op(i, "exec_path", json_op(s.stateful_op(op_exec_path)));
+  s.register_op(i, "set_env", json_op(s.stateful_op(op_set_env)));
+  s.register_op(i, "get_env", json_op(s.stateful_op(op_get_env)));
+  s.register_op(i, "delete_env", json_op(s.stateful_op(op_delete_env)));
+  s.register_op(i, "home_dir", json_op(s.stateful_op(op_home_dir)));
+  s.register_op(i, "hostname", json_op(s.stateful_op(op_hostname)));
+  s.register_op(i, "process_ids", json_op(s.stateful_op(op_process_ids)));
+  s.register_op(i, "os_info", json_op(s.stateful_op(op_os_info)));
+  s.register_op(i, "loadavg", json_op(s.stateful_op(op_loadavg)));
+  s.register_op(i, "mem_info", json_op(s.stateful_op(op_mem_info)));
+  s.register_op(
+    i,
+    "network_interfaces",
+    json_op(s.stateful_op(op_network_interfaces)),
+  );
+  s.register_op(i, "start", json_op(s.stateful_op(op_start)));
 }
 
 fn op_start(
@@ -99,18 +112,66 @@ fn op_set_env(
   _zero_copy: Option<PinnedBuf>,
 ) -> Result<JsonOp, ErrBox> {
   let args: SetEnv = serde_json::from_value(args)?;
-  state.check_env()?;
+  state.check_env_var(&args.key)?;
+  check_env_key(&args.key)?;
+  if args.value.contains('\0') {
+    return Err(invalid_env("value", &args.value));
+  }
   env::set_var(args.key, args.value);
   Ok(JsonOp::Sync(json!({})))
 }
 
+/// `env::set_var` and `env::remove_var` panic on keys the OS can't store.
+fn check_env_key(key: &str) -> Result<(), ErrBox> {
+  if key.is_empty() || key.contains('=') || key.contains('\0') {
+    return Err(invalid_env("key", key));
+  }
+  Ok(())
+}
+
+fn invalid_env(what: &str, s: &str) -> ErrBox {
+  DenoError::new(
+    ErrorKind::InvalidInput,
+    format!("Invalid environment variable {}: {:?}", what, s),
+  )
+  .into()
+}
+
+#[derive(Deserialize)]
+struct DeleteEnv {
+  key: String,
+}
+
+fn op_delete_env(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: DeleteEnv = serde_json::from_value(args)?;
+  state.check_env_var(&args.key)?;
+  check_env_key(&args.key)?;
+  env::remove_var(args.key);
+  Ok(JsonOp::Sync(json!({})))
+}
+
 fn op_env(
   state: &ThreadSafeState,
   _args: Value,
   _zero_copy: Option<PinnedBuf>,
 ) -> Result<JsonOp, ErrBox> {
-  state.check_env()?;
-  let v = env::vars().collect::<HashMap<String, String>>();
+  let permissions = &state.permissions;
+  // With only some variables allowed, the snapshot holds just those.
+  let v = if permissions.env_whitelist.is_empty() || permissions.allows_env() {
+    state.check_env()?;
+    env::vars().collect::<HashMap<String, String>>()
+  } else {
+    env::vars()
+      .filter(|(key, _)| {
+        permissions.env_whitelist.contains(key)
+          && state.check_env_var(key).is_ok()
+      })
+      .collect::<HashMap<String, String>>()
+  };
   Ok(JsonOp::Sync(json!(v)))
 }
 
@@ -125,7 +186,7 @@ fn op_get_env(
   _zero_copy: Option<PinnedBuf>,
 ) -> Result<JsonOp, ErrBox> {
   let args: GetEnv = serde_json::from_value(args)?;
-  state.check_env()?;
+  state.check_env_var(&args.key)?;
   let r = match env::var(args.key) {
     Err(env::VarError::NotPresent) => json!([]),
     v => json!([v?]),
@@ -144,6 +205,7 @@ fn op_exit(
   _zero_copy: Option<PinnedBuf>,
 ) -> Result<JsonOp, ErrBox> {
   let args: Exit = serde_json::from_value(args)?;
+  resources::cleanup();
   std::process::exit(args.code)
 }
 
@@ -165,6 +227,497 @@ fn op_hostname(
   _zero_copy: Option<PinnedBuf>,
 ) -> Result<JsonOp, ErrBox> {
   state.check_env()?;
-  let hostname = sys_info::hostname().unwrap_or_else(|_| "".to_owned());
-  Ok(JsonOp::Sync(json!(hostname)))
+  Ok(JsonOp::Sync(json!(hostname()?)))
+}
+
+#[cfg(unix)]
+fn hostname() -> Result<String, ErrBox> {
+  // Large enough for HOST_NAME_MAX on every platform, plus the NUL.
+  let mut buf = [0u8; 256];
+  let name = nix::unistd::gethostname(&mut buf)?;
+  Ok(name.to_string_lossy().into_owned())
+}
+
+#[cfg(not(unix))]
+fn hostname() -> Result<String, ErrBox> {
+  sys_info::hostname().map_err(sys_info_error)
+}
+
+fn op_process_ids(
+  _state: &ThreadSafeState,
+  _args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  Ok(JsonOp::Sync(process_ids()?))
+}
+
+#[cfg(unix)]
+fn process_ids() -> Result<Value, ErrBox> {
+  use nix::unistd;
+  Ok(json!({
+    "ppid": unistd::getppid().as_raw(),
+    "uid": unistd::getuid().as_raw(),
+    "gid": unistd::getgid().as_raw(),
+    "euid": unistd::geteuid().as_raw(),
+    "egid": unistd::getegid().as_raw(),
+  }))
+}
+
+/// Windows has no user or group ids, only security identifiers, so those are
+/// null.
+#[cfg(windows)]
+fn process_ids() -> Result<Value, ErrBox> {
+  Ok(json!({
+    "ppid": parent_pid()?,
+    "uid": null,
+    "gid": null,
+    "euid": null,
+    "egid": null,
+  }))
+}
+
+/// Finds our entry in a snapshot of all processes, which is the only place
+/// Windows keeps the parent's pid.
+#[cfg(windows)]
+fn parent_pid() -> Result<u32, ErrBox> {
+  use std::io::Error;
+  use std::mem;
+  use winapi::shared::minwindef::FALSE;
+  use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+  use winapi::um::tlhelp32::{
+    CreateToolhelp32Snapshot, Process32First, Process32Next, PROCESSENTRY32,
+    TH32CS_SNAPPROCESS,
+  };
+
+  let pid = std::process::id();
+  let snapshot = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) };
+  if snapshot == INVALID_HANDLE_VALUE {
+    return Err(Error::last_os_error().into());
+  }
+  let mut entry: PROCESSENTRY32 = unsafe { mem::zeroed() };
+  entry.dwSize = mem::size_of::<PROCESSENTRY32>() as u32;
+  let mut ppid = None;
+  let mut ok = unsafe { Process32First(snapshot, &mut entry) };
+  while ok != FALSE {
+    if entry.th32ProcessID == pid {
+      ppid = Some(entry.th32ParentProcessID);
+      break;
+    }
+    ok = unsafe { Process32Next(snapshot, &mut entry) };
+  }
+  unsafe { CloseHandle(snapshot) };
+  ppid.ok_or_else(|| {
+    DenoError::new(
+      ErrorKind::NotFound,
+      "Current process not found in process snapshot".to_string(),
+    )
+    .into()
+  })
+}
+
+fn op_os_info(
+  state: &ThreadSafeState,
+  _args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  state.check_env()?;
+  let kernel = kernel_info()?;
+  let (name, version) = os_name_version();
+  Ok(JsonOp::Sync(json!({
+    "os": BUILD_OS,
+    "arch": BUILD_ARCH,
+    "name": name,
+    "version": version,
+    "kernelName": kernel.name,
+    "kernelRelease": kernel.release,
+    "kernelVersion": kernel.version,
+    "machine": kernel.machine,
+  })))
+}
+
+struct KernelInfo {
+  name: String,
+  release: String,
+  version: String,
+  machine: String,
+}
+
+#[cfg(unix)]
+fn kernel_info() -> Result<KernelInfo, ErrBox> {
+  let uts = nix::sys::utsname::uname();
+  Ok(KernelInfo {
+    name: uts.sysname().to_string(),
+    release: uts.release().to_string(),
+    version: uts.version().to_string(),
+    machine: uts.machine().to_string(),
+  })
+}
+
+#[cfg(not(unix))]
+fn kernel_info() -> Result<KernelInfo, ErrBox> {
+  let release = sys_info::os_release().map_err(sys_info_error)?;
+  Ok(KernelInfo {
+    name: "Windows_NT".to_string(),
+    release,
+    version: String::new(),
+    machine: env::consts::ARCH.to_string(),
+  })
+}
+
+/// Returns the name and version of the distribution, e.g. ("Ubuntu",
+/// "18.04"), as found in os-release(5). Either is empty if unknown.
+#[cfg(target_os = "linux")]
+fn os_name_version() -> (String, String) {
+  let contents = std::fs::read_to_string("/etc/os-release")
+    .or_else(|_| std::fs::read_to_string("/usr/lib/os-release"))
+    .unwrap_or_default();
+  let mut name = String::new();
+  let mut version = String::new();
+  for line in contents.lines() {
+    let mut parts = line.splitn(2, '=');
+    let key = parts.next().unwrap_or("").trim();
+    let value = parts
+      .next()
+      .unwrap_or("")
+      .trim()
+      .trim_matches(|c| c == '"' || c == '\'');
+    match key {
+      "NAME" => name = value.to_string(),
+      "VERSION_ID" => version = value.to_string(),
+      _ => {}
+    }
+  }
+  if name.is_empty() {
+    name = "Linux".to_string();
+  }
+  (name, version)
+}
+
+#[cfg(target_os = "macos")]
+fn os_name_version() -> (String, String) {
+  // kern.osproductversion is only available since 10.13.4.
+  let mut buf = [0u8; 32];
+  let mut len = buf.len();
+  let key = b"kern.osproductversion\0";
+  let r = unsafe {
+    libc::sysctlbyname(
+      key.as_ptr() as *const libc::c_char,
+      buf.as_mut_ptr() as *mut libc::c_void,
+      &mut len,
+      std::ptr::null_mut(),
+      0,
+    )
+  };
+  let version = if r == 0 && len > 0 {
+    String::from_utf8_lossy(&buf[..len - 1]).into_owned()
+  } else {
+    String::new()
+  };
+  ("macOS".to_string(), version)
+}
+
+#[cfg(windows)]
+fn os_name_version() -> (String, String) {
+  let version = sys_info::os_release().unwrap_or_default();
+  ("Windows".to_string(), version)
+}
+
+fn sys_info_error(e: sys_info::Error) -> ErrBox {
+  DenoError::new(ErrorKind::Other, e.to_string()).into()
+}
+
+fn op_loadavg(
+  state: &ThreadSafeState,
+  _args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  state.check_env()?;
+  Ok(JsonOp::Sync(json!(loadavg()?)))
+}
+
+#[cfg(unix)]
+fn loadavg() -> Result<[f64; 3], ErrBox> {
+  let load = sys_info::loadavg().map_err(sys_info_error)?;
+  Ok([load.one, load.five, load.fifteen])
+}
+
+/// Windows has no load average, report zeros like Node does.
+#[cfg(not(unix))]
+fn loadavg() -> Result<[f64; 3], ErrBox> {
+  Ok([0.0, 0.0, 0.0])
+}
+
+fn op_mem_info(
+  state: &ThreadSafeState,
+  _args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  state.check_env()?;
+  let mem = sys_info::mem_info().map_err(sys_info_error)?;
+  // sys_info reports kibibytes.
+  Ok(JsonOp::Sync(json!({
+    "total": mem.total * 1024,
+    "free": mem.free * 1024,
+    "available": mem.avail * 1024,
+    "swapTotal": mem.swap_total * 1024,
+    "swapFree": mem.swap_free * 1024,
+    "rss": rss()?,
+  })))
+}
+
+/// Returns the resident set size of this process in bytes.
+#[cfg(target_os = "linux")]
+fn rss() -> Result<u64, ErrBox> {
+  // The second field of statm is the number of resident pages.
+  let statm = std::fs::read_to_string("/proc/self/statm")?;
+  let pages: u64 = statm
+    .split_whitespace()
+    .nth(1)
+    .and_then(|s| s.parse().ok())
+    .ok_or_else(|| {
+      DenoError::new(ErrorKind::Other, "malformed /proc/self/statm".to_string())
+    })?;
+  let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as u64;
+  Ok(pages * page_size)
+}
+
+#[cfg(target_os = "macos")]
+fn rss() -> Result<u64, ErrBox> {
+  let mut info: libc::proc_taskinfo = unsafe { std::mem::zeroed() };
+  let size = std::mem::size_of::<libc::proc_taskinfo>() as libc::c_int;
+  let r = unsafe {
+    libc::proc_pidinfo(
+      libc::getpid(),
+      libc::PROC_PIDTASKINFO,
+      0,
+      &mut info as *mut _ as *mut libc::c_void,
+      size,
+    )
+  };
+  if r != size {
+    return Err(std::io::Error::last_os_error().into());
+  }
+  Ok(info.pti_resident_size)
+}
+
+#[cfg(windows)]
+fn rss() -> Result<u64, ErrBox> {
+  use winapi::um::processthreadsapi::GetCurrentProcess;
+  use winapi::um::psapi::GetProcessMemoryInfo;
+  use winapi::um::psapi::PROCESS_MEMORY_COUNTERS;
+  let mut counters: PROCESS_MEMORY_COUNTERS = unsafe { std::mem::zeroed() };
+  let size = std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32;
+  let ok =
+    unsafe { GetProcessMemoryInfo(GetCurrentProcess(), &mut counters, size) };
+  if ok == 0 {
+    return Err(std::io::Error::last_os_error().into());
+  }
+  Ok(counters.WorkingSetSize as u64)
+}
+
+struct NetworkInterface {
+  name: String,
+  mac: Option<String>,
+  up: bool,
+  loopback: bool,
+  addresses: Vec<(IpAddr, Option<IpAddr>)>,
+}
+
+fn op_network_interfaces(
+  state: &ThreadSafeState,
+  _args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  state.check_env()?;
+  let interfaces: Vec<Value> = network_interfaces()?
+    .into_iter()
+    .map(|iface| {
+      let addresses: Vec<Value> = iface
+        .addresses
+        .iter()
+        .map(|(address, netmask)| {
+          json!({
+            "family": if address.is_ipv4() { "IPv4" } else { "IPv6" },
+            "address": address.to_string(),
+            "netmask": netmask.map(|m| m.to_string()),
+          })
+        })
+        .collect();
+      json!({
+        "name": iface.name,
+        "mac": iface.mac,
+        "up": iface.up,
+        "loopback": iface.loopback,
+        "addresses": addresses,
+      })
+    })
+    .collect();
+  Ok(JsonOp::Sync(json!(interfaces)))
+}
+
+fn format_mac(bytes: &[u8]) -> String {
+  let octets: Vec<String> =
+    bytes.iter().map(|b| format!("{:02x}", b)).collect();
+  octets.join(":")
+}
+
+#[cfg(unix)]
+fn network_interfaces() -> Result<Vec<NetworkInterface>, ErrBox> {
+  use nix::ifaddrs::getifaddrs;
+  use nix::net::if_::InterfaceFlags;
+  use nix::sys::socket::SockAddr;
+
+  // getifaddrs returns one entry per address, so entries are grouped by the
+  // name of their interface, in the order the interfaces are first seen.
+  let mut interfaces: Vec<NetworkInterface> = Vec::new();
+  for ifaddr in getifaddrs()? {
+    let index = match interfaces
+      .iter()
+      .position(|iface| iface.name == ifaddr.interface_name)
+    {
+      Some(index) => index,
+      None => {
+        interfaces.push(NetworkInterface {
+          name: ifaddr.interface_name.clone(),
+          mac: None,
+          up: ifaddr.flags.contains(InterfaceFlags::IFF_UP),
+          loopback: ifaddr.flags.contains(InterfaceFlags::IFF_LOOPBACK),
+          addresses: Vec::new(),
+        });
+        interfaces.len() - 1
+      }
+    };
+    let iface = &mut interfaces[index];
+    match ifaddr.address {
+      Some(SockAddr::Inet(addr)) => {
+        let netmask = match ifaddr.netmask {
+          Some(SockAddr::Inet(mask)) => Some(mask.ip().to_std()),
+          _ => None,
+        };
+        iface.addresses.push((addr.ip().to_std(), netmask));
+      }
+      Some(SockAddr::Link(link)) => {
+        let mac = link.addr();
+        // Interfaces without a hardware address, like loopback, report zeros.
+        if mac.iter().any(|b| *b != 0) {
+          iface.mac = Some(format_mac(&mac));
+        }
+      }
+      _ => {}
+    }
+  }
+  Ok(interfaces)
+}
+
+#[cfg(windows)]
+fn network_interfaces() -> Result<Vec<NetworkInterface>, ErrBox> {
+  use std::net::Ipv4Addr;
+  use std::net::Ipv6Addr;
+  use winapi::shared::ifdef::IfOperStatusUp;
+  use winapi::shared::ipifcons::IF_TYPE_SOFTWARE_LOOPBACK;
+  use winapi::shared::winerror::ERROR_BUFFER_OVERFLOW;
+  use winapi::shared::winerror::ERROR_SUCCESS;
+  use winapi::shared::ws2def::AF_INET;
+  use winapi::shared::ws2def::AF_INET6;
+  use winapi::shared::ws2def::AF_UNSPEC;
+  use winapi::shared::ws2def::SOCKADDR_IN;
+  use winapi::shared::ws2ipdef::SOCKADDR_IN6_LH;
+  use winapi::um::iphlpapi::GetAdaptersAddresses;
+  use winapi::um::iptypes::GAA_FLAG_SKIP_ANYCAST;
+  use winapi::um::iptypes::GAA_FLAG_SKIP_DNS_SERVER;
+  use winapi::um::iptypes::GAA_FLAG_SKIP_MULTICAST;
+  use winapi::um::iptypes::IP_ADAPTER_ADDRESSES;
+
+  // The buffer holds a linked list of adapters. Retry with the size the call
+  // asks for until the list fits.
+  let mut size: u32 = 16 * 1024;
+  let mut buf: Vec<u64>;
+  loop {
+    buf = vec![0; (size as usize + 7) / 8];
+    let r = unsafe {
+      GetAdaptersAddresses(
+        AF_UNSPEC as u32,
+        GAA_FLAG_SKIP_ANYCAST
+          | GAA_FLAG_SKIP_MULTICAST
+          | GAA_FLAG_SKIP_DNS_SERVER,
+        std::ptr::null_mut(),
+        buf.as_mut_ptr() as *mut IP_ADAPTER_ADDRESSES,
+        &mut size,
+      )
+    };
+    match r {
+      ERROR_SUCCESS => break,
+      ERROR_BUFFER_OVERFLOW => continue,
+      e => return Err(std::io::Error::from_raw_os_error(e as i32).into()),
+    }
+  }
+
+  let mut interfaces = Vec::new();
+  let mut adapter = buf.as_ptr() as *const IP_ADAPTER_ADDRESSES;
+  while !adapter.is_null() {
+    let a = unsafe { &*adapter };
+    let mut addresses = Vec::new();
+    let mut unicast = a.FirstUnicastAddress;
+    while !unicast.is_null() {
+      let u = unsafe { &*unicast };
+      let sockaddr = u.Address.lpSockaddr;
+      let prefix = u32::from(u.OnLinkPrefixLength);
+      match i32::from(unsafe { (*sockaddr).sa_family }) {
+        AF_INET => {
+          let sin = unsafe { &*(sockaddr as *const SOCKADDR_IN) };
+          let ip = u32::from_be(unsafe { *sin.sin_addr.S_un.S_addr() });
+          let mask = if prefix == 0 {
+            0
+          } else {
+            !0u32 << (32 - prefix)
+          };
+          addresses.push((
+            IpAddr::V4(Ipv4Addr::from(ip)),
+            Some(IpAddr::V4(Ipv4Addr::from(mask))),
+          ));
+        }
+        AF_INET6 => {
+          let sin6 = unsafe { &*(sockaddr as *const SOCKADDR_IN6_LH) };
+          let ip = unsafe { *sin6.sin6_addr.u.Byte() };
+          let mask = if prefix == 0 {
+            0
+          } else {
+            !0u128 << (128 - prefix)
+          };
+          addresses.push((
+            IpAddr::V6(Ipv6Addr::from(ip)),
+            Some(IpAddr::V6(Ipv6Addr::from(mask))),
+          ));
+        }
+        _ => {}
+      }
+      unicast = u.Next;
+    }
+
+    let mac_len = a.PhysicalAddressLength as usize;
+    interfaces.push(NetworkInterface {
+      name: wide_to_string(a.FriendlyName),
+      mac: if mac_len > 0 {
+        Some(format_mac(&a.PhysicalAddress[..mac_len]))
+      } else {
+        None
+      },
+      up: a.OperStatus == IfOperStatusUp,
+      loopback: a.IfType == IF_TYPE_SOFTWARE_LOOPBACK,
+      addresses,
+    });
+    adapter = a.Next;
+  }
+  Ok(interfaces)
+}
+
+#[cfg(windows)]
+fn wide_to_string(s: *const u16) -> String {
+  let mut len = 0;
+  unsafe {
+    while *s.add(len) != 0 {
+      len += 1;
+    }
+    String::from_utf16_lossy(std::slice::from_raw_parts(s, len))
+  }
 }
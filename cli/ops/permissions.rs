@@ -5,13 +5,21 @@ use crate::state::ThreadSafeState;
 use deno::*;
 
 pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
-  i.register_op(
-    "permissions",
-    s.core_op(json_op(s.stateful_op(op_permissions))),
+  s.register_op(i, "permissions", json_op(s.stateful_op(op_permissions)));
+  s.register_op(
+    i,
+    "permission_descriptors",
+    json_op(s.stateful_op(op_permission_descriptors)),
   );
-  i.register_op(
+  s.register_op(
+    i,
+    "request_permission",
+    json_op(s.stateful_op(op_request_permission)),
+  );
+  s.register_op(
+    i,
     "revoke_permission",
-    s.core_op(json_op(s.stateful_op(op_revoke_permission))),
+    json_op(s.stateful_op(op_revoke_permission)),
   );
 }
 
@@ -27,9 +35,33 @@ pub fn op_permissions(
     "net": state.permissions.allows_net(),
     "env": state.permissions.allows_env(),
     "hrtime": state.permissions.allows_hrtime(),
+    "plugin": state.permissions.allows_plugin(),
   })))
 }
 
+pub fn op_permission_descriptors(
+  state: &ThreadSafeState,
+  _args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  Ok(JsonOp::Sync(json!(state.permissions.descriptors())))
+}
+
+#[derive(Deserialize)]
+struct RequestPermissionArgs {
+  permission: String,
+}
+
+pub fn op_request_permission(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: RequestPermissionArgs = serde_json::from_value(args)?;
+  let granted = state.permissions.request(&args.permission)?;
+  Ok(JsonOp::Sync(json!(granted)))
+}
+
 #[derive(Deserialize)]
 struct RevokePermissionArgs {
   permission: String,
@@ -49,6 +81,7 @@ pub fn op_revoke_permission(
     "net" => state.permissions.revoke_net(),
     "env" => state.permissions.revoke_env(),
     "hrtime" => state.permissions.revoke_hrtime(),
+    "plugin" => state.permissions.revoke_plugin(),
     _ => Ok(()),
   }?;
 
@@ -0,0 +1,90 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+use super::dispatch_json::{Deserialize, JsonOp, Value};
+use crate::deno_error::DenoError;
+use crate::deno_error::ErrorKind;
+use crate::fs as deno_fs;
+use crate::ops::json_op;
+use crate::state::ThreadSafeState;
+use deno::*;
+use dlopen::symbor::Library;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
+  let op_registry = i.op_registry();
+  s.register_op(
+    i,
+    "open_plugin",
+    json_op(s.stateful_op(move |state, args, zero_copy| {
+      op_open_plugin(&op_registry, state, args, zero_copy)
+    })),
+  );
+}
+
+lazy_static! {
+  // Plugins are never unloaded, as the ops they registered may run as long as
+  // the process does.
+  static ref LIBRARIES: Mutex<Vec<Library>> = Mutex::new(Vec::new());
+}
+
+#[derive(Default)]
+struct PluginInitContextImpl {
+  ops: Vec<(String, Box<OpDispatcher>)>,
+}
+
+impl PluginInitContext for PluginInitContextImpl {
+  fn register_op(&mut self, name: &str, op: Box<OpDispatcher>) {
+    self.ops.push((name.to_string(), op));
+  }
+}
+
+#[derive(Deserialize)]
+struct OpenPluginArgs {
+  filename: String,
+}
+
+fn op_open_plugin(
+  op_registry: &Arc<OpRegistry>,
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: OpenPluginArgs = serde_json::from_value(args)?;
+  let (filename, filename_) = deno_fs::resolve_from_cwd(&args.filename)?;
+
+  state.check_plugin(&filename_)?;
+
+  let library = Library::open(&filename).map_err(|e| {
+    DenoError::new(
+      ErrorKind::Other,
+      format!("Could not open plugin \"{}\": {}", filename_, e),
+    )
+  })?;
+  let init_fn = unsafe { library.symbol::<PluginInitFn>(PLUGIN_INIT_SYMBOL) }
+    .map_err(|e| {
+    DenoError::new(
+      ErrorKind::Other,
+      format!("Not a plugin \"{}\": {}", filename_, e),
+    )
+  })?;
+
+  let mut context = PluginInitContextImpl::default();
+  init_fn(&mut context);
+  LIBRARIES.lock().unwrap().push(library);
+
+  // Opening a plugin twice, or two plugins with an op of the same name,
+  // fails instead of replacing ops which JavaScript may be using. None of the
+  // ops are registered then.
+  let names: Vec<String> =
+    context.ops.iter().map(|(name, _)| name.clone()).collect();
+  let op_ids = op_registry.register_all(context.ops).map_err(|name| {
+    DenoError::new(
+      ErrorKind::AlreadyExists,
+      format!("Op already registered: {}", name),
+    )
+  })?;
+  let ops: HashMap<String, OpId> = names.into_iter().zip(op_ids).collect();
+
+  Ok(JsonOp::Sync(json!({ "ops": ops })))
+}
@@ -1,27 +1,39 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
 use super::dispatch_json::{Deserialize, JsonOp, Value};
+use crate::deno_error::DenoError;
+use crate::deno_error::ErrorKind;
 use crate::ops::json_op;
 use crate::resources;
-use crate::signal::kill;
+use crate::signal::{kill, kill_group, signal_from_name};
 use crate::state::ThreadSafeState;
 use deno::*;
 use futures;
-use futures::Future;
+use futures::future::Either;
+use futures::sync::oneshot;
+use futures::{Async, Future, Poll};
 use std;
 use std::convert::From;
-use std::process::Command;
+use std::path::PathBuf;
+use std::process::{Command, ExitStatus};
+use std::time::{Duration, Instant};
+use tokio::io::AsyncRead;
+use tokio::timer::Delay;
 use tokio_process::CommandExt;
 
 #[cfg(unix)]
 use std::os::unix::process::ExitStatusExt;
 
 pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
-  i.register_op("run", s.core_op(json_op(s.stateful_op(op_run))));
-  i.register_op(
-    "run_status",
-    s.core_op(json_op(s.stateful_op(op_run_status))),
-  );
-  i.register_op("kill", s.core_op(json_op(s.stateful_op(op_kill))));
+  s.register_op(i, "run", json_op(s.stateful_op(op_run)));
+  s.register_op(i, "exec", json_op(s.stateful_op(op_exec)));
+  s.register_op(i, "set_ids", json_op(s.stateful_op(op_set_ids)));
+  s.register_op(i, "run_status", json_op(s.stateful_op(op_run_status)));
+  s.register_op(i, "run_output", json_op(s.stateful_op(op_run_output)));
+  s.register_op(i, "kill", json_op(s.stateful_op(op_kill)));
+  s.register_op(i, "get_priority", json_op(s.stateful_op(op_get_priority)));
+  s.register_op(i, "set_priority", json_op(s.stateful_op(op_set_priority)));
+  s.register_op(i, "kill_group", json_op(s.stateful_op(op_kill_group)));
+  s.register_op(i, "pty_resize", json_op(s.stateful_op(op_pty_resize)));
 }
 
 fn subprocess_stdio_map(s: &str) -> std::process::Stdio {
@@ -39,12 +51,46 @@ struct RunArgs {
   args: Vec<String>,
   cwd: Option<String>,
   env: Vec<(String, String)>,
+  clear_env: bool,
+  inherit_env: Option<Vec<String>>,
   stdin: String,
   stdout: String,
   stderr: String,
   stdin_rid: u32,
   stdout_rid: u32,
   stderr_rid: u32,
+  detached: bool,
+  process_group: bool,
+  limits: Option<RunLimits>,
+  uid: Option<u32>,
+  gid: Option<u32>,
+  timeout: Option<u64>,
+  pty: Option<PtySize>,
+}
+
+#[derive(Deserialize)]
+struct PtySize {
+  columns: u16,
+  rows: u16,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RunLimits {
+  cpu: Option<u64>,
+  address_space: Option<u64>,
+  open_files: Option<u64>,
+}
+
+/// Checks the permission to run `program` and returns what to spawn for it:
+/// the path it was checked as, if it passed through the run whitelist.
+fn check_run_program(
+  state: &ThreadSafeState,
+  program: &str,
+  cwd: Option<&String>,
+) -> Result<PathBuf, ErrBox> {
+  let resolved = state.check_run_program(program, cwd.map(String::as_str))?;
+  Ok(resolved.unwrap_or_else(|| PathBuf::from(program)))
 }
 
 fn op_run(
@@ -54,49 +100,94 @@ fn op_run(
 ) -> Result<JsonOp, ErrBox> {
   let run_args: RunArgs = serde_json::from_value(args)?;
 
-  state.check_run()?;
+  let program =
+    check_run_program(state, &run_args.args[0], run_args.cwd.as_ref())?;
 
   let args = run_args.args;
   let env = run_args.env;
   let cwd = run_args.cwd;
 
-  let mut c = Command::new(args.get(0).unwrap());
+  let mut c = Command::new(program);
   (1..args.len()).for_each(|i| {
     let arg = args.get(i).unwrap();
     c.arg(arg);
   });
   cwd.map(|d| c.current_dir(d));
+  if run_args.clear_env || run_args.inherit_env.is_some() {
+    c.env_clear();
+    for key in run_args.inherit_env.iter().flatten() {
+      if let Some(value) = std::env::var_os(key) {
+        c.env(key, value);
+      }
+    }
+  }
   for (key, value) in &env {
     c.env(key, value);
   }
 
-  // TODO: make this work with other resources, eg. sockets
   let stdin_rid = run_args.stdin_rid;
   if stdin_rid > 0 {
-    c.stdin(resources::get_file(stdin_rid)?);
+    c.stdin(resources::get_stdio(stdin_rid)?);
   } else {
     c.stdin(subprocess_stdio_map(run_args.stdin.as_ref()));
   }
 
   let stdout_rid = run_args.stdout_rid;
   if stdout_rid > 0 {
-    c.stdout(resources::get_file(stdout_rid)?);
+    c.stdout(resources::get_stdio(stdout_rid)?);
   } else {
     c.stdout(subprocess_stdio_map(run_args.stdout.as_ref()));
   }
 
   let stderr_rid = run_args.stderr_rid;
   if stderr_rid > 0 {
-    c.stderr(resources::get_file(stderr_rid)?);
+    c.stderr(resources::get_stdio(stderr_rid)?);
   } else {
     c.stderr(subprocess_stdio_map(run_args.stderr.as_ref()));
   }
 
-  // Spawn the command.
+  // The pseudo-terminal replaces whatever stdio was asked for.
+  let pty = match run_args.pty {
+    Some(size) => Some(attach_pty(&mut c, size)?),
+    None => None,
+  };
+
+  // A new session comes with a new process group.
+  if pty.is_some() {
+    // attach_pty already starts the child in a session of its own.
+  } else if run_args.detached {
+    detach(&mut c);
+  } else if run_args.process_group {
+    new_process_group(&mut c);
+  }
+  if let Some(limits) = run_args.limits {
+    set_limits(&mut c, limits)?;
+  }
+  set_child_ids(&mut c, run_args.uid, run_args.gid)?;
+
+  // Spawn the command. Piped stdio is driven by the reactor rather than by
+  // blocking reads on a thread: on Windows our ends of the pipes are
+  // overlapped named pipes whose reads and writes complete through IOCP.
   let child = c.spawn_async().map_err(ErrBox::from)?;
 
   let pid = child.id();
-  let resources = resources::add_child(child);
+  let (cancel_timeout, timeout) = match run_args.timeout {
+    Some(ms) => {
+      let (sender, receiver) = oneshot::channel();
+      (Some(sender), Some((ms, receiver)))
+    }
+    None => (None, None),
+  };
+  let resources = resources::add_child(
+    child,
+    run_args.detached,
+    pty.is_some() || run_args.detached || run_args.process_group,
+    cancel_timeout,
+  );
+  if let Some((ms, cancelled)) = timeout {
+    spawn_timeout(resources.child_rid, ms, cancelled);
+  }
+  let pty_rid = pty.map(add_pty);
 
   Ok(JsonOp::Sync(json!({
     "rid": resources.child_rid,
@@ -104,9 +195,332 @@ fn op_run(
     "stdinRid": resources.stdin_rid,
     "stdoutRid": resources.stdout_rid,
     "stderrRid": resources.stderr_rid,
+    "ptyRid": pty_rid,
   })))
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExecArgs {
+  args: Vec<String>,
+  cwd: Option<String>,
+  env: Vec<(String, String)>,
+}
+
+fn op_exec(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let exec_args: ExecArgs = serde_json::from_value(args)?;
+
+  let program =
+    check_run_program(state, &exec_args.args[0], exec_args.cwd.as_ref())?;
+
+  let args = exec_args.args;
+  let mut c = Command::new(program);
+  c.args(&args[1..]);
+  exec_args.cwd.map(|d| c.current_dir(d));
+  for (key, value) in &exec_args.env {
+    c.env(key, value);
+  }
+  Err(exec(c))
+}
+
+/// Replaces the current process with `c`, looking it up in `PATH` like
+/// execvp(3) does. Only returns if that fails.
+#[cfg(unix)]
+fn exec(mut c: Command) -> ErrBox {
+  use std::os::unix::process::CommandExt;
+  ErrBox::from(c.exec())
+}
+
+/// Windows can't replace a process, so `c` is run with our stdio instead and
+/// we exit with its exit code once it is done.
+#[cfg(windows)]
+fn exec(mut c: Command) -> ErrBox {
+  match c.status() {
+    Ok(status) => {
+      resources::cleanup();
+      std::process::exit(status.code().unwrap_or(1))
+    }
+    Err(e) => ErrBox::from(e),
+  }
+}
+
+/// Kills the child `rid` once `ms` milliseconds have passed, unless it has
+/// exited or been closed before, either of which cancels the timer.
+fn spawn_timeout(rid: u32, ms: u64, cancelled: oneshot::Receiver<()>) {
+  let deadline = Instant::now() + Duration::from_millis(ms);
+  let timer = Delay::new(deadline)
+    .map_err(|_| ())
+    .select2(cancelled.then(|_| Ok::<(), ()>(())))
+    .then(move |result| {
+      if let Ok(Either::A(_)) = result {
+        resources::kill_timed_out_child(rid);
+      }
+      Ok(())
+    });
+  tokio::spawn(timer);
+}
+
+/// Starts the child in a session of its own, so that it is neither part of
+/// our process group nor hung up on when our controlling terminal goes away.
+#[cfg(unix)]
+fn detach(c: &mut Command) {
+  use std::os::unix::process::CommandExt;
+  unsafe {
+    c.pre_exec(|| {
+      if libc::setsid() == -1 {
+        return Err(std::io::Error::last_os_error());
+      }
+      Ok(())
+    });
+  }
+}
+
+/// Starts the child without our console and in a process group of its own,
+/// so that it doesn't receive the Ctrl+C meant for us.
+#[cfg(windows)]
+fn detach(c: &mut Command) {
+  use std::os::windows::process::CommandExt;
+  use winapi::um::winbase::{CREATE_NEW_PROCESS_GROUP, DETACHED_PROCESS};
+  c.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
+}
+
+/// Starts the child as the leader of a new process group, so that it and
+/// everything it spawns can be signaled together.
+#[cfg(unix)]
+fn new_process_group(c: &mut Command) {
+  use std::os::unix::process::CommandExt;
+  unsafe {
+    c.pre_exec(|| {
+      if libc::setpgid(0, 0) == -1 {
+        return Err(std::io::Error::last_os_error());
+      }
+      Ok(())
+    });
+  }
+}
+
+#[cfg(windows)]
+fn new_process_group(c: &mut Command) {
+  use std::os::windows::process::CommandExt;
+  use winapi::um::winbase::CREATE_NEW_PROCESS_GROUP;
+  c.creation_flags(CREATE_NEW_PROCESS_GROUP);
+}
+
+/// Lowers both the soft and the hard limits in the child, so that it can't
+/// raise them again.
+#[cfg(unix)]
+fn set_limits(c: &mut Command, limits: RunLimits) -> Result<(), ErrBox> {
+  use std::os::unix::process::CommandExt;
+  let rlimits: Vec<_> = vec![
+    (libc::RLIMIT_CPU, limits.cpu),
+    (libc::RLIMIT_AS, limits.address_space),
+    (libc::RLIMIT_NOFILE, limits.open_files),
+  ]
+  .into_iter()
+  .filter_map(|(resource, value)| value.map(|v| (resource, v)))
+  .collect();
+  unsafe {
+    c.pre_exec(move || {
+      for &(resource, value) in &rlimits {
+        let limit = libc::rlimit {
+          rlim_cur: value as libc::rlim_t,
+          rlim_max: value as libc::rlim_t,
+        };
+        if libc::setrlimit(resource, &limit) == -1 {
+          return Err(std::io::Error::last_os_error());
+        }
+      }
+      Ok(())
+    });
+  }
+  Ok(())
+}
+
+#[cfg(windows)]
+fn set_limits(_c: &mut Command, limits: RunLimits) -> Result<(), ErrBox> {
+  if limits.cpu.is_none()
+    && limits.address_space.is_none()
+    && limits.open_files.is_none()
+  {
+    return Ok(());
+  }
+  Err(
+    DenoError::new(
+      ErrorKind::OpNotAvailable,
+      "Resource limits are not supported on Windows".to_string(),
+    )
+    .into(),
+  )
+}
+
+#[cfg(unix)]
+type PtyMaster = crate::pty::PtyMaster;
+
+/// Pseudo-terminals are Unix only, so there is never one on Windows.
+#[cfg(windows)]
+enum PtyMaster {}
+
+#[cfg(windows)]
+fn pty_not_supported() -> ErrBox {
+  DenoError::new(
+    ErrorKind::OpNotAvailable,
+    "Pseudo-terminals are not supported on Windows".to_string(),
+  )
+  .into()
+}
+
+/// Gives the child a new pseudo-terminal as its stdio and controlling
+/// terminal. Returns the master side.
+#[cfg(unix)]
+fn attach_pty(c: &mut Command, size: PtySize) -> Result<PtyMaster, ErrBox> {
+  let (master, slave) = crate::pty::open(size.columns, size.rows)?;
+  c.stdin(slave.try_clone()?);
+  c.stdout(slave.try_clone()?);
+  c.stderr(slave);
+  crate::pty::set_controlling_terminal(c);
+  Ok(master)
+}
+
+#[cfg(windows)]
+fn attach_pty(_c: &mut Command, _size: PtySize) -> Result<PtyMaster, ErrBox> {
+  Err(pty_not_supported())
+}
+
+#[cfg(unix)]
+fn add_pty(master: PtyMaster) -> u32 {
+  resources::add_pty(master).rid
+}
+
+#[cfg(windows)]
+fn add_pty(master: PtyMaster) -> u32 {
+  match master {}
+}
+
+#[derive(Deserialize)]
+struct PtyResizeArgs {
+  rid: i32,
+  columns: u16,
+  rows: u16,
+}
+
+fn op_pty_resize(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: PtyResizeArgs = serde_json::from_value(args)?;
+  pty_resize(args.rid as u32, args.columns, args.rows)?;
+  Ok(JsonOp::Sync(json!({})))
+}
+
+#[cfg(unix)]
+fn pty_resize(rid: u32, columns: u16, rows: u16) -> Result<(), ErrBox> {
+  resources::resize_pty(rid, columns, rows)
+}
+
+#[cfg(windows)]
+fn pty_resize(_rid: u32, _columns: u16, _rows: u16) -> Result<(), ErrBox> {
+  Err(pty_not_supported())
+}
+
+#[cfg(windows)]
+fn ids_not_supported() -> ErrBox {
+  DenoError::new(
+    ErrorKind::OpNotAvailable,
+    "Changing user or group ids is not supported on Windows".to_string(),
+  )
+  .into()
+}
+
+/// Runs the child as another user and/or group.
+#[cfg(unix)]
+fn set_child_ids(
+  c: &mut Command,
+  uid: Option<u32>,
+  gid: Option<u32>,
+) -> Result<(), ErrBox> {
+  use std::os::unix::process::CommandExt;
+  if let Some(gid) = gid {
+    c.gid(gid);
+  }
+  if let Some(uid) = uid {
+    c.uid(uid);
+  }
+  Ok(())
+}
+
+#[cfg(windows)]
+fn set_child_ids(
+  _c: &mut Command,
+  uid: Option<u32>,
+  gid: Option<u32>,
+) -> Result<(), ErrBox> {
+  if uid.is_none() && gid.is_none() {
+    return Ok(());
+  }
+  Err(ids_not_supported())
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SetIdsArgs {
+  uid: Option<u32>,
+  gid: Option<u32>,
+}
+
+fn op_set_ids(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: SetIdsArgs = serde_json::from_value(args)?;
+  state.check_run()?;
+  set_ids(args.uid, args.gid)?;
+  Ok(JsonOp::Sync(json!({})))
+}
+
+/// Switches the group first and the user last, since giving up root first
+/// would take away the right to change groups.
+#[cfg(unix)]
+fn set_ids(uid: Option<u32>, gid: Option<u32>) -> Result<(), ErrBox> {
+  unsafe {
+    if let Some(gid) = gid {
+      if libc::getuid() == 0 {
+        let groups = [gid as libc::gid_t];
+        if libc::setgroups(1, groups.as_ptr()) == -1 {
+          return Err(std::io::Error::last_os_error().into());
+        }
+      }
+      if libc::setgid(gid as libc::gid_t) == -1 {
+        return Err(std::io::Error::last_os_error().into());
+      }
+    }
+    if let Some(uid) = uid {
+      if gid.is_none() && libc::getuid() == 0 {
+        if libc::setgroups(0, std::ptr::null()) == -1 {
+          return Err(std::io::Error::last_os_error().into());
+        }
+      }
+      if libc::setuid(uid as libc::uid_t) == -1 {
+        return Err(std::io::Error::last_os_error().into());
+      }
+    }
+  }
+  Ok(())
+}
+
+#[cfg(windows)]
+fn set_ids(uid: Option<u32>, gid: Option<u32>) -> Result<(), ErrBox> {
+  if uid.is_none() && gid.is_none() {
+    return Ok(());
+  }
+  Err(ids_not_supported())
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct RunStatusArgs {
@@ -114,44 +528,148 @@ struct RunStatusArgs {
 }
 
 fn op_run_status(
-  state: &ThreadSafeState,
+  _state: &ThreadSafeState,
   args: Value,
   _zero_copy: Option<PinnedBuf>,
 ) -> Result<JsonOp, ErrBox> {
   let args: RunStatusArgs = serde_json::from_value(args)?;
   let rid = args.rid as u32;
 
-  state.check_run()?;
-
   let future = resources::child_status(rid)?;
 
-  let future = future.and_then(move |run_status| {
-    let code = run_status.code();
+  let future = future.map(move |run_status| run_status_json(rid, run_status));
+  let future = resources::cancel_on_close(rid, future);
 
-    #[cfg(unix)]
-    let signal = run_status.signal();
-    #[cfg(not(unix))]
-    let signal = None;
+  Ok(JsonOp::Async(Box::new(future)))
+}
 
-    code
-      .or(signal)
-      .expect("Should have either an exit code or a signal.");
-    let got_signal = signal.is_some();
+fn run_status_json(rid: u32, run_status: ExitStatus) -> Value {
+  let code = run_status.code();
 
-    futures::future::ok(json!({
-      "gotSignal": got_signal,
-       "exitCode": code.unwrap_or(-1),
-       "exitSignal": signal.unwrap_or(-1),
-    }))
-  });
+  #[cfg(unix)]
+  let signal = run_status.signal();
+  #[cfg(not(unix))]
+  let signal = None;
+
+  code
+    .or(signal)
+    .expect("Should have either an exit code or a signal.");
+  let got_signal = signal.is_some();
+
+  let timed_out = resources::child_timed_out(rid).unwrap_or(false);
+
+  json!({
+    "gotSignal": got_signal,
+    "exitCode": code.unwrap_or(-1),
+    "exitSignal": signal.unwrap_or(-1),
+    "timedOut": timed_out,
+  })
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RunOutputArgs {
+  rid: i32,
+  stdout_rid: Option<u32>,
+  stderr_rid: Option<u32>,
+  max_buffer: usize,
+}
+
+/// Waits for a child while reading its piped stdout and stderr to the end, so
+/// that it can't block on a full pipe that nobody reads.
+fn op_run_output(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: RunOutputArgs = serde_json::from_value(args)?;
+  let rid = args.rid as u32;
+
+  let status = resources::child_status(rid)?;
+  let take = |rid: Option<u32>| rid.map(resources::take_child_output);
+  let stdout =
+    CappedOutput::new(take(args.stdout_rid).transpose()?, args.max_buffer);
+  let stderr =
+    CappedOutput::new(take(args.stderr_rid).transpose()?, args.max_buffer);
+
+  let future = stdout.join3(stderr, status).map(
+    move |((stdout, stdout_truncated), (stderr, stderr_truncated), status)| {
+      json!({
+        "status": run_status_json(rid, status),
+        "stdout": stdout,
+        "stderr": stderr,
+        "truncated": stdout_truncated || stderr_truncated,
+      })
+    },
+  );
 
   Ok(JsonOp::Async(Box::new(future)))
 }
 
+/// Reads a pipe to the end, keeping at most `cap` bytes of it. The rest is
+/// read and dropped all the same, for the child not to block on writing it.
+struct CappedOutput {
+  pipe: Option<Box<dyn AsyncRead + Send>>,
+  buf: Vec<u8>,
+  cap: usize,
+  truncated: bool,
+}
+
+impl CappedOutput {
+  fn new(pipe: Option<Box<dyn AsyncRead + Send>>, cap: usize) -> Self {
+    Self {
+      pipe,
+      buf: Vec::new(),
+      cap,
+      truncated: false,
+    }
+  }
+}
+
+impl Future for CappedOutput {
+  type Item = (Vec<u8>, bool);
+  type Error = ErrBox;
+
+  fn poll(&mut self) -> Poll<Self::Item, ErrBox> {
+    let mut chunk = [0u8; 8192];
+    if let Some(ref mut pipe) = self.pipe {
+      loop {
+        let n = try_ready!(pipe.poll_read(&mut chunk));
+        if n == 0 {
+          break;
+        }
+        let keep = n.min(self.cap - self.buf.len());
+        self.buf.extend_from_slice(&chunk[..keep]);
+        self.truncated |= keep < n;
+      }
+    }
+    self.pipe = None;
+    let buf = std::mem::replace(&mut self.buf, Vec::new());
+    Ok(Async::Ready((buf, self.truncated)))
+  }
+}
+
+/// A signal given either by number or by name, e.g. "SIGTERM".
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum SignalArg {
+  Number(i32),
+  Name(String),
+}
+
+impl SignalArg {
+  pub fn to_signo(&self) -> Result<i32, ErrBox> {
+    match self {
+      SignalArg::Number(signo) => Ok(*signo),
+      SignalArg::Name(name) => signal_from_name(name),
+    }
+  }
+}
+
 #[derive(Deserialize)]
 struct KillArgs {
   pid: i32,
-  signo: i32,
+  signo: SignalArg,
 }
 
 fn op_kill(
@@ -162,6 +680,182 @@ fn op_kill(
   state.check_run()?;
 
   let args: KillArgs = serde_json::from_value(args)?;
-  kill(args.pid, args.signo)?;
+  kill(args.pid, args.signo.to_signo()?)?;
   Ok(JsonOp::Sync(json!({})))
 }
+
+#[derive(Deserialize)]
+struct KillGroupArgs {
+  rid: i32,
+  signo: SignalArg,
+}
+
+/// Signals the process group led by a child, which must have been started in
+/// one of its own. Otherwise the group would be ours.
+fn op_kill_group(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  state.check_run()?;
+
+  let args: KillGroupArgs = serde_json::from_value(args)?;
+  let pgid =
+    resources::child_process_group(args.rid as u32)?.ok_or_else(|| {
+      DenoError::new(
+        ErrorKind::InvalidInput,
+        "process was not started in a process group of its own".to_string(),
+      )
+    })?;
+  kill_group(pgid as i32, args.signo.to_signo()?)?;
+  Ok(JsonOp::Sync(json!({})))
+}
+
+/// Nice values, from most to least favorable scheduling.
+const PRIORITY_HIGHEST: i32 = -20;
+const PRIORITY_LOWEST: i32 = 19;
+
+#[derive(Deserialize)]
+struct GetPriorityArgs {
+  pid: u32,
+}
+
+fn op_get_priority(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  state.check_run()?;
+
+  let args: GetPriorityArgs = serde_json::from_value(args)?;
+  Ok(JsonOp::Sync(json!(get_priority(args.pid)?)))
+}
+
+#[derive(Deserialize)]
+struct SetPriorityArgs {
+  pid: u32,
+  priority: i32,
+}
+
+fn op_set_priority(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  state.check_run()?;
+
+  let args: SetPriorityArgs = serde_json::from_value(args)?;
+  if args.priority < PRIORITY_HIGHEST || args.priority > PRIORITY_LOWEST {
+    return Err(
+      DenoError::new(
+        ErrorKind::InvalidInput,
+        format!(
+          "Priority must be between {} and {}, got {}",
+          PRIORITY_HIGHEST, PRIORITY_LOWEST, args.priority
+        ),
+      )
+      .into(),
+    );
+  }
+  set_priority(args.pid, args.priority)?;
+  Ok(JsonOp::Sync(json!({})))
+}
+
+/// Returns the nice value of `pid`, or of the current process for 0.
+#[cfg(unix)]
+fn get_priority(pid: u32) -> Result<i32, ErrBox> {
+  // -1 is a valid priority, so errors can only be told apart through errno.
+  unsafe { nix::errno::Errno::clear() };
+  let priority = unsafe { libc::getpriority(libc::PRIO_PROCESS, pid as _) };
+  if priority == -1 && nix::errno::errno() != 0 {
+    return Err(std::io::Error::last_os_error().into());
+  }
+  Ok(priority)
+}
+
+#[cfg(unix)]
+fn set_priority(pid: u32, priority: i32) -> Result<(), ErrBox> {
+  if unsafe { libc::setpriority(libc::PRIO_PROCESS, pid as _, priority) } == -1
+  {
+    return Err(std::io::Error::last_os_error().into());
+  }
+  Ok(())
+}
+
+/// Windows has priority classes instead of nice values. They are mapped to
+/// and from nice values the same way Node.js does it.
+#[cfg(windows)]
+fn get_priority(pid: u32) -> Result<i32, ErrBox> {
+  use winapi::um::processthreadsapi::GetPriorityClass;
+  use winapi::um::winbase::{
+    ABOVE_NORMAL_PRIORITY_CLASS, BELOW_NORMAL_PRIORITY_CLASS,
+    HIGH_PRIORITY_CLASS, IDLE_PRIORITY_CLASS, REALTIME_PRIORITY_CLASS,
+  };
+  use winapi::um::winnt::PROCESS_QUERY_LIMITED_INFORMATION;
+  let class =
+    with_process(pid, PROCESS_QUERY_LIMITED_INFORMATION, |h| unsafe {
+      GetPriorityClass(h)
+    })?;
+  Ok(match class {
+    REALTIME_PRIORITY_CLASS => -20,
+    HIGH_PRIORITY_CLASS => -14,
+    ABOVE_NORMAL_PRIORITY_CLASS => -7,
+    BELOW_NORMAL_PRIORITY_CLASS => 10,
+    IDLE_PRIORITY_CLASS => 19,
+    _ => 0,
+  })
+}
+
+#[cfg(windows)]
+fn set_priority(pid: u32, priority: i32) -> Result<(), ErrBox> {
+  use winapi::um::processthreadsapi::SetPriorityClass;
+  use winapi::um::winbase::{
+    ABOVE_NORMAL_PRIORITY_CLASS, BELOW_NORMAL_PRIORITY_CLASS,
+    HIGH_PRIORITY_CLASS, IDLE_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS,
+    REALTIME_PRIORITY_CLASS,
+  };
+  use winapi::um::winnt::PROCESS_SET_INFORMATION;
+  let class = match priority {
+    p if p < -14 => REALTIME_PRIORITY_CLASS,
+    p if p < -7 => HIGH_PRIORITY_CLASS,
+    p if p < 0 => ABOVE_NORMAL_PRIORITY_CLASS,
+    p if p < 7 => NORMAL_PRIORITY_CLASS,
+    p if p < 19 => BELOW_NORMAL_PRIORITY_CLASS,
+    _ => IDLE_PRIORITY_CLASS,
+  };
+  with_process(pid, PROCESS_SET_INFORMATION, |h| unsafe {
+    SetPriorityClass(h, class) as u32
+  })?;
+  Ok(())
+}
+
+/// Runs `f` with a handle to `pid`, or to the current process for 0, and
+/// turns a result of 0 into the last OS error.
+#[cfg(windows)]
+fn with_process(
+  pid: u32,
+  access: u32,
+  f: impl FnOnce(winapi::um::winnt::HANDLE) -> u32,
+) -> Result<u32, ErrBox> {
+  use winapi::shared::minwindef::FALSE;
+  use winapi::um::handleapi::CloseHandle;
+  use winapi::um::processthreadsapi::{GetCurrentProcess, OpenProcess};
+  let (handle, owned) = if pid == 0 {
+    (unsafe { GetCurrentProcess() }, false)
+  } else {
+    let handle = unsafe { OpenProcess(access, FALSE, pid) };
+    if handle.is_null() {
+      return Err(std::io::Error::last_os_error().into());
+    }
+    (handle, true)
+  };
+  let result = f(handle);
+  let err = std::io::Error::last_os_error();
+  if owned {
+    unsafe { CloseHandle(handle) };
+  }
+  if result == 0 {
+    return Err(err.into());
+  }
+  Ok(result)
+}
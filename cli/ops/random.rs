@@ -7,9 +7,10 @@ use rand::thread_rng;
 use rand::Rng;
 
 pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
-  i.register_op(
+  s.register_op(
+    i,
     "get_random_values",
-    s.core_op(json_op(s.stateful_op(op_get_random_values))),
+    json_op(s.stateful_op(op_get_random_values)),
   );
 }
 
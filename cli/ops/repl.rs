@@ -7,14 +7,8 @@ use crate::state::ThreadSafeState;
 use deno::*;
 
 pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
-  i.register_op(
-    "repl_start",
-    s.core_op(json_op(s.stateful_op(op_repl_start))),
-  );
-  i.register_op(
-    "repl_readline",
-    s.core_op(json_op(s.stateful_op(op_repl_readline))),
-  );
+  s.register_op(i, "repl_start", json_op(s.stateful_op(op_repl_start)));
+  s.register_op(i, "repl_readline", json_op(s.stateful_op(op_repl_readline)));
 }
 
 #[derive(Deserialize)]
@@ -1,19 +1,47 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
-use super::dispatch_json::{JsonOp, Value};
+use super::dispatch_json::{Deserialize, JsonOp, Value};
 use crate::ops::json_op;
-use crate::resources::table_entries;
+use crate::resources::{table_entries, table_info};
 use crate::state::ThreadSafeState;
 use deno::*;
+use std::time::UNIX_EPOCH;
 
 pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
-  i.register_op("resources", s.core_op(json_op(s.stateful_op(op_resources))));
+  s.register_op(i, "resources", json_op(s.stateful_op(op_resources)));
+}
+
+#[derive(Deserialize)]
+struct ResourcesArgs {
+  verbose: Option<bool>,
 }
 
 fn op_resources(
   _state: &ThreadSafeState,
-  _args: Value,
+  args: Value,
   _zero_copy: Option<PinnedBuf>,
 ) -> Result<JsonOp, ErrBox> {
-  let serialized_resources = table_entries();
+  let args: ResourcesArgs = serde_json::from_value(args)?;
+  if !args.verbose.unwrap_or(false) {
+    let serialized_resources = table_entries();
+    return Ok(JsonOp::Sync(json!(serialized_resources)));
+  }
+
+  let serialized_resources: Vec<Value> = table_info()
+    .into_iter()
+    .map(|(rid, type_, info)| {
+      let created = info
+        .created
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0);
+      json!([rid, {
+        "type": type_,
+        "created": created,
+        "op": info.op,
+        "bytesRead": info.bytes_read,
+        "bytesWritten": info.bytes_written,
+      }])
+    })
+    .collect();
   Ok(JsonOp::Sync(json!(serialized_resources)))
 }
@@ -0,0 +1,144 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+//! Connects to js/ring.ts, the fast path of small sync ops like `now` and
+//! `seek`. JS shares a buffer of float64s with the `ring` op once, then queues
+//! the ops as records in it and dispatches `ring` without a control buffer.
+//! The op services every record queued since the last dispatch and writes the
+//! results over its arguments, so neither side encodes them, nor allocates a
+//! buffer to carry them.
+use super::timers::now_nanos;
+use crate::deno_error::DenoError;
+use crate::deno_error::ErrorKind;
+use crate::resources;
+use crate::state::ThreadSafeState;
+use deno::*;
+use futures::Future;
+use std::convert::TryInto;
+
+pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
+  let state = s.clone();
+  s.register_op(i, "ring", move |_control, zero_copy| {
+    op_ring(&state, zero_copy)
+  });
+}
+
+// These consts are shared with js/ring.ts. Update with care.
+const HEADER_LEN: usize = 2;
+const RECORD_LEN: usize = 4;
+const CAPACITY: usize = 16;
+const RING_ERR: i32 = -1;
+const RING_OK: i32 = 0;
+const RING_NOW: i32 = 1;
+const RING_SEEK: i32 = 2;
+
+/// The buffer of float64s `[head, tail]` followed by `CAPACITY` records of
+/// `[op, x, y, z]`. The records from `head` up to `tail` are queued.
+struct Ring<'a>(&'a mut [u8]);
+
+impl Ring<'_> {
+  fn get(&self, i: usize) -> f64 {
+    let bytes = &self.0[i * 8..i * 8 + 8];
+    f64::from_ne_bytes(bytes.try_into().unwrap())
+  }
+
+  fn set(&mut self, i: usize, value: f64) {
+    self.0[i * 8..i * 8 + 8].copy_from_slice(&value.to_ne_bytes());
+  }
+}
+
+fn op_ring(state: &ThreadSafeState, zero_copy: Option<PinnedBuf>) -> CoreOp {
+  let mut ring = state.ring.lock().unwrap();
+  if zero_copy.is_some() {
+    *ring = zero_copy;
+  }
+  if let Some(buf) = ring.as_mut() {
+    service(state, buf);
+  }
+  Op::Sync(Box::new([]))
+}
+
+fn service(state: &ThreadSafeState, buf: &mut [u8]) {
+  if buf.len() != (HEADER_LEN + RECORD_LEN * CAPACITY) * 8 {
+    debug!("bad ring of {} bytes", buf.len());
+    return;
+  }
+  let mut ring = Ring(buf);
+  let mut head = ring.get(0) as usize;
+  let tail = ring.get(1) as usize;
+  if tail >= CAPACITY {
+    debug!("bad ring tail {}", tail);
+    return;
+  }
+  while head != tail {
+    let record = HEADER_LEN + (head % CAPACITY) * RECORD_LEN;
+    let op = ring.get(record) as i32;
+    let args = (
+      ring.get(record + 1),
+      ring.get(record + 2),
+      ring.get(record + 3),
+    );
+    let status = match run(state, op, args) {
+      Ok((x, y)) => {
+        ring.set(record + 1, x);
+        ring.set(record + 2, y);
+        RING_OK
+      }
+      Err(err) => {
+        // JS makes the op again without the ring, for the error itself.
+        debug!("ring op {} failed: {}", op, err);
+        RING_ERR
+      }
+    };
+    ring.set(record, f64::from(status));
+    head = (head + 1) % CAPACITY;
+  }
+  ring.set(0, head as f64);
+}
+
+fn run(
+  state: &ThreadSafeState,
+  op: i32,
+  (x, y, z): (f64, f64, f64),
+) -> Result<(f64, f64), ErrBox> {
+  match op {
+    RING_NOW => {
+      let nanos = now_nanos(state);
+      Ok((
+        (nanos / 1_000_000_000) as f64,
+        (nanos % 1_000_000_000) as f64,
+      ))
+    }
+    RING_SEEK => {
+      // Like the JSON op, which takes them as int32s.
+      let resource = resources::lookup(x as i32 as u32)?;
+      let whence = z as i32 as u32;
+      let pos = resources::seek(resource, y as i64, whence).wait()?;
+      Ok((pos as f64, 0.0))
+    }
+    _ => Err(
+      DenoError::new(ErrorKind::InvalidInput, format!("Bad ring op {}", op))
+        .into(),
+    ),
+  }
+}
+
+#[test]
+fn test_service() {
+  let state = ThreadSafeState::mock(vec![String::from("./deno")]);
+  let mut buf = vec![0u8; (HEADER_LEN + RECORD_LEN * CAPACITY) * 8];
+  let mut ring = Ring(&mut buf);
+  // The ring wraps around, with a `now` in the last record and a `seek` of a
+  // bad rid in the first one.
+  ring.set(0, (CAPACITY - 1) as f64);
+  ring.set(1, 1.0);
+  let last = HEADER_LEN + (CAPACITY - 1) * RECORD_LEN;
+  ring.set(last, f64::from(RING_NOW));
+  ring.set(HEADER_LEN, f64::from(RING_SEEK));
+  ring.set(HEADER_LEN + 1, 9999.0);
+
+  service(&state, &mut buf);
+  let ring = Ring(&mut buf);
+  assert_eq!(ring.get(0) as usize, 1);
+  assert_eq!(ring.get(last) as i32, RING_OK);
+  assert!(ring.get(last + 2) < 1_000_000_000.0);
+  assert_eq!(ring.get(HEADER_LEN) as i32, RING_ERR);
+}
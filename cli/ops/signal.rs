@@ -0,0 +1,71 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+use super::dispatch_json::{Deserialize, JsonOp, Value};
+use crate::deno_error::bad_resource;
+use crate::ops::json_op;
+use crate::ops::process::SignalArg;
+use crate::resources;
+use crate::signal;
+use crate::state::ThreadSafeState;
+use deno::*;
+use futures::sync::mpsc;
+use futures::Future;
+
+pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
+  s.register_op(i, "signal_bind", json_op(s.stateful_op(op_signal_bind)));
+  s.register_op(i, "signal_poll", json_op(s.stateful_op(op_signal_poll)));
+}
+
+/// Yields every time the process receives the signal it was bound to.
+pub struct SignalWatcher {
+  pub receiver: mpsc::UnboundedReceiver<()>,
+}
+
+#[derive(Deserialize)]
+struct SignalBindArgs {
+  signo: SignalArg,
+}
+
+fn op_signal_bind(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: SignalBindArgs = serde_json::from_value(args)?;
+  let signo = args.signo.to_signo()?;
+
+  let (sender, receiver) = mpsc::unbounded::<()>();
+  // Sending fails once the watcher is closed, which unbinds it.
+  signal::listen(signo, Box::new(move || sender.unbounded_send(()).is_ok()))?;
+  let resource = resources::add_signal_watcher(SignalWatcher { receiver });
+  Ok(JsonOp::Sync(json!(resource.rid)))
+}
+
+#[derive(Deserialize)]
+struct SignalPollArgs {
+  rid: i32,
+}
+
+fn op_signal_poll(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: SignalPollArgs = serde_json::from_value(args)?;
+  let rid = args.rid as u32;
+
+  if resources::get_type(rid) != Some(String::from("signal")) {
+    return Err(bad_resource());
+  }
+
+  let op = resources::cancel_on_close(rid, resources::poll_signal(rid));
+  let op = op.then(move |result| {
+    let value = match result {
+      Ok(Some(())) => json!({ "done": false }),
+      // The watcher was closed while we were waiting.
+      Ok(None) | Err(_) => json!({ "done": true }),
+    };
+    futures::future::ok(value)
+  });
+
+  Ok(JsonOp::Async(Box::new(op)))
+}
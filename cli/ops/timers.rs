@@ -1,81 +1,132 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
-use super::dispatch_json::{Deserialize, JsonOp, Value};
+use super::dispatch_json::{JsonOp, Value};
 use crate::ops::json_op;
 use crate::state::ThreadSafeState;
+use crate::timers::TimerId;
+use crate::timers::TimersPoll;
 use deno::*;
 use futures::Future;
 use std;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
 use std::time::Duration;
-use std::time::Instant;
 
 pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
-  i.register_op(
-    "global_timer_stop",
-    s.core_op(json_op(s.stateful_op(op_global_timer_stop))),
-  );
-  i.register_op(
-    "global_timer",
-    s.core_op(json_op(s.stateful_op(op_global_timer))),
-  );
-  i.register_op("now", s.core_op(json_op(s.stateful_op(op_now))));
+  let state = s.clone();
+  s.register_op(i, "timer", move |control, _zero_copy| {
+    op_timer(&state, control)
+  });
+  s.register_op(i, "now", json_op(s.stateful_op(op_now)));
 }
 
-fn op_global_timer_stop(
-  state: &ThreadSafeState,
-  _args: Value,
-  _zero_copy: Option<PinnedBuf>,
-) -> Result<JsonOp, ErrBox> {
-  let state = state;
-  let mut t = state.global_timer.lock().unwrap();
-  t.cancel();
-  Ok(JsonOp::Sync(json!({})))
+// These consts are shared with js/timers.ts. Update with care.
+const TIMER_START: i32 = 0;
+const TIMER_CLEAR: i32 = 1;
+const TIMER_POLL: i32 = 2;
+
+/// Manages the timers of the isolate, see `crate::timers`. The control buffer
+/// holds the int32s `[command, id, delay, repeat]`. Starting and clearing a
+/// timer is sync and returns nothing, while polling resolves with the ids of
+/// the timers that fired, as int32s.
+fn op_timer(state: &ThreadSafeState, control: &[u8]) -> CoreOp {
+  let args = match parse_timer_args(control) {
+    Some(args) => args,
+    None => panic!("bad timer op control buffer"),
+  };
+  let (command, id) = (args[0], args[1] as TimerId);
+
+  match command {
+    TIMER_START => {
+      let delay = Duration::from_millis(args[2] as u64);
+      let repeat = args[3] != 0;
+      state.timers.lock().unwrap().start(id, delay, repeat);
+      Op::Sync(Vec::new().into_boxed_slice())
+    }
+    TIMER_CLEAR => {
+      state.timers.lock().unwrap().clear(id);
+      Op::Sync(Vec::new().into_boxed_slice())
+    }
+    TIMER_POLL => {
+      let fut = TimersPoll(state.timers.clone()).map(|fired| {
+        let bytes: Vec<u8> = fired
+          .iter()
+          .flat_map(|id| id.to_le_bytes().to_vec())
+          .collect();
+        bytes.into_boxed_slice()
+      });
+      Op::Async(Box::new(fut))
+    }
+    _ => panic!("bad timer op command {}", command),
+  }
 }
 
-#[derive(Deserialize)]
-struct GlobalTimerArgs {
-  timeout: u64,
+fn parse_timer_args(control: &[u8]) -> Option<[i32; 4]> {
+  if control.len() != 16 {
+    return None;
+  }
+  let mut args = [0; 4];
+  for (i, chunk) in control.chunks(4).enumerate() {
+    let mut bytes = [0; 4];
+    bytes.copy_from_slice(chunk);
+    args[i] = i32::from_le_bytes(bytes);
+  }
+  Some(args)
 }
 
-fn op_global_timer(
-  state: &ThreadSafeState,
-  args: Value,
-  _zero_copy: Option<PinnedBuf>,
-) -> Result<JsonOp, ErrBox> {
-  let args: GlobalTimerArgs = serde_json::from_value(args)?;
-  let val = args.timeout;
+#[test]
+fn test_parse_timer_args() {
+  let control = [0, 0, 0, 0, 7, 0, 0, 0, 100, 0, 0, 0, 1, 0, 0, 0];
+  assert_eq!(parse_timer_args(&control), Some([TIMER_START, 7, 100, 1]));
+  assert_eq!(parse_timer_args(&control[..12]), None);
+}
 
-  let state = state;
-  let mut t = state.global_timer.lock().unwrap();
-  let deadline = Instant::now() + Duration::from_millis(val);
-  let f = t
-    .new_timeout(deadline)
-    .then(move |_| futures::future::ok(json!({})));
+// Returns the nanoseconds since the start time of the deno runtime.
+// If the High precision flag is not set, they are rounded on 2ms.
+pub fn now_nanos(state: &ThreadSafeState) -> u64 {
+  let elapsed = state.start_time.elapsed();
+  let nanos =
+    elapsed.as_secs() * 1_000_000_000 + u64::from(elapsed.subsec_nanos());
 
-  Ok(JsonOp::Async(Box::new(f)))
+  // If the permission is not enabled, reduce the precision to 2ms
+  // see: https://developer.mozilla.org/en-US/docs/Web/API/DOMHighResTimeStamp#Reduced_time_precision
+  if state.permissions.allows_hrtime() {
+    nanos
+  } else {
+    reduce_precision(nanos, 2_000_000)
+  }
 }
 
 // Returns a milliseconds and nanoseconds subsec
 // since the start time of the deno runtime.
-// If the High precision flag is not set, the
-// nanoseconds are rounded on 2ms.
 fn op_now(
   state: &ThreadSafeState,
   _args: Value,
   _zero_copy: Option<PinnedBuf>,
 ) -> Result<JsonOp, ErrBox> {
-  let seconds = state.start_time.elapsed().as_secs();
-  let mut subsec_nanos = state.start_time.elapsed().subsec_nanos();
-  let reduced_time_precision = 2_000_000; // 2ms in nanoseconds
-
-  // If the permission is not enabled
-  // Round the nano result on 2 milliseconds
-  // see: https://developer.mozilla.org/en-US/docs/Web/API/DOMHighResTimeStamp#Reduced_time_precision
-  if !state.permissions.allows_hrtime() {
-    subsec_nanos -= subsec_nanos % reduced_time_precision
-  }
-
+  let nanos = now_nanos(state);
   Ok(JsonOp::Sync(json!({
-    "seconds": seconds,
-    "subsecNanos": subsec_nanos,
+    "seconds": nanos / 1_000_000_000,
+    "subsecNanos": nanos % 1_000_000_000,
   })))
 }
+
+lazy_static! {
+  static ref JITTER_KEY: u64 = rand::random();
+}
+
+/// Rounds `nanos` down to a multiple of `precision`. The time at which the
+/// result moves on to the next multiple is jittered within the step, so the
+/// exact moment it does can't be used to time events more precisely. The
+/// jitter is fixed per step, which keeps the result monotonic.
+fn reduce_precision(nanos: u64, precision: u64) -> u64 {
+  let step = nanos / precision;
+  let mut hasher = DefaultHasher::new();
+  hasher.write_u64(*JITTER_KEY);
+  hasher.write_u64(step);
+  let jitter = hasher.finish() % precision;
+  if nanos % precision >= jitter || step == 0 {
+    step * precision
+  } else {
+    (step - 1) * precision
+  }
+}
@@ -2,7 +2,9 @@
 use super::dispatch_json::{Deserialize, JsonOp, Value};
 use crate::deno_error::DenoError;
 use crate::deno_error::ErrorKind;
+use crate::http_util::ignores_certificate_errors;
 use crate::ops::json_op;
+use crate::permissions::join_host_port;
 use crate::resolve_addr::resolve_addr;
 use crate::resources;
 use crate::state::ThreadSafeState;
@@ -21,7 +23,8 @@ use tokio_rustls::{rustls::ClientConfig, TlsConnector};
 use tokio_rustls::{
   rustls::{
     internal::pemfile::{certs, pkcs8_private_keys, rsa_private_keys},
-    Certificate, NoClientAuth, PrivateKey, ServerConfig,
+    Certificate, NoClientAuth, PrivateKey, RootCertStore, ServerCertVerified,
+    ServerCertVerifier, ServerConfig, TLSError,
   },
   TlsAcceptor,
 };
@@ -30,15 +33,9 @@ use webpki::DNSNameRef;
 use webpki_roots;
 
 pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
-  i.register_op("dial_tls", s.core_op(json_op(s.stateful_op(op_dial_tls))));
-  i.register_op(
-    "listen_tls",
-    s.core_op(json_op(s.stateful_op(op_listen_tls))),
-  );
-  i.register_op(
-    "accept_tls",
-    s.core_op(json_op(s.stateful_op(op_accept_tls))),
-  );
+  s.register_op(i, "dial_tls", json_op(s.stateful_op(op_dial_tls)));
+  s.register_op(i, "listen_tls", json_op(s.stateful_op(op_listen_tls)));
+  s.register_op(i, "accept_tls", json_op(s.stateful_op(op_accept_tls)));
 }
 
 #[derive(Deserialize)]
@@ -56,9 +53,9 @@ pub fn op_dial_tls(
 ) -> Result<JsonOp, ErrBox> {
   let args: DialTLSArgs = serde_json::from_value(args)?;
 
-  // TODO(ry) Using format! is suboptimal here. Better would be if
+  // TODO(ry) Joining the address is suboptimal here. Better would be if
   // state.check_net and resolve_addr() took hostname and port directly.
-  let address = format!("{}:{}", args.hostname, args.port);
+  let address = join_host_port(&args.hostname, Some(&args.port.to_string()));
   let cert_file = args.cert_file;
 
   state.check_net(&address)?;
@@ -70,6 +67,10 @@ pub fn op_dial_tls(
   if domain.is_empty() {
     domain.push_str("localhost");
   }
+  let accept_invalid_certs = ignores_certificate_errors(
+    &state.flags.unsafely_ignore_certificate_errors,
+    &domain,
+  );
 
   let op = resolve_addr(&address).and_then(move |addr| {
     TcpStream::connect(&addr)
@@ -86,6 +87,11 @@ pub fn op_dial_tls(
           let reader = &mut BufReader::new(key_file);
           config.root_store.add_pem_file(reader).unwrap();
         }
+        if accept_invalid_certs {
+          config
+            .dangerous()
+            .set_certificate_verifier(Arc::new(NoCertificateVerification));
+        }
 
         let tls_connector = TlsConnector::from(Arc::new(config));
         Ok((tls_connector, tcp_stream, local_addr, remote_addr))
@@ -113,6 +119,21 @@ pub fn op_dial_tls(
   Ok(JsonOp::Async(Box::new(op)))
 }
 
+/// Accepts any server certificate, for --unsafely-ignore-certificate-errors.
+struct NoCertificateVerification;
+
+impl ServerCertVerifier for NoCertificateVerification {
+  fn verify_server_cert(
+    &self,
+    _roots: &RootCertStore,
+    _presented_certs: &[Certificate],
+    _dns_name: DNSNameRef<'_>,
+    _ocsp_response: &[u8],
+  ) -> Result<ServerCertVerified, TLSError> {
+    Ok(ServerCertVerified::assertion())
+  }
+}
+
 fn load_certs(path: &str) -> Result<Vec<Certificate>, ErrBox> {
   let cert_file = File::open(path)?;
   let reader = &mut BufReader::new(cert_file);
@@ -189,9 +210,9 @@ fn op_listen_tls(
   let args: ListenTlsArgs = serde_json::from_value(args)?;
   assert_eq!(args.transport, "tcp");
 
-  // TODO(ry) Using format! is suboptimal here. Better would be if
+  // TODO(ry) Joining the address is suboptimal here. Better would be if
   // state.check_net and resolve_addr() took hostname and port directly.
-  let address = format!("{}:{}", args.hostname, args.port);
+  let address = join_host_port(&args.hostname, Some(&args.port.to_string()));
   let cert_file = args.cert_file;
   let key_file = args.key_file;
 
@@ -253,6 +274,7 @@ fn op_accept_tls(
         "remoteAddr": remote_addr.to_string(),
       }))
     });
+  let op = resources::cancel_on_close(server_rid, op);
 
   Ok(JsonOp::Async(Box::new(op)))
 }
@@ -0,0 +1,401 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+use super::dispatch_json::{Deserialize, JsonOp, Value};
+use crate::deno_error::bad_resource;
+use crate::deno_error::DenoError;
+use crate::deno_error::ErrorKind;
+use crate::ops::json_op;
+use crate::resources;
+use crate::resources::RawStdio;
+use crate::state::ThreadSafeState;
+use deno::*;
+use futures::sync::mpsc;
+use futures::Future;
+use std::io;
+use std::sync::Mutex;
+use std::sync::Once;
+
+pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
+  s.register_op(i, "isatty", json_op(s.stateful_op(op_isatty)));
+  s.register_op(i, "console_size", json_op(s.stateful_op(op_console_size)));
+  s.register_op(i, "set_raw", json_op(s.stateful_op(op_set_raw)));
+  s.register_op(
+    i,
+    "console_resize_open",
+    json_op(s.stateful_op(op_console_resize_open)),
+  );
+  s.register_op(
+    i,
+    "console_resize_poll",
+    json_op(s.stateful_op(op_console_resize_poll)),
+  );
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConsoleSize {
+  pub columns: u32,
+  pub rows: u32,
+}
+
+impl ConsoleSize {
+  pub fn to_json(&self) -> Value {
+    json!({ "columns": self.columns, "rows": self.rows })
+  }
+}
+
+/// Yields the size of the console behind an rid every time it changes.
+pub struct ConsoleResizeWatcher {
+  pub receiver: mpsc::UnboundedReceiver<ConsoleSize>,
+}
+
+#[cfg(unix)]
+fn isatty(raw: RawStdio) -> bool {
+  unsafe { libc::isatty(raw) == 1 }
+}
+
+#[cfg(windows)]
+fn isatty(raw: RawStdio) -> bool {
+  use winapi::um::consoleapi::GetConsoleMode;
+  let mut mode = 0;
+  unsafe { GetConsoleMode(raw, &mut mode) != 0 }
+}
+
+#[cfg(unix)]
+fn console_size(raw: RawStdio) -> io::Result<ConsoleSize> {
+  let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+  if unsafe { libc::ioctl(raw, libc::TIOCGWINSZ, &mut size) } != 0 {
+    return Err(io::Error::last_os_error());
+  }
+  Ok(ConsoleSize {
+    columns: size.ws_col.into(),
+    rows: size.ws_row.into(),
+  })
+}
+
+#[cfg(windows)]
+fn console_size(raw: RawStdio) -> io::Result<ConsoleSize> {
+  use winapi::um::wincon::GetConsoleScreenBufferInfo;
+  use winapi::um::wincon::CONSOLE_SCREEN_BUFFER_INFO;
+  let mut info: CONSOLE_SCREEN_BUFFER_INFO = unsafe { std::mem::zeroed() };
+  if unsafe { GetConsoleScreenBufferInfo(raw, &mut info) } == 0 {
+    return Err(io::Error::last_os_error());
+  }
+  // The visible window, not the whole scrollback buffer.
+  let window = info.srWindow;
+  Ok(ConsoleSize {
+    columns: (window.Right - window.Left + 1) as u32,
+    rows: (window.Bottom - window.Top + 1) as u32,
+  })
+}
+
+fn rid_console_size(rid: u32) -> Result<ConsoleSize, ErrBox> {
+  match resources::with_raw_handle(rid, console_size)? {
+    Some(result) => result.map_err(ErrBox::from),
+    None => Err(not_a_tty()),
+  }
+}
+
+fn not_a_tty() -> ErrBox {
+  DenoError::new(
+    ErrorKind::InvalidInput,
+    "The resource is not a terminal".to_string(),
+  )
+  .into()
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TtyArgs {
+  rid: i32,
+}
+
+fn op_isatty(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: TtyArgs = serde_json::from_value(args)?;
+  let is_tty = resources::with_raw_handle(args.rid as u32, isatty)?;
+  Ok(JsonOp::Sync(json!(is_tty.unwrap_or(false))))
+}
+
+fn op_console_size(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: TtyArgs = serde_json::from_value(args)?;
+  let size = rid_console_size(args.rid as u32)?;
+  Ok(JsonOp::Sync(size.to_json()))
+}
+
+#[cfg(unix)]
+mod raw_mode {
+  use crate::resources::RawStdio;
+  use std::io;
+
+  pub type Mode = libc::termios;
+
+  pub fn stdin_handle() -> RawStdio {
+    0
+  }
+
+  pub fn get_mode(fd: RawStdio) -> io::Result<Mode> {
+    let mut mode: Mode = unsafe { std::mem::zeroed() };
+    if unsafe { libc::tcgetattr(fd, &mut mode) } != 0 {
+      return Err(io::Error::last_os_error());
+    }
+    Ok(mode)
+  }
+
+  pub fn set_mode(fd: RawStdio, mode: &Mode) -> io::Result<()> {
+    if unsafe { libc::tcsetattr(fd, libc::TCSADRAIN, mode) } != 0 {
+      return Err(io::Error::last_os_error());
+    }
+    Ok(())
+  }
+
+  pub fn make_raw(mut mode: Mode, cbreak: bool) -> Mode {
+    if cbreak {
+      // Unbuffered and unechoed, but ^C and friends still raise signals and
+      // output is still post-processed.
+      mode.c_lflag &= !(libc::ICANON | libc::ECHO);
+      mode.c_cc[libc::VMIN] = 1;
+      mode.c_cc[libc::VTIME] = 0;
+    } else {
+      unsafe { libc::cfmakeraw(&mut mode) };
+    }
+    mode
+  }
+}
+
+#[cfg(windows)]
+mod raw_mode {
+  use crate::resources::RawStdio;
+  use std::io;
+  use winapi::um::consoleapi::{GetConsoleMode, SetConsoleMode};
+  use winapi::um::wincon::{
+    ENABLE_ECHO_INPUT, ENABLE_LINE_INPUT, ENABLE_PROCESSED_INPUT,
+  };
+
+  pub type Mode = u32;
+
+  pub fn stdin_handle() -> RawStdio {
+    use winapi::um::processenv::GetStdHandle;
+    use winapi::um::winbase::STD_INPUT_HANDLE;
+    unsafe { GetStdHandle(STD_INPUT_HANDLE) }
+  }
+
+  pub fn get_mode(handle: RawStdio) -> io::Result<Mode> {
+    let mut mode = 0;
+    if unsafe { GetConsoleMode(handle, &mut mode) } == 0 {
+      return Err(io::Error::last_os_error());
+    }
+    Ok(mode)
+  }
+
+  pub fn set_mode(handle: RawStdio, mode: &Mode) -> io::Result<()> {
+    if unsafe { SetConsoleMode(handle, *mode) } == 0 {
+      return Err(io::Error::last_os_error());
+    }
+    Ok(())
+  }
+
+  pub fn make_raw(mode: Mode, cbreak: bool) -> Mode {
+    let mode = mode & !(ENABLE_LINE_INPUT | ENABLE_ECHO_INPUT);
+    if cbreak {
+      mode
+    } else {
+      mode & !ENABLE_PROCESSED_INPUT
+    }
+  }
+}
+
+lazy_static! {
+  /// The mode stdin was in before it was first switched to raw mode. Set for
+  /// as long as stdin is in raw mode.
+  static ref ORIGINAL_MODE: Mutex<Option<raw_mode::Mode>> = Mutex::new(None);
+}
+
+static RESTORE_AT_EXIT: Once = Once::new();
+
+/// Runs from `exit()`, which is also where the process ends up after a panic.
+extern "C" fn restore_mode_at_exit() {
+  // Another thread may have been holding the lock when exit was called, in
+  // which case it is better to leave the terminal be than to hang.
+  if let Ok(original) = ORIGINAL_MODE.try_lock() {
+    if let Some(mode) = original.as_ref() {
+      let _ = raw_mode::set_mode(raw_mode::stdin_handle(), mode);
+    }
+  }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SetRawArgs {
+  rid: i32,
+  mode: bool,
+  cbreak: bool,
+}
+
+fn op_set_raw(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: SetRawArgs = serde_json::from_value(args)?;
+  match resources::get_type(args.rid as u32) {
+    Some(ref t) if t == "stdin" => {}
+    Some(_) => {
+      return Err(
+        DenoError::new(
+          ErrorKind::InvalidInput,
+          "Only stdin can be switched to raw mode".to_string(),
+        )
+        .into(),
+      )
+    }
+    None => return Err(bad_resource()),
+  }
+
+  let handle = raw_mode::stdin_handle();
+  let mut original = ORIGINAL_MODE.lock().unwrap();
+  if args.mode {
+    let current = raw_mode::get_mode(handle)?;
+    // Switching between raw and cbreak mode starts over from the original
+    // mode, which is also what is restored in the end.
+    let base = *original.get_or_insert(current);
+    raw_mode::set_mode(handle, &raw_mode::make_raw(base, args.cbreak))?;
+    RESTORE_AT_EXIT.call_once(|| unsafe {
+      libc::atexit(restore_mode_at_exit);
+    });
+  } else if let Some(mode) = original.take() {
+    raw_mode::set_mode(handle, &mode)?;
+  }
+  Ok(JsonOp::Sync(json!({})))
+}
+
+struct Subscriber {
+  rid: u32,
+  size: ConsoleSize,
+  sender: mpsc::UnboundedSender<ConsoleSize>,
+}
+
+struct Subscribers {
+  /// Whether resize notifications have been hooked up yet. That only happens
+  /// once somebody is interested in them.
+  started: bool,
+  list: Vec<Subscriber>,
+}
+
+lazy_static! {
+  static ref SUBSCRIBERS: Mutex<Subscribers> = Mutex::new(Subscribers {
+    started: false,
+    list: Vec::new(),
+  });
+}
+
+/// Called whenever a console may have been resized. A notification doesn't
+/// say which console it is about, so every watched one is measured again and
+/// only actual changes are sent on.
+fn notify_resize() {
+  let mut subscribers = SUBSCRIBERS.lock().unwrap();
+  let list = &mut subscribers.list;
+  let mut i = 0;
+  while i < list.len() {
+    let keep = match rid_console_size(list[i].rid) {
+      Ok(size) if size == list[i].size => true,
+      Ok(size) => {
+        list[i].size = size;
+        list[i].sender.unbounded_send(size).is_ok()
+      }
+      // The rid behind the watcher was closed.
+      Err(_) => false,
+    };
+    if keep {
+      i += 1;
+    } else {
+      list.remove(i);
+    }
+  }
+}
+
+#[cfg(unix)]
+mod resize {
+  use super::notify_resize;
+  use deno::ErrBox;
+
+  pub fn start() -> Result<(), ErrBox> {
+    crate::signal::listen(
+      libc::SIGWINCH,
+      Box::new(|| {
+        notify_resize();
+        true
+      }),
+    )
+  }
+}
+
+#[cfg(windows)]
+mod resize {
+  use super::notify_resize;
+  use deno::ErrBox;
+  use std::thread;
+  use std::time::Duration;
+
+  /// Windows reports resizes as console input events, which can't be read
+  /// without taking keystrokes away from stdin, so sizes are polled instead.
+  pub fn start() -> Result<(), ErrBox> {
+    thread::spawn(|| loop {
+      thread::sleep(Duration::from_millis(250));
+      notify_resize();
+    });
+    Ok(())
+  }
+}
+
+fn op_console_resize_open(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: TtyArgs = serde_json::from_value(args)?;
+  let rid = args.rid as u32;
+  let size = rid_console_size(rid)?;
+
+  let (sender, receiver) = mpsc::unbounded::<ConsoleSize>();
+  let mut subscribers = SUBSCRIBERS.lock().unwrap();
+  if !subscribers.started {
+    resize::start()?;
+    subscribers.started = true;
+  }
+  subscribers.list.push(Subscriber { rid, size, sender });
+
+  let resource =
+    resources::add_console_resize_watcher(ConsoleResizeWatcher { receiver });
+  Ok(JsonOp::Sync(json!(resource.rid)))
+}
+
+fn op_console_resize_poll(
+  _state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: TtyArgs = serde_json::from_value(args)?;
+  let rid = args.rid as u32;
+
+  if resources::get_type(rid) != Some(String::from("consoleResize")) {
+    return Err(bad_resource());
+  }
+
+  let op = resources::cancel_on_close(rid, resources::poll_console_resize(rid));
+  let op = op.then(move |result| {
+    let value = match result {
+      Ok(Some(size)) => json!({ "value": size.to_json(), "done": false }),
+      // The watcher or the console's rid was closed while we were waiting.
+      Ok(None) | Err(_) => json!({ "done": true }),
+    };
+    futures::future::ok(value)
+  });
+
+  Ok(JsonOp::Async(Box::new(op)))
+}
@@ -19,32 +19,34 @@ use std::convert::From;
 use std::sync::atomic::Ordering;
 
 pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
-  i.register_op(
-    "create_worker",
-    s.core_op(json_op(s.stateful_op(op_create_worker))),
-  );
-  i.register_op(
+  s.register_op(i, "create_worker", json_op(s.stateful_op(op_create_worker)));
+  s.register_op(
+    i,
     "host_get_worker_closed",
-    s.core_op(json_op(s.stateful_op(op_host_get_worker_closed))),
+    json_op(s.stateful_op(op_host_get_worker_closed)),
   );
-  i.register_op(
+  s.register_op(
+    i,
     "host_post_message",
-    s.core_op(json_op(s.stateful_op(op_host_post_message))),
+    json_op(s.stateful_op(op_host_post_message)),
   );
-  i.register_op(
+  s.register_op(
+    i,
     "host_get_message",
-    s.core_op(json_op(s.stateful_op(op_host_get_message))),
+    json_op(s.stateful_op(op_host_get_message)),
   );
   // TODO: make sure these two ops are only accessible to appropriate Worker
-  i.register_op(
+  s.register_op(
+    i,
     "worker_post_message",
-    s.core_op(json_op(s.stateful_op(op_worker_post_message))),
+    json_op(s.stateful_op(op_worker_post_message)),
   );
-  i.register_op(
+  s.register_op(
+    i,
     "worker_get_message",
-    s.core_op(json_op(s.stateful_op(op_worker_get_message))),
+    json_op(s.stateful_op(op_worker_get_message)),
   );
-  i.register_op("metrics", s.core_op(json_op(s.stateful_op(op_metrics))));
+  s.register_op(i, "metrics", json_op(s.stateful_op(op_metrics)));
 }
 
 struct GetMessageFuture {
@@ -112,8 +114,12 @@ struct CreateWorkerArgs {
   include_deno_namespace: bool,
   has_source_code: bool,
   source_code: String,
+  permissions: Option<Vec<String>>,
 }
 
+const PERMISSIONS: [&str; 7] =
+  ["read", "write", "net", "env", "run", "hrtime", "plugin"];
+
 /// Create worker as the host
 fn op_create_worker(
   state: &ThreadSafeState,
@@ -144,12 +150,50 @@ fn op_create_worker(
     }
   }
 
+  // The worker gets the permissions it asks for, but only as far as the
+  // parent has them.
+  let kept: Vec<String> = match args.permissions {
+    Some(permissions) => permissions,
+    None => PERMISSIONS.iter().map(|p| p.to_string()).collect(),
+  };
+  if let Some(unknown) =
+    kept.iter().find(|p| !PERMISSIONS.contains(&p.as_str()))
+  {
+    return Err(
+      DenoError::new(
+        ErrorKind::InvalidInput,
+        format!("Unknown permission \"{}\"", unknown),
+      )
+      .into(),
+    );
+  }
+  let mut child_flags = parent_state.flags.clone();
+  let keeps = |permission: &str| kept.iter().any(|p| p == permission);
+  if !keeps("read") {
+    child_flags.read_whitelist.clear();
+  }
+  if !keeps("write") {
+    child_flags.write_whitelist.clear();
+  }
+  if !keeps("net") {
+    child_flags.net_whitelist.clear();
+  }
+  if !keeps("env") {
+    child_flags.env_whitelist.clear();
+  }
+  if !keeps("run") {
+    child_flags.run_whitelist.clear();
+  }
+
   let child_state = ThreadSafeState::new(
-    parent_state.flags.clone(),
+    child_flags,
     child_argv,
     parent_state.progress.clone(),
     include_deno_namespace,
   )?;
+  child_state
+    .permissions
+    .inherit(&parent_state.permissions, &kept);
   let rid = child_state.resource.rid;
   let name = format!("USER-WORKER-{}", specifier);
   let deno_main_call = format!("denoMain({})", include_deno_namespace);
@@ -222,13 +266,12 @@ fn op_host_get_message(
   let args: HostGetMessageArgs = serde_json::from_value(args)?;
 
   let rid = args.rid as u32;
-  let op = resources::get_message_from_worker(rid)
-    .map_err(move |_| -> ErrBox { unimplemented!() })
-    .and_then(move |maybe_buf| {
-      futures::future::ok(json!({
-        "data": maybe_buf.map(|buf| buf.to_owned())
-      }))
-    });
+  let op = resources::get_message_from_worker(rid).and_then(move |maybe_buf| {
+    futures::future::ok(json!({
+      "data": maybe_buf.map(|buf| buf.to_owned())
+    }))
+  });
+  let op = resources::cancel_on_close(rid, op);
 
   Ok(JsonOp::Async(Box::new(op)))
 }
@@ -263,12 +306,36 @@ fn op_metrics(
   _zero_copy: Option<PinnedBuf>,
 ) -> Result<JsonOp, ErrBox> {
   let m = &state.metrics;
+  let allows_hrtime = state.permissions.allows_hrtime();
+  let ops: serde_json::Map<String, Value> = m
+    .ops
+    .lock()
+    .unwrap()
+    .iter()
+    .map(|(name, op)| {
+      let mut micros = op.latency.load(Ordering::SeqCst) / 1000;
+      // Like performance.now(), only precise to 2ms without --allow-hrtime.
+      if !allows_hrtime {
+        micros -= micros % 2000;
+      }
+      let metrics = json!({
+        "opsDispatched": op.dispatched.load(Ordering::SeqCst),
+        "opsCompleted": op.completed.load(Ordering::SeqCst),
+        "bytesSentControl": op.bytes_sent_control.load(Ordering::SeqCst),
+        "bytesSentData": op.bytes_sent_data.load(Ordering::SeqCst),
+        "bytesReceived": op.bytes_received.load(Ordering::SeqCst),
+        "latency": micros as f64 / 1000.0,
+      });
+      (name.to_string(), metrics)
+    })
+    .collect();
 
   Ok(JsonOp::Sync(json!({
     "opsDispatched": m.ops_dispatched.load(Ordering::SeqCst) as u64,
     "opsCompleted": m.ops_completed.load(Ordering::SeqCst) as u64,
     "bytesSentControl": m.bytes_sent_control.load(Ordering::SeqCst) as u64,
     "bytesSentData": m.bytes_sent_data.load(Ordering::SeqCst) as u64,
-    "bytesReceived": m.bytes_received.load(Ordering::SeqCst) as u64
+    "bytesReceived": m.bytes_received.load(Ordering::SeqCst) as u64,
+    "ops": ops,
   })))
 }
@@ -0,0 +1,220 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+use super::dispatch_json::{blocking_json, Deserialize, JsonOp, Value};
+use crate::deno_error;
+use crate::deno_error::DenoError;
+use crate::deno_error::ErrorKind;
+use crate::fs as deno_fs;
+use crate::ops::json_op;
+use crate::resources;
+use crate::state::ThreadSafeState;
+use deno::*;
+use std::path::PathBuf;
+
+pub fn init(i: &mut Isolate, s: &ThreadSafeState) {
+  s.register_op(i, "get_xattr", json_op(s.stateful_op(op_get_xattr)));
+  s.register_op(i, "set_xattr", json_op(s.stateful_op(op_set_xattr)));
+  s.register_op(i, "list_xattr", json_op(s.stateful_op(op_list_xattr)));
+  s.register_op(i, "remove_xattr", json_op(s.stateful_op(op_remove_xattr)));
+}
+
+/// Extended attributes can be accessed through a path, or through a file that
+/// is already open. Permissions for the latter were checked when it was
+/// opened, so changing its attributes requires it to be open for writing.
+enum Target {
+  Path(PathBuf),
+  File(std::fs::File),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct XattrArgs {
+  promise_id: Option<u64>,
+  path: Option<String>,
+  rid: Option<i32>,
+  name: Option<String>,
+}
+
+impl XattrArgs {
+  fn target(
+    &self,
+    state: &ThreadSafeState,
+    write: bool,
+  ) -> Result<Target, ErrBox> {
+    match (&self.path, self.rid) {
+      (Some(path), None) => {
+        let (path, path_) = deno_fs::resolve_from_cwd(path)?;
+        if write {
+          state.check_write(&path_)?;
+        } else {
+          state.check_read(&path_)?;
+        }
+        Ok(Target::Path(path))
+      }
+      (None, Some(rid)) => {
+        let file = resources::get_file(rid as u32)?;
+        if write {
+          deno_fs::ensure_opened_for_write(&file)?;
+        }
+        Ok(Target::File(file))
+      }
+      _ => Err(invalid_input("Either a path or a rid is required")),
+    }
+  }
+
+  fn name(&self) -> Result<String, ErrBox> {
+    self
+      .name
+      .clone()
+      .ok_or_else(|| invalid_input("Attribute name is required"))
+  }
+}
+
+fn invalid_input(msg: &str) -> ErrBox {
+  DenoError::new(ErrorKind::InvalidInput, msg.to_string()).into()
+}
+
+fn op_get_xattr(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: XattrArgs = serde_json::from_value(args)?;
+  let target = args.target(state, false)?;
+  let name = args.name()?;
+
+  let is_sync = args.promise_id.is_none();
+  blocking_json(is_sync, move || {
+    debug!("op_get_xattr {}", name);
+    let value = platform::get(&target, &name)?;
+    Ok(json!(value))
+  })
+}
+
+fn op_set_xattr(
+  state: &ThreadSafeState,
+  args: Value,
+  zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: XattrArgs = serde_json::from_value(args)?;
+  let target = args.target(state, true)?;
+  let name = args.name()?;
+  let value = match zero_copy {
+    Some(buf) => buf,
+    None => return Err(deno_error::no_buffer_specified()),
+  };
+
+  let is_sync = args.promise_id.is_none();
+  blocking_json(is_sync, move || {
+    debug!("op_set_xattr {}", name);
+    platform::set(&target, &name, &value)?;
+    Ok(json!({}))
+  })
+}
+
+fn op_list_xattr(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: XattrArgs = serde_json::from_value(args)?;
+  let target = args.target(state, false)?;
+
+  let is_sync = args.promise_id.is_none();
+  blocking_json(is_sync, move || {
+    debug!("op_list_xattr");
+    let names = platform::list(&target)?;
+    Ok(json!(names))
+  })
+}
+
+fn op_remove_xattr(
+  state: &ThreadSafeState,
+  args: Value,
+  _zero_copy: Option<PinnedBuf>,
+) -> Result<JsonOp, ErrBox> {
+  let args: XattrArgs = serde_json::from_value(args)?;
+  let target = args.target(state, true)?;
+  let name = args.name()?;
+
+  let is_sync = args.promise_id.is_none();
+  blocking_json(is_sync, move || {
+    debug!("op_remove_xattr {}", name);
+    platform::remove(&target, &name)?;
+    Ok(json!({}))
+  })
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+mod platform {
+  use super::Target;
+  use deno::ErrBox;
+  use xattr::FileExt;
+
+  pub fn get(target: &Target, name: &str) -> Result<Option<Vec<u8>>, ErrBox> {
+    let value = match target {
+      Target::Path(path) => xattr::get(path, name)?,
+      Target::File(file) => file.get_xattr(name)?,
+    };
+    Ok(value)
+  }
+
+  pub fn set(target: &Target, name: &str, value: &[u8]) -> Result<(), ErrBox> {
+    match target {
+      Target::Path(path) => xattr::set(path, name, value)?,
+      Target::File(file) => file.set_xattr(name, value)?,
+    };
+    Ok(())
+  }
+
+  pub fn list(target: &Target) -> Result<Vec<String>, ErrBox> {
+    let names = match target {
+      Target::Path(path) => xattr::list(path)?,
+      Target::File(file) => file.list_xattr()?,
+    };
+    Ok(names.map(|n| n.to_string_lossy().into_owned()).collect())
+  }
+
+  pub fn remove(target: &Target, name: &str) -> Result<(), ErrBox> {
+    match target {
+      Target::Path(path) => xattr::remove(path, name)?,
+      Target::File(file) => file.remove_xattr(name)?,
+    };
+    Ok(())
+  }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+mod platform {
+  use super::Target;
+  use crate::deno_error::DenoError;
+  use crate::deno_error::ErrorKind;
+  use deno::ErrBox;
+
+  fn unsupported() -> ErrBox {
+    DenoError::new(
+      ErrorKind::OpNotAvailable,
+      "Extended attributes are not supported on this platform".to_string(),
+    )
+    .into()
+  }
+
+  pub fn get(_target: &Target, _name: &str) -> Result<Option<Vec<u8>>, ErrBox> {
+    Err(unsupported())
+  }
+
+  pub fn set(
+    _target: &Target,
+    _name: &str,
+    _value: &[u8],
+  ) -> Result<(), ErrBox> {
+    Err(unsupported())
+  }
+
+  pub fn list(_target: &Target) -> Result<Vec<String>, ErrBox> {
+    Err(unsupported())
+  }
+
+  pub fn remove(_target: &Target, _name: &str) -> Result<(), ErrBox> {
+    Err(unsupported())
+  }
+}
@@ -1,19 +1,62 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
 use crate::deno_error::permission_denied;
+use crate::deno_error::{DenoError, ErrorKind};
 use crate::flags::DenoFlags;
+use crate::fs as deno_fs;
 use ansi_term::Style;
 use atty;
 use deno::ErrBox;
 use log;
+use serde_json::Value;
+use std::collections::BTreeSet;
 use std::collections::HashSet;
 use std::fmt;
+use std::fs::OpenOptions;
 use std::io;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 const PERMISSION_EMOJI: &str = "⚠️";
 
+/// Splits `host:port` into the host and the port, if any. An IPv6 host with a
+/// port is written in brackets, like `[::1]:8000`, and the brackets are
+/// stripped; a bare IPv6 address like `::1` is a host without a port.
+pub fn split_host_port(host_and_port: &str) -> (&str, Option<&str>) {
+  if host_and_port.starts_with('[') {
+    if let Some(end) = host_and_port.find(']') {
+      let rest = &host_and_port[end + 1..];
+      let port = if rest.starts_with(':') {
+        Some(&rest[1..])
+      } else {
+        None
+      };
+      return (&host_and_port[1..end], port);
+    }
+  }
+  let mut parts = host_and_port.splitn(2, ':');
+  let host = parts.next().unwrap();
+  match parts.next() {
+    Some(port) if !port.contains(':') => (host, Some(port)),
+    Some(_) => (host_and_port, None),
+    None => (host, None),
+  }
+}
+
+/// The inverse of `split_host_port`, putting IPv6 hosts in brackets.
+pub fn join_host_port(host: &str, port: Option<&str>) -> String {
+  let host = if host.contains(':') && !host.starts_with('[') {
+    format!("[{}]", host)
+  } else {
+    host.to_string()
+  };
+  match port {
+    Some(port) => format!("{}:{}", host, port),
+    None => host,
+  }
+}
+
 /// Tri-state value for storing permission state
 pub enum PermissionAccessorState {
   Allow = 0,
@@ -130,35 +173,73 @@ pub struct DenoPermissions {
   // Keep in sync with src/permissions.ts
   pub allow_read: PermissionAccessor,
   pub read_whitelist: Arc<HashSet<String>>,
+  pub read_blacklist: Arc<HashSet<String>>,
   pub allow_write: PermissionAccessor,
   pub write_whitelist: Arc<HashSet<String>>,
+  pub write_blacklist: Arc<HashSet<String>>,
   pub allow_net: PermissionAccessor,
   pub net_whitelist: Arc<HashSet<String>>,
+  pub net_blacklist: Arc<HashSet<String>>,
   pub allow_env: PermissionAccessor,
+  pub env_whitelist: Arc<HashSet<String>>,
   pub allow_run: PermissionAccessor,
+  pub run_whitelist: Arc<HashSet<String>>,
   pub allow_hrtime: PermissionAccessor,
+  pub allow_plugin: PermissionAccessor,
   pub no_prompts: AtomicBool,
+  // Targets allowed at the prompt, which aren't asked about again.
+  read_grants: Mutex<HashSet<String>>,
+  write_grants: Mutex<HashSet<String>>,
+  net_grants: Mutex<HashSet<String>>,
+  log: Option<PermissionLog>,
 }
 
 impl DenoPermissions {
   pub fn from_flags(flags: &DenoFlags) -> Self {
     Self {
-      allow_read: PermissionAccessor::from(flags.allow_read),
-      read_whitelist: Arc::new(flags.read_whitelist.iter().cloned().collect()),
-      allow_write: PermissionAccessor::from(flags.allow_write),
-      write_whitelist: Arc::new(
-        flags.write_whitelist.iter().cloned().collect(),
-      ),
-      allow_net: PermissionAccessor::from(flags.allow_net),
-      net_whitelist: Arc::new(flags.net_whitelist.iter().cloned().collect()),
-      allow_env: PermissionAccessor::from(flags.allow_env),
+      allow_read: permission_accessor(flags.allow_read, flags.deny_read),
+      read_whitelist: Arc::new(canonicalize_path_list(&flags.read_whitelist)),
+      read_blacklist: Arc::new(canonicalize_path_list(&flags.read_blacklist)),
+      allow_write: permission_accessor(flags.allow_write, flags.deny_write),
+      write_whitelist: Arc::new(canonicalize_path_list(&flags.write_whitelist)),
+      write_blacklist: Arc::new(canonicalize_path_list(&flags.write_blacklist)),
+      allow_net: permission_accessor(flags.allow_net, flags.deny_net),
+      net_whitelist: Arc::new(normalize_net_list(&flags.net_whitelist)),
+      net_blacklist: Arc::new(normalize_net_list(&flags.net_blacklist)),
+      allow_env: permission_accessor(flags.allow_env, flags.deny_env),
+      env_whitelist: Arc::new(flags.env_whitelist.iter().cloned().collect()),
       allow_run: PermissionAccessor::from(flags.allow_run),
+      run_whitelist: Arc::new(
+        flags
+          .run_whitelist
+          .iter()
+          .map(|program| {
+            if program.contains(std::path::is_separator) {
+              canonicalize_path(Path::new(program))
+                .to_string_lossy()
+                .into_owned()
+            } else {
+              program.clone()
+            }
+          })
+          .collect(),
+      ),
       allow_hrtime: PermissionAccessor::from(flags.allow_hrtime),
+      allow_plugin: PermissionAccessor::from(flags.allow_plugin),
       no_prompts: AtomicBool::new(flags.no_prompts),
+      log: flags
+        .log_permissions
+        .as_ref()
+        .map(|path| PermissionLog::open(path)),
+      ..Default::default()
     }
   }
 
   pub fn check_run(&self) -> Result<(), ErrBox> {
+    self.audit("run", "*", self.check_run_inner())
+  }
+
+  fn check_run_inner(&self) -> Result<(), ErrBox> {
     let msg = "access to run a subprocess";
 
     match self.allow_run.get_state() {
@@ -179,151 +260,183 @@ impl DenoPermissions {
     }
   }
 
-  pub fn check_read(&self, filename: &str) -> Result<(), ErrBox> {
-    let msg = &format!("read access to \"{}\"", filename);
-    match self.allow_read.get_state() {
+  pub fn check_plugin(&self, filename: &str) -> Result<(), ErrBox> {
+    self.audit("plugin", filename, self.check_plugin_inner(filename))
+  }
+
+  fn check_plugin_inner(&self, filename: &str) -> Result<(), ErrBox> {
+    let msg = &format!("access to load the plugin \"{}\"", filename);
+    match self.allow_plugin.get_state() {
       PermissionAccessorState::Allow => {
         self.log_perm_access(msg);
         Ok(())
       }
-      state => {
-        if check_path_white_list(filename, &self.read_whitelist) {
+      PermissionAccessorState::Ask => match self.try_permissions_prompt(msg) {
+        Err(e) => Err(e),
+        Ok(v) => {
+          self.allow_plugin.update_with_prompt_result(&v);
+          v.check()?;
           self.log_perm_access(msg);
           Ok(())
-        } else {
-          match state {
-            PermissionAccessorState::Ask => {
-              match self.try_permissions_prompt(msg) {
-                Err(e) => Err(e),
-                Ok(v) => {
-                  self.allow_read.update_with_prompt_result(&v);
-                  v.check()?;
-                  self.log_perm_access(msg);
-                  Ok(())
-                }
-              }
-            }
-            PermissionAccessorState::Deny => Err(permission_denied()),
-            _ => unreachable!(),
-          }
         }
+      },
+      PermissionAccessorState::Deny => Err(permission_denied()),
+    }
+  }
+
+  /// Like `check_run`, but passes without a prompt for programs in the run
+  /// whitelist. Programs given as a path are resolved against `cwd`, and bare
+  /// names are looked up in deno's own PATH right away. A whitelisted program
+  /// comes back as the canonical path that was checked, which is what must be
+  /// spawned: the child's PATH or cwd could point somewhere else.
+  pub fn check_run_program(
+    &self,
+    program: &str,
+    cwd: Option<&str>,
+  ) -> Result<Option<PathBuf>, ErrBox> {
+    let resolved = if program.contains(std::path::is_separator) {
+      let path = match cwd {
+        Some(cwd) => Path::new(cwd).join(program),
+        None => PathBuf::from(program),
+      };
+      deno_fs::resolve_from_cwd(&path.to_string_lossy())
+        .ok()
+        .map(|(path, _)| canonicalize_path(&path))
+        .filter(|path| {
+          self.run_whitelist.contains(path.to_string_lossy().as_ref())
+        })
+    } else {
+      find_in_path(program)
+        .and_then(|path| path.canonicalize().ok())
+        .filter(|path| {
+          self.run_whitelist.contains(program)
+            || self.run_whitelist.contains(path.to_string_lossy().as_ref())
+        })
+    };
+    match resolved {
+      Some(path) => {
+        self.log_perm_access(&format!("access to run \"{}\"", program));
+        self.audit("run", program, Ok(Some(path)))
       }
+      None => self.audit("run", program, self.check_run_inner().map(|_| None)),
     }
   }
 
-  pub fn check_write(&self, filename: &str) -> Result<(), ErrBox> {
-    let msg = &format!("write access to \"{}\"", filename);
-    match self.allow_write.get_state() {
+  pub fn check_read(&self, filename: &str) -> Result<(), ErrBox> {
+    self.audit("read", filename, self.check_read_inner(filename))
+  }
+
+  fn check_read_inner(&self, filename: &str) -> Result<(), ErrBox> {
+    let msg = &format!("read access to \"{}\"", filename);
+    if check_path_list(filename, &self.read_blacklist) {
+      return Err(permission_denied());
+    }
+    match self.allow_read.get_state() {
+      PermissionAccessorState::Deny => Err(permission_denied()),
       PermissionAccessorState::Allow => {
         self.log_perm_access(msg);
         Ok(())
       }
-      state => {
-        if check_path_white_list(filename, &self.write_whitelist) {
+      PermissionAccessorState::Ask => {
+        if check_path_list(filename, &self.read_whitelist)
+          || self.read_grants.lock().unwrap().contains(filename)
+        {
           self.log_perm_access(msg);
           Ok(())
         } else {
-          match state {
-            PermissionAccessorState::Ask => {
-              match self.try_permissions_prompt(msg) {
-                Err(e) => Err(e),
-                Ok(v) => {
-                  self.allow_write.update_with_prompt_result(&v);
-                  v.check()?;
-                  self.log_perm_access(msg);
-                  Ok(())
-                }
-              }
-            }
-            PermissionAccessorState::Deny => Err(permission_denied()),
-            _ => unreachable!(),
-          }
+          self.prompt_for_target(
+            &self.allow_read,
+            &self.read_grants,
+            filename,
+            msg,
+          )
         }
       }
     }
   }
 
-  pub fn check_net(&self, host_and_port: &str) -> Result<(), ErrBox> {
-    let msg = &format!("network access to \"{}\"", host_and_port);
-    match self.allow_net.get_state() {
+  pub fn check_write(&self, filename: &str) -> Result<(), ErrBox> {
+    self.audit("write", filename, self.check_write_inner(filename))
+  }
+
+  fn check_write_inner(&self, filename: &str) -> Result<(), ErrBox> {
+    let msg = &format!("write access to \"{}\"", filename);
+    if check_path_list(filename, &self.write_blacklist) {
+      return Err(permission_denied());
+    }
+    match self.allow_write.get_state() {
+      PermissionAccessorState::Deny => Err(permission_denied()),
       PermissionAccessorState::Allow => {
         self.log_perm_access(msg);
         Ok(())
       }
-      state => {
-        let parts = host_and_port.split(':').collect::<Vec<&str>>();
-        if match parts.len() {
-          2 => {
-            if self.net_whitelist.contains(parts[0]) {
-              true
-            } else {
-              self
-                .net_whitelist
-                .contains(&format!("{}:{}", parts[0], parts[1]))
-            }
-          }
-          1 => self.net_whitelist.contains(parts[0]),
-          _ => panic!("Failed to parse origin string: {}", host_and_port),
-        } {
+      PermissionAccessorState::Ask => {
+        if check_path_list(filename, &self.write_whitelist)
+          || self.write_grants.lock().unwrap().contains(filename)
+        {
           self.log_perm_access(msg);
           Ok(())
         } else {
-          self.check_net_inner(state, msg)
+          self.prompt_for_target(
+            &self.allow_write,
+            &self.write_grants,
+            filename,
+            msg,
+          )
         }
       }
     }
   }
 
+  pub fn check_net(&self, host_and_port: &str) -> Result<(), ErrBox> {
+    let (host, port) = split_host_port(host_and_port);
+    let result = self.check_net_host(host, port, host_and_port);
+    self.audit("net", host_and_port, result)
+  }
+
   pub fn check_net_url(&self, url: &url::Url) -> Result<(), ErrBox> {
-    let msg = &format!("network access to \"{}\"", url);
+    let host = url.host().unwrap().to_string();
+    let port = url.port().map(|port| port.to_string());
+    let result = self.check_net_host(
+      &host,
+      port.as_ref().map(String::as_str),
+      url.as_str(),
+    );
+    self.audit("net", url.as_str(), result)
+  }
+
+  fn check_net_host(
+    &self,
+    host: &str,
+    port: Option<&str>,
+    target: &str,
+  ) -> Result<(), ErrBox> {
+    let msg = &format!("network access to \"{}\"", target);
+    if check_net_list(host, port, &self.net_blacklist) {
+      return Err(permission_denied());
+    }
     match self.allow_net.get_state() {
+      PermissionAccessorState::Deny => Err(permission_denied()),
       PermissionAccessorState::Allow => {
         self.log_perm_access(msg);
         Ok(())
       }
-      state => {
-        let host = url.host().unwrap();
-        let whitelist_result = {
-          if self.net_whitelist.contains(&format!("{}", host)) {
-            true
-          } else {
-            match url.port() {
-              Some(port) => {
-                self.net_whitelist.contains(&format!("{}:{}", host, port))
-              }
-              None => false,
-            }
-          }
-        };
-        if whitelist_result {
+      PermissionAccessorState::Ask => {
+        let host_and_port = join_host_port(host, port);
+        if check_net_list(host, port, &self.net_whitelist)
+          || self.net_grants.lock().unwrap().contains(&host_and_port)
+        {
           self.log_perm_access(msg);
           Ok(())
         } else {
-          self.check_net_inner(state, msg)
-        }
-      }
-    }
-  }
-
-  fn check_net_inner(
-    &self,
-    state: PermissionAccessorState,
-    prompt_str: &str,
-  ) -> Result<(), ErrBox> {
-    match state {
-      PermissionAccessorState::Ask => {
-        match self.try_permissions_prompt(prompt_str) {
-          Err(e) => Err(e),
-          Ok(v) => {
-            self.allow_net.update_with_prompt_result(&v);
-            v.check()?;
-            self.log_perm_access(prompt_str);
-            Ok(())
-          }
+          self.prompt_for_target(
+            &self.allow_net,
+            &self.net_grants,
+            &host_and_port,
+            msg,
+          )
         }
       }
-      PermissionAccessorState::Deny => Err(permission_denied()),
-      _ => unreachable!(),
     }
   }
 
@@ -347,6 +460,60 @@ impl DenoPermissions {
     }
   }
 
+  /// Like `check_env`, but passes without a prompt for variables in the env
+  /// whitelist, unless env access is denied outright.
+  pub fn check_env_var(&self, key: &str) -> Result<(), ErrBox> {
+    match self.allow_env.get_state() {
+      PermissionAccessorState::Deny => Err(permission_denied()),
+      _ if self.env_whitelist.contains(key) => {
+        self.log_perm_access(&format!(
+          "access to environment variable \"{}\"",
+          key
+        ));
+        Ok(())
+      }
+      _ => self.check_env(),
+    }
+  }
+
+  /// Writes the result of checking `permission` for `target` to the
+  /// permission log, if there is one, and passes it on.
+  fn audit<T>(
+    &self,
+    permission: &str,
+    target: &str,
+    result: Result<T, ErrBox>,
+  ) -> Result<T, ErrBox> {
+    if let Some(log) = &self.log {
+      log.write(permission, target, result.is_ok());
+    }
+    result
+  }
+
+  /// Sets the module the permission log names as the origin of checks.
+  pub fn set_log_origin(&mut self, origin: &str) {
+    if let Some(log) = &mut self.log {
+      log.origin = origin.to_string();
+    }
+  }
+
+  /// Prompts for access to `target`. An "allow" answer is remembered in
+  /// `grants`, so the same access isn't asked about again.
+  fn prompt_for_target(
+    &self,
+    accessor: &PermissionAccessor,
+    grants: &Mutex<HashSet<String>>,
+    target: &str,
+    msg: &str,
+  ) -> Result<(), ErrBox> {
+    let v = self.try_permissions_prompt(msg)?;
+    accessor.update_with_prompt_result(&v);
+    v.check()?;
+    grants.lock().unwrap().insert(target.to_string());
+    self.log_perm_access(msg);
+    Ok(())
+  }
+
   /// Try to present the user with a permission prompt
   /// will error with permission_denied if no_prompts is enabled
   fn try_permissions_prompt(
@@ -397,6 +564,143 @@ impl DenoPermissions {
     self.allow_hrtime.is_allow()
   }
 
+  pub fn allows_plugin(&self) -> bool {
+    self.allow_plugin.is_allow()
+  }
+
+  /// Describes the effective sandbox as a list of
+  /// `{permission, target?, state}` descriptors, where `state` is "granted",
+  /// "prompt" or "denied". Every permission gets one without a target, and
+  /// one per path, host, variable or program it is granted or denied for on
+  /// its own.
+  pub fn descriptors(&self) -> Vec<Value> {
+    let read_grants = self.read_grants.lock().unwrap();
+    let write_grants = self.write_grants.lock().unwrap();
+    let net_grants = self.net_grants.lock().unwrap();
+    let permissions = [
+      (
+        "read",
+        &self.allow_read,
+        self.read_whitelist.union(&read_grants).collect(),
+        self.read_blacklist.iter().collect(),
+      ),
+      (
+        "write",
+        &self.allow_write,
+        self.write_whitelist.union(&write_grants).collect(),
+        self.write_blacklist.iter().collect(),
+      ),
+      (
+        "net",
+        &self.allow_net,
+        self.net_whitelist.union(&net_grants).collect(),
+        self.net_blacklist.iter().collect(),
+      ),
+      (
+        "env",
+        &self.allow_env,
+        self.env_whitelist.iter().collect(),
+        BTreeSet::new(),
+      ),
+      (
+        "run",
+        &self.allow_run,
+        self.run_whitelist.iter().collect(),
+        BTreeSet::new(),
+      ),
+      (
+        "hrtime",
+        &self.allow_hrtime,
+        BTreeSet::new(),
+        BTreeSet::new(),
+      ),
+      (
+        "plugin",
+        &self.allow_plugin,
+        BTreeSet::new(),
+        BTreeSet::new(),
+      ),
+    ];
+
+    let mut descriptors = Vec::new();
+    for (permission, accessor, granted, denied) in permissions.iter() {
+      let state = match accessor.get_state() {
+        PermissionAccessorState::Allow => "granted",
+        PermissionAccessorState::Ask => "prompt",
+        PermissionAccessorState::Deny => "denied",
+      };
+      descriptors.push(json!({ "permission": permission, "state": state }));
+      let lists: &[(&BTreeSet<&String>, &str)] =
+        &[(granted, "granted"), (denied, "denied")];
+      for (targets, target_state) in lists {
+        for target in targets.iter() {
+          descriptors.push(json!({
+            "permission": permission,
+            "target": target,
+            "state": target_state,
+          }));
+        }
+      }
+    }
+    descriptors
+  }
+
+  /// Gives these permissions, of a worker being created, the current state of
+  /// the `parent` worker's. Those not in `kept` are denied instead.
+  pub fn inherit(&self, parent: &DenoPermissions, kept: &[String]) {
+    let accessors = [
+      ("read", &self.allow_read, &parent.allow_read),
+      ("write", &self.allow_write, &parent.allow_write),
+      ("net", &self.allow_net, &parent.allow_net),
+      ("env", &self.allow_env, &parent.allow_env),
+      ("run", &self.allow_run, &parent.allow_run),
+      ("hrtime", &self.allow_hrtime, &parent.allow_hrtime),
+      ("plugin", &self.allow_plugin, &parent.allow_plugin),
+    ];
+    for (name, accessor, parent_accessor) in accessors.iter() {
+      if kept.iter().any(|permission| permission == name) {
+        accessor.set_state(parent_accessor.get_state());
+      } else {
+        accessor.deny();
+      }
+    }
+  }
+
+  /// Prompts the user for the whole of `permission`, unless it was already
+  /// granted or denied for good. Returns whether it is granted afterwards,
+  /// which it never is without a terminal to prompt on.
+  pub fn request(&self, permission: &str) -> Result<bool, ErrBox> {
+    let (accessor, msg) = match permission {
+      "run" => (&self.allow_run, "access to run subprocesses"),
+      "read" => (&self.allow_read, "read access to all files"),
+      "write" => (&self.allow_write, "write access to all files"),
+      "net" => (&self.allow_net, "network access"),
+      "env" => (&self.allow_env, "access to environment variables"),
+      "hrtime" => (&self.allow_hrtime, "high resolution time measurement"),
+      "plugin" => (&self.allow_plugin, "access to load plugins"),
+      _ => {
+        return Err(
+          DenoError::new(
+            ErrorKind::InvalidInput,
+            format!("Unknown permission \"{}\"", permission),
+          )
+          .into(),
+        )
+      }
+    };
+    if let PermissionAccessorState::Ask = accessor.get_state() {
+      match self.try_permissions_prompt(msg) {
+        // Asking was the point, so even "once" grants it until revoked.
+        Ok(PromptResult::AllowAlways) | Ok(PromptResult::AllowOnce) => {
+          accessor.allow()
+        }
+        Ok(PromptResult::DenyAlways) => accessor.deny(),
+        Ok(PromptResult::DenyOnce) | Err(_) => {}
+      }
+    }
+    Ok(accessor.is_allow())
+  }
+
   pub fn revoke_run(&self) -> Result<(), ErrBox> {
     self.allow_run.revoke();
     Ok(())
@@ -404,16 +708,19 @@ impl DenoPermissions {
 
   pub fn revoke_read(&self) -> Result<(), ErrBox> {
     self.allow_read.revoke();
+    self.read_grants.lock().unwrap().clear();
     Ok(())
   }
 
   pub fn revoke_write(&self) -> Result<(), ErrBox> {
     self.allow_write.revoke();
+    self.write_grants.lock().unwrap().clear();
     Ok(())
   }
 
   pub fn revoke_net(&self) -> Result<(), ErrBox> {
     self.allow_net.revoke();
+    self.net_grants.lock().unwrap().clear();
     Ok(())
   }
 
@@ -425,6 +732,57 @@ impl DenoPermissions {
     self.allow_hrtime.revoke();
     Ok(())
   }
+
+  pub fn revoke_plugin(&self) -> Result<(), ErrBox> {
+    self.allow_plugin.revoke();
+    Ok(())
+  }
+}
+
+/// Where --log-permissions writes a line of JSON for every permission check.
+pub struct PermissionLog {
+  origin: String,
+  out: Mutex<Box<dyn Write + Send>>,
+}
+
+impl PermissionLog {
+  /// Opens the log for appending, or stderr if `path` is "-".
+  fn open(path: &str) -> Self {
+    let out: Box<dyn Write + Send> = if path == "-" {
+      Box::new(io::stderr())
+    } else {
+      match OpenOptions::new().create(true).append(true).open(path) {
+        Ok(file) => Box::new(file),
+        Err(e) => {
+          eprintln!("Cannot open permission log {}: {}", path, e);
+          Box::new(io::stderr())
+        }
+      }
+    };
+    Self {
+      origin: String::new(),
+      out: Mutex::new(out),
+    }
+  }
+
+  fn write(&self, permission: &str, target: &str, granted: bool) {
+    let line = json!({
+      "origin": self.origin,
+      "permission": permission,
+      "target": target,
+      "granted": granted,
+    });
+    let mut out = self.out.lock().unwrap();
+    let _ = writeln!(out, "{}", line);
+  }
+}
+
+impl fmt::Debug for PermissionLog {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("PermissionLog")
+      .field("origin", &self.origin)
+      .finish()
+  }
 }
 
 /// Quad-state value for representing user input on permission prompt
@@ -459,7 +817,7 @@ impl fmt::Display for PromptResult {
 }
 
 fn permission_prompt(message: &str) -> Result<PromptResult, ErrBox> {
-  let msg = format!("️{}  Deno requests {}. Grant? [a/y/n/d (a = allow always, y = allow once, n = deny once, d = deny always)] ", PERMISSION_EMOJI, message);
+  let msg = format!("️{}  Deno requests {}. Grant? [a/y/n/d (a = allow always, y = allow, n = deny once, d = deny always)] ", PERMISSION_EMOJI, message);
   // print to stderr so that if deno is > to a file this is still displayed.
   eprint!("{}", Style::new().bold().paint(msg));
   loop {
@@ -474,21 +832,20 @@ fn permission_prompt(message: &str) -> Result<PromptResult, ErrBox> {
       'd' => return Ok(PromptResult::DenyAlways),
       _ => {
         // If we don't get a recognized option try again.
-        let msg_again = format!("Unrecognized option '{}' [a/y/n/d (a = allow always, y = allow once, n = deny once, d = deny always)] ", ch);
+        let msg_again = format!("Unrecognized option '{}' [a/y/n/d (a = allow always, y = allow, n = deny once, d = deny always)] ", ch);
         eprint!("{}", Style::new().bold().paint(msg_again));
       }
     };
   }
 }
 
-fn check_path_white_list(
-  filename: &str,
-  white_list: &Arc<HashSet<String>>,
-) -> bool {
-  let mut path_buf = PathBuf::from(filename);
+/// Returns whether `filename` is one of the paths in `list` or inside one.
+fn check_path_list(filename: &str, list: &Arc<HashSet<String>>) -> bool {
+  let mut path_buf = canonicalize_path(Path::new(filename));
 
   loop {
-    if white_list.contains(path_buf.to_str().unwrap()) {
+    // The whitelist only has UTF-8 paths, which a symlink may not resolve to.
+    if path_buf.to_str().map_or(false, |path| list.contains(path)) {
       return true;
     }
     if !path_buf.pop() {
@@ -498,6 +855,89 @@ fn check_path_white_list(
   false
 }
 
+fn permission_accessor(allow: bool, deny: bool) -> PermissionAccessor {
+  if deny {
+    PermissionAccessor::new(PermissionAccessorState::Deny)
+  } else {
+    PermissionAccessor::from(allow)
+  }
+}
+
+fn canonicalize_path_list(paths: &[String]) -> HashSet<String> {
+  paths
+    .iter()
+    .map(|path| {
+      canonicalize_path(Path::new(path))
+        .to_string_lossy()
+        .into_owned()
+    })
+    .collect()
+}
+
+fn normalize_net_list(hosts: &[String]) -> HashSet<String> {
+  hosts
+    .iter()
+    .map(|entry| {
+      let (host, port) = split_host_port(entry);
+      join_host_port(host, port)
+    })
+    .collect()
+}
+
+/// Returns whether `host`, or `host:port` if there is a port, is in `list`.
+fn check_net_list(
+  host: &str,
+  port: Option<&str>,
+  list: &Arc<HashSet<String>>,
+) -> bool {
+  list.contains(&join_host_port(host, None))
+    || port.map_or(false, |port| {
+      list.contains(&join_host_port(host, Some(port)))
+    })
+}
+
+/// Looks `program` up in the PATH of deno itself, like the shell would.
+fn find_in_path(program: &str) -> Option<PathBuf> {
+  let paths = std::env::var_os("PATH")?;
+  let names: Vec<String> =
+    if cfg!(windows) && Path::new(program).extension().is_none() {
+      std::env::var("PATHEXT")
+        .unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string())
+        .split(';')
+        .map(|ext| format!("{}{}", program, ext))
+        .collect()
+    } else {
+      vec![program.to_string()]
+    };
+  std::env::split_paths(&paths)
+    .flat_map(|dir| names.iter().map(move |name| dir.join(name)))
+    .find(|path| path.is_file())
+}
+
+/// Resolves symlinks in `path`, so that a link inside a whitelisted directory
+/// doesn't grant access to where it points. Trailing components that don't
+/// exist yet, like a file about to be created, are appended to their
+/// canonicalized parent.
+fn canonicalize_path(path: &Path) -> PathBuf {
+  let mut missing = Vec::new();
+  let mut ancestor = path;
+  loop {
+    if let Ok(canonical) = ancestor.canonicalize() {
+      return missing
+        .iter()
+        .rev()
+        .fold(canonical, |path_buf, name| path_buf.join(name));
+    }
+    match (ancestor.parent(), ancestor.file_name()) {
+      (Some(parent), Some(name)) => {
+        missing.push(name);
+        ancestor = parent;
+      }
+      _ => return path.to_path_buf(),
+    }
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -551,6 +991,265 @@ mod tests {
     assert!(perms.check_write("/a/b").is_err());
   }
 
+  #[cfg(unix)]
+  #[test]
+  fn check_paths_through_symlink() {
+    let temp_dir = tempfile::TempDir::new().expect("tempdir fail");
+    let allowed = temp_dir.path().join("allowed");
+    let secret = temp_dir.path().join("secret");
+    std::fs::create_dir(&allowed).unwrap();
+    std::fs::create_dir(&secret).unwrap();
+    std::os::unix::fs::symlink(&secret, allowed.join("link")).unwrap();
+
+    let perms = DenoPermissions::from_flags(&DenoFlags {
+      read_whitelist: vec![allowed.to_str().unwrap().to_string()],
+      no_prompts: true,
+      ..Default::default()
+    });
+
+    let inside = allowed.join("new_file");
+    assert!(perms.check_read(inside.to_str().unwrap()).is_ok());
+    let through_link = allowed.join("link").join("file");
+    assert!(perms.check_read(through_link.to_str().unwrap()).is_err());
+    assert!(perms.check_read(secret.to_str().unwrap()).is_err());
+  }
+
+  #[cfg(target_os = "linux")]
+  #[test]
+  fn check_paths_through_symlink_to_non_utf8() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    let temp_dir = tempfile::TempDir::new().expect("tempdir fail");
+    let allowed = temp_dir.path().join("allowed");
+    let target = temp_dir.path().join(OsStr::from_bytes(b"\xff"));
+    std::fs::create_dir(&allowed).unwrap();
+    std::fs::create_dir(&target).unwrap();
+    std::os::unix::fs::symlink(&target, allowed.join("link")).unwrap();
+
+    let perms = DenoPermissions::from_flags(&DenoFlags {
+      read_whitelist: vec![allowed.to_str().unwrap().to_string()],
+      no_prompts: true,
+      ..Default::default()
+    });
+
+    let through_link = allowed.join("link").join("file");
+    assert!(perms.check_read(through_link.to_str().unwrap()).is_err());
+  }
+
+  #[cfg(unix)]
+  #[test]
+  fn test_check_run_program() {
+    let perms = DenoPermissions::from_flags(&DenoFlags {
+      run_whitelist: svec!["echo", "/a/bin/tool"],
+      no_prompts: true,
+      ..Default::default()
+    });
+
+    let echo = perms.check_run_program("echo", None).unwrap().unwrap();
+    assert!(echo.is_absolute());
+    assert_eq!(echo, echo.canonicalize().unwrap());
+    assert!(perms.check_run_program("cat", None).is_err());
+    let tool = Some(PathBuf::from("/a/bin/tool"));
+    assert_eq!(perms.check_run_program("/a/bin/tool", None).unwrap(), tool);
+    assert_eq!(
+      perms.check_run_program("./tool", Some("/a/bin")).unwrap(),
+      tool
+    );
+    assert_eq!(
+      perms
+        .check_run_program("../bin/tool", Some("/a/lib"))
+        .unwrap(),
+      tool
+    );
+    // Not in deno's PATH, whatever the child's would be.
+    assert!(perms.check_run_program("tool", None).is_err());
+    assert!(perms.check_run_program("/b/bin/echo", None).is_err());
+    assert!(perms.check_run().is_err());
+  }
+
+  #[test]
+  fn test_check_plugin() {
+    let perms = DenoPermissions::from_flags(&DenoFlags {
+      allow_plugin: true,
+      no_prompts: true,
+      ..Default::default()
+    });
+    assert!(perms.check_plugin("/a/libplugin.so").is_ok());
+    perms.revoke_plugin().unwrap();
+    assert!(perms.check_plugin("/a/libplugin.so").is_err());
+  }
+
+  #[test]
+  fn test_check_env_var() {
+    let perms = DenoPermissions::from_flags(&DenoFlags {
+      env_whitelist: svec!["HOME", "PATH"],
+      no_prompts: true,
+      ..Default::default()
+    });
+    assert!(perms.check_env_var("HOME").is_ok());
+    assert!(perms.check_env_var("AWS_SECRET_ACCESS_KEY").is_err());
+    assert!(perms.check_env().is_err());
+
+    let perms = DenoPermissions::from_flags(&DenoFlags {
+      env_whitelist: svec!["HOME"],
+      deny_env: true,
+      ..Default::default()
+    });
+    assert!(perms.check_env_var("HOME").is_err());
+  }
+
+  #[test]
+  fn test_descriptors() {
+    let perms = DenoPermissions::from_flags(&DenoFlags {
+      allow_net: true,
+      net_blacklist: svec!["169.254.169.254"],
+      env_whitelist: svec!["PATH", "HOME"],
+      ..Default::default()
+    });
+    perms.allow_hrtime.deny();
+    let descriptors = perms.descriptors();
+    assert_eq!(
+      descriptors,
+      vec![
+        json!({"permission": "read", "state": "prompt"}),
+        json!({"permission": "write", "state": "prompt"}),
+        json!({"permission": "net", "state": "granted"}),
+        json!({
+          "permission": "net",
+          "target": "169.254.169.254",
+          "state": "denied",
+        }),
+        json!({"permission": "env", "state": "prompt"}),
+        json!({"permission": "env", "target": "HOME", "state": "granted"}),
+        json!({"permission": "env", "target": "PATH", "state": "granted"}),
+        json!({"permission": "run", "state": "prompt"}),
+        json!({"permission": "hrtime", "state": "denied"}),
+        json!({"permission": "plugin", "state": "prompt"}),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_deny_overrides_allow() {
+    let perms = DenoPermissions::from_flags(&DenoFlags {
+      allow_read: true,
+      read_blacklist: svec!["/data/secrets"],
+      write_whitelist: svec!["/data"],
+      write_blacklist: svec!["/data/secrets"],
+      allow_net: true,
+      net_blacklist: svec!["169.254.169.254", "deno.land:8000"],
+      allow_env: true,
+      deny_env: true,
+      no_prompts: true,
+      ..Default::default()
+    });
+
+    assert!(perms.check_read("/data/public").is_ok());
+    assert!(perms.check_read("/data/secrets").is_err());
+    assert!(perms.check_read("/data/secrets/key").is_err());
+    assert!(perms.check_write("/data/public").is_ok());
+    assert!(perms.check_write("/data/secrets/key").is_err());
+
+    assert!(perms.check_net("deno.land:4545").is_ok());
+    assert!(perms.check_net("deno.land:8000").is_err());
+    assert!(perms.check_net("169.254.169.254:80").is_err());
+    let url = url::Url::parse("http://169.254.169.254/latest").unwrap();
+    assert!(perms.check_net_url(&url).is_err());
+
+    assert!(perms.check_env().is_err());
+  }
+
+  #[test]
+  fn test_deny_overrides_whitelist() {
+    let perms = DenoPermissions::from_flags(&DenoFlags {
+      read_whitelist: svec!["/a"],
+      deny_read: true,
+      write_whitelist: svec!["/a"],
+      deny_write: true,
+      net_whitelist: svec!["localhost"],
+      deny_net: true,
+      env_whitelist: svec!["HOME"],
+      deny_env: true,
+      no_prompts: true,
+      ..Default::default()
+    });
+
+    assert!(perms.check_read("/a/file").is_err());
+    assert!(perms.check_write("/a/file").is_err());
+    assert!(perms.check_net("localhost:4545").is_err());
+    let url = url::Url::parse("http://localhost:4545/").unwrap();
+    assert!(perms.check_net_url(&url).is_err());
+    assert!(perms.check_env_var("HOME").is_err());
+  }
+
+  #[test]
+  fn test_inherit() {
+    let parent = DenoPermissions::from_flags(&DenoFlags {
+      allow_read: true,
+      allow_net: true,
+      allow_env: true,
+      ..Default::default()
+    });
+    parent.allow_env.revoke();
+
+    let child = DenoPermissions::from_flags(&DenoFlags {
+      allow_read: true,
+      allow_net: true,
+      allow_env: true,
+      no_prompts: true,
+      ..Default::default()
+    });
+    child.inherit(&parent, &svec!["read", "env", "run"]);
+
+    assert!(child.allow_read.is_allow());
+    assert!(!child.allow_net.is_allow());
+    assert!(child.check_net("deno.land:443").is_err());
+    // Revoked in the parent after startup.
+    assert!(!child.allow_env.is_allow());
+    assert!(!child.allow_run.is_allow());
+  }
+
+  #[test]
+  fn test_grants() {
+    let perms = DenoPermissions::from_flags(&DenoFlags {
+      no_prompts: true,
+      ..Default::default()
+    });
+    perms
+      .read_grants
+      .lock()
+      .unwrap()
+      .insert("/a/file".to_string());
+    perms
+      .net_grants
+      .lock()
+      .unwrap()
+      .insert("deno.land:443".to_string());
+
+    assert!(perms.check_read("/a/file").is_ok());
+    assert!(perms.check_read("/a/other").is_err());
+    assert!(perms.check_write("/a/file").is_err());
+    assert!(perms.check_net("deno.land:443").is_ok());
+    assert!(perms.check_net("deno.land:80").is_err());
+
+    perms.revoke_read().unwrap();
+    assert!(perms.check_read("/a/file").is_err());
+  }
+
+  #[test]
+  fn test_request() {
+    let perms = DenoPermissions::from_flags(&DenoFlags {
+      allow_read: true,
+      no_prompts: true,
+      ..Default::default()
+    });
+    assert!(perms.request("read").unwrap());
+    // Without prompts, nothing else can be granted.
+    assert!(!perms.request("write").unwrap());
+    assert!(!perms.allows_write());
+    assert!(perms.request("bogus").is_err());
+  }
+
   #[test]
   fn test_check_net() {
     let perms = DenoPermissions::from_flags(&DenoFlags {
@@ -630,6 +1329,51 @@ mod tests {
       assert_eq!(*is_ok, perms.check_net_url(&u).is_ok());
     }
 
+    for (domain, is_ok) in domain_tests.iter() {
+      assert_eq!(*is_ok, perms.check_net(domain).is_ok());
+    }
+  }
+  #[test]
+  fn test_split_host_port() {
+    assert_eq!(split_host_port("deno.land"), ("deno.land", None));
+    assert_eq!(split_host_port("deno.land:80"), ("deno.land", Some("80")));
+    assert_eq!(split_host_port(":80"), ("", Some("80")));
+    assert_eq!(split_host_port("::1"), ("::1", None));
+    assert_eq!(split_host_port("[::1]"), ("::1", None));
+    assert_eq!(split_host_port("[::1]:80"), ("::1", Some("80")));
+    assert_eq!(join_host_port("::1", Some("80")), "[::1]:80");
+    assert_eq!(join_host_port("deno.land", None), "deno.land");
+  }
+
+  #[test]
+  fn test_check_net_ipv6() {
+    let perms = DenoPermissions::from_flags(&DenoFlags {
+      net_whitelist: svec!["::1", "[fe80::1]:8000"],
+      no_prompts: true,
+      ..Default::default()
+    });
+
+    let domain_tests = vec![
+      ("[::1]:1234", true),
+      ("::1", true),
+      ("[::2]:1234", false),
+      ("[fe80::1]:8000", true),
+      ("[fe80::1]:6000", false),
+      ("fe80::1", false),
+    ];
+
+    let url_tests = vec![
+      ("tcp://[::1]", true),
+      ("https://[::1]:3000", true),
+      ("https://[fe80::1]:8000", true),
+      ("https://[fe80::1]:6000", false),
+    ];
+
+    for (url_str, is_ok) in url_tests.iter() {
+      let u = url::Url::parse(url_str).unwrap();
+      assert_eq!(*is_ok, perms.check_net_url(&u).is_ok());
+    }
+
     for (domain, is_ok) in domain_tests.iter() {
       assert_eq!(*is_ok, perms.check_net(domain).is_ok());
     }
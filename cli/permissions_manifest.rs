@@ -0,0 +1,201 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+//! Loads a permissions manifest, a JSON file granting the permissions that
+//! would otherwise be given by the --allow-* flags:
+//!
+//! ```json
+//! {
+//!   "read": ["./data"],
+//!   "write": ["./data/out"],
+//!   "net": ["deno.land", "localhost:8000"],
+//!   "env": ["HOME", "PATH"],
+//!   "run": ["git"],
+//!   "hrtime": false,
+//!   "plugin": false
+//! }
+//! ```
+//!
+//! `read`, `write`, `net`, `env` and `run` take `true` or a whitelist, and
+//! `hrtime` and `plugin` take a boolean. Relative paths are resolved against
+//! the directory of the manifest. The manifest adds to the flags given on the
+//! command line.
+use crate::deno_error::{DenoError, ErrorKind};
+use crate::flags::{resolve_hosts, resolve_paths, resolve_programs, DenoFlags};
+use deno::ErrBox;
+use serde_derive::Deserialize;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+struct Manifest {
+  read: Grant,
+  write: Grant,
+  net: Grant,
+  env: Grant,
+  run: Grant,
+  hrtime: bool,
+  plugin: bool,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum Grant {
+  All(bool),
+  Only(Vec<String>),
+}
+
+impl Default for Grant {
+  fn default() -> Self {
+    Grant::All(false)
+  }
+}
+
+/// Grants the permissions of the manifest at `path` in `flags`.
+pub fn load(path: &str, flags: &mut DenoFlags) -> Result<(), ErrBox> {
+  let source = fs::read_to_string(path).map_err(|e| {
+    DenoError::new(
+      ErrorKind::NotFound,
+      format!("Cannot read permissions manifest {}: {}", path, e),
+    )
+  })?;
+  let manifest = parse(&source).map_err(|e| {
+    DenoError::new(ErrorKind::InvalidInput, format!("{}: {}", path, e))
+  })?;
+  let dir = Path::new(path).parent().unwrap_or_else(|| Path::new(""));
+  let relative_to_dir = |paths: Vec<String>| -> Vec<String> {
+    paths
+      .into_iter()
+      .map(|path| dir.join(path).to_string_lossy().into_owned())
+      .collect()
+  };
+
+  match manifest.read {
+    Grant::All(allow) => flags.allow_read |= allow,
+    Grant::Only(paths) => flags
+      .read_whitelist
+      .extend(resolve_paths(relative_to_dir(paths))),
+  }
+  match manifest.write {
+    Grant::All(allow) => flags.allow_write |= allow,
+    Grant::Only(paths) => flags
+      .write_whitelist
+      .extend(resolve_paths(relative_to_dir(paths))),
+  }
+  match manifest.net {
+    Grant::All(allow) => flags.allow_net |= allow,
+    Grant::Only(hosts) => flags.net_whitelist.extend(resolve_hosts(hosts)),
+  }
+  match manifest.env {
+    Grant::All(allow) => flags.allow_env |= allow,
+    Grant::Only(vars) => flags.env_whitelist.extend(vars),
+  }
+  match manifest.run {
+    Grant::All(allow) => flags.allow_run |= allow,
+    Grant::Only(programs) => {
+      // Bare program names are looked up in PATH, so only paths are
+      // relative to the manifest.
+      let programs = programs
+        .into_iter()
+        .map(|program| {
+          if program.contains(std::path::is_separator) {
+            dir.join(program).to_string_lossy().into_owned()
+          } else {
+            program
+          }
+        })
+        .collect();
+      flags.run_whitelist.extend(resolve_programs(programs))
+    }
+  }
+  flags.allow_hrtime |= manifest.hrtime;
+  flags.allow_plugin |= manifest.plugin;
+  Ok(())
+}
+
+fn parse(source: &str) -> Result<Manifest, String> {
+  serde_json::from_str(source).map_err(|e| {
+    // Untagged enums only say that no variant matched.
+    let message = e.to_string();
+    if message.starts_with("data did not match any variant") {
+      format!(
+        "expected true, false or a list of strings at line {} column {}",
+        e.line(),
+        e.column()
+      )
+    } else {
+      message
+    }
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse() {
+    let manifest = parse(
+      r#"{
+        "read": ["./data"],
+        "net": true,
+        "env": true,
+        "run": ["git"]
+      }"#,
+    )
+    .unwrap();
+    assert_eq!(
+      manifest,
+      Manifest {
+        read: Grant::Only(vec!["./data".to_string()]),
+        net: Grant::All(true),
+        env: Grant::All(true),
+        run: Grant::Only(vec!["git".to_string()]),
+        ..Default::default()
+      }
+    );
+  }
+
+  #[test]
+  fn test_parse_errors() {
+    assert!(parse(r#"{"reed": true}"#)
+      .unwrap_err()
+      .starts_with("unknown field `reed`"));
+    assert!(parse(r#"{"net": "deno.land"}"#)
+      .unwrap_err()
+      .starts_with("expected true, false or a list of strings at line 1"));
+    assert!(parse(r#"{"hrtime": ["HOME"]}"#)
+      .unwrap_err()
+      .starts_with("invalid type: sequence, expected a boolean"));
+  }
+
+  #[test]
+  fn test_load() {
+    let temp_dir = tempfile::TempDir::new().expect("tempdir fail");
+    let path = temp_dir.path().join("deno-permissions.json");
+    fs::write(
+      &path,
+      r#"{"write": ["out"], "env": ["HOME"], "hrtime": true}"#,
+    )
+    .unwrap();
+
+    let mut flags = DenoFlags {
+      allow_net: true,
+      ..Default::default()
+    };
+    load(path.to_str().unwrap(), &mut flags).unwrap();
+    let (_, out_dir) = crate::fs::resolve_from_cwd(
+      temp_dir.path().join("out").to_str().unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+      flags,
+      DenoFlags {
+        allow_net: true,
+        write_whitelist: vec![out_dir],
+        env_whitelist: vec!["HOME".to_string()],
+        allow_hrtime: true,
+        ..Default::default()
+      }
+    );
+  }
+}
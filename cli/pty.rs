@@ -0,0 +1,174 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+use futures::Poll;
+use mio::unix::EventedFd;
+use mio::{Evented, PollOpt, Ready, Token};
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+use std::process::Command;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
+use tokio::reactor::PollEvented2;
+
+/// The master side of a pseudo-terminal, in non-blocking mode so that the
+/// reactor can drive it.
+struct MasterFd(File);
+
+impl Evented for MasterFd {
+  fn register(
+    &self,
+    poll: &mio::Poll,
+    token: Token,
+    interest: Ready,
+    opts: PollOpt,
+  ) -> io::Result<()> {
+    EventedFd(&self.0.as_raw_fd()).register(poll, token, interest, opts)
+  }
+
+  fn reregister(
+    &self,
+    poll: &mio::Poll,
+    token: Token,
+    interest: Ready,
+    opts: PollOpt,
+  ) -> io::Result<()> {
+    EventedFd(&self.0.as_raw_fd()).reregister(poll, token, interest, opts)
+  }
+
+  fn deregister(&self, poll: &mio::Poll) -> io::Result<()> {
+    EventedFd(&self.0.as_raw_fd()).deregister(poll)
+  }
+}
+
+impl Read for MasterFd {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    self.0.read(buf)
+  }
+}
+
+impl Write for MasterFd {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    self.0.write(buf)
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    self.0.flush()
+  }
+}
+
+/// Lets us talk to a subprocess attached to a pseudo-terminal as if we were
+/// its terminal: what the subprocess writes is read here, and what is written
+/// here is what it reads as keyboard input.
+pub struct PtyMaster {
+  master: PollEvented2<MasterFd>,
+}
+
+impl PtyMaster {
+  /// Changes the size of the terminal. The subprocess is sent SIGWINCH.
+  pub fn resize(&self, columns: u16, rows: u16) -> io::Result<()> {
+    set_size(self.master.get_ref().0.as_raw_fd(), columns, rows)
+  }
+}
+
+impl Read for PtyMaster {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    match self.master.read(buf) {
+      // Linux reports EIO rather than EOF once every process has closed the
+      // slave side.
+      Err(ref e) if e.raw_os_error() == Some(libc::EIO) => Ok(0),
+      r => r,
+    }
+  }
+}
+
+impl AsyncRead for PtyMaster {}
+
+impl Write for PtyMaster {
+  fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    self.master.write(buf)
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    self.master.flush()
+  }
+}
+
+impl AsyncWrite for PtyMaster {
+  fn shutdown(&mut self) -> Poll<(), io::Error> {
+    Ok(futures::Async::Ready(()))
+  }
+}
+
+fn set_size(fd: RawFd, columns: u16, rows: u16) -> io::Result<()> {
+  let size = libc::winsize {
+    ws_row: rows,
+    ws_col: columns,
+    ws_xpixel: 0,
+    ws_ypixel: 0,
+  };
+  if unsafe { libc::ioctl(fd, libc::TIOCSWINSZ, &size) } == -1 {
+    return Err(io::Error::last_os_error());
+  }
+  Ok(())
+}
+
+fn set_cloexec(fd: RawFd) -> io::Result<()> {
+  if unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) } == -1 {
+    return Err(io::Error::last_os_error());
+  }
+  Ok(())
+}
+
+/// Opens a pseudo-terminal of the given size. Returns the master side, and
+/// the slave side to hand to a subprocess as its stdio.
+pub fn open(columns: u16, rows: u16) -> io::Result<(PtyMaster, File)> {
+  let mut master: libc::c_int = -1;
+  let mut slave: libc::c_int = -1;
+  let r = unsafe {
+    libc::openpty(
+      &mut master,
+      &mut slave,
+      std::ptr::null_mut(),
+      std::ptr::null_mut(),
+      std::ptr::null_mut(),
+    )
+  };
+  if r == -1 {
+    return Err(io::Error::last_os_error());
+  }
+  // Owning both right away closes them should anything below fail.
+  let master = unsafe { File::from_raw_fd(master) };
+  let slave = unsafe { File::from_raw_fd(slave) };
+
+  set_cloexec(master.as_raw_fd())?;
+  set_cloexec(slave.as_raw_fd())?;
+  set_size(master.as_raw_fd(), columns, rows)?;
+  unsafe {
+    let fd = master.as_raw_fd();
+    let flags = libc::fcntl(fd, libc::F_GETFL);
+    if flags == -1
+      || libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK) == -1
+    {
+      return Err(io::Error::last_os_error());
+    }
+  }
+
+  let master = PollEvented2::new(MasterFd(master));
+  Ok((PtyMaster { master }, slave))
+}
+
+/// Starts the child in a session of its own, with the pseudo-terminal on its
+/// stdin as its controlling terminal, so that job control and ^C work in it.
+pub fn set_controlling_terminal(c: &mut Command) {
+  use std::os::unix::process::CommandExt;
+  unsafe {
+    c.pre_exec(|| {
+      if libc::setsid() == -1 || libc::ioctl(0, libc::TIOCSCTTY as _, 0) == -1 {
+        return Err(io::Error::last_os_error());
+      }
+      Ok(())
+    });
+  }
+}
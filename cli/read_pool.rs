@@ -0,0 +1,73 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+//! The buffers which `Deno.readPooled()` reads into. They are handed to JS as
+//! external buffers, and go back to the pool once JS releases or garbage
+//! collects them, so that reading a stream doesn't allocate per read.
+use std::ops::{Deref, DerefMut};
+use std::sync::Mutex;
+
+pub const BUF_LEN: usize = 64 * 1024;
+// Buffers dropped while the pool is full are freed.
+const MAX_POOLED: usize = 64;
+
+lazy_static! {
+  static ref POOL: Mutex<Vec<Box<[u8]>>> = Mutex::new(Vec::new());
+}
+
+/// A buffer of the pool, which derefs to the bytes read into it.
+pub struct PooledBuf {
+  buf: Box<[u8]>,
+  len: usize,
+}
+
+impl PooledBuf {
+  pub fn take() -> Self {
+    let buf = POOL.lock().unwrap().pop();
+    let buf = buf.unwrap_or_else(|| vec![0; BUF_LEN].into_boxed_slice());
+    Self { buf, len: 0 }
+  }
+
+  pub fn set_len(&mut self, len: usize) {
+    assert!(len <= self.buf.len());
+    self.len = len;
+  }
+}
+
+/// The whole buffer, to read into.
+impl AsMut<[u8]> for PooledBuf {
+  fn as_mut(&mut self) -> &mut [u8] {
+    &mut self.buf
+  }
+}
+
+impl Deref for PooledBuf {
+  type Target = [u8];
+  fn deref(&self) -> &[u8] {
+    &self.buf[..self.len]
+  }
+}
+
+impl DerefMut for PooledBuf {
+  fn deref_mut(&mut self) -> &mut [u8] {
+    &mut self.buf[..self.len]
+  }
+}
+
+impl Drop for PooledBuf {
+  fn drop(&mut self) {
+    let mut pool = POOL.lock().unwrap();
+    if pool.len() < MAX_POOLED {
+      let buf = std::mem::replace(&mut self.buf, Box::new([]));
+      pool.push(buf);
+    }
+  }
+}
+
+#[test]
+fn test_pooled_buf() {
+  let mut buf = PooledBuf::take();
+  assert_eq!(buf.as_mut().len(), BUF_LEN);
+  assert_eq!(buf.len(), 0);
+  buf.as_mut()[..3].copy_from_slice(b"abc");
+  buf.set_len(3);
+  assert_eq!(&*buf, b"abc");
+}
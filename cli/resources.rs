@@ -10,9 +10,18 @@
 
 use crate::deno_error;
 use crate::deno_error::bad_resource;
+use crate::deno_error::resource_closed;
 use crate::http_body::HttpBody;
+use crate::ops::fs_events::{FsEvent, FsEventsWatcher};
+use crate::ops::glob::GlobWalker;
+use crate::ops::signal::SignalWatcher;
+use crate::ops::tty::{ConsoleResizeWatcher, ConsoleSize};
+#[cfg(unix)]
+use crate::pty::PtyMaster;
+use crate::read_pool::PooledBuf;
 use crate::repl::Repl;
 use crate::state::WorkerChannels;
+use crate::stdio::{StdinReader, StdioWriter};
 
 use deno::Buf;
 use deno::ErrBox;
@@ -22,15 +31,22 @@ use futures::Future;
 use futures::Poll;
 use futures::Sink;
 use futures::Stream;
+use memmap::MmapMut;
 use reqwest::r#async::Decoder as ReqwestDecoder;
 use std;
+use std::any::Any;
+use std::cell::Cell;
 use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::io::{Error, Read, Seek, SeekFrom, Write};
 use std::net::{Shutdown, SocketAddr};
+use std::ops::{Deref, DerefMut};
 use std::process::ExitStatus;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::SystemTime;
 use tokio;
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::TcpStream;
@@ -43,8 +59,29 @@ use tokio_rustls::TlsAcceptor;
 pub type ResourceId = u32; // Sometimes referred to RID.
 
 // These store Deno's file descriptors. These are not necessarily the operating
-// system ones.
-type ResourceTable = BTreeMap<ResourceId, Repr>;
+// system ones. A shard of the table also keeps what goes along with each of
+// its resources, so that nothing but the shard is locked to use one.
+#[derive(Default)]
+struct ResourceTable {
+  entries: BTreeMap<ResourceId, Repr>,
+  info: HashMap<ResourceId, ResourceInfo>,
+  // Ops pending on a resource, with the task to wake up when it is closed,
+  // once they have been polled.
+  pending_ops: HashMap<ResourceId, PendingOps>,
+}
+
+impl Deref for ResourceTable {
+  type Target = BTreeMap<ResourceId, Repr>;
+  fn deref(&self) -> &Self::Target {
+    &self.entries
+  }
+}
+
+impl DerefMut for ResourceTable {
+  fn deref_mut(&mut self) -> &mut Self::Target {
+    &mut self.entries
+  }
+}
 
 #[cfg(not(windows))]
 use std::os::unix::io::FromRawFd;
@@ -59,39 +96,135 @@ extern crate winapi;
 lazy_static! {
   // Starts at 3 because stdio is [0-2].
   static ref NEXT_RID: AtomicUsize = AtomicUsize::new(3);
-  static ref RESOURCE_TABLE: Mutex<ResourceTable> = Mutex::new({
-    let mut m = BTreeMap::new();
+  static ref RESOURCE_TABLE: Vec<Mutex<ResourceTable>> = {
+    let shards: Vec<Mutex<ResourceTable>> =
+      (0..TABLE_SHARDS).map(|_| Mutex::default()).collect();
     // TODO Load these lazily during lookup?
-    m.insert(0, Repr::Stdin(tokio::io::stdin()));
-
-    m.insert(1, Repr::Stdout({
-      #[cfg(not(windows))]
-      let stdout = unsafe { std::fs::File::from_raw_fd(1) };
-      #[cfg(windows)]
-      let stdout = unsafe {
-        std::fs::File::from_raw_handle(winapi::um::processenv::GetStdHandle(
-            winapi::um::winbase::STD_OUTPUT_HANDLE))
-      };
-      tokio::fs::File::from_std(stdout)
-    }));
+    let stdin = Repr::Stdin(StdinReader::new());
+    let stdout = Repr::Stdout(StdioWriter::new("stdout", stdout_file()));
+    let stderr = Repr::Stderr(StdioWriter::new("stderr", std::io::stderr()));
+    for (rid, repr) in vec![stdin, stdout, stderr].into_iter().enumerate() {
+      let mut table = shards[rid % TABLE_SHARDS].lock().unwrap();
+      table.insert(rid as u32, repr);
+      table.info.insert(rid as u32, ResourceInfo::new(None));
+    }
+    shards
+  };
+  static ref NEXT_PENDING_OP: AtomicUsize = AtomicUsize::new(0);
+}
 
-    m.insert(2, Repr::Stderr(tokio::io::stderr()));
-    m
-  });
+type PendingOps = HashMap<usize, Option<futures::task::Task>>;
+
+// The table is split by rid into shards with a lock each, so that ops on
+// different resources seldom wait for one another.
+const TABLE_SHARDS: usize = 16;
+
+/// Locks the shard of the table which holds `rid`. No other shard may be
+/// locked meanwhile, except by `lock_all_tables`.
+fn lock_table(rid: ResourceId) -> MutexGuard<'static, ResourceTable> {
+  RESOURCE_TABLE[rid as usize % TABLE_SHARDS].lock().unwrap()
+}
+
+/// Locks every shard of the table, in order.
+fn lock_all_tables() -> Vec<MutexGuard<'static, ResourceTable>> {
+  RESOURCE_TABLE
+    .iter()
+    .map(|shard| shard.lock().unwrap())
+    .collect()
+}
+
+lazy_static! {
+  static ref CLEANUP_HOOKS: Mutex<Vec<Box<dyn FnOnce() + Send>>> =
+    Mutex::new(Vec::new());
+}
+
+thread_local! {
+  // The op being dispatched or polled on this thread, see `with_op`.
+  static CURRENT_OP: Cell<Option<&'static str>> = Cell::new(None);
+}
+
+/// Where a resource came from and how much it has been used, which helps
+/// tracking down resources that are leaked.
+#[derive(Clone, Debug)]
+pub struct ResourceInfo {
+  pub created: SystemTime,
+  /// The op which added the resource. None for stdio.
+  pub op: Option<&'static str>,
+  pub bytes_read: u64,
+  pub bytes_written: u64,
+}
+
+impl ResourceInfo {
+  fn new(op: Option<&'static str>) -> Self {
+    Self {
+      created: SystemTime::now(),
+      op,
+      bytes_read: 0,
+      bytes_written: 0,
+    }
+  }
+}
+
+/// Runs `f` on behalf of the op `name`, which resources added meanwhile are
+/// attributed to.
+pub fn with_op<F, R>(name: &'static str, f: F) -> R
+where
+  F: FnOnce() -> R,
+{
+  let previous = CURRENT_OP.with(|op| op.replace(Some(name)));
+  let r = f();
+  CURRENT_OP.with(|op| op.set(previous));
+  r
+}
+
+fn count_bytes(
+  table: &mut ResourceTable,
+  rid: ResourceId,
+  read: bool,
+  n: usize,
+) {
+  if let Some(info) = table.info.get_mut(&rid) {
+    if read {
+      info.bytes_read += n as u64;
+    } else {
+      info.bytes_written += n as u64;
+    }
+  }
+}
+
+/// Takes ownership of the process's stdout, closing it once dropped.
+#[cfg(not(windows))]
+fn stdout_file() -> std::fs::File {
+  unsafe { std::fs::File::from_raw_fd(1) }
+}
+
+#[cfg(windows)]
+fn stdout_file() -> std::fs::File {
+  unsafe {
+    std::fs::File::from_raw_handle(winapi::um::processenv::GetStdHandle(
+      winapi::um::winbase::STD_OUTPUT_HANDLE,
+    ))
+  }
 }
 
 // Internal representation of Resource.
 enum Repr {
-  Stdin(tokio::io::Stdin),
-  Stdout(tokio::fs::File),
-  Stderr(tokio::io::Stderr),
+  Stdin(StdinReader),
+  Stdout(StdioWriter<std::fs::File>),
+  Stderr(StdioWriter<std::io::Stderr>),
   FsFile(tokio::fs::File),
+  // An anonymous temporary file, see `add_temp_file`. It's used like an
+  // FsFile, but is the only kind of file that may be linked into place.
+  TempFile(tokio::fs::File),
+  // A file opened for reading only up to a given number of bytes, if any.
+  FileStream(tokio::fs::File, Option<u64>),
   // Since TcpListener might be closed while there is a pending accept task,
   // we need to track the task so that when the listener is closed,
   // this pending task could be notified and die.
   // Currently TcpListener itself does not take care of this issue.
   // See: https://github.com/tokio-rs/tokio/issues/846
-  TcpListener(tokio::net::TcpListener, Option<futures::task::Task>),
+  // The flag is set while the listener is draining, see `Resource::drain`.
+  TcpListener(tokio::net::TcpListener, Option<futures::task::Task>, bool),
   TlsListener(
     tokio::net::TcpListener,
     TlsAcceptor,
@@ -105,27 +238,63 @@ enum Repr {
   // Enum size is bounded by the largest variant.
   // Use `Box` around large `Child` struct.
   // https://rust-lang.github.io/rust-clippy/master/index.html#large_enum_variant
-  Child(Box<tokio_process::Child>),
+  Child(Box<ChildResource>),
   ChildStdin(tokio_process::ChildStdin),
   ChildStdout(tokio_process::ChildStdout),
   ChildStderr(tokio_process::ChildStderr),
   Worker(WorkerChannels),
+  FsEvents(FsEventsWatcher),
+  Mmap(MmapMut),
+  // The bytes of a read of `Deno.readPooled()`, until JS takes them.
+  ReadBuffer(PooledBuf),
+  Glob(Arc<Mutex<GlobWalker>>),
+  Tee(TeeBranch),
+  ConsoleResize(ConsoleResizeWatcher),
+  #[cfg(unix)]
+  Pty(PtyMaster),
+  Signal(SignalWatcher),
+  // A resource of an embedder's ops, see `add_custom`.
+  Custom(&'static str, Arc<Mutex<Box<dyn Any + Send>>>),
 }
 
 /// If the given rid is open, this returns the type of resource, E.G. "worker".
 /// If the rid is closed or was never open, it returns None.
 pub fn get_type(rid: ResourceId) -> Option<String> {
-  let table = RESOURCE_TABLE.lock().unwrap();
+  let table = lock_table(rid);
   table.get(&rid).map(inspect_repr)
 }
 
 pub fn table_entries() -> Vec<(u32, String)> {
-  let table = RESOURCE_TABLE.lock().unwrap();
+  let tables = lock_all_tables();
 
-  table
+  let mut entries: Vec<(u32, String)> = tables
     .iter()
+    .flat_map(|table| table.iter())
     .map(|(key, value)| (*key, inspect_repr(&value)))
-    .collect()
+    .collect();
+  entries.sort();
+  entries
+}
+
+/// Like `table_entries`, along with what is known about each resource.
+pub fn table_info() -> Vec<(u32, String, ResourceInfo)> {
+  let tables = lock_all_tables();
+
+  let mut entries: Vec<(u32, String, ResourceInfo)> = tables
+    .iter()
+    .flat_map(|table| {
+      table.iter().map(move |(key, value)| {
+        let i = table
+          .info
+          .get(key)
+          .cloned()
+          .unwrap_or_else(|| ResourceInfo::new(None));
+        (*key, inspect_repr(&value), i)
+      })
+    })
+    .collect();
+  entries.sort_by_key(|(key, _, _)| *key);
+  entries
 }
 
 #[test]
@@ -137,13 +306,45 @@ fn test_table_entries() {
   assert_eq!(entries[2], (2, String::from("stderr")));
 }
 
+#[test]
+fn test_resource_info() {
+  let rid = with_op("open", new_rid);
+  let info = lock_table(rid).info.get(&rid).cloned().unwrap();
+  assert_eq!(info.op, Some("open"));
+  assert_eq!(info.bytes_read, 0);
+  assert_eq!(info.bytes_written, 0);
+  count_bytes(&mut lock_table(rid), rid, true, 3);
+  table_info();
+  count_bytes(&mut lock_table(rid), rid, true, 3);
+  count_bytes(&mut lock_table(rid), rid, false, 5);
+  let info = lock_table(rid).info.get(&rid).cloned().unwrap();
+  assert_eq!(info.bytes_read, 3);
+  assert_eq!(info.bytes_written, 5);
+  lock_table(rid).info.remove(&rid);
+}
+
+#[test]
+fn test_custom() {
+  let resource = add_custom("counter", 1u32);
+  assert_eq!(get_type(resource.rid), Some(String::from("counter")));
+  let r = with_custom(resource.rid, |count: &mut u32| {
+    *count += 1;
+    *count
+  });
+  assert_eq!(r.unwrap(), 2);
+  assert!(with_custom(resource.rid, |_: &mut String| ()).is_err());
+  resource.close();
+  assert!(with_custom(resource.rid, |_: &mut u32| ()).is_err());
+}
+
 fn inspect_repr(repr: &Repr) -> String {
   let h_repr = match repr {
     Repr::Stdin(_) => "stdin",
     Repr::Stdout(_) => "stdout",
     Repr::Stderr(_) => "stderr",
-    Repr::FsFile(_) => "fsFile",
-    Repr::TcpListener(_, _) => "tcpListener",
+    Repr::FsFile(_) | Repr::TempFile(_) => "fsFile",
+    Repr::FileStream(_, _) => "fileStream",
+    Repr::TcpListener(_, _, _) => "tcpListener",
     Repr::TlsListener(_, _, _) => "tlsListener",
     Repr::TcpStream(_) => "tcpStream",
     Repr::ClientTlsStream(_) => "clientTlsStream",
@@ -155,11 +356,59 @@ fn inspect_repr(repr: &Repr) -> String {
     Repr::ChildStdout(_) => "childStdout",
     Repr::ChildStderr(_) => "childStderr",
     Repr::Worker(_) => "worker",
+    Repr::FsEvents(_) => "fsEvents",
+    Repr::Mmap(_) => "mmap",
+    Repr::ReadBuffer(_) => "readBuffer",
+    Repr::Glob(_) => "glob",
+    Repr::Tee(_) => "tee",
+    Repr::ConsoleResize(_) => "consoleResize",
+    #[cfg(unix)]
+    Repr::Pty(_) => "pty",
+    Repr::Signal(_) => "signal",
+    Repr::Custom(name, _) => *name,
   };
 
   String::from(h_repr)
 }
 
+/// Has `hook` run by `cleanup`, once the resources are gone. Hooks run in the
+/// order they were added.
+pub fn on_cleanup<F: FnOnce() + Send + 'static>(hook: F) {
+  CLEANUP_HOOKS.lock().unwrap().push(Box::new(hook));
+}
+
+/// Runs right before the process exits, however it does: writes what is
+/// still queued for stdout and stderr, and drops every other resource but
+/// children, which are left running as they would be anyway. Then runs the
+/// hooks added with `on_cleanup`, each only once.
+pub fn cleanup() {
+  let mut tables = lock_all_tables();
+  let mut dropped = Vec::new();
+  for table in tables.iter_mut() {
+    let rids: Vec<ResourceId> = table.keys().cloned().collect();
+    for rid in rids {
+      match table.get_mut(&rid) {
+        Some(Repr::Stdout(ref mut w)) => {
+          let _ = w.flush();
+        }
+        Some(Repr::Stderr(ref mut w)) => {
+          let _ = w.flush();
+        }
+        Some(Repr::Stdin(_)) | Some(Repr::Child(_)) => {}
+        _ => dropped.extend(table.remove(&rid)),
+      }
+    }
+  }
+  // Some resources lock the table again when they are dropped.
+  drop(tables);
+  drop(dropped);
+
+  let hooks: Vec<_> = CLEANUP_HOOKS.lock().unwrap().drain(..).collect();
+  for hook in hooks {
+    hook();
+  }
+}
+
 // Abstract async file interface.
 // Ideally in unix, if Resource represents an OS rid, it will be the same.
 #[derive(Clone, Debug)]
@@ -170,26 +419,37 @@ pub struct Resource {
 impl Resource {
   // TODO Should it return a Resource instead of net::TcpStream?
   pub fn poll_accept(&mut self) -> Poll<(TcpStream, SocketAddr), Error> {
-    let mut table = RESOURCE_TABLE.lock().unwrap();
+    let closed = || {
+      std::io::Error::new(std::io::ErrorKind::Other, "Listener has been closed")
+    };
+    let mut table = lock_table(self.rid);
     let maybe_repr = table.get_mut(&self.rid);
-    match maybe_repr {
-      None => Err(std::io::Error::new(
-        std::io::ErrorKind::Other,
-        "Listener has been closed",
-      )),
+    let (result, draining) = match maybe_repr {
+      None => return Err(closed()),
       Some(repr) => match repr {
-        Repr::TcpListener(ref mut s, _) => s.poll_accept(),
-        Repr::TlsListener(ref mut s, _, _) => s.poll_accept(),
+        Repr::TcpListener(ref mut s, _, draining) => {
+          (s.poll_accept(), *draining)
+        }
+        Repr::TlsListener(ref mut s, _, _) => (s.poll_accept(), false),
         _ => panic!("Cannot accept"),
       },
+    };
+    if draining {
+      // This was the last poll, which had its chance to take a connection
+      // from the backlog.
+      remove_resource(&mut table, self.rid);
+      if let Ok(futures::Async::NotReady) = result {
+        return Err(closed());
+      }
     }
+    result
   }
 
   pub fn poll_accept_tls(
     &mut self,
     tcp_stream: TcpStream,
   ) -> impl Future<Item = ServerTlsStream<TcpStream>, Error = Error> {
-    let mut table = RESOURCE_TABLE.lock().unwrap();
+    let mut table = lock_table(self.rid);
     let maybe_repr = table.get_mut(&self.rid);
     match maybe_repr {
       None => Either::A(futures::future::err(std::io::Error::new(
@@ -205,12 +465,14 @@ impl Resource {
     }
   }
 
-  /// Track the current task (for TcpListener resource).
+  /// Track the current task (for listener resources).
   /// Throws an error if another task is already tracked.
   pub fn track_task(&mut self) -> Result<(), std::io::Error> {
-    let mut table = RESOURCE_TABLE.lock().unwrap();
-    // Only track if is TcpListener.
-    if let Some(Repr::TcpListener(_, t)) = table.get_mut(&self.rid) {
+    let mut table = lock_table(self.rid);
+    // Only track if is a listener.
+    if let Some(Repr::TcpListener(_, t, _)) | Some(Repr::TlsListener(_, _, t)) =
+      table.get_mut(&self.rid)
+    {
       // Currently, we only allow tracking a single accept task for a listener.
       // This might be changed in the future with multiple workers.
       // Caveat: TcpListener by itself also only tracks an accept task at a time.
@@ -226,12 +488,14 @@ impl Resource {
     Ok(())
   }
 
-  /// Stop tracking a task (for TcpListener resource).
+  /// Stop tracking a task (for listener resources).
   /// Happens when the task is done and thus no further tracking is needed.
   pub fn untrack_task(&mut self) {
-    let mut table = RESOURCE_TABLE.lock().unwrap();
-    // Only untrack if is TcpListener.
-    if let Some(Repr::TcpListener(_, t)) = table.get_mut(&self.rid) {
+    let mut table = lock_table(self.rid);
+    // Only untrack if is a listener.
+    if let Some(Repr::TcpListener(_, t, _)) | Some(Repr::TlsListener(_, _, t)) =
+      table.get_mut(&self.rid)
+    {
       if t.is_some() {
         t.take();
       }
@@ -241,17 +505,63 @@ impl Resource {
   // close(2) is done by dropping the value. Therefore we just need to remove
   // the resource from the RESOURCE_TABLE.
   pub fn close(&self) {
-    let mut table = RESOURCE_TABLE.lock().unwrap();
-    let r = table.remove(&self.rid).unwrap();
-    // If TcpListener, we must kill all pending accepts!
-    if let Repr::TcpListener(_, Some(t)) = r {
-      // Call notify on the tracked task, so that they would error out.
-      t.notify();
+    let mut table = lock_table(self.rid);
+    remove_resource(&mut table, self.rid);
+  }
+
+  /// Closes a TCP listener like `close`, except that an accept pending on it
+  /// is polled once more first, which hands it a connection that is already
+  /// waiting in the backlog. Other listeners are closed right away.
+  pub fn drain(&self) -> Result<(), ErrBox> {
+    let mut guard = lock_table(self.rid);
+    let table = &mut *guard;
+    match (
+      table.entries.get_mut(&self.rid),
+      table.pending_ops.get(&self.rid),
+    ) {
+      (Some(Repr::TcpListener(_, _, draining)), Some(ops)) => {
+        *draining = true;
+        for task in ops.values().flatten() {
+          task.notify();
+        }
+        Ok(())
+      }
+      (Some(Repr::TcpListener(_, _, _)), None)
+      | (Some(Repr::TlsListener(_, _, _)), _) => {
+        remove_resource(table, self.rid);
+        Ok(())
+      }
+      _ => Err(bad_resource()),
+    }
+  }
+
+  /// Waits for the TCP stream to become readable, or writable, without reading
+  /// or writing anything. The stream stays so until a read or write finds out
+  /// otherwise.
+  pub fn poll_ready(&self, write: bool) -> Poll<(), ErrBox> {
+    let table = lock_table(self.rid);
+    match table.get(&self.rid) {
+      Some(Repr::TcpStream(ref s)) => {
+        if write {
+          try_ready!(s.poll_write_ready());
+        } else {
+          try_ready!(s.poll_read_ready(mio::Ready::readable()));
+        }
+        Ok(().into())
+      }
+      Some(repr) => Err(
+        deno_error::DenoError::new(
+          deno_error::ErrorKind::BadResource,
+          format!("A {} resource can't be waited on", inspect_repr(repr)),
+        )
+        .into(),
+      ),
+      None => Err(bad_resource()),
     }
   }
 
   pub fn shutdown(&mut self, how: Shutdown) -> Result<(), ErrBox> {
-    let mut table = RESOURCE_TABLE.lock().unwrap();
+    let mut table = lock_table(self.rid);
     let repr = table.get_mut(&self.rid).ok_or_else(bad_resource)?;
 
     match repr {
@@ -263,6 +573,123 @@ impl Resource {
   }
 }
 
+/// Fast paths for `tokio_copy`, which move bytes between a file or pipe and a
+/// TCP stream without copying them into user space. The syscall is made while
+/// holding the table lock, so the stream can't be closed from under it.
+#[cfg(target_os = "linux")]
+impl Resource {
+  pub fn is_tcp_stream(&self) -> bool {
+    let table = lock_table(self.rid);
+    match table.get(&self.rid) {
+      Some(Repr::TcpStream(_)) => true,
+      _ => false,
+    }
+  }
+
+  /// Sends up to `count` bytes from `file`, starting at its current offset,
+  /// to this TCP stream using sendfile(2).
+  pub fn poll_sendfile(
+    &self,
+    file: &std::fs::File,
+    count: usize,
+  ) -> Poll<usize, ErrBox> {
+    use std::os::unix::io::AsRawFd;
+    let in_fd = file.as_raw_fd();
+    self.poll_tcp_syscall(true, |out_fd| unsafe {
+      libc::sendfile(out_fd, in_fd, std::ptr::null_mut(), count)
+    })
+  }
+
+  /// Moves up to `count` bytes from this TCP stream into the write end of a
+  /// pipe using splice(2).
+  pub fn poll_splice_to_pipe(
+    &self,
+    pipe: std::os::unix::io::RawFd,
+    count: usize,
+  ) -> Poll<usize, ErrBox> {
+    self.poll_tcp_syscall(false, |in_fd| unsafe {
+      libc::splice(
+        in_fd,
+        std::ptr::null_mut(),
+        pipe,
+        std::ptr::null_mut(),
+        count,
+        libc::SPLICE_F_MOVE | libc::SPLICE_F_NONBLOCK,
+      )
+    })
+  }
+
+  /// Moves up to `count` bytes from the read end of a pipe into this TCP
+  /// stream using splice(2).
+  pub fn poll_splice_from_pipe(
+    &self,
+    pipe: std::os::unix::io::RawFd,
+    count: usize,
+  ) -> Poll<usize, ErrBox> {
+    self.poll_tcp_syscall(true, |out_fd| unsafe {
+      libc::splice(
+        pipe,
+        std::ptr::null_mut(),
+        out_fd,
+        std::ptr::null_mut(),
+        count,
+        libc::SPLICE_F_MOVE | libc::SPLICE_F_NONBLOCK,
+      )
+    })
+  }
+
+  /// Writes from several buffers to this TCP stream using writev(2), as many
+  /// of them as a single call takes.
+  pub fn poll_writev(&self, bufs: &[&[u8]]) -> Poll<usize, ErrBox> {
+    let iovecs: Vec<libc::iovec> = bufs
+      .iter()
+      .take(libc::UIO_MAXIOV as usize)
+      .map(|buf| libc::iovec {
+        iov_base: buf.as_ptr() as *mut libc::c_void,
+        iov_len: buf.len(),
+      })
+      .collect();
+    self.poll_tcp_syscall(true, |out_fd| unsafe {
+      libc::writev(out_fd, iovecs.as_ptr(), iovecs.len() as libc::c_int)
+    })
+  }
+
+  /// Waits for the TCP stream to become readable or writable, then calls `f`
+  /// with its fd. `EAGAIN` clears the readiness so that the task is woken up
+  /// again by the reactor.
+  fn poll_tcp_syscall<F>(&self, write: bool, f: F) -> Poll<usize, ErrBox>
+  where
+    F: FnOnce(std::os::unix::io::RawFd) -> isize,
+  {
+    use std::os::unix::io::AsRawFd;
+    let mut table = lock_table(self.rid);
+    let stream = match table.get(&self.rid) {
+      Some(Repr::TcpStream(ref s)) => s,
+      _ => return Err(bad_resource()),
+    };
+    if write {
+      try_ready!(stream.poll_write_ready());
+    } else {
+      try_ready!(stream.poll_read_ready(mio::Ready::readable()));
+    }
+    let r = f(stream.as_raw_fd());
+    if r >= 0 {
+      count_bytes(&mut table, self.rid, !write, r as usize);
+      return Ok(futures::Async::Ready(r as usize));
+    }
+    let err = std::io::Error::last_os_error();
+    if err.kind() != std::io::ErrorKind::WouldBlock {
+      return Err(err.into());
+    }
+    if write {
+      stream.clear_write_ready()?;
+    } else {
+      stream.clear_read_ready(mio::Ready::readable())?;
+    }
+    Ok(futures::Async::NotReady)
+  }
+}
+
 impl Read for Resource {
   fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
     unimplemented!();
@@ -277,24 +704,150 @@ pub trait DenoAsyncRead {
 
 impl DenoAsyncRead for Resource {
   fn poll_read(&mut self, buf: &mut [u8]) -> Poll<usize, ErrBox> {
-    let mut table = RESOURCE_TABLE.lock().unwrap();
+    let mut table = lock_table(self.rid);
     let repr = table.get_mut(&self.rid).ok_or_else(bad_resource)?;
+    let r = poll_read_repr(repr, buf);
+    if let Ok(futures::Async::Ready(n)) = r {
+      count_bytes(&mut table, self.rid, true, n);
+    }
+    r
+  }
+}
 
-    let r = match repr {
-      Repr::FsFile(ref mut f) => f.poll_read(buf),
-      Repr::Stdin(ref mut f) => f.poll_read(buf),
-      Repr::TcpStream(ref mut f) => f.poll_read(buf),
-      Repr::ClientTlsStream(ref mut f) => f.poll_read(buf),
-      Repr::ServerTlsStream(ref mut f) => f.poll_read(buf),
-      Repr::HttpBody(ref mut f) => f.poll_read(buf),
-      Repr::ChildStdout(ref mut f) => f.poll_read(buf),
-      Repr::ChildStderr(ref mut f) => f.poll_read(buf),
-      _ => {
-        return Err(bad_resource());
+fn is_readable(repr: &Repr) -> bool {
+  match repr {
+    Repr::FsFile(_)
+    | Repr::TempFile(_)
+    | Repr::FileStream(_, _)
+    | Repr::Stdin(_)
+    | Repr::TcpStream(_)
+    | Repr::ClientTlsStream(_)
+    | Repr::ServerTlsStream(_)
+    | Repr::HttpBody(_)
+    | Repr::ChildStdout(_)
+    | Repr::ChildStderr(_)
+    | Repr::Tee(_) => true,
+    #[cfg(unix)]
+    Repr::Pty(_) => true,
+    _ => false,
+  }
+}
+
+fn poll_read_repr(repr: &mut Repr, buf: &mut [u8]) -> Poll<usize, ErrBox> {
+  let r = match repr {
+    Repr::FsFile(ref mut f) | Repr::TempFile(ref mut f) => f.poll_read(buf),
+    Repr::FileStream(ref mut f, ref mut remaining) => {
+      let max = match remaining {
+        Some(remaining) => std::cmp::min(*remaining, buf.len() as u64),
+        None => buf.len() as u64,
+      } as usize;
+      let r = f.poll_read(&mut buf[..max]);
+      if let (Ok(futures::Async::Ready(n)), Some(remaining)) = (&r, remaining) {
+        *remaining -= *n as u64;
+      }
+      r
+    }
+    Repr::Stdin(ref mut f) => f.poll_read(buf),
+    Repr::TcpStream(ref mut f) => f.poll_read(buf),
+    Repr::ClientTlsStream(ref mut f) => f.poll_read(buf),
+    Repr::ServerTlsStream(ref mut f) => f.poll_read(buf),
+    Repr::HttpBody(ref mut f) => f.poll_read(buf),
+    Repr::ChildStdout(ref mut f) => f.poll_read(buf),
+    Repr::ChildStderr(ref mut f) => f.poll_read(buf),
+    Repr::Tee(ref mut t) => return t.poll_read(buf),
+    #[cfg(unix)]
+    Repr::Pty(ref mut p) => p.poll_read(buf),
+    _ => {
+      return Err(bad_resource());
+    }
+  };
+
+  r.map_err(ErrBox::from)
+}
+
+/// Largest number of bytes held for the slower branch of a tee. Once reached,
+/// the faster branch has to wait for it to catch up.
+const TEE_BUFFER_SIZE: usize = 64 * 1024;
+
+/// The source of a tee, which has been moved out of the resource table, along
+/// with the bytes each branch is yet to read.
+struct TeeSource {
+  source: Repr,
+  pending: [VecDeque<u8>; 2],
+  closed: [bool; 2],
+  // Tasks of branches waiting for bytes or for the other branch to catch up.
+  tasks: [Option<futures::task::Task>; 2],
+  eof: bool,
+}
+
+impl TeeSource {
+  fn notify(&mut self, index: usize) {
+    if let Some(task) = self.tasks[index].take() {
+      task.notify();
+    }
+  }
+}
+
+/// One of the two readers created by `add_tee`. Whichever branch is read
+/// first pulls from the source and keeps a copy for the other.
+pub struct TeeBranch {
+  index: usize,
+  shared: Arc<Mutex<TeeSource>>,
+}
+
+impl TeeBranch {
+  fn poll_read(&mut self, buf: &mut [u8]) -> Poll<usize, ErrBox> {
+    let mut tee = self.shared.lock().unwrap();
+    let (this, other) = (self.index, 1 - self.index);
+    if buf.is_empty() {
+      return Ok(0.into());
+    }
+
+    if !tee.pending[this].is_empty() {
+      let n = std::cmp::min(buf.len(), tee.pending[this].len());
+      for (dst, src) in buf.iter_mut().zip(tee.pending[this].drain(..n)) {
+        *dst = src;
+      }
+      tee.notify(other);
+      return Ok(n.into());
+    }
+    if tee.eof {
+      return Ok(0.into());
+    }
+
+    let max = if tee.closed[other] {
+      buf.len()
+    } else {
+      std::cmp::min(buf.len(), TEE_BUFFER_SIZE - tee.pending[other].len())
+    };
+    if max == 0 {
+      tee.tasks[this] = Some(futures::task::current());
+      return Ok(futures::Async::NotReady);
+    }
+    let n = match poll_read_repr(&mut tee.source, &mut buf[..max])? {
+      futures::Async::Ready(n) => n,
+      futures::Async::NotReady => {
+        tee.tasks[this] = Some(futures::task::current());
+        return Ok(futures::Async::NotReady);
       }
     };
+    if n == 0 {
+      tee.eof = true;
+    } else if !tee.closed[other] {
+      tee.pending[other].extend(&buf[..n]);
+    }
+    tee.notify(other);
+    Ok(n.into())
+  }
+}
 
-    r.map_err(ErrBox::from)
+impl Drop for TeeBranch {
+  fn drop(&mut self) {
+    let mut tee = self.shared.lock().unwrap();
+    tee.closed[self.index] = true;
+    tee.pending[self.index].clear();
+    // The other branch may be waiting for this one to catch up.
+    tee.notify(1 - self.index);
   }
 }
 
@@ -313,52 +866,203 @@ impl Write for Resource {
 pub trait DenoAsyncWrite {
   fn poll_write(&mut self, buf: &[u8]) -> Poll<usize, ErrBox>;
 
+  /// Writes from several buffers in order, as if they were concatenated.
+  /// Unless overridden, this only writes from the first non-empty one.
+  fn poll_write_vectored(&mut self, bufs: &[&[u8]]) -> Poll<usize, ErrBox> {
+    match bufs.iter().find(|buf| !buf.is_empty()) {
+      Some(buf) => self.poll_write(buf),
+      None => Ok(0.into()),
+    }
+  }
+
   fn shutdown(&mut self) -> Poll<(), ErrBox>;
+
+  /// Called when a future is dropped before the write it polled resolved, so
+  /// that a writer which keeps the write in flight across polls forgets it.
+  fn cancel_write(&mut self) {}
 }
 
 impl DenoAsyncWrite for Resource {
   fn poll_write(&mut self, buf: &[u8]) -> Poll<usize, ErrBox> {
-    let mut table = RESOURCE_TABLE.lock().unwrap();
+    let mut table = lock_table(self.rid);
     let repr = table.get_mut(&self.rid).ok_or_else(bad_resource)?;
 
     let r = match repr {
-      Repr::FsFile(ref mut f) => f.poll_write(buf),
+      Repr::FsFile(ref mut f) | Repr::TempFile(ref mut f) => f.poll_write(buf),
       Repr::Stdout(ref mut f) => f.poll_write(buf),
       Repr::Stderr(ref mut f) => f.poll_write(buf),
       Repr::TcpStream(ref mut f) => f.poll_write(buf),
       Repr::ClientTlsStream(ref mut f) => f.poll_write(buf),
       Repr::ServerTlsStream(ref mut f) => f.poll_write(buf),
       Repr::ChildStdin(ref mut f) => f.poll_write(buf),
+      #[cfg(unix)]
+      Repr::Pty(ref mut p) => p.poll_write(buf),
       _ => {
         return Err(bad_resource());
       }
     };
 
+    if let Ok(futures::Async::Ready(n)) = r {
+      count_bytes(&mut table, self.rid, false, n);
+    }
     r.map_err(ErrBox::from)
   }
 
+  fn poll_write_vectored(&mut self, bufs: &[&[u8]]) -> Poll<usize, ErrBox> {
+    #[cfg(target_os = "linux")]
+    {
+      if self.is_tcp_stream() {
+        return self.poll_writev(bufs);
+      }
+    }
+    match bufs.iter().find(|buf| !buf.is_empty()) {
+      Some(buf) => self.poll_write(buf),
+      None => Ok(0.into()),
+    }
+  }
+
   fn shutdown(&mut self) -> futures::Poll<(), ErrBox> {
     unimplemented!()
   }
+
+  fn cancel_write(&mut self) {
+    let mut table = lock_table(self.rid);
+    match table.get_mut(&self.rid) {
+      Some(Repr::Stdout(ref mut f)) => f.cancel_write(),
+      Some(Repr::Stderr(ref mut f)) => f.cancel_write(),
+      _ => {}
+    }
+  }
+}
+
+/// Resolves like `future`, which waits on the resource `rid`, unless the
+/// resource is closed first. It then fails with `resource_closed`, whatever
+/// the type of resource and wherever `future` is waiting.
+pub fn cancel_on_close<F>(rid: ResourceId, future: F) -> CancelOnClose<F>
+where
+  F: Future<Error = ErrBox>,
+{
+  let id = NEXT_PENDING_OP.fetch_add(1, Ordering::SeqCst);
+  let mut table = lock_table(rid);
+  table.pending_ops.entry(rid).or_default().insert(id, None);
+  CancelOnClose { rid, id, future }
+}
+
+pub struct CancelOnClose<F> {
+  rid: ResourceId,
+  id: usize,
+  future: F,
+}
+
+impl<F> Future for CancelOnClose<F>
+where
+  F: Future<Error = ErrBox>,
+{
+  type Item = F::Item;
+  type Error = ErrBox;
+
+  fn poll(&mut self) -> Poll<F::Item, ErrBox> {
+    let (rid, id) = (self.rid, self.id);
+    let is_pending = |pending: &HashMap<ResourceId, PendingOps>| {
+      pending.get(&rid).map_or(false, |ops| ops.contains_key(&id))
+    };
+    if !is_pending(&lock_table(self.rid).pending_ops) {
+      return Err(resource_closed());
+    }
+    // The table lock is taken by `future`, so it can't be held meanwhile.
+    let result = self.future.poll();
+    let mut table = lock_table(self.rid);
+    let pending = &mut table.pending_ops;
+    if !is_pending(pending) {
+      // Closed while being polled, which only a result that was ready makes
+      // it through.
+      return match result {
+        Ok(futures::Async::Ready(item)) => Ok(item.into()),
+        _ => Err(resource_closed()),
+      };
+    }
+    if let Ok(futures::Async::NotReady) = result {
+      let ops = pending.get_mut(&self.rid).unwrap();
+      ops.insert(self.id, Some(futures::task::current()));
+    }
+    result
+  }
+}
+
+impl<F> Drop for CancelOnClose<F> {
+  fn drop(&mut self) {
+    let mut table = lock_table(self.rid);
+    let pending = &mut table.pending_ops;
+    if let Some(ops) = pending.get_mut(&self.rid) {
+      ops.remove(&self.id);
+      if ops.is_empty() {
+        pending.remove(&self.rid);
+      }
+    }
+  }
+}
+
+/// Removes `rid` from the table, waking up the ops still pending on it to
+/// fail with `resource_closed`.
+fn remove_resource(table: &mut ResourceTable, rid: ResourceId) {
+  let r = table.remove(&rid).unwrap();
+  table.info.remove(&rid);
+  // Dropping a child kills it, unless it is forgotten first.
+  if let Repr::Child(c) = r {
+    if c.detached {
+      c.child.forget();
+    }
+  }
+  if let Some(ops) = table.pending_ops.remove(&rid) {
+    for task in ops.values().flatten() {
+      task.notify();
+    }
+  }
 }
 
 fn new_rid() -> ResourceId {
-  let next_rid = NEXT_RID.fetch_add(1, Ordering::SeqCst);
-  next_rid as ResourceId
+  let next_rid = NEXT_RID.fetch_add(1, Ordering::SeqCst) as ResourceId;
+  let op = CURRENT_OP.with(Cell::get);
+  lock_table(next_rid)
+    .info
+    .insert(next_rid, ResourceInfo::new(op));
+  next_rid
 }
 
 pub fn add_fs_file(fs_file: tokio::fs::File) -> Resource {
   let rid = new_rid();
-  let mut tg = RESOURCE_TABLE.lock().unwrap();
+  let mut tg = lock_table(rid);
   let r = tg.insert(rid, Repr::FsFile(fs_file));
   assert!(r.is_none());
   Resource { rid }
 }
 
+/// Adds a file created by `deno_fs::make_anonymous_temp`. Unlike the files
+/// added with `add_fs_file`, it can be retrieved with `get_temp_file`.
+pub fn add_temp_file(fs_file: tokio::fs::File) -> Resource {
+  let rid = new_rid();
+  let mut tg = lock_table(rid);
+  let r = tg.insert(rid, Repr::TempFile(fs_file));
+  assert!(r.is_none());
+  Resource { rid }
+}
+
+/// Adds a read-only file which reports EOF after `length` bytes, if given.
+pub fn add_file_stream(
+  fs_file: tokio::fs::File,
+  length: Option<u64>,
+) -> Resource {
+  let rid = new_rid();
+  let mut tg = lock_table(rid);
+  let r = tg.insert(rid, Repr::FileStream(fs_file, length));
+  assert!(r.is_none());
+  Resource { rid }
+}
+
 pub fn add_tcp_listener(listener: tokio::net::TcpListener) -> Resource {
   let rid = new_rid();
-  let mut tg = RESOURCE_TABLE.lock().unwrap();
-  let r = tg.insert(rid, Repr::TcpListener(listener, None));
+  let mut tg = lock_table(rid);
+  let r = tg.insert(rid, Repr::TcpListener(listener, None, false));
   assert!(r.is_none());
   Resource { rid }
 }
@@ -368,7 +1072,7 @@ pub fn add_tls_listener(
   acceptor: TlsAcceptor,
 ) -> Resource {
   let rid = new_rid();
-  let mut tg = RESOURCE_TABLE.lock().unwrap();
+  let mut tg = lock_table(rid);
   let r = tg.insert(rid, Repr::TlsListener(listener, acceptor, None));
   assert!(r.is_none());
   Resource { rid }
@@ -376,7 +1080,7 @@ pub fn add_tls_listener(
 
 pub fn add_tcp_stream(stream: tokio::net::TcpStream) -> Resource {
   let rid = new_rid();
-  let mut tg = RESOURCE_TABLE.lock().unwrap();
+  let mut tg = lock_table(rid);
   let r = tg.insert(rid, Repr::TcpStream(stream));
   assert!(r.is_none());
   Resource { rid }
@@ -384,7 +1088,7 @@ pub fn add_tcp_stream(stream: tokio::net::TcpStream) -> Resource {
 
 pub fn add_tls_stream(stream: ClientTlsStream<TcpStream>) -> Resource {
   let rid = new_rid();
-  let mut tg = RESOURCE_TABLE.lock().unwrap();
+  let mut tg = lock_table(rid);
   let r = tg.insert(rid, Repr::ClientTlsStream(Box::new(stream)));
   assert!(r.is_none());
   Resource { rid }
@@ -392,7 +1096,7 @@ pub fn add_tls_stream(stream: ClientTlsStream<TcpStream>) -> Resource {
 
 pub fn add_server_tls_stream(stream: ServerTlsStream<TcpStream>) -> Resource {
   let rid = new_rid();
-  let mut tg = RESOURCE_TABLE.lock().unwrap();
+  let mut tg = lock_table(rid);
   let r = tg.insert(rid, Repr::ServerTlsStream(Box::new(stream)));
   assert!(r.is_none());
   Resource { rid }
@@ -400,7 +1104,7 @@ pub fn add_server_tls_stream(stream: ServerTlsStream<TcpStream>) -> Resource {
 
 pub fn add_reqwest_body(body: ReqwestDecoder) -> Resource {
   let rid = new_rid();
-  let mut tg = RESOURCE_TABLE.lock().unwrap();
+  let mut tg = lock_table(rid);
   let body = HttpBody::from(body);
   let r = tg.insert(rid, Repr::HttpBody(body));
   assert!(r.is_none());
@@ -409,7 +1113,7 @@ pub fn add_reqwest_body(body: ReqwestDecoder) -> Resource {
 
 pub fn add_repl(repl: Repl) -> Resource {
   let rid = new_rid();
-  let mut tg = RESOURCE_TABLE.lock().unwrap();
+  let mut tg = lock_table(rid);
   let r = tg.insert(rid, Repr::Repl(Arc::new(Mutex::new(repl))));
   assert!(r.is_none());
   Resource { rid }
@@ -417,7 +1121,7 @@ pub fn add_repl(repl: Repl) -> Resource {
 
 pub fn add_worker(wc: WorkerChannels) -> Resource {
   let rid = new_rid();
-  let mut tg = RESOURCE_TABLE.lock().unwrap();
+  let mut tg = lock_table(rid);
   let r = tg.insert(rid, Repr::Worker(wc));
   assert!(r.is_none());
   Resource { rid }
@@ -428,7 +1132,7 @@ pub fn post_message_to_worker(
   rid: ResourceId,
   buf: Buf,
 ) -> futures::sink::Send<mpsc::Sender<Buf>> {
-  let mut table = RESOURCE_TABLE.lock().unwrap();
+  let mut table = lock_table(rid);
   let maybe_repr = table.get_mut(&rid);
   match maybe_repr {
     Some(Repr::Worker(ref mut wc)) => {
@@ -450,7 +1154,7 @@ impl Future for WorkerReceiver {
   type Error = ErrBox;
 
   fn poll(&mut self) -> Poll<Option<Buf>, ErrBox> {
-    let mut table = RESOURCE_TABLE.lock().unwrap();
+    let mut table = lock_table(self.rid);
     let maybe_repr = table.get_mut(&self.rid);
     match maybe_repr {
       Some(Repr::Worker(ref mut wc)) => wc.1.poll().map_err(ErrBox::from),
@@ -473,7 +1177,7 @@ impl Stream for WorkerReceiverStream {
   type Error = ErrBox;
 
   fn poll(&mut self) -> Poll<Option<Buf>, ErrBox> {
-    let mut table = RESOURCE_TABLE.lock().unwrap();
+    let mut table = lock_table(self.rid);
     let maybe_repr = table.get_mut(&self.rid);
     match maybe_repr {
       Some(Repr::Worker(ref mut wc)) => wc.1.poll().map_err(ErrBox::from),
@@ -486,6 +1190,179 @@ pub fn get_message_stream_from_worker(rid: ResourceId) -> WorkerReceiverStream {
   WorkerReceiverStream { rid }
 }
 
+pub fn add_fs_events_watcher(watcher: FsEventsWatcher) -> Resource {
+  let rid = new_rid();
+  let mut tg = lock_table(rid);
+  let r = tg.insert(rid, Repr::FsEvents(watcher));
+  assert!(r.is_none());
+  Resource { rid }
+}
+
+pub fn add_console_resize_watcher(watcher: ConsoleResizeWatcher) -> Resource {
+  let rid = new_rid();
+  let mut tg = lock_table(rid);
+  let r = tg.insert(rid, Repr::ConsoleResize(watcher));
+  assert!(r.is_none());
+  Resource { rid }
+}
+
+pub fn add_signal_watcher(watcher: SignalWatcher) -> Resource {
+  let rid = new_rid();
+  let mut tg = lock_table(rid);
+  let r = tg.insert(rid, Repr::Signal(watcher));
+  assert!(r.is_none());
+  Resource { rid }
+}
+
+#[cfg(unix)]
+pub fn add_pty(master: PtyMaster) -> Resource {
+  let rid = new_rid();
+  let mut tg = lock_table(rid);
+  let r = tg.insert(rid, Repr::Pty(master));
+  assert!(r.is_none());
+  Resource { rid }
+}
+
+pub fn add_mmap(mapping: MmapMut) -> Resource {
+  let rid = new_rid();
+  let mut tg = lock_table(rid);
+  let r = tg.insert(rid, Repr::Mmap(mapping));
+  assert!(r.is_none());
+  Resource { rid }
+}
+
+pub fn add_read_buffer(buf: PooledBuf) -> Resource {
+  let rid = new_rid();
+  let mut tg = lock_table(rid);
+  let r = tg.insert(rid, Repr::ReadBuffer(buf));
+  assert!(r.is_none());
+  Resource { rid }
+}
+
+/// Adds a resource for the ops of an embedder, which `Deno.resources()` lists
+/// as `name`. Closing it drops `value`.
+pub fn add_custom<T: Any + Send>(name: &'static str, value: T) -> Resource {
+  let rid = new_rid();
+  let mut tg = lock_table(rid);
+  let value: Box<dyn Any + Send> = Box::new(value);
+  let r = tg.insert(rid, Repr::Custom(name, Arc::new(Mutex::new(value))));
+  assert!(r.is_none());
+  Resource { rid }
+}
+
+pub fn add_glob_walker(walker: GlobWalker) -> Resource {
+  let rid = new_rid();
+  let mut tg = lock_table(rid);
+  let r = tg.insert(rid, Repr::Glob(Arc::new(Mutex::new(walker))));
+  assert!(r.is_none());
+  Resource { rid }
+}
+
+/// Takes the readable resource `rid` out of the table and adds two resources
+/// in its place which both read the same bytes from it. The source is closed
+/// once both of them are.
+pub fn add_tee(rid: ResourceId) -> Result<(Resource, Resource), ErrBox> {
+  let mut table = lock_table(rid);
+  match table.get(&rid) {
+    Some(repr) if is_readable(repr) => {}
+    _ => return Err(bad_resource()),
+  }
+  let source = table.remove(&rid).unwrap();
+  table.info.remove(&rid);
+  drop(table);
+  let shared = Arc::new(Mutex::new(TeeSource {
+    source,
+    pending: [VecDeque::new(), VecDeque::new()],
+    closed: [false, false],
+    tasks: [None, None],
+    eof: false,
+  }));
+
+  let add_branch = |index| {
+    let rid = new_rid();
+    let branch = TeeBranch {
+      index,
+      shared: shared.clone(),
+    };
+    let r = lock_table(rid).insert(rid, Repr::Tee(branch));
+    assert!(r.is_none());
+    Resource { rid }
+  };
+  Ok((add_branch(0), add_branch(1)))
+}
+
+pub struct FsEventReceiver {
+  rid: ResourceId,
+}
+
+impl Future for FsEventReceiver {
+  type Item = Option<FsEvent>;
+  type Error = ErrBox;
+
+  fn poll(&mut self) -> Poll<Option<FsEvent>, ErrBox> {
+    let mut table = lock_table(self.rid);
+    let maybe_repr = table.get_mut(&self.rid);
+    match maybe_repr {
+      Some(Repr::FsEvents(ref mut w)) => {
+        w.receiver.poll().map_err(|_| bad_resource())
+      }
+      _ => Err(bad_resource()),
+    }
+  }
+}
+
+pub fn poll_fs_event(rid: ResourceId) -> FsEventReceiver {
+  FsEventReceiver { rid }
+}
+
+pub struct ConsoleResizeReceiver {
+  rid: ResourceId,
+}
+
+impl Future for ConsoleResizeReceiver {
+  type Item = Option<ConsoleSize>;
+  type Error = ErrBox;
+
+  fn poll(&mut self) -> Poll<Option<ConsoleSize>, ErrBox> {
+    let mut table = lock_table(self.rid);
+    let maybe_repr = table.get_mut(&self.rid);
+    match maybe_repr {
+      Some(Repr::ConsoleResize(ref mut w)) => {
+        w.receiver.poll().map_err(|_| bad_resource())
+      }
+      _ => Err(bad_resource()),
+    }
+  }
+}
+
+pub fn poll_console_resize(rid: ResourceId) -> ConsoleResizeReceiver {
+  ConsoleResizeReceiver { rid }
+}
+
+pub struct SignalReceiver {
+  rid: ResourceId,
+}
+
+impl Future for SignalReceiver {
+  type Item = Option<()>;
+  type Error = ErrBox;
+
+  fn poll(&mut self) -> Poll<Option<()>, ErrBox> {
+    let mut table = lock_table(self.rid);
+    let maybe_repr = table.get_mut(&self.rid);
+    match maybe_repr {
+      Some(Repr::Signal(ref mut w)) => {
+        w.receiver.poll().map_err(|_| bad_resource())
+      }
+      _ => Err(bad_resource()),
+    }
+  }
+}
+
+pub fn poll_signal(rid: ResourceId) -> SignalReceiver {
+  SignalReceiver { rid }
+}
+
 pub struct ChildResources {
   pub child_rid: ResourceId,
   pub stdin_rid: Option<ResourceId>,
@@ -493,9 +1370,28 @@ pub struct ChildResources {
   pub stderr_rid: Option<ResourceId>,
 }
 
-pub fn add_child(mut c: tokio_process::Child) -> ChildResources {
+pub struct ChildResource {
+  child: tokio_process::Child,
+  // Left running when the resource is closed, instead of being killed.
+  detached: bool,
+  // The process group the child leads, if it was started in one of its own.
+  pgid: Option<u32>,
+  // Dropped to stop the timeout timer once the child has exited or is closed.
+  timeout: Option<futures::sync::oneshot::Sender<()>>,
+  // Whether the child was killed for running past its timeout.
+  timed_out: bool,
+}
+
+/// Adds a spawned child along with its piped stdio. `own_group` tells whether
+/// it was started as the leader of a new process group. `timeout` is dropped
+/// when the timer killing the child is no longer needed.
+pub fn add_child(
+  mut c: tokio_process::Child,
+  detached: bool,
+  own_group: bool,
+  timeout: Option<futures::sync::oneshot::Sender<()>>,
+) -> ChildResources {
   let child_rid = new_rid();
-  let mut tg = RESOURCE_TABLE.lock().unwrap();
 
   let mut resources = ChildResources {
     child_rid,
@@ -507,26 +1403,36 @@ pub fn add_child(mut c: tokio_process::Child) -> ChildResources {
   if c.stdin().is_some() {
     let stdin = c.stdin().take().unwrap();
     let rid = new_rid();
-    let r = tg.insert(rid, Repr::ChildStdin(stdin));
+    let r = lock_table(rid).insert(rid, Repr::ChildStdin(stdin));
     assert!(r.is_none());
     resources.stdin_rid = Some(rid);
   }
   if c.stdout().is_some() {
     let stdout = c.stdout().take().unwrap();
     let rid = new_rid();
-    let r = tg.insert(rid, Repr::ChildStdout(stdout));
+    let r = lock_table(rid).insert(rid, Repr::ChildStdout(stdout));
     assert!(r.is_none());
     resources.stdout_rid = Some(rid);
   }
   if c.stderr().is_some() {
     let stderr = c.stderr().take().unwrap();
     let rid = new_rid();
-    let r = tg.insert(rid, Repr::ChildStderr(stderr));
+    let r = lock_table(rid).insert(rid, Repr::ChildStderr(stderr));
     assert!(r.is_none());
     resources.stderr_rid = Some(rid);
   }
 
-  let r = tg.insert(child_rid, Repr::Child(Box::new(c)));
+  let pgid = if own_group { Some(c.id()) } else { None };
+  let r = lock_table(child_rid).insert(
+    child_rid,
+    Repr::Child(Box::new(ChildResource {
+      child: c,
+      detached,
+      pgid,
+      timeout,
+      timed_out: false,
+    })),
+  );
   assert!(r.is_none());
 
   resources
@@ -542,17 +1448,21 @@ impl Future for ChildStatus {
   type Error = ErrBox;
 
   fn poll(&mut self) -> Poll<ExitStatus, ErrBox> {
-    let mut table = RESOURCE_TABLE.lock().unwrap();
+    let mut table = lock_table(self.rid);
     let maybe_repr = table.get_mut(&self.rid);
     match maybe_repr {
-      Some(Repr::Child(ref mut child)) => child.poll().map_err(ErrBox::from),
+      Some(Repr::Child(ref mut c)) => {
+        let status = try_ready!(c.child.poll());
+        c.timeout.take();
+        Ok(futures::Async::Ready(status))
+      }
       _ => Err(bad_resource()),
     }
   }
 }
 
 pub fn child_status(rid: ResourceId) -> Result<ChildStatus, ErrBox> {
-  let mut table = RESOURCE_TABLE.lock().unwrap();
+  let mut table = lock_table(rid);
   let maybe_repr = table.get_mut(&rid);
   match maybe_repr {
     Some(Repr::Child(ref mut _child)) => Ok(ChildStatus { rid }),
@@ -560,8 +1470,79 @@ pub fn child_status(rid: ResourceId) -> Result<ChildStatus, ErrBox> {
   }
 }
 
+/// Kills a child that ran past its timeout, along with the process group it
+/// leads, if any. Must be called from within a task.
+pub fn kill_timed_out_child(rid: ResourceId) {
+  let mut table = lock_table(rid);
+  if let Some(Repr::Child(ref mut c)) = table.get_mut(&rid) {
+    // The child may have exited right before the timer fired. Then it didn't
+    // time out, and its pid may belong to another process by now.
+    match c.child.poll() {
+      Ok(futures::Async::NotReady) => {}
+      _ => return,
+    }
+    c.timed_out = true;
+    #[cfg(unix)]
+    {
+      if let Some(pgid) = c.pgid {
+        let _ = crate::signal::kill_group(pgid as i32, libc::SIGKILL);
+      }
+    }
+    let _ = c.child.kill();
+  }
+}
+
+pub fn child_timed_out(rid: ResourceId) -> Result<bool, ErrBox> {
+  let table = lock_table(rid);
+  match table.get(&rid) {
+    Some(Repr::Child(ref c)) => Ok(c.timed_out),
+    _ => Err(bad_resource()),
+  }
+}
+
+/// Takes the piped stdout or stderr `rid` of a child out of the table, so that
+/// it can be read to the end in Rust.
+pub fn take_child_output(
+  rid: ResourceId,
+) -> Result<Box<dyn AsyncRead + Send>, ErrBox> {
+  let mut table = lock_table(rid);
+  match table.get(&rid) {
+    Some(Repr::ChildStdout(_)) | Some(Repr::ChildStderr(_)) => {}
+    _ => return Err(bad_resource()),
+  }
+  table.info.remove(&rid);
+  match table.remove(&rid) {
+    Some(Repr::ChildStdout(p)) => Ok(Box::new(p)),
+    Some(Repr::ChildStderr(p)) => Ok(Box::new(p)),
+    _ => unreachable!(),
+  }
+}
+
+/// Changes the size of the pseudo-terminal whose master side is `rid`.
+#[cfg(unix)]
+pub fn resize_pty(
+  rid: ResourceId,
+  columns: u16,
+  rows: u16,
+) -> Result<(), ErrBox> {
+  let table = lock_table(rid);
+  match table.get(&rid) {
+    Some(Repr::Pty(ref p)) => p.resize(columns, rows).map_err(ErrBox::from),
+    _ => Err(bad_resource()),
+  }
+}
+
+/// Returns the process group led by the child `rid`, if any.
+pub fn child_process_group(rid: ResourceId) -> Result<Option<u32>, ErrBox> {
+  let table = lock_table(rid);
+  match table.get(&rid) {
+    Some(Repr::Child(ref c)) => Ok(c.pgid),
+    _ => Err(bad_resource()),
+  }
+}
+
 pub fn get_repl(rid: ResourceId) -> Result<Arc<Mutex<Repl>>, ErrBox> {
-  let mut table = RESOURCE_TABLE.lock().unwrap();
+  let mut table = lock_table(rid);
   let maybe_repr = table.get_mut(&rid);
   match maybe_repr {
     Some(Repr::Repl(ref mut r)) => Ok(r.clone()),
@@ -569,54 +1550,412 @@ pub fn get_repl(rid: ResourceId) -> Result<Arc<Mutex<Repl>>, ErrBox> {
   }
 }
 
+/// Takes the mapping `rid` out of the table, to hand it to JS.
+pub fn take_mmap(rid: ResourceId) -> Result<MmapMut, ErrBox> {
+  let mut table = lock_table(rid);
+  match table.get(&rid) {
+    Some(Repr::Mmap(_)) => {}
+    _ => return Err(bad_resource()),
+  }
+  table.info.remove(&rid);
+  match table.remove(&rid) {
+    Some(Repr::Mmap(m)) => Ok(m),
+    _ => unreachable!(),
+  }
+}
+
+/// Takes the read buffer `rid` out of the table, to hand it to JS.
+pub fn take_read_buffer(rid: ResourceId) -> Result<PooledBuf, ErrBox> {
+  let mut table = lock_table(rid);
+  match table.get(&rid) {
+    Some(Repr::ReadBuffer(_)) => {}
+    _ => return Err(bad_resource()),
+  }
+  table.info.remove(&rid);
+  match table.remove(&rid) {
+    Some(Repr::ReadBuffer(buf)) => Ok(buf),
+    _ => unreachable!(),
+  }
+}
+
+pub fn get_glob_walker(
+  rid: ResourceId,
+) -> Result<Arc<Mutex<GlobWalker>>, ErrBox> {
+  let table = lock_table(rid);
+  match table.get(&rid) {
+    Some(Repr::Glob(ref w)) => Ok(w.clone()),
+    _ => Err(bad_resource()),
+  }
+}
+
+/// Runs `f` on the value of a resource added by `add_custom`. Fails if `rid`
+/// isn't one, or holds a value of another type than `T`.
+pub fn with_custom<T, F, R>(rid: ResourceId, f: F) -> Result<R, ErrBox>
+where
+  T: Any + Send,
+  F: FnOnce(&mut T) -> R,
+{
+  // The table isn't locked while `f` runs, which may use other resources.
+  let value = match lock_table(rid).get(&rid) {
+    Some(Repr::Custom(_, ref value)) => value.clone(),
+    _ => return Err(bad_resource()),
+  };
+  let mut value = value.lock().unwrap();
+  match value.downcast_mut::<T>() {
+    Some(value) => Ok(f(value)),
+    None => Err(bad_resource()),
+  }
+}
+
 // TODO: revamp this after the following lands:
 // https://github.com/tokio-rs/tokio/pull/785
 pub fn get_file(rid: ResourceId) -> Result<std::fs::File, ErrBox> {
-  let mut table = RESOURCE_TABLE.lock().unwrap();
+  get_std_file(rid, false)
+}
+
+/// Like `get_file`, but only for files added with `add_temp_file`. Any other
+/// file may have been opened somewhere the script can't write to, so it must
+/// never be linked elsewhere.
+pub fn get_temp_file(rid: ResourceId) -> Result<std::fs::File, ErrBox> {
+  get_std_file(rid, true)
+}
+
+fn get_std_file(
+  rid: ResourceId,
+  temp_only: bool,
+) -> Result<std::fs::File, ErrBox> {
+  let mut table = lock_table(rid);
   // We take ownership of File here.
   // It is put back below while still holding the lock.
   let maybe_repr = table.remove(&rid);
 
-  match maybe_repr {
-    Some(Repr::FsFile(r)) => {
-      // Trait Clone not implemented on tokio::fs::File,
-      // so convert to std File first.
-      let std_file = r.into_std();
-      // Create a copy and immediately put back.
-      // We don't want to block other resource ops.
-      // try_clone() would yield a copy containing the same
-      // underlying fd, so operations on the copy would also
-      // affect the one in resource table, and we don't need
-      // to write back.
-      let maybe_std_file_copy = std_file.try_clone();
-      // Insert the entry back with the same rid.
-      table.insert(rid, Repr::FsFile(tokio_fs::File::from_std(std_file)));
-
-      maybe_std_file_copy.map_err(ErrBox::from)
+  let (file, is_temp) = match maybe_repr {
+    Some(Repr::FsFile(r)) => (r, false),
+    Some(Repr::TempFile(r)) => (r, true),
+    Some(repr) => {
+      table.insert(rid, repr);
+      return Err(bad_resource());
     }
-    _ => Err(bad_resource()),
+    None => return Err(bad_resource()),
+  };
+  if temp_only && !is_temp {
+    table.insert(rid, Repr::FsFile(file));
+    return Err(bad_resource());
+  }
+  // Trait Clone not implemented on tokio::fs::File,
+  // so convert to std File first.
+  let std_file = file.into_std();
+  // Create a copy and immediately put back.
+  // We don't want to block other resource ops.
+  // try_clone() would yield a copy containing the same
+  // underlying fd, so operations on the copy would also
+  // affect the one in resource table, and we don't need
+  // to write back.
+  let maybe_std_file_copy = std_file.try_clone();
+  // Insert the entry back with the same rid.
+  let file = tokio_fs::File::from_std(std_file);
+  if is_temp {
+    table.insert(rid, Repr::TempFile(file));
+  } else {
+    table.insert(rid, Repr::FsFile(file));
+  }
+
+  maybe_std_file_copy.map_err(ErrBox::from)
+}
+
+/// Returns what a subprocess can use as its stdin, stdout or stderr to read or
+/// write `rid` directly. Files and our own stdout and stderr are shared with
+/// the subprocess. Sockets and the pipes of other children are handed over to
+/// it and closed here, since they can't be used in non-blocking mode on this
+/// side and in blocking mode on the other.
+pub fn get_stdio(rid: ResourceId) -> Result<std::process::Stdio, ErrBox> {
+  if get_type(rid).as_ref().map(String::as_str) == Some("fsFile") {
+    return get_file(rid).map(std::process::Stdio::from);
   }
+
+  let mut table = lock_table(rid);
+  let repr = table.get(&rid).ok_or_else(bad_resource)?;
+  let (raw, hand_over) = raw_stdio(repr).ok_or_else(|| {
+    deno_error::DenoError::new(
+      deno_error::ErrorKind::BadResource,
+      format!(
+        "A {} resource can't be subprocess stdio",
+        inspect_repr(repr)
+      ),
+    )
+  })?;
+  let stdio = dup_stdio(raw, hand_over)?;
+  if hand_over {
+    table.remove(&rid);
+    table.info.remove(&rid);
+  }
+  Ok(stdio)
+}
+
+#[cfg(unix)]
+pub type RawStdio = std::os::unix::io::RawFd;
+#[cfg(windows)]
+pub type RawStdio = std::os::windows::io::RawHandle;
+
+/// Returns the OS handle behind `repr`, along with whether it has to be handed
+/// over rather than shared.
+#[cfg(unix)]
+fn raw_stdio(repr: &Repr) -> Option<(RawStdio, bool)> {
+  use std::os::unix::io::AsRawFd;
+  match repr {
+    Repr::Stdout(_) => Some((1, false)),
+    Repr::Stderr(_) => Some((2, false)),
+    Repr::TcpStream(s) => Some((s.as_raw_fd(), true)),
+    Repr::ChildStdin(p) => Some((p.as_raw_fd(), true)),
+    Repr::ChildStdout(p) => Some((p.as_raw_fd(), true)),
+    Repr::ChildStderr(p) => Some((p.as_raw_fd(), true)),
+    _ => None,
+  }
+}
+
+#[cfg(windows)]
+fn raw_stdio(repr: &Repr) -> Option<(RawStdio, bool)> {
+  use std::os::windows::io::AsRawHandle;
+  use winapi::um::processenv::GetStdHandle;
+  use winapi::um::winbase::{STD_ERROR_HANDLE, STD_OUTPUT_HANDLE};
+  match repr {
+    Repr::Stdout(_) => {
+      Some((unsafe { GetStdHandle(STD_OUTPUT_HANDLE) }, false))
+    }
+    Repr::Stderr(_) => Some((unsafe { GetStdHandle(STD_ERROR_HANDLE) }, false)),
+    Repr::ChildStdin(p) => Some((p.as_raw_handle(), true)),
+    Repr::ChildStdout(p) => Some((p.as_raw_handle(), true)),
+    Repr::ChildStderr(p) => Some((p.as_raw_handle(), true)),
+    _ => None,
+  }
+}
+
+/// Duplicates `raw` for a subprocess, switching it to blocking mode if asked.
+#[cfg(unix)]
+fn dup_stdio(
+  raw: RawStdio,
+  blocking: bool,
+) -> std::io::Result<std::process::Stdio> {
+  let fd = unsafe { libc::fcntl(raw, libc::F_DUPFD_CLOEXEC, 0) };
+  if fd == -1 {
+    return Err(Error::last_os_error());
+  }
+  // Closes `fd` should anything below fail.
+  let stdio = unsafe { std::process::Stdio::from_raw_fd(fd) };
+  if blocking {
+    unsafe {
+      let flags = libc::fcntl(fd, libc::F_GETFL);
+      if flags == -1
+        || libc::fcntl(fd, libc::F_SETFL, flags & !libc::O_NONBLOCK) == -1
+      {
+        return Err(Error::last_os_error());
+      }
+    }
+  }
+  Ok(stdio)
+}
+
+#[cfg(windows)]
+fn dup_stdio(
+  raw: RawStdio,
+  _blocking: bool,
+) -> std::io::Result<std::process::Stdio> {
+  use std::os::windows::io::FromRawHandle;
+  use winapi::shared::minwindef::FALSE;
+  use winapi::um::handleapi::DuplicateHandle;
+  use winapi::um::processthreadsapi::GetCurrentProcess;
+  use winapi::um::winnt::DUPLICATE_SAME_ACCESS;
+  let mut handle = std::ptr::null_mut();
+  let ok = unsafe {
+    DuplicateHandle(
+      GetCurrentProcess(),
+      raw,
+      GetCurrentProcess(),
+      &mut handle,
+      0,
+      FALSE,
+      DUPLICATE_SAME_ACCESS,
+    )
+  };
+  if ok == FALSE {
+    return Err(Error::last_os_error());
+  }
+  Ok(unsafe { std::process::Stdio::from_raw_handle(handle) })
+}
+
+/// Runs `f` with the OS handle behind stdio or a file, e.g. to ask whether it
+/// is a terminal. Yields `None` for resources that have no such handle.
+pub fn with_raw_handle<R>(
+  rid: ResourceId,
+  f: impl FnOnce(RawStdio) -> R,
+) -> Result<Option<R>, ErrBox> {
+  if get_type(rid).as_ref().map(String::as_str) == Some("fsFile") {
+    let file = get_file(rid)?;
+    return Ok(Some(f(file_handle(&file))));
+  }
+
+  let table = lock_table(rid);
+  let repr = table.get(&rid).ok_or_else(bad_resource)?;
+  Ok(std_handle(repr).map(f))
+}
+
+#[cfg(unix)]
+fn std_handle(repr: &Repr) -> Option<RawStdio> {
+  match repr {
+    Repr::Stdin(_) => Some(0),
+    Repr::Stdout(_) => Some(1),
+    Repr::Stderr(_) => Some(2),
+    _ => None,
+  }
+}
+
+#[cfg(windows)]
+fn std_handle(repr: &Repr) -> Option<RawStdio> {
+  use winapi::um::processenv::GetStdHandle;
+  use winapi::um::winbase::{
+    STD_ERROR_HANDLE, STD_INPUT_HANDLE, STD_OUTPUT_HANDLE,
+  };
+  let which = match repr {
+    Repr::Stdin(_) => STD_INPUT_HANDLE,
+    Repr::Stdout(_) => STD_OUTPUT_HANDLE,
+    Repr::Stderr(_) => STD_ERROR_HANDLE,
+    _ => return None,
+  };
+  Some(unsafe { GetStdHandle(which) })
+}
+
+#[cfg(unix)]
+fn file_handle(file: &std::fs::File) -> RawStdio {
+  use std::os::unix::io::AsRawFd;
+  file.as_raw_fd()
+}
+
+#[cfg(windows)]
+fn file_handle(file: &std::fs::File) -> RawStdio {
+  use std::os::windows::io::AsRawHandle;
+  file.as_raw_handle()
 }
 
 pub fn lookup(rid: ResourceId) -> Result<Resource, ErrBox> {
   debug!("resource lookup {}", rid);
-  let table = RESOURCE_TABLE.lock().unwrap();
+  let table = lock_table(rid);
   table
     .get(&rid)
     .ok_or_else(bad_resource)
     .map(|_| Resource { rid })
 }
 
+/// Adds a second resource for the same underlying file or stream. The two
+/// have independent lifetimes, closing one leaves the other open, but share
+/// the OS handle's state such as the file cursor.
+pub fn dup(rid: ResourceId) -> Result<Resource, ErrBox> {
+  let mut table = lock_table(rid);
+  // We take ownership of the entry here, since files can only be cloned once
+  // converted back to std. It is put back below while still holding the lock.
+  let repr = table.remove(&rid).ok_or_else(bad_resource)?;
+  let (repr, copy) = match repr {
+    Repr::Stdin(stdin) => {
+      (Repr::Stdin(stdin), Ok(Repr::Stdin(StdinReader::new())))
+    }
+    Repr::Stdout(stdout) => {
+      // Only the copy may be closed when dropped.
+      let copy = std::mem::ManuallyDrop::new(stdout_file())
+        .try_clone()
+        .map(|file| Repr::Stdout(StdioWriter::new("stdout", file)))
+        .map_err(ErrBox::from);
+      (Repr::Stdout(stdout), copy)
+    }
+    Repr::Stderr(stderr) => {
+      let copy = StdioWriter::new("stderr", std::io::stderr());
+      (Repr::Stderr(stderr), Ok(Repr::Stderr(copy)))
+    }
+    Repr::FsFile(file) => {
+      let (file, copy) = clone_fs_file(file);
+      (Repr::FsFile(file), copy.map(Repr::FsFile))
+    }
+    Repr::TempFile(file) => {
+      let (file, copy) = clone_fs_file(file);
+      (Repr::TempFile(file), copy.map(Repr::TempFile))
+    }
+    Repr::FileStream(file, length) => {
+      let (file, copy) = clone_fs_file(file);
+      (
+        Repr::FileStream(file, length),
+        copy.map(|copy| Repr::FileStream(copy, length)),
+      )
+    }
+    Repr::TcpStream(stream) => {
+      let copy = clone_tcp_stream(&stream);
+      (Repr::TcpStream(stream), copy.map(Repr::TcpStream))
+    }
+    repr => {
+      let err = deno_error::DenoError::new(
+        deno_error::ErrorKind::BadResource,
+        format!("A {} resource can't be duplicated", inspect_repr(&repr)),
+      );
+      (repr, Err(err.into()))
+    }
+  };
+  table.insert(rid, repr);
+  drop(table);
+
+  let rid = new_rid();
+  let r = lock_table(rid).insert(rid, copy?);
+  assert!(r.is_none());
+  Ok(Resource { rid })
+}
+
+/// Trait Clone not implemented on tokio::fs::File, so this goes through the
+/// std File and hands the original back along with the copy.
+fn clone_fs_file(
+  file: tokio::fs::File,
+) -> (tokio::fs::File, Result<tokio::fs::File, ErrBox>) {
+  let std_file = file.into_std();
+  let copy = std_file
+    .try_clone()
+    .map(tokio::fs::File::from_std)
+    .map_err(ErrBox::from);
+  (tokio::fs::File::from_std(std_file), copy)
+}
+
+#[cfg(not(windows))]
+fn clone_tcp_stream(stream: &TcpStream) -> Result<TcpStream, ErrBox> {
+  use std::os::unix::io::AsRawFd;
+  // The std stream only borrows the socket to duplicate it, it must not close
+  // it when dropped.
+  let std_stream = std::mem::ManuallyDrop::new(unsafe {
+    std::net::TcpStream::from_raw_fd(stream.as_raw_fd())
+  });
+  let copy = std_stream.try_clone()?;
+  TcpStream::from_std(copy, &tokio::reactor::Handle::default())
+    .map_err(ErrBox::from)
+}
+
+#[cfg(windows)]
+fn clone_tcp_stream(stream: &TcpStream) -> Result<TcpStream, ErrBox> {
+  use std::os::windows::io::{AsRawSocket, FromRawSocket};
+  // The std stream only borrows the socket to duplicate it, it must not close
+  // it when dropped.
+  let std_stream = std::mem::ManuallyDrop::new(unsafe {
+    std::net::TcpStream::from_raw_socket(stream.as_raw_socket())
+  });
+  let copy = std_stream.try_clone()?;
+  TcpStream::from_std(copy, &tokio::reactor::Handle::default())
+    .map_err(ErrBox::from)
+}
+
+/// Moves the cursor of a file resource and resolves to the new position,
+/// measured in bytes from the start of the file.
 pub fn seek(
   resource: Resource,
-  offset: i32,
+  offset: i64,
   whence: u32,
-) -> Box<dyn Future<Item = (), Error = ErrBox> + Send> {
+) -> Box<dyn Future<Item = u64, Error = ErrBox> + Send> {
   // Translate seek mode to Rust repr.
   let seek_from = match whence {
     0 => SeekFrom::Start(offset as u64),
-    1 => SeekFrom::Current(i64::from(offset)),
-    2 => SeekFrom::End(i64::from(offset)),
+    1 => SeekFrom::Current(offset),
+    2 => SeekFrom::End(offset),
     _ => {
       return Box::new(futures::future::err(
         deno_error::DenoError::new(
@@ -630,7 +1969,7 @@ pub fn seek(
 
   match get_file(resource.rid) {
     Ok(mut file) => Box::new(futures::future::lazy(move || {
-      let result = file.seek(seek_from).map(|_| {}).map_err(ErrBox::from);
+      let result = file.seek(seek_from).map_err(ErrBox::from);
       futures::future::result(result)
     })),
     Err(err) => Box::new(futures::future::err(err)),
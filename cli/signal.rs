@@ -1,4 +1,5 @@
 use deno::ErrBox;
+use std::sync::Mutex;
 
 #[cfg(unix)]
 pub fn kill(pid: i32, signo: i32) -> Result<(), ErrBox> {
@@ -8,9 +9,310 @@ pub fn kill(pid: i32, signo: i32) -> Result<(), ErrBox> {
   unix_kill(Pid::from_raw(pid), Option::Some(sig)).map_err(ErrBox::from)
 }
 
+// The C runtime's numbers for the signals that can be emulated on Windows.
 #[cfg(not(unix))]
-pub fn kill(_pid: i32, _signal: i32) -> Result<(), ErrBox> {
-  // NOOP
-  // TODO: implement this for windows
+const SIGINT: i32 = 2;
+#[cfg(not(unix))]
+const SIGKILL: i32 = 9;
+#[cfg(not(unix))]
+const SIGTERM: i32 = 15;
+#[cfg(not(unix))]
+const SIGBREAK: i32 = 21;
+
+/// There are no signals on Windows. SIGKILL and SIGTERM terminate the process
+/// outright, while SIGINT and SIGBREAK send Ctrl+Break to a process group
+/// which shares our console, i.e. to a child started with `processGroup`.
+#[cfg(not(unix))]
+pub fn kill(pid: i32, signo: i32) -> Result<(), ErrBox> {
+  use std::io::Error;
+  use winapi::shared::minwindef::FALSE;
+  use winapi::um::handleapi::CloseHandle;
+  use winapi::um::processthreadsapi::{OpenProcess, TerminateProcess};
+  use winapi::um::wincon::{GenerateConsoleCtrlEvent, CTRL_BREAK_EVENT};
+  use winapi::um::winnt::PROCESS_TERMINATE;
+
+  match signo {
+    SIGINT | SIGBREAK => {
+      let ok =
+        unsafe { GenerateConsoleCtrlEvent(CTRL_BREAK_EVENT, pid.abs() as u32) };
+      if ok == FALSE {
+        return Err(Error::last_os_error().into());
+      }
+      Ok(())
+    }
+    SIGKILL | SIGTERM if pid > 0 => {
+      let handle = unsafe { OpenProcess(PROCESS_TERMINATE, FALSE, pid as u32) };
+      if handle.is_null() {
+        return Err(Error::last_os_error().into());
+      }
+      let ok = unsafe { TerminateProcess(handle, 1) };
+      let err = Error::last_os_error();
+      unsafe { CloseHandle(handle) };
+      if ok == FALSE {
+        return Err(err.into());
+      }
+      Ok(())
+    }
+    _ => Err(invalid_signal(&signo.to_string())),
+  }
+}
+
+#[cfg(unix)]
+pub fn kill_group(pgid: i32, signo: i32) -> Result<(), ErrBox> {
+  use nix::sys::signal::{killpg, Signal};
+  use nix::unistd::Pid;
+  let sig = Signal::from_c_int(signo)?;
+  killpg(Pid::from_raw(pgid), Option::Some(sig)).map_err(ErrBox::from)
+}
+
+#[cfg(not(unix))]
+pub fn kill_group(pgid: i32, signo: i32) -> Result<(), ErrBox> {
+  kill(-pgid, signo)
+}
+
+/// Returns the number of the signal called `name`, e.g. "SIGTERM", on this
+/// platform.
+#[cfg(unix)]
+pub fn signal_from_name(name: &str) -> Result<i32, ErrBox> {
+  use nix::sys::signal::Signal;
+  use std::str::FromStr;
+  Signal::from_str(name)
+    .map(|sig| sig as i32)
+    .map_err(|_| invalid_signal(name))
+}
+
+/// Returns the number of the signal called `name`, e.g. "SIGTERM", on this
+/// platform.
+#[cfg(not(unix))]
+pub fn signal_from_name(name: &str) -> Result<i32, ErrBox> {
+  match name {
+    "SIGINT" => Ok(SIGINT),
+    "SIGKILL" => Ok(SIGKILL),
+    "SIGTERM" => Ok(SIGTERM),
+    "SIGBREAK" => Ok(SIGBREAK),
+    _ => Err(invalid_signal(name)),
+  }
+}
+
+fn invalid_signal(signal: &str) -> ErrBox {
+  use crate::deno_error::{DenoError, ErrorKind};
+  DenoError::new(
+    ErrorKind::InvalidInput,
+    format!("Signal {} is not supported on this platform", signal),
+  )
+  .into()
+}
+
+/// Called every time the process receives the signal it listens to. Returns
+/// whether it still wants to be called.
+pub type Listener = Box<dyn FnMut() -> bool + Send>;
+
+struct Listeners {
+  /// Signals our handler is installed for.
+  installed: Vec<i32>,
+  list: Vec<(i32, Listener)>,
+}
+
+lazy_static! {
+  static ref LISTENERS: Mutex<Listeners> = Mutex::new(Listeners {
+    installed: Vec::new(),
+    list: Vec::new(),
+  });
+}
+
+/// Calls `listener` every time the process receives `signo`, instead of the
+/// handler it had before, usually the default action of that signal. That
+/// handler applies again once no listener wants `signo` anymore.
+///
+/// On Windows, Ctrl+C and Ctrl+Break in the console are received as SIGINT
+/// and SIGBREAK, which are the only signals that can be listened to there.
+pub fn listen(signo: i32, listener: Listener) -> Result<(), ErrBox> {
+  let mut listeners = LISTENERS.lock().unwrap();
+  if !listeners.installed.contains(&signo) {
+    handler::install(signo)?;
+    listeners.installed.push(signo);
+  }
+  listeners.list.push((signo, listener));
   Ok(())
 }
+
+/// Runs the listeners of `signo`. Returns false if none of them is left, in
+/// which case our handler is gone and the previous one applies again.
+fn deliver(signo: i32) -> bool {
+  let mut listeners = LISTENERS.lock().unwrap();
+  let mut listening = false;
+  let mut i = 0;
+  while i < listeners.list.len() {
+    let (s, ref mut listener) = listeners.list[i];
+    if s != signo {
+      i += 1;
+    } else if listener() {
+      listening = true;
+      i += 1;
+    } else {
+      listeners.list.remove(i);
+    }
+  }
+  if !listening {
+    listeners.installed.retain(|s| *s != signo);
+    handler::uninstall(signo);
+  }
+  listening
+}
+
+#[cfg(unix)]
+mod handler {
+  use super::{deliver, invalid_signal};
+  use deno::ErrBox;
+  use nix::errno::Errno;
+  use nix::sys::signal::Signal;
+  use nix::sys::signal::{sigaction, SaFlags, SigAction, SigHandler, SigSet};
+  use std::collections::HashMap;
+  use std::os::unix::io::RawFd;
+  use std::sync::atomic::{AtomicI32, Ordering};
+  use std::sync::Mutex;
+  use std::thread;
+
+  /// Write end of the pipe that wakes up the thread running the listeners.
+  static WAKE_FD: AtomicI32 = AtomicI32::new(-1);
+
+  lazy_static! {
+    /// The actions our handler replaced, restored once it's uninstalled.
+    static ref PREVIOUS: Mutex<HashMap<i32, SigAction>> =
+      Mutex::new(HashMap::new());
+  }
+
+  extern "C" fn on_signal(signo: libc::c_int) {
+    // Only async-signal-safe calls are allowed here. If the pipe is full the
+    // thread has plenty of wakeups pending, so a failed write is fine.
+    let fd = WAKE_FD.load(Ordering::SeqCst);
+    let byte = signo as u8;
+    unsafe { libc::write(fd, &byte as *const u8 as *const libc::c_void, 1) };
+  }
+
+  fn set_flags(fd: RawFd, nonblocking: bool) -> Result<(), ErrBox> {
+    unsafe {
+      if libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) == -1 {
+        return Err(std::io::Error::last_os_error().into());
+      }
+      if nonblocking && libc::fcntl(fd, libc::F_SETFL, libc::O_NONBLOCK) == -1 {
+        return Err(std::io::Error::last_os_error().into());
+      }
+    }
+    Ok(())
+  }
+
+  /// Signals are turned into bytes on a pipe, which a thread waits on to run
+  /// the listeners outside of the signal handler.
+  fn start() -> Result<(), ErrBox> {
+    let (read_fd, write_fd) = nix::unistd::pipe()?;
+    set_flags(read_fd, false)?;
+    set_flags(write_fd, true)?;
+    WAKE_FD.store(write_fd, Ordering::SeqCst);
+
+    thread::spawn(move || {
+      let mut buf = [0u8; 64];
+      loop {
+        match nix::unistd::read(read_fd, &mut buf) {
+          Ok(0) => return,
+          Ok(n) => {
+            for &signo in &buf[..n] {
+              if !deliver(signo as i32) {
+                // Nobody listens anymore, so do what the signal would have
+                // done without us.
+                unsafe { libc::raise(signo as i32) };
+              }
+            }
+          }
+          Err(nix::Error::Sys(Errno::EINTR)) => continue,
+          Err(_) => return,
+        }
+      }
+    });
+    Ok(())
+  }
+
+  pub fn install(signo: i32) -> Result<(), ErrBox> {
+    let signal = Signal::from_c_int(signo)?;
+    match signal {
+      // These can't be caught, or are raised by faults the program can't go
+      // on from.
+      Signal::SIGKILL
+      | Signal::SIGSTOP
+      | Signal::SIGSEGV
+      | Signal::SIGBUS
+      | Signal::SIGFPE
+      | Signal::SIGILL => return Err(invalid_signal(signal.as_ref())),
+      // The runtime relies on these: on SIGCHLD to reap children, and on
+      // SIGPIPE being ignored, so that writing to a closed pipe fails instead
+      // of killing the process.
+      Signal::SIGCHLD | Signal::SIGPIPE => {
+        return Err(invalid_signal(signal.as_ref()))
+      }
+      _ => {}
+    }
+    if WAKE_FD.load(Ordering::SeqCst) == -1 {
+      start()?;
+    }
+    let action = SigAction::new(
+      SigHandler::Handler(on_signal),
+      SaFlags::SA_RESTART,
+      SigSet::empty(),
+    );
+    let previous = unsafe { sigaction(signal, &action) }?;
+    PREVIOUS.lock().unwrap().insert(signo, previous);
+    Ok(())
+  }
+
+  pub fn uninstall(signo: i32) {
+    let previous = PREVIOUS.lock().unwrap().remove(&signo);
+    if let (Ok(signal), Some(previous)) = (Signal::from_c_int(signo), previous)
+    {
+      let _ = unsafe { sigaction(signal, &previous) };
+    }
+  }
+}
+
+#[cfg(not(unix))]
+mod handler {
+  use super::{deliver, invalid_signal, SIGBREAK, SIGINT};
+  use deno::ErrBox;
+  use std::sync::atomic::{AtomicBool, Ordering};
+  use winapi::shared::minwindef::{BOOL, DWORD, FALSE, TRUE};
+  use winapi::um::consoleapi::SetConsoleCtrlHandler;
+  use winapi::um::wincon::{CTRL_BREAK_EVENT, CTRL_C_EVENT};
+
+  static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+  /// Runs on a thread of its own that Windows starts for every event.
+  /// Returning FALSE passes the event on to the default handler, which exits
+  /// the process.
+  unsafe extern "system" fn on_ctrl(ctrl_type: DWORD) -> BOOL {
+    let signo = match ctrl_type {
+      CTRL_C_EVENT => SIGINT,
+      CTRL_BREAK_EVENT => SIGBREAK,
+      _ => return FALSE,
+    };
+    if deliver(signo) {
+      TRUE
+    } else {
+      FALSE
+    }
+  }
+
+  pub fn install(signo: i32) -> Result<(), ErrBox> {
+    if signo != SIGINT && signo != SIGBREAK {
+      return Err(invalid_signal(&signo.to_string()));
+    }
+    if !INSTALLED.load(Ordering::SeqCst) {
+      if unsafe { SetConsoleCtrlHandler(Some(on_ctrl), TRUE) } == FALSE {
+        return Err(std::io::Error::last_os_error().into());
+      }
+      INSTALLED.store(true, Ordering::SeqCst);
+    }
+    Ok(())
+  }
+
+  /// The handler stays, since it passes on the events nobody listens to.
+  pub fn uninstall(_signo: i32) {}
+}
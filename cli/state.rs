@@ -3,25 +3,29 @@ use crate::compilers::CompiledModule;
 use crate::compilers::JsCompiler;
 use crate::compilers::JsonCompiler;
 use crate::compilers::TsCompiler;
+use crate::compilers::WasmCompiler;
 use crate::deno_dir;
 use crate::deno_error::permission_denied;
 use crate::file_fetcher::SourceFileFetcher;
 use crate::flags;
-use crate::global_timer::GlobalTimer;
 use crate::import_map::ImportMap;
 use crate::msg;
+use crate::op_trace;
 use crate::ops::JsonOp;
 use crate::permissions::DenoPermissions;
 use crate::progress::Progress;
 use crate::resources;
 use crate::resources::ResourceId;
+use crate::timers::Timers;
 use crate::worker::Worker;
 use deno::Buf;
 use deno::CoreOp;
 use deno::ErrBox;
+use deno::Isolate;
 use deno::Loader;
 use deno::ModuleSpecifier;
 use deno::Op;
+use deno::OpId;
 use deno::PinnedBuf;
 use futures::future::Shared;
 use futures::Future;
@@ -32,8 +36,9 @@ use std;
 use std::collections::HashMap;
 use std::env;
 use std::ops::Deref;
+use std::path::PathBuf;
 use std::str;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::time::Instant;
@@ -53,6 +58,29 @@ pub struct Metrics {
   pub bytes_received: AtomicUsize,
   pub resolve_count: AtomicUsize,
   pub compiler_starts: AtomicUsize,
+  /// Only locked to register an op or to read the metrics; dispatch updates
+  /// the counters of its op directly.
+  pub ops: Mutex<HashMap<&'static str, Arc<OpMetrics>>>,
+}
+
+impl Metrics {
+  /// The counters of the op registered as `name`.
+  fn op(&self, name: &'static str) -> Arc<OpMetrics> {
+    self.ops.lock().unwrap().entry(name).or_default().clone()
+  }
+}
+
+/// The metrics of a single op, by the name it is registered with.
+#[derive(Default)]
+pub struct OpMetrics {
+  pub dispatched: AtomicU64,
+  pub completed: AtomicU64,
+  pub bytes_sent_control: AtomicU64,
+  pub bytes_sent_data: AtomicU64,
+  pub bytes_received: AtomicU64,
+  /// The time from dispatch to completion in nanoseconds, summed over
+  /// completed calls.
+  pub latency: AtomicU64,
 }
 
 /// Isolate cannot be passed between threads but ThreadSafeState can.
@@ -73,9 +101,11 @@ pub struct State {
   pub import_map: Option<ImportMap>,
   pub metrics: Metrics,
   pub worker_channels: Mutex<WorkerChannels>,
-  pub global_timer: Mutex<GlobalTimer>,
+  pub timers: Arc<Mutex<Timers>>,
   pub workers: Mutex<UserWorkerTable>,
   pub start_time: Instant,
+  /// The buffer which js/ring.ts shares with the `ring` op, once it has.
+  pub ring: Mutex<Option<PinnedBuf>>,
   /// A reference to this worker's resource.
   pub resource: resources::Resource,
   /// Reference to global progress bar.
@@ -86,6 +116,7 @@ pub struct State {
   pub js_compiler: JsCompiler,
   pub json_compiler: JsonCompiler,
   pub ts_compiler: TsCompiler,
+  pub wasm_compiler: WasmCompiler,
 
   pub include_deno_namespace: bool,
 }
@@ -104,33 +135,93 @@ impl Deref for ThreadSafeState {
 }
 
 impl ThreadSafeState {
-  /// Wrap core `OpDispatcher` to collect metrics.
-  pub fn core_op<D>(
+  /// Registers `dispatcher` with `i` as the op `name`, wrapped by `core_op`.
+  pub fn register_op<D>(
+    &self,
+    i: &mut Isolate,
+    name: &'static str,
+    dispatcher: D,
+  ) -> OpId
+  where
+    D: Fn(&[u8], Option<PinnedBuf>) -> CoreOp + Send + Sync + 'static,
+  {
+    i.register_op(name, self.core_op(name, dispatcher))
+  }
+
+  /// Wrap core `OpDispatcher` to collect metrics, in total and for the op
+  /// registered as `name`.
+  fn core_op<D>(
     &self,
+    name: &'static str,
     dispatcher: D,
   ) -> impl Fn(&[u8], Option<PinnedBuf>) -> CoreOp
   where
     D: Fn(&[u8], Option<PinnedBuf>) -> CoreOp,
   {
     let state = self.clone();
+    let metrics = self.metrics.op(name);
 
     move |control: &[u8], zero_copy: Option<PinnedBuf>| -> CoreOp {
+      let dispatched_at = Instant::now();
       let bytes_sent_control = control.len();
       let bytes_sent_zero_copy =
         zero_copy.as_ref().map(|b| b.len()).unwrap_or(0);
 
-      let op = dispatcher(control, zero_copy);
-      state.metrics_op_dispatched(bytes_sent_control, bytes_sent_zero_copy);
+      let op = resources::with_op(name, || dispatcher(control, zero_copy));
+      state.metrics_op_dispatched(
+        &metrics,
+        bytes_sent_control,
+        bytes_sent_zero_copy,
+      );
 
       match op {
         Op::Sync(buf) => {
-          state.metrics_op_completed(buf.len());
+          state.metrics_op_completed(&metrics, buf.len(), dispatched_at);
+          if op_trace::is_enabled() {
+            op_trace::record(
+              name,
+              state.resource.rid,
+              dispatched_at,
+              false,
+              bytes_sent_control + bytes_sent_zero_copy,
+              buf.len(),
+            );
+          }
           Op::Sync(buf)
         }
-        Op::Async(fut) => {
+        Op::External(buf) => {
+          state.metrics_op_completed(&metrics, buf.len(), dispatched_at);
+          if op_trace::is_enabled() {
+            op_trace::record(
+              name,
+              state.resource.rid,
+              dispatched_at,
+              false,
+              bytes_sent_control + bytes_sent_zero_copy,
+              buf.len(),
+            );
+          }
+          Op::External(buf)
+        }
+        Op::Async(mut fut) => {
           let state = state.clone();
+          let metrics = metrics.clone();
+          // Resources the op adds once dispatched are attributed to it too.
+          let fut = futures::future::poll_fn(move || {
+            resources::with_op(name, || fut.poll())
+          });
           let result_fut = Box::new(fut.map(move |buf: Buf| {
-            state.clone().metrics_op_completed(buf.len());
+            state.metrics_op_completed(&metrics, buf.len(), dispatched_at);
+            if op_trace::is_enabled() {
+              op_trace::record(
+                name,
+                state.resource.rid,
+                dispatched_at,
+                true,
+                bytes_sent_control + bytes_sent_zero_copy,
+                buf.len(),
+              );
+            }
             buf
           }));
           Op::Async(result_fut)
@@ -227,6 +318,7 @@ impl ThreadSafeState {
       !flags.reload,
       flags.cache_blacklist.clone(),
       flags.no_fetch,
+      flags.unsafely_ignore_certificate_errors.clone(),
     )?;
 
     let ts_compiler = TsCompiler::new(
@@ -248,26 +340,35 @@ impl ThreadSafeState {
       Some(file_path) => Some(ImportMap::load(file_path)?),
     };
 
+    // Math.random() is seeded by V8, see --random-seed.
     let mut seeded_rng = None;
-    if let Some(seed) = flags.seed {
-      seeded_rng = Some(Mutex::new(StdRng::seed_from_u64(seed)));
+    if flags.unsafely_seed_crypto {
+      if let Some(seed) = flags.seed {
+        seeded_rng = Some(Mutex::new(StdRng::seed_from_u64(seed)));
+      }
     };
 
     let modules = Arc::new(Mutex::new(deno::Modules::new()));
 
+    let mut permissions = DenoPermissions::from_flags(&flags);
+    if let Some(module) = &main_module {
+      permissions.set_log_origin(&module.to_string());
+    }
+
     let state = State {
       main_module,
       modules,
       dir,
       argv: argv_rest,
-      permissions: DenoPermissions::from_flags(&flags),
+      permissions,
       flags,
       import_map,
       metrics: Metrics::default(),
       worker_channels: Mutex::new(internal_channels),
-      global_timer: Mutex::new(GlobalTimer::new()),
+      timers: Arc::new(Mutex::new(Timers::new())),
       workers: Mutex::new(UserWorkerTable::new()),
       start_time: Instant::now(),
+      ring: Mutex::new(None),
       resource,
       progress,
       seeded_rng,
@@ -275,6 +376,7 @@ impl ThreadSafeState {
       ts_compiler,
       js_compiler: JsCompiler {},
       json_compiler: JsonCompiler {},
+      wasm_compiler: WasmCompiler {},
       include_deno_namespace,
     };
 
@@ -297,6 +399,9 @@ impl ThreadSafeState {
         msg::MediaType::Json => {
           state_.json_compiler.compile_async(state_.clone(), &out)
         }
+        msg::MediaType::Wasm => {
+          state_.wasm_compiler.compile_async(state_.clone(), &out)
+        }
         msg::MediaType::TypeScript
         | msg::MediaType::TSX
         | msg::MediaType::JSX => {
@@ -335,6 +440,11 @@ impl ThreadSafeState {
     self.permissions.check_env()
   }
 
+  #[inline]
+  pub fn check_env_var(&self, key: &str) -> Result<(), ErrBox> {
+    self.permissions.check_env_var(key)
+  }
+
   #[inline]
   pub fn check_net(&self, host_and_port: &str) -> Result<(), ErrBox> {
     self.permissions.check_net(host_and_port)
@@ -350,6 +460,20 @@ impl ThreadSafeState {
     self.permissions.check_run()
   }
 
+  #[inline]
+  pub fn check_run_program(
+    &self,
+    program: &str,
+    cwd: Option<&str>,
+  ) -> Result<Option<PathBuf>, ErrBox> {
+    self.permissions.check_run_program(program, cwd)
+  }
+
+  #[inline]
+  pub fn check_plugin(&self, filename: &str) -> Result<(), ErrBox> {
+    self.permissions.check_plugin(filename)
+  }
+
   pub fn check_dyn_import(
     self: &Self,
     module_specifier: &ModuleSpecifier,
@@ -387,6 +511,7 @@ impl ThreadSafeState {
 
   pub fn metrics_op_dispatched(
     &self,
+    op: &OpMetrics,
     bytes_sent_control: usize,
     bytes_sent_data: usize,
   ) {
@@ -399,14 +524,29 @@ impl ThreadSafeState {
       .metrics
       .bytes_sent_data
       .fetch_add(bytes_sent_data, Ordering::SeqCst);
+    op.dispatched.fetch_add(1, Ordering::SeqCst);
+    op.bytes_sent_control
+      .fetch_add(bytes_sent_control as u64, Ordering::SeqCst);
+    op.bytes_sent_data
+      .fetch_add(bytes_sent_data as u64, Ordering::SeqCst);
   }
 
-  pub fn metrics_op_completed(&self, bytes_received: usize) {
+  pub fn metrics_op_completed(
+    &self,
+    op: &OpMetrics,
+    bytes_received: usize,
+    dispatched_at: Instant,
+  ) {
     self.metrics.ops_completed.fetch_add(1, Ordering::SeqCst);
     self
       .metrics
       .bytes_received
       .fetch_add(bytes_received, Ordering::SeqCst);
+    op.completed.fetch_add(1, Ordering::SeqCst);
+    op.bytes_received
+      .fetch_add(bytes_received as u64, Ordering::SeqCst);
+    let latency = dispatched_at.elapsed().as_nanos() as u64;
+    op.latency.fetch_add(latency, Ordering::SeqCst);
   }
 }
 
@@ -0,0 +1,182 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+
+use futures::sync::oneshot;
+use futures::Async;
+use futures::Future;
+use futures::Poll;
+use std::cmp::min;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::sync::mpsc;
+use std::thread;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
+
+type Job<T> = Box<dyn FnOnce(&mut T) + Send>;
+
+/// Owns a blocking std handle on a dedicated thread and runs jobs against it
+/// one at a time, so that a read from a slow pipe or a write to a full one
+/// never holds up the event loop. The thread exits once the worker is
+/// dropped and the job it may be blocked in returns.
+struct Worker<T> {
+  jobs: mpsc::Sender<Job<T>>,
+}
+
+impl<T: Send + 'static> Worker<T> {
+  fn spawn(name: &str, mut handle: T) -> Self {
+    let (jobs, rx) = mpsc::channel::<Job<T>>();
+    thread::Builder::new()
+      .name(name.to_string())
+      .spawn(move || {
+        for job in rx {
+          job(&mut handle);
+        }
+      })
+      .unwrap();
+    Self { jobs }
+  }
+
+  /// Runs `f` on the worker thread and resolves to its result.
+  fn run<R, F>(&self, f: F) -> oneshot::Receiver<io::Result<R>>
+  where
+    R: Send + 'static,
+    F: FnOnce(&mut T) -> io::Result<R> + Send + 'static,
+  {
+    let (tx, rx) = oneshot::channel();
+    let job: Job<T> = Box::new(move |handle| {
+      let _ = tx.send(f(handle));
+    });
+    // A send only fails if the thread is gone, in which case dropping the
+    // job cancels the receiver.
+    let _ = self.jobs.send(job);
+    rx
+  }
+}
+
+fn poll_job<R>(
+  job: &mut oneshot::Receiver<io::Result<R>>,
+) -> Poll<R, io::Error> {
+  match job.poll() {
+    Ok(Async::Ready(result)) => result.map(Async::Ready),
+    Ok(Async::NotReady) => Ok(Async::NotReady),
+    Err(oneshot::Canceled) => Err(worker_gone()),
+  }
+}
+
+fn worker_gone() -> io::Error {
+  io::Error::new(io::ErrorKind::BrokenPipe, "stdio worker thread exited")
+}
+
+/// Reads stdin on a dedicated thread. A read is only issued when JS asks for
+/// one, so bytes meant for a subprocess that inherits stdin are never taken.
+pub struct StdinReader {
+  worker: Worker<io::Stdin>,
+  pending: Option<oneshot::Receiver<io::Result<Vec<u8>>>>,
+  // Bytes read on behalf of a caller that then came back with a smaller
+  // buffer.
+  buffered: Vec<u8>,
+}
+
+impl StdinReader {
+  pub fn new() -> Self {
+    Self {
+      worker: Worker::spawn("stdin", io::stdin()),
+      pending: None,
+      buffered: Vec::new(),
+    }
+  }
+}
+
+impl Read for StdinReader {
+  fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+    unimplemented!();
+  }
+}
+
+impl AsyncRead for StdinReader {
+  fn poll_read(&mut self, buf: &mut [u8]) -> Poll<usize, io::Error> {
+    if self.buffered.is_empty() {
+      if self.pending.is_none() {
+        let len = buf.len();
+        self.pending = Some(self.worker.run(move |stdin| {
+          let mut data = vec![0; len];
+          let n = stdin.read(&mut data)?;
+          data.truncate(n);
+          Ok(data)
+        }));
+      }
+      let data = try_ready!(poll_job(self.pending.as_mut().unwrap()));
+      self.pending = None;
+      self.buffered = data;
+    }
+
+    let n = min(buf.len(), self.buffered.len());
+    buf[..n].copy_from_slice(&self.buffered[..n]);
+    self.buffered.drain(..n);
+    Ok(Async::Ready(n))
+  }
+}
+
+/// Writes to stdout or stderr on a dedicated thread. A write only resolves
+/// once the bytes have been handed to the OS, so a reader that doesn't keep
+/// up slows the writer down instead of output piling up in memory.
+pub struct StdioWriter<W> {
+  worker: Worker<W>,
+  pending: Option<oneshot::Receiver<io::Result<usize>>>,
+}
+
+impl<W: Write + Send + 'static> StdioWriter<W> {
+  pub fn new(name: &str, handle: W) -> Self {
+    Self {
+      worker: Worker::spawn(name, handle),
+      pending: None,
+    }
+  }
+
+  /// Forgets the write in flight, whose future was dropped. The worker still
+  /// makes it, but the next `poll_write` starts a write of its own bytes
+  /// instead of resolving with the result of the stale one.
+  pub fn cancel_write(&mut self) {
+    self.pending = None;
+  }
+}
+
+impl<W: Write + Send + 'static> Write for StdioWriter<W> {
+  fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
+    unimplemented!()
+  }
+
+  /// Blocks until every write handed to the worker so far has been made.
+  fn flush(&mut self) -> io::Result<()> {
+    let job = self.worker.run(|handle| handle.flush());
+    job
+      .wait()
+      .unwrap_or_else(|oneshot::Canceled| Err(worker_gone()))
+  }
+}
+
+impl<W: Write + Send + 'static> AsyncWrite for StdioWriter<W> {
+  fn poll_write(&mut self, buf: &[u8]) -> Poll<usize, io::Error> {
+    // The caller retries with the same bytes until the write resolves, so the
+    // copy taken on the first poll is what ends up written.
+    if self.pending.is_none() {
+      let data = buf.to_vec();
+      self.pending = Some(self.worker.run(move |handle| {
+        let n = handle.write(&data)?;
+        handle.flush()?;
+        Ok(n)
+      }));
+    }
+    let result = poll_job(self.pending.as_mut().unwrap());
+    if let Ok(Async::NotReady) = result {
+      return result;
+    }
+    self.pending = None;
+    result
+  }
+
+  fn shutdown(&mut self) -> Poll<(), io::Error> {
+    Ok(Async::Ready(()))
+  }
+}
@@ -0,0 +1,8 @@
+const worker = new Worker("./subdir/worker_permissions.ts", {
+  permissions: ["read", "env"]
+});
+worker.onmessage = (msg): void => {
+  console.log(msg.data);
+  Deno.exit(0);
+};
+worker.postMessage("START");
@@ -0,0 +1,3 @@
+const env = Deno.env();
+console.log(env.GREETING);
+console.log(env.QUOTED);
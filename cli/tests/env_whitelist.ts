@@ -0,0 +1,9 @@
+const env = Deno.env();
+env.DENO_ALLOWED = "hello";
+console.log(Deno.env("DENO_ALLOWED"));
+console.log(Object.keys(Deno.env()));
+try {
+  Deno.env("HOME");
+} catch (e) {
+  console.log(e.name);
+}
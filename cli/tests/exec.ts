@@ -0,0 +1,4 @@
+Deno.exec([Deno.execPath(), "eval", "console.log(Deno.env().EXEC_TEST)"], {
+  env: { EXEC_TEST: "replaced" }
+});
+console.log("unreachable");
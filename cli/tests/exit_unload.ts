@@ -0,0 +1,8 @@
+window.addEventListener("unload", (): void => {
+  console.log("unload");
+  // Exiting from an unload handler doesn't unload again.
+  Deno.exit(3);
+});
+
+console.log("exiting");
+Deno.exit(3);
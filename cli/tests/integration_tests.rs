@@ -339,6 +339,11 @@ itest!(_049_info_flag_script_jsx {
   http_server: true,
 });
 
+itest!(_050_worker_permissions {
+  args: "run --reload --allow-read --allow-net 050_worker_permissions.ts",
+  output: "050_worker_permissions.ts.out",
+});
+
 itest!(async_error {
   exit_code: 1,
   args: "run --reload async_error.ts",
@@ -346,6 +351,17 @@ itest!(async_error {
   output: "async_error.ts.out",
 });
 
+itest!(unhandled_rejection_warn {
+  args: "run --reload --unhandled-rejections=warn unhandled_rejection.ts",
+  check_stderr: true,
+  output: "unhandled_rejection_warn.out",
+});
+
+itest!(unhandled_rejection_none {
+  args: "run --reload --unhandled-rejections=none unhandled_rejection.ts",
+  output: "unhandled_rejection_none.out",
+});
+
 itest!(circular1 {
   args: "run --reload circular1.js",
   output: "circular1.js.out",
@@ -520,10 +536,43 @@ itest!(import_meta {
 });
 
 itest!(seed_random {
-  args: "run --seed=100 seed_random.js",
+  args: "run --seed=100 --unsafely-seed-crypto seed_random.js",
   output: "seed_random.js.out",
 });
 
+itest!(dotenv {
+  args: "run --reload --allow-env --env=dotenv.env dotenv.ts",
+  output: "dotenv.ts.out",
+});
+
+itest!(exit_unload {
+  args: "run --reload exit_unload.ts",
+  output: "exit_unload.ts.out",
+  exit_code: 3,
+});
+
+itest!(log_permissions {
+  args: "run --reload --allow-read --log-permissions log_permissions.ts",
+  output: "log_permissions.ts.out",
+  check_stderr: true,
+});
+
+itest!(env_whitelist {
+  args: "run --reload --no-prompt --allow-env=DENO_ALLOWED env_whitelist.ts",
+  output: "env_whitelist.ts.out",
+});
+
+itest!(exec {
+  args: "run --reload --allow-run --allow-env exec.ts",
+  output: "exec.ts.out",
+});
+
+itest!(stdio_async {
+  args: "run --reload stdio_async.ts",
+  input: Some("a\nb\n\nc"),
+  output: "stdio_async.ts.out",
+});
+
 itest!(type_definitions {
   args: "run --reload type_definitions.ts",
   output: "type_definitions.ts.out",
@@ -580,6 +629,12 @@ itest!(wasm_async {
   output: "wasm_async.out",
 });
 
+itest!(wasi_hello {
+  args: "run --reload wasi_hello.wasm",
+  output: "wasi_hello.wasm.out",
+  exit_code: 3,
+});
+
 itest!(top_level_await {
   args: "--allow-read top_level_await.js",
   output: "top_level_await.out",
@@ -0,0 +1,4 @@
+Deno.readFileSync("subdir/mod1.ts");
+try {
+  Deno.writeFileSync("log_permissions.txt", new Uint8Array());
+} catch {}
@@ -0,0 +1,4 @@
+const { stdin, stdout } = Deno;
+
+const input = new TextDecoder().decode(await Deno.readAll(stdin));
+await Deno.writeAll(stdout, new TextEncoder().encode(input.toUpperCase()));
@@ -0,0 +1,4 @@
+onmessage = (): void => {
+  const { read, net, env } = Deno.permissions();
+  postMessage(`read: ${read}, net: ${net}, env: ${env}`);
+};
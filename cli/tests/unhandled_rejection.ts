@@ -0,0 +1,4 @@
+Promise.reject(new Error("rejected"));
+setTimeout(() => {
+  console.log("still running");
+}, 0);
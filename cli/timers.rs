@@ -0,0 +1,217 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+
+//! This module implements the timers of setTimeout and setInterval.
+//!
+//! JavaScript starts and clears timers with sync calls of the timer op, which
+//! don't allocate a promise or a future each. A single async call, which
+//! //js/timers.ts keeps pending while there are timers, resolves with the ids
+//! of the timers that fired. Due times are rounded up to whole milliseconds
+//! since the isolate started, so the timers due within a millisecond fire
+//! together, behind a single Delay for the whole isolate.
+//!
+//! Intervals are rescheduled from when they were due rather than when they
+//! fired, so they don't drift by the time it takes to run their callbacks.
+
+use futures::task;
+use futures::task::Task;
+use futures::Async;
+use futures::Future;
+use futures::Poll;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+use tokio::timer::Delay;
+
+pub type TimerId = u32;
+
+struct Timer {
+  due: Instant,
+  interval: Option<Duration>,
+}
+
+pub struct Timers {
+  start_time: Instant,
+  timers: HashMap<TimerId, Timer>,
+  queue: BTreeSet<(Instant, TimerId)>,
+  delay: Option<Delay>,
+  task: Option<Task>,
+}
+
+impl Timers {
+  pub fn new() -> Self {
+    Self {
+      start_time: Instant::now(),
+      timers: HashMap::new(),
+      queue: BTreeSet::new(),
+      delay: None,
+      task: None,
+    }
+  }
+
+  /// Rounds `instant` up to a whole millisecond since the timers started.
+  fn coalesce(&self, instant: Instant) -> Instant {
+    let elapsed = instant - self.start_time;
+    let mut millis = elapsed.as_millis() as u64;
+    if elapsed.subsec_nanos() % 1_000_000 != 0 {
+      millis += 1;
+    }
+    self.start_time + Duration::from_millis(millis)
+  }
+
+  /// Starts the timer `id`, which fires after `delay` and, if `repeat` is
+  /// set, every `delay` after that. Replaces a timer with the same id.
+  pub fn start(&mut self, id: TimerId, delay: Duration, repeat: bool) {
+    self.clear(id);
+    let due = self.coalesce(Instant::now() + delay);
+    let interval = if repeat {
+      // An interval of zero would fire on every poll.
+      Some(std::cmp::max(delay, Duration::from_millis(1)))
+    } else {
+      None
+    };
+    self.timers.insert(id, Timer { due, interval });
+    self.queue.insert((due, id));
+    self.notify();
+  }
+
+  /// Clears the timer `id`. Clearing a timer which fired, or never existed,
+  /// does nothing.
+  pub fn clear(&mut self, id: TimerId) {
+    if let Some(timer) = self.timers.remove(&id) {
+      self.queue.remove(&(timer.due, id));
+      self.notify();
+    }
+  }
+
+  /// Wakes the pending `TimersPoll`, as the first timer may have changed.
+  fn notify(&mut self) {
+    if let Some(task) = self.task.take() {
+      task.notify();
+    }
+  }
+
+  /// Returns the ids of the timers that are due, in the order they were due,
+  /// and reschedules the intervals among them. Returns an empty list once
+  /// there are no timers left.
+  fn poll_fired(&mut self) -> Poll<Vec<TimerId>, ()> {
+    let now = Instant::now();
+    let mut fired = Vec::new();
+    while let Some(&(due, id)) = self.queue.iter().next() {
+      if due > now {
+        break;
+      }
+      self.queue.remove(&(due, id));
+      fired.push(id);
+      let interval = self.timers[&id].interval;
+      match interval {
+        Some(interval) => {
+          // An interval which fell behind skips the runs it missed.
+          let mut next = due + interval;
+          if next <= now {
+            next = self.coalesce(now + interval);
+          }
+          self.timers.get_mut(&id).unwrap().due = next;
+          self.queue.insert((next, id));
+        }
+        None => {
+          self.timers.remove(&id);
+        }
+      }
+    }
+
+    if !fired.is_empty() || self.queue.is_empty() {
+      self.delay = None;
+      return Ok(Async::Ready(fired));
+    }
+
+    let (deadline, _) = *self.queue.iter().next().unwrap();
+    let delay = self.delay.get_or_insert_with(|| Delay::new(deadline));
+    if delay.deadline() != deadline {
+      delay.reset(deadline);
+    }
+    match delay.poll() {
+      Ok(Async::NotReady) => {
+        self.task = Some(task::current());
+        Ok(Async::NotReady)
+      }
+      // The deadline passed in the meantime.
+      Ok(Async::Ready(())) | Err(_) => {
+        task::current().notify();
+        Ok(Async::NotReady)
+      }
+    }
+  }
+}
+
+impl Default for Timers {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Resolves with the ids of the next timers that fire, see
+/// `Timers::poll_fired`.
+pub struct TimersPoll(pub Arc<Mutex<Timers>>);
+
+impl Future for TimersPoll {
+  type Item = Vec<TimerId>;
+  type Error = ();
+
+  fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+    self.0.lock().unwrap().poll_fired()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tokio::runtime::current_thread;
+
+  fn poll_timers(timers: &Arc<Mutex<Timers>>) -> Vec<TimerId> {
+    current_thread::block_on_all(TimersPoll(timers.clone())).unwrap()
+  }
+
+  #[test]
+  fn test_timers_fire_in_order() {
+    let timers = Arc::new(Mutex::new(Timers::new()));
+    {
+      let mut t = timers.lock().unwrap();
+      t.start(1, Duration::from_millis(20), false);
+      t.start(2, Duration::from_millis(0), false);
+      t.start(3, Duration::from_millis(20), false);
+      t.clear(3);
+    }
+    assert_eq!(poll_timers(&timers), vec![2]);
+    assert_eq!(poll_timers(&timers), vec![1]);
+    assert_eq!(timers.lock().unwrap().timers.len(), 0);
+    assert_eq!(poll_timers(&timers), Vec::<TimerId>::new());
+  }
+
+  #[test]
+  fn test_timers_interval() {
+    let timers = Arc::new(Mutex::new(Timers::new()));
+    timers
+      .lock()
+      .unwrap()
+      .start(1, Duration::from_millis(5), true);
+    assert_eq!(poll_timers(&timers), vec![1]);
+    assert_eq!(poll_timers(&timers), vec![1]);
+    assert_eq!(timers.lock().unwrap().timers.len(), 1);
+    timers.lock().unwrap().clear(1);
+    assert_eq!(poll_timers(&timers), Vec::<TimerId>::new());
+  }
+
+  #[test]
+  fn test_timers_coalesce() {
+    let timers = Timers::new();
+    let start = timers.start_time;
+    assert_eq!(timers.coalesce(start), start);
+    assert_eq!(
+      timers.coalesce(start + Duration::from_micros(1500)),
+      start + Duration::from_millis(2)
+    );
+  }
+}
@@ -0,0 +1,214 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+#[cfg(target_os = "linux")]
+use crate::resources;
+use crate::resources::DenoAsyncRead;
+use crate::resources::DenoAsyncWrite;
+use crate::resources::Resource;
+use deno::ErrBox;
+use futures::{Future, Poll};
+use std::cmp;
+use std::io;
+
+/// Largest amount of bytes moved by a single syscall.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+enum Mode {
+  /// Bytes are read into a buffer owned by the future, then written out.
+  Buffered {
+    buf: Box<[u8]>,
+    pos: usize,
+    cap: usize,
+    eof: bool,
+  },
+  /// File to TCP stream, using sendfile(2).
+  #[cfg(target_os = "linux")]
+  Sendfile { file: std::fs::File },
+  /// TCP stream to TCP stream, using splice(2) through an intermediate pipe.
+  #[cfg(target_os = "linux")]
+  Splice {
+    pipe: (std::os::unix::io::RawFd, std::os::unix::io::RawFd),
+    in_pipe: usize,
+    eof: bool,
+  },
+}
+
+/// A future which copies bytes from one resource to another without them ever
+/// reaching JS.
+///
+/// This is created by the [`copy`] top-level method.
+///
+/// [`copy`]: fn.copy.html
+pub struct Copy {
+  src: Resource,
+  dst: Resource,
+  remaining: Option<u64>,
+  copied: u64,
+  mode: Mode,
+}
+
+/// Creates a future that copies `limit` bytes, or everything up to EOF if no
+/// limit is given, from `src` to `dst`. Resolves to the number of bytes
+/// copied.
+///
+/// On Linux a file to TCP stream copy uses sendfile(2) and a TCP stream to
+/// TCP stream copy uses splice(2). Everything else goes through a buffer.
+pub fn copy(
+  src: Resource,
+  dst: Resource,
+  limit: Option<u64>,
+) -> Result<Copy, ErrBox> {
+  let mode = fast_path(&src, &dst)?.unwrap_or_else(|| Mode::Buffered {
+    buf: vec![0; CHUNK_SIZE].into_boxed_slice(),
+    pos: 0,
+    cap: 0,
+    eof: false,
+  });
+  Ok(Copy {
+    src,
+    dst,
+    remaining: limit,
+    copied: 0,
+    mode,
+  })
+}
+
+#[cfg(target_os = "linux")]
+fn fast_path(src: &Resource, dst: &Resource) -> Result<Option<Mode>, ErrBox> {
+  if !dst.is_tcp_stream() {
+    return Ok(None);
+  }
+  if resources::get_type(src.rid) == Some(String::from("fsFile")) {
+    let file = resources::get_file(src.rid)?;
+    return Ok(Some(Mode::Sendfile { file }));
+  }
+  if src.is_tcp_stream() {
+    let pipe = nix::unistd::pipe2(nix::fcntl::OFlag::O_CLOEXEC)?;
+    return Ok(Some(Mode::Splice {
+      pipe,
+      in_pipe: 0,
+      eof: false,
+    }));
+  }
+  Ok(None)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn fast_path(_src: &Resource, _dst: &Resource) -> Result<Option<Mode>, ErrBox> {
+  Ok(None)
+}
+
+impl Copy {
+  /// Number of bytes the next syscall may move.
+  fn next_count(&self) -> usize {
+    match self.remaining {
+      Some(remaining) => cmp::min(remaining, CHUNK_SIZE as u64) as usize,
+      None => CHUNK_SIZE,
+    }
+  }
+
+  fn advance(&mut self, n: usize) {
+    self.copied += n as u64;
+    if let Some(ref mut remaining) = self.remaining {
+      *remaining -= n as u64;
+    }
+  }
+}
+
+impl Future for Copy {
+  type Item = u64;
+  type Error = ErrBox;
+
+  fn poll(&mut self) -> Poll<u64, ErrBox> {
+    loop {
+      let count = self.next_count();
+      let n = match self.mode {
+        Mode::Buffered {
+          ref mut buf,
+          ref mut pos,
+          ref mut cap,
+          ref mut eof,
+        } => {
+          if *pos == *cap && !*eof {
+            if count == 0 {
+              *eof = true;
+            } else {
+              let n = try_ready!(self.src.poll_read(&mut buf[..count]));
+              *pos = 0;
+              *cap = n;
+              *eof = n == 0;
+            }
+          }
+          if *pos == *cap {
+            return Ok(self.copied.into());
+          }
+          let n = try_ready!(self.dst.poll_write(&buf[*pos..*cap]));
+          if n == 0 {
+            return Err(
+              io::Error::new(
+                io::ErrorKind::WriteZero,
+                "write zero byte into writer",
+              )
+              .into(),
+            );
+          }
+          *pos += n;
+          n
+        }
+        #[cfg(target_os = "linux")]
+        Mode::Sendfile { ref file } => {
+          if count == 0 {
+            return Ok(self.copied.into());
+          }
+          let n = try_ready!(self.dst.poll_sendfile(file, count));
+          if n == 0 {
+            return Ok(self.copied.into());
+          }
+          n
+        }
+        #[cfg(target_os = "linux")]
+        Mode::Splice {
+          pipe: (pipe_r, pipe_w),
+          ref mut in_pipe,
+          ref mut eof,
+        } => {
+          if *in_pipe == 0 {
+            if count == 0 || *eof {
+              return Ok(self.copied.into());
+            }
+            let n = try_ready!(self.src.poll_splice_to_pipe(pipe_w, count));
+            *in_pipe = n;
+            *eof = n == 0;
+            continue;
+          }
+          let n = try_ready!(self.dst.poll_splice_from_pipe(pipe_r, *in_pipe));
+          *in_pipe -= n;
+          n
+        }
+      };
+      self.advance(n);
+    }
+  }
+}
+
+impl Drop for Copy {
+  fn drop(&mut self) {
+    match self.mode {
+      Mode::Buffered { pos, cap, .. } => {
+        // Dropped while writing out the buffer.
+        if pos < cap {
+          self.dst.cancel_write();
+        }
+      }
+      #[cfg(target_os = "linux")]
+      Mode::Sendfile { .. } => {}
+      #[cfg(target_os = "linux")]
+      Mode::Splice {
+        pipe: (pipe_r, pipe_w),
+        ..
+      } => {
+        let _ = nix::unistd::close(pipe_r);
+        let _ = nix::unistd::close(pipe_w);
+      }
+    }
+  }
+}
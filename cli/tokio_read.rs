@@ -62,3 +62,61 @@ where
     }
   }
 }
+
+/// Creates a future that reads into `buf` until it is full or the stream
+/// reaches EOF, whichever comes first.
+///
+/// The returned future resolves to the I/O stream, the buffer and the number
+/// of bytes read, which is less than the length of the buffer only at EOF.
+pub fn read_all<R, T>(rd: R, buf: T) -> ReadAll<R, T>
+where
+  R: DenoAsyncRead,
+  T: AsMut<[u8]>,
+{
+  ReadAll {
+    state: State::Pending { rd, buf },
+    pos: 0,
+  }
+}
+
+/// A future which reads until a buffer is full or EOF is reached.
+///
+/// Created by the [`read_all`] function.
+#[derive(Debug)]
+pub struct ReadAll<R, T> {
+  state: State<R, T>,
+  pos: usize,
+}
+
+impl<R, T> Future for ReadAll<R, T>
+where
+  R: DenoAsyncRead,
+  T: AsMut<[u8]>,
+{
+  type Item = (R, T, usize);
+  type Error = ErrBox;
+
+  fn poll(&mut self) -> Poll<(R, T, usize), ErrBox> {
+    match self.state {
+      State::Pending {
+        ref mut rd,
+        ref mut buf,
+      } => {
+        let buf = buf.as_mut();
+        while self.pos < buf.len() {
+          let n = try_ready!(rd.poll_read(&mut buf[self.pos..]));
+          if n == 0 {
+            break;
+          }
+          self.pos += n;
+        }
+      }
+      State::Empty => panic!("poll a ReadAll after it's done"),
+    };
+
+    match mem::replace(&mut self.state, State::Empty) {
+      State::Pending { rd, buf } => Ok((rd, buf, self.pos).into()),
+      State::Empty => panic!("invalid internal state"),
+    }
+  }
+}
@@ -2,7 +2,9 @@
 use crate::resources::DenoAsyncWrite;
 use deno::ErrBox;
 use futures::{Future, Poll};
+use std::io;
 use std::mem;
+use std::ops::Range;
 
 /// A future used to write some data to a stream.
 ///
@@ -10,7 +12,7 @@ use std::mem;
 ///
 /// [`write`]: fn.write.html
 #[derive(Debug)]
-pub struct Write<A, T> {
+pub struct Write<A: DenoAsyncWrite, T> {
   state: State<A, T>,
 }
 
@@ -60,3 +62,182 @@ where
     }
   }
 }
+
+impl<A: DenoAsyncWrite, T> Drop for Write<A, T> {
+  fn drop(&mut self) {
+    cancel_pending(&mut self.state);
+  }
+}
+
+/// Lets the stream forget the write polled by a future that's dropped before
+/// it resolved.
+fn cancel_pending<A: DenoAsyncWrite, T>(state: &mut State<A, T>) {
+  if let State::Pending { a, .. } = state {
+    a.cancel_write();
+  }
+}
+
+/// A future used to write the whole of a buffer to a stream.
+///
+/// This is created by the [`write_all`] top-level method.
+///
+/// [`write_all`]: fn.write_all.html
+#[derive(Debug)]
+pub struct WriteAll<A: DenoAsyncWrite, T> {
+  state: State<A, T>,
+  pos: usize,
+}
+
+/// Creates a future that will write all of the buffer `buf` to the stream
+/// `a` provided, issuing as many writes as it takes.
+///
+/// Any error which happens during writing will cause both the stream and the
+/// buffer to get destroyed.
+pub fn write_all<A, T>(a: A, buf: T) -> WriteAll<A, T>
+where
+  A: DenoAsyncWrite,
+  T: AsRef<[u8]>,
+{
+  WriteAll {
+    state: State::Pending { a, buf },
+    pos: 0,
+  }
+}
+
+impl<A, T> Future for WriteAll<A, T>
+where
+  A: DenoAsyncWrite,
+  T: AsRef<[u8]>,
+{
+  type Item = (A, T, usize);
+  type Error = ErrBox;
+
+  fn poll(&mut self) -> Poll<(A, T, usize), ErrBox> {
+    match self.state {
+      State::Pending {
+        ref mut a,
+        ref mut buf,
+      } => {
+        let buf = buf.as_ref();
+        while self.pos < buf.len() {
+          let n = try_ready!(a.poll_write(&buf[self.pos..]));
+          if n == 0 {
+            return Err(
+              io::Error::new(
+                io::ErrorKind::WriteZero,
+                "write zero byte into writer",
+              )
+              .into(),
+            );
+          }
+          self.pos += n;
+        }
+      }
+      State::Empty => panic!("poll a WriteAll after it's done"),
+    };
+
+    match mem::replace(&mut self.state, State::Empty) {
+      State::Pending { a, buf } => Ok((a, buf, self.pos).into()),
+      State::Empty => panic!("invalid internal state"),
+    }
+  }
+}
+
+impl<A: DenoAsyncWrite, T> Drop for WriteAll<A, T> {
+  fn drop(&mut self) {
+    cancel_pending(&mut self.state);
+  }
+}
+
+/// A future used to write the whole of several ranges of a buffer to a
+/// stream, in order.
+///
+/// This is created by the [`write_vectored_all`] top-level method.
+///
+/// [`write_vectored_all`]: fn.write_vectored_all.html
+#[derive(Debug)]
+pub struct WriteVectoredAll<A: DenoAsyncWrite, T> {
+  state: State<A, T>,
+  ranges: Vec<Range<usize>>,
+  pos: usize,
+}
+
+/// Creates a future that will write all of the `ranges` of the buffer `buf`
+/// to the stream `a` provided, as if they were concatenated, issuing as many
+/// vectored writes as it takes. The ranges must be within `buf`.
+///
+/// Any error which happens during writing will cause both the stream and the
+/// buffer to get destroyed.
+pub fn write_vectored_all<A, T>(
+  a: A,
+  buf: T,
+  ranges: Vec<Range<usize>>,
+) -> WriteVectoredAll<A, T>
+where
+  A: DenoAsyncWrite,
+  T: AsRef<[u8]>,
+{
+  WriteVectoredAll {
+    state: State::Pending { a, buf },
+    ranges,
+    pos: 0,
+  }
+}
+
+impl<A, T> Future for WriteVectoredAll<A, T>
+where
+  A: DenoAsyncWrite,
+  T: AsRef<[u8]>,
+{
+  type Item = (A, T, usize);
+  type Error = ErrBox;
+
+  fn poll(&mut self) -> Poll<(A, T, usize), ErrBox> {
+    match self.state {
+      State::Pending {
+        ref mut a,
+        ref mut buf,
+      } => {
+        let buf = buf.as_ref();
+        let len: usize = self.ranges.iter().map(|r| r.end - r.start).sum();
+        while self.pos < len {
+          // Leave out what has been written already.
+          let mut skip = self.pos;
+          let mut slices = Vec::with_capacity(self.ranges.len());
+          for r in self.ranges.iter() {
+            let b = &buf[r.clone()];
+            if skip >= b.len() {
+              skip -= b.len();
+            } else {
+              slices.push(&b[skip..]);
+              skip = 0;
+            }
+          }
+          let n = try_ready!(a.poll_write_vectored(&slices));
+          if n == 0 {
+            return Err(
+              io::Error::new(
+                io::ErrorKind::WriteZero,
+                "write zero byte into writer",
+              )
+              .into(),
+            );
+          }
+          self.pos += n;
+        }
+      }
+      State::Empty => panic!("poll a WriteVectoredAll after it's done"),
+    };
+
+    match mem::replace(&mut self.state, State::Empty) {
+      State::Pending { a, buf } => Ok((a, buf, self.pos).into()),
+      State::Empty => panic!("invalid internal state"),
+    }
+  }
+}
+
+impl<A: DenoAsyncWrite, T> Drop for WriteVectoredAll<A, T> {
+  fn drop(&mut self) {
+    cancel_pending(&mut self.state);
+  }
+}
@@ -1,9 +1,11 @@
 // Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+use crate::flags::UnhandledRejections;
 use crate::fmt_errors::JSError;
 use crate::ops;
 use crate::state::ThreadSafeState;
 use deno;
 use deno::ErrBox;
+use deno::InspectorHandle;
 use deno::ModuleSpecifier;
 use deno::RecursiveLoad;
 use deno::StartupData;
@@ -32,22 +34,32 @@ impl Worker {
     {
       let mut i = isolate.lock().unwrap();
 
+      ops::batch::init(&mut i, &state);
       ops::compiler::init(&mut i, &state);
       ops::errors::init(&mut i, &state);
       ops::fetch::init(&mut i, &state);
       ops::files::init(&mut i, &state);
       ops::fs::init(&mut i, &state);
+      ops::fs_events::init(&mut i, &state);
+      ops::glob::init(&mut i, &state);
       ops::io::init(&mut i, &state);
+      ops::mmap::init(&mut i, &state);
       ops::net::init(&mut i, &state);
       ops::tls::init(&mut i, &state);
       ops::os::init(&mut i, &state);
       ops::permissions::init(&mut i, &state);
+      ops::plugins::init(&mut i, &state);
       ops::process::init(&mut i, &state);
       ops::random::init(&mut i, &state);
       ops::repl::init(&mut i, &state);
       ops::resources::init(&mut i, &state);
+      ops::ring::init(&mut i, &state);
+      ops::signal::init(&mut i, &state);
       ops::timers::init(&mut i, &state);
+      ops::tty::init(&mut i, &state);
       ops::workers::init(&mut i, &state);
+      ops::xattr::init(&mut i, &state);
+      ops::init_custom(&mut i, &state);
 
       let state_ = state.clone();
       i.set_dyn_import(move |id, specifier, referrer| {
@@ -64,7 +76,17 @@ impl Worker {
       let state_ = state.clone();
       i.set_js_error_create(move |v8_exception| {
         JSError::from_v8_exception(v8_exception, &state_.ts_compiler)
-      })
+      });
+
+      // Strict is the isolate's own behavior, failing on the first one.
+      match state.flags.unhandled_rejections {
+        UnhandledRejections::Strict => {}
+        UnhandledRejections::Warn => i.set_unhandled_rejection(|err| {
+          eprintln!("{}", err.to_string());
+          Ok(())
+        }),
+        UnhandledRejections::None => i.set_unhandled_rejection(|_| Ok(())),
+      }
     }
     Self { isolate, state }
   }
@@ -87,6 +109,19 @@ impl Worker {
     isolate.execute(js_filename, js_source)
   }
 
+  /// Returns a handle to connect debugger frontends to the isolate.
+  pub fn inspector_handle(&self) -> InspectorHandle {
+    let mut isolate = self.isolate.lock().unwrap();
+    isolate.inspector_handle()
+  }
+
+  /// Blocks until a debugger frontend connects and tells the program to run.
+  /// JavaScript then pauses on its next statement.
+  pub fn wait_for_debugger(&self) {
+    let mut isolate = self.isolate.lock().unwrap();
+    isolate.wait_for_debugger()
+  }
+
   /// Executes the provided JavaScript module.
   pub fn execute_mod_async(
     &mut self,
@@ -385,4 +420,29 @@ mod tests {
       assert!(result.is_ok());
     })
   }
+
+  fn op_answer(
+    _state: &ThreadSafeState,
+    args: serde_json::Value,
+    _zero_copy: Option<deno::PinnedBuf>,
+  ) -> Result<ops::JsonOp, ErrBox> {
+    Ok(ops::JsonOp::Sync(json!({ "answer": args["question"] })))
+  }
+
+  fn init_answer(i: &mut deno::Isolate, s: &ThreadSafeState) {
+    s.register_op(i, "answer", ops::json_op(s.stateful_op(op_answer)));
+  }
+
+  #[test]
+  fn test_custom_ops() {
+    ops::register_custom_ops(init_answer);
+    let mut worker = create_test_worker();
+    let source = r#"
+      const res = Deno.opSync("answer", { question: 42 });
+      if (res.answer !== 42) {
+        throw Error("bad answer");
+      }
+    "#;
+    worker.execute(source).unwrap();
+  }
 }
@@ -0,0 +1,254 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+//! Sessions between a debugger frontend, such as the Chrome DevTools, and the
+//! V8 inspector of an isolate. Both sides exchange JSON messages of the
+//! DevTools protocol.
+use crate::libdeno;
+use futures::stream::Stream;
+use futures::sync::mpsc;
+use futures::task::AtomicTask;
+use futures::Async::*;
+use libc::c_char;
+use libc::c_int;
+use libc::c_void;
+use std::collections::VecDeque;
+use std::ptr::null;
+use std::slice;
+use std::sync::{Arc, Condvar, Mutex};
+
+type SharedLibdenoIsolate = Arc<Mutex<Option<*const libdeno::isolate>>>;
+
+#[derive(Default)]
+struct Queue {
+  messages: VecDeque<String>,
+  closed: bool,
+}
+
+/// The messages of a frontend, which wait for the isolate's thread to
+/// dispatch them. That thread either polls them from the isolate's task, or
+/// blocks on them while JavaScript is paused.
+struct Incoming {
+  queue: Mutex<Queue>,
+  cond: Condvar,
+  task: AtomicTask,
+}
+
+impl Incoming {
+  fn new() -> Self {
+    Self {
+      queue: Mutex::new(Queue::default()),
+      cond: Condvar::new(),
+      task: AtomicTask::new(),
+    }
+  }
+
+  fn update<F>(&self, f: F)
+  where
+    F: FnOnce(&mut Queue),
+  {
+    f(&mut self.queue.lock().unwrap());
+    self.cond.notify_one();
+    self.task.notify();
+  }
+}
+
+/// The frontend's end of a session. Dropping it ends the session.
+pub struct InspectorSender {
+  incoming: Arc<Incoming>,
+  shared_libdeno_isolate: SharedLibdenoIsolate,
+}
+
+unsafe impl Send for InspectorSender {}
+
+impl InspectorSender {
+  /// Sends a message to the inspector. It's dispatched even if JavaScript is
+  /// busy running.
+  pub fn send(&self, message: String) {
+    self
+      .incoming
+      .update(|queue| queue.messages.push_back(message));
+    if let Some(isolate) = *self.shared_libdeno_isolate.lock().unwrap() {
+      unsafe { libdeno::deno_inspector_interrupt(isolate) }
+    }
+  }
+}
+
+impl Drop for InspectorSender {
+  fn drop(&mut self) {
+    self.incoming.update(|queue| queue.closed = true);
+  }
+}
+
+/// InspectorHandle is a thread safe handle to connect frontends to the
+/// inspector of an Isolate.
+#[derive(Clone)]
+pub struct InspectorHandle {
+  sessions: mpsc::UnboundedSender<Session>,
+  shared_libdeno_isolate: SharedLibdenoIsolate,
+}
+
+unsafe impl Send for InspectorHandle {}
+
+impl InspectorHandle {
+  /// Connects a frontend, disconnecting the one connected before if any. The
+  /// session starts once the isolate is next polled. Returns the sender of the
+  /// frontend's messages, and the stream of the inspector's ones, which ends
+  /// with the session.
+  pub fn connect(&self) -> (InspectorSender, mpsc::UnboundedReceiver<String>) {
+    let incoming = Arc::new(Incoming::new());
+    let (outgoing_tx, outgoing_rx) = mpsc::unbounded();
+    let session = Session {
+      incoming: incoming.clone(),
+      outgoing: outgoing_tx,
+      libdeno_isolate: null(),
+      user_data: null(),
+    };
+    // If the isolate is gone, dropping the session ends the outgoing stream.
+    let _ = self.sessions.unbounded_send(session);
+    let sender = InspectorSender {
+      incoming,
+      shared_libdeno_isolate: self.shared_libdeno_isolate.clone(),
+    };
+    (sender, outgoing_rx)
+  }
+}
+
+/// The isolate's end of a session.
+struct Session {
+  incoming: Arc<Incoming>,
+  outgoing: mpsc::UnboundedSender<String>,
+  libdeno_isolate: *const libdeno::isolate,
+  user_data: *const c_void,
+}
+
+impl Session {
+  /// Dispatches the queued messages of the frontend, waiting for one first if
+  /// `block` is set. Returns false once the frontend is gone.
+  fn dispatch_queued(&self, mut block: bool) -> bool {
+    loop {
+      let message = {
+        let mut queue = self.incoming.queue.lock().unwrap();
+        while block && queue.messages.is_empty() && !queue.closed {
+          queue = self.incoming.cond.wait(queue).unwrap();
+        }
+        match queue.messages.pop_front() {
+          Some(message) => message,
+          None => return !queue.closed,
+        }
+      };
+      block = false;
+      // Dispatching may pause JavaScript and come back here, so the queue
+      // isn't locked meanwhile.
+      unsafe {
+        libdeno::deno_inspector_dispatch(
+          self.libdeno_isolate,
+          self.user_data,
+          message.as_ptr() as *const c_char,
+          message.len(),
+        )
+      }
+    }
+  }
+
+  extern "C" fn send_cb(
+    user_data: *mut c_void,
+    message: *const c_char,
+    len: usize,
+  ) {
+    let session = unsafe { &*(user_data as *const Session) };
+    let message = unsafe { slice::from_raw_parts(message as *const u8, len) };
+    let message = String::from_utf8_lossy(message).into_owned();
+    // The frontend may be gone already.
+    let _ = session.outgoing.unbounded_send(message);
+  }
+
+  extern "C" fn poll_cb(user_data: *mut c_void, block: c_int) -> c_int {
+    let session = unsafe { &*(user_data as *const Session) };
+    session.dispatch_queued(block != 0).into()
+  }
+}
+
+/// The inspector of an Isolate, to which a single frontend is connected at a
+/// time.
+pub(crate) struct Inspector {
+  libdeno_isolate: *const libdeno::isolate,
+  handle: InspectorHandle,
+  sessions: mpsc::UnboundedReceiver<Session>,
+  // Boxed since libdeno keeps a pointer to it.
+  session: Option<Box<Session>>,
+}
+
+impl Inspector {
+  pub fn new(
+    libdeno_isolate: *const libdeno::isolate,
+    shared_libdeno_isolate: SharedLibdenoIsolate,
+  ) -> Self {
+    let (sessions_tx, sessions_rx) = mpsc::unbounded();
+    Self {
+      libdeno_isolate,
+      handle: InspectorHandle {
+        sessions: sessions_tx,
+        shared_libdeno_isolate,
+      },
+      sessions: sessions_rx,
+      session: None,
+    }
+  }
+
+  pub fn handle(&self) -> InspectorHandle {
+    self.handle.clone()
+  }
+
+  /// Starts the sessions of frontends which just connected, and dispatches
+  /// the messages of the current one. Must be called from the isolate's task.
+  /// `user_data` is the Isolate, which must not move while a session lasts.
+  pub fn poll(&mut self, user_data: *const c_void) {
+    while let Ok(Ready(Some(session))) = self.sessions.poll() {
+      self.connect(session, user_data);
+    }
+    let connected = match self.session {
+      Some(ref session) => {
+        session.incoming.task.register();
+        session.dispatch_queued(false)
+      }
+      None => return,
+    };
+    if !connected {
+      self.disconnect();
+    }
+  }
+
+  /// Blocks until a frontend connects and tells the program to run, then
+  /// pauses JavaScript on its next statement.
+  pub fn wait_for_debugger(&mut self, user_data: *const c_void) {
+    if self.session.is_none() {
+      match (&mut self.sessions).wait().next() {
+        Some(Ok(session)) => self.connect(session, user_data),
+        _ => return,
+      }
+    }
+    unsafe { libdeno::deno_inspector_wait_for_debugger(self.libdeno_isolate) }
+  }
+
+  fn connect(&mut self, mut session: Session, user_data: *const c_void) {
+    self.disconnect();
+    session.libdeno_isolate = self.libdeno_isolate;
+    session.user_data = user_data;
+    let session = Box::new(session);
+    unsafe {
+      libdeno::deno_inspector_connect(
+        self.libdeno_isolate,
+        &*session as *const Session as *const c_void,
+        Session::send_cb,
+        Session::poll_cb,
+      )
+    };
+    self.session = Some(session);
+  }
+
+  fn disconnect(&mut self) {
+    if let Some(session) = self.session.take() {
+      unsafe { libdeno::deno_inspector_disconnect(self.libdeno_isolate) }
+      drop(session);
+    }
+  }
+}
@@ -6,6 +6,8 @@
 // asynchronous module loading.
 
 use crate::any_error::ErrBox;
+use crate::inspector::Inspector;
+use crate::inspector::InspectorHandle;
 use crate::js_errors::CoreJSError;
 use crate::js_errors::V8Exception;
 use crate::libdeno;
@@ -137,6 +139,7 @@ pub enum StartupData<'a> {
 }
 
 type JSErrorCreateFn = dyn Fn(V8Exception) -> ErrBox;
+type UnhandledRejectionFn = dyn Fn(ErrBox) -> Result<(), ErrBox>;
 
 /// A single execution context of JavaScript. Corresponds roughly to the "Web
 /// Worker" concept in the DOM. An Isolate is a Future that can be used with
@@ -151,14 +154,16 @@ pub struct Isolate {
   shared_libdeno_isolate: Arc<Mutex<Option<*const libdeno::isolate>>>,
   dyn_import: Option<Arc<DynImportFn>>,
   js_error_create: Arc<JSErrorCreateFn>,
+  unhandled_rejection: Option<Arc<UnhandledRejectionFn>>,
   needs_init: bool,
   shared: SharedQueue,
   pending_ops: FuturesUnordered<PendingOpFuture>,
   pending_dyn_imports: FuturesUnordered<StreamFuture<DynImport>>,
   have_unpolled_ops: bool,
   startup_script: Option<OwnedScript>,
-  op_registry: OpRegistry,
+  op_registry: Arc<OpRegistry>,
   eager_poll_count: u32,
+  inspector: Option<Inspector>,
 }
 
 unsafe impl Send for Isolate {}
@@ -217,14 +222,16 @@ impl Isolate {
       shared_libdeno_isolate: Arc::new(Mutex::new(Some(libdeno_isolate))),
       dyn_import: None,
       js_error_create: Arc::new(CoreJSError::from_v8_exception),
+      unhandled_rejection: None,
       shared,
       needs_init,
       pending_ops: FuturesUnordered::new(),
       have_unpolled_ops: false,
       pending_dyn_imports: FuturesUnordered::new(),
       startup_script,
-      op_registry: OpRegistry::new(),
+      op_registry: Arc::new(OpRegistry::new()),
       eager_poll_count: 0,
+      inspector: None,
     }
   }
 
@@ -240,6 +247,12 @@ impl Isolate {
     self.op_registry.register(name, op)
   }
 
+  /// Get a handle on the ops of the isolate, which lets ops register others
+  /// while it runs, e.g. when loading a plugin.
+  pub fn op_registry(&self) -> Arc<OpRegistry> {
+    self.op_registry.clone()
+  }
+
   pub fn set_dyn_import<F>(&mut self, f: F)
   where
     F: Fn(deno_dyn_import_id, &str, &str) -> DynImportStream
@@ -260,6 +273,17 @@ impl Isolate {
     self.js_error_create = Arc::new(f);
   }
 
+  /// Allows a callback to be set whenever a promise is rejected without a
+  /// handler. It's given the error, and returning it makes the isolate fail
+  /// like an uncaught exception would. Without a callback, the isolate fails
+  /// on the first unhandled rejection.
+  pub fn set_unhandled_rejection<F>(&mut self, f: F)
+  where
+    F: Fn(ErrBox) -> Result<(), ErrBox> + 'static,
+  {
+    self.unhandled_rejection = Some(Arc::new(f));
+  }
+
   /// Get a thread safe handle on the isolate.
   pub fn shared_isolate_handle(&mut self) -> IsolateHandle {
     IsolateHandle {
@@ -267,6 +291,29 @@ impl Isolate {
     }
   }
 
+  /// Get a thread safe handle to connect debugger frontends to the V8
+  /// inspector. Their messages are dispatched when the isolate is polled, or
+  /// right away while JavaScript runs.
+  pub fn inspector_handle(&mut self) -> InspectorHandle {
+    let libdeno_isolate = self.libdeno_isolate;
+    let shared_libdeno_isolate = self.shared_libdeno_isolate.clone();
+    self
+      .inspector
+      .get_or_insert_with(|| {
+        Inspector::new(libdeno_isolate, shared_libdeno_isolate)
+      })
+      .handle()
+  }
+
+  /// Blocks until a debugger frontend connects through inspector_handle() and
+  /// tells the program to run, then pauses on the next JavaScript statement.
+  pub fn wait_for_debugger(&mut self) {
+    let user_data = self.as_raw_ptr();
+    if let Some(ref mut inspector) = self.inspector {
+      inspector.wait_for_debugger(user_data);
+    }
+  }
+
   /// Executes a bit of built-in JavaScript to provide Deno.sharedQueue.
   fn shared_init(&mut self) {
     if self.needs_init {
@@ -340,7 +387,7 @@ impl Isolate {
             Ok(NotReady) => Op::Async(fut),
           }
         }
-        Op::Sync(buf) => Op::Sync(buf),
+        op => op,
       }
     } else {
       op
@@ -358,6 +405,7 @@ impl Isolate {
           // into JavaScript. We should not get an error here.
           .expect("unexpected error");
       }
+      Op::External(buf) => isolate.respond_external(buf),
       Op::Async(fut) => {
         let fut2 = fut.map(move |buf| (op_id, buf));
         isolate.pending_ops.push(Box::new(fut2));
@@ -415,9 +463,22 @@ impl Isolate {
     }
   }
 
-  fn check_promise_errors(&self) {
-    unsafe {
-      libdeno::deno_check_promise_errors(self.libdeno_isolate);
+  fn check_promise_errors(&self) -> Result<(), ErrBox> {
+    loop {
+      unsafe {
+        libdeno::deno_check_promise_errors(self.libdeno_isolate);
+      }
+      let err = match self.check_last_exception() {
+        Ok(()) => return Ok(()),
+        Err(err) => err,
+      };
+      match self.unhandled_rejection {
+        Some(ref unhandled_rejection) => {
+          unsafe { libdeno::deno_clear_last_exception(self.libdeno_isolate) };
+          unhandled_rejection(err)?;
+        }
+        None => return Err(err),
+      }
     }
   }
 
@@ -442,6 +503,23 @@ impl Isolate {
     self.check_last_exception()
   }
 
+  fn respond_external(&mut self, buf: ExternalBuf) {
+    unsafe extern "C" fn free_external(free_data: *mut c_void) {
+      drop(Box::from_raw(free_data as *mut ExternalBuf));
+    }
+
+    let buf = Box::new(buf);
+    let data = unsafe { deno_buf::from_raw_parts(buf.as_ptr(), buf.len()) };
+    unsafe {
+      libdeno::deno_respond_external(
+        self.libdeno_isolate,
+        data,
+        free_external,
+        Box::into_raw(buf) as *mut c_void,
+      )
+    }
+  }
+
   /// Low-level module creation.
   pub fn mod_new(
     &self,
@@ -662,6 +740,12 @@ impl Future for Isolate {
 
     let mut overflow_response: Option<(OpId, Buf)> = None;
 
+    // Debugger messages can run JavaScript, so dispatch them before the ops.
+    let user_data = self.as_raw_ptr();
+    if let Some(ref mut inspector) = self.inspector {
+      inspector.poll(user_data);
+    }
+
     loop {
       // If there are any pending dyn_import futures, do those first.
       if !self.pending_dyn_imports.is_empty() {
@@ -706,7 +790,7 @@ impl Future for Isolate {
       drop(locker);
     }
 
-    self.check_promise_errors();
+    self.check_promise_errors()?;
     self.check_last_exception()?;
 
     // We're idle if pending_ops is empty.
@@ -1452,6 +1536,52 @@ pub mod tests {
     });
   }
 
+  #[test]
+  fn test_external_op() {
+    struct Owner(Vec<u8>, Arc<AtomicUsize>);
+
+    impl std::ops::Deref for Owner {
+      type Target = [u8];
+      fn deref(&self) -> &[u8] {
+        &self.0
+      }
+    }
+
+    impl std::ops::DerefMut for Owner {
+      fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+      }
+    }
+
+    impl Drop for Owner {
+      fn drop(&mut self) {
+        assert_eq!(self.0, b"Bar");
+        self.1.fetch_add(1, Ordering::Relaxed);
+      }
+    }
+
+    let (mut isolate, _dispatch_count) = setup(Mode::AsyncImmediate);
+    let drop_count = Arc::new(AtomicUsize::new(0));
+    let drop_count_ = drop_count.clone();
+    isolate.register_op("external", move |_, _| {
+      Op::External(ExternalBuf::new(Owner(
+        b"bar".to_vec(),
+        drop_count_.clone(),
+      )))
+    });
+    js_check(isolate.execute(
+      "external.js",
+      r#"
+        const buf = Deno.core.send(2, new Uint8Array([]));
+        assert(String.fromCharCode(...buf) === "bar");
+        buf[0] = "B".charCodeAt(0);
+        Deno.core.release(buf);
+        assert(buf.byteLength === 0);
+        "#,
+    ));
+    assert_eq!(drop_count.load(Ordering::Relaxed), 1);
+  }
+
   #[test]
   fn test_js() {
     run_in_task(|| {
@@ -1466,6 +1596,42 @@ pub mod tests {
     });
   }
 
+  #[test]
+  fn test_unhandled_rejection() {
+    run_in_task(|| {
+      let (mut isolate, _dispatch_count) = setup(Mode::AsyncImmediate);
+      js_check(isolate.execute(
+        "unhandled_rejection.js",
+        "Promise.reject(new Error('oops'));",
+      ));
+      assert!(isolate.poll().is_err());
+    });
+  }
+
+  #[test]
+  fn test_set_unhandled_rejection() {
+    run_in_task(|| {
+      let (mut isolate, _dispatch_count) = setup(Mode::AsyncImmediate);
+      let rejections = Arc::new(AtomicUsize::new(0));
+      let rejections_ = rejections.clone();
+      isolate.set_unhandled_rejection(move |err| {
+        assert!(err.to_string().contains("oops"));
+        rejections_.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+      });
+      js_check(isolate.execute(
+        "unhandled_rejection.js",
+        r#"
+          Promise.reject(new Error('oops 1'));
+          Promise.reject(new Error('oops 2'));
+          Promise.reject(new Error('caught')).catch(() => {});
+        "#,
+      ));
+      assert_eq!(Async::Ready(()), isolate.poll().unwrap());
+      assert_eq!(rejections.load(Ordering::Relaxed), 2);
+    });
+  }
+
   #[test]
   fn will_snapshot() {
     let snapshot = {
@@ -1480,4 +1646,22 @@ pub mod tests {
     let mut isolate2 = Isolate::new(startup_data, false);
     js_check(isolate2.execute("check.js", "if (a != 3) throw Error('x')"));
   }
+
+  #[test]
+  fn test_inspector() {
+    run_in_task(|| {
+      let mut isolate = Isolate::new(StartupData::None, false);
+      js_check(isolate.execute("a.js", "let a = 1 + 2;"));
+      let (sender, outgoing) = isolate.inspector_handle().connect();
+      sender.send(
+        r#"{"id":1,"method":"Runtime.evaluate","params":{"expression":"a"}}"#
+          .to_string(),
+      );
+      // Polling the isolate starts the session and dispatches the message.
+      assert_eq!(Async::Ready(()), isolate.poll().unwrap());
+      let response = outgoing.wait().next().unwrap().unwrap();
+      assert!(response.contains(r#""id":1"#));
+      assert!(response.contains(r#""value":3"#));
+    });
+  }
 }
@@ -6,16 +6,21 @@ extern crate libc;
 
 mod any_error;
 mod flags;
+mod inspector;
 mod isolate;
 mod js_errors;
 mod libdeno;
 mod module_specifier;
 mod modules;
 mod ops;
+mod plugins;
 mod shared_queue;
+mod snapshot;
 
 pub use crate::any_error::*;
 pub use crate::flags::v8_set_flags;
+pub use crate::inspector::InspectorHandle;
+pub use crate::inspector::InspectorSender;
 pub use crate::isolate::*;
 pub use crate::js_errors::*;
 pub use crate::libdeno::deno_mod;
@@ -24,6 +29,8 @@ pub use crate::libdeno::PinnedBuf;
 pub use crate::module_specifier::*;
 pub use crate::modules::*;
 pub use crate::ops::*;
+pub use crate::plugins::*;
+pub use crate::snapshot::SnapshotBuilder;
 
 pub fn v8_version() -> &'static str {
   use std::ffi::CStr;
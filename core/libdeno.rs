@@ -195,6 +195,11 @@ type deno_recv_cb = unsafe extern "C" fn(
   zero_copy_buf: deno_pinned_buf,
 );
 
+/// Called once JS can't access the memory passed to deno_respond_external()
+/// anymore.
+#[allow(non_camel_case_types)]
+pub type deno_external_free_cb = unsafe extern "C" fn(free_data: *mut c_void);
+
 /// Called when dynamic import is called in JS: import('foo')
 /// Embedder must call deno_dyn_import_done() with the specified id and
 /// the module.
@@ -219,6 +224,17 @@ type deno_resolve_cb = unsafe extern "C" fn(
   referrer: deno_mod,
 ) -> deno_mod;
 
+#[allow(non_camel_case_types)]
+pub type deno_inspector_send_cb = unsafe extern "C" fn(
+  user_data: *mut c_void,
+  message: *const c_char,
+  len: size_t,
+);
+
+#[allow(non_camel_case_types)]
+pub type deno_inspector_poll_cb =
+  unsafe extern "C" fn(user_data: *mut c_void, block: c_int) -> c_int;
+
 #[repr(C)]
 pub struct deno_config<'a> {
   pub will_snapshot: c_int,
@@ -264,6 +280,7 @@ extern "C" {
   pub fn deno_delete(i: *const isolate);
   pub fn deno_last_exception(i: *const isolate) -> *const c_char;
   pub fn deno_check_promise_errors(i: *const isolate);
+  pub fn deno_clear_last_exception(i: *const isolate);
   pub fn deno_lock(i: *const isolate);
   pub fn deno_unlock(i: *const isolate);
   pub fn deno_throw_exception(i: *const isolate, text: *const c_char);
@@ -273,6 +290,12 @@ extern "C" {
     op_id: OpId,
     buf: deno_buf,
   );
+  pub fn deno_respond_external(
+    i: *const isolate,
+    buf: deno_buf,
+    free_cb: deno_external_free_cb,
+    free_data: *mut c_void,
+  );
   pub fn deno_pinned_buf_delete(buf: &mut deno_pinned_buf);
   pub fn deno_execute(
     i: *const isolate,
@@ -327,4 +350,21 @@ extern "C" {
 
   #[allow(dead_code)]
   pub fn deno_snapshot_delete(s: &mut deno_snapshot);
+
+  // Inspector
+  pub fn deno_inspector_connect(
+    i: *const isolate,
+    user_data: *const c_void,
+    send_cb: deno_inspector_send_cb,
+    poll_cb: deno_inspector_poll_cb,
+  );
+  pub fn deno_inspector_disconnect(i: *const isolate);
+  pub fn deno_inspector_dispatch(
+    i: *const isolate,
+    user_data: *const c_void,
+    message: *const c_char,
+    len: size_t,
+  );
+  pub fn deno_inspector_wait_for_debugger(i: *const isolate);
+  pub fn deno_inspector_interrupt(i: *const isolate);
 }
@@ -212,6 +212,15 @@ void deno_respond(Deno* d_, void* user_data, deno_op_id op_id, deno_buf buf) {
   }
 }
 
+void deno_respond_external(Deno* d_, deno_buf buf,
+                           deno_external_free_cb free_cb, void* free_data) {
+  auto* d = unwrap(d_);
+  CHECK_NOT_NULL(d->current_args_);
+  auto ab = deno::ImportExternalBuf(d, buf, free_cb, free_data);
+  d->current_args_->GetReturnValue().Set(ab);
+  d->current_args_ = nullptr;
+}
+
 void deno_check_promise_errors(Deno* d_) {
   auto* d = unwrap(d_);
   if (d->pending_promise_map_.size() > 0) {
@@ -222,15 +231,33 @@ void deno_check_promise_errors(Deno* d_) {
     auto context = d->context_.Get(d->isolate_);
     v8::Context::Scope context_scope(context);
 
+    // The oldest rejection is reported first. The others stay pending until
+    // the next call, so that each of them can be handled in turn.
     auto it = d->pending_promise_map_.begin();
-    while (it != d->pending_promise_map_.end()) {
-      auto error = it->second.Get(isolate);
-      deno::HandleException(context, error);
-      it = d->pending_promise_map_.erase(it);
+    auto error = it->second.Get(isolate);
+    deno::HandleException(context, error);
+    int key = it->first;
+    d->pending_promise_map_.erase(it);
+    for (auto entry = d->pending_promise_keys_.begin();
+         entry != d->pending_promise_keys_.end(); ++entry) {
+      if (entry->second == key) {
+        d->pending_promise_keys_.erase(entry);
+        break;
+      }
     }
   }
 }
 
+void deno_clear_last_exception(Deno* d_) {
+  auto* d = unwrap(d_);
+  auto* isolate = d->isolate_;
+  v8::Locker locker(isolate);
+  v8::Isolate::Scope isolate_scope(isolate);
+  v8::HandleScope handle_scope(isolate);
+  auto context = d->context_.Get(d->isolate_);
+  deno::ClearException(context);
+}
+
 void deno_delete(Deno* d_) {
   deno::DenoIsolate* d = reinterpret_cast<deno::DenoIsolate*>(d_);
   delete d;
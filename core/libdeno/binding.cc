@@ -71,16 +71,20 @@ void PromiseRejectCallback(v8::PromiseRejectMessage promise_reject_message) {
 
   int promise_id = promise->GetIdentityHash();
   switch (promise_reject_message.GetEvent()) {
-    case v8::kPromiseRejectWithNoHandler:
-      // Insert the error into the pending_promise_map_ using the promise's id
-      // as the key.
+    case v8::kPromiseRejectWithNoHandler: {
+      // Insert the error into the pending_promise_map_ using a key which
+      // grows with each rejection, so that the oldest comes first.
+      d->ForgetPendingPromise(promise_id);
+      int key = d->next_pending_promise_key_++;
+      d->pending_promise_keys_[promise_id] = key;
       d->pending_promise_map_.emplace(std::piecewise_construct,
-                                      std::make_tuple(promise_id),
+                                      std::make_tuple(key),
                                       std::make_tuple(d->isolate_, error));
       break;
+    }
 
     case v8::kPromiseHandlerAddedAfterReject:
-      d->pending_promise_map_.erase(promise_id);
+      d->ForgetPendingPromise(promise_id);
       break;
 
     case v8::kPromiseRejectAfterResolved:
@@ -195,6 +199,53 @@ v8::Local<v8::Uint8Array> ImportBuf(DenoIsolate* d, deno_buf buf) {
   return view;
 }
 
+static void ExternalBufWeakCallback(
+    const v8::WeakCallbackInfo<ExternalBuf>& info) {
+  DenoIsolate* d = DenoIsolate::FromIsolate(info.GetIsolate());
+  d->FreeExternalBuf(info.GetParameter());
+}
+
+v8::Local<v8::Uint8Array> ImportExternalBuf(DenoIsolate* d, deno_buf buf,
+                                            deno_external_free_cb free_cb,
+                                            void* free_data) {
+  CHECK_NOT_NULL(buf.data_ptr);
+  // The memory stays owned by the embedder, V8 never frees it.
+  auto ab = v8::ArrayBuffer::New(d->isolate_, buf.data_ptr, buf.data_len,
+                                 v8::ArrayBufferCreationMode::kExternalized);
+  auto* external = new ExternalBuf();
+  external->data = buf.data_ptr;
+  external->handle.Reset(d->isolate_, ab);
+  external->handle.SetWeak(external, ExternalBufWeakCallback,
+                           v8::WeakCallbackType::kParameter);
+  external->free_cb = free_cb;
+  external->free_data = free_data;
+  d->external_bufs_[buf.data_ptr] = external;
+  return v8::Uint8Array::New(ab, 0, buf.data_len);
+}
+
+// Detaches the ArrayBuffer of a view returned by deno_respond_external(), so
+// neither it nor any other view of it can be used anymore, and hands its
+// memory back to the embedder right away instead of on garbage collection.
+void Release(const v8::FunctionCallbackInfo<v8::Value>& args) {
+  v8::Isolate* isolate = args.GetIsolate();
+  DenoIsolate* d = DenoIsolate::FromIsolate(isolate);
+  v8::HandleScope handle_scope(isolate);
+
+  if (!args[0]->IsArrayBufferView()) {
+    ThrowInvalidArgument(isolate);
+    return;
+  }
+  auto ab = v8::Local<v8::ArrayBufferView>::Cast(args[0])->Buffer();
+  // A detached ArrayBuffer has no data, so releasing twice throws too.
+  auto it = d->external_bufs_.find(ab->GetContents().Data());
+  if (it == d->external_bufs_.end() || it->second->handle != ab) {
+    ThrowInvalidArgument(isolate);
+    return;
+  }
+  ab->Detach();
+  d->FreeExternalBuf(it->second);
+}
+
 // Sets the recv_ callback.
 void Recv(const v8::FunctionCallbackInfo<v8::Value>& args) {
   v8::Isolate* isolate = args.GetIsolate();
@@ -455,6 +506,205 @@ void EvalContext(const v8::FunctionCallbackInfo<v8::Value>& args) {
   args.GetReturnValue().Set(output);
 }
 
+// Returns the heap statistics of the isolate, sizes being in bytes.
+void HeapStats(const v8::FunctionCallbackInfo<v8::Value>& args) {
+  v8::Isolate* isolate = args.GetIsolate();
+  v8::HandleScope handle_scope(isolate);
+  auto context = isolate->GetCurrentContext();
+
+  v8::HeapStatistics stats;
+  isolate->GetHeapStatistics(&stats);
+
+  auto output = v8::Object::New(isolate);
+  auto set = [&](const char* name, size_t value) {
+    CHECK(output
+              ->Set(context, v8_str(name),
+                    v8::Number::New(isolate, static_cast<double>(value)))
+              .FromJust());
+  };
+  set("totalHeapSize", stats.total_heap_size());
+  set("totalHeapSizeExecutable", stats.total_heap_size_executable());
+  set("totalPhysicalSize", stats.total_physical_size());
+  set("totalAvailableSize", stats.total_available_size());
+  set("usedHeapSize", stats.used_heap_size());
+  set("heapSizeLimit", stats.heap_size_limit());
+  set("mallocedMemory", stats.malloced_memory());
+  set("peakMallocedMemory", stats.peak_malloced_memory());
+  set("externalMemory", stats.external_memory());
+  set("numberOfNativeContexts", stats.number_of_native_contexts());
+  set("numberOfDetachedContexts", stats.number_of_detached_contexts());
+  args.GetReturnValue().Set(output);
+}
+
+// Passes each chunk of a serialized heap snapshot to a JavaScript callback,
+// stopping at the first exception it throws.
+class HeapSnapshotStream : public v8::OutputStream {
+ public:
+  HeapSnapshotStream(v8::Local<v8::Context> context,
+                     v8::Local<v8::Function> callback)
+      : context_(context), callback_(callback) {}
+  void EndOfStream() override {}
+  int GetChunkSize() override { return 64 * 1024; }
+  WriteResult WriteAsciiChunk(char* data, int size) override {
+    v8::Isolate* isolate = context_->GetIsolate();
+    v8::HandleScope handle_scope(isolate);
+    auto ab = v8::ArrayBuffer::New(isolate, size);
+    memcpy(ab->GetContents().Data(), data, size);
+    v8::Local<v8::Value> chunk = v8::Uint8Array::New(ab, 0, size);
+    auto r = callback_->Call(context_, context_->Global(), 1, &chunk);
+    return r.IsEmpty() ? kAbort : kContinue;
+  }
+
+ private:
+  v8::Local<v8::Context> context_;
+  v8::Local<v8::Function> callback_;
+};
+
+// Takes a heap snapshot of the isolate and passes the JSON that the Chrome
+// DevTools memory panel loads to the callback, one Uint8Array chunk at a time.
+void HeapSnapshot(const v8::FunctionCallbackInfo<v8::Value>& args) {
+  v8::Isolate* isolate = args.GetIsolate();
+  v8::HandleScope handle_scope(isolate);
+  if (!args[0]->IsFunction()) {
+    isolate->ThrowException(v8::Exception::TypeError(
+        v8_str("heapSnapshot expects a callback for the chunks.")));
+    return;
+  }
+  auto context = isolate->GetCurrentContext();
+  auto callback = v8::Local<v8::Function>::Cast(args[0]);
+
+  const v8::HeapSnapshot* snapshot =
+      isolate->GetHeapProfiler()->TakeHeapSnapshot();
+  HeapSnapshotStream stream(context, callback);
+  snapshot->Serialize(&stream, v8::HeapSnapshot::kJSON);
+  const_cast<v8::HeapSnapshot*>(snapshot)->Delete();
+}
+
+// There is a single CPU profile per isolate, recorded under this title. The
+// profiler only exists while profiling.
+static const char kCpuProfileTitle[] = "deno";
+
+void StartCpuProfile(const v8::FunctionCallbackInfo<v8::Value>& args) {
+  v8::Isolate* isolate = args.GetIsolate();
+  DenoIsolate* d = DenoIsolate::FromIsolate(isolate);
+  v8::HandleScope handle_scope(isolate);
+
+  if (d->cpu_profiler_ != nullptr) {
+    isolate->ThrowException(
+        v8::Exception::Error(v8_str("CPU profiling already started.")));
+    return;
+  }
+  d->cpu_profiler_ = v8::CpuProfiler::New(isolate);
+  d->cpu_profiler_->StartProfiling(v8_str(kCpuProfileTitle), true);
+}
+
+// Appends `node` and its descendants to `nodes`, in the layout of the
+// "nodes" of a .cpuprofile file.
+static void SerializeProfileNode(v8::Local<v8::Context> context,
+                                 const v8::CpuProfileNode* node,
+                                 v8::Local<v8::Array> nodes) {
+  v8::Isolate* isolate = context->GetIsolate();
+  auto number = [&](double value) { return v8::Number::New(isolate, value); };
+
+  auto call_frame = v8::Object::New(isolate);
+  CHECK(call_frame
+            ->Set(context, v8_str("functionName"), node->GetFunctionName())
+            .FromJust());
+  auto script_id = v8::Integer::New(isolate, node->GetScriptId());
+  CHECK(call_frame
+            ->Set(context, v8_str("scriptId"),
+                  script_id->ToString(context).ToLocalChecked())
+            .FromJust());
+  CHECK(call_frame
+            ->Set(context, v8_str("url"), node->GetScriptResourceName())
+            .FromJust());
+  // V8 numbers lines and columns from 1, DevTools from 0.
+  CHECK(call_frame
+            ->Set(context, v8_str("lineNumber"),
+                  number(node->GetLineNumber() - 1))
+            .FromJust());
+  CHECK(call_frame
+            ->Set(context, v8_str("columnNumber"),
+                  number(node->GetColumnNumber() - 1))
+            .FromJust());
+
+  int children_count = node->GetChildrenCount();
+  auto children = v8::Array::New(isolate, children_count);
+  for (int i = 0; i < children_count; i++) {
+    CHECK(children->Set(context, i, number(node->GetChild(i)->GetNodeId()))
+              .FromJust());
+  }
+
+  auto output = v8::Object::New(isolate);
+  CHECK(output->Set(context, v8_str("id"), number(node->GetNodeId()))
+            .FromJust());
+  CHECK(output->Set(context, v8_str("callFrame"), call_frame).FromJust());
+  CHECK(output->Set(context, v8_str("hitCount"), number(node->GetHitCount()))
+            .FromJust());
+  CHECK(output->Set(context, v8_str("children"), children).FromJust());
+  CHECK(nodes->Set(context, nodes->Length(), output).FromJust());
+
+  for (int i = 0; i < children_count; i++) {
+    SerializeProfileNode(context, node->GetChild(i), nodes);
+  }
+}
+
+// Stops the CPU profile started by StartCpuProfile and returns it as an object
+// in the .cpuprofile format which the Chrome DevTools load.
+void StopCpuProfile(const v8::FunctionCallbackInfo<v8::Value>& args) {
+  v8::Isolate* isolate = args.GetIsolate();
+  DenoIsolate* d = DenoIsolate::FromIsolate(isolate);
+  v8::HandleScope handle_scope(isolate);
+  auto context = isolate->GetCurrentContext();
+
+  if (d->cpu_profiler_ == nullptr) {
+    isolate->ThrowException(
+        v8::Exception::Error(v8_str("CPU profiling not started.")));
+    return;
+  }
+  v8::CpuProfile* profile =
+      d->cpu_profiler_->StopProfiling(v8_str(kCpuProfileTitle));
+  CHECK_NOT_NULL(profile);
+  auto number = [&](double value) { return v8::Number::New(isolate, value); };
+
+  auto nodes = v8::Array::New(isolate);
+  SerializeProfileNode(context, profile->GetTopDownRoot(), nodes);
+
+  // Timestamps are in microseconds; each delta is from the previous sample,
+  // the first one from the start of the profile.
+  int samples_count = profile->GetSamplesCount();
+  auto samples = v8::Array::New(isolate, samples_count);
+  auto time_deltas = v8::Array::New(isolate, samples_count);
+  int64_t last_timestamp = profile->GetStartTime();
+  for (int i = 0; i < samples_count; i++) {
+    int64_t timestamp = profile->GetSampleTimestamp(i);
+    CHECK(samples->Set(context, i, number(profile->GetSample(i)->GetNodeId()))
+              .FromJust());
+    CHECK(time_deltas
+              ->Set(context, i,
+                    number(static_cast<double>(timestamp - last_timestamp)))
+              .FromJust());
+    last_timestamp = timestamp;
+  }
+
+  auto output = v8::Object::New(isolate);
+  CHECK(output->Set(context, v8_str("nodes"), nodes).FromJust());
+  CHECK(output
+            ->Set(context, v8_str("startTime"),
+                  number(static_cast<double>(profile->GetStartTime())))
+            .FromJust());
+  CHECK(output
+            ->Set(context, v8_str("endTime"),
+                  number(static_cast<double>(profile->GetEndTime())))
+            .FromJust());
+  CHECK(output->Set(context, v8_str("samples"), samples).FromJust());
+  CHECK(output->Set(context, v8_str("timeDeltas"), time_deltas).FromJust());
+  profile->Delete();
+  d->cpu_profiler_->Dispose();
+  d->cpu_profiler_ = nullptr;
+  args.GetReturnValue().Set(output);
+}
+
 void QueueMicrotask(const v8::FunctionCallbackInfo<v8::Value>& args) {
   v8::Isolate* isolate = args.GetIsolate();
 
@@ -501,6 +751,39 @@ void InitializeContext(v8::Isolate* isolate, v8::Local<v8::Context> context) {
   CHECK(core_val->Set(context, deno::v8_str("errorToJSON"), error_to_json_val)
             .FromJust());
 
+  auto heap_stats_tmpl = v8::FunctionTemplate::New(isolate, HeapStats);
+  auto heap_stats_val = heap_stats_tmpl->GetFunction(context).ToLocalChecked();
+  CHECK(core_val->Set(context, deno::v8_str("heapStats"), heap_stats_val)
+            .FromJust());
+
+  auto heap_snapshot_tmpl = v8::FunctionTemplate::New(isolate, HeapSnapshot);
+  auto heap_snapshot_val =
+      heap_snapshot_tmpl->GetFunction(context).ToLocalChecked();
+  CHECK(core_val->Set(context, deno::v8_str("heapSnapshot"), heap_snapshot_val)
+            .FromJust());
+
+  auto start_cpu_profile_tmpl =
+      v8::FunctionTemplate::New(isolate, StartCpuProfile);
+  auto start_cpu_profile_val =
+      start_cpu_profile_tmpl->GetFunction(context).ToLocalChecked();
+  CHECK(core_val
+            ->Set(context, deno::v8_str("startCpuProfile"),
+                  start_cpu_profile_val)
+            .FromJust());
+
+  auto stop_cpu_profile_tmpl =
+      v8::FunctionTemplate::New(isolate, StopCpuProfile);
+  auto stop_cpu_profile_val =
+      stop_cpu_profile_tmpl->GetFunction(context).ToLocalChecked();
+  CHECK(core_val
+            ->Set(context, deno::v8_str("stopCpuProfile"), stop_cpu_profile_val)
+            .FromJust());
+
+  auto release_tmpl = v8::FunctionTemplate::New(isolate, Release);
+  auto release_val = release_tmpl->GetFunction(context).ToLocalChecked();
+  CHECK(
+      core_val->Set(context, deno::v8_str("release"), release_val).FromJust());
+
   CHECK(core_val->SetAccessor(context, deno::v8_str("shared"), Shared)
             .FromJust());
 
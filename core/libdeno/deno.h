@@ -110,15 +110,33 @@ void deno_execute(Deno* d, void* user_data, const char* js_filename,
 // If a JS exception was encountered, deno_last_exception() will be non-NULL.
 void deno_respond(Deno* d, void* user_data, deno_op_id op_id, deno_buf buf);
 
+// Called once JavaScript can't access the memory of an external buffer
+// anymore, see deno_respond_external().
+typedef void (*deno_external_free_cb)(void* free_data);
+
+// Like deno_respond(), but only for a synchronous response. The issuing
+// Deno.core.send() returns a Uint8Array backed by buf itself instead of a copy.
+// The embedder must keep the memory valid until libdeno calls free_cb with
+// free_data, which happens once the ArrayBuffer is garbage collected, released
+// with Deno.core.release() or the isolate is deleted.
+void deno_respond_external(Deno* d, deno_buf buf, deno_external_free_cb free_cb,
+                           void* free_data);
+
 void deno_throw_exception(Deno* d, const char* text);
 
 // consumes zero_copy
 void deno_pinned_buf_delete(deno_pinned_buf* buf);
 
+// Sets the last exception to the error of a promise which was rejected
+// without a handler, if any. Only one is reported per call, so call it again
+// once that error is handled to check for more.
 void deno_check_promise_errors(Deno* d);
 
 const char* deno_last_exception(Deno* d);
 
+// Forgets the last exception, e.g. once a promise error has been handled.
+void deno_clear_last_exception(Deno* d);
+
 void deno_terminate_execution(Deno* d);
 
 void deno_run_microtasks(Deno* d, void* user_data);
@@ -153,6 +171,45 @@ void deno_mod_evaluate(Deno* d, void* user_data, deno_mod id);
 void deno_dyn_import_done(Deno* d, void* user_data, deno_dyn_import_id id,
                           deno_mod mod_id, const char* error_str);
 
+// Inspector API
+
+// Called with each message of the V8 inspector for the debugger frontend,
+// which is UTF-8 JSON of the DevTools protocol. The message is only valid for
+// the lifetime of this callback.
+typedef void (*deno_inspector_send_cb)(void* user_data, const char* message,
+                                       size_t len);
+
+// Called when the inspector needs the messages of the frontend: while
+// JavaScript is paused or waiting for a debugger, and after
+// deno_inspector_interrupt(). The embedder should pass each of them to
+// deno_inspector_dispatch(), waiting for one first if `block` is set, and
+// return zero once the frontend is gone.
+typedef int (*deno_inspector_poll_cb)(void* user_data, int block);
+
+// Connects a debugger frontend to the V8 inspector of the isolate. Only one
+// frontend can be connected at a time. user_data is passed to the callbacks.
+void deno_inspector_connect(Deno* d, void* user_data,
+                            deno_inspector_send_cb send_cb,
+                            deno_inspector_poll_cb poll_cb);
+
+// After this the callbacks passed to deno_inspector_connect() are no longer
+// called. Does nothing if no frontend is connected.
+void deno_inspector_disconnect(Deno* d);
+
+// Dispatches a message of the frontend, which is UTF-8 JSON. It may run
+// JavaScript, e.g. to evaluate an expression typed in the DevTools console.
+void deno_inspector_dispatch(Deno* d, void* user_data, const char* message,
+                             size_t len);
+
+// Polls the messages of the frontend until it tells the inspector to run the
+// program (Runtime.runIfWaitingForDebugger), then pauses on the next
+// statement. Used for --inspect-brk.
+void deno_inspector_wait_for_debugger(Deno* d);
+
+// Makes the isolate poll the messages of the frontend even while JavaScript
+// is running. Unlike the other functions, it can be called from any thread.
+void deno_inspector_interrupt(Deno* d);
+
 #ifdef __cplusplus
 }  // extern "C"
 #endif
@@ -0,0 +1,228 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+#include <string.h>
+#include <vector>
+
+#include "v8/include/v8-inspector.h"
+#include "v8/include/v8.h"
+#include "v8/src/base/logging.h"
+
+#include "deno.h"
+#include "internal.h"
+
+using deno::DenoIsolate;
+using v8_inspector::StringBuffer;
+using v8_inspector::StringView;
+
+namespace deno {
+
+// The isolate has a single context, hence a single context group.
+static const int kContextGroupId = 1;
+
+static StringView ToStringView(const char* str) {
+  return StringView(reinterpret_cast<const uint8_t*>(str), strlen(str));
+}
+
+Inspector::Inspector(DenoIsolate* d)
+    : d_(d),
+      user_data_(nullptr),
+      send_cb_(nullptr),
+      poll_cb_(nullptr),
+      dispatch_depth_(0),
+      paused_(false),
+      waiting_for_debugger_(false) {
+  v8::Isolate* isolate = d_->isolate_;
+  v8::Locker locker(isolate);
+  v8::Isolate::Scope isolate_scope(isolate);
+  v8::HandleScope handle_scope(isolate);
+  auto context = d_->context_.Get(isolate);
+  inspector_ = v8_inspector::V8Inspector::create(isolate, this);
+  inspector_->contextCreated(v8_inspector::V8ContextInfo(
+      context, kContextGroupId, ToStringView("deno")));
+}
+
+Inspector::~Inspector() {
+  v8::Locker locker(d_->isolate_);
+  v8::Isolate::Scope isolate_scope(d_->isolate_);
+  session_.reset();
+  inspector_.reset();
+}
+
+void Inspector::Connect(void* user_data, deno_inspector_send_cb send_cb,
+                        deno_inspector_poll_cb poll_cb) {
+  CHECK_NULL(send_cb_);
+  v8::Locker locker(d_->isolate_);
+  v8::Isolate::Scope isolate_scope(d_->isolate_);
+  user_data_ = user_data;
+  send_cb_ = send_cb;
+  poll_cb_ = poll_cb;
+  session_ = inspector_->connect(kContextGroupId, this, StringView());
+}
+
+void Inspector::Disconnect() {
+  user_data_ = nullptr;
+  send_cb_ = nullptr;
+  poll_cb_ = nullptr;
+  paused_ = false;
+  waiting_for_debugger_ = false;
+  MaybeDeleteSession();
+}
+
+void Inspector::MaybeDeleteSession() {
+  if (send_cb_ == nullptr && dispatch_depth_ == 0 && !paused_) {
+    // Deleting the session resumes JavaScript if it was paused.
+    v8::Locker locker(d_->isolate_);
+    v8::Isolate::Scope isolate_scope(d_->isolate_);
+    session_.reset();
+  }
+}
+
+void Inspector::Dispatch(const char* message, size_t len) {
+  if (session_ == nullptr || send_cb_ == nullptr) {
+    return;
+  }
+  v8::Isolate* isolate = d_->isolate_;
+  v8::Locker locker(isolate);
+  v8::Isolate::Scope isolate_scope(isolate);
+  v8::HandleScope handle_scope(isolate);
+  auto context = d_->context_.Get(isolate);
+  v8::Context::Scope context_scope(context);
+
+  // The frontend sends UTF-8, but V8 only takes Latin-1 or UTF-16.
+  auto str = v8::String::NewFromUtf8(isolate, message,
+                                     v8::NewStringType::kNormal,
+                                     static_cast<int>(len))
+                 .ToLocalChecked();
+  std::vector<uint16_t> buffer(str->Length());
+  str->Write(isolate, buffer.data(), 0, str->Length());
+
+  dispatch_depth_++;
+  session_->dispatchProtocolMessage(StringView(buffer.data(), buffer.size()));
+  dispatch_depth_--;
+  MaybeDeleteSession();
+}
+
+void Inspector::WaitForDebugger() {
+  waiting_for_debugger_ = true;
+  while (waiting_for_debugger_ && Poll(true)) {
+  }
+  waiting_for_debugger_ = false;
+  if (session_ != nullptr && send_cb_ != nullptr) {
+    v8::Locker locker(d_->isolate_);
+    v8::Isolate::Scope isolate_scope(d_->isolate_);
+    session_->schedulePauseOnNextStatement(ToStringView("Break on start"),
+                                           StringView());
+  }
+}
+
+bool Inspector::Poll(bool block) {
+  if (poll_cb_ == nullptr) {
+    return false;
+  }
+  if (!poll_cb_(user_data_, block ? 1 : 0)) {
+    Disconnect();
+    return false;
+  }
+  return true;
+}
+
+void Inspector::runMessageLoopOnPause(int context_group_id) {
+  CHECK_EQ(context_group_id, kContextGroupId);
+  if (paused_) {
+    return;
+  }
+  paused_ = true;
+  while (paused_ && Poll(true)) {
+  }
+  paused_ = false;
+  MaybeDeleteSession();
+}
+
+void Inspector::quitMessageLoopOnPause() { paused_ = false; }
+
+void Inspector::runIfWaitingForDebugger(int context_group_id) {
+  CHECK_EQ(context_group_id, kContextGroupId);
+  waiting_for_debugger_ = false;
+}
+
+v8::Local<v8::Context> Inspector::ensureDefaultContextInGroup(
+    int context_group_id) {
+  CHECK_EQ(context_group_id, kContextGroupId);
+  return d_->context_.Get(d_->isolate_);
+}
+
+void Inspector::sendResponse(int call_id,
+                             std::unique_ptr<StringBuffer> message) {
+  Send(message->string());
+}
+
+void Inspector::sendNotification(std::unique_ptr<StringBuffer> message) {
+  Send(message->string());
+}
+
+void Inspector::Send(const StringView& message) {
+  if (send_cb_ == nullptr) {
+    return;
+  }
+  v8::Isolate* isolate = d_->isolate_;
+  v8::HandleScope handle_scope(isolate);
+  int length = static_cast<int>(message.length());
+  v8::Local<v8::String> str =
+      message.is8Bit()
+          ? v8::String::NewFromOneByte(isolate, message.characters8(),
+                                       v8::NewStringType::kNormal, length)
+                .ToLocalChecked()
+          : v8::String::NewFromTwoByte(isolate, message.characters16(),
+                                       v8::NewStringType::kNormal, length)
+                .ToLocalChecked();
+  v8::String::Utf8Value utf8(isolate, str);
+  send_cb_(user_data_, *utf8, utf8.length());
+}
+
+}  // namespace deno
+
+extern "C" {
+
+void deno_inspector_connect(Deno* d_, void* user_data,
+                            deno_inspector_send_cb send_cb,
+                            deno_inspector_poll_cb poll_cb) {
+  auto* d = reinterpret_cast<DenoIsolate*>(d_);
+  if (d->inspector_ == nullptr) {
+    d->inspector_ = new deno::Inspector(d);
+  }
+  d->inspector_->Connect(user_data, send_cb, poll_cb);
+}
+
+void deno_inspector_disconnect(Deno* d_) {
+  auto* d = reinterpret_cast<DenoIsolate*>(d_);
+  if (d->inspector_ != nullptr) {
+    d->inspector_->Disconnect();
+  }
+}
+
+void deno_inspector_dispatch(Deno* d_, void* user_data, const char* message,
+                             size_t len) {
+  auto* d = reinterpret_cast<DenoIsolate*>(d_);
+  CHECK_NOT_NULL(d->inspector_);
+  deno::UserDataScope user_data_scope(d, user_data);
+  d->inspector_->Dispatch(message, len);
+}
+
+void deno_inspector_wait_for_debugger(Deno* d_) {
+  auto* d = reinterpret_cast<DenoIsolate*>(d_);
+  CHECK_NOT_NULL(d->inspector_);
+  d->inspector_->WaitForDebugger();
+}
+
+void deno_inspector_interrupt(Deno* d_) {
+  auto* d = reinterpret_cast<DenoIsolate*>(d_);
+  d->isolate_->RequestInterrupt(
+      [](v8::Isolate* isolate, void*) {
+        DenoIsolate* d = DenoIsolate::FromIsolate(isolate);
+        if (d->inspector_ != nullptr) {
+          d->inspector_->Poll(false);
+        }
+      },
+      nullptr);
+}
+
+}  // extern "C"
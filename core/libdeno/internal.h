@@ -9,6 +9,8 @@
 
 #include "buffer.h"
 #include "deno.h"
+#include "v8/include/v8-inspector.h"
+#include "v8/include/v8-profiler.h"
 #include "v8/include/v8.h"
 #include "v8/src/base/logging.h"
 
@@ -27,6 +29,66 @@ struct ModuleInfo {
   }
 };
 
+class DenoIsolate;
+
+// Memory of the embedder which JavaScript accesses through an ArrayBuffer, see
+// deno_respond_external().
+struct ExternalBuf {
+  void* data;
+  v8::Global<v8::ArrayBuffer> handle;
+  deno_external_free_cb free_cb;
+  void* free_data;
+};
+
+// Connects a debugger frontend, through the callbacks of the embedder, to the
+// V8 inspector of an isolate.
+class Inspector : public v8_inspector::V8InspectorClient,
+                  public v8_inspector::V8Inspector::Channel {
+ public:
+  explicit Inspector(DenoIsolate* d);
+  ~Inspector() override;
+
+  void Connect(void* user_data, deno_inspector_send_cb send_cb,
+               deno_inspector_poll_cb poll_cb);
+  void Disconnect();
+  void Dispatch(const char* message, size_t len);
+  void WaitForDebugger();
+  // Has the embedder dispatch the messages of the frontend. Returns false once
+  // it's gone.
+  bool Poll(bool block);
+
+  // v8_inspector::V8InspectorClient
+  void runMessageLoopOnPause(int context_group_id) override;
+  void quitMessageLoopOnPause() override;
+  void runIfWaitingForDebugger(int context_group_id) override;
+  v8::Local<v8::Context> ensureDefaultContextInGroup(
+      int context_group_id) override;
+
+  // v8_inspector::V8Inspector::Channel
+  void sendResponse(
+      int call_id,
+      std::unique_ptr<v8_inspector::StringBuffer> message) override;
+  void sendNotification(
+      std::unique_ptr<v8_inspector::StringBuffer> message) override;
+  void flushProtocolNotifications() override {}
+
+ private:
+  void Send(const v8_inspector::StringView& message);
+  // The session can't be deleted while it's dispatching a message or paused,
+  // so disconnecting then only stops calling the embedder.
+  void MaybeDeleteSession();
+
+  DenoIsolate* d_;
+  std::unique_ptr<v8_inspector::V8Inspector> inspector_;
+  std::unique_ptr<v8_inspector::V8InspectorSession> session_;
+  void* user_data_;
+  deno_inspector_send_cb send_cb_;
+  deno_inspector_poll_cb poll_cb_;
+  int dispatch_depth_;
+  bool paused_;
+  bool waiting_for_debugger_;
+};
+
 // deno_s = Wrapped Isolate.
 class DenoIsolate {
  public:
@@ -42,6 +104,9 @@ class DenoIsolate {
         resolve_cb_(nullptr),
         next_dyn_import_id_(0),
         dyn_import_cb_(config.dyn_import_cb),
+        next_pending_promise_key_(0),
+        cpu_profiler_(nullptr),
+        inspector_(nullptr),
         has_snapshotted_(false) {
     if (config.load_snapshot.data_ptr) {
       snapshot_.data =
@@ -51,11 +116,20 @@ class DenoIsolate {
   }
 
   ~DenoIsolate() {
+    if (inspector_) {
+      delete inspector_;
+    }
+    while (!external_bufs_.empty()) {
+      FreeExternalBuf(external_bufs_.begin()->second);
+    }
     last_exception_handle_.Reset();
     shared_ab_.Reset();
     if (locker_) {
       delete locker_;
     }
+    if (cpu_profiler_) {
+      cpu_profiler_->Dispose();
+    }
     if (snapshot_creator_) {
       // TODO(ry) V8 has a strange assert which prevents a SnapshotCreator from
       // being deallocated if it hasn't created a snapshot yet.
@@ -76,9 +150,27 @@ class DenoIsolate {
 
   void AddIsolate(v8::Isolate* isolate);
 
+  // Hands the memory of an external buffer back to the embedder.
+  void FreeExternalBuf(ExternalBuf* buf) {
+    external_bufs_.erase(buf->data);
+    buf->handle.Reset();
+    buf->free_cb(buf->free_data);
+    delete buf;
+  }
+
   deno_mod RegisterModule(bool main, const char* name, const char* source);
   void ClearModules();
 
+  // Forgets the rejection of the promise whose identity hash is promise_id,
+  // if it's pending.
+  void ForgetPendingPromise(int promise_id) {
+    auto it = pending_promise_keys_.find(promise_id);
+    if (it != pending_promise_keys_.end()) {
+      pending_promise_map_.erase(it->second);
+      pending_promise_keys_.erase(it);
+    }
+  }
+
   ModuleInfo* GetModuleInfo(deno_mod id) {
     if (id == 0) {
       return nullptr;
@@ -110,13 +202,20 @@ class DenoIsolate {
       dyn_import_map_;
 
   v8::Persistent<v8::Context> context_;
+  // The errors of promises rejected without a handler, keyed by the order
+  // they were rejected in, and those keys by the identity hash of the promise.
   std::map<int, v8::Persistent<v8::Value>> pending_promise_map_;
+  std::map<int, int> pending_promise_keys_;
+  int next_pending_promise_key_;
   std::string last_exception_;
   v8::Persistent<v8::Value> last_exception_handle_;
   v8::Persistent<v8::Function> recv_;
   v8::StartupData snapshot_;
   v8::Persistent<v8::ArrayBuffer> global_import_buf_;
   v8::Persistent<v8::SharedArrayBuffer> shared_ab_;
+  std::map<void*, ExternalBuf*> external_bufs_;
+  v8::CpuProfiler* cpu_profiler_;
+  Inspector* inspector_;
   bool has_snapshotted_;
 };
 
@@ -153,6 +252,11 @@ void Recv(const v8::FunctionCallbackInfo<v8::Value>& args);
 void Send(const v8::FunctionCallbackInfo<v8::Value>& args);
 void EvalContext(const v8::FunctionCallbackInfo<v8::Value>& args);
 void ErrorToJSON(const v8::FunctionCallbackInfo<v8::Value>& args);
+void HeapStats(const v8::FunctionCallbackInfo<v8::Value>& args);
+void HeapSnapshot(const v8::FunctionCallbackInfo<v8::Value>& args);
+void StartCpuProfile(const v8::FunctionCallbackInfo<v8::Value>& args);
+void StopCpuProfile(const v8::FunctionCallbackInfo<v8::Value>& args);
+void Release(const v8::FunctionCallbackInfo<v8::Value>& args);
 void Shared(v8::Local<v8::Name> property,
             const v8::PropertyCallbackInfo<v8::Value>& info);
 void MessageCallback(v8::Local<v8::Message> message, v8::Local<v8::Value> data);
@@ -163,6 +267,11 @@ static intptr_t external_references[] = {
     reinterpret_cast<intptr_t>(Send),
     reinterpret_cast<intptr_t>(EvalContext),
     reinterpret_cast<intptr_t>(ErrorToJSON),
+    reinterpret_cast<intptr_t>(HeapStats),
+    reinterpret_cast<intptr_t>(HeapSnapshot),
+    reinterpret_cast<intptr_t>(StartCpuProfile),
+    reinterpret_cast<intptr_t>(StopCpuProfile),
+    reinterpret_cast<intptr_t>(Release),
     reinterpret_cast<intptr_t>(Shared),
     reinterpret_cast<intptr_t>(MessageCallback),
     reinterpret_cast<intptr_t>(QueueMicrotask),
@@ -182,6 +291,9 @@ v8::StartupData SerializeInternalFields(v8::Local<v8::Object> holder, int index,
                                         void* data);
 
 v8::Local<v8::Uint8Array> ImportBuf(DenoIsolate* d, deno_buf buf);
+v8::Local<v8::Uint8Array> ImportExternalBuf(DenoIsolate* d, deno_buf buf,
+                                            deno_external_free_cb free_cb,
+                                            void* free_data);
 
 bool Execute(v8::Local<v8::Context> context, const char* js_filename,
              const char* js_source);
@@ -119,6 +119,28 @@ TEST(LibDenoTest, RecvReturnBar) {
   deno_delete(d);
 }
 
+TEST(LibDenoTest, RecvReturnExternal) {
+  static uint8_t response[] = {'b', 'a', 'r'};
+  static int freed = 0;
+  auto free_cb = [](void* free_data) {
+    EXPECT_EQ(free_data, response);
+    freed++;
+  };
+  auto recv_cb = [](auto user_data, deno_op_id op_id, auto buf,
+                    auto zero_copy_buf) {
+    auto d = reinterpret_cast<Deno*>(user_data);
+    deno_respond_external(d, {response, sizeof response}, free_cb, response);
+  };
+  Deno* d = deno_new(deno_config{0, snapshot, empty, recv_cb, nullptr});
+  deno_execute(d, d, "a.js", "RecvReturnExternal()");
+  EXPECT_EQ(nullptr, deno_last_exception(d));
+  // Written through the view, without copying.
+  EXPECT_EQ(response[0], 'B');
+  EXPECT_EQ(freed, 1);
+  deno_delete(d);
+  EXPECT_EQ(freed, 1);
+}
+
 TEST(LibDenoTest, DoubleRecvFails) {
   Deno* d = deno_new(deno_config{0, snapshot, empty, nullptr, nullptr});
   deno_execute(d, nullptr, "a.js", "DoubleRecvFails()");
@@ -206,6 +228,27 @@ TEST(LibDenoTest, CheckPromiseErrors) {
   deno_delete(d);
 }
 
+TEST(LibDenoTest, CheckUnhandledPromiseErrors) {
+  Deno* d = deno_new(deno_config{0, snapshot, empty, nullptr, nullptr});
+  deno_execute(d, nullptr, "a.js", "UnhandledPromiseErrors()");
+  EXPECT_EQ(nullptr, deno_last_exception(d));
+  // The rejections are reported one at a time, oldest first.
+  deno_check_promise_errors(d);
+  EXPECT_NE(nullptr, deno_last_exception(d));
+  EXPECT_NE(std::string(deno_last_exception(d)).find("first"),
+            std::string::npos);
+  deno_clear_last_exception(d);
+  EXPECT_EQ(nullptr, deno_last_exception(d));
+  deno_check_promise_errors(d);
+  EXPECT_NE(nullptr, deno_last_exception(d));
+  EXPECT_NE(std::string(deno_last_exception(d)).find("second"),
+            std::string::npos);
+  deno_clear_last_exception(d);
+  deno_check_promise_errors(d);
+  EXPECT_EQ(nullptr, deno_last_exception(d));
+  deno_delete(d);
+}
+
 TEST(LibDenoTest, LastException) {
   Deno* d = deno_new(deno_config{0, empty_snapshot, empty, nullptr, nullptr});
   EXPECT_EQ(deno_last_exception(d), nullptr);
@@ -280,6 +323,45 @@ TEST(LibDenoTest, LibDenoEvalContextInvalidArgument) {
   deno_delete(d);
 }
 
+TEST(LibDenoTest, LibDenoHeapStats) {
+  Deno* d = deno_new(deno_config{0, snapshot, empty, nullptr, nullptr});
+  deno_execute(d, nullptr, "a.js", "LibDenoHeapStats();");
+  EXPECT_EQ(nullptr, deno_last_exception(d));
+  deno_delete(d);
+}
+
+TEST(LibDenoTest, LibDenoHeapSnapshot) {
+  Deno* d = deno_new(deno_config{0, snapshot, empty, nullptr, nullptr});
+  deno_execute(d, nullptr, "a.js", "LibDenoHeapSnapshot();");
+  EXPECT_EQ(nullptr, deno_last_exception(d));
+  deno_delete(d);
+}
+
+TEST(LibDenoTest, LibDenoCpuProfile) {
+  Deno* d = deno_new(deno_config{0, snapshot, empty, nullptr, nullptr});
+  deno_execute(d, nullptr, "a.js", "LibDenoCpuProfile();");
+  EXPECT_EQ(nullptr, deno_last_exception(d));
+  deno_delete(d);
+}
+
+TEST(LibDenoTest, InspectorEvaluate) {
+  static std::string last_message;
+  Deno* d = deno_new(deno_config{0, snapshot, empty, nullptr, nullptr});
+  auto send_cb = [](auto _, const char* message, size_t len) {
+    last_message.assign(message, len);
+  };
+  auto poll_cb = [](auto _, int block) { return 1; };
+  deno_inspector_connect(d, nullptr, send_cb, poll_cb);
+  const char* message =
+      "{\"id\":1,\"method\":\"Runtime.evaluate\","
+      "\"params\":{\"expression\":\"1 + 2\"}}";
+  deno_inspector_dispatch(d, nullptr, message, strlen(message));
+  EXPECT_NE(last_message.find("\"id\":1"), std::string::npos);
+  EXPECT_NE(last_message.find("\"value\":3"), std::string::npos);
+  deno_inspector_disconnect(d);
+  deno_delete(d);
+}
+
 TEST(LibDenoTest, LibDenoPrintInvalidArgument) {
   Deno* d = deno_new(deno_config{0, snapshot, empty, nullptr, nullptr});
   deno_execute(d, nullptr, "a.js", "LibDenoPrintInvalidArgument();");
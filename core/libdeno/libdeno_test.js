@@ -59,6 +59,24 @@ global.RecvReturnBar = () => {
   assert(rstr === "bar");
 };
 
+global.RecvReturnExternal = () => {
+  const r = Deno.core.send(42, new Uint8Array([1]));
+  assert(r instanceof Uint8Array);
+  assert(String.fromCharCode(...r) === "bar");
+  r[0] = "B".charCodeAt(0);
+  const view = r.subarray(1);
+  Deno.core.release(r);
+  assert(r.byteLength === 0);
+  assert(view.byteLength === 0);
+  let threw = false;
+  try {
+    Deno.core.release(r);
+  } catch (e) {
+    threw = true;
+  }
+  assert(threw);
+};
+
 global.DoubleRecvFails = () => {
   // Deno.core.recv is an internal function and should only be called once from the
   // runtime.
@@ -150,6 +168,11 @@ global.CheckPromiseErrors = () => {
   })();
 };
 
+global.UnhandledPromiseErrors = () => {
+  Promise.reject(new Error("first"));
+  Promise.reject(new Error("second"));
+};
+
 global.Shared = () => {
   const ab = Deno.core.shared;
   assert(ab instanceof SharedArrayBuffer);
@@ -221,6 +244,58 @@ global.LibDenoEvalContextInvalidArgument = () => {
   }
 };
 
+global.LibDenoHeapStats = () => {
+  const stats = Deno.core.heapStats();
+  assert(stats.usedHeapSize > 0);
+  assert(stats.totalHeapSize >= stats.usedHeapSize);
+  assert(stats.heapSizeLimit > 0);
+  assert(stats.numberOfNativeContexts >= 1);
+};
+
+global.LibDenoHeapSnapshot = () => {
+  let json = "";
+  let chunks = 0;
+  Deno.core.heapSnapshot(buf => {
+    assert(buf instanceof Uint8Array);
+    for (let i = 0; i < buf.byteLength; i++) {
+      json += String.fromCharCode(buf[i]);
+    }
+    chunks++;
+  });
+  assert(chunks > 0);
+  const snapshot = JSON.parse(json);
+  assert(snapshot.snapshot.node_count > 0);
+  assert(Array.isArray(snapshot.nodes));
+};
+
+global.LibDenoCpuProfile = () => {
+  Deno.core.startCpuProfile();
+  let threw = false;
+  try {
+    Deno.core.startCpuProfile();
+  } catch (e) {
+    threw = true;
+  }
+  assert(threw);
+  let x = 0;
+  for (let i = 0; i < 1e6; i++) {
+    x += Math.sqrt(i);
+  }
+  assert(x > 0);
+  const profile = Deno.core.stopCpuProfile();
+  assert(profile.nodes.length > 0);
+  assert(profile.nodes[0].callFrame.functionName === "(root)");
+  assert(profile.samples.length === profile.timeDeltas.length);
+  assert(profile.endTime >= profile.startTime);
+  threw = false;
+  try {
+    Deno.core.stopCpuProfile();
+  } catch (e) {
+    threw = true;
+  }
+  assert(threw);
+};
+
 global.LibDenoPrintInvalidArgument = () => {
   try {
     Deno.core.print();
@@ -3,6 +3,10 @@ pub use crate::libdeno::OpId;
 use crate::PinnedBuf;
 use futures::Future;
 use std::collections::HashMap;
+use std::ops::Deref;
+use std::ops::DerefMut;
+use std::sync::Arc;
+use std::sync::RwLock;
 
 pub type Buf = Box<[u8]>;
 
@@ -16,6 +20,28 @@ pub type OpResult<E> = Result<Op<E>, E>;
 pub enum Op<E> {
   Sync(Buf),
   Async(OpAsyncFuture<E>),
+  /// A synchronous response which JS accesses without it being copied.
+  External(ExternalBuf),
+}
+
+/// Memory handed to JS as is, see `Op::External`. JS may write to it, so it
+/// must be writable. It's dropped once JS can't access it anymore.
+pub struct ExternalBuf(Box<dyn DerefMut<Target = [u8]> + Send>);
+
+impl ExternalBuf {
+  pub fn new<T>(owner: T) -> Self
+  where
+    T: DerefMut<Target = [u8]> + Send + 'static,
+  {
+    ExternalBuf(Box::new(owner))
+  }
+}
+
+impl Deref for ExternalBuf {
+  type Target = [u8];
+  fn deref(&self) -> &[u8] {
+    &self.0
+  }
 }
 
 pub type CoreError = ();
@@ -23,17 +49,20 @@ pub type CoreError = ();
 pub type CoreOp = Op<CoreError>;
 
 /// Main type describing op
-type OpDispatcher = dyn Fn(&[u8], Option<PinnedBuf>) -> CoreOp;
+pub type OpDispatcher =
+  dyn Fn(&[u8], Option<PinnedBuf>) -> CoreOp + Send + Sync + 'static;
 
+/// The ops of an isolate. Ops may be registered while others run, e.g. by an
+/// op loading a plugin, so it's shared through an `Arc`.
 #[derive(Default)]
 pub struct OpRegistry {
-  dispatchers: Vec<Box<OpDispatcher>>,
-  name_to_id: HashMap<String, OpId>,
+  dispatchers: RwLock<Vec<Arc<Box<OpDispatcher>>>>,
+  name_to_id: RwLock<HashMap<String, OpId>>,
 }
 
 impl OpRegistry {
   pub fn new() -> Self {
-    let mut registry = Self::default();
+    let registry = Self::default();
     let op_id = registry.register("ops", |_, _| {
       // ops is a special op which is handled in call.
       unreachable!()
@@ -42,24 +71,50 @@ impl OpRegistry {
     registry
   }
 
-  pub fn register<F>(&mut self, name: &str, op: F) -> OpId
+  pub fn register<F>(&self, name: &str, op: F) -> OpId
   where
     F: Fn(&[u8], Option<PinnedBuf>) -> CoreOp + Send + Sync + 'static,
   {
-    let op_id = self.dispatchers.len() as u32;
+    let op: Box<OpDispatcher> = Box::new(op);
+    match self.register_all(vec![(name.to_string(), op)]) {
+      Ok(op_ids) => op_ids[0],
+      Err(name) => panic!("Op already registered: {}", name),
+    }
+  }
 
-    let existing = self.name_to_id.insert(name.to_string(), op_id);
-    assert!(
-      existing.is_none(),
-      format!("Op already registered: {}", name)
-    );
+  /// Registers either all of `ops`, returning their ids, or none of them if
+  /// one of their names is taken, returning that name.
+  pub fn register_all(
+    &self,
+    ops: Vec<(String, Box<OpDispatcher>)>,
+  ) -> Result<Vec<OpId>, String> {
+    let mut dispatchers = self.dispatchers.write().unwrap();
+    let mut name_to_id = self.name_to_id.write().unwrap();
+    for (i, (name, _)) in ops.iter().enumerate() {
+      if name_to_id.contains_key(name)
+        || ops[..i].iter().any(|(other, _)| other == name)
+      {
+        return Err(name.clone());
+      }
+    }
+    let mut op_ids = Vec::with_capacity(ops.len());
+    for (name, op) in ops {
+      let op_id = dispatchers.len() as OpId;
+      dispatchers.push(Arc::new(op));
+      name_to_id.insert(name, op_id);
+      op_ids.push(op_id);
+    }
+    Ok(op_ids)
+  }
 
-    self.dispatchers.push(Box::new(op));
-    op_id
+  /// Returns the id of the op registered as `name`, if any.
+  pub fn get_op_id(&self, name: &str) -> Option<OpId> {
+    self.name_to_id.read().unwrap().get(name).cloned()
   }
 
   fn json_map(&self) -> Buf {
-    let op_map_json = serde_json::to_string(&self.name_to_id).unwrap();
+    let name_to_id = self.name_to_id.read().unwrap();
+    let op_map_json = serde_json::to_string(&*name_to_id).unwrap();
     op_map_json.as_bytes().to_owned().into_boxed_slice()
   }
 
@@ -77,8 +132,9 @@ impl OpRegistry {
       return Some(Op::Sync(self.json_map()));
     }
 
-    let d = match self.dispatchers.get(op_id as usize) {
-      Some(handler) => &*handler,
+    // The registry isn't locked while the op runs, which may register others.
+    let d = match self.dispatchers.read().unwrap().get(op_id as usize) {
+      Some(handler) => handler.clone(),
       None => return None,
     };
 
@@ -89,8 +145,7 @@ impl OpRegistry {
 #[test]
 fn test_op_registry() {
   use std::sync::atomic;
-  use std::sync::Arc;
-  let mut op_registry = OpRegistry::new();
+  let op_registry = OpRegistry::new();
 
   let c = Arc::new(atomic::AtomicUsize::new(0));
   let c_ = c.clone();
@@ -104,7 +159,9 @@ fn test_op_registry() {
   let mut expected = HashMap::new();
   expected.insert("ops".to_string(), 0);
   expected.insert("test".to_string(), 1);
-  assert_eq!(op_registry.name_to_id, expected);
+  assert_eq!(*op_registry.name_to_id.read().unwrap(), expected);
+  assert_eq!(op_registry.get_op_id("test"), Some(test_id));
+  assert_eq!(op_registry.get_op_id("missing"), None);
 
   let res = op_registry.call(test_id, &[], None).unwrap();
   if let Op::Sync(buf) = res {
@@ -117,3 +174,31 @@ fn test_op_registry() {
   let res = op_registry.call(100, &[], None);
   assert!(res.is_none());
 }
+
+#[test]
+fn test_op_registry_register_all() {
+  fn op(_: &[u8], _: Option<PinnedBuf>) -> CoreOp {
+    Op::Sync(Box::new([]))
+  }
+  fn ops(
+    names: impl Iterator<Item = String>,
+  ) -> Vec<(String, Box<OpDispatcher>)> {
+    names
+      .map(|name| (name, Box::new(op) as Box<OpDispatcher>))
+      .collect()
+  }
+  let op_registry = OpRegistry::new();
+  op_registry.register("a", op);
+
+  let taken = ops(vec!["b".to_string(), "a".to_string()].into_iter());
+  assert_eq!(op_registry.register_all(taken), Err("a".to_string()));
+  assert_eq!(op_registry.get_op_id("b"), None);
+
+  let twice = ops(vec!["b".to_string(), "b".to_string()].into_iter());
+  assert_eq!(op_registry.register_all(twice), Err("b".to_string()));
+
+  let both = ops(vec!["b".to_string(), "c".to_string()].into_iter());
+  assert_eq!(op_registry.register_all(both), Ok(vec![2, 3]));
+  assert!(op_registry.call(3, &[], None).is_some());
+  assert!(op_registry.call(4, &[], None).is_none());
+}
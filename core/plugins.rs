@@ -0,0 +1,45 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+
+//! The interface of native plugins, which are dynamic libraries loaded with
+//! `Deno.openPlugin()`. A plugin exports an init function, named
+//! `deno_plugin_init`, that registers its ops:
+//!
+//! ```ignore
+//! use deno::*;
+//!
+//! fn init(context: &mut dyn PluginInitContext) {
+//!   context.register_op("hello", Box::new(op_hello));
+//! }
+//! init_fn!(init);
+//!
+//! fn op_hello(control: &[u8], _zero_copy: Option<PinnedBuf>) -> CoreOp {
+//!   Op::Sync(control.to_vec().into_boxed_slice())
+//! }
+//! ```
+//!
+//! Plugins must be built against the same version of this crate as Deno is,
+//! since the interface isn't stable across versions.
+use crate::ops::OpDispatcher;
+
+/// The symbol a plugin exports its init function as.
+pub static PLUGIN_INIT_SYMBOL: &str = "deno_plugin_init";
+
+pub type PluginInitFn = fn(context: &mut dyn PluginInitContext);
+
+/// What a plugin is given to register its ops with. They're only registered
+/// once the init function returns, and only if none of their names is taken,
+/// so JavaScript learns their ids from `Deno.openPlugin()`.
+pub trait PluginInitContext {
+  fn register_op(&mut self, name: &str, op: Box<OpDispatcher>);
+}
+
+/// Exports `$fn`, a `PluginInitFn`, as the init function of a plugin.
+#[macro_export]
+macro_rules! init_fn {
+  ($fn:path) => {
+    #[no_mangle]
+    pub fn deno_plugin_init(context: &mut dyn $crate::PluginInitContext) {
+      $fn(context)
+    }
+  };
+}
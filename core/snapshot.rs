@@ -0,0 +1,48 @@
+// Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
+use crate::any_error::ErrBox;
+use crate::isolate::Isolate;
+use crate::isolate::StartupData;
+use std::fs;
+use std::path::Path;
+
+/// Builds a V8 snapshot out of scripts, which run in the order they're added.
+/// Build scripts of embedders use it to bake their own JavaScript into the
+/// startup snapshot of their binary, which is then loaded with
+/// `StartupData::Snapshot`.
+pub struct SnapshotBuilder {
+  isolate: Isolate,
+}
+
+impl Default for SnapshotBuilder {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl SnapshotBuilder {
+  pub fn new() -> Self {
+    Self {
+      isolate: Isolate::new(StartupData::None, true),
+    }
+  }
+
+  /// Runs a script, whose globals end up in the snapshot.
+  pub fn add_script(
+    mut self,
+    filename: &str,
+    source: &str,
+  ) -> Result<Self, ErrBox> {
+    self.isolate.execute(filename, source)?;
+    Ok(self)
+  }
+
+  /// Writes the snapshot to `path`, returning its size in bytes.
+  pub fn write(self, path: &Path) -> Result<usize, ErrBox> {
+    let snapshot = self.isolate.snapshot()?;
+    let snapshot_slice = unsafe {
+      std::slice::from_raw_parts(snapshot.data_ptr, snapshot.data_len)
+    };
+    fs::write(path, snapshot_slice)?;
+    Ok(snapshot_slice.len())
+  }
+}
@@ -49,6 +49,10 @@ declare interface DenoCore {
 
   shared: SharedArrayBuffer;
 
+  /** Detaches a view of memory which an op handed out without copying, and
+   * frees that memory right away instead of when it's garbage collected. */
+  release(view: ArrayBufferView): void;
+
   /** Evaluate provided code in the current context.
    * It differs from eval(...) in that it does not create a new context.
    * Returns an array: [output, errInfo].
@@ -58,6 +62,50 @@ declare interface DenoCore {
   evalContext(code: string): [any, EvalErrorInfo | null];
 
   errorToJSON: (e: Error) => string;
+
+  /** Returns the statistics of the isolate's heap, in bytes. */
+  heapStats(): {
+    totalHeapSize: number;
+    totalHeapSizeExecutable: number;
+    totalPhysicalSize: number;
+    totalAvailableSize: number;
+    usedHeapSize: number;
+    heapSizeLimit: number;
+    mallocedMemory: number;
+    peakMallocedMemory: number;
+    externalMemory: number;
+    numberOfNativeContexts: number;
+    numberOfDetachedContexts: number;
+  };
+
+  /** Takes a snapshot of the isolate's heap and passes it serialized as JSON
+   * to `onChunk`, a chunk at a time. An exception thrown by `onChunk` stops
+   * the serialization and is rethrown. */
+  heapSnapshot(onChunk: (chunk: Uint8Array) => void): void;
+
+  /** Starts sampling the isolate with the CPU profiler. */
+  startCpuProfile(): void;
+
+  /** Stops the CPU profiler and returns the profile in the .cpuprofile
+   * format. */
+  stopCpuProfile(): {
+    nodes: Array<{
+      id: number;
+      callFrame: {
+        functionName: string;
+        scriptId: string;
+        url: string;
+        lineNumber: number;
+        columnNumber: number;
+      };
+      hitCount: number;
+      children: number[];
+    }>;
+    startTime: number;
+    endTime: number;
+    samples: number[];
+    timeDeltas: number[];
+  };
 }
 
 declare interface DenoInterface {
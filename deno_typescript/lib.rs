@@ -11,6 +11,7 @@ use deno::ErrBox;
 use deno::Isolate;
 use deno::ModuleSpecifier;
 use deno::PinnedBuf;
+pub use deno::SnapshotBuilder;
 use deno::StartupData;
 pub use ops::EmitResult;
 use ops::WrittenFile;
@@ -172,24 +173,51 @@ fn print_source_code(code: &str) {
   }
 }
 
-/// Create a V8 snapshot.
-pub fn mksnapshot_bundle(
-  bundle: &Path,
-  state: Arc<Mutex<TSState>>,
-) -> Result<(), ErrBox> {
-  let mut runtime_isolate = Isolate::new(StartupData::None, true);
-  let source_code_vec = std::fs::read(bundle)?;
-  let source_code = std::str::from_utf8(&source_code_vec)?;
+/// A deno::SnapshotBuilder which has run the AMD runtime, so that bundles made
+/// by compile_bundle() can be added to it.
+pub fn snapshot_builder() -> Result<SnapshotBuilder, ErrBox> {
+  SnapshotBuilder::new().add_script("amd_runtime.js", AMD_RUNTIME_CODE)
+}
 
-  js_check(runtime_isolate.execute("amd_runtime.js", AMD_RUNTIME_CODE));
-  js_check(runtime_isolate.execute(&bundle.to_string_lossy(), &source_code));
+/// Adds TypeScript to a builder made by snapshot_builder().
+pub trait SnapshotBuilderExt: Sized {
+  /// Runs typescript.js, for snapshots which compile TypeScript at runtime.
+  fn add_typescript(self) -> Result<Self, ErrBox>;
+
+  /// Runs a bundle made by compile_bundle(), then requires its main module.
+  fn add_bundle(
+    self,
+    bundle: &Path,
+    state: Arc<Mutex<TSState>>,
+  ) -> Result<Self, ErrBox>;
+}
 
-  let main = state.lock().unwrap().main_module_name();
-  js_check(runtime_isolate.execute("anon", &format!("require('{}')", main)));
+impl SnapshotBuilderExt for SnapshotBuilder {
+  fn add_typescript(self) -> Result<Self, ErrBox> {
+    self.add_script("typescript.js", TYPESCRIPT_CODE)
+  }
 
-  write_snapshot(runtime_isolate, bundle)?;
+  fn add_bundle(
+    self,
+    bundle: &Path,
+    state: Arc<Mutex<TSState>>,
+  ) -> Result<Self, ErrBox> {
+    let source_code = fs::read_to_string(bundle)?;
+    let main = state.lock().unwrap().main_module_name();
+    self
+      .add_script(&bundle.to_string_lossy(), &source_code)?
+      .add_script("anon", &format!("require('{}')", main))
+  }
+}
 
-  Ok(())
+/// Create a V8 snapshot, returning its size in bytes.
+pub fn mksnapshot_bundle(
+  bundle: &Path,
+  state: Arc<Mutex<TSState>>,
+) -> Result<usize, ErrBox> {
+  snapshot_builder()?
+    .add_bundle(bundle, state)?
+    .write(&bundle.with_extension("bin"))
 }
 
 /// Create a V8 snapshot. This differs from mksnapshot_bundle in that is also
@@ -197,38 +225,11 @@ pub fn mksnapshot_bundle(
 pub fn mksnapshot_bundle_ts(
   bundle: &Path,
   state: Arc<Mutex<TSState>>,
-) -> Result<(), ErrBox> {
-  let mut runtime_isolate = Isolate::new(StartupData::None, true);
-  let source_code_vec = std::fs::read(bundle)?;
-  let source_code = std::str::from_utf8(&source_code_vec)?;
-
-  js_check(runtime_isolate.execute("amd_runtime.js", AMD_RUNTIME_CODE));
-  js_check(runtime_isolate.execute("typescript.js", TYPESCRIPT_CODE));
-  js_check(runtime_isolate.execute(&bundle.to_string_lossy(), &source_code));
-
-  let main = state.lock().unwrap().main_module_name();
-  js_check(runtime_isolate.execute("anon", &format!("require('{}')", main)));
-
-  write_snapshot(runtime_isolate, bundle)?;
-
-  Ok(())
-}
-
-fn write_snapshot(
-  runtime_isolate: Isolate,
-  bundle: &Path,
-) -> Result<(), ErrBox> {
-  println!("creating snapshot...");
-  let snapshot = runtime_isolate.snapshot()?;
-  let snapshot_slice =
-    unsafe { std::slice::from_raw_parts(snapshot.data_ptr, snapshot.data_len) };
-  println!("snapshot bytes {}", snapshot_slice.len());
-
-  let snapshot_path = bundle.with_extension("bin");
-
-  fs::write(&snapshot_path, snapshot_slice)?;
-  println!("snapshot path {} ", snapshot_path.display());
-  Ok(())
+) -> Result<usize, ErrBox> {
+  snapshot_builder()?
+    .add_typescript()?
+    .add_bundle(bundle, state)?
+    .write(&bundle.with_extension("bin"))
 }
 
 /// Same as get_asset() but returns NotFound intead of None.
@@ -2,6 +2,8 @@
 # -*- coding: utf-8 -*-
 # Copyright 2018-2019 the Deno authors. All rights reserved. MIT license.
 import os
+import shutil
+import tempfile
 import unittest
 
 import http_server
@@ -110,6 +112,38 @@ class TestWritePermissions(BaseReadWritePermissionsTest,
     test_type = "write"
 
 
+class TestChmodRecursivePermissions(BaseComplexPermissionTest):
+    test_type = "chmodRecursive"
+
+    def setUp(self):
+        super(TestChmodRecursivePermissions, self).setUp()
+        self.tmp_dir = os.path.realpath(tempfile.mkdtemp())
+        self.allowed = os.path.join(self.tmp_dir, "allowed")
+        outside = os.path.join(self.tmp_dir, "outside")
+        os.mkdir(self.allowed)
+        os.mkdir(outside)
+        open(os.path.join(outside, "file.txt"), "w").close()
+        os.symlink(outside, os.path.join(self.allowed, "link"))
+
+    def tearDown(self):
+        shutil.rmtree(self.tmp_dir)
+        super(TestChmodRecursivePermissions, self).tearDown()
+
+    def test_followed_link_inside_allowed_dir(self):
+        code, _stdout, stderr = self._run_deno(
+            ["--allow-write=" + self.tmp_dir], [self.test_type, self.allowed])
+        assert code == 0
+        assert PROMPT_PATTERN not in stderr
+        assert PERMISSION_DENIED_PATTERN not in stderr
+
+    def test_followed_link_outside_allowed_dir(self):
+        code, _stdout, stderr = self._run_deno(
+            ["--allow-write=" + self.allowed], [self.test_type, self.allowed])
+        assert code == 1
+        assert PROMPT_PATTERN not in stderr
+        assert PERMISSION_DENIED_PATTERN in stderr
+
+
 class TestNetFetchPermissions(BaseComplexPermissionTest):
     test_type = "netFetch"
 
@@ -11,6 +11,11 @@ const test: (args: string[]) => void = {
       writeFileSync(file, new Uint8Array(0), { append: true })
     );
   },
+  chmodRecursive(dirs: string[]): void {
+    dirs.forEach(dir =>
+      Deno.chmodSync(dir, 0o755, { recursive: true, followSymlinks: true })
+    );
+  },
   netFetch(hosts: string[]): void {
     hosts.forEach(host => fetch(host));
   },